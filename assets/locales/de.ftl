@@ -0,0 +1,1135 @@
+### GeminiD UI strings, German
+
+## Common
+
+yes = Ja
+no = Nein
+cancel = Abbrechen
+confirm = Bestätigen
+close = Schließen
+delete = Löschen
+remove = Entfernen
+add = ➕ Hinzufügen
+clear = Leeren
+edit = Bearbeiten
+export = Exportieren
+export-cancelled = Export abgebrochen
+no-file-selected = Keine Datei ausgewählt
+import = Importieren
+save = Speichern
+load = Laden
+none = Keine
+you = Du
+new-chat = Neuer Chat
+no-matches = Keine Treffer
+chat-count =
+    { $count ->
+        [one] { $count } Chat
+       *[other] { $count } Chats
+    }
+message-count =
+    { $count ->
+        [one] { $count } Nachricht
+       *[other] { $count } Nachrichten
+    }
+token-count =
+    { $count ->
+        [one] { $count } Token
+       *[other] { $count } Tokens
+    }
+date-long = %A, %-d. %B %Y
+date-short = %-d. %b %Y
+date-day = %a, %d.%m.%Y
+date-time = %d.%m.%Y %H:%M
+time-just-now = gerade eben
+time-minutes-ago =
+    { $count ->
+        [one] vor { $count } Minute
+       *[other] vor { $count } Minuten
+    }
+time-hours-ago =
+    { $count ->
+        [one] vor { $count } Stunde
+       *[other] vor { $count } Stunden
+    }
+time-days-ago =
+    { $count ->
+        [one] vor { $count } Tag
+       *[other] vor { $count } Tagen
+    }
+timestamps = Zeitstempel der Nachrichten
+timestamps-hidden = Ausgeblendet
+timestamps-relative = Relativ
+timestamps-absolute = Datum und Uhrzeit
+timestamps-utc = Zeiten in UTC anzeigen
+timestamps-utc-help = UTC statt der Zeitzone des Systems für Nachrichtenzeiten verwenden
+
+## Settings
+
+settings-reset-title = Einstellungen zurücksetzen
+settings-reset-confirm = Möchtest du die globalen Einstellungen wirklich zurücksetzen? Das kann nicht rückgängig gemacht werden!
+settings-authentication = Anmeldung
+settings-auth-method = Methode
+settings-api-key = API-Schlüssel
+settings-api-key-hint = Gib deinen Google AI Studio API-Schlüssel ein
+settings-login-google = 🔑 Mit Google anmelden
+settings-logged-in = ✅ Angemeldet
+settings-logout = 🚪 Abmelden
+settings-logged-out = Abgemeldet und Cache geleert.
+settings-login-success = Google-Anmeldung erfolgreich!
+settings-project = Projekt
+settings-select-project = Projekt auswählen
+settings-no-projects = Keine Projekte gefunden oder sie werden geladen...
+settings-model = Modell
+settings-default-model = Standardmodell für neue Chats
+settings-inherit-model = Von Chats übernehmen
+settings-inherit-model-help = Modelländerungen aus Chats übernehmen
+settings-behavior = Verhalten
+settings-streaming = Antwort streamen
+settings-streaming-help = Die Antwort erscheint während sie erzeugt wird. Ausgeschaltet wird auf die vollständige Antwort gewartet
+settings-persist-thoughts = Gedanken im Kontext behalten
+settings-persist-thoughts-help = Die Gedanken des Modells werden für folgende Anfragen an den Kontext angehängt. Achtung: Der Tokenverbrauch steigt dadurch schnell
+settings-file-api = Dateien hochladen (File API)
+settings-file-api-help = Dateien werden auf die Server von Google hochgeladen (File API), statt als base64 in der Anfrage gesendet zu werden. Hochgeladene Dateien sind temporär und nur für dich zugänglich.
+settings-voice-mode = Sprachmodus
+settings-voice-mode-help = Jede Antwort vorlesen, sobald sie fertig ist
+settings-notifications = Desktop-Benachrichtigungen
+settings-notifications-help = Eine Benachrichtigung anzeigen, wenn eine Antwort fertig ist, während das Fenster im Hintergrund oder minimiert ist
+settings-close-to-tray = In den Infobereich schließen
+settings-close-to-tray-help = Beim Schließen verschwindet das Fenster in den Infobereich und die App läuft weiter. Beenden über das Menü im Infobereich
+settings-snippets = Textbausteine
+settings-appearance = Darstellung
+settings-language = Sprache
+settings-language-system = System ({ $language })
+settings-rtl-layout = Rechts-nach-links-Layout
+settings-rtl-layout-help = Verschiebt die Seitenleiste nach rechts und die Schaltflächen des Eingabefelds hinter den Text, für Arabisch, Hebräisch, Persisch und andere linksläufige Sprachen
+settings-reading = Lesen
+settings-fonts = Schriften
+settings-spellcheck = Rechtschreibprüfung
+settings-retrieval = Wissenssuche
+settings-safety = Sicherheitsfilter
+settings-injection = Prompt-Injection
+settings-verify = Antwortprüfung
+settings-cache = Kontext-Caching
+settings-retry = Wiederholungen
+settings-summarize = Verlaufszusammenfassung
+settings-plugins = Plugins
+settings-scripting = Skripte
+settings-shortcuts = Tastenkürzel
+settings-misc = Sonstiges
+settings-proxy = Proxy verwenden
+settings-proxy-help = Anfragen an die Gemini API über den Proxy senden
+settings-editor-command = Editor-Befehl
+settings-editor-command-help = Öffnet zitierte Dateien an der zitierten Zeile, {"{"}path{"}"} und {"{"}line{"}"} werden ersetzt. Leer lassen, um die Standard-App zu verwenden
+settings-snow = Lass es schneien!
+settings-ui-scale = UI-Skalierung
+settings-ui-scale-help = Die Gesamtgröße der Oberfläche anpassen
+settings-reset-label = Globale Einstellungen auf Standard zurücksetzen
+settings-reset = Zurücksetzen
+settings-save-load-label = Einstellungen als JSON speichern und laden
+
+## Main window
+
+toggle-sidebar = Seitenleiste ein-/ausblenden
+back = Zurück
+forward = Vorwärts
+reopen-closed-chat = Geschlossenen Chat wiederherstellen
+no-closed-chats = Keine kürzlich geschlossenen Chats
+timeline = Zeitleiste
+settings = Einstellungen
+token-count-help = Geschätzte Tokenanzahl im Kontext
+token-count-limit = { $count } Tokens mit der Nachricht, die gerade geschrieben wird, { $percent } % der { $limit }, die das Modell annimmt
+exit-focus-mode = Fokusmodus verlassen
+dropping-files = Dateien werden abgelegt:
+notification-ready = Antwort fertig
+notification-failed = Erzeugung fehlgeschlagen
+notification-open-chat = Chat öffnen
+voice-mode-on = Sprachmodus an
+voice-mode-off = Sprachmodus aus
+streaming-enabled = Streaming aktiviert
+streaming-disabled = Streaming deaktiviert
+chat-detached = Dieser Chat ist in einem eigenen Fenster geöffnet.
+chat-bring-back = Zurückholen
+split-close = Geteilte Ansicht schließen
+split-swap = Mit der Hauptansicht tauschen
+remove-chat-title = Chat entfernen
+remove-chat-confirm =
+    Möchtest du diesen Chat wirklich entfernen? Du kannst ihn später wiederherstellen{ $shortcut }.
+    Halte Umschalt gedrückt, um diese Warnung zu überspringen.
+remove-chat-named = Chat „{ $name }“ entfernen
+remove-chat = Chat entfernen
+bulk-delete-title = Chats löschen
+bulk-delete-summary = Damit werden { $chats } mit { $messages } gelöscht.
+bulk-archive-title = Chats archivieren
+bulk-archive-summary = Damit werden { $chats } mit { $messages } archiviert.
+bulk-unarchive-title = Chats wiederherstellen
+bulk-unarchive-summary = Damit werden { $chats } mit { $messages } aus dem Archiv geholt.
+bulk-tag-title = Chats taggen
+bulk-tag-summary = Damit wird der Tag „{ $tag }“ zu { $chats } mit { $messages } hinzugefügt.
+bulk-untag-summary = Damit wird der Tag „{ $tag }“ von { $chats } mit { $messages } entfernt.
+bulk-move-title = Chats verschieben
+bulk-move-summary = Damit werden { $chats } mit { $messages } nach „{ $folder }“ verschoben.
+bulk-export-title = Chats exportieren
+bulk-export-summary = Damit werden { $chats } mit { $messages } exportiert.
+bulk-tag-hint = Tag hinzufügen oder entfernen
+selected-count = { $count } ausgewählt
+archive = Archivieren
+archive-button = 📦 Archivieren
+unarchive = Aus dem Archiv holen
+no-tags = Noch keine Tags
+tag-delete = Tag löschen
+tag-new-hint = Neues Tag
+top-level = Oberste Ebene
+move-to-folder = In Ordner verschieben
+clear-selection = Auswahl aufheben
+chat-edit-persona = Persona
+chat-edit-tags = Tags
+chat-edit-knowledge = Wissen
+chat-edit-knowledge-help = Passende Auszüge aus der Sammlung werden jedem Prompt hinzugefügt, die Antwort führt sie als Quellen auf
+chat-edit-remember = Diesen Chat merken in:
+chat-edit-remember-button = 🧠 Merken
+chat-edit-remember-help = Sucht die dauerhaften Fakten und Entscheidungen aus diesem Chat heraus und fügt sie der Sammlung hinzu, damit spätere Chats sie kennen. Erneutes Merken ersetzt sie
+chat-edit-manage-tags = Tags verwalten
+chat-edit-model = Modell
+chat-edit-export-label = Chatverlauf in eine Datei exportieren
+chat-edit-export-format = Exportformat
+save-as = Speichern unter…
+export-with-plugin = Speichern als { $name }…
+tab-chats = Chats
+tab-prompts = Prompts
+tab-schedules = Zeitpläne
+tab-knowledge = Wissen
+tab-videos = Videos
+no-recent-messages = Keine Nachrichten
+new-chat-button = ➕ Neuer Chat
+new-chat-hint = Einen neuen Chat beginnen
+new-folder-button = 📁 Neuer Ordner
+new-folder-hint = Einen neuen Ordner anlegen, Chats per Drag & Drop hineinziehen
+folder-remove-hint = Ordner entfernen, sein Inhalt rückt eine Ebene nach oben
+folder-edit-hint = Ordner bearbeiten
+folder-new-chat-hint = Neuer Chat in diesem Ordner
+folder-new-name = Neuer Ordner
+folder-name-hint = Ordnername
+folder-template = Standardvorlage
+folder-template-help = Neue Chats in diesem Ordner und seinen Unterordnern starten mit diesen Einstellungen.
+folder-template-enable = Vorlage verwenden
+project-title = Projekt
+project-help = Chats in diesem Ordner und seinen Unterordnern erhalten dies bei jeder Anfrage.
+project-enable = Zum Projekt machen
+project-instructions-hint = Anweisungen für jeden Chat im Projekt
+project-knowledge = Wissen
+drop-to-top-level = Hier ablegen, um auf die oberste Ebene zu verschieben
+archived-header = 📦 Archiviert ({ $count })
+remove-saved-search = Gespeicherte Suche entfernen
+no-matching-chats = Keine passenden Chats
+open-in-split-view = ◫ In geteilter Ansicht öffnen
+open-in-new-window = 🗗 In neuem Fenster öffnen
+
+## Command palette
+
+palette-hint = Befehl, Chat oder Modell eingeben…
+palette-nothing = Keine Treffer
+palette-enable-streaming = Streaming aktivieren
+palette-disable-streaming = Streaming deaktivieren
+palette-switch-model = Modell wechseln: { $model }
+palette-use-prompt = Als Systemprompt verwenden: { $prompt }
+palette-prompt-applied = Systemprompt auf { $prompt } gesetzt
+palette-setting = Einstellung
+palette-model = Modell
+palette-prompt = Prompt
+palette-chat = Chat
+
+## Chat
+
+generation-time = Erzeugungsdauer
+token-usage = Ein: { $input } / Aus: { $output } / Gesamt: { $total }
+retry-generation = 🔄 Erneut erzeugen
+retry-generation-hint = Versuche erneut, eine Antwort zu erzeugen. Prüfe den API-Schlüssel und die Verbindung.
+stream-degraded = ⚠ Stream beschädigt, der Inhalt ist womöglich unvollständig
+stream-degraded-hint = Teile der gestreamten Antwort waren nicht lesbar und wurden ausgelassen, oder der Stream brach ab, oft wegen eines Proxys. Der Rest der Antwort wird angezeigt
+answer-truncated = ✂ Die Antwort endete am Limit der Ausgabe-Tokens
+answer-interrupted = ⏹ Vor dem Ende der Antwort gestoppt
+answer-interrupted-hint = Du hast die Generierung gestoppt, der bis dahin empfangene Text bleibt erhalten
+continue-generating = ⏩ Weiter generieren
+continue-generating-hint = Das Modell dort weitermachen lassen, wo es aufgehört hat, der Rest wird an diese Antwort angehängt
+prepend-hint = Text vor der Antwort…
+regenerate-button = 🔄 Neu erzeugen
+regenerate-hint = Die Antwort neu erzeugen, das Modell setzt nach dem vorangestellten Text fort
+regenerate-with = Neu erzeugen mit…
+regenerate-with-hint = Diese Antwort mit einem anderen Modell neu erzeugen, der Chat behält sein Modell
+edit-message-button = ✏ Bearbeiten
+edit-message-hint = Die Nachricht im Kontext ändern, ohne sie neu zu erzeugen
+cancel-button = ❌ Abbrechen
+copied = Kopiert!
+copy-message = Nachricht kopieren
+read-aloud-hint = Nachricht vorlesen. Rechtsklick zum Wiederholen
+regenerate = Neu erzeugen
+pick-files = Dateien auswählen
+attach-url = Über einen Link anhängen
+attach-url-hint = https://example.com/paper.pdf
+attach-url-download = Herunterladen und anhängen
+attach-url-cancel = Abbrechen
+attach-url-downloading = Wird heruntergeladen…
+attach-url-failed = Datei konnte nicht heruntergeladen werden: { $error }
+bundle-attach-hint = Die Textdateien eines Ordners anhängen
+bundle-title = Ordner anhängen
+bundle-change = Ändern…
+bundle-include = Einschließen
+bundle-exclude = Ausschließen
+bundle-globs-help = Glob-Muster relativ zum Ordner, eines pro Zeile, z. B. src/**/*.rs. Ohne einschließende Muster wird jede Datei genommen.
+bundle-max-size = Max. Dateigröße
+bundle-gitignore = Überspringen, was .gitignore auslässt
+bundle-scanning = Ordner wird gelesen und Tokens werden gezählt…
+bundle-rescan = 🔄 Filter anwenden
+bundle-summary =
+    { $files ->
+        [one] { $files } Datei, { $size }
+       *[other] { $files } Dateien, { $size }
+    }
+bundle-tokens = ≈ { $tokens } Tokens mit dem Modell dieses Chats
+bundle-tokens-unknown = Die Tokens konnten nicht gezählt werden.
+bundle-skipped = Übersprungen: { $large } zu groß, { $binary } kein Text
+bundle-files = Dateien im Bündel
+bundle-attach = 📎 Als eine Textdatei anhängen
+bundle-attach-stale = Zuerst den geänderten Filter anwenden
+archive-title = { $name } entpacken
+archive-help = Wähle die Dateien, die statt des Archivs angehängt werden. Dateien, die das Modell nicht lesen kann, sind ausgegraut.
+archive-reading = Archiv wird gelesen…
+archive-select-supported = Lesbare Dateien wählen
+archive-select-none = Keine wählen
+archive-unsupported = Das Modell kann diesen Dateityp nicht lesen
+archive-extracting = Wird entpackt…
+archive-extract =
+    { $count ->
+        [one] { $count } Datei entpacken und anhängen
+       *[other] { $count } Dateien entpacken und anhängen
+    }
+archive-keep = Archiv behalten
+upload-progress = { $sent } / { $total } MB
+upload-uploading = Wird hochgeladen…
+upload-failed = Fehlgeschlagen
+attachment-missing = (DATEI NICHT GEFUNDEN)
+attachment-info-missing = { $name }, Datei nicht gefunden
+attachment-info-uploading = { $name }, wird hochgeladen
+attachment-info-uploading-percent = { $name }, { $percent } % hochgeladen
+attachment-info-failed = { $name }, fehlgeschlagen: { $error }
+attachment-info-expired = { $name }, Upload abgelaufen
+attachment-remove-hint = Mit Entf entfernen
+upload-cancel = Upload abbrechen, die Nachricht wird ohne diese Datei gesendet
+upload-cancelling = Wird abgebrochen…
+upload-expires = läuft in { $hours } h { $minutes } min ab
+upload-expires-hint = Die File API löscht Uploads nach 48 Stunden
+upload-expired = Upload abgelaufen
+upload-reupload = Erneut hochladen
+upload-reupload-hint = Die Datei erneut hochladen, damit die nächsten Nachrichten sie weiter verwenden können
+upload-reupload-unavailable = Aktiviere Uploads über die File API mit einem API-Schlüssel, um Dateien hochzuladen
+screenshot-capture = Bildschirmbereich aufnehmen
+screenshot-help = Bereich mit der Maus aufziehen · Enter nimmt den ganzen Bildschirm · Esc bricht ab
+screenshot-failed = Bildschirmaufnahme fehlgeschlagen: { $error }
+record-start = Mit dem Mikrofon aufnehmen
+record-stop = Aufnahme beenden und anhängen
+record-discard = Aufnahme verwerfen
+record-failed = Aufnahme fehlgeschlagen: { $error }
+youtube-detected =
+    { $count ->
+        [one] ▶ Die Nachricht enthält einen YouTube-Link, das Modell kann sich das Video selbst ansehen.
+       *[other] ▶ Die Nachricht enthält { $count } YouTube-Links, das Modell kann sich die Videos selbst ansehen.
+    }
+youtube-attach = Als Video anhängen
+youtube-attach-hint = Sendet das Video statt des Links. Mit einem Rechtsklick auf den Anhang lässt sich nur ein Teil davon senden.
+youtube-keep-text = Als Text lassen
+youtube-clip = Teil des Videos
+youtube-clip-start = Anfang
+youtube-clip-end = Ende
+youtube-clip-invalid = Das Ende muss nach dem Anfang liegen.
+improve-prompt = Prompt verbessern
+improve-prompt-failed = Der Prompt konnte nicht verbessert werden: { $error }
+preview-request = Anfrage ansehen
+param-override-hint = Temperatur oder Denkbudget für die nächste Nachricht überschreiben
+param-override-active = Die nächste Nachricht wird mit überschriebenen Einstellungen gesendet
+param-override-title = Nur für die nächste Nachricht:
+param-override-help = Gilt nur für die nächste Anfrage, die Inferenz-Einstellungen des Chats bleiben unverändert
+param-override-temperature = Temperatur
+param-override-thinking = Denkbudget
+param-override-clear = Überschreibung verwerfen
+presets-pick = Vorlagen…
+presets-empty = Noch keine Vorlagen gespeichert
+presets-name-hint = Name der Vorlage
+presets-save = 💾 Vorlage speichern
+presets-save-hint = Den Systemprompt unter diesem Namen speichern
+presets-overwrite = Ein gespeicherter Prompt mit diesem Namen existiert, Speichern fügt ihm eine neue Version hinzu
+presets-manage = Vorlagen verwalten
+presets-remove = Vorlage entfernen
+presets-export = Vorlagen exportieren…
+inference-settings = Inferenz-Einstellungen
+inference-enable = Aktivieren
+inference-reset = Zurücksetzen
+inference-reset-hint = Auf den Standardwert zurücksetzen
+inference-reset-all = Einstellungen zurücksetzen
+system-prompt-title = System-Prompt
+system-prompt-help = Mit einem System-Prompt lässt sich das Verhalten des Modells festlegen.
+system-prompt-enable = Eigenen System-Prompt verwenden
+system-prompt-hint = Ein System-Prompt für das Modell, z. B. „Du bist ein hilfreicher Assistent, der sich auf Rust-Code spezialisiert hat.“
+few-shot-title = Few-Shot-Beispiele
+few-shot-help = Beispielpaare aus Eingabe und Ausgabe werden vor dem Gespräch gesendet, als hätte das Modell sie schon beantwortet.
+few-shot-input = Eingabe
+few-shot-output = Ausgabe
+few-shot-input-hint = Nachricht des Nutzers
+few-shot-output-hint = Antwort des Modells
+few-shot-remove = Beispiel entfernen
+thinking-title = Denken
+thinking-help = Aktiviert das eingebaute Denken der Gemini-2.5-Modelle für besseres Schlussfolgern.
+thinking-include = Zusammenfassungen der Gedanken einbeziehen
+thinking-budget = Denkbudget festlegen
+thinking-budget-help = Token-Budget fürs Denken. -1 für dynamisch, 0 zum Abschalten.
+temperature-title = Temperatur
+temperature-help = Steuert die Zufälligkeit der Ausgabe. Höhere Werte (z. B. 1.0) ergeben kreativere Antworten, niedrigere (z. B. 0.2) machen die Ausgabe vorhersehbarer.
+max-tokens-title = Max. Ausgabe-Tokens
+max-tokens-help = Höchstzahl der Tokens, die in der Antwort erzeugt werden.
+top-k-title = Top-K
+top-k-help = Ändert, wie das Modell Tokens für die Ausgabe auswählt. Ein kleinerer Wert beschränkt die Auswahl auf weniger, wahrscheinlichere Tokens.
+top-p-title = Top-P
+top-p-help = Ändert, wie das Modell Tokens für die Ausgabe auswählt, anhand einer kumulativen Wahrscheinlichkeitsverteilung. Verwende entweder Top-K oder Top-P, nicht beides.
+stop-title = Stoppsequenz
+stop-help = Bis zu 5 Zeichenfolgen, bei denen die Generierung anhält.
+preview-title = Anfragevorschau
+preview-loading = Anfrage wird zusammengestellt…
+preview-model = Modell: { $model }
+preview-copy = 🗐 Als JSON kopieren
+preview-no-count = Für die Tokenzählung ist ein Gemini-API-Schlüssel nötig
+preview-citations =
+    { $count ->
+        [one] { $count } Wissensauszug in der Systemanweisung
+       *[other] { $count } Wissensauszüge in der Systemanweisung
+    }
+preview-thoughts-kept = Gedanken werden als Teil des Verlaufs mitgeschickt
+preview-thoughts-dropped = Gedanken werden aus dem Verlauf weggelassen
+preview-system = Systemanweisung
+preview-examples = Few-Shot-Beispiele
+preview-history = Verlauf
+preview-prompt = Neue Nachricht
+preview-role-user = Nutzer
+preview-role-model = Modell
+preview-inline-file = 📎 { $mime }, { $size } eingebettet
+preview-uploaded-file = 🔗 { $mime }, hochgeladen als { $uri }
+preview-empty = Keine
+preview-config = Generierungseinstellungen
+preview-safety = Sicherheitseinstellungen
+preview-tools = Werkzeuge:
+safety-help = Wie wahrscheinlich schädlich eine Antwort sein darf, bevor sie blockiert wird, je Kategorie. Manche Inhalte blockiert Google unabhängig von diesen Einstellungen immer
+safety-override = Die app-weiten Sicherheitsfilter überschreiben
+safety-override-help = Anfragen mit dieser Modellkonfiguration verwenden die Sicherheitsfilter aus den Einstellungen
+grounding-title = Google-Suche
+grounding-help = Lässt das Modell im Web nachschlagen und nennt die verwendeten Seiten unter der Antwort.
+grounding-enabled = Antworten auf die Google-Suche stützen
+grounding-sources =
+    { $count ->
+        [one] { $count } Webquelle
+       *[other] { $count } Webquellen
+    }
+grounding-queries = Gesucht nach: { $queries }
+url-context-title = URL-Kontext
+url-context-help = Links in deiner Nachricht werden von Google abgerufen und vom Modell gelesen, statt als reiner Text gesendet zu werden. Die gelesenen Seiten stehen unter der Antwort.
+url-context-enabled = Verlinkte Seiten lesen
+url-context-pages =
+    { $count ->
+        [one] { $count } Seite gelesen
+       *[other] { $count } Seiten gelesen
+    }
+structured-output = Strukturierte Ausgabe
+structured-help = Lässt das Modell in JSON antworten, nach einem Schema, wenn du eins angibst. JSON-Antworten werden als aufklappbarer Baum angezeigt.
+structured-enabled = In JSON antworten
+structured-schema-hint = JSON-Schema der Antwort, z. B. {"{"} "type": "object", "properties": {"{"} … {"}"} {"}"}
+structured-schema-valid = ✔ Die Antworten folgen diesem Schema
+structured-schema-empty = Ohne Schema ist jedes JSON recht
+structured-invalid-schema = Das Schema ist kein gültiges JSON: { $error }
+structured-schema-not-object = Das Schema muss ein JSON-Objekt sein
+structured-title = {"{ }"} JSON
+structured-copy = 📋 JSON kopieren
+structured-copy-path = Pfad kopieren
+structured-copy-value = Wert kopieren
+structured-valid = ✔ Entspricht dem Schema
+structured-invalid =
+    { $count ->
+        [one] ✖ { $count } Stelle entspricht nicht dem Schema
+       *[other] ✖ { $count } Stellen entsprechen nicht dem Schema
+    }
+structured-issues = Schemaverstöße
+structured-not-json = ✖ Die Antwort ist kein gültiges JSON: { $error }
+structured-issue-type = { $expected } erwartet, { $actual } erhalten
+structured-issue-required = die Pflichteigenschaft „{ $name }“ fehlt
+structured-issue-unexpected = das Schema erlaubt diese Eigenschaft nicht
+structured-issue-enum = nicht eines von { $allowed }
+structured-issue-minimum = kleiner als das Minimum von { $limit }
+structured-issue-maximum = größer als das Maximum von { $limit }
+structured-issue-min-items = weniger als { $count } Elemente
+structured-issue-max-items = mehr als { $count } Elemente
+structured-issue-any-of = passt zu keiner der erlaubten Formen
+response-modalities-title = Antwortmodalitäten
+response-modalities-help = Womit das Modell antwortet. Bilder brauchen ein Modell, das zeichnen kann, Audio eines, das sprechen kann, andere Modelle lehnen die Anfrage ab.
+response-modalities-text = Text
+response-modalities-image = Bild
+response-modalities-audio = Audio
+response-modalities-default = Nichts ausgewählt, das Modell antwortet mit Text
+media-resolution-title = Medienauflösung
+media-resolution-help = Wie viele Tokens jedes Bild, jeder Videoframe und jede PDF-Seite bekommt. Niedrig macht lange Videos und große Dokumente günstiger, hoch hält kleine Schrift und feine Details lesbar. Per Rechtsklick auf einen Anhang lässt sie sich für diese Datei ändern.
+media-resolution-attachment = Medienauflösung dieser Datei
+media-resolution-auto = Standard des Modells
+media-resolution-low = Niedrig
+media-resolution-medium = Mittel
+media-resolution-high = Hoch
+image-invalid = ⚠ Das Modell hat ein Bild gesendet, das nicht angezeigt werden kann
+image-hint = Rechtsklick zum Speichern oder Kopieren
+image-save = 💾 Bild speichern…
+image-copy = 📋 Bild kopieren
+image-edit = 🖌 Bild bearbeiten…
+audio-title = Gesprochene Antworten
+audio-help = Lässt die Sprachmodelle, etwa die TTS-Vorschaumodelle, ihre Antwort vorlesen. Andere Modelle lehnen die Anfrage ab.
+audio-enabled = Sprache anfordern
+audio-voice = Stimme
+audio-language = Sprache
+audio-language-hint = auto
+audio-invalid = ⚠ Das Modell hat Audio gesendet, das nicht abgespielt werden kann
+audio-play = Abspielen
+audio-play-external = In der Standard-App abspielen
+audio-stop = Stoppen
+audio-save = 💾 Speichern…
+audio-save-hint = Speichert die Sprachausgabe als WAV-Datei
+video-new = Neues Video
+video-none = Noch keine Videos
+video-remove = Video entfernen
+video-title = Neues Video
+video-help = Veo erstellt aus dem Prompt einen kurzen Clip, optional ausgehend von einem Bild. Das dauert meist einige Minuten.
+video-prompt = Beschreibe das Video…
+video-negative-prompt = Was vermieden werden soll (optional)
+video-model = Modell
+video-aspect-ratio = Seitenverhältnis
+video-image = Startbild
+video-image-none = Keins
+video-pick = Auswählen…
+video-generate = 🎬 Erstellen
+video-needs-key = Die Videoerstellung braucht einen API-Schlüssel
+video-submitting = Wird gesendet…
+video-generating = Wird erstellt…
+video-downloading = Wird heruntergeladen…
+video-done = Fertig
+video-failed = Fehlgeschlagen: { $error }
+video-invalid = Die API hat kein Video geliefert
+video-avoiding = Vermeiden: { $text }
+video-open = ▶ Im Player öffnen
+video-save = 💾 Speichern unter…
+penalty-presence = Präsenzstrafe
+penalty-presence-help = Bestraft Tokens, die schon in der Antwort vorkamen, egal wie oft. Positive Werte lenken das Modell zu neuen Themen, negative lassen es beim selben bleiben.
+penalty-frequency = Häufigkeitsstrafe
+penalty-frequency-help = Bestraft Tokens danach, wie oft sie schon in der Antwort vorkamen. Positive Werte machen Wiederholungen unwahrscheinlicher, negative wahrscheinlicher.
+candidates-title = Kandidaten
+candidates-help = Wie viele Antworten das Modell pro Anfrage schreibt. Die erste bleibt im Gespräch, die anderen erscheinen als Tabs an der Nachricht und können ihren Platz einnehmen.
+candidates-current = Antwort 1
+candidates-tab = Antwort { $number }
+candidates-promote = ⬆ Mit dieser Antwort weitermachen
+candidates-promote-hint = Setzt diese Antwort ins Gespräch, die aktuelle wird zur Alternative
+functions-title = Werkzeuge
+functions-help = Deklariere Funktionen, deren Aufruf das Modell anfordern kann. Tut es das, erscheint der Aufruf im Chat und du gibst ein, was die Funktion zurückgegeben hat.
+functions-enabled = Mit jeder Anfrage senden
+functions-unnamed = (ohne Namen)
+functions-add = ➕ Funktion hinzufügen
+functions-remove = Funktion entfernen
+functions-none-selected = Wähle eine Funktion aus oder füge eine hinzu
+functions-name = Name
+functions-description = Beschreibung
+functions-description-hint = Was die Funktion tut und wann sie aufgerufen werden soll
+functions-parameters = Parameter (JSON-Schema)
+functions-valid = ✔ Gültige Deklaration
+functions-invalid-name = Der Name muss mit einem Buchstaben oder Unterstrich beginnen und darf Buchstaben, Ziffern, Unterstriche, Punkte und Bindestriche enthalten, höchstens 64 Zeichen
+functions-invalid-schema = Die Parameter sind kein gültiges JSON: { $error }
+functions-schema-not-object = Das Parameterschema muss vom Typ "object" sein
+functions-answered = beantwortet
+functions-response-hint = Was die Funktion zurückgegeben hat, JSON oder einfacher Text
+functions-send = Antworten senden
+functions-send-disabled = Beantworte zuerst jeden Aufruf
+functions-run-commands = Lokale Befehle ausführen
+functions-run-commands-help = Lässt einen Shell-Befehl auf diesem Computer eine Funktion beantworten. Jeder Lauf muss bestätigt werden, die Argumente kommen als JSON über stdin und in GEMINID_ARGS, und was der Befehl ausgibt, geht an das Modell zurück.
+functions-command = Befehl
+functions-command-hint = z. B. python3 ~/tools/weather.py
+functions-command-disabled = Schalte zuerst lokale Befehle ein
+functions-command-timeout = Der Befehl wurde nicht innerhalb von { $seconds } Sekunden fertig
+functions-command-failed = Der Befehl ist fehlgeschlagen, der Fehler steht im Antwortfeld
+functions-run = ▶ Befehl ausführen
+functions-run-plugin = Wird vom Plugin { $plugin } beantwortet
+functions-running = Befehl läuft…
+functions-confirm-title = Lokalen Befehl ausführen?
+functions-confirm = Das Modell hat { $name } aufgerufen, das auf deinem Computer Folgendes ausführt:
+functions-confirm-run = Ausführen
+cache-invalid = Die API hat einen Cache ohne Namen zurückgegeben
+cache-failed = Der Chat konnte nicht gecacht werden: { $error }
+cache-creating = Der Anfang des Chats wird gecacht…
+cache-stale = Der Anfang des Chats hat sich seit dem Cachen geändert, der Cache wird nicht genutzt
+cache-expired = Der Kontext-Cache ist abgelaufen
+cache-recreate = Erneut cachen
+cache-drop = Cache verwerfen
+cache-active = Die ersten { $count } Nachrichten sind bis { $time } gecacht
+cache-offer-banner = Dieser Chat hat einen langen Kontext. Cachen, um bei jeder Anfrage weniger zu zahlen?
+cache-create = Kontext cachen
+cache-dismiss = Nicht jetzt
+cache-offer = Cachen langer Chats anbieten
+cache-offer-help = Zeigt ein Banner, wenn ein Chat etwa 32.000 Tokens überschreitet. Gecachte Tokens werden günstiger abgerechnet, zuzüglich Speicher, solange der Cache besteht. Benötigt einen API-Schlüssel.
+cache-ttl = Caches behalten für
+retry-enabled = Wiederholen, wenn die API ausgelastet ist
+retry-enabled-help = Anfragen, die wegen Ratenlimits (429) oder eines überlasteten Servers (503) abgelehnt werden, werden nach wachsender Wartezeit erneut gesendet, statt die Nachricht scheitern zu lassen.
+retry-attempts = Wiederholungen
+retry-fallback = Danach ausweichen auf
+retry-fallback-help = Wenn die Wiederholungen aufgebraucht sind, wird stattdessen dieses Modell gefragt. Chats mit Kontext-Cache bleiben bei ihrem Modell.
+retry-no-fallback = Kein Ausweichmodell
+retry-waiting = Die API ist ausgelastet, neuer Versuch in { $seconds } s ({ $attempt }/{ $attempts })…
+retry-falling-back = Frage stattdessen { $model }…
+summarize-threshold = Zusammenfassen anbieten ab
+summarize-threshold-help = Anteil des Kontextfensters des Modells, den der Chat füllen muss, bevor das Zusammenfassen der älteren Nachrichten vorgeschlagen wird
+summarize-auto = Automatisch zusammenfassen
+summarize-auto-help = Fasst die älteren Nachrichten ohne Nachfrage zusammen, sobald die Schwelle erreicht ist. Die Zusammenfassung lässt sich jederzeit rückgängig machen.
+summarize-model = Zusammenfassendes Modell
+summarize-same-model = Wie im Chat
+summarize-offer = ⚠ Der Chat füllt { $percent }% des Kontextfensters.
+summarize-now = 🗜 Ältere Nachrichten zusammenfassen
+summarize-now-hint = Fasst alles außer den letzten Wechseln in einer Nachricht zusammen. Die ursprünglichen Nachrichten bleiben erhalten und lassen sich wiederherstellen.
+summarize-dismiss = Nicht jetzt
+summarize-running = Ältere Nachrichten werden zusammengefasst…
+summarize-failed = Der Chat konnte nicht zusammengefasst werden: { $error }
+summary-title = 🗜 Zusammenfassung von { $count } früheren Nachrichten
+summary-text = Zusammenfassung
+summary-replaced = Zusammengefasste Nachrichten
+summary-restore = ↩ Wiederherstellen
+summary-restore-hint = Setzt die ursprünglichen Nachrichten anstelle der Zusammenfassung wieder ein
+cache-title = Kontext-Caches
+cache-help = Für deinen API-Schlüssel gespeicherte Caches. Sie kosten, solange sie bestehen – lösche die, die kein Chat braucht.
+cache-refresh = ⟳ Aktualisieren
+cache-none = Keine Kontext-Caches
+cache-expires = läuft in { $minutes } Min. ab
+cache-extend = Verlängern
+cache-delete = 🗑 Löschen
+models-context = Kontextfenster: { $input } Tokens Eingabe, { $output } Ausgabe
+models-methods = Unterstützt: { $methods }
+safety-harassment = Belästigung
+safety-hate-speech = Hassrede
+safety-sexually-explicit = Sexuell explizit
+safety-dangerous-content = Gefährliche Inhalte
+safety-block-none = Nichts blockieren
+safety-block-high = Nur hohe blockieren
+safety-block-medium = Mittlere und höhere blockieren
+safety-block-low = Niedrige und höhere blockieren
+safety-prompt-blocked = Der Prompt wurde blockiert ({ $reason })
+safety-response-blocked = Die Antwort wurde blockiert oder abgebrochen ({ $reason })
+safety-reason-safety = Die Sicherheitsfilter haben den Inhalt in den hervorgehobenen Kategorien als wahrscheinlich schädlich eingestuft.
+safety-reason-prohibited = Der Inhalt ist durch die Nutzungsrichtlinien von Google verboten, das lässt sich in den Einstellungen nicht ändern.
+safety-reason-recitation = Die Antwort hat zu viel aus einer urheberrechtlich oder anderweitig geschützten Quelle wiedergegeben.
+safety-reason-other = Das Modell hat aus einem nicht genannten Grund angehalten.
+safety-blocked-by = Blockiert durch: { $categories }
+safety-not-adjustable = Die Filter dieser Kategorien sind bereits aus, die Blockierung erzwingt Google und sie lässt sich nicht anpassen.
+safety-relax-warning = ⚠ Ein gelockerter Filter lässt Inhalte durch, die beleidigend oder schädlich sein können. Das gilt für alle Chats, oder nur für diesen, wenn seine Modelleinstellungen die Filter überschreiben, und lässt sich in den Einstellungen zurücksetzen.
+safety-relax = { $category } nicht mehr blockieren und neu generieren
+safety-relax-all = Keine davon mehr blockieren und neu generieren
+safety-relax-all-hint = Schaltet die Filter aller hervorgehobenen Kategorien auf einmal ab und fragt erneut
+injection-enabled = Anhänge und Wissensauszüge auf eingeschleuste Anweisungen prüfen
+injection-enabled-help = Sucht in Textdateien und in Auszügen aus Wissenssammlungen, abgerufene Webseiten eingeschlossen, nach Text, der sich an das Modell richtet, etwa „ignoriere vorherige Anweisungen“
+injection-auto-quarantine = Verdächtige Quellen automatisch aus dem Prompt weglassen
+injection-quarantined-sources = Wissensquellen in Quarantäne:
+injection-suspicious = ⚠ Mögliche Prompt-Injection
+injection-quarantined = ⛔ In Quarantäne
+injection-found = Anweisungsähnlicher Text: { $snippet }
+injection-quarantine = Quarantäne
+injection-release = Freigeben
+injection-quarantine-hint = Quellen in Quarantäne werden ab der nächsten Nachricht aus dem Prompt weggelassen
+verify-answer = Diese Antwort mit einem zweiten Modell gegenprüfen
+verify-running = Antwort wird auf Fakten geprüft…
+verify-title = Geprüft von { $model }: { $totals }
+verify-totals = { $supported } belegt, { $disputed } bestritten, { $unverifiable } nicht prüfbar
+verify-supported = Belegt
+verify-disputed = Bestritten
+verify-unverifiable = Nicht überprüfbar
+verify-ungrounded = Ohne Websuche geprüft, aus dem Wissen des Modells
+verify-no-claims = Keine Tatsachenbehauptungen zu prüfen
+verify-invalid = Die Prüfung konnte nicht gelesen werden
+verify-model = Prüfendes Modell
+verify-same-model = Wie bei der Antwort
+verify-grounding = Dem prüfenden Modell die Websuche erlauben
+verify-grounding-help = Stützt die Urteile auf Ergebnisse der Google-Suche und listet die zugrunde liegenden Seiten auf
+chatbox-hint = Frag mich etwas…
+rtl-preview-hint = So liest sich der linksläufige Text, das Eingabefeld selbst zeigt die Zeichen in Eingabereihenfolge
+persona-switched = Zu Persona { $persona } gewechselt
+persona-removed = Persona entfernt
+last-message-you = Du: { $content }
+suggestion-fact-title = Erzähl mir eine lustige Tatsache
+suggestion-fact-subtitle = über das Römische Reich
+suggestion-code-title = Zeig mir ein Codebeispiel
+suggestion-code-subtitle = für einen Webserver in Rust
+suggestion-joke-title = Erzähl mir einen Witz
+suggestion-joke-subtitle = über Krabben
+suggestion-ideas-title = Gib mir Ideen
+suggestion-ideas-subtitle = für ein Geburtstagsgeschenk
+
+## Find in chat
+
+find-hint = Im Chat suchen…
+find-match-case = Groß-/Kleinschreibung beachten
+find-regex = Regulären Ausdruck verwenden
+find-invalid-pattern = Ungültiges Muster
+find-position = { $current } von { $total }
+find-previous = Vorheriger Treffer (Umschalt+Enter)
+find-next = Nächster Treffer (Enter)
+find-close = Schließen (Esc)
+
+## Timeline
+
+timeline-earlier = Früher
+timeline-later = Später
+timeline-pick-day = Klicke auf einen Tag, um zu sehen, worüber ihr gesprochen habt.
+timeline-no-messages = An diesem Tag gibt es keine Nachrichten.
+
+## Search
+
+search-title = 🔍 Alle Chats durchsuchen
+search-hint = Nachrichten durchsuchen…
+search-hint-semantic = Beschreibe, was du suchst, und drücke Enter…
+search-hint-files = Dateiname oder Text in der Datei…
+search-syntax-help =
+    Filter: model:<Name> has:attachment after:<Datum> before:<Datum> tag:<Name>
+    Daten können 2024, 2024-06 oder 2024-06-15 sein. `after` schließt das Datum ein, `before` schließt es aus.
+search-semantic = 🧠 Semantisch
+search-semantic-help = Findet verwandte Unterhaltungen nach Bedeutung. Mit dem Gemini-Embedder wird der Nachrichtentext zum Aufbau des Index an die API gesendet.
+search-files = 📎 Dateien
+search-files-help = Findet Chats nach dem Namen angehängter Dateien oder Text darin
+search-save = 💾 Speichern
+search-save-help = Chats zu dieser Suche als Ordner in der Seitenleiste zeigen
+search-saved-name = Gespeicherte Suche
+search-saved = Suche in der Seitenleiste gespeichert
+search-indexing = Indiziere { $indexed }/{ $total } Nachrichten
+search-after = Nach
+search-before = Vor
+search-any-model = Jedes Modell
+search-any-chat = Jeder Chat
+search-searching = Suche…
+search-similarity = Ähnlichkeit zur Suchanfrage
+search-match-count =
+    { $count ->
+        [one] { $count } Treffer
+       *[other] { $count } Treffer
+    }
+
+## Prompt library
+
+prompt-library-new = ➕ Neuer Prompt
+prompt-library-new-name = Neuer Prompt
+prompt-library-versions =
+    { $count ->
+        [one] { $count } Version
+       *[other] { $count } Versionen
+    }
+prompt-library-remove = Prompt entfernen
+prompt-library-select = Wähle links einen Prompt aus oder lege einen an.
+prompt-library-name-hint = Name des Prompts
+prompt-library-text-hint = System-Prompt
+prompt-library-save-version = 💾 Version speichern
+prompt-library-discard = Verwerfen
+prompt-library-history = Verlauf ({ $count })
+prompt-library-restore = Wiederherstellen
+prompt-library-restore-hint = Diese Version in den Editor laden
+prompt-library-ab = A/B-Test
+prompt-library-ab-help = Dieselbe Eingabe gegen zwei Versionen oder Modelle laufen lassen und die bessere Antwort wählen.
+prompt-library-test-input = Testeingabe
+prompt-library-run = ▶ Ausführen
+prompt-library-prefer = 👍 { $side } bevorzugen
+prompt-library-tie = Unentschieden
+prompt-library-results = Ergebnisse ({ $count })
+
+## Personas and snippets
+
+personas-help = Personas bündeln Avatar, System-Prompt, Modell, Inferenz-Voreinstellung und Stimme.
+persona-new-name = Neue Persona
+persona-avatar = Avatar (Emoji)
+persona-name-hint = Name der Persona
+persona-remove = Persona entfernen
+persona-add = ➕ Persona hinzufügen
+persona-voice = Stimme
+persona-voice-default = Systemstandard
+snippets-help = Kürzel werden zu längerem Text erweitert, wenn im Eingabefeld ein Leerzeichen folgt.
+snippets-shortcut = Kürzel
+snippets-expansion = Erweiterung
+snippets-expansion-hint = Einzufügender Text
+snippets-remove = Kürzel entfernen
+
+## Tray
+
+tray-show = GeminiD anzeigen
+tray-quick-ask = Schnell fragen…
+tray-voice-mode = Sprachmodus umschalten
+tray-quit = Beenden
+
+## Appearance
+
+theme-mode = Modus
+theme-mode-auto = Automatisch
+theme-mode-light = ☀ Hell
+theme-mode-dark = 🌙 Dunkel
+theme-accent = Akzentfarbe
+theme-code-blocks = Codeblöcke
+theme-file = Themendatei
+theme-reload = Themen neu laden
+theme-open-folder = Themenordner öffnen
+theme-file-help = Themendateien sind .toml oder .json mit `name`, `dark` und Hex-Farben für `accent`, `text`, `window`, `panel`, `extreme_background`, `faint_background`, `code_background` und `hyperlink`.
+theme-high-contrast = Hoher Kontrast
+theme-high-contrast-help = Schwarz-weiße Farben mit umrandeten Elementen
+theme-thick-focus = Dicker Fokusrahmen
+theme-thick-focus-help = Macht den Rahmen um das fokussierte Element bei Tastaturbedienung besser sichtbar
+theme-reduced-motion = Bewegung reduzieren
+theme-reduced-motion-help = Schaltet Animationen, die animierte Denkanzeige und den Schnee ab
+reading-enabled = Lesehilfen für Antworten
+reading-enabled-help = Schrift und Abstände unten für Antworten des Assistenten verwenden. Codeblöcke bleiben unverändert
+reading-font = Schriftdatei
+reading-font-hint = z. B. OpenDyslexic-Regular.otf
+reading-line-height = Zeilenhöhe
+reading-letter-spacing = Buchstabenabstand
+fonts-fallbacks = Ersatzschriften, der Reihe nach für Zeichen, die den mitgelieferten Schriften fehlen:
+fonts-raise = Diese Schrift früher versuchen
+fonts-missing = Datei nicht gefunden
+fonts-path-hint = Pfad zu einer .ttf-, .otf- oder .ttc-Datei
+fonts-add = Hinzufügen
+fonts-system = Installierte CJK- und Emoji-Schriften verwenden
+fonts-system-help = Chinesische, japanische, koreanische und Emoji-Schriften in den üblichen Systemordnern suchen und zuletzt versuchen
+fonts-bundled-cjk = Nach all diesen wird eine mitgelieferte Teilmenge von Noto Sans CJK versucht
+
+spellcheck-enabled = Rechtschreibung im Eingabefeld prüfen
+spellcheck-enabled-help = Falsch geschriebene Wörter unterstreichen. Rechtsklick auf ein Wort zeigt Vorschläge
+spellcheck-language = Wörterbuch
+spellcheck-folder = Wörterbuchordner
+spellcheck-folder-hint = Ordner mit .aff- und .dic-Dateien von Hunspell
+spellcheck-ignored =
+    { $count ->
+        [one] { $count } Wort zum Wörterbuch hinzugefügt
+       *[other] { $count } Wörter zum Wörterbuch hinzugefügt
+    }
+spellcheck-clear = Leeren
+spellcheck-reload = Wörterbücher neu laden
+spellcheck-no-suggestions = Keine Vorschläge
+spellcheck-add-word = Zum Wörterbuch hinzufügen
+
+plugins-unsupported = Dieser Build unterstützt keine Plugins, mit dem Feature `plugins` neu bauen
+plugins-folder = Plugins werden aus { $path } geladen
+plugins-rescan = Neu suchen
+plugins-none = Keine Plugins gefunden
+plugins-renderer = Stellt Codeblöcke dieser Sprache dar
+plugins-exporter = Fügt ein Exportformat hinzu
+plugins-permissions = Angeforderte Berechtigungen, nur die markierten werden erlaubt:
+plugins-host = Mit { $host } verbinden
+plugins-path = { $path } lesen und schreiben
+scripting-unsupported = Dieser Build unterstützt keine Skripte, mit dem Feature `scripting` neu bauen
+scripting-enabled = Skripte ausführen
+scripting-enabled-help = Ruft on_before_send, on_response und on_chat_created in den Rhai-Skripten des Skriptordners auf. Geänderte Skripte werden automatisch neu geladen.
+scripting-folder = Skripte werden aus { $path } geladen
+scripting-open-folder = Ordner öffnen
+scripting-none = Keine Skripte gefunden
+schedule-new = Neuer Zeitplan
+schedule-add = Neuer Zeitplan
+schedule-none = Noch keine geplanten Prompts
+schedule-remove = Zeitplan entfernen
+schedule-name = Name des Zeitplans
+schedule-enabled = Aktiviert
+schedule-enabled-help = Sendet den Prompt nach Zeitplan, solange die App läuft; verpasste Läufe werden einmal nachgeholt
+schedule-cron = Zeitplan
+schedule-cron-help = Minute, Stunde, Tag im Monat, Monat und Wochentag wie bei cron. `0 8 * * 1-5` ist werktags um 8:00.
+schedule-next-run = Nächster Lauf: { $time }
+schedule-last-run = Letzter Lauf: { $time }
+schedule-disabled = Deaktiviert
+schedule-chat = Chat
+schedule-new-chat = Neuer Chat beim ersten Lauf
+schedule-prompt = Prompt
+schedule-custom-text = Eigener Text
+schedule-text-hint = Was bei jedem Lauf gesendet wird
+schedule-files = Anhänge:
+schedule-add-files = 📎 Dateien hinzufügen
+schedule-notify = Immer benachrichtigen
+schedule-notify-help = Zeigt eine Desktop-Benachrichtigung zur Antwort, auch wenn das Fenster aktiv ist
+schedule-run-now = ▶ Jetzt ausführen
+schedule-finished = Der geplante Prompt „{ $name }“ hat eine Antwort erhalten
+knowledge-new = Neue Sammlung
+knowledge-add = Neue Sammlung
+knowledge-none = Noch keine Wissenssammlungen
+knowledge-remove = Sammlung entfernen
+knowledge-name = Name der Sammlung
+knowledge-help = Ordner, Dateien und Webseiten werden in Abschnitte geteilt und mit Gemini eingebettet. Chats mit dieser Sammlung bekommen zu jedem Prompt die passendsten Abschnitte. Benötigt einen API-Schlüssel.
+knowledge-sources = Quellen:
+knowledge-add-folder = 📁 Ordner hinzufügen
+knowledge-add-files = 📄 Dateien hinzufügen
+knowledge-add-repository = 🗂 Repository hinzufügen
+knowledge-add-repository-help = Liest den Code eines Projektordners und überspringt, was in .gitignore steht. Antworten zitieren Dateien mit Zeilennummern
+knowledge-url-hint = https://…
+knowledge-add-url = 🌐 Seite hinzufügen
+knowledge-chunks =
+    { $count ->
+        [one] { $count } Abschnitt
+       *[other] { $count } Abschnitte
+    }
+knowledge-status =
+    { $count ->
+        [one] { $count } Abschnitt, indiziert { $time }
+       *[other] { $count } Abschnitte, indiziert { $time }
+    }
+knowledge-not-indexed = Noch nicht indiziert
+knowledge-up-to-date = Aktuell, geprüft { $time }
+knowledge-pending =
+    { $count ->
+        [one] { $count } Datei seit der Indexierung geändert
+       *[other] { $count } Dateien seit der Indexierung geändert
+    }
+knowledge-watch = Aktuell halten
+knowledge-watch-help = Prüft die Dateien alle 30 Sekunden und bettet geänderte im Hintergrund ein
+knowledge-embedder = Embeddings:
+knowledge-embedder-help = Womit die Dokumente in Vektoren umgewandelt werden. Nach einem Wechsel wird die ganze Sammlung beim nächsten Update neu eingebettet.
+embedder-gemini = Gemini-API
+embedder-local = Lokales Modell (offline)
+embedder-local-unsupported = Dieser Build enthält keine lokalen Embeddings, mit dem Feature `local-embeddings` neu bauen
+knowledge-update = Aktualisieren
+knowledge-update-help = Bettet nur die geänderten Dateien ein
+knowledge-debug = 🔍 Suche debuggen
+knowledge-debug-help = Zeigt, welche Abschnitte eine Frage bekommt, mit ihren Bewertungen. Einstellbar in den Einstellungen.
+knowledge-debug-hint = Eine Frage zum Ausprobieren
+knowledge-debug-run = Suchen
+knowledge-debug-none = Keine Abschnitte gefunden
+knowledge-debug-source = Quelle
+knowledge-debug-similarity = Ähnlichkeit
+knowledge-debug-keywords = Stichwörter
+knowledge-debug-score = Bewertung
+knowledge-debug-injected = Im Prompt
+retrieval-top-k = Abschnitte pro Prompt
+retrieval-top-k-help = Wie viele der am besten passenden Abschnitte in den Prompt kommen
+retrieval-min-score = Mindestbewertung
+retrieval-min-score-help = Niedriger bewertete Abschnitte werden ausgelassen, auch wenn weniger gefunden werden
+retrieval-keyword-weight = Stichwortgewicht
+retrieval-keyword-weight-help = Wie stark exakte Wortübereinstimmungen neben der Bedeutung zählen. Erhöhen für Namen, Bezeichner und Codes
+knowledge-index = Indizieren
+knowledge-reindex = Neu indizieren
+knowledge-progress = { $done } / { $total } Abschnitte
+knowledge-nothing-to-index = Die Quellen enthalten keinen Text zum Indizieren
+knowledge-cancelled = Indizierung abgebrochen
+knowledge-searching = Durchsuche die Wissenssammlung…
+knowledge-memory-empty = Nichts im Chat war es wert, gemerkt zu werden
+knowledge-citations =
+    { $count ->
+        [one] 📚 { $count } Quelle
+       *[other] 📚 { $count } Quellen
+    }
+knowledge-open-source = Quelle öffnen
+knowledge-off = Keine
+embedding-open = 🧪 Embedding-Spielwiese
+embedding-title = Embedding-Spielwiese
+embedding-help = Bettet jede Zeile mit dem Gemini-Embedding-Modell ein, um die inhaltliche Nähe von Texten zu vergleichen und sie zu gruppieren. Benötigt einen API-Schlüssel.
+embedding-items-hint = Ein Text pro Zeile
+embedding-task = Optimieren für
+embedding-task-similarity = Ähnlichkeit
+embedding-task-clustering = Clustering
+embedding-task-classification = Klassifizierung
+embedding-task-document = Suche: Dokumente
+embedding-task-query = Suche: Anfragen
+embedding-run = Einbetten
+embedding-summary = { $count } Vektoren mit { $dimensions } Dimensionen
+embedding-copy = 🗐 Als JSON kopieren
+embedding-similarity = Kosinus-Ähnlichkeit
+embedding-clusters = Cluster
+embedding-cluster-count = Gruppen:
+embedding-cluster = Gruppieren
+embedding-group = Gruppe { $number }
+batch-open = 📋 Stapellauf
+batch-title = Stapellauf
+batch-input = Eingabedatei
+batch-input-hint = CSV mit Kopfzeile oder JSON Lines
+batch-output = Ergebnisdatei
+batch-output-hint = .jsonl oder .csv
+batch-model = Modell
+batch-rate = Anfragen pro Minute
+batch-retries = Wiederholungen pro Zeile
+batch-template = Prompt-Vorlage, { "{{" }Spalte{ "}}" } wird durch den Wert der Zeile ersetzt:
+batch-from-prompt = Aus gespeichertem Prompt
+batch-template-hint = Fasse diese Bewertung in einem Satz zusammen: { "{{" }review{ "}}" }
+batch-system-prompt = System-Prompt (optional)
+batch-run = ▶ Starten
+batch-cancel = ⏹ Abbrechen
+batch-progress = { $done } von { $total } Zeilen fertig
+batch-failed =
+    { $count ->
+        [one] { $count } Zeile fehlgeschlagen, siehe Spalte error
+       *[other] { $count } Zeilen fehlgeschlagen, siehe Spalte error
+    }
+batch-tokens = Verbrauchte Tokens: { $count }
+batch-eta = Noch etwa { $minutes } Min.
+batch-finished = Fertig
+batch-cancelled = Abgebrochen, die fertigen Zeilen wurden gespeichert
+batch-no-output = Wähle eine Ergebnisdatei
+batch-empty = Die Eingabedatei hat keine Zeilen
+batch-missing-columns = Die Eingabedatei hat keine Spalte { $columns }
+batch-api = Batch-API verwenden
+batch-api-hint = Halber Preis regulärer Anfragen. Die ganze Datei wird als ein Auftrag gesendet, der meist in Minuten fertig ist, aber bis zu einem Tag dauern kann.
+batch-api-needs-key = Die Batch-API braucht einen API-Schlüssel
+batch-api-waiting = Warte auf die Batch-API: { $state }
+batch-no-result = Die Batch-API hat für diese Zeile kein Ergebnis geliefert
+batch-rows = Zeilen
+batch-config-hint = Die Inferenzeinstellungen des aktuellen Chats gelten für jede Zeile.
+compare-open-window = ⚖ Dokumente vergleichen
+compare-title = Dokumente vergleichen
+compare-help = Hängen Sie zwei Fassungen eines Dokuments an, etwa eines Vertrags oder einer Spezifikation, und sehen Sie, was sich geändert hat.
+compare-original = Original
+compare-revised = Überarbeitet
+compare-path-hint = Pfad zur Datei
+compare-model = Modell
+compare-focus = Achten auf
+compare-focus-hint = z. B. Zahlungsbedingungen und Haftung
+compare-run = ⚖ Vergleichen
+compare-invalid = Das Modell hat keinen Vergleich geliefert
+compare-no-changes = Keine Unterschiede gefunden
+compare-added = Hinzugefügt
+compare-removed = Entfernt
+compare-changed = Geändert
+compare-clause = Klausel
+compare-note = Hinweis
+compare-page = S. { $page }
+compare-open = Öffnen
+model-compare-open = 🆚 Modelle vergleichen
+model-compare-title = Modelle vergleichen
+model-compare-help = Sendet denselben Prompt und dieselben Dateien gleichzeitig an jedes Modell, mit dem Systemprompt und den Inferenz-Einstellungen des offenen Chats.
+model-compare-add = Modell hinzufügen
+model-compare-prompt-hint = Prompt für alle Modelle…
+model-compare-attach = Dateien anhängen
+model-compare-run = ▶ An alle senden
+model-compare-stop = ⏹ Stoppen
+model-compare-tokens = { $tokens } Ausgabe-Tokens
+image-edit-open-window = 🖌 Bilder bearbeiten
+image-edit-title = Bildeditor
+image-edit-help = Beschreibe eine Änderung, und ein Bildmodell wendet sie an. Jede Bearbeitung baut auf dem letzten Ergebnis auf, wähle einen früheren Schritt, um von dort neu anzusetzen.
+image-edit-model = Modell
+image-edit-open = 📂 Bild öffnen…
+image-edit-empty = Öffne ein Bild, oder klicke in einem Chat mit der rechten Maustaste auf ein Bild und wähle Bearbeiten.
+image-edit-instruction-hint = z. B. mach den Himmel zum Sonnenuntergang, entferne das Auto
+image-edit-apply = ✨ Bearbeitung anwenden
+image-edit-steps = Schritte
+image-edit-original = Original
+image-edit-step-hint = Diesen Schritt zeigen, die nächste Bearbeitung setzt hier an
+deeplink-register = geminid://-Links mit dieser App öffnen
+deeplink-register-help = Lässt Browser-Erweiterungen und andere Apps Prompts über Links wie geminid://new?prompt=Hallo oder geminid://chat/<id> übergeben
+deeplink-registered = Registriert
+deeplink-unsupported = Unter macOS richtet das App-Bundle den Link-Handler ein
+deeplink-chat-not-found = Den Chat aus dem Link gibt es nicht mehr
+code-scheme-follow = Wie Thema
+code-scheme-dark = Dunkel
+code-scheme-light = Hell
+code-scheme-sepia = Sepia
+
+## Keyboard shortcuts
+
+command-send = Nachricht senden
+command-new-chat = Neuer Chat
+command-regenerate = Letzte Antwort neu erzeugen
+command-stop = Erzeugung stoppen
+command-find-in-chat = Im Chat suchen
+command-global-search = Alle Chats durchsuchen
+command-palette = Befehlspalette
+command-settings = Einstellungen ein-/ausblenden
+command-next-model = Nächstes Modell
+command-previous-model = Vorheriges Modell
+command-toggle-sidebar = Seitenleiste ein-/ausblenden
+command-focus-mode = Fokusmodus umschalten
+command-timeline = Zeitleiste ein-/ausblenden
+command-back = Vorheriger Chat
+command-forward = Nächster Chat
+command-reopen-closed = Geschlossenen Chat wiederherstellen
+command-logs = Protokoll anzeigen
+command-diagnostics = Diagnose
+command-statistics = Statistik
+command-tools = Werkzeuge
+command-context-caches = Kontext-Caches
+logs-title = Protokoll
+logs-search = Suchen
+logs-follow = Mitlaufen
+logs-export = 💾 Exportieren…
+logs-copy = 🗐 Kopieren
+logs-open-folder = 📂 Protokollordner
+logs-empty = Keine passenden Einträge
+diagnostics-title = Diagnose
+diagnostics-run = ⟳ Erneut prüfen
+diagnostics-running = Wird geprüft…
+diagnostics-copy = 🗐 Bericht kopieren
+diagnostics-endpoint = API-Endpunkt
+diagnostics-credentials = Zugangsdaten
+diagnostics-proxy = Proxy
+diagnostics-latency = Latenz
+diagnostics-disk = Speicherplatz
+diagnostics-reachable = { $target } hat in { $ms } ms geantwortet
+diagnostics-timeout = { $target } hat nicht rechtzeitig geantwortet
+diagnostics-no-proxy = Kein Proxy festgelegt
+diagnostics-invalid-proxy = ungültige Proxy-URL
+diagnostics-no-api-key = Kein API-Schlüssel festgelegt
+diagnostics-api-key-valid = Der API-Schlüssel wurde akzeptiert
+diagnostics-not-logged-in = Nicht bei Code Assist angemeldet
+diagnostics-code-assist-valid = Angemeldet, Projekt { $project }
+diagnostics-rejected = Abgelehnt: { $reason }
+diagnostics-latency-result = Umlaufzeit { $min }–{ $max } ms, Median { $median } ms
+diagnostics-no-storage = Kein Ordner zum Speichern der Chats
+diagnostics-disk-result = { $free } frei, Chats und Einstellungen belegen { $used } in { $path }
+analytics-title = Statistik
+analytics-enabled = Lokale Nutzungsstatistik führen
+analytics-enabled-help = Zählt, welche Modelle du zu welchen Uhrzeiten nutzt und wie lange sie zum Antworten brauchen. Bleibt auf diesem Computer, nichts wird irgendwohin gesendet
+analytics-empty = Noch nichts gesammelt, die Statistik erscheint nach der nächsten Antwort
+analytics-disabled = Die Erfassung ist aus. Schalte sie ein, um deine meistgenutzten Modelle, die aktivsten Stunden und die Antwortzeiten zu sehen
+analytics-responses =
+    { $count ->
+        [one] { $count } Antwort
+       *[other] { $count } Antworten
+    }
+analytics-since = seit { $date }
+analytics-average-latency = Durchschnittliche Antwortzeit: { $seconds } s
+analytics-models = Meistgenutzte Modelle
+analytics-model = Modell
+analytics-count = Antworten
+analytics-latency = Ø Zeit
+analytics-errors = Fehler
+analytics-hours = Aktivste Stunden
+analytics-hour = { $hour }: { $count }
+analytics-busiest = Am aktivsten gegen { $hour }
+analytics-clear = Statistik löschen
+shortcut-press-key = Taste drücken…
+shortcut-unbound = Nicht belegt
+shortcut-record-hint = Klicken und dann das neue Kürzel drücken. Escape bricht ab.
+shortcut-conflicts = Auch belegt mit: { $commands }
+shortcut-unbind = Belegung entfernen
+shortcut-reset = Auf Standard zurücksetzen
+shortcut-reset-all = Alle Kürzel zurücksetzen
+vim-mode = Verlaufsnavigation im Vim-Stil
+vim-mode-help = Solange kein Textfeld fokussiert ist: j/k wechseln zwischen Nachrichten, g/G springen zur ersten/letzten und i fokussiert das Eingabefeld
+ctrl-enter-sends = Mit Strg+Enter senden
+ctrl-enter-sends-help = Enter fügt einen Zeilenumbruch ein und Strg+Enter sendet die Nachricht, statt des Senden-Kürzels und Umschalt+Enter. Praktisch mit Eingabemethoden, die mit Enter bestätigen
+
+## Onboarding
+
+onboarding-title = Willkommen bei GeminiD
+onboarding-step = Schritt { $step } von { $total }
+onboarding-welcome = Lass uns alles einrichten
+onboarding-welcome-text = GeminiD spricht mit den Gemini-Modellen von Google. Dafür braucht es eine Anmeldung, ein Standardmodell und, falls dein Netzwerk es verlangt, einen Proxy. Alles lässt sich später in ⚙ Einstellungen ändern.
+onboarding-auth = Wie möchtest du dich anmelden?
+onboarding-auth-api-key = Gemini API-Schlüssel
+onboarding-auth-api-key-text = Ein kostenloser Schlüssel aus Google AI Studio. Einfach einzurichten und nötig für die semantische Suche.
+onboarding-auth-google = Google-Konto (Code Assist)
+onboarding-auth-google-text = Melde dich mit deinem Google-Konto an und nutze das Code-Assist-Kontingent eines deiner Cloud-Projekte.
+onboarding-paste-key = Füge deinen API-Schlüssel ein
+onboarding-get-key = Erhältlich unter
+onboarding-check-key = Schlüssel prüfen
+onboarding-key-valid = ✅ Schlüssel funktioniert
+onboarding-key-text = Der Schlüssel wird lokal mit dem restlichen App-Zustand gespeichert. Wenn du hinter einem Proxy bist, richte ihn zuerst im letzten Schritt ein.
+onboarding-login = Mit Google anmelden
+onboarding-login-browser = Schließe die Anmeldung im Browser ab
+onboarding-project-id = Google-Cloud-Projekt-ID
+onboarding-login-text = Für die Anmeldung öffnet sich ein Browserfenster. Anfragen werden über das gewählte Cloud-Projekt abgerechnet.
+onboarding-model = Wähle ein Standardmodell
+onboarding-model-text = Neue Chats starten mit diesem Modell. Flash-Modelle sind schneller und günstiger, Pro-Modelle klüger. Du kannst pro Chat jederzeit wechseln.
+onboarding-network = Netzwerk
+onboarding-network-text = Nur nötig, wenn die Gemini API aus deinem Netzwerk nicht direkt erreichbar ist. HTTP- und SOCKS5-Proxys werden unterstützt.
+onboarding-skip = Einrichtung überspringen
+onboarding-finish = Fertig
+onboarding-next = Weiter ⏵
+onboarding-back = ⏴ Zurück
+onboarding-finished = Du kannst all das später in ⚙ Einstellungen ändern
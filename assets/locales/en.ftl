@@ -0,0 +1,1135 @@
+### GeminiD UI strings, English
+
+## Common
+
+yes = Yes
+no = No
+cancel = Cancel
+confirm = Confirm
+close = Close
+delete = Delete
+remove = Remove
+add = ➕ Add
+clear = Clear
+edit = Edit
+export = Export
+export-cancelled = Export cancelled
+no-file-selected = No file selected
+import = Import
+save = Save
+load = Load
+none = None
+you = You
+new-chat = New Chat
+no-matches = No matches
+chat-count =
+    { $count ->
+        [one] { $count } chat
+       *[other] { $count } chats
+    }
+message-count =
+    { $count ->
+        [one] { $count } message
+       *[other] { $count } messages
+    }
+token-count =
+    { $count ->
+        [one] { $count } token
+       *[other] { $count } tokens
+    }
+date-long = %A, %B %-d, %Y
+date-short = %b %-d, %Y
+date-day = %a, %Y-%m-%d
+date-time = %Y-%m-%d %H:%M
+time-just-now = just now
+time-minutes-ago =
+    { $count ->
+        [one] { $count } minute ago
+       *[other] { $count } minutes ago
+    }
+time-hours-ago =
+    { $count ->
+        [one] { $count } hour ago
+       *[other] { $count } hours ago
+    }
+time-days-ago =
+    { $count ->
+        [one] { $count } day ago
+       *[other] { $count } days ago
+    }
+timestamps = Message timestamps
+timestamps-hidden = Hidden
+timestamps-relative = Relative
+timestamps-absolute = Date and time
+timestamps-utc = Show times in UTC
+timestamps-utc-help = Use UTC instead of the system time zone for message times
+
+## Settings
+
+settings-reset-title = Reset Settings
+settings-reset-confirm = Are you sure you want to reset global settings? This action cannot be undone!
+settings-authentication = Authentication
+settings-auth-method = Method
+settings-api-key = API Key
+settings-api-key-hint = Enter your Google AI Studio API Key
+settings-login-google = 🔑 Login with Google
+settings-logged-in = ✅ Logged in
+settings-logout = 🚪 Logout
+settings-logged-out = Logged out and cache cleared.
+settings-login-success = Google Login successful!
+settings-project = Project
+settings-select-project = Select Project
+settings-no-projects = No projects found or loading...
+settings-model = Model
+settings-default-model = Default model for new chats
+settings-inherit-model = Inherit from chats
+settings-inherit-model-help = Inherit model changes from chats
+settings-behavior = Behavior
+settings-streaming = Stream response
+settings-streaming-help = Receive the response as it's being generated. Disabling this will wait for the full response before displaying it
+settings-persist-thoughts = Persist Thoughts in Context
+settings-persist-thoughts-help = When enabled, the model's 'thought' parts are appended to the session context for subsequent requests. Warning: This will rapidly increase token consumption
+settings-file-api = Upload files (File API)
+settings-file-api-help = When enabled, files will be uploaded to Google's servers (File API) instead of being sent as base64 inline data. Uploaded files are temporary and only accessible by you.
+settings-voice-mode = Voice mode
+settings-voice-mode-help = Read every response aloud as soon as it finishes generating
+settings-notifications = Desktop notifications
+settings-notifications-help = Show a desktop notification when a response finishes while the window is unfocused or minimized
+settings-close-to-tray = Close to tray
+settings-close-to-tray-help = Closing the window hides it to the system tray and keeps the app running. Use Quit from the tray menu to exit
+settings-snippets = Snippets
+settings-appearance = Appearance
+settings-language = Language
+settings-language-system = System ({ $language })
+settings-rtl-layout = Right-to-left layout
+settings-rtl-layout-help = Puts the sidebar on the right and the chatbox buttons after the text, for Arabic, Hebrew, Persian and other right-to-left languages
+settings-reading = Reading
+settings-fonts = Fonts
+settings-spellcheck = Spellcheck
+settings-retrieval = Knowledge Retrieval
+settings-safety = Safety Filters
+settings-injection = Prompt Injection
+settings-verify = Answer Verification
+settings-cache = Context Caching
+settings-retry = Retries
+settings-summarize = History Summarization
+settings-plugins = Plugins
+settings-scripting = Scripts
+settings-shortcuts = Keyboard Shortcuts
+settings-misc = Miscellaneous
+settings-proxy = Use proxy
+settings-proxy-help = Use the proxy for gemini api request
+settings-editor-command = Editor command
+settings-editor-command-help = Opens cited files at the cited line, {"{"}path{"}"} and {"{"}line{"}"} are filled in. Leave empty to use the default app
+settings-snow = Let It Snow!
+settings-ui-scale = UI Scale
+settings-ui-scale-help = Adjust the overall size of the user interface
+settings-reset-label = Reset global settings to defaults
+settings-reset = Reset
+settings-save-load-label = Save and load settings as JSON
+
+## Main window
+
+toggle-sidebar = Toggle Sidebar
+back = Back
+forward = Forward
+reopen-closed-chat = Reopen closed chat
+no-closed-chats = No recently closed chats
+timeline = Timeline
+settings = Settings
+token-count-help = Estimated total tokens in context
+token-count-limit = { $count } tokens with the message being written, { $percent }% of the { $limit } the model takes
+exit-focus-mode = Exit focus mode
+dropping-files = Dropping files:
+notification-ready = Response ready
+notification-failed = Generation failed
+notification-open-chat = Open chat
+voice-mode-on = Voice mode on
+voice-mode-off = Voice mode off
+streaming-enabled = Streaming enabled
+streaming-disabled = Streaming disabled
+chat-detached = This chat is open in a separate window.
+chat-bring-back = Bring it back
+split-close = Close split view
+split-swap = Swap with the main view
+remove-chat-title = Remove Chat
+remove-chat-confirm =
+    Do you really want to remove this chat? You can reopen it later{ $shortcut }.
+    Hold Shift to skip this warning.
+remove-chat-named = Remove chat “{ $name }”
+remove-chat = Remove chat
+bulk-delete-title = Delete Chats
+bulk-delete-summary = This will delete { $chats } containing { $messages }.
+bulk-archive-title = Archive Chats
+bulk-archive-summary = This will archive { $chats } containing { $messages }.
+bulk-unarchive-title = Unarchive Chats
+bulk-unarchive-summary = This will unarchive { $chats } containing { $messages }.
+bulk-tag-title = Tag Chats
+bulk-tag-summary = This will add the tag “{ $tag }” to { $chats } containing { $messages }.
+bulk-untag-summary = This will remove the tag “{ $tag }” from { $chats } containing { $messages }.
+bulk-move-title = Move Chats
+bulk-move-summary = This will move { $chats } containing { $messages } to “{ $folder }”.
+bulk-export-title = Export Chats
+bulk-export-summary = This will export { $chats } containing { $messages }.
+bulk-tag-hint = Add or remove a tag
+selected-count = { $count } selected
+archive = Archive
+archive-button = 📦 Archive
+unarchive = Unarchive
+no-tags = No tags yet
+tag-delete = Delete tag
+tag-new-hint = New tag
+top-level = Top level
+move-to-folder = Move to folder
+clear-selection = Clear selection
+chat-edit-persona = Persona
+chat-edit-tags = Tags
+chat-edit-knowledge = Knowledge
+chat-edit-knowledge-help = Relevant excerpts from the collection are added to every prompt, the answer lists them as sources
+chat-edit-remember = Remember this chat in:
+chat-edit-remember-button = 🧠 Remember
+chat-edit-remember-help = Picks the lasting facts and decisions out of this chat and adds them to the collection, so later chats using it know them. Remembering again replaces them
+chat-edit-manage-tags = Manage tags
+chat-edit-model = Model
+chat-edit-export-label = Export chat history to a file
+chat-edit-export-format = Export Format
+save-as = Save As…
+export-with-plugin = Save as { $name }…
+tab-chats = Chats
+tab-prompts = Prompts
+tab-schedules = Schedules
+tab-knowledge = Knowledge
+tab-videos = Videos
+no-recent-messages = No recent messages
+new-chat-button = ➕ New Chat
+new-chat-hint = Create a new chat
+new-folder-button = 📁 New Folder
+new-folder-hint = Create a new folder, drag chats onto it to move them
+folder-remove-hint = Remove folder, its contents move one level up
+folder-edit-hint = Edit folder
+folder-new-chat-hint = New chat in this folder
+folder-new-name = New Folder
+folder-name-hint = Folder name
+folder-template = Default Template
+folder-template-help = New chats created in this folder or its subfolders start with these settings.
+folder-template-enable = Use a template
+project-title = Project
+project-help = Chats in this folder and its subfolders get these on every request.
+project-enable = Make it a project
+project-instructions-hint = Instructions for every chat in the project
+project-knowledge = Knowledge
+drop-to-top-level = Drop here to move to the top level
+archived-header = 📦 Archived ({ $count })
+remove-saved-search = Remove saved search
+no-matching-chats = No matching chats
+open-in-split-view = ◫ Open in split view
+open-in-new-window = 🗗 Open in new window
+
+## Command palette
+
+palette-hint = Type a command, chat or model…
+palette-nothing = Nothing matches
+palette-enable-streaming = Enable streaming
+palette-disable-streaming = Disable streaming
+palette-switch-model = Switch model: { $model }
+palette-use-prompt = Use as system prompt: { $prompt }
+palette-prompt-applied = System prompt set to { $prompt }
+palette-setting = Setting
+palette-model = Model
+palette-prompt = Prompt
+palette-chat = Chat
+
+## Chat
+
+generation-time = Generation time
+token-usage = In: { $input } / Out: { $output } / Total: { $total }
+retry-generation = 🔄 Retry Generation
+retry-generation-hint = Try to generate a response again. Make sure you have a valid API Key and stable connection.
+stream-degraded = ⚠ Stream degraded, content may be incomplete
+stream-degraded-hint = Parts of the streamed answer couldn't be read and were left out, or the stream broke off, often because of a proxy. The rest of the answer is shown
+answer-truncated = ✂ The answer stopped at the output token limit
+answer-interrupted = ⏹ Stopped before the answer was finished
+answer-interrupted-hint = You stopped the generation, the text that arrived until then is kept
+continue-generating = ⏩ Continue generating
+continue-generating-hint = Ask the model to go on from where it stopped, the rest is added to this answer
+prepend-hint = Prepend text to response…
+regenerate-button = 🔄 Regenerate
+regenerate-hint = Generate the response again, the LLM will start after any prepended text
+regenerate-with = Regenerate with…
+regenerate-with-hint = Generate this answer again with another model, the chat keeps its own model
+edit-message-button = ✏ Edit
+edit-message-hint = Edit the message in the context, but don't regenerate it
+cancel-button = ❌ Cancel
+copied = Copied!
+copy-message = Copy message
+read-aloud-hint = Read the message out loud. Right click to repeat
+regenerate = Regenerate
+pick-files = Pick files
+attach-url = Attach from a link
+attach-url-hint = https://example.com/paper.pdf
+attach-url-download = Download and attach
+attach-url-cancel = Cancel
+attach-url-downloading = Downloading…
+attach-url-failed = Failed to download the file: { $error }
+bundle-attach-hint = Attach the text files of a folder
+bundle-title = Attach Folder
+bundle-change = Change…
+bundle-include = Include
+bundle-exclude = Exclude
+bundle-globs-help = Glob patterns relative to the folder, one per line, e.g. src/**/*.rs. Without any to include, every file is taken.
+bundle-max-size = Max file size
+bundle-gitignore = Skip what .gitignore leaves out
+bundle-scanning = Reading the folder and counting tokens…
+bundle-rescan = 🔄 Apply filter
+bundle-summary =
+    { $files ->
+        [one] { $files } file, { $size }
+       *[other] { $files } files, { $size }
+    }
+bundle-tokens = ≈ { $tokens } tokens with this chat's model
+bundle-tokens-unknown = The tokens couldn't be counted.
+bundle-skipped = Skipped: { $large } too large, { $binary } not text
+bundle-files = Files in the bundle
+bundle-attach = 📎 Attach as one text file
+bundle-attach-stale = Apply the changed filter first
+archive-title = Extract { $name }
+archive-help = Pick the files to attach instead of the archive. Files the model can't read are greyed out.
+archive-reading = Reading the archive…
+archive-select-supported = Select readable files
+archive-select-none = Select none
+archive-unsupported = The model can't read this type of file
+archive-extracting = Extracting…
+archive-extract =
+    { $count ->
+        [one] Extract and attach { $count } file
+       *[other] Extract and attach { $count } files
+    }
+archive-keep = Keep the archive
+upload-progress = { $sent } / { $total } MB
+upload-uploading = Uploading…
+upload-failed = Failed
+attachment-missing = (FILE NOT FOUND)
+attachment-info-missing = { $name }, file not found
+attachment-info-uploading = { $name }, uploading
+attachment-info-uploading-percent = { $name }, uploading { $percent }%
+attachment-info-failed = { $name }, failed: { $error }
+attachment-info-expired = { $name }, upload expired
+attachment-remove-hint = Press Delete to remove
+upload-cancel = Cancel the upload, the message is sent without this file
+upload-cancelling = Cancelling…
+upload-expires = expires in { $hours } h { $minutes } min
+upload-expires-hint = The File API deletes uploads after 48 hours
+upload-expired = Upload expired
+upload-reupload = Upload again
+upload-reupload-hint = Upload the file again, so the next messages can still refer to it
+upload-reupload-unavailable = Turn on uploads through the File API with an API key to upload files
+screenshot-capture = Capture a screen region
+screenshot-help = Drag to select a region · Enter takes the whole screen · Esc cancels
+screenshot-failed = Failed to capture the screen: { $error }
+record-start = Record from the microphone
+record-stop = Stop and attach the recording
+record-discard = Discard the recording
+record-failed = Recording failed: { $error }
+youtube-detected =
+    { $count ->
+        [one] ▶ The message has a YouTube link, the model can watch the video itself.
+       *[other] ▶ The message has { $count } YouTube links, the model can watch the videos itself.
+    }
+youtube-attach = Attach as video
+youtube-attach-hint = Sends the video instead of its link. Right-click the attachment to send only a part of it.
+youtube-keep-text = Keep as text
+youtube-clip = Part of the video
+youtube-clip-start = Start
+youtube-clip-end = End
+youtube-clip-invalid = The end has to come after the start.
+improve-prompt = Improve prompt
+improve-prompt-failed = Couldn't improve the prompt: { $error }
+preview-request = Preview the request
+param-override-hint = Override the temperature or thinking budget for the next message
+param-override-active = The next message is sent with overridden settings
+param-override-title = Next message only:
+param-override-help = Applies to the next request alone, the chat's inference settings don't change
+param-override-temperature = Temperature
+param-override-thinking = Thinking budget
+param-override-clear = Drop the override
+presets-pick = Presets…
+presets-empty = No presets saved yet
+presets-name-hint = Preset name
+presets-save = 💾 Save preset
+presets-save-hint = Save the system prompt under this name
+presets-overwrite = A saved prompt of this name exists, saving adds a new version of it
+presets-manage = Manage presets
+presets-remove = Remove preset
+presets-export = Export presets…
+inference-settings = Inference Settings
+inference-enable = Enable
+inference-reset = Reset
+inference-reset-hint = Reset to default
+inference-reset-all = Reset Settings
+system-prompt-title = System Prompt
+system-prompt-help = A system prompt can be used to specify custom behavior from the model.
+system-prompt-enable = Enable custom system prompt
+system-prompt-hint = A system prompt for the model. E.g., 'You are a helpful assistant that specializes in writing Rust code.'
+few-shot-title = Few-shot Examples
+few-shot-help = Example input/output pairs are sent before the conversation as if the model had already answered them.
+few-shot-input = Input
+few-shot-output = Output
+few-shot-input-hint = User message
+few-shot-output-hint = Model response
+few-shot-remove = Remove example
+thinking-title = Thinking
+thinking-help = Enable native thinking for Gemini 2.5 models to improve reasoning.
+thinking-include = Include thought summaries
+thinking-budget = Set thinking budget
+thinking-budget-help = Token budget for thinking. -1 for dynamic, 0 to disable.
+temperature-title = Temperature
+temperature-help = Controls the randomness of the output. Higher values (e.g., 1.0) produce more creative responses, while lower values (e.g., 0.2) make the output more deterministic.
+max-tokens-title = Max Output Tokens
+max-tokens-help = Maximum number of tokens to generate in the response.
+top-k-title = Top-K
+top-k-help = Changes how the model selects tokens for output. A lower value limits the sampling to a smaller set of the most likely tokens.
+top-p-title = Top-P
+top-p-help = Changes how the model selects tokens for output, sampling from a cumulative probability distribution. Use either Top-K or Top-P, not both.
+stop-title = Stop Sequence
+stop-help = A set of up to 5 character sequences that will stop output generation.
+preview-title = Request Preview
+preview-loading = Assembling the request…
+preview-model = Model: { $model }
+preview-copy = 🗐 Copy as JSON
+preview-no-count = Token counts need a Gemini API key
+preview-citations =
+    { $count ->
+        [one] { $count } knowledge excerpt added to the system instruction
+       *[other] { $count } knowledge excerpts added to the system instruction
+    }
+preview-thoughts-kept = Thoughts are sent back as part of the history
+preview-thoughts-dropped = Thoughts are left out of the history
+preview-system = System instruction
+preview-examples = Few-shot examples
+preview-history = History
+preview-prompt = New message
+preview-role-user = User
+preview-role-model = Model
+preview-inline-file = 📎 { $mime }, { $size } inline
+preview-uploaded-file = 🔗 { $mime }, uploaded as { $uri }
+preview-empty = None
+preview-config = Generation config
+preview-safety = Safety settings
+preview-tools = Tools:
+safety-help = How likely harmful an answer may be before it is blocked, per category. Some content is always blocked by Google regardless of these settings
+safety-override = Override the app-wide safety filters
+safety-override-help = Requests with this model configuration use the safety filters from the settings
+grounding-title = Google Search
+grounding-help = Lets the model look things up on the web and cite the pages it used under the answer.
+grounding-enabled = Ground answers in Google Search
+grounding-sources =
+    { $count ->
+        [one] { $count } web source
+       *[other] { $count } web sources
+    }
+grounding-queries = Searched for: { $queries }
+url-context-title = URL Context
+url-context-help = Links in your message are fetched by Google and read by the model, instead of being sent as plain text. The pages it read are listed under the answer.
+url-context-enabled = Read linked pages
+url-context-pages =
+    { $count ->
+        [one] { $count } page read
+       *[other] { $count } pages read
+    }
+structured-output = Structured Output
+structured-help = Makes the model answer in JSON, shaped by a schema if you give one. JSON answers are shown as a tree you can fold.
+structured-enabled = Answer in JSON
+structured-schema-hint = JSON schema of the answer, e.g. {"{"} "type": "object", "properties": {"{"} … {"}"} {"}"}
+structured-schema-valid = ✔ The answers will follow this schema
+structured-schema-empty = Without a schema any JSON will do
+structured-invalid-schema = The schema isn't valid JSON: { $error }
+structured-schema-not-object = The schema has to be a JSON object
+structured-title = {"{ }"} JSON
+structured-copy = 📋 Copy JSON
+structured-copy-path = Copy path
+structured-copy-value = Copy value
+structured-valid = ✔ Matches the schema
+structured-invalid =
+    { $count ->
+        [one] ✖ { $count } place doesn't match the schema
+       *[other] ✖ { $count } places don't match the schema
+    }
+structured-issues = Schema violations
+structured-not-json = ✖ The answer isn't valid JSON: { $error }
+structured-issue-type = expected { $expected }, got { $actual }
+structured-issue-required = the required property "{ $name }" is missing
+structured-issue-unexpected = the schema doesn't allow this property
+structured-issue-enum = not one of { $allowed }
+structured-issue-minimum = less than the minimum of { $limit }
+structured-issue-maximum = more than the maximum of { $limit }
+structured-issue-min-items = fewer than { $count } items
+structured-issue-max-items = more than { $count } items
+structured-issue-any-of = matches none of the allowed shapes
+response-modalities-title = Response Modalities
+response-modalities-help = What the model answers with. Images need a model that can draw, audio one that can speak, other models reject the request.
+response-modalities-text = Text
+response-modalities-image = Image
+response-modalities-audio = Audio
+response-modalities-default = Nothing picked, the model answers with text
+media-resolution-title = Media Resolution
+media-resolution-help = How many tokens each image, video frame and PDF page gets. Low makes long videos and big documents cheaper, high keeps small text and fine detail readable. Right-click an attachment to override it for that file.
+media-resolution-attachment = Media resolution of this file
+media-resolution-auto = Model default
+media-resolution-low = Low
+media-resolution-medium = Medium
+media-resolution-high = High
+image-invalid = ⚠ The model sent an image that can't be shown
+image-hint = Right-click to save or copy
+image-save = 💾 Save image…
+image-copy = 📋 Copy image
+image-edit = 🖌 Edit image…
+audio-title = Spoken Answers
+audio-help = Makes the speech models, like the TTS preview models, read their answer out. Other models reject the request.
+audio-enabled = Ask for speech
+audio-voice = Voice
+audio-language = Language
+audio-language-hint = auto
+audio-invalid = ⚠ The model sent audio that can't be played
+audio-play = Play
+audio-play-external = Play in the default app
+audio-stop = Stop
+audio-save = 💾 Save…
+audio-save-hint = Saves raw speech as a WAV file
+video-new = New video
+video-none = No videos yet
+video-remove = Remove the video
+video-title = New video
+video-help = Veo makes a short clip from the prompt, optionally starting from an image. It usually takes a few minutes.
+video-prompt = Describe the video…
+video-negative-prompt = What to avoid (optional)
+video-model = Model
+video-aspect-ratio = Aspect ratio
+video-image = Starting image
+video-image-none = None
+video-pick = Choose…
+video-generate = 🎬 Generate
+video-needs-key = Video generation needs an API key
+video-submitting = Submitting…
+video-generating = Generating…
+video-downloading = Downloading…
+video-done = Done
+video-failed = Failed: { $error }
+video-invalid = The API sent no video
+video-avoiding = Avoiding: { $text }
+video-open = ▶ Open in player
+video-save = 💾 Save as…
+penalty-presence = Presence Penalty
+penalty-presence-help = Penalizes tokens that already appeared in the answer, however often. Positive values push the model towards new topics, negative ones let it stay on the same.
+penalty-frequency = Frequency Penalty
+penalty-frequency-help = Penalizes tokens by how often they already appeared in the answer. Positive values make repetition less likely, negative ones more.
+candidates-title = Candidates
+candidates-help = How many answers the model writes per request. The first one stays in the conversation, the others are shown as tabs on the message and can take its place.
+candidates-current = Answer 1
+candidates-tab = Answer { $number }
+candidates-promote = ⬆ Continue from this answer
+candidates-promote-hint = Puts this answer in the conversation, the current one becomes an alternative
+functions-title = Tools
+functions-help = Declare functions the model can ask to call. When it does, the call shows up in the chat and you type in what the function returned.
+functions-enabled = Send with every request
+functions-unnamed = (unnamed)
+functions-add = ➕ Add function
+functions-remove = Remove function
+functions-none-selected = Select a function or add one
+functions-name = Name
+functions-description = Description
+functions-description-hint = What the function does and when to call it
+functions-parameters = Parameters (JSON schema)
+functions-valid = ✔ Valid declaration
+functions-invalid-name = The name has to start with a letter or underscore and may contain letters, digits, underscores, dots and dashes, up to 64 characters
+functions-invalid-schema = The parameters aren't valid JSON: { $error }
+functions-schema-not-object = The parameters schema has to be of type "object"
+functions-answered = answered
+functions-response-hint = What the function returned, JSON or plain text
+functions-send = Send responses
+functions-send-disabled = Answer every call first
+functions-run-commands = Run local commands
+functions-run-commands-help = Lets a function be answered by a shell command on this computer. Every run has to be confirmed, the arguments arrive as JSON on stdin and in GEMINID_ARGS, and what the command prints goes back to the model.
+functions-command = Command
+functions-command-hint = e.g. python3 ~/tools/weather.py
+functions-command-disabled = Turn on local commands first
+functions-command-timeout = The command didn't finish within { $seconds } seconds
+functions-command-failed = The command failed, its error is in the response field
+functions-run = ▶ Run command
+functions-run-plugin = Answered by the { $plugin } plugin
+functions-running = Running the command…
+functions-confirm-title = Run a local command?
+functions-confirm = The model called { $name }, which runs this on your computer:
+functions-confirm-run = Run
+cache-invalid = The API returned a cache without a name
+cache-failed = Couldn't cache the chat: { $error }
+cache-creating = Caching the start of the chat…
+cache-stale = The start of the chat changed since it was cached, the cache isn't used
+cache-expired = The context cache expired
+cache-recreate = Cache again
+cache-drop = Drop the cache
+cache-active = The first { $count } messages are cached until { $time }
+cache-offer-banner = This chat has a long context. Cache it to pay less for it on every request?
+cache-create = Cache context
+cache-dismiss = Not now
+cache-offer = Offer to cache long chats
+cache-offer-help = Shows a banner when a chat grows past about 32,000 tokens. Cached tokens are billed at a lower rate, plus storage for as long as the cache lives. Needs an API key.
+cache-ttl = Keep caches for
+retry-enabled = Retry when the API is busy
+retry-enabled-help = Requests refused for rate limits (429) or an overloaded server (503) are sent again after a growing wait, instead of failing the message.
+retry-attempts = Retries
+retry-fallback = Then fall back to
+retry-fallback-help = Once the retries run out, the answer is asked from this model instead. Chats using a context cache stay on their model.
+retry-no-fallback = No fallback
+retry-waiting = The API is busy, retrying in { $seconds } s ({ $attempt }/{ $attempts })…
+retry-falling-back = Asking { $model } instead…
+summarize-threshold = Offer to summarize from
+summarize-threshold-help = Share of the model's context window the chat has to fill before summarizing its older messages is suggested
+summarize-auto = Summarize automatically
+summarize-auto-help = Condenses the older messages as soon as the threshold is reached, without asking. The summary can always be reverted.
+summarize-model = Summarizing model
+summarize-same-model = Same as the chat
+summarize-offer = ⚠ The chat fills { $percent }% of the context window.
+summarize-now = 🗜 Summarize older messages
+summarize-now-hint = Condenses everything but the last few turns into one summary message. The original messages are kept and can be restored.
+summarize-dismiss = Not now
+summarize-running = Summarizing older messages…
+summarize-failed = Couldn't summarize the chat: { $error }
+summary-title = 🗜 Summary of { $count } earlier messages
+summary-text = Summary
+summary-replaced = Summarized messages
+summary-restore = ↩ Restore
+summary-restore-hint = Puts the original messages back in place of the summary
+cache-title = Context Caches
+cache-help = Caches stored for your API key. They are billed while they live, delete the ones no chat needs.
+cache-refresh = ⟳ Refresh
+cache-none = No context caches
+cache-expires = expires in { $minutes } min
+cache-extend = Extend
+cache-delete = 🗑 Delete
+models-context = Context window: { $input } tokens in, { $output } out
+models-methods = Supports: { $methods }
+safety-harassment = Harassment
+safety-hate-speech = Hate speech
+safety-sexually-explicit = Sexually explicit
+safety-dangerous-content = Dangerous content
+safety-block-none = Block none
+safety-block-high = Block only high
+safety-block-medium = Block medium and above
+safety-block-low = Block low and above
+safety-prompt-blocked = The prompt was blocked ({ $reason })
+safety-response-blocked = The answer was blocked or cut short ({ $reason })
+safety-reason-safety = The safety filters rated the content as likely harmful in the highlighted categories.
+safety-reason-prohibited = The content is prohibited by Google's usage policies, this can't be changed in the settings.
+safety-reason-recitation = The answer repeated too much of a copyrighted or otherwise protected source.
+safety-reason-other = The model stopped for a reason it didn't explain.
+safety-blocked-by = Blocked by: { $categories }
+safety-not-adjustable = The filters for these categories are already off, the block is enforced by Google and can't be adjusted.
+safety-relax-warning = ⚠ Relaxing a filter lets through content that may be offensive or harmful. It applies to every chat, or only to this one if its model settings override the filters, and can be changed back in the settings.
+safety-relax = Stop blocking { $category } and regenerate
+safety-relax-all = Stop blocking all of them and regenerate
+safety-relax-all-hint = Turns off the filters of every highlighted category at once and asks again
+injection-enabled = Scan attachments and knowledge excerpts for injected instructions
+injection-enabled-help = Looks for text that addresses the model, like "ignore previous instructions", in text files and in the excerpts of knowledge collections, fetched web pages included
+injection-auto-quarantine = Leave suspicious sources out of the prompt automatically
+injection-quarantined-sources = Quarantined knowledge sources:
+injection-suspicious = ⚠ Possible prompt injection
+injection-quarantined = ⛔ Quarantined
+injection-found = Instruction-like text: { $snippet }
+injection-quarantine = Quarantine
+injection-release = Release
+injection-quarantine-hint = Quarantined sources are left out of the prompt from the next message on
+verify-answer = Double-check this answer with a second model
+verify-running = Fact-checking the answer…
+verify-title = Checked by { $model }: { $totals }
+verify-totals = { $supported } supported, { $disputed } disputed, { $unverifiable } unverifiable
+verify-supported = Supported
+verify-disputed = Disputed
+verify-unverifiable = Could not be verified
+verify-ungrounded = Checked without web search, from the model's own knowledge
+verify-no-claims = No factual claims to check
+verify-invalid = The verification could not be read
+verify-model = Checking model
+verify-same-model = Same as the answer
+verify-grounding = Let the checking model search the web
+verify-grounding-help = Grounds the verdicts in Google Search results and lists the pages they rest on
+chatbox-hint = Ask me anything…
+rtl-preview-hint = How the right-to-left text reads, the chatbox itself shows characters in typing order
+persona-switched = Switched to persona { $persona }
+persona-removed = Persona removed
+last-message-you = You: { $content }
+suggestion-fact-title = Tell me a fun fact
+suggestion-fact-subtitle = about the Roman empire
+suggestion-code-title = Show me a code snippet
+suggestion-code-subtitle = of a web server in Rust
+suggestion-joke-title = Tell me a joke
+suggestion-joke-subtitle = about crabs
+suggestion-ideas-title = Give me ideas
+suggestion-ideas-subtitle = for a birthday present
+
+## Find in chat
+
+find-hint = Find in chat…
+find-match-case = Match case
+find-regex = Use regular expression
+find-invalid-pattern = Invalid pattern
+find-position = { $current } of { $total }
+find-previous = Previous match (Shift+Enter)
+find-next = Next match (Enter)
+find-close = Close (Esc)
+
+## Timeline
+
+timeline-earlier = Earlier
+timeline-later = Later
+timeline-pick-day = Click a day to see what you talked about.
+timeline-no-messages = No messages on this day.
+
+## Search
+
+search-title = 🔍 Search all chats
+search-hint = Search messages…
+search-hint-semantic = Describe what you're looking for, then press Enter…
+search-hint-files = File name or text inside the file…
+search-syntax-help =
+    Filters: model:<name> has:attachment after:<date> before:<date> tag:<name>
+    Dates can be 2024, 2024-06 or 2024-06-15. `after` includes the date, `before` excludes it.
+search-semantic = 🧠 Semantic
+search-semantic-help = Find related conversations by meaning. With the Gemini embedder, message text is sent to the API to build the index.
+search-files = 📎 Files
+search-files-help = Find chats by attached file name or text inside attached files
+search-save = 💾 Save
+search-save-help = Show chats matching this query as a folder in the sidebar
+search-saved-name = Saved Search
+search-saved = Search saved to the sidebar
+search-indexing = Indexing { $indexed }/{ $total } messages
+search-after = After
+search-before = Before
+search-any-model = Any model
+search-any-chat = Any chat
+search-searching = Searching…
+search-similarity = Similarity to the query
+search-match-count =
+    { $count ->
+        [one] { $count } match
+       *[other] { $count } matches
+    }
+
+## Prompt library
+
+prompt-library-new = ➕ New Prompt
+prompt-library-new-name = New Prompt
+prompt-library-versions =
+    { $count ->
+        [one] { $count } version
+       *[other] { $count } versions
+    }
+prompt-library-remove = Remove prompt
+prompt-library-select = Select or create a prompt on the left.
+prompt-library-name-hint = Prompt name
+prompt-library-text-hint = System prompt
+prompt-library-save-version = 💾 Save Version
+prompt-library-discard = Discard
+prompt-library-history = History ({ $count })
+prompt-library-restore = Restore
+prompt-library-restore-hint = Load this version into the editor
+prompt-library-ab = A/B Test
+prompt-library-ab-help = Run the same input against two versions or models and pick the better answer.
+prompt-library-test-input = Test input
+prompt-library-run = ▶ Run
+prompt-library-prefer = 👍 Prefer { $side }
+prompt-library-tie = Tie
+prompt-library-results = Results ({ $count })
+
+## Personas and snippets
+
+personas-help = Personas bundle an avatar, system prompt, model, inference preset and voice.
+persona-new-name = New Persona
+persona-avatar = Avatar (emoji)
+persona-name-hint = Persona name
+persona-remove = Remove persona
+persona-add = ➕ Add Persona
+persona-voice = Voice
+persona-voice-default = System default
+snippets-help = Shortcuts expand into longer text when followed by a space in the composer.
+snippets-shortcut = Shortcut
+snippets-expansion = Expansion
+snippets-expansion-hint = Text to insert
+snippets-remove = Remove snippet
+
+## Tray
+
+tray-show = Show GeminiD
+tray-quick-ask = Quick Ask…
+tray-voice-mode = Toggle Voice Mode
+tray-quit = Quit
+
+## Appearance
+
+theme-mode = Mode
+theme-mode-auto = Auto
+theme-mode-light = ☀ Light
+theme-mode-dark = 🌙 Dark
+theme-accent = Accent color
+theme-code-blocks = Code blocks
+theme-file = Theme file
+theme-reload = Reload themes from disk
+theme-open-folder = Open themes folder
+theme-file-help = Theme files are .toml or .json with `name`, `dark` and hex colors for `accent`, `text`, `window`, `panel`, `extreme_background`, `faint_background`, `code_background` and `hyperlink`.
+theme-high-contrast = High contrast
+theme-high-contrast-help = Black and white colors with outlined widgets
+theme-thick-focus = Thick focus outline
+theme-thick-focus-help = Make the outline around the focused widget easier to see when using the keyboard
+theme-reduced-motion = Reduce motion
+theme-reduced-motion-help = Turn off animations, the animated thinking indicator and the snow
+reading-enabled = Reading aids for responses
+reading-enabled-help = Use the font and spacing below for assistant messages. Code blocks are not affected
+reading-font = Font file
+reading-font-hint = e.g. OpenDyslexic-Regular.otf
+reading-line-height = Line height
+reading-letter-spacing = Letter spacing
+fonts-fallbacks = Fallback fonts, tried in order for characters the bundled fonts don't have:
+fonts-raise = Try this font earlier
+fonts-missing = File not found
+fonts-path-hint = Path to a .ttf, .otf or .ttc file
+fonts-add = Add
+fonts-system = Use installed CJK and emoji fonts
+fonts-system-help = Look for Chinese, Japanese, Korean and emoji fonts in the usual system folders and try them last
+fonts-bundled-cjk = A bundled Noto Sans CJK subset is tried after all of these
+
+spellcheck-enabled = Check spelling in the chatbox
+spellcheck-enabled-help = Underline misspelled words. Right-click a word for suggestions
+spellcheck-language = Dictionary
+spellcheck-folder = Dictionary folder
+spellcheck-folder-hint = Folder with .aff and .dic hunspell files
+spellcheck-ignored =
+    { $count ->
+        [one] { $count } word added to the dictionary
+       *[other] { $count } words added to the dictionary
+    }
+spellcheck-clear = Clear
+spellcheck-reload = Reload dictionaries
+spellcheck-no-suggestions = No suggestions
+spellcheck-add-word = Add to dictionary
+
+plugins-unsupported = This build doesn't include plugin support, rebuild with the `plugins` feature
+plugins-folder = Plugins are loaded from { $path }
+plugins-rescan = Rescan
+plugins-none = No plugins found
+plugins-renderer = Renders code blocks of this language
+plugins-exporter = Adds an export format
+plugins-permissions = Requested permissions, only the checked ones are allowed:
+plugins-host = Connect to { $host }
+plugins-path = Read and write { $path }
+scripting-unsupported = This build doesn't include scripting support, rebuild with the `scripting` feature
+scripting-enabled = Run scripts
+scripting-enabled-help = Calls on_before_send, on_response and on_chat_created in the Rhai scripts of the scripts folder. Changed scripts are reloaded automatically.
+scripting-folder = Scripts are loaded from { $path }
+scripting-open-folder = Open folder
+scripting-none = No scripts found
+schedule-new = New schedule
+schedule-add = New Schedule
+schedule-none = No scheduled prompts yet
+schedule-remove = Remove schedule
+schedule-name = Schedule name
+schedule-enabled = Enabled
+schedule-enabled-help = Sends the prompt on schedule while the app is running, runs missed while it was closed are made up for once
+schedule-cron = Schedule
+schedule-cron-help = Minute, hour, day of month, month and day of week, like in cron. `0 8 * * 1-5` is 8:00 on weekdays.
+schedule-next-run = Next run: { $time }
+schedule-last-run = Last run: { $time }
+schedule-disabled = Disabled
+schedule-chat = Chat
+schedule-new-chat = New chat on the first run
+schedule-prompt = Prompt
+schedule-custom-text = Custom text
+schedule-text-hint = What to send on every run
+schedule-files = Attachments:
+schedule-add-files = 📎 Add files
+schedule-notify = Always notify
+schedule-notify-help = Shows a desktop notification for the answer even while the window is focused
+schedule-run-now = ▶ Run now
+schedule-finished = The scheduled prompt "{ $name }" got an answer
+knowledge-new = New collection
+knowledge-add = New Collection
+knowledge-none = No knowledge collections yet
+knowledge-remove = Remove collection
+knowledge-name = Collection name
+knowledge-help = Folders, files and web pages are split into chunks and embedded with Gemini. Chats that use the collection get the closest chunks with every prompt. Needs an API key.
+knowledge-sources = Sources:
+knowledge-add-folder = 📁 Add folder
+knowledge-add-files = 📄 Add files
+knowledge-add-repository = 🗂 Add repository
+knowledge-add-repository-help = Reads the code of a project folder, skipping what .gitignore lists. Answers cite files by line
+knowledge-url-hint = https://…
+knowledge-add-url = 🌐 Add page
+knowledge-chunks =
+    { $count ->
+        [one] { $count } chunk
+       *[other] { $count } chunks
+    }
+knowledge-status =
+    { $count ->
+        [one] { $count } chunk, indexed { $time }
+       *[other] { $count } chunks, indexed { $time }
+    }
+knowledge-not-indexed = Not indexed yet
+knowledge-up-to-date = Up to date, checked { $time }
+knowledge-pending =
+    { $count ->
+        [one] { $count } file changed since the indexing
+       *[other] { $count } files changed since the indexing
+    }
+knowledge-watch = Keep up to date
+knowledge-watch-help = Checks the files every 30 seconds and embeds the changed ones in the background
+knowledge-embedder = Embeddings:
+knowledge-embedder-help = What turns the documents into vectors. Switching re-embeds the whole collection on the next update.
+embedder-gemini = Gemini API
+embedder-local = Local model (offline)
+embedder-local-unsupported = This build doesn't include local embeddings, rebuild with the `local-embeddings` feature
+knowledge-update = Update
+knowledge-update-help = Embeds only the files that changed
+knowledge-debug = 🔍 Retrieval debug
+knowledge-debug-help = Shows which chunks a question gets, with their scores. Tune them in the settings.
+knowledge-debug-hint = A question to try
+knowledge-debug-run = Search
+knowledge-debug-none = No chunks found
+knowledge-debug-source = Source
+knowledge-debug-similarity = Similarity
+knowledge-debug-keywords = Keywords
+knowledge-debug-score = Score
+knowledge-debug-injected = In prompt
+retrieval-top-k = Chunks per prompt
+retrieval-top-k-help = How many of the best matching chunks go into the prompt
+retrieval-min-score = Minimum score
+retrieval-min-score-help = Chunks scoring lower are left out, even when fewer are found
+retrieval-keyword-weight = Keyword weight
+retrieval-keyword-weight-help = How much exact word matches count next to meaning. Raise it for names, identifiers and codes
+knowledge-index = Index
+knowledge-reindex = Index again
+knowledge-progress = { $done } / { $total } chunks
+knowledge-nothing-to-index = The sources have no text to index
+knowledge-cancelled = Indexing was cancelled
+knowledge-searching = Searching the knowledge collection…
+knowledge-memory-empty = Nothing in the chat was worth remembering
+knowledge-citations =
+    { $count ->
+        [one] 📚 { $count } source
+       *[other] 📚 { $count } sources
+    }
+knowledge-open-source = Open the source
+knowledge-off = None
+embedding-open = 🧪 Embedding Playground
+embedding-title = Embedding Playground
+embedding-help = Embeds every line with the Gemini embedding model to compare how close texts are in meaning and group them. Needs an API key.
+embedding-items-hint = One text per line
+embedding-task = Optimize for
+embedding-task-similarity = Similarity
+embedding-task-clustering = Clustering
+embedding-task-classification = Classification
+embedding-task-document = Search documents
+embedding-task-query = Search queries
+embedding-run = Embed
+embedding-summary = { $count } vectors with { $dimensions } dimensions
+embedding-copy = 🗐 Copy as JSON
+embedding-similarity = Cosine similarity
+embedding-clusters = Clusters
+embedding-cluster-count = Groups:
+embedding-cluster = Group
+embedding-group = Group { $number }
+batch-open = 📋 Batch Run
+batch-title = Batch run
+batch-input = Input file
+batch-input-hint = CSV with a header row, or JSON lines
+batch-output = Results file
+batch-output-hint = .jsonl or .csv
+batch-model = Model
+batch-rate = Requests per minute
+batch-retries = Retries per row
+batch-template = Prompt template, { "{{" }column{ "}}" } is replaced by the row's value:
+batch-from-prompt = From a saved prompt
+batch-template-hint = Summarize this review in one sentence: { "{{" }review{ "}}" }
+batch-system-prompt = System prompt (optional)
+batch-run = ▶ Run
+batch-cancel = ⏹ Cancel
+batch-progress = { $done } of { $total } rows done
+batch-failed =
+    { $count ->
+        [one] { $count } row failed, see the error column
+       *[other] { $count } rows failed, see the error column
+    }
+batch-tokens = Tokens used: { $count }
+batch-eta = About { $minutes } min left
+batch-finished = Finished
+batch-cancelled = Cancelled, the finished rows were saved
+batch-no-output = Choose a results file
+batch-empty = The input file has no rows
+batch-missing-columns = The input file has no { $columns } column
+batch-api = Use the Batch API
+batch-api-hint = Half the price of regular requests. The whole file is sent as one job, which usually finishes within minutes but may take up to a day.
+batch-api-needs-key = The Batch API needs an API key
+batch-api-waiting = Waiting for the Batch API: { $state }
+batch-no-result = The Batch API sent no result for this row
+batch-rows = Rows
+batch-config-hint = The inference settings of the current chat apply to every row.
+compare-open-window = ⚖ Compare Documents
+compare-title = Compare Documents
+compare-help = Attach two versions of a document, like a contract or a spec, and list what changed between them.
+compare-original = Original
+compare-revised = Revised
+compare-path-hint = Path to the file
+compare-model = Model
+compare-focus = Focus on
+compare-focus-hint = e.g. payment terms and liability
+compare-run = ⚖ Compare
+compare-invalid = The model didn't answer with a comparison
+compare-no-changes = No differences found
+compare-added = Added
+compare-removed = Removed
+compare-changed = Changed
+compare-clause = Clause
+compare-note = Note
+compare-page = p. { $page }
+compare-open = Open
+model-compare-open = 🆚 Compare Models
+model-compare-title = Compare Models
+model-compare-help = Sends the same prompt and files to every model at once, with the system prompt and inference settings of the open chat.
+model-compare-add = Add a model
+model-compare-prompt-hint = Prompt for every model…
+model-compare-attach = Attach files
+model-compare-run = ▶ Send to all
+model-compare-stop = ⏹ Stop
+model-compare-tokens = { $tokens } output tokens
+image-edit-open-window = 🖌 Edit Images
+image-edit-title = Image Editor
+image-edit-help = Describe a change and an image model applies it. Each edit starts from the last result, pick an earlier step to go back and branch from there.
+image-edit-model = Model
+image-edit-open = 📂 Open image…
+image-edit-empty = Open an image, or right-click an image in a chat and choose Edit.
+image-edit-instruction-hint = e.g. make the sky a sunset, remove the car
+image-edit-apply = ✨ Apply edit
+image-edit-steps = Steps
+image-edit-original = Original
+image-edit-step-hint = Show this step, the next edit starts from it
+deeplink-register = Open geminid:// links with this app
+deeplink-register-help = Lets browser extensions and other apps hand prompts over with links like geminid://new?prompt=Hello or geminid://chat/<id>
+deeplink-registered = Registered
+deeplink-unsupported = On macOS the link handler is set up by the app bundle
+deeplink-chat-not-found = The chat from the link doesn't exist anymore
+code-scheme-follow = Follow theme
+code-scheme-dark = Dark
+code-scheme-light = Light
+code-scheme-sepia = Sepia
+
+## Keyboard shortcuts
+
+command-send = Send message
+command-new-chat = New chat
+command-regenerate = Regenerate last response
+command-stop = Stop generating
+command-find-in-chat = Find in chat
+command-global-search = Search all chats
+command-palette = Command palette
+command-settings = Toggle settings
+command-next-model = Next model
+command-previous-model = Previous model
+command-toggle-sidebar = Toggle sidebar
+command-focus-mode = Toggle focus mode
+command-timeline = Toggle timeline
+command-back = Previous chat
+command-forward = Next chat
+command-reopen-closed = Reopen closed chat
+command-logs = Show logs
+command-diagnostics = Diagnostics
+command-statistics = Statistics
+command-tools = Tools
+command-context-caches = Context caches
+logs-title = Logs
+logs-search = Search
+logs-follow = Follow
+logs-export = 💾 Export…
+logs-copy = 🗐 Copy
+logs-open-folder = 📂 Log folder
+logs-empty = Nothing logged that matches
+diagnostics-title = Diagnostics
+diagnostics-run = ⟳ Run again
+diagnostics-running = Checking…
+diagnostics-copy = 🗐 Copy report
+diagnostics-endpoint = API endpoint
+diagnostics-credentials = Credentials
+diagnostics-proxy = Proxy
+diagnostics-latency = Latency
+diagnostics-disk = Disk space
+diagnostics-reachable = { $target } answered in { $ms } ms
+diagnostics-timeout = { $target } didn't answer in time
+diagnostics-no-proxy = No proxy set
+diagnostics-invalid-proxy = invalid proxy URL
+diagnostics-no-api-key = No API key set
+diagnostics-api-key-valid = The API key was accepted
+diagnostics-not-logged-in = Not logged in to Code Assist
+diagnostics-code-assist-valid = Logged in, project { $project }
+diagnostics-rejected = Rejected: { $reason }
+diagnostics-latency-result = Round trip { $min }–{ $max } ms, median { $median } ms
+diagnostics-no-storage = No folder to store the chats in
+diagnostics-disk-result = { $free } free, chats and settings take { $used } in { $path }
+analytics-title = Statistics
+analytics-enabled = Keep local usage statistics
+analytics-enabled-help = Counts which models you use, at what hours and how long they take to answer. Stays on this computer, nothing is sent anywhere
+analytics-empty = Nothing collected yet, statistics appear after the next answer
+analytics-disabled = Collection is off. Turn it on to see your most used models, busiest hours and response times
+analytics-responses =
+    { $count ->
+        [one] { $count } response
+       *[other] { $count } responses
+    }
+analytics-since = since { $date }
+analytics-average-latency = Average response time: { $seconds }s
+analytics-models = Most used models
+analytics-model = Model
+analytics-count = Responses
+analytics-latency = Avg. time
+analytics-errors = Errors
+analytics-hours = Busiest hours
+analytics-hour = { $hour }: { $count }
+analytics-busiest = Busiest around { $hour }
+analytics-clear = Clear statistics
+shortcut-press-key = Press a key…
+shortcut-unbound = Unbound
+shortcut-record-hint = Click, then press the new shortcut. Escape cancels.
+shortcut-conflicts = Also bound to: { $commands }
+shortcut-unbind = Unbind
+shortcut-reset = Reset to default
+shortcut-reset-all = Reset all shortcuts
+vim-mode = Vim-style history navigation
+vim-mode-help = While no text field is focused: j/k move between messages, g/G jump to the first/last one and i focuses the chatbox
+ctrl-enter-sends = Ctrl+Enter sends
+ctrl-enter-sends-help = Enter inserts a newline and Ctrl+Enter sends the message, instead of the Send shortcut and Shift+Enter. Handy with input methods that confirm with Enter
+
+## Onboarding
+
+onboarding-title = Welcome to GeminiD
+onboarding-step = Step { $step } of { $total }
+onboarding-welcome = Let's get you set up
+onboarding-welcome-text = GeminiD talks to Google's Gemini models. It needs a way to authenticate, a default model and, if your network requires it, a proxy. Everything here can be changed later in ⚙ Settings.
+onboarding-auth = How do you want to sign in?
+onboarding-auth-api-key = Gemini API key
+onboarding-auth-api-key-text = A free key from Google AI Studio. Simple to set up and required for semantic search.
+onboarding-auth-google = Google account (Code Assist)
+onboarding-auth-google-text = Log in with your Google account and use the Code Assist quota of one of your Cloud projects.
+onboarding-paste-key = Paste your API key
+onboarding-get-key = Get one at
+onboarding-check-key = Check key
+onboarding-key-valid = ✅ Key works
+onboarding-key-text = The key is stored locally with the rest of the app state. If you are behind a proxy, set it up in the last step first.
+onboarding-login = Log in with Google
+onboarding-login-browser = Finish the login in your browser
+onboarding-project-id = Google Cloud project id
+onboarding-login-text = A browser window opens for the login. Requests are billed against the selected Cloud project.
+onboarding-model = Pick a default model
+onboarding-model-text = New chats start with this model. Flash models are faster and cheaper, Pro models are smarter. You can switch per chat at any time.
+onboarding-network = Network
+onboarding-network-text = Only needed if the Gemini API isn't reachable directly from your network. HTTP and SOCKS5 proxies are supported.
+onboarding-skip = Skip setup
+onboarding-finish = Finish
+onboarding-next = Next ⏵
+onboarding-back = ⏴ Back
+onboarding-finished = You can change all of this later in ⚙ Settings
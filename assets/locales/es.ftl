@@ -0,0 +1,1135 @@
+### GeminiD UI strings, Spanish
+
+## Common
+
+yes = Sí
+no = No
+cancel = Cancelar
+confirm = Confirmar
+close = Cerrar
+delete = Eliminar
+remove = Quitar
+add = ➕ Añadir
+clear = Vaciar
+edit = Editar
+export = Exportar
+export-cancelled = Exportación cancelada
+no-file-selected = Ningún archivo seleccionado
+import = Importar
+save = Guardar
+load = Cargar
+none = Ninguno
+you = Tú
+new-chat = Nuevo chat
+no-matches = Sin coincidencias
+chat-count =
+    { $count ->
+        [one] { $count } chat
+       *[other] { $count } chats
+    }
+message-count =
+    { $count ->
+        [one] { $count } mensaje
+       *[other] { $count } mensajes
+    }
+token-count =
+    { $count ->
+        [one] { $count } token
+       *[other] { $count } tokens
+    }
+date-long = %A, %-d de %B de %Y
+date-short = %-d %b %Y
+date-day = %a, %d/%m/%Y
+date-time = %d/%m/%Y %H:%M
+time-just-now = ahora mismo
+time-minutes-ago =
+    { $count ->
+        [one] hace { $count } minuto
+       *[other] hace { $count } minutos
+    }
+time-hours-ago =
+    { $count ->
+        [one] hace { $count } hora
+       *[other] hace { $count } horas
+    }
+time-days-ago =
+    { $count ->
+        [one] hace { $count } día
+       *[other] hace { $count } días
+    }
+timestamps = Hora de los mensajes
+timestamps-hidden = Oculta
+timestamps-relative = Relativa
+timestamps-absolute = Fecha y hora
+timestamps-utc = Mostrar horas en UTC
+timestamps-utc-help = Usar UTC en lugar de la zona horaria del sistema para las horas de los mensajes
+
+## Settings
+
+settings-reset-title = Restablecer ajustes
+settings-reset-confirm = ¿Seguro que quieres restablecer los ajustes globales? ¡Esta acción no se puede deshacer!
+settings-authentication = Autenticación
+settings-auth-method = Método
+settings-api-key = Clave de API
+settings-api-key-hint = Introduce tu clave de API de Google AI Studio
+settings-login-google = 🔑 Iniciar sesión con Google
+settings-logged-in = ✅ Sesión iniciada
+settings-logout = 🚪 Cerrar sesión
+settings-logged-out = Sesión cerrada y caché borrada.
+settings-login-success = ¡Inicio de sesión con Google correcto!
+settings-project = Proyecto
+settings-select-project = Selecciona un proyecto
+settings-no-projects = No se encontraron proyectos o se están cargando...
+settings-model = Modelo
+settings-default-model = Modelo predeterminado para chats nuevos
+settings-inherit-model = Heredar de los chats
+settings-inherit-model-help = Aplicar los cambios de modelo hechos en los chats
+settings-behavior = Comportamiento
+settings-streaming = Transmitir respuesta
+settings-streaming-help = Recibir la respuesta mientras se genera. Si se desactiva, se espera a la respuesta completa antes de mostrarla
+settings-persist-thoughts = Conservar razonamientos en el contexto
+settings-persist-thoughts-help = Los razonamientos del modelo se añaden al contexto para las siguientes peticiones. Atención: el consumo de tokens aumentará rápidamente
+settings-file-api = Subir archivos (File API)
+settings-file-api-help = Los archivos se suben a los servidores de Google (File API) en lugar de enviarse en base64 dentro de la petición. Los archivos subidos son temporales y solo tú puedes acceder a ellos.
+settings-voice-mode = Modo de voz
+settings-voice-mode-help = Leer en voz alta cada respuesta en cuanto termine de generarse
+settings-notifications = Notificaciones de escritorio
+settings-notifications-help = Mostrar una notificación cuando termine una respuesta con la ventana en segundo plano o minimizada
+settings-close-to-tray = Cerrar a la bandeja
+settings-close-to-tray-help = Al cerrar la ventana se oculta en la bandeja del sistema y la aplicación sigue en marcha. Usa Salir en el menú de la bandeja para cerrarla
+settings-snippets = Fragmentos
+settings-appearance = Apariencia
+settings-language = Idioma
+settings-language-system = Del sistema ({ $language })
+settings-rtl-layout = Diseño de derecha a izquierda
+settings-rtl-layout-help = Coloca la barra lateral a la derecha y los botones del cuadro de texto después del texto, para árabe, hebreo, persa y otros idiomas de derecha a izquierda
+settings-reading = Lectura
+settings-fonts = Fuentes
+settings-spellcheck = Ortografía
+settings-retrieval = Búsqueda de conocimiento
+settings-safety = Filtros de seguridad
+settings-injection = Inyección de prompts
+settings-verify = Verificación de respuestas
+settings-cache = Caché de contexto
+settings-retry = Reintentos
+settings-summarize = Resumen del historial
+settings-plugins = Plugins
+settings-scripting = Scripts
+settings-shortcuts = Atajos de teclado
+settings-misc = Otros
+settings-proxy = Usar proxy
+settings-proxy-help = Enviar las peticiones a la API de Gemini a través del proxy
+settings-editor-command = Comando del editor
+settings-editor-command-help = Abre los archivos citados en la línea citada, se sustituyen {"{"}path{"}"} y {"{"}line{"}"}. Déjalo vacío para usar la aplicación predeterminada
+settings-snow = ¡Que nieve!
+settings-ui-scale = Escala de la interfaz
+settings-ui-scale-help = Ajustar el tamaño general de la interfaz
+settings-reset-label = Restablecer los ajustes globales a los valores predeterminados
+settings-reset = Restablecer
+settings-save-load-label = Guardar y cargar los ajustes como JSON
+
+## Main window
+
+toggle-sidebar = Mostrar u ocultar la barra lateral
+back = Atrás
+forward = Adelante
+reopen-closed-chat = Reabrir chat cerrado
+no-closed-chats = No hay chats cerrados recientemente
+timeline = Cronología
+settings = Ajustes
+token-count-help = Número estimado de tokens en el contexto
+token-count-limit = { $count } tokens con el mensaje que se está escribiendo, el { $percent } % de los { $limit } que admite el modelo
+exit-focus-mode = Salir del modo concentración
+dropping-files = Soltando archivos:
+notification-ready = Respuesta lista
+notification-failed = Error en la generación
+notification-open-chat = Abrir chat
+voice-mode-on = Modo de voz activado
+voice-mode-off = Modo de voz desactivado
+streaming-enabled = Transmisión activada
+streaming-disabled = Transmisión desactivada
+chat-detached = Este chat está abierto en otra ventana.
+chat-bring-back = Traerlo de vuelta
+split-close = Cerrar la vista dividida
+split-swap = Intercambiar con la vista principal
+remove-chat-title = Eliminar chat
+remove-chat-confirm =
+    ¿Seguro que quieres eliminar este chat? Podrás reabrirlo más tarde{ $shortcut }.
+    Mantén Mayús pulsada para omitir este aviso.
+remove-chat-named = Eliminar el chat «{ $name }»
+remove-chat = Eliminar chat
+bulk-delete-title = Eliminar chats
+bulk-delete-summary = Se eliminarán { $chats } con { $messages }.
+bulk-archive-title = Archivar chats
+bulk-archive-summary = Se archivarán { $chats } con { $messages }.
+bulk-unarchive-title = Desarchivar chats
+bulk-unarchive-summary = Se desarchivarán { $chats } con { $messages }.
+bulk-tag-title = Etiquetar chats
+bulk-tag-summary = Se añadirá la etiqueta «{ $tag }» a { $chats } con { $messages }.
+bulk-untag-summary = Se quitará la etiqueta «{ $tag }» de { $chats } con { $messages }.
+bulk-move-title = Mover chats
+bulk-move-summary = Se moverán { $chats } con { $messages } a «{ $folder }».
+bulk-export-title = Exportar chats
+bulk-export-summary = Se exportarán { $chats } con { $messages }.
+bulk-tag-hint = Añadir o quitar una etiqueta
+selected-count = { $count } seleccionados
+archive = Archivar
+archive-button = 📦 Archivar
+unarchive = Desarchivar
+no-tags = Aún no hay etiquetas
+tag-delete = Eliminar etiqueta
+tag-new-hint = Nueva etiqueta
+top-level = Nivel superior
+move-to-folder = Mover a carpeta
+clear-selection = Quitar selección
+chat-edit-persona = Persona
+chat-edit-tags = Etiquetas
+chat-edit-knowledge = Conocimiento
+chat-edit-knowledge-help = Los fragmentos relevantes de la colección se añaden a cada prompt y la respuesta los cita como fuentes
+chat-edit-remember = Recordar este chat en:
+chat-edit-remember-button = 🧠 Recordar
+chat-edit-remember-help = Extrae los hechos y decisiones duraderos de este chat y los añade a la colección, para que los chats posteriores los conozcan. Recordar de nuevo los reemplaza
+chat-edit-manage-tags = Gestionar etiquetas
+chat-edit-model = Modelo
+chat-edit-export-label = Exportar el historial del chat a un archivo
+chat-edit-export-format = Formato de exportación
+save-as = Guardar como…
+export-with-plugin = Guardar como { $name }…
+tab-chats = Chats
+tab-prompts = Prompts
+tab-schedules = Programados
+tab-knowledge = Conocimiento
+tab-videos = Vídeos
+no-recent-messages = Sin mensajes recientes
+new-chat-button = ➕ Nuevo chat
+new-chat-hint = Crear un chat nuevo
+new-folder-button = 📁 Nueva carpeta
+new-folder-hint = Crear una carpeta nueva. Arrastra chats sobre ella para moverlos
+folder-remove-hint = Eliminar carpeta, su contenido sube un nivel
+folder-edit-hint = Editar carpeta
+folder-new-chat-hint = Nuevo chat en esta carpeta
+folder-new-name = Nueva carpeta
+folder-name-hint = Nombre de la carpeta
+folder-template = Plantilla predeterminada
+folder-template-help = Los chats nuevos de esta carpeta y sus subcarpetas empiezan con estos ajustes.
+folder-template-enable = Usar una plantilla
+project-title = Proyecto
+project-help = Los chats de esta carpeta y sus subcarpetas reciben esto en cada solicitud.
+project-enable = Convertir en proyecto
+project-instructions-hint = Instrucciones para cada chat del proyecto
+project-knowledge = Conocimiento
+drop-to-top-level = Suelta aquí para mover al nivel superior
+archived-header = 📦 Archivados ({ $count })
+remove-saved-search = Eliminar búsqueda guardada
+no-matching-chats = No hay chats que coincidan
+open-in-split-view = ◫ Abrir en vista dividida
+open-in-new-window = 🗗 Abrir en una ventana nueva
+
+## Command palette
+
+palette-hint = Escribe un comando, chat o modelo…
+palette-nothing = No hay coincidencias
+palette-enable-streaming = Activar la transmisión
+palette-disable-streaming = Desactivar la transmisión
+palette-switch-model = Cambiar de modelo: { $model }
+palette-use-prompt = Usar como prompt de sistema: { $prompt }
+palette-prompt-applied = Prompt de sistema cambiado a { $prompt }
+palette-setting = Ajuste
+palette-model = Modelo
+palette-prompt = Prompt
+palette-chat = Chat
+
+## Chat
+
+generation-time = Tiempo de generación
+token-usage = Entrada: { $input } / Salida: { $output } / Total: { $total }
+retry-generation = 🔄 Reintentar generación
+retry-generation-hint = Intenta generar la respuesta de nuevo. Comprueba que la clave de API es válida y que la conexión es estable.
+stream-degraded = ⚠ Transmisión degradada, el contenido puede estar incompleto
+stream-degraded-hint = Partes de la respuesta no se pudieron leer y se omitieron, o la transmisión se cortó, a menudo por un proxy. Se muestra el resto de la respuesta
+answer-truncated = ✂ La respuesta se detuvo en el límite de tokens de salida
+answer-interrupted = ⏹ Detenida antes de terminar la respuesta
+answer-interrupted-hint = Detuviste la generación, se conserva el texto recibido hasta entonces
+continue-generating = ⏩ Seguir generando
+continue-generating-hint = Pedir al modelo que siga donde se detuvo, el resto se añade a esta respuesta
+prepend-hint = Texto al inicio de la respuesta…
+regenerate-button = 🔄 Regenerar
+regenerate-hint = Generar la respuesta de nuevo, el modelo continuará tras el texto añadido
+regenerate-with = Regenerar con…
+regenerate-with-hint = Generar esta respuesta de nuevo con otro modelo, el chat conserva su modelo
+edit-message-button = ✏ Editar
+edit-message-hint = Editar el mensaje en el contexto sin regenerarlo
+cancel-button = ❌ Cancelar
+copied = ¡Copiado!
+copy-message = Copiar mensaje
+read-aloud-hint = Leer el mensaje en voz alta. Clic derecho para repetir
+regenerate = Regenerar
+pick-files = Elegir archivos
+attach-url = Adjuntar desde un enlace
+attach-url-hint = https://example.com/paper.pdf
+attach-url-download = Descargar y adjuntar
+attach-url-cancel = Cancelar
+attach-url-downloading = Descargando…
+attach-url-failed = No se pudo descargar el archivo: { $error }
+bundle-attach-hint = Adjuntar los archivos de texto de una carpeta
+bundle-title = Adjuntar carpeta
+bundle-change = Cambiar…
+bundle-include = Incluir
+bundle-exclude = Excluir
+bundle-globs-help = Patrones glob relativos a la carpeta, uno por línea, p. ej. src/**/*.rs. Sin patrones de inclusión se toman todos los archivos.
+bundle-max-size = Tamaño máx. de archivo
+bundle-gitignore = Omitir lo que excluye .gitignore
+bundle-scanning = Leyendo la carpeta y contando tokens…
+bundle-rescan = 🔄 Aplicar filtro
+bundle-summary =
+    { $files ->
+        [one] { $files } archivo, { $size }
+       *[other] { $files } archivos, { $size }
+    }
+bundle-tokens = ≈ { $tokens } tokens con el modelo de este chat
+bundle-tokens-unknown = No se pudieron contar los tokens.
+bundle-skipped = Omitidos: { $large } demasiado grandes, { $binary } que no son texto
+bundle-files = Archivos del paquete
+bundle-attach = 📎 Adjuntar como un solo archivo de texto
+bundle-attach-stale = Aplica primero el filtro cambiado
+archive-title = Extraer { $name }
+archive-help = Elige los archivos que se adjuntan en lugar del archivo comprimido. Los que el modelo no puede leer aparecen en gris.
+archive-reading = Leyendo el archivo comprimido…
+archive-select-supported = Seleccionar los legibles
+archive-select-none = No seleccionar ninguno
+archive-unsupported = El modelo no puede leer este tipo de archivo
+archive-extracting = Extrayendo…
+archive-extract =
+    { $count ->
+        [one] Extraer y adjuntar { $count } archivo
+       *[other] Extraer y adjuntar { $count } archivos
+    }
+archive-keep = Mantener el archivo comprimido
+upload-progress = { $sent } / { $total } MB
+upload-uploading = Subiendo…
+upload-failed = Error
+attachment-missing = (ARCHIVO NO ENCONTRADO)
+attachment-info-missing = { $name }, archivo no encontrado
+attachment-info-uploading = { $name }, subiendo
+attachment-info-uploading-percent = { $name }, subido el { $percent } %
+attachment-info-failed = { $name }, error: { $error }
+attachment-info-expired = { $name }, la subida caducó
+attachment-remove-hint = Pulsa Supr para quitarlo
+upload-cancel = Cancelar la subida, el mensaje se envía sin este archivo
+upload-cancelling = Cancelando…
+upload-expires = caduca en { $hours } h { $minutes } min
+upload-expires-hint = La File API borra las subidas a las 48 horas
+upload-expired = La subida caducó
+upload-reupload = Volver a subir
+upload-reupload-hint = Subir el archivo de nuevo para que los próximos mensajes puedan seguir usándolo
+upload-reupload-unavailable = Activa las subidas por la File API con una clave de API para subir archivos
+screenshot-capture = Capturar una región de la pantalla
+screenshot-help = Arrastra para seleccionar una región · Enter captura toda la pantalla · Esc cancela
+screenshot-failed = No se pudo capturar la pantalla: { $error }
+record-start = Grabar con el micrófono
+record-stop = Detener y adjuntar la grabación
+record-discard = Descartar la grabación
+record-failed = No se pudo grabar: { $error }
+youtube-detected =
+    { $count ->
+        [one] ▶ El mensaje tiene un enlace de YouTube, el modelo puede ver el vídeo por sí mismo.
+       *[other] ▶ El mensaje tiene { $count } enlaces de YouTube, el modelo puede ver los vídeos por sí mismo.
+    }
+youtube-attach = Adjuntar como vídeo
+youtube-attach-hint = Envía el vídeo en lugar del enlace. Haz clic derecho en el adjunto para enviar solo una parte.
+youtube-keep-text = Dejar como texto
+youtube-clip = Parte del vídeo
+youtube-clip-start = Inicio
+youtube-clip-end = Fin
+youtube-clip-invalid = El fin tiene que ir después del inicio.
+improve-prompt = Mejorar el prompt
+improve-prompt-failed = No se pudo mejorar el prompt: { $error }
+preview-request = Ver la solicitud
+param-override-hint = Cambiar la temperatura o el presupuesto de razonamiento del próximo mensaje
+param-override-active = El próximo mensaje se envía con ajustes cambiados
+param-override-title = Solo el próximo mensaje:
+param-override-help = Se aplica solo a la próxima solicitud, los ajustes de inferencia del chat no cambian
+param-override-temperature = Temperatura
+param-override-thinking = Presupuesto de razonamiento
+param-override-clear = Descartar el cambio
+presets-pick = Plantillas…
+presets-empty = Aún no hay plantillas guardadas
+presets-name-hint = Nombre de la plantilla
+presets-save = 💾 Guardar plantilla
+presets-save-hint = Guardar el prompt de sistema con este nombre
+presets-overwrite = Ya existe un prompt guardado con este nombre, guardar le añade una versión nueva
+presets-manage = Gestionar plantillas
+presets-remove = Eliminar plantilla
+presets-export = Exportar plantillas…
+inference-settings = Ajustes de inferencia
+inference-enable = Activar
+inference-reset = Restablecer
+inference-reset-hint = Volver al valor predeterminado
+inference-reset-all = Restablecer ajustes
+system-prompt-title = Prompt de sistema
+system-prompt-help = Un prompt de sistema permite indicar al modelo un comportamiento propio.
+system-prompt-enable = Usar un prompt de sistema propio
+system-prompt-hint = Un prompt de sistema para el modelo. Por ejemplo: «Eres un asistente útil especializado en escribir código Rust.»
+few-shot-title = Ejemplos few-shot
+few-shot-help = Los pares de entrada y salida se envían antes de la conversación, como si el modelo ya los hubiera respondido.
+few-shot-input = Entrada
+few-shot-output = Salida
+few-shot-input-hint = Mensaje del usuario
+few-shot-output-hint = Respuesta del modelo
+few-shot-remove = Quitar ejemplo
+thinking-title = Razonamiento
+thinking-help = Activa el razonamiento nativo de los modelos Gemini 2.5 para mejorar sus conclusiones.
+thinking-include = Incluir resúmenes del razonamiento
+thinking-budget = Fijar presupuesto de razonamiento
+thinking-budget-help = Presupuesto de tokens para razonar. -1 para dinámico, 0 para desactivarlo.
+temperature-title = Temperatura
+temperature-help = Controla la aleatoriedad de la salida. Los valores altos (p. ej., 1.0) dan respuestas más creativas y los bajos (p. ej., 0.2) las hacen más deterministas.
+max-tokens-title = Máximo de tokens de salida
+max-tokens-help = Número máximo de tokens que se generan en la respuesta.
+top-k-title = Top-K
+top-k-help = Cambia cómo elige el modelo los tokens de salida. Un valor menor limita el muestreo a un conjunto más pequeño de los tokens más probables.
+top-p-title = Top-P
+top-p-help = Cambia cómo elige el modelo los tokens de salida, muestreando de una distribución de probabilidad acumulada. Usa Top-K o Top-P, no ambos.
+stop-title = Secuencia de parada
+stop-help = Hasta 5 secuencias de caracteres que detienen la generación.
+preview-title = Vista previa de la solicitud
+preview-loading = Preparando la solicitud…
+preview-model = Modelo: { $model }
+preview-copy = 🗐 Copiar como JSON
+preview-no-count = Contar tokens requiere una clave de API de Gemini
+preview-citations =
+    { $count ->
+        [one] { $count } fragmento de conocimiento añadido a la instrucción del sistema
+       *[other] { $count } fragmentos de conocimiento añadidos a la instrucción del sistema
+    }
+preview-thoughts-kept = Los pensamientos se envían como parte del historial
+preview-thoughts-dropped = Los pensamientos se omiten del historial
+preview-system = Instrucción del sistema
+preview-examples = Ejemplos few-shot
+preview-history = Historial
+preview-prompt = Mensaje nuevo
+preview-role-user = Usuario
+preview-role-model = Modelo
+preview-inline-file = 📎 { $mime }, { $size } en línea
+preview-uploaded-file = 🔗 { $mime }, subido como { $uri }
+preview-empty = Ninguno
+preview-config = Configuración de generación
+preview-safety = Ajustes de seguridad
+preview-tools = Herramientas:
+safety-help = Qué probabilidad de ser dañina puede tener una respuesta antes de bloquearla, por categoría. Google siempre bloquea parte del contenido, sin importar estos ajustes
+safety-override = Sustituir los filtros de seguridad generales
+safety-override-help = Las solicitudes con esta configuración del modelo usan los filtros de seguridad de los ajustes
+grounding-title = Búsqueda de Google
+grounding-help = Permite al modelo buscar en la web y cita bajo la respuesta las páginas que usó.
+grounding-enabled = Basar las respuestas en la Búsqueda de Google
+grounding-sources =
+    { $count ->
+        [one] { $count } fuente web
+       *[other] { $count } fuentes web
+    }
+grounding-queries = Búsquedas: { $queries }
+url-context-title = Contexto de URL
+url-context-help = Google descarga los enlaces de tu mensaje y el modelo lee las páginas, en lugar de recibirlos como texto. Las páginas leídas aparecen bajo la respuesta.
+url-context-enabled = Leer las páginas enlazadas
+url-context-pages =
+    { $count ->
+        [one] { $count } página leída
+       *[other] { $count } páginas leídas
+    }
+structured-output = Salida estructurada
+structured-help = Hace que el modelo responda en JSON, con la forma de un esquema si das uno. Las respuestas JSON se muestran como un árbol plegable.
+structured-enabled = Responder en JSON
+structured-schema-hint = Esquema JSON de la respuesta, p. ej. {"{"} "type": "object", "properties": {"{"} … {"}"} {"}"}
+structured-schema-valid = ✔ Las respuestas seguirán este esquema
+structured-schema-empty = Sin esquema vale cualquier JSON
+structured-invalid-schema = El esquema no es JSON válido: { $error }
+structured-schema-not-object = El esquema debe ser un objeto JSON
+structured-title = {"{ }"} JSON
+structured-copy = 📋 Copiar JSON
+structured-copy-path = Copiar ruta
+structured-copy-value = Copiar valor
+structured-valid = ✔ Cumple el esquema
+structured-invalid =
+    { $count ->
+        [one] ✖ { $count } lugar no cumple el esquema
+       *[other] ✖ { $count } lugares no cumplen el esquema
+    }
+structured-issues = Incumplimientos del esquema
+structured-not-json = ✖ La respuesta no es JSON válido: { $error }
+structured-issue-type = se esperaba { $expected }, se obtuvo { $actual }
+structured-issue-required = falta la propiedad obligatoria "{ $name }"
+structured-issue-unexpected = el esquema no permite esta propiedad
+structured-issue-enum = no es uno de { $allowed }
+structured-issue-minimum = menor que el mínimo de { $limit }
+structured-issue-maximum = mayor que el máximo de { $limit }
+structured-issue-min-items = menos de { $count } elementos
+structured-issue-max-items = más de { $count } elementos
+structured-issue-any-of = no coincide con ninguna de las formas permitidas
+response-modalities-title = Modalidades de respuesta
+response-modalities-help = Con qué responde el modelo. Las imágenes necesitan un modelo que sepa dibujar, el audio uno que sepa hablar, otros modelos rechazan la solicitud.
+response-modalities-text = Texto
+response-modalities-image = Imagen
+response-modalities-audio = Audio
+response-modalities-default = Nada elegido, el modelo responde con texto
+media-resolution-title = Resolución de medios
+media-resolution-help = Cuántos tokens recibe cada imagen, fotograma de vídeo y página de PDF. La baja abarata los vídeos largos y los documentos grandes, la alta mantiene legibles el texto pequeño y los detalles. Haz clic derecho en un adjunto para cambiarla solo para ese archivo.
+media-resolution-attachment = Resolución de medios de este archivo
+media-resolution-auto = Predeterminada del modelo
+media-resolution-low = Baja
+media-resolution-medium = Media
+media-resolution-high = Alta
+image-invalid = ⚠ El modelo envió una imagen que no se puede mostrar
+image-hint = Clic derecho para guardar o copiar
+image-save = 💾 Guardar imagen…
+image-copy = 📋 Copiar imagen
+image-edit = 🖌 Editar imagen…
+audio-title = Respuestas habladas
+audio-help = Hace que los modelos de voz, como los modelos TTS en vista previa, lean su respuesta. Otros modelos rechazan la solicitud.
+audio-enabled = Pedir voz
+audio-voice = Voz
+audio-language = Idioma
+audio-language-hint = auto
+audio-invalid = ⚠ El modelo envió audio que no se puede reproducir
+audio-play = Reproducir
+audio-play-external = Reproducir en la aplicación predeterminada
+audio-stop = Detener
+audio-save = 💾 Guardar…
+audio-save-hint = Guarda la voz como archivo WAV
+video-new = Nuevo vídeo
+video-none = Todavía no hay vídeos
+video-remove = Eliminar el vídeo
+video-title = Nuevo vídeo
+video-help = Veo crea un clip corto a partir del prompt, opcionalmente partiendo de una imagen. Suele tardar unos minutos.
+video-prompt = Describe el vídeo…
+video-negative-prompt = Qué evitar (opcional)
+video-model = Modelo
+video-aspect-ratio = Relación de aspecto
+video-image = Imagen inicial
+video-image-none = Ninguna
+video-pick = Elegir…
+video-generate = 🎬 Generar
+video-needs-key = La generación de vídeo necesita una clave de API
+video-submitting = Enviando…
+video-generating = Generando…
+video-downloading = Descargando…
+video-done = Listo
+video-failed = Error: { $error }
+video-invalid = La API no devolvió ningún vídeo
+video-avoiding = Evitar: { $text }
+video-open = ▶ Abrir en el reproductor
+video-save = 💾 Guardar como…
+penalty-presence = Penalización por presencia
+penalty-presence-help = Penaliza los tokens que ya aparecieron en la respuesta, sin importar cuántas veces. Los valores positivos llevan al modelo hacia temas nuevos, los negativos le permiten seguir en el mismo.
+penalty-frequency = Penalización por frecuencia
+penalty-frequency-help = Penaliza los tokens según cuántas veces ya aparecieron en la respuesta. Los valores positivos hacen menos probable la repetición, los negativos más.
+candidates-title = Candidatos
+candidates-help = Cuántas respuestas escribe el modelo por solicitud. La primera queda en la conversación, las demás se muestran como pestañas en el mensaje y pueden ocupar su lugar.
+candidates-current = Respuesta 1
+candidates-tab = Respuesta { $number }
+candidates-promote = ⬆ Seguir desde esta respuesta
+candidates-promote-hint = Pone esta respuesta en la conversación, la actual pasa a ser una alternativa
+functions-title = Herramientas
+functions-help = Declara funciones que el modelo puede pedir llamar. Cuando lo hace, la llamada aparece en el chat y escribes lo que devolvió la función.
+functions-enabled = Enviar con cada solicitud
+functions-unnamed = (sin nombre)
+functions-add = ➕ Añadir función
+functions-remove = Quitar función
+functions-none-selected = Selecciona una función o añade una
+functions-name = Nombre
+functions-description = Descripción
+functions-description-hint = Qué hace la función y cuándo llamarla
+functions-parameters = Parámetros (esquema JSON)
+functions-valid = ✔ Declaración válida
+functions-invalid-name = El nombre debe empezar por una letra o un guion bajo y puede contener letras, dígitos, guiones bajos, puntos y guiones, hasta 64 caracteres
+functions-invalid-schema = Los parámetros no son JSON válido: { $error }
+functions-schema-not-object = El esquema de parámetros debe ser de tipo "object"
+functions-answered = respondida
+functions-response-hint = Lo que devolvió la función, JSON o texto
+functions-send = Enviar respuestas
+functions-send-disabled = Responde primero a todas las llamadas
+functions-run-commands = Ejecutar comandos locales
+functions-run-commands-help = Permite que un comando de shell en este equipo responda a una función. Cada ejecución hay que confirmarla, los argumentos llegan como JSON por stdin y en GEMINID_ARGS, y lo que imprime el comando vuelve al modelo.
+functions-command = Comando
+functions-command-hint = p. ej. python3 ~/tools/weather.py
+functions-command-disabled = Activa primero los comandos locales
+functions-command-timeout = El comando no terminó en { $seconds } segundos
+functions-command-failed = El comando falló, el error está en el campo de respuesta
+functions-run = ▶ Ejecutar comando
+functions-run-plugin = Lo responde el plugin { $plugin }
+functions-running = Ejecutando el comando…
+functions-confirm-title = ¿Ejecutar un comando local?
+functions-confirm = El modelo llamó a { $name }, que ejecuta esto en tu equipo:
+functions-confirm-run = Ejecutar
+cache-invalid = La API devolvió una caché sin nombre
+cache-failed = No se pudo guardar el chat en caché: { $error }
+cache-creating = Guardando el inicio del chat en caché…
+cache-stale = El inicio del chat cambió desde que se guardó en caché, la caché no se usa
+cache-expired = La caché de contexto caducó
+cache-recreate = Volver a guardar
+cache-drop = Descartar la caché
+cache-active = Los primeros { $count } mensajes están en caché hasta las { $time }
+cache-offer-banner = Este chat tiene un contexto largo. ¿Guardarlo en caché para pagar menos en cada solicitud?
+cache-create = Guardar contexto en caché
+cache-dismiss = Ahora no
+cache-offer = Ofrecer guardar chats largos en caché
+cache-offer-help = Muestra un aviso cuando un chat supera unos 32.000 tokens. Los tokens en caché se cobran a una tarifa menor, más el almacenamiento mientras la caché existe. Requiere una clave de API.
+cache-ttl = Mantener las cachés
+retry-enabled = Reintentar cuando la API esté ocupada
+retry-enabled-help = Las solicitudes rechazadas por límites de uso (429) o un servidor sobrecargado (503) se reenvían tras una espera creciente, en lugar de fallar el mensaje.
+retry-attempts = Reintentos
+retry-fallback = Después, pasar a
+retry-fallback-help = Cuando se agotan los reintentos, se pide la respuesta a este modelo. Los chats con caché de contexto se quedan con su modelo.
+retry-no-fallback = Sin modelo alternativo
+retry-waiting = La API está ocupada, reintentando en { $seconds } s ({ $attempt }/{ $attempts })…
+retry-falling-back = Preguntando a { $model }…
+summarize-threshold = Ofrecer resumir desde
+summarize-threshold-help = Parte de la ventana de contexto del modelo que debe ocupar el chat antes de sugerir resumir sus mensajes antiguos
+summarize-auto = Resumir automáticamente
+summarize-auto-help = Resume los mensajes antiguos en cuanto se alcanza el umbral, sin preguntar. El resumen siempre se puede revertir.
+summarize-model = Modelo para resumir
+summarize-same-model = El mismo del chat
+summarize-offer = ⚠ El chat ocupa el { $percent }% de la ventana de contexto.
+summarize-now = 🗜 Resumir mensajes antiguos
+summarize-now-hint = Condensa todo salvo los últimos turnos en un único mensaje de resumen. Los mensajes originales se conservan y pueden restaurarse.
+summarize-dismiss = Ahora no
+summarize-running = Resumiendo mensajes antiguos…
+summarize-failed = No se pudo resumir el chat: { $error }
+summary-title = 🗜 Resumen de { $count } mensajes anteriores
+summary-text = Resumen
+summary-replaced = Mensajes resumidos
+summary-restore = ↩ Restaurar
+summary-restore-hint = Vuelve a poner los mensajes originales en lugar del resumen
+cache-title = Cachés de contexto
+cache-help = Cachés guardadas para tu clave de API. Se cobran mientras existen, elimina las que ningún chat necesite.
+cache-refresh = ⟳ Actualizar
+cache-none = No hay cachés de contexto
+cache-expires = caduca en { $minutes } min
+cache-extend = Extender
+cache-delete = 🗑 Eliminar
+models-context = Ventana de contexto: { $input } tokens de entrada, { $output } de salida
+models-methods = Admite: { $methods }
+safety-harassment = Acoso
+safety-hate-speech = Discurso de odio
+safety-sexually-explicit = Sexualmente explícito
+safety-dangerous-content = Contenido peligroso
+safety-block-none = No bloquear
+safety-block-high = Bloquear solo alta
+safety-block-medium = Bloquear media y superior
+safety-block-low = Bloquear baja y superior
+safety-prompt-blocked = El prompt fue bloqueado ({ $reason })
+safety-response-blocked = La respuesta fue bloqueada o interrumpida ({ $reason })
+safety-reason-safety = Los filtros de seguridad consideraron el contenido probablemente dañino en las categorías resaltadas.
+safety-reason-prohibited = El contenido está prohibido por las políticas de uso de Google, no se puede cambiar en los ajustes.
+safety-reason-recitation = La respuesta repetía demasiado de una fuente protegida por derechos de autor o de otro modo.
+safety-reason-other = El modelo se detuvo por un motivo que no explicó.
+safety-blocked-by = Bloqueado por: { $categories }
+safety-not-adjustable = Los filtros de estas categorías ya están desactivados, el bloqueo lo impone Google y no se puede ajustar.
+safety-relax-warning = ⚠ Relajar un filtro deja pasar contenido que puede ser ofensivo o dañino. Se aplica a todos los chats, o solo a este si sus ajustes del modelo sustituyen los filtros, y puede revertirse en los ajustes.
+safety-relax = Dejar de bloquear { $category } y regenerar
+safety-relax-all = Dejar de bloquear todas y regenerar
+safety-relax-all-hint = Desactiva a la vez los filtros de todas las categorías resaltadas y vuelve a preguntar
+injection-enabled = Analizar adjuntos y extractos de conocimiento en busca de instrucciones inyectadas
+injection-enabled-help = Busca texto dirigido al modelo, como «ignora las instrucciones anteriores», en archivos de texto y en los extractos de las colecciones de conocimiento, incluidas las páginas web descargadas
+injection-auto-quarantine = Dejar fuera del prompt las fuentes sospechosas automáticamente
+injection-quarantined-sources = Fuentes de conocimiento en cuarentena:
+injection-suspicious = ⚠ Posible inyección de prompt
+injection-quarantined = ⛔ En cuarentena
+injection-found = Texto con forma de instrucción: { $snippet }
+injection-quarantine = Poner en cuarentena
+injection-release = Liberar
+injection-quarantine-hint = Las fuentes en cuarentena se dejan fuera del prompt a partir del siguiente mensaje
+verify-answer = Comprobar esta respuesta con un segundo modelo
+verify-running = Verificando los datos de la respuesta…
+verify-title = Comprobado por { $model }: { $totals }
+verify-totals = { $supported } respaldadas, { $disputed } discutidas, { $unverifiable } sin verificar
+verify-supported = Respaldada
+verify-disputed = Discutida
+verify-unverifiable = No se pudo verificar
+verify-ungrounded = Comprobado sin búsqueda web, con el conocimiento propio del modelo
+verify-no-claims = No hay afirmaciones de hecho que comprobar
+verify-invalid = No se pudo leer la verificación
+verify-model = Modelo verificador
+verify-same-model = El mismo de la respuesta
+verify-grounding = Permitir que el modelo verificador busque en la web
+verify-grounding-help = Basa los veredictos en resultados de la Búsqueda de Google y enumera las páginas en que se apoyan
+chatbox-hint = Pregúntame lo que quieras…
+rtl-preview-hint = Así se lee el texto de derecha a izquierda, el cuadro de texto muestra los caracteres en el orden en que se escriben
+persona-switched = Cambiado a la persona { $persona }
+persona-removed = Persona quitada
+last-message-you = Tú: { $content }
+suggestion-fact-title = Cuéntame un dato curioso
+suggestion-fact-subtitle = sobre el Imperio romano
+suggestion-code-title = Muéstrame un fragmento de código
+suggestion-code-subtitle = de un servidor web en Rust
+suggestion-joke-title = Cuéntame un chiste
+suggestion-joke-subtitle = sobre cangrejos
+suggestion-ideas-title = Dame ideas
+suggestion-ideas-subtitle = para un regalo de cumpleaños
+
+## Find in chat
+
+find-hint = Buscar en el chat…
+find-match-case = Distinguir mayúsculas
+find-regex = Usar expresión regular
+find-invalid-pattern = Patrón no válido
+find-position = { $current } de { $total }
+find-previous = Coincidencia anterior (Mayús+Intro)
+find-next = Siguiente coincidencia (Intro)
+find-close = Cerrar (Esc)
+
+## Timeline
+
+timeline-earlier = Antes
+timeline-later = Después
+timeline-pick-day = Haz clic en un día para ver de qué hablasteis.
+timeline-no-messages = No hay mensajes este día.
+
+## Search
+
+search-title = 🔍 Buscar en todos los chats
+search-hint = Buscar mensajes…
+search-hint-semantic = Describe lo que buscas y pulsa Intro…
+search-hint-files = Nombre del archivo o texto dentro de él…
+search-syntax-help =
+    Filtros: model:<nombre> has:attachment after:<fecha> before:<fecha> tag:<nombre>
+    Las fechas pueden ser 2024, 2024-06 o 2024-06-15. `after` incluye la fecha, `before` la excluye.
+search-semantic = 🧠 Semántica
+search-semantic-help = Encuentra conversaciones relacionadas por su significado. Con el embedder de Gemini, el texto de los mensajes se envía a la API para crear el índice.
+search-files = 📎 Archivos
+search-files-help = Busca chats por el nombre de los archivos adjuntos o el texto que contienen
+search-save = 💾 Guardar
+search-save-help = Mostrar los chats de esta búsqueda como una carpeta en la barra lateral
+search-saved-name = Búsqueda guardada
+search-saved = Búsqueda guardada en la barra lateral
+search-indexing = Indexando { $indexed }/{ $total } mensajes
+search-after = Después de
+search-before = Antes de
+search-any-model = Cualquier modelo
+search-any-chat = Cualquier chat
+search-searching = Buscando…
+search-similarity = Similitud con la consulta
+search-match-count =
+    { $count ->
+        [one] { $count } coincidencia
+       *[other] { $count } coincidencias
+    }
+
+## Prompt library
+
+prompt-library-new = ➕ Nuevo prompt
+prompt-library-new-name = Nuevo prompt
+prompt-library-versions =
+    { $count ->
+        [one] { $count } versión
+       *[other] { $count } versiones
+    }
+prompt-library-remove = Quitar prompt
+prompt-library-select = Elige o crea un prompt a la izquierda.
+prompt-library-name-hint = Nombre del prompt
+prompt-library-text-hint = Prompt de sistema
+prompt-library-save-version = 💾 Guardar versión
+prompt-library-discard = Descartar
+prompt-library-history = Historial ({ $count })
+prompt-library-restore = Restaurar
+prompt-library-restore-hint = Cargar esta versión en el editor
+prompt-library-ab = Prueba A/B
+prompt-library-ab-help = Ejecuta la misma entrada con dos versiones o modelos y elige la mejor respuesta.
+prompt-library-test-input = Entrada de prueba
+prompt-library-run = ▶ Ejecutar
+prompt-library-prefer = 👍 Prefiero { $side }
+prompt-library-tie = Empate
+prompt-library-results = Resultados ({ $count })
+
+## Personas and snippets
+
+personas-help = Las personas reúnen un avatar, un prompt de sistema, un modelo, un ajuste de inferencia y una voz.
+persona-new-name = Nueva persona
+persona-avatar = Avatar (emoji)
+persona-name-hint = Nombre de la persona
+persona-remove = Quitar persona
+persona-add = ➕ Añadir persona
+persona-voice = Voz
+persona-voice-default = Predeterminada del sistema
+snippets-help = Los atajos se expanden en un texto más largo cuando les sigue un espacio en el cuadro de texto.
+snippets-shortcut = Atajo
+snippets-expansion = Expansión
+snippets-expansion-hint = Texto a insertar
+snippets-remove = Quitar atajo
+
+## Tray
+
+tray-show = Mostrar GeminiD
+tray-quick-ask = Pregunta rápida…
+tray-voice-mode = Activar o desactivar el modo de voz
+tray-quit = Salir
+
+## Appearance
+
+theme-mode = Modo
+theme-mode-auto = Automático
+theme-mode-light = ☀ Claro
+theme-mode-dark = 🌙 Oscuro
+theme-accent = Color de acento
+theme-code-blocks = Bloques de código
+theme-file = Archivo de tema
+theme-reload = Volver a cargar los temas del disco
+theme-open-folder = Abrir la carpeta de temas
+theme-file-help = Los archivos de tema son .toml o .json con `name`, `dark` y colores hexadecimales para `accent`, `text`, `window`, `panel`, `extreme_background`, `faint_background`, `code_background` y `hyperlink`.
+theme-high-contrast = Alto contraste
+theme-high-contrast-help = Colores en blanco y negro con controles contorneados
+theme-thick-focus = Contorno de foco grueso
+theme-thick-focus-help = Hace más visible el contorno del control enfocado al usar el teclado
+theme-reduced-motion = Reducir movimiento
+theme-reduced-motion-help = Desactiva las animaciones, el indicador animado de razonamiento y la nieve
+reading-enabled = Ayudas de lectura para las respuestas
+reading-enabled-help = Usa la fuente y el espaciado de abajo en los mensajes del asistente. Los bloques de código no cambian
+reading-font = Archivo de fuente
+reading-font-hint = p. ej. OpenDyslexic-Regular.otf
+reading-line-height = Altura de línea
+reading-letter-spacing = Espaciado entre letras
+fonts-fallbacks = Fuentes de respaldo, en orden, para los caracteres que no tienen las fuentes incluidas:
+fonts-raise = Probar esta fuente antes
+fonts-missing = Archivo no encontrado
+fonts-path-hint = Ruta a un archivo .ttf, .otf o .ttc
+fonts-add = Añadir
+fonts-system = Usar fuentes CJK y de emoji instaladas
+fonts-system-help = Buscar fuentes chinas, japonesas, coreanas y de emoji en las carpetas habituales del sistema y probarlas al final
+fonts-bundled-cjk = Después de todas ellas se prueba un subconjunto incluido de Noto Sans CJK
+
+spellcheck-enabled = Revisar la ortografía en el cuadro de texto
+spellcheck-enabled-help = Subrayar las palabras mal escritas. Haz clic derecho en una palabra para ver sugerencias
+spellcheck-language = Diccionario
+spellcheck-folder = Carpeta de diccionarios
+spellcheck-folder-hint = Carpeta con archivos .aff y .dic de hunspell
+spellcheck-ignored =
+    { $count ->
+        [one] { $count } palabra añadida al diccionario
+       *[other] { $count } palabras añadidas al diccionario
+    }
+spellcheck-clear = Borrar
+spellcheck-reload = Recargar diccionarios
+spellcheck-no-suggestions = Sin sugerencias
+spellcheck-add-word = Añadir al diccionario
+
+plugins-unsupported = Esta compilación no admite plugins, vuelve a compilar con la función `plugins`
+plugins-folder = Los plugins se cargan desde { $path }
+plugins-rescan = Volver a buscar
+plugins-none = No se encontraron plugins
+plugins-renderer = Muestra los bloques de código de este lenguaje
+plugins-exporter = Añade un formato de exportación
+plugins-permissions = Permisos solicitados, solo se permiten los marcados:
+plugins-host = Conectarse a { $host }
+plugins-path = Leer y escribir { $path }
+scripting-unsupported = Esta compilación no admite scripts, recompila con la función `scripting`
+scripting-enabled = Ejecutar scripts
+scripting-enabled-help = Llama a on_before_send, on_response y on_chat_created en los scripts Rhai de la carpeta de scripts. Los scripts modificados se recargan automáticamente.
+scripting-folder = Los scripts se cargan desde { $path }
+scripting-open-folder = Abrir carpeta
+scripting-none = No se encontraron scripts
+schedule-new = Nueva programación
+schedule-add = Nueva programación
+schedule-none = Aún no hay prompts programados
+schedule-remove = Eliminar programación
+schedule-name = Nombre de la programación
+schedule-enabled = Activada
+schedule-enabled-help = Envía el prompt según la programación mientras la app está abierta; las ejecuciones perdidas se recuperan una vez
+schedule-cron = Programación
+schedule-cron-help = Minuto, hora, día del mes, mes y día de la semana, como en cron. `0 8 * * 1-5` es a las 8:00 los días laborables.
+schedule-next-run = Próxima ejecución: { $time }
+schedule-last-run = Última ejecución: { $time }
+schedule-disabled = Desactivada
+schedule-chat = Chat
+schedule-new-chat = Chat nuevo en la primera ejecución
+schedule-prompt = Prompt
+schedule-custom-text = Texto propio
+schedule-text-hint = Qué enviar en cada ejecución
+schedule-files = Adjuntos:
+schedule-add-files = 📎 Añadir archivos
+schedule-notify = Notificar siempre
+schedule-notify-help = Muestra una notificación de escritorio con la respuesta aunque la ventana esté activa
+schedule-run-now = ▶ Ejecutar ahora
+schedule-finished = El prompt programado «{ $name }» recibió respuesta
+knowledge-new = Nueva colección
+knowledge-add = Nueva colección
+knowledge-none = Aún no hay colecciones de conocimiento
+knowledge-remove = Eliminar colección
+knowledge-name = Nombre de la colección
+knowledge-help = Las carpetas, archivos y páginas web se dividen en fragmentos y se vectorizan con Gemini. Los chats que usan la colección reciben los fragmentos más cercanos con cada prompt. Requiere una clave de API.
+knowledge-sources = Fuentes:
+knowledge-add-folder = 📁 Añadir carpeta
+knowledge-add-files = 📄 Añadir archivos
+knowledge-add-repository = 🗂 Añadir repositorio
+knowledge-add-repository-help = Lee el código de una carpeta de proyecto, omitiendo lo que indica .gitignore. Las respuestas citan los archivos por línea
+knowledge-url-hint = https://…
+knowledge-add-url = 🌐 Añadir página
+knowledge-chunks =
+    { $count ->
+        [one] { $count } fragmento
+       *[other] { $count } fragmentos
+    }
+knowledge-status =
+    { $count ->
+        [one] { $count } fragmento, indexado { $time }
+       *[other] { $count } fragmentos, indexados { $time }
+    }
+knowledge-not-indexed = Aún sin indexar
+knowledge-up-to-date = Actualizado, comprobado { $time }
+knowledge-pending =
+    { $count ->
+        [one] { $count } archivo cambió desde la indexación
+       *[other] { $count } archivos cambiaron desde la indexación
+    }
+knowledge-watch = Mantener actualizado
+knowledge-watch-help = Comprueba los archivos cada 30 segundos e incrusta los modificados en segundo plano
+knowledge-embedder = Embeddings:
+knowledge-embedder-help = Con qué se convierten los documentos en vectores. Al cambiarlo, toda la colección se vuelve a procesar en la siguiente actualización.
+embedder-gemini = API de Gemini
+embedder-local = Modelo local (sin conexión)
+embedder-local-unsupported = Esta compilación no incluye embeddings locales, recompila con la función `local-embeddings`
+knowledge-update = Actualizar
+knowledge-update-help = Incrusta solo los archivos modificados
+knowledge-debug = 🔍 Depurar la búsqueda
+knowledge-debug-help = Muestra qué fragmentos recibe una pregunta, con sus puntuaciones. Ajústalas en la configuración.
+knowledge-debug-hint = Una pregunta de prueba
+knowledge-debug-run = Buscar
+knowledge-debug-none = No se encontraron fragmentos
+knowledge-debug-source = Fuente
+knowledge-debug-similarity = Similitud
+knowledge-debug-keywords = Palabras clave
+knowledge-debug-score = Puntuación
+knowledge-debug-injected = En el prompt
+retrieval-top-k = Fragmentos por prompt
+retrieval-top-k-help = Cuántos de los fragmentos más relevantes van al prompt
+retrieval-min-score = Puntuación mínima
+retrieval-min-score-help = Los fragmentos con menor puntuación se omiten, aunque se encuentren menos
+retrieval-keyword-weight = Peso de las palabras clave
+retrieval-keyword-weight-help = Cuánto cuentan las coincidencias exactas de palabras frente al significado. Súbelo para nombres, identificadores y códigos
+knowledge-index = Indexar
+knowledge-reindex = Indexar de nuevo
+knowledge-progress = { $done } / { $total } fragmentos
+knowledge-nothing-to-index = Las fuentes no tienen texto para indexar
+knowledge-cancelled = Indexación cancelada
+knowledge-searching = Buscando en la colección de conocimiento…
+knowledge-memory-empty = No había nada en el chat que valiera la pena recordar
+knowledge-citations =
+    { $count ->
+        [one] 📚 { $count } fuente
+       *[other] 📚 { $count } fuentes
+    }
+knowledge-open-source = Abrir la fuente
+knowledge-off = Ninguna
+embedding-open = 🧪 Laboratorio de embeddings
+embedding-title = Laboratorio de embeddings
+embedding-help = Vectoriza cada línea con el modelo de embeddings de Gemini para comparar lo cerca que están los textos en significado y agruparlos. Requiere una clave de API.
+embedding-items-hint = Un texto por línea
+embedding-task = Optimizar para
+embedding-task-similarity = Similitud
+embedding-task-clustering = Agrupamiento
+embedding-task-classification = Clasificación
+embedding-task-document = Búsqueda: documentos
+embedding-task-query = Búsqueda: consultas
+embedding-run = Vectorizar
+embedding-summary = { $count } vectores de { $dimensions } dimensiones
+embedding-copy = 🗐 Copiar como JSON
+embedding-similarity = Similitud coseno
+embedding-clusters = Grupos
+embedding-cluster-count = Grupos:
+embedding-cluster = Agrupar
+embedding-group = Grupo { $number }
+batch-open = 📋 Ejecución por lotes
+batch-title = Ejecución por lotes
+batch-input = Archivo de entrada
+batch-input-hint = CSV con fila de encabezado, o JSON lines
+batch-output = Archivo de resultados
+batch-output-hint = .jsonl o .csv
+batch-model = Modelo
+batch-rate = Solicitudes por minuto
+batch-retries = Reintentos por fila
+batch-template = Plantilla del prompt, { "{{" }columna{ "}}" } se sustituye por el valor de la fila:
+batch-from-prompt = De un prompt guardado
+batch-template-hint = Resume esta reseña en una frase: { "{{" }review{ "}}" }
+batch-system-prompt = Prompt de sistema (opcional)
+batch-run = ▶ Ejecutar
+batch-cancel = ⏹ Cancelar
+batch-progress = { $done } de { $total } filas listas
+batch-failed =
+    { $count ->
+        [one] { $count } fila falló, mira la columna error
+       *[other] { $count } filas fallaron, mira la columna error
+    }
+batch-tokens = Tokens usados: { $count }
+batch-eta = Quedan unos { $minutes } min
+batch-finished = Terminado
+batch-cancelled = Cancelado, las filas terminadas se guardaron
+batch-no-output = Elige un archivo de resultados
+batch-empty = El archivo de entrada no tiene filas
+batch-missing-columns = El archivo de entrada no tiene la columna { $columns }
+batch-api = Usar la Batch API
+batch-api-hint = La mitad del precio de las solicitudes normales. Todo el archivo se envía como un solo trabajo, que suele terminar en minutos pero puede tardar hasta un día.
+batch-api-needs-key = La Batch API necesita una clave de API
+batch-api-waiting = Esperando a la Batch API: { $state }
+batch-no-result = La Batch API no devolvió ningún resultado para esta fila
+batch-rows = Filas
+batch-config-hint = Los ajustes de inferencia del chat actual se aplican a cada fila.
+compare-open-window = ⚖ Comparar documentos
+compare-title = Comparar documentos
+compare-help = Adjunta dos versiones de un documento, como un contrato o una especificación, y obtén la lista de cambios entre ellas.
+compare-original = Original
+compare-revised = Revisada
+compare-path-hint = Ruta del archivo
+compare-model = Modelo
+compare-focus = Fijarse en
+compare-focus-hint = p. ej. condiciones de pago y responsabilidad
+compare-run = ⚖ Comparar
+compare-invalid = El modelo no devolvió una comparación
+compare-no-changes = No se encontraron diferencias
+compare-added = Añadido
+compare-removed = Eliminado
+compare-changed = Modificado
+compare-clause = Cláusula
+compare-note = Nota
+compare-page = p. { $page }
+compare-open = Abrir
+model-compare-open = 🆚 Comparar modelos
+model-compare-title = Comparar modelos
+model-compare-help = Envía el mismo prompt y archivos a todos los modelos a la vez, con el prompt de sistema y los ajustes de inferencia del chat abierto.
+model-compare-add = Añadir un modelo
+model-compare-prompt-hint = Prompt para todos los modelos…
+model-compare-attach = Adjuntar archivos
+model-compare-run = ▶ Enviar a todos
+model-compare-stop = ⏹ Detener
+model-compare-tokens = { $tokens } tokens de salida
+image-edit-open-window = 🖌 Editar imágenes
+image-edit-title = Editor de imágenes
+image-edit-help = Describe un cambio y un modelo de imágenes lo aplica. Cada edición parte del último resultado; elige un paso anterior para volver y seguir desde ahí.
+image-edit-model = Modelo
+image-edit-open = 📂 Abrir imagen…
+image-edit-empty = Abre una imagen, o haz clic derecho en una imagen de un chat y elige Editar.
+image-edit-instruction-hint = p. ej. convierte el cielo en un atardecer, quita el coche
+image-edit-apply = ✨ Aplicar edición
+image-edit-steps = Pasos
+image-edit-original = Original
+image-edit-step-hint = Mostrar este paso, la siguiente edición parte de él
+deeplink-register = Abrir los enlaces geminid:// con esta app
+deeplink-register-help = Permite que las extensiones del navegador y otras apps pasen prompts con enlaces como geminid://new?prompt=Hola o geminid://chat/<id>
+deeplink-registered = Registrado
+deeplink-unsupported = En macOS el manejador de enlaces lo configura el paquete de la app
+deeplink-chat-not-found = El chat del enlace ya no existe
+code-scheme-follow = Según el tema
+code-scheme-dark = Oscuro
+code-scheme-light = Claro
+code-scheme-sepia = Sepia
+
+## Keyboard shortcuts
+
+command-send = Enviar mensaje
+command-new-chat = Nuevo chat
+command-regenerate = Regenerar la última respuesta
+command-stop = Detener la generación
+command-find-in-chat = Buscar en el chat
+command-global-search = Buscar en todos los chats
+command-palette = Paleta de comandos
+command-settings = Mostrar u ocultar los ajustes
+command-next-model = Modelo siguiente
+command-previous-model = Modelo anterior
+command-toggle-sidebar = Mostrar u ocultar la barra lateral
+command-focus-mode = Activar o desactivar el modo concentración
+command-timeline = Mostrar u ocultar la cronología
+command-back = Chat anterior
+command-forward = Chat siguiente
+command-reopen-closed = Reabrir chat cerrado
+command-logs = Mostrar registros
+command-diagnostics = Diagnóstico
+command-statistics = Estadísticas
+command-tools = Herramientas
+command-context-caches = Cachés de contexto
+logs-title = Registros
+logs-search = Buscar
+logs-follow = Seguir
+logs-export = 💾 Exportar…
+logs-copy = 🗐 Copiar
+logs-open-folder = 📂 Carpeta de registros
+logs-empty = No hay entradas que coincidan
+diagnostics-title = Diagnóstico
+diagnostics-run = ⟳ Volver a comprobar
+diagnostics-running = Comprobando…
+diagnostics-copy = 🗐 Copiar informe
+diagnostics-endpoint = Servidor de la API
+diagnostics-credentials = Credenciales
+diagnostics-proxy = Proxy
+diagnostics-latency = Latencia
+diagnostics-disk = Espacio en disco
+diagnostics-reachable = { $target } respondió en { $ms } ms
+diagnostics-timeout = { $target } no respondió a tiempo
+diagnostics-no-proxy = No hay proxy configurado
+diagnostics-invalid-proxy = URL de proxy no válida
+diagnostics-no-api-key = No hay clave de API configurada
+diagnostics-api-key-valid = La clave de API fue aceptada
+diagnostics-not-logged-in = No has iniciado sesión en Code Assist
+diagnostics-code-assist-valid = Sesión iniciada, proyecto { $project }
+diagnostics-rejected = Rechazado: { $reason }
+diagnostics-latency-result = Ida y vuelta { $min }–{ $max } ms, mediana { $median } ms
+diagnostics-no-storage = No hay carpeta para guardar los chats
+diagnostics-disk-result = { $free } libres, los chats y ajustes ocupan { $used } en { $path }
+analytics-title = Estadísticas
+analytics-enabled = Llevar estadísticas de uso locales
+analytics-enabled-help = Cuenta qué modelos usas, a qué horas y cuánto tardan en responder. Se queda en este equipo, no se envía nada a ningún sitio
+analytics-empty = Aún no hay nada recopilado, las estadísticas aparecen tras la próxima respuesta
+analytics-disabled = La recopilación está desactivada. Actívala para ver tus modelos más usados, las horas de más actividad y los tiempos de respuesta
+analytics-responses =
+    { $count ->
+        [one] { $count } respuesta
+       *[other] { $count } respuestas
+    }
+analytics-since = desde { $date }
+analytics-average-latency = Tiempo medio de respuesta: { $seconds } s
+analytics-models = Modelos más usados
+analytics-model = Modelo
+analytics-count = Respuestas
+analytics-latency = Tiempo medio
+analytics-errors = Errores
+analytics-hours = Horas de más actividad
+analytics-hour = { $hour }: { $count }
+analytics-busiest = Más actividad hacia las { $hour }
+analytics-clear = Borrar estadísticas
+shortcut-press-key = Pulsa una tecla…
+shortcut-unbound = Sin asignar
+shortcut-record-hint = Haz clic y pulsa el nuevo atajo. Escape cancela.
+shortcut-conflicts = También asignado a: { $commands }
+shortcut-unbind = Quitar asignación
+shortcut-reset = Restablecer el valor predeterminado
+shortcut-reset-all = Restablecer todos los atajos
+vim-mode = Navegación por el historial al estilo Vim
+vim-mode-help = Sin ningún campo de texto activo: j/k se mueven entre mensajes, g/G saltan al primero/último e i activa el cuadro de texto
+ctrl-enter-sends = Enviar con Ctrl+Enter
+ctrl-enter-sends-help = Enter inserta un salto de línea y Ctrl+Enter envía el mensaje, en lugar del atajo de enviar y Mayús+Enter. Útil con métodos de entrada que confirman con Enter
+
+## Onboarding
+
+onboarding-title = Te damos la bienvenida a GeminiD
+onboarding-step = Paso { $step } de { $total }
+onboarding-welcome = Vamos a configurarlo todo
+onboarding-welcome-text = GeminiD se comunica con los modelos Gemini de Google. Necesita una forma de autenticarse, un modelo predeterminado y, si tu red lo requiere, un proxy. Todo se puede cambiar más tarde en ⚙ Ajustes.
+onboarding-auth = ¿Cómo quieres iniciar sesión?
+onboarding-auth-api-key = Clave de API de Gemini
+onboarding-auth-api-key-text = Una clave gratuita de Google AI Studio. Fácil de configurar y necesaria para la búsqueda semántica.
+onboarding-auth-google = Cuenta de Google (Code Assist)
+onboarding-auth-google-text = Inicia sesión con tu cuenta de Google y usa la cuota de Code Assist de uno de tus proyectos de Cloud.
+onboarding-paste-key = Pega tu clave de API
+onboarding-get-key = Consíguela en
+onboarding-check-key = Comprobar clave
+onboarding-key-valid = ✅ La clave funciona
+onboarding-key-text = La clave se guarda localmente con el resto del estado de la aplicación. Si estás detrás de un proxy, configúralo primero en el último paso.
+onboarding-login = Iniciar sesión con Google
+onboarding-login-browser = Termina de iniciar sesión en el navegador
+onboarding-project-id = ID del proyecto de Google Cloud
+onboarding-login-text = Se abrirá una ventana del navegador para iniciar sesión. Las peticiones se facturan al proyecto de Cloud seleccionado.
+onboarding-model = Elige un modelo predeterminado
+onboarding-model-text = Los chats nuevos empiezan con este modelo. Los modelos Flash son más rápidos y baratos, los Pro son más listos. Puedes cambiarlo en cada chat cuando quieras.
+onboarding-network = Red
+onboarding-network-text = Solo hace falta si la API de Gemini no es accesible directamente desde tu red. Se admiten proxies HTTP y SOCKS5.
+onboarding-skip = Omitir la configuración
+onboarding-finish = Terminar
+onboarding-next = Siguiente ⏵
+onboarding-back = ⏴ Atrás
+onboarding-finished = Puedes cambiar todo esto más tarde en ⚙ Ajustes
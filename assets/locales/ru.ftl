@@ -0,0 +1,1157 @@
+### GeminiD UI strings, Russian
+
+## Common
+
+yes = Да
+no = Нет
+cancel = Отмена
+confirm = Подтвердить
+close = Закрыть
+delete = Удалить
+remove = Удалить
+add = ➕ Добавить
+clear = Очистить
+edit = Изменить
+export = Экспорт
+export-cancelled = Экспорт отменён
+no-file-selected = Файл не выбран
+import = Импорт
+save = Сохранить
+load = Загрузить
+none = Нет
+you = Вы
+new-chat = Новый чат
+no-matches = Нет совпадений
+chat-count =
+    { $count ->
+        [one] { $count } чат
+        [few] { $count } чата
+       *[many] { $count } чатов
+    }
+message-count =
+    { $count ->
+        [one] { $count } сообщение
+        [few] { $count } сообщения
+       *[many] { $count } сообщений
+    }
+token-count =
+    { $count ->
+        [one] { $count } токен
+        [few] { $count } токена
+       *[many] { $count } токенов
+    }
+date-long = %A, %-d %B %Y
+date-short = %-d %b %Y
+date-day = %a, %d.%m.%Y
+date-time = %d.%m.%Y %H:%M
+time-just-now = только что
+time-minutes-ago =
+    { $count ->
+        [one] { $count } минуту назад
+        [few] { $count } минуты назад
+       *[many] { $count } минут назад
+    }
+time-hours-ago =
+    { $count ->
+        [one] { $count } час назад
+        [few] { $count } часа назад
+       *[many] { $count } часов назад
+    }
+time-days-ago =
+    { $count ->
+        [one] { $count } день назад
+        [few] { $count } дня назад
+       *[many] { $count } дней назад
+    }
+timestamps = Время сообщений
+timestamps-hidden = Скрыто
+timestamps-relative = Относительное
+timestamps-absolute = Дата и время
+timestamps-utc = Показывать время в UTC
+timestamps-utc-help = Использовать UTC вместо системного часового пояса для времени сообщений
+
+## Settings
+
+settings-reset-title = Сброс настроек
+settings-reset-confirm = Вы уверены, что хотите сбросить глобальные настройки? Это действие нельзя отменить!
+settings-authentication = Аутентификация
+settings-auth-method = Способ
+settings-api-key = API-ключ
+settings-api-key-hint = Введите API-ключ Google AI Studio
+settings-login-google = 🔑 Войти через Google
+settings-logged-in = ✅ Вход выполнен
+settings-logout = 🚪 Выйти
+settings-logged-out = Вы вышли, кэш очищен.
+settings-login-success = Вход через Google выполнен!
+settings-project = Проект
+settings-select-project = Выберите проект
+settings-no-projects = Проекты не найдены или загружаются...
+settings-model = Модель
+settings-default-model = Модель по умолчанию для новых чатов
+settings-inherit-model = Наследовать из чатов
+settings-inherit-model-help = Перенимать изменения модели из чатов
+settings-behavior = Поведение
+settings-streaming = Потоковый ответ
+settings-streaming-help = Получать ответ по мере генерации. Если выключено, ответ появится только целиком
+settings-persist-thoughts = Сохранять размышления в контексте
+settings-persist-thoughts-help = Размышления модели добавляются в контекст сессии для следующих запросов. Внимание: расход токенов быстро вырастет
+settings-file-api = Загружать файлы (File API)
+settings-file-api-help = Файлы загружаются на серверы Google (File API), а не отправляются внутри запроса в base64. Загруженные файлы временные и доступны только вам.
+settings-voice-mode = Голосовой режим
+settings-voice-mode-help = Зачитывать каждый ответ вслух, как только он сгенерирован
+settings-notifications = Уведомления на рабочем столе
+settings-notifications-help = Показывать уведомление, когда ответ готов, а окно неактивно или свёрнуто
+settings-close-to-tray = Сворачивать в трей
+settings-close-to-tray-help = Закрытие окна прячет его в системный трей, приложение продолжает работать. Для выхода используйте «Выход» в меню трея
+settings-snippets = Сниппеты
+settings-appearance = Внешний вид
+settings-language = Язык
+settings-language-system = Системный ({ $language })
+settings-rtl-layout = Интерфейс справа налево
+settings-rtl-layout-help = Переносит боковую панель вправо, а кнопки поля ввода после текста, для арабского, иврита, персидского и других языков с письмом справа налево
+settings-reading = Чтение
+settings-fonts = Шрифты
+settings-spellcheck = Проверка орфографии
+settings-retrieval = Поиск по базам знаний
+settings-safety = Фильтры безопасности
+settings-injection = Внедрение инструкций
+settings-verify = Проверка ответов
+settings-cache = Кэширование контекста
+settings-retry = Повторы запросов
+settings-summarize = Сжатие истории
+settings-plugins = Плагины
+settings-scripting = Скрипты
+settings-shortcuts = Горячие клавиши
+settings-misc = Прочее
+settings-proxy = Использовать прокси
+settings-proxy-help = Отправлять запросы к Gemini API через прокси
+settings-editor-command = Команда редактора
+settings-editor-command-help = Открывает цитируемые файлы на нужной строке, {"{"}path{"}"} и {"{"}line{"}"} подставляются. Оставьте пустым, чтобы использовать приложение по умолчанию
+settings-snow = Пусть идёт снег!
+settings-ui-scale = Масштаб интерфейса
+settings-ui-scale-help = Изменить общий размер интерфейса
+settings-reset-label = Сбросить глобальные настройки
+settings-reset = Сбросить
+settings-save-load-label = Сохранение и загрузка настроек в JSON
+
+## Main window
+
+toggle-sidebar = Показать или скрыть боковую панель
+back = Назад
+forward = Вперёд
+reopen-closed-chat = Вернуть закрытый чат
+no-closed-chats = Нет недавно закрытых чатов
+timeline = Хронология
+settings = Настройки
+token-count-help = Примерное число токенов в контексте
+token-count-limit = { $count } токенов вместе с набираемым сообщением, { $percent }% из { $limit }, которые принимает модель
+exit-focus-mode = Выйти из режима фокуса
+dropping-files = Перетаскиваемые файлы:
+notification-ready = Ответ готов
+notification-failed = Ошибка генерации
+notification-open-chat = Открыть чат
+voice-mode-on = Голосовой режим включён
+voice-mode-off = Голосовой режим выключен
+streaming-enabled = Потоковый вывод включён
+streaming-disabled = Потоковый вывод выключен
+chat-detached = Этот чат открыт в отдельном окне.
+chat-bring-back = Вернуть сюда
+split-close = Закрыть разделённый вид
+split-swap = Поменять с основным видом
+remove-chat-title = Удаление чата
+remove-chat-confirm =
+    Вы действительно хотите удалить этот чат? Его можно будет вернуть{ $shortcut }.
+    Удерживайте Shift, чтобы пропустить это предупреждение.
+remove-chat-named = Удалить чат «{ $name }»
+remove-chat = Удалить чат
+bulk-delete-title = Удаление чатов
+bulk-delete-summary = Будут удалены чаты ({ $chats }, { $messages }).
+bulk-archive-title = Архивация чатов
+bulk-archive-summary = Будут заархивированы чаты ({ $chats }, { $messages }).
+bulk-unarchive-title = Разархивация чатов
+bulk-unarchive-summary = Будут извлечены из архива чаты ({ $chats }, { $messages }).
+bulk-tag-title = Метки чатов
+bulk-tag-summary = Метка «{ $tag }» будет добавлена к чатам ({ $chats }, { $messages }).
+bulk-untag-summary = Метка «{ $tag }» будет снята с чатов ({ $chats }, { $messages }).
+bulk-move-title = Перемещение чатов
+bulk-move-summary = Чаты ({ $chats }, { $messages }) будут перемещены в «{ $folder }».
+bulk-export-title = Экспорт чатов
+bulk-export-summary = Будут экспортированы чаты ({ $chats }, { $messages }).
+bulk-tag-hint = Добавить или снять метку
+selected-count = Выбрано: { $count }
+archive = Архивировать
+archive-button = 📦 В архив
+unarchive = Из архива
+no-tags = Меток пока нет
+tag-delete = Удалить метку
+tag-new-hint = Новая метка
+top-level = Верхний уровень
+move-to-folder = Переместить в папку
+clear-selection = Снять выделение
+chat-edit-persona = Персона
+chat-edit-tags = Метки
+chat-edit-knowledge = Знания
+chat-edit-knowledge-help = Подходящие фрагменты из коллекции добавляются к каждому промпту, ответ перечисляет их как источники
+chat-edit-remember = Запомнить этот чат в:
+chat-edit-remember-button = 🧠 Запомнить
+chat-edit-remember-help = Выбирает из чата долговечные факты и решения и добавляет их в коллекцию, чтобы следующие чаты с ней их знали. Повторное запоминание заменяет их
+chat-edit-manage-tags = Управление метками
+chat-edit-model = Модель
+chat-edit-export-label = Экспорт истории чата в файл
+chat-edit-export-format = Формат
+save-as = Сохранить как…
+export-with-plugin = Сохранить как { $name }…
+tab-chats = Чаты
+tab-prompts = Промпты
+tab-schedules = Расписание
+tab-knowledge = Знания
+tab-videos = Видео
+no-recent-messages = Нет сообщений
+new-chat-button = ➕ Новый чат
+new-chat-hint = Создать новый чат
+new-folder-button = 📁 Новая папка
+new-folder-hint = Создать папку. Перетащите на неё чаты, чтобы переместить их
+folder-remove-hint = Удалить папку, её содержимое поднимется на уровень выше
+folder-edit-hint = Изменить папку
+folder-new-chat-hint = Новый чат в этой папке
+folder-new-name = Новая папка
+folder-name-hint = Название папки
+folder-template = Шаблон по умолчанию
+folder-template-help = Новые чаты в этой папке и её подпапках начинаются с этих настроек.
+folder-template-enable = Использовать шаблон
+project-title = Проект
+project-help = Чаты в этой папке и её подпапках получают это с каждым запросом.
+project-enable = Сделать проектом
+project-instructions-hint = Инструкции для каждого чата проекта
+project-knowledge = Знания
+drop-to-top-level = Перетащите сюда, чтобы переместить на верхний уровень
+archived-header = 📦 Архив ({ $count })
+remove-saved-search = Удалить сохранённый поиск
+no-matching-chats = Подходящих чатов нет
+open-in-split-view = ◫ Открыть рядом
+open-in-new-window = 🗗 Открыть в новом окне
+
+## Command palette
+
+palette-hint = Команда, чат или модель…
+palette-nothing = Ничего не найдено
+palette-enable-streaming = Включить потоковый вывод
+palette-disable-streaming = Выключить потоковый вывод
+palette-switch-model = Сменить модель: { $model }
+palette-use-prompt = Сделать системным промптом: { $prompt }
+palette-prompt-applied = Системный промпт: { $prompt }
+palette-setting = Настройка
+palette-model = Модель
+palette-prompt = Промпт
+palette-chat = Чат
+
+## Chat
+
+generation-time = Время генерации
+token-usage = Вход: { $input } / Выход: { $output } / Всего: { $total }
+retry-generation = 🔄 Повторить генерацию
+retry-generation-hint = Попробовать сгенерировать ответ ещё раз. Проверьте API-ключ и подключение к сети.
+stream-degraded = ⚠ Поток повреждён, ответ может быть неполным
+stream-degraded-hint = Часть потокового ответа не удалось прочитать и она пропущена, или поток оборвался, часто из-за прокси. Показана остальная часть ответа
+answer-truncated = ✂ Ответ остановился на лимите выходных токенов
+answer-interrupted = ⏹ Остановлено до завершения ответа
+answer-interrupted-hint = Вы остановили генерацию, полученный к этому моменту текст сохранён
+continue-generating = ⏩ Продолжить генерацию
+continue-generating-hint = Попросить модель продолжить с того места, где она остановилась, продолжение добавится к этому ответу
+prepend-hint = Начало ответа…
+regenerate-button = 🔄 Сгенерировать заново
+regenerate-hint = Сгенерировать ответ заново, модель продолжит после добавленного текста
+regenerate-with = Сгенерировать заново с…
+regenerate-with-hint = Сгенерировать этот ответ другой моделью, модель чата не меняется
+edit-message-button = ✏ Изменить
+edit-message-hint = Изменить сообщение в контексте без повторной генерации
+cancel-button = ❌ Отмена
+copied = Скопировано!
+copy-message = Копировать сообщение
+read-aloud-hint = Прочитать сообщение вслух. Правый клик — повторить
+regenerate = Сгенерировать заново
+pick-files = Выбрать файлы
+attach-url = Прикрепить по ссылке
+attach-url-hint = https://example.com/paper.pdf
+attach-url-download = Скачать и прикрепить
+attach-url-cancel = Отмена
+attach-url-downloading = Загрузка…
+attach-url-failed = Не удалось скачать файл: { $error }
+bundle-attach-hint = Прикрепить текстовые файлы папки
+bundle-title = Прикрепить папку
+bundle-change = Изменить…
+bundle-include = Включить
+bundle-exclude = Исключить
+bundle-globs-help = Шаблоны путей относительно папки, по одному в строке, например src/**/*.rs. Если включающих шаблонов нет, берутся все файлы.
+bundle-max-size = Макс. размер файла
+bundle-gitignore = Пропускать то, что исключает .gitignore
+bundle-scanning = Чтение папки и подсчёт токенов…
+bundle-rescan = 🔄 Применить фильтр
+bundle-summary =
+    { $files ->
+        [one] { $files } файл, { $size }
+        [few] { $files } файла, { $size }
+       *[many] { $files } файлов, { $size }
+    }
+bundle-tokens = ≈ { $tokens } токенов для модели этого чата
+bundle-tokens-unknown = Не удалось посчитать токены.
+bundle-skipped = Пропущено: { $large } слишком больших, { $binary } не текстовых
+bundle-files = Файлы в наборе
+bundle-attach = 📎 Прикрепить одним текстовым файлом
+bundle-attach-stale = Сначала примените изменённый фильтр
+archive-title = Распаковать { $name }
+archive-help = Выберите файлы, которые прикрепить вместо архива. Файлы, которые модель не может прочитать, недоступны.
+archive-reading = Чтение архива…
+archive-select-supported = Выбрать читаемые файлы
+archive-select-none = Снять выбор
+archive-unsupported = Модель не может прочитать файлы этого типа
+archive-extracting = Распаковка…
+archive-extract =
+    { $count ->
+        [one] Распаковать и прикрепить { $count } файл
+        [few] Распаковать и прикрепить { $count } файла
+       *[many] Распаковать и прикрепить { $count } файлов
+    }
+archive-keep = Оставить архив
+upload-progress = { $sent } / { $total } МБ
+upload-uploading = Загрузка…
+upload-failed = Ошибка
+attachment-missing = (ФАЙЛ НЕ НАЙДЕН)
+attachment-info-missing = { $name }, файл не найден
+attachment-info-uploading = { $name }, загружается
+attachment-info-uploading-percent = { $name }, загружено { $percent }%
+attachment-info-failed = { $name }, ошибка: { $error }
+attachment-info-expired = { $name }, срок загрузки истёк
+attachment-remove-hint = Нажмите Delete, чтобы убрать
+upload-cancel = Отменить загрузку, сообщение уйдёт без этого файла
+upload-cancelling = Отмена…
+upload-expires = истекает через { $hours } ч { $minutes } мин
+upload-expires-hint = File API удаляет загрузки через 48 часов
+upload-expired = Срок загрузки истёк
+upload-reupload = Загрузить снова
+upload-reupload-hint = Загрузить файл снова, чтобы следующие сообщения могли на него ссылаться
+upload-reupload-unavailable = Включите загрузку через File API с API-ключом, чтобы загружать файлы
+screenshot-capture = Снимок области экрана
+screenshot-help = Выделите область мышью · Enter — весь экран · Esc — отмена
+screenshot-failed = Не удалось сделать снимок экрана: { $error }
+record-start = Записать с микрофона
+record-stop = Остановить и прикрепить запись
+record-discard = Удалить запись
+record-failed = Не удалось записать звук: { $error }
+youtube-detected =
+    { $count ->
+        [one] ▶ В сообщении есть ссылка на YouTube, модель может сама посмотреть видео.
+        [few] ▶ В сообщении { $count } ссылки на YouTube, модель может сама посмотреть видео.
+       *[many] ▶ В сообщении { $count } ссылок на YouTube, модель может сама посмотреть видео.
+    }
+youtube-attach = Прикрепить как видео
+youtube-attach-hint = Отправляет само видео вместо ссылки. Щёлкните по вложению правой кнопкой, чтобы отправить только его часть.
+youtube-keep-text = Оставить текстом
+youtube-clip = Часть видео
+youtube-clip-start = Начало
+youtube-clip-end = Конец
+youtube-clip-invalid = Конец должен быть позже начала.
+improve-prompt = Улучшить промпт
+improve-prompt-failed = Не удалось улучшить промпт: { $error }
+preview-request = Предпросмотр запроса
+param-override-hint = Изменить температуру или бюджет размышлений для следующего сообщения
+param-override-active = Следующее сообщение отправится с изменёнными настройками
+param-override-title = Только для следующего сообщения:
+param-override-help = Действует только на следующий запрос, настройки генерации чата не меняются
+param-override-temperature = Температура
+param-override-thinking = Бюджет размышлений
+param-override-clear = Сбросить
+presets-pick = Пресеты…
+presets-empty = Пресетов пока нет
+presets-name-hint = Название пресета
+presets-save = 💾 Сохранить пресет
+presets-save-hint = Сохранить системный промпт под этим названием
+presets-overwrite = Сохранённый промпт с таким названием уже есть, сохранение добавит ему новую версию
+presets-manage = Управление пресетами
+presets-remove = Удалить пресет
+presets-export = Экспортировать пресеты…
+inference-settings = Параметры генерации
+inference-enable = Включить
+inference-reset = Сбросить
+inference-reset-hint = Вернуть значение по умолчанию
+inference-reset-all = Сбросить настройки
+system-prompt-title = Системный промпт
+system-prompt-help = Системный промпт задаёт модели особое поведение.
+system-prompt-enable = Свой системный промпт
+system-prompt-hint = Системный промпт для модели. Например: «Ты полезный ассистент, который специализируется на коде на Rust.»
+few-shot-title = Примеры (few-shot)
+few-shot-help = Пары вход/выход отправляются перед разговором, как будто модель уже на них ответила.
+few-shot-input = Вход
+few-shot-output = Выход
+few-shot-input-hint = Сообщение пользователя
+few-shot-output-hint = Ответ модели
+few-shot-remove = Удалить пример
+thinking-title = Размышления
+thinking-help = Встроенные размышления моделей Gemini 2.5 улучшают рассуждения.
+thinking-include = Показывать краткое содержание размышлений
+thinking-budget = Задать бюджет размышлений
+thinking-budget-help = Бюджет токенов на размышления. -1 — динамический, 0 — отключить.
+temperature-title = Температура
+temperature-help = Управляет случайностью ответа. Большие значения (например, 1.0) дают более творческие ответы, малые (например, 0.2) делают ответ более предсказуемым.
+max-tokens-title = Максимум токенов ответа
+max-tokens-help = Наибольшее число токенов, которое модель сгенерирует в ответе.
+top-k-title = Top-K
+top-k-help = Меняет выбор токенов для ответа. Меньшее значение ограничивает выборку меньшим набором самых вероятных токенов.
+top-p-title = Top-P
+top-p-help = Меняет выбор токенов для ответа, выборка идёт по накопленной вероятности. Используйте либо Top-K, либо Top-P, но не оба.
+stop-title = Стоп-последовательности
+stop-help = До 5 последовательностей символов, на которых генерация останавливается.
+preview-title = Предпросмотр запроса
+preview-loading = Сборка запроса…
+preview-model = Модель: { $model }
+preview-copy = 🗐 Копировать как JSON
+preview-no-count = Для подсчёта токенов нужен API-ключ Gemini
+preview-citations =
+    { $count ->
+        [one] { $count } фрагмент базы знаний добавлен в системную инструкцию
+        [few] { $count } фрагмента базы знаний добавлены в системную инструкцию
+       *[many] { $count } фрагментов базы знаний добавлены в системную инструкцию
+    }
+preview-thoughts-kept = Размышления отправляются в составе истории
+preview-thoughts-dropped = Размышления не входят в историю
+preview-system = Системная инструкция
+preview-examples = Примеры (few-shot)
+preview-history = История
+preview-prompt = Новое сообщение
+preview-role-user = Пользователь
+preview-role-model = Модель
+preview-inline-file = 📎 { $mime }, { $size } в запросе
+preview-uploaded-file = 🔗 { $mime }, загружен как { $uri }
+preview-empty = Нет
+preview-config = Параметры генерации
+preview-safety = Настройки безопасности
+preview-tools = Инструменты:
+safety-help = Насколько вероятно вредным может быть ответ, прежде чем он будет заблокирован, по категориям. Часть содержимого Google блокирует всегда, независимо от этих настроек
+safety-override = Переопределить общие фильтры безопасности
+safety-override-help = Запросы с этой конфигурацией модели используют фильтры безопасности из настроек
+grounding-title = Google Поиск
+grounding-help = Позволяет модели искать в интернете и указывает под ответом страницы, на которые она опиралась.
+grounding-enabled = Основывать ответы на Google Поиске
+grounding-sources =
+    { $count ->
+        [one] { $count } источник из интернета
+        [few] { $count } источника из интернета
+       *[many] { $count } источников из интернета
+    }
+grounding-queries = Поисковые запросы: { $queries }
+url-context-title = Контекст ссылок
+url-context-help = Ссылки из сообщения загружает Google, и модель читает сами страницы, а не только текст ссылки. Прочитанные страницы перечислены под ответом.
+url-context-enabled = Читать страницы по ссылкам
+url-context-pages =
+    { $count ->
+        [one] Прочитана { $count } страница
+        [few] Прочитано { $count } страницы
+       *[many] Прочитано { $count } страниц
+    }
+structured-output = Структурированный вывод
+structured-help = Модель отвечает в JSON, по схеме, если она задана. Ответы в JSON показываются деревом, которое можно сворачивать.
+structured-enabled = Отвечать в JSON
+structured-schema-hint = JSON-схема ответа, например {"{"} "type": "object", "properties": {"{"} … {"}"} {"}"}
+structured-schema-valid = ✔ Ответы будут следовать этой схеме
+structured-schema-empty = Без схемы подойдёт любой JSON
+structured-invalid-schema = Схема не является корректным JSON: { $error }
+structured-schema-not-object = Схема должна быть JSON-объектом
+structured-title = {"{ }"} JSON
+structured-copy = 📋 Копировать JSON
+structured-copy-path = Копировать путь
+structured-copy-value = Копировать значение
+structured-valid = ✔ Соответствует схеме
+structured-invalid =
+    { $count ->
+        [one] ✖ { $count } место не соответствует схеме
+        [few] ✖ { $count } места не соответствуют схеме
+       *[many] ✖ { $count } мест не соответствуют схеме
+    }
+structured-issues = Нарушения схемы
+structured-not-json = ✖ Ответ не является корректным JSON: { $error }
+structured-issue-type = ожидался тип { $expected }, получен { $actual }
+structured-issue-required = нет обязательного свойства «{ $name }»
+structured-issue-unexpected = схема не допускает этого свойства
+structured-issue-enum = не входит в { $allowed }
+structured-issue-minimum = меньше минимума { $limit }
+structured-issue-maximum = больше максимума { $limit }
+structured-issue-min-items = меньше { $count } элементов
+structured-issue-max-items = больше { $count } элементов
+structured-issue-any-of = не подходит ни под один из допустимых вариантов
+response-modalities-title = Модальности ответа
+response-modalities-help = Чем отвечает модель. Для изображений нужна модель, умеющая рисовать, для аудио — умеющая говорить, другие модели отклонят запрос.
+response-modalities-text = Текст
+response-modalities-image = Изображение
+response-modalities-audio = Аудио
+response-modalities-default = Ничего не выбрано, модель отвечает текстом
+media-resolution-title = Разрешение медиа
+media-resolution-help = Сколько токенов получает каждое изображение, кадр видео и страница PDF. Низкое удешевляет длинные видео и большие документы, высокое сохраняет мелкий текст и детали. Щёлкните вложение правой кнопкой, чтобы задать его для отдельного файла.
+media-resolution-attachment = Разрешение медиа для этого файла
+media-resolution-auto = По умолчанию модели
+media-resolution-low = Низкое
+media-resolution-medium = Среднее
+media-resolution-high = Высокое
+image-invalid = ⚠ Модель прислала изображение, которое не удаётся показать
+image-hint = Правый клик — сохранить или скопировать
+image-save = 💾 Сохранить изображение…
+image-copy = 📋 Копировать изображение
+image-edit = 🖌 Редактировать изображение…
+audio-title = Озвученные ответы
+audio-help = Модели речи (например, TTS preview) зачитывают свой ответ. Другие модели отклонят запрос.
+audio-enabled = Запрашивать речь
+audio-voice = Голос
+audio-language = Язык
+audio-language-hint = авто
+audio-invalid = ⚠ Модель прислала аудио, которое не удаётся воспроизвести
+audio-play = Воспроизвести
+audio-play-external = Воспроизвести в приложении по умолчанию
+audio-stop = Остановить
+audio-save = 💾 Сохранить…
+audio-save-hint = Сохраняет речь в файл WAV
+video-new = Новое видео
+video-none = Видео пока нет
+video-remove = Удалить видео
+video-title = Новое видео
+video-help = Veo создаёт короткий ролик по описанию, по желанию начиная с изображения. Обычно это занимает несколько минут.
+video-prompt = Опишите видео…
+video-negative-prompt = Чего избегать (необязательно)
+video-model = Модель
+video-aspect-ratio = Соотношение сторон
+video-image = Начальное изображение
+video-image-none = Нет
+video-pick = Выбрать…
+video-generate = 🎬 Создать
+video-needs-key = Для создания видео нужен API-ключ
+video-submitting = Отправка…
+video-generating = Создание…
+video-downloading = Загрузка…
+video-done = Готово
+video-failed = Ошибка: { $error }
+video-invalid = API не вернул видео
+video-avoiding = Избегать: { $text }
+video-open = ▶ Открыть в плеере
+video-save = 💾 Сохранить как…
+penalty-presence = Штраф за присутствие
+penalty-presence-help = Штрафует токены, которые уже встречались в ответе, сколько бы раз это ни было. Положительные значения подталкивают модель к новым темам, отрицательные позволяют держаться прежней.
+penalty-frequency = Штраф за частоту
+penalty-frequency-help = Штрафует токены в зависимости от того, как часто они уже встречались в ответе. Положительные значения делают повторы менее вероятными, отрицательные — более.
+candidates-title = Варианты ответа
+candidates-help = Сколько ответов модель пишет на один запрос. Первый остаётся в диалоге, остальные показываются вкладками у сообщения и могут занять его место.
+candidates-current = Ответ 1
+candidates-tab = Ответ { $number }
+candidates-promote = ⬆ Продолжить с этого ответа
+candidates-promote-hint = Помещает этот ответ в диалог, текущий становится альтернативой
+functions-title = Инструменты
+functions-help = Объявите функции, которые модель может попросить вызвать. Когда она это делает, вызов появляется в чате, и вы вводите, что вернула функция.
+functions-enabled = Отправлять с каждым запросом
+functions-unnamed = (без имени)
+functions-add = ➕ Добавить функцию
+functions-remove = Удалить функцию
+functions-none-selected = Выберите функцию или добавьте новую
+functions-name = Имя
+functions-description = Описание
+functions-description-hint = Что делает функция и когда её вызывать
+functions-parameters = Параметры (JSON-схема)
+functions-valid = ✔ Объявление корректно
+functions-invalid-name = Имя должно начинаться с буквы или подчёркивания и может содержать буквы, цифры, подчёркивания, точки и дефисы, не длиннее 64 символов
+functions-invalid-schema = Параметры не являются корректным JSON: { $error }
+functions-schema-not-object = Схема параметров должна иметь тип "object"
+functions-answered = отвечено
+functions-response-hint = Что вернула функция, JSON или простой текст
+functions-send = Отправить ответы
+functions-send-disabled = Сначала ответьте на все вызовы
+functions-run-commands = Запускать локальные команды
+functions-run-commands-help = Позволяет отвечать на вызов функции командой оболочки на этом компьютере. Каждый запуск нужно подтвердить, аргументы приходят в формате JSON на stdin и в GEMINID_ARGS, а то, что выведет команда, уходит модели.
+functions-command = Команда
+functions-command-hint = например, python3 ~/tools/weather.py
+functions-command-disabled = Сначала включите локальные команды
+functions-command-timeout = Команда не завершилась за { $seconds } с
+functions-command-failed = Команда завершилась с ошибкой, она в поле ответа
+functions-run = ▶ Запустить команду
+functions-run-plugin = Ответит плагин { $plugin }
+functions-running = Команда выполняется…
+functions-confirm-title = Запустить локальную команду?
+functions-confirm = Модель вызвала { $name }, что запустит на вашем компьютере:
+functions-confirm-run = Запустить
+cache-invalid = API вернул кэш без имени
+cache-failed = Не удалось закэшировать чат: { $error }
+cache-creating = Начало чата кэшируется…
+cache-stale = Начало чата изменилось после кэширования, кэш не используется
+cache-expired = Срок кэша контекста истёк
+cache-recreate = Закэшировать снова
+cache-drop = Удалить кэш
+cache-active = Первые сообщения ({ $count }) закэшированы до { $time }
+cache-offer-banner = У этого чата длинный контекст. Закэшировать его, чтобы платить меньше за каждый запрос?
+cache-create = Кэшировать контекст
+cache-dismiss = Не сейчас
+cache-offer = Предлагать кэшировать длинные чаты
+cache-offer-help = Показывает баннер, когда чат превышает примерно 32 000 токенов. Кэшированные токены оплачиваются по сниженной ставке плюс хранение, пока кэш существует. Нужен API-ключ.
+cache-ttl = Хранить кэши
+retry-enabled = Повторять запрос, когда API занят
+retry-enabled-help = Запросы, отклонённые из-за лимитов (429) или перегрузки сервера (503), отправляются снова после растущей паузы, а не завершаются ошибкой.
+retry-attempts = Повторов
+retry-fallback = Затем переключаться на
+retry-fallback-help = Когда повторы закончатся, ответ запрашивается у этой модели. Чаты с кэшем контекста остаются на своей модели.
+retry-no-fallback = Не переключаться
+retry-waiting = API занят, повтор через { $seconds } с ({ $attempt }/{ $attempts })…
+retry-falling-back = Запрос к { $model }…
+summarize-threshold = Предлагать сжатие с
+summarize-threshold-help = Какую долю контекстного окна модели должен занять чат, чтобы было предложено сжать старые сообщения
+summarize-auto = Сжимать автоматически
+summarize-auto-help = Сжимает старые сообщения сразу по достижении порога, не спрашивая. Сжатие всегда можно отменить.
+summarize-model = Модель для сжатия
+summarize-same-model = Как в чате
+summarize-offer = ⚠ Чат занимает { $percent }% контекстного окна.
+summarize-now = 🗜 Сжать старые сообщения
+summarize-now-hint = Заменяет всё, кроме последних нескольких реплик, одним сообщением-сводкой. Исходные сообщения сохраняются и могут быть восстановлены.
+summarize-dismiss = Не сейчас
+summarize-running = Сжатие старых сообщений…
+summarize-failed = Не удалось сжать чат: { $error }
+summary-title = 🗜 Сводка { $count } предыдущих сообщений
+summary-text = Сводка
+summary-replaced = Сжатые сообщения
+summary-restore = ↩ Восстановить
+summary-restore-hint = Возвращает исходные сообщения на место сводки
+cache-title = Кэши контекста
+cache-help = Кэши, сохранённые для вашего API-ключа. Они оплачиваются, пока существуют — удаляйте ненужные.
+cache-refresh = ⟳ Обновить
+cache-none = Нет кэшей контекста
+cache-expires = истекает через { $minutes } мин
+cache-extend = Продлить
+cache-delete = 🗑 Удалить
+models-context = Контекстное окно: { $input } токенов на вход, { $output } на выход
+models-methods = Поддерживает: { $methods }
+safety-harassment = Оскорбления
+safety-hate-speech = Разжигание ненависти
+safety-sexually-explicit = Откровенно сексуальное
+safety-dangerous-content = Опасное содержимое
+safety-block-none = Не блокировать
+safety-block-high = Блокировать только высокую
+safety-block-medium = Блокировать среднюю и выше
+safety-block-low = Блокировать низкую и выше
+safety-prompt-blocked = Запрос заблокирован ({ $reason })
+safety-response-blocked = Ответ заблокирован или оборван ({ $reason })
+safety-reason-safety = Фильтры безопасности сочли содержимое вероятно вредным в выделенных категориях.
+safety-reason-prohibited = Содержимое запрещено правилами использования Google, в настройках это не изменить.
+safety-reason-recitation = Ответ слишком близко повторял защищённый авторским правом или иной источник.
+safety-reason-other = Модель остановилась по необъяснённой причине.
+safety-blocked-by = Заблокировано категориями: { $categories }
+safety-not-adjustable = Фильтры этих категорий уже отключены, блокировку вводит Google, и её нельзя изменить.
+safety-relax-warning = ⚠ Ослабленный фильтр пропускает содержимое, которое может быть оскорбительным или вредным. Это касается всех чатов, или только этого, если его настройки модели переопределяют фильтры, и отменяется в настройках.
+safety-relax = Не блокировать «{ $category }» и сгенерировать заново
+safety-relax-all = Не блокировать ни одну из них и сгенерировать заново
+safety-relax-all-hint = Отключает фильтры всех выделенных категорий сразу и повторяет запрос
+injection-enabled = Проверять вложения и фрагменты знаний на внедрённые инструкции
+injection-enabled-help = Ищет текст, обращённый к модели, вроде «игнорируй предыдущие инструкции», в текстовых файлах и во фрагментах коллекций знаний, включая загруженные веб-страницы
+injection-auto-quarantine = Автоматически исключать подозрительные источники из запроса
+injection-quarantined-sources = Источники знаний в карантине:
+injection-suspicious = ⚠ Возможное внедрение инструкций
+injection-quarantined = ⛔ В карантине
+injection-found = Текст, похожий на инструкцию: { $snippet }
+injection-quarantine = В карантин
+injection-release = Вернуть
+injection-quarantine-hint = Источники в карантине не попадают в запрос, начиная со следующего сообщения
+verify-answer = Перепроверить ответ другой моделью
+verify-running = Проверка фактов в ответе…
+verify-title = Проверено { $model }: { $totals }
+verify-totals = подтверждено: { $supported }, оспорено: { $disputed }, не проверить: { $unverifiable }
+verify-supported = Подтверждено
+verify-disputed = Оспорено
+verify-unverifiable = Не удалось проверить
+verify-ungrounded = Проверено без поиска в интернете, по знаниям самой модели
+verify-no-claims = Нет фактических утверждений для проверки
+verify-invalid = Не удалось прочитать результат проверки
+verify-model = Модель для проверки
+verify-same-model = Та же, что ответила
+verify-grounding = Разрешить проверяющей модели искать в интернете
+verify-grounding-help = Основывает выводы на результатах Google Поиска и перечисляет страницы, на которые они опираются
+chatbox-hint = Спросите что угодно…
+rtl-preview-hint = Так читается текст справа налево, само поле ввода показывает символы в порядке набора
+persona-switched = Выбрана персона { $persona }
+persona-removed = Персона убрана
+last-message-you = Вы: { $content }
+suggestion-fact-title = Расскажи интересный факт
+suggestion-fact-subtitle = о Римской империи
+suggestion-code-title = Покажи пример кода
+suggestion-code-subtitle = веб-сервера на Rust
+suggestion-joke-title = Расскажи шутку
+suggestion-joke-subtitle = про крабов
+suggestion-ideas-title = Подкинь идей
+suggestion-ideas-subtitle = для подарка на день рождения
+
+## Find in chat
+
+find-hint = Найти в чате…
+find-match-case = Учитывать регистр
+find-regex = Регулярное выражение
+find-invalid-pattern = Неверный шаблон
+find-position = { $current } из { $total }
+find-previous = Предыдущее совпадение (Shift+Enter)
+find-next = Следующее совпадение (Enter)
+find-close = Закрыть (Esc)
+
+## Timeline
+
+timeline-earlier = Раньше
+timeline-later = Позже
+timeline-pick-day = Выберите день, чтобы увидеть, о чём шла речь.
+timeline-no-messages = В этот день сообщений нет.
+
+## Search
+
+search-title = 🔍 Поиск по всем чатам
+search-hint = Искать в сообщениях…
+search-hint-semantic = Опишите, что ищете, и нажмите Enter…
+search-hint-files = Имя файла или текст в нём…
+search-syntax-help =
+    Фильтры: model:<имя> has:attachment after:<дата> before:<дата> tag:<имя>
+    Даты можно писать как 2024, 2024-06 или 2024-06-15. `after` включает дату, `before` исключает.
+search-semantic = 🧠 По смыслу
+search-semantic-help = Находит похожие разговоры по смыслу. С эмбеддером Gemini текст сообщений отправляется в API для построения индекса.
+search-files = 📎 Файлы
+search-files-help = Ищет чаты по имени прикреплённого файла или тексту внутри него
+search-save = 💾 Сохранить
+search-save-help = Показывать чаты по этому запросу как папку в боковой панели
+search-saved-name = Сохранённый поиск
+search-saved = Поиск сохранён на боковой панели
+search-indexing = Индексация { $indexed }/{ $total } сообщений
+search-after = После
+search-before = До
+search-any-model = Любая модель
+search-any-chat = Любой чат
+search-searching = Поиск…
+search-similarity = Сходство с запросом
+search-match-count =
+    { $count ->
+        [one] { $count } совпадение
+        [few] { $count } совпадения
+       *[many] { $count } совпадений
+    }
+
+## Prompt library
+
+prompt-library-new = ➕ Новый промпт
+prompt-library-new-name = Новый промпт
+prompt-library-versions =
+    { $count ->
+        [one] { $count } версия
+        [few] { $count } версии
+       *[many] { $count } версий
+    }
+prompt-library-remove = Удалить промпт
+prompt-library-select = Выберите или создайте промпт слева.
+prompt-library-name-hint = Название промпта
+prompt-library-text-hint = Системный промпт
+prompt-library-save-version = 💾 Сохранить версию
+prompt-library-discard = Отменить изменения
+prompt-library-history = История ({ $count })
+prompt-library-restore = Восстановить
+prompt-library-restore-hint = Загрузить эту версию в редактор
+prompt-library-ab = A/B-тест
+prompt-library-ab-help = Запустите один и тот же ввод на двух версиях или моделях и выберите лучший ответ.
+prompt-library-test-input = Тестовый ввод
+prompt-library-run = ▶ Запустить
+prompt-library-prefer = 👍 Лучше { $side }
+prompt-library-tie = Ничья
+prompt-library-results = Результаты ({ $count })
+
+## Personas and snippets
+
+personas-help = Персона объединяет аватар, системный промпт, модель, пресет параметров и голос.
+persona-new-name = Новая персона
+persona-avatar = Аватар (эмодзи)
+persona-name-hint = Имя персоны
+persona-remove = Удалить персону
+persona-add = ➕ Добавить персону
+persona-voice = Голос
+persona-voice-default = Системный по умолчанию
+snippets-help = Сокращения разворачиваются в длинный текст, если после них в поле ввода поставить пробел.
+snippets-shortcut = Сокращение
+snippets-expansion = Текст
+snippets-expansion-hint = Вставляемый текст
+snippets-remove = Удалить сокращение
+
+## Tray
+
+tray-show = Показать GeminiD
+tray-quick-ask = Быстрый вопрос…
+tray-voice-mode = Голосовой режим
+tray-quit = Выход
+
+## Appearance
+
+theme-mode = Режим
+theme-mode-auto = Авто
+theme-mode-light = ☀ Светлая
+theme-mode-dark = 🌙 Тёмная
+theme-accent = Акцентный цвет
+theme-code-blocks = Блоки кода
+theme-file = Файл темы
+theme-reload = Перечитать темы с диска
+theme-open-folder = Открыть папку тем
+theme-file-help = Файлы тем — это .toml или .json с полями `name`, `dark` и hex-цветами `accent`, `text`, `window`, `panel`, `extreme_background`, `faint_background`, `code_background` и `hyperlink`.
+theme-high-contrast = Высокая контрастность
+theme-high-contrast-help = Чёрно-белые цвета и обведённые элементы
+theme-thick-focus = Толстая рамка фокуса
+theme-thick-focus-help = Делает рамку вокруг элемента в фокусе заметнее при работе с клавиатуры
+theme-reduced-motion = Уменьшить движение
+theme-reduced-motion-help = Отключает анимации, анимированный индикатор размышлений и снег
+reading-enabled = Помощь при чтении ответов
+reading-enabled-help = Использовать шрифт и интервалы ниже для сообщений ассистента. Блоки кода не затрагиваются
+reading-font = Файл шрифта
+reading-font-hint = например, OpenDyslexic-Regular.otf
+reading-line-height = Высота строки
+reading-letter-spacing = Межбуквенный интервал
+fonts-fallbacks = Запасные шрифты, по порядку, для символов, которых нет во встроенных шрифтах:
+fonts-raise = Пробовать этот шрифт раньше
+fonts-missing = Файл не найден
+fonts-path-hint = Путь к файлу .ttf, .otf или .ttc
+fonts-add = Добавить
+fonts-system = Использовать установленные шрифты CJK и эмодзи
+fonts-system-help = Искать китайские, японские, корейские шрифты и шрифты эмодзи в обычных системных папках и пробовать их последними
+fonts-bundled-cjk = После всех них используется встроенное подмножество Noto Sans CJK
+
+spellcheck-enabled = Проверять орфографию в поле ввода
+spellcheck-enabled-help = Подчёркивать слова с ошибками. Щёлкните правой кнопкой по слову, чтобы увидеть варианты
+spellcheck-language = Словарь
+spellcheck-folder = Папка словарей
+spellcheck-folder-hint = Папка с файлами hunspell .aff и .dic
+spellcheck-ignored =
+    { $count ->
+        [one] { $count } слово добавлено в словарь
+        [few] { $count } слова добавлено в словарь
+       *[many] { $count } слов добавлено в словарь
+    }
+spellcheck-clear = Очистить
+spellcheck-reload = Перезагрузить словари
+spellcheck-no-suggestions = Нет вариантов
+spellcheck-add-word = Добавить в словарь
+
+plugins-unsupported = Эта сборка не поддерживает плагины, пересоберите с функцией `plugins`
+plugins-folder = Плагины загружаются из { $path }
+plugins-rescan = Обновить
+plugins-none = Плагины не найдены
+plugins-renderer = Отображает блоки кода на этом языке
+plugins-exporter = Добавляет формат экспорта
+plugins-permissions = Запрошенные разрешения, разрешены только отмеченные:
+plugins-host = Подключаться к { $host }
+plugins-path = Читать и изменять { $path }
+scripting-unsupported = Эта сборка не поддерживает скрипты, пересоберите с функцией `scripting`
+scripting-enabled = Запускать скрипты
+scripting-enabled-help = Вызывает on_before_send, on_response и on_chat_created в скриптах Rhai из папки скриптов. Изменённые скрипты перезагружаются автоматически.
+scripting-folder = Скрипты загружаются из { $path }
+scripting-open-folder = Открыть папку
+scripting-none = Скрипты не найдены
+schedule-new = Новое расписание
+schedule-add = Новое расписание
+schedule-none = Запланированных промптов пока нет
+schedule-remove = Удалить расписание
+schedule-name = Название расписания
+schedule-enabled = Включено
+schedule-enabled-help = Отправляет промпт по расписанию, пока приложение запущено; пропущенные за время простоя запуски выполняются один раз
+schedule-cron = Расписание
+schedule-cron-help = Минута, час, день месяца, месяц и день недели, как в cron. `0 8 * * 1-5` — 8:00 по будням.
+schedule-next-run = Следующий запуск: { $time }
+schedule-last-run = Последний запуск: { $time }
+schedule-disabled = Отключено
+schedule-chat = Чат
+schedule-new-chat = Новый чат при первом запуске
+schedule-prompt = Промпт
+schedule-custom-text = Свой текст
+schedule-text-hint = Что отправлять при каждом запуске
+schedule-files = Вложения:
+schedule-add-files = 📎 Добавить файлы
+schedule-notify = Всегда уведомлять
+schedule-notify-help = Показывает уведомление об ответе, даже когда окно активно
+schedule-run-now = ▶ Запустить сейчас
+schedule-finished = Запланированный промпт «{ $name }» получил ответ
+knowledge-new = Новая коллекция
+knowledge-add = Новая коллекция
+knowledge-none = Коллекций знаний пока нет
+knowledge-remove = Удалить коллекцию
+knowledge-name = Название коллекции
+knowledge-help = Папки, файлы и веб-страницы делятся на фрагменты и векторизуются Gemini. Чаты с этой коллекцией получают самые близкие фрагменты к каждому промпту. Нужен API-ключ.
+knowledge-sources = Источники:
+knowledge-add-folder = 📁 Добавить папку
+knowledge-add-files = 📄 Добавить файлы
+knowledge-add-repository = 🗂 Добавить репозиторий
+knowledge-add-repository-help = Читает код папки проекта, пропуская то, что указано в .gitignore. Ответы ссылаются на файлы с номерами строк
+knowledge-url-hint = https://…
+knowledge-add-url = 🌐 Добавить страницу
+knowledge-chunks =
+    { $count ->
+        [one] { $count } фрагмент
+        [few] { $count } фрагмента
+       *[many] { $count } фрагментов
+    }
+knowledge-status =
+    { $count ->
+        [one] { $count } фрагмент, проиндексировано { $time }
+        [few] { $count } фрагмента, проиндексировано { $time }
+       *[many] { $count } фрагментов, проиндексировано { $time }
+    }
+knowledge-not-indexed = Ещё не проиндексировано
+knowledge-up-to-date = Актуально, проверено { $time }
+knowledge-pending =
+    { $count ->
+        [one] { $count } файл изменился после индексации
+        [few] { $count } файла изменились после индексации
+       *[many] { $count } файлов изменились после индексации
+    }
+knowledge-watch = Поддерживать в актуальном состоянии
+knowledge-watch-help = Проверяет файлы каждые 30 секунд и встраивает изменённые в фоне
+knowledge-embedder = Эмбеддинги:
+knowledge-embedder-help = Чем документы превращаются в векторы. После переключения вся коллекция будет заново обработана при следующем обновлении.
+embedder-gemini = Gemini API
+embedder-local = Локальная модель (офлайн)
+embedder-local-unsupported = Эта сборка не поддерживает локальные эмбеддинги, пересоберите с функцией `local-embeddings`
+knowledge-update = Обновить
+knowledge-update-help = Встраивает только изменённые файлы
+knowledge-debug = 🔍 Отладка поиска
+knowledge-debug-help = Показывает, какие фрагменты получает вопрос, и их оценки. Настроить можно в настройках.
+knowledge-debug-hint = Вопрос для проверки
+knowledge-debug-run = Найти
+knowledge-debug-none = Фрагменты не найдены
+knowledge-debug-source = Источник
+knowledge-debug-similarity = Сходство
+knowledge-debug-keywords = Ключевые слова
+knowledge-debug-score = Оценка
+knowledge-debug-injected = В запросе
+retrieval-top-k = Фрагментов на запрос
+retrieval-top-k-help = Сколько лучших фрагментов попадает в запрос
+retrieval-min-score = Минимальная оценка
+retrieval-min-score-help = Фрагменты с меньшей оценкой отбрасываются, даже если найдено меньше
+retrieval-keyword-weight = Вес ключевых слов
+retrieval-keyword-weight-help = Насколько точные совпадения слов важнее смысла. Увеличьте для имён, идентификаторов и кодов
+knowledge-index = Проиндексировать
+knowledge-reindex = Проиндексировать заново
+knowledge-progress = { $done } / { $total } фрагментов
+knowledge-nothing-to-index = В источниках нет текста для индексации
+knowledge-cancelled = Индексация отменена
+knowledge-searching = Поиск по коллекции знаний…
+knowledge-memory-empty = В чате не нашлось ничего, что стоит запомнить
+knowledge-citations =
+    { $count ->
+        [one] 📚 { $count } источник
+        [few] 📚 { $count } источника
+       *[many] 📚 { $count } источников
+    }
+knowledge-open-source = Открыть источник
+knowledge-off = Нет
+embedding-open = 🧪 Песочница эмбеддингов
+embedding-title = Песочница эмбеддингов
+embedding-help = Векторизует каждую строку моделью эмбеддингов Gemini, чтобы сравнить близость текстов по смыслу и сгруппировать их. Нужен API-ключ.
+embedding-items-hint = Один текст на строку
+embedding-task = Оптимизировать для
+embedding-task-similarity = Сходства
+embedding-task-clustering = Кластеризации
+embedding-task-classification = Классификации
+embedding-task-document = Поиска: документы
+embedding-task-query = Поиска: запросы
+embedding-run = Векторизовать
+embedding-summary = Векторов: { $count }, размерность { $dimensions }
+embedding-copy = 🗐 Копировать как JSON
+embedding-similarity = Косинусное сходство
+embedding-clusters = Кластеры
+embedding-cluster-count = Групп:
+embedding-cluster = Сгруппировать
+embedding-group = Группа { $number }
+batch-open = 📋 Пакетный запуск
+batch-title = Пакетный запуск
+batch-input = Входной файл
+batch-input-hint = CSV со строкой заголовков или JSON lines
+batch-output = Файл результатов
+batch-output-hint = .jsonl или .csv
+batch-model = Модель
+batch-rate = Запросов в минуту
+batch-retries = Повторов на строку
+batch-template = Шаблон промпта, { "{{" }столбец{ "}}" } заменяется значением из строки:
+batch-from-prompt = Из сохранённого промпта
+batch-template-hint = Перескажи этот отзыв одним предложением: { "{{" }review{ "}}" }
+batch-system-prompt = Системный промпт (необязательно)
+batch-run = ▶ Запустить
+batch-cancel = ⏹ Отменить
+batch-progress = Готово строк: { $done } из { $total }
+batch-failed =
+    { $count ->
+        [one] { $count } строка с ошибкой, см. столбец error
+        [few] { $count } строки с ошибкой, см. столбец error
+       *[many] { $count } строк с ошибкой, см. столбец error
+    }
+batch-tokens = Потрачено токенов: { $count }
+batch-eta = Осталось около { $minutes } мин
+batch-finished = Готово
+batch-cancelled = Отменено, готовые строки сохранены
+batch-no-output = Выберите файл результатов
+batch-empty = Во входном файле нет строк
+batch-missing-columns = Во входном файле нет столбца { $columns }
+batch-api = Использовать Batch API
+batch-api-hint = Вдвое дешевле обычных запросов. Весь файл отправляется одним заданием, которое обычно выполняется за несколько минут, но может занять до суток.
+batch-api-needs-key = Для Batch API нужен API-ключ
+batch-api-waiting = Ожидание Batch API: { $state }
+batch-no-result = Batch API не вернул результат для этой строки
+batch-rows = Строки
+batch-config-hint = Настройки генерации текущего чата применяются к каждой строке.
+compare-open-window = ⚖ Сравнить документы
+compare-title = Сравнение документов
+compare-help = Прикрепите две версии документа, например договора или спецификации, и получите список изменений между ними.
+compare-original = Исходная
+compare-revised = Новая
+compare-path-hint = Путь к файлу
+compare-model = Модель
+compare-focus = Обратить внимание на
+compare-focus-hint = например, условия оплаты и ответственность
+compare-run = ⚖ Сравнить
+compare-invalid = Модель не вернула результат сравнения
+compare-no-changes = Различий не найдено
+compare-added = Добавлено
+compare-removed = Удалено
+compare-changed = Изменено
+compare-clause = Пункт
+compare-note = Примечание
+compare-page = с. { $page }
+compare-open = Открыть
+model-compare-open = 🆚 Сравнить модели
+model-compare-title = Сравнение моделей
+model-compare-help = Отправляет один и тот же запрос и файлы всем моделям сразу, с системным промптом и настройками генерации открытого чата.
+model-compare-add = Добавить модель
+model-compare-prompt-hint = Запрос для всех моделей…
+model-compare-attach = Прикрепить файлы
+model-compare-run = ▶ Отправить всем
+model-compare-stop = ⏹ Остановить
+model-compare-tokens = Выходных токенов: { $tokens }
+image-edit-open-window = 🖌 Редактор изображений
+image-edit-title = Редактор изображений
+image-edit-help = Опишите изменение, и модель изображений применит его. Каждая правка начинается с последнего результата; выберите более ранний шаг, чтобы вернуться и продолжить с него.
+image-edit-model = Модель
+image-edit-open = 📂 Открыть изображение…
+image-edit-empty = Откройте изображение или щёлкните правой кнопкой по изображению в чате и выберите «Редактировать».
+image-edit-instruction-hint = например: сделай небо закатным, убери машину
+image-edit-apply = ✨ Применить правку
+image-edit-steps = Шаги
+image-edit-original = Исходное
+image-edit-step-hint = Показать этот шаг, следующая правка начнётся с него
+deeplink-register = Открывать ссылки geminid:// в этом приложении
+deeplink-register-help = Позволяет расширениям браузера и другим приложениям передавать промпты ссылками вида geminid://new?prompt=Привет или geminid://chat/<id>
+deeplink-registered = Зарегистрировано
+deeplink-unsupported = В macOS обработчик ссылок настраивается пакетом приложения
+deeplink-chat-not-found = Чата из ссылки больше нет
+code-scheme-follow = Как в теме
+code-scheme-dark = Тёмная
+code-scheme-light = Светлая
+code-scheme-sepia = Сепия
+
+## Keyboard shortcuts
+
+command-send = Отправить сообщение
+command-new-chat = Новый чат
+command-regenerate = Перегенерировать последний ответ
+command-stop = Остановить генерацию
+command-find-in-chat = Найти в чате
+command-global-search = Поиск по всем чатам
+command-palette = Палитра команд
+command-settings = Открыть или закрыть настройки
+command-next-model = Следующая модель
+command-previous-model = Предыдущая модель
+command-toggle-sidebar = Показать или скрыть боковую панель
+command-focus-mode = Режим фокуса
+command-timeline = Показать или скрыть хронологию
+command-back = Предыдущий чат
+command-forward = Следующий чат
+command-reopen-closed = Вернуть закрытый чат
+command-logs = Показать журнал
+command-diagnostics = Диагностика
+command-statistics = Статистика
+command-tools = Инструменты
+command-context-caches = Кэши контекста
+logs-title = Журнал
+logs-search = Поиск
+logs-follow = Следить
+logs-export = 💾 Экспорт…
+logs-copy = 🗐 Копировать
+logs-open-folder = 📂 Папка журналов
+logs-empty = Подходящих записей нет
+diagnostics-title = Диагностика
+diagnostics-run = ⟳ Проверить снова
+diagnostics-running = Проверка…
+diagnostics-copy = 🗐 Копировать отчёт
+diagnostics-endpoint = Сервер API
+diagnostics-credentials = Учётные данные
+diagnostics-proxy = Прокси
+diagnostics-latency = Задержка
+diagnostics-disk = Место на диске
+diagnostics-reachable = { $target } ответил за { $ms } мс
+diagnostics-timeout = { $target } не ответил вовремя
+diagnostics-no-proxy = Прокси не задан
+diagnostics-invalid-proxy = неверный адрес прокси
+diagnostics-no-api-key = API-ключ не задан
+diagnostics-api-key-valid = API-ключ принят
+diagnostics-not-logged-in = Вход в Code Assist не выполнен
+diagnostics-code-assist-valid = Вход выполнен, проект { $project }
+diagnostics-rejected = Отклонено: { $reason }
+diagnostics-latency-result = Круговая задержка { $min }–{ $max } мс, медиана { $median } мс
+diagnostics-no-storage = Нет папки для хранения чатов
+diagnostics-disk-result = Свободно { $free }, чаты и настройки занимают { $used } в { $path }
+analytics-title = Статистика
+analytics-enabled = Вести локальную статистику использования
+analytics-enabled-help = Считает, какими моделями вы пользуетесь, в какие часы и как быстро они отвечают. Остаётся на этом компьютере, ничего никуда не отправляется
+analytics-empty = Пока ничего не собрано, статистика появится после следующего ответа
+analytics-disabled = Сбор выключен. Включите его, чтобы видеть самые используемые модели, самые загруженные часы и время ответа
+analytics-responses =
+    { $count ->
+        [one] { $count } ответ
+        [few] { $count } ответа
+       *[many] { $count } ответов
+    }
+analytics-since = с { $date }
+analytics-average-latency = Среднее время ответа: { $seconds } с
+analytics-models = Самые используемые модели
+analytics-model = Модель
+analytics-count = Ответов
+analytics-latency = Ср. время
+analytics-errors = Ошибок
+analytics-hours = Самые загруженные часы
+analytics-hour = { $hour }: { $count }
+analytics-busiest = Пик около { $hour }
+analytics-clear = Очистить статистику
+shortcut-press-key = Нажмите клавишу…
+shortcut-unbound = Не назначено
+shortcut-record-hint = Нажмите, затем введите новое сочетание. Escape — отмена.
+shortcut-conflicts = Также назначено: { $commands }
+shortcut-unbind = Снять назначение
+shortcut-reset = Сбросить по умолчанию
+shortcut-reset-all = Сбросить все сочетания
+vim-mode = Навигация по истории в стиле Vim
+vim-mode-help = Когда поле ввода не в фокусе: j/k — переход между сообщениями, g/G — к первому/последнему, i — фокус на поле ввода
+ctrl-enter-sends = Отправка по Ctrl+Enter
+ctrl-enter-sends-help = Enter вставляет перевод строки, а Ctrl+Enter отправляет сообщение вместо сочетания «Отправить» и Shift+Enter. Удобно с методами ввода, которые подтверждают ввод клавишей Enter
+
+## Onboarding
+
+onboarding-title = Добро пожаловать в GeminiD
+onboarding-step = Шаг { $step } из { $total }
+onboarding-welcome = Давайте всё настроим
+onboarding-welcome-text = GeminiD работает с моделями Google Gemini. Нужны способ входа, модель по умолчанию и, если этого требует ваша сеть, прокси. Всё это можно изменить позже в ⚙ Настройках.
+onboarding-auth = Как вы хотите войти?
+onboarding-auth-api-key = API-ключ Gemini
+onboarding-auth-api-key-text = Бесплатный ключ из Google AI Studio. Просто настроить, нужен для семантического поиска.
+onboarding-auth-google = Аккаунт Google (Code Assist)
+onboarding-auth-google-text = Войдите через аккаунт Google и используйте квоту Code Assist одного из ваших облачных проектов.
+onboarding-paste-key = Вставьте API-ключ
+onboarding-get-key = Получить ключ:
+onboarding-check-key = Проверить ключ
+onboarding-key-valid = ✅ Ключ работает
+onboarding-key-text = Ключ хранится локально вместе с остальными данными приложения. Если вы за прокси, сначала настройте его на последнем шаге.
+onboarding-login = Вход через Google
+onboarding-login-browser = Завершите вход в браузере
+onboarding-project-id = ID проекта Google Cloud
+onboarding-login-text = Для входа откроется окно браузера. Запросы оплачиваются из выбранного облачного проекта.
+onboarding-model = Выберите модель по умолчанию
+onboarding-model-text = Новые чаты начинаются с этой модели. Модели Flash быстрее и дешевле, модели Pro умнее. Модель можно сменить в любом чате.
+onboarding-network = Сеть
+onboarding-network-text = Нужен, только если Gemini API недоступен из вашей сети напрямую. Поддерживаются HTTP- и SOCKS5-прокси.
+onboarding-skip = Пропустить настройку
+onboarding-finish = Готово
+onboarding-next = Далее ⏵
+onboarding-back = ⏴ Назад
+onboarding-finished = Всё это можно изменить позже в ⚙ Настройках
@@ -13,4 +13,8 @@ pub enum AdapterError {
 
     #[error("Stream error: {0}")]
     StreamError(String),
+
+    /// A stream chunk that didn't parse. The stream goes on after it.
+    #[error("Malformed stream chunk: {0}")]
+    MalformedChunk(#[source] serde_json::Error),
 }
@@ -1,6 +1,7 @@
 pub mod error;
 pub mod models;
 pub mod auth;
+mod resync;
 
 use std::pin::Pin;
 use futures::{Stream, StreamExt};
@@ -224,22 +225,30 @@ impl CodeAssistClient {
 
         let stream = response.bytes_stream().eventsource();
 
-        let mapped_stream = stream.map(|event_result| {
+        // proxies sometimes split, merge or mangle events, the buffer puts the JSON back together
+        let mut buffer = resync::ChunkBuffer::default();
+        let mapped_stream = stream.map(move |event_result| {
             match event_result {
                 Ok(event) => {
                     if event.data == "[DONE]" {
-                        return None;
+                        return Vec::new();
                     }
 
-                    match serde_json::from_str::<CodeAssistResponseEnvelope>(&event.data) {
-                        Ok(envelope) => Some(Ok(envelope.response)),
-                        Err(e) => Some(Err(AdapterError::SerdeError(e))),
-                    }
+                    buffer
+                        .push(&event.data)
+                        .into_iter()
+                        .map(|chunk| {
+                            chunk
+                                .and_then(serde_json::from_value::<CodeAssistResponseEnvelope>)
+                                .map(|envelope| envelope.response)
+                                .map_err(AdapterError::MalformedChunk)
+                        })
+                        .collect()
                 }
-                Err(e) => Some(Err(AdapterError::StreamError(e.to_string()))),
+                Err(e) => vec![Err(AdapterError::StreamError(e.to_string()))],
             }
         })
-        .filter_map(|x| async { x }); // Remove None
+        .flat_map(futures::stream::iter);
 
         Ok(Box::pin(mapped_stream))
     }
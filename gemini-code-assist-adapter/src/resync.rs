@@ -0,0 +1,59 @@
+use serde_json::Value;
+
+/// A chunk that doesn't end after this many bytes is dropped rather than awaited forever.
+const MAX_PENDING: usize = 4 * 1024 * 1024;
+
+/// Reassembles JSON objects from SSE event data that may be split across events, several
+/// to an event, or have garbage in between.
+#[derive(Default)]
+pub struct ChunkBuffer {
+    pending: String,
+}
+
+impl ChunkBuffer {
+    /// Adds the data of an event, returning the objects it completed. Garbage is skipped
+    /// up to the next object and reported as an error.
+    pub fn push(&mut self, data: &str) -> Vec<Result<Value, serde_json::Error>> {
+        self.pending.push_str(data.trim());
+        let mut out = Vec::new();
+        let mut consumed = 0;
+        loop {
+            let rest = &self.pending[consumed..];
+            let Some(start) = rest.find('{') else {
+                consumed = self.pending.len();
+                break;
+            };
+            let mut objects =
+                serde_json::Deserializer::from_str(&rest[start..]).into_iter::<Value>();
+            match objects.next() {
+                Some(Ok(value)) => {
+                    consumed += start + objects.byte_offset();
+                    out.push(Ok(value));
+                }
+                // the rest of the object comes with the next event
+                Some(Err(e)) if e.is_eof() => {
+                    consumed += start;
+                    break;
+                }
+                Some(Err(e)) => {
+                    log::warn!("skipping a malformed stream chunk: {e}");
+                    out.push(Err(e));
+                    consumed += start + 1;
+                }
+                None => {
+                    consumed = self.pending.len();
+                    break;
+                }
+            }
+        }
+        self.pending.drain(..consumed);
+        if self.pending.len() > MAX_PENDING {
+            log::warn!(
+                "dropping {} bytes of an unfinished stream chunk",
+                self.pending.len()
+            );
+            self.pending.clear();
+        }
+        out
+    }
+}
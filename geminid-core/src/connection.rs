@@ -0,0 +1,43 @@
+use crate::model::{AuthMethod, GeminiModel};
+use gemini_rust::{Gemini, GeminiBuilder, Model};
+
+/// What a request needs to reach the API, taken from the app settings.
+#[derive(Debug, Clone, Default)]
+pub struct Connection {
+    pub auth_method: AuthMethod,
+    pub api_key: String,
+    pub oauth_token: String,
+    pub project_id: String,
+    pub proxy_path: Option<String>,
+}
+
+impl Connection {
+    /// An API key client for `model`.
+    pub fn client(&self, model: GeminiModel) -> Result<Gemini, gemini_rust::ClientError> {
+        build_client(&self.api_key, self.proxy_path.clone(), model.into())
+    }
+}
+
+/// Builds an API client for `model`, routed through the proxy if one is set.
+pub fn build_client(
+    api_key: &str,
+    proxy_path: Option<String>,
+    model: Model,
+) -> Result<Gemini, gemini_rust::ClientError> {
+    let mut client_builder = reqwest::Client::builder();
+
+    if let Some(proxy_url) = proxy_path {
+        if !proxy_url.is_empty() {
+            if let Ok(proxy) = reqwest::Proxy::all(proxy_url) {
+                client_builder = client_builder.proxy(proxy);
+            } else {
+                log::error!("Invalid proxy URL, ignoring it.");
+            }
+        }
+    }
+
+    GeminiBuilder::new(api_key)
+        .with_model(model)
+        .with_http_client(client_builder)
+        .build()
+}
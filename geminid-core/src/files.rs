@@ -0,0 +1,681 @@
+use crate::{connection::Connection, model::GeminiModel, youtube::YouTubeVideo};
+use anyhow::{anyhow, Result};
+use base64::Engine;
+use gemini_rust::{prelude::*, Blob, FileState, Part};
+use image::{ImageFormat, ImageReader};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+pub const IMAGE_FORMATS: &[&str] = &[
+    "bmp", "dds", "ff", "gif", "hdr", "ico", "jpeg", "jpg", "exr", "png", "pnm", "qoi", "tga",
+    "tiff", "webp",
+];
+pub const VIDEO_FORMATS: &[&str] = &["mp4", "mpeg", "mov", "avi", "flv", "webm"];
+pub const TEXT_FORMATS: &[&str] = &[
+    "txt", "md", "rs", "py", "js", "html", "css", "json", "toml", "yaml", "log", "csv", "xml",
+    "pdf",
+];
+pub const MUSIC_FORMATS: &[&str] = &[
+    "aac", "flac", "mp3", "m4a", "mpeg", "mpga", "opus", "pcm", "wav", "webm", "aiff", "ogg",
+];
+
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Keeps the upload cache and the downloads in `dir`, the app data folder. Called once
+/// at startup, before anything is attached.
+pub fn init(dir: Option<PathBuf>) {
+    if let Some(dir) = dir {
+        DATA_DIR.get_or_init(|| dir);
+    }
+}
+
+static GLOBAL_FILE_CACHE: LazyLock<Mutex<HashMap<ContentKey, gemini_rust::File>>> =
+    LazyLock::new(|| Mutex::new(load_uploads()));
+
+/// What uploads are cached by, so the same content at two paths is uploaded once and an
+/// edited file is uploaded again. Uploads belong to the project of the API key, another
+/// key can't use them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+struct ContentKey {
+    sha256: [u8; 32],
+    size: u64,
+    /// SHA-256 of the API key the file was uploaded with, entries saved without one never match
+    #[serde(default)]
+    api_key: [u8; 32],
+}
+
+impl ContentKey {
+    fn of(bytes: &[u8], api_key: &str) -> Self {
+        Self {
+            sha256: Sha256::digest(bytes).into(),
+            size: bytes.len() as u64,
+            api_key: Sha256::digest(api_key.as_bytes()).into(),
+        }
+    }
+}
+
+fn uploads_path() -> Option<PathBuf> {
+    DATA_DIR.get().map(|dir| dir.join("uploads.json"))
+}
+
+/// Whether the File API has deleted `file` by now.
+pub fn is_expired(file: &gemini_rust::File) -> bool {
+    file.expiration_time
+        .is_some_and(|exp| exp < time::OffsetDateTime::now_utc())
+}
+
+/// Uploads of earlier runs that the File API still keeps, it deletes them after 48 hours.
+fn load_uploads() -> HashMap<ContentKey, gemini_rust::File> {
+    let Some(path) = uploads_path().filter(|p| p.exists()) else {
+        return HashMap::new();
+    };
+    let uploads: Vec<(ContentKey, gemini_rust::File)> = match std::fs::File::open(&path)
+        .map_err(anyhow::Error::from)
+        .and_then(|f| Ok(serde_json::from_reader(std::io::BufReader::new(f))?))
+    {
+        Ok(uploads) => uploads,
+        Err(e) => {
+            log::error!("failed to read `{}`: {e}", path.display());
+            return HashMap::new();
+        }
+    };
+    uploads
+        .into_iter()
+        .filter(|(_, file)| !is_expired(file))
+        .collect()
+}
+
+/// Writes the cache out on a blocking thread. Writes take turns and each one reads the
+/// cache when its turn comes, so the file ends up with the latest uploads.
+async fn save_uploads() {
+    static WRITING: Mutex<()> = Mutex::new(());
+    let saved = tokio::task::spawn_blocking(|| {
+        let _writing = WRITING.lock().unwrap_or_else(|e| e.into_inner());
+        let uploads: Vec<_> = GLOBAL_FILE_CACHE
+            .lock()
+            .map_err(|_| anyhow!("the upload cache is poisoned"))?
+            .iter()
+            .filter(|(_, file)| !is_expired(file))
+            .map(|(key, file)| (*key, file.clone()))
+            .collect();
+        write_uploads(&uploads)
+    })
+    .await;
+    match saved {
+        Ok(Ok(())) => (),
+        Ok(Err(e)) => log::error!("failed to save the uploaded files: {e}"),
+        Err(e) => log::error!("failed to save the uploaded files: {e}"),
+    }
+}
+
+/// Replaces `uploads.json` through a temporary file, a crash mid-write leaves the old one.
+fn write_uploads(uploads: &[(ContentKey, gemini_rust::File)]) -> Result<()> {
+    let path = uploads_path().ok_or_else(|| anyhow!("no storage folder"))?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let temp = path.with_extension("json.tmp");
+    let mut writer = std::io::BufWriter::new(std::fs::File::create(&temp)?);
+    serde_json::to_writer(&mut writer, uploads)?;
+    writer
+        .into_inner()
+        .map_err(|e| e.into_error())?
+        .sync_all()?;
+    std::fs::rename(&temp, &path)?;
+    Ok(())
+}
+
+// Supported Gemini MIME types
+const GEMINI_MIME: &[&str] = &[
+    "image/png",
+    "image/jpeg",
+    "image/webp",
+    "image/heic",
+    "image/heif",
+    "video/mp4",
+    "video/mpeg",
+    "video/quicktime",
+    "video/avi",
+    "video/x-flv",
+    "video/mpg",
+    "video/webm",
+    "video/wmv",
+    "video/3gpp",
+    "audio/wav",
+    "audio/mp3",
+    "audio/aiff",
+    "audio/aac",
+    "audio/ogg",
+    "audio/flac",
+    "text/plain",
+    "text/html",
+    "text/css",
+    "text/javascript",
+    "text/typescript",
+    "application/x-javascript",
+    "application/json",
+    "text/xml",
+    "application/rtf",
+    "text/rtf",
+    "application/pdf",
+];
+
+/// How many tokens images, video frames and PDF pages are given. More tokens
+/// keep finer detail, fewer make long videos and big PDFs affordable.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum MediaResolution {
+    /// Whatever the model picks
+    #[default]
+    Auto,
+    Low,
+    Medium,
+    High,
+}
+
+impl MediaResolution {
+    pub const ALL: [Self; 4] = [Self::Auto, Self::Low, Self::Medium, Self::High];
+
+    fn level(self) -> Option<&'static str> {
+        match self {
+            Self::Auto => None,
+            Self::Low => Some("MEDIA_RESOLUTION_LOW"),
+            Self::Medium => Some("MEDIA_RESOLUTION_MEDIUM"),
+            Self::High => Some("MEDIA_RESOLUTION_HIGH"),
+        }
+    }
+
+    /// The setting of the generation config, built through serde as the API spells it.
+    pub fn config<T: serde::de::DeserializeOwned>(self) -> Option<T> {
+        serde_json::from_value(self.level()?.into())
+            .map_err(|e| log::warn!("ignoring the media resolution: {e}"))
+            .ok()
+    }
+
+    /// The setting of a single inline file, overriding the one of the config.
+    fn part<T: serde::de::DeserializeOwned>(self) -> Option<T> {
+        serde_json::from_value(serde_json::json!({ "level": self.level()? }))
+            .map_err(|e| log::warn!("ignoring the media resolution of a file: {e}"))
+            .ok()
+    }
+
+    /// Whether files of this type are tokenized by resolution at all.
+    pub fn applies_to(mime: &str) -> bool {
+        mime.starts_with("image/") || mime.starts_with("video/") || mime == "application/pdf"
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum AttachmentState {
+    #[default]
+    Local,
+    /// `sent` of `total` bytes are uploaded, `total` is 0 until the upload starts
+    Uploading {
+        sent: u64,
+        total: u64,
+    },
+    Uploaded(gemini_rust::File),
+    Failed(String),
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Attachment {
+    pub path: PathBuf,
+    pub mime: String,
+    #[serde(skip)]
+    pub state: AttachmentState,
+    /// Instruction-like text found in the file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suspicious: Option<String>,
+    /// Left out of the prompt
+    #[serde(default)]
+    pub quarantined: bool,
+    /// Overrides the media resolution of the model settings, for inline files
+    #[serde(default)]
+    pub media_resolution: MediaResolution,
+    /// A YouTube video sent by URL, `path` only holds the link then
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub youtube: Option<YouTubeVideo>,
+    /// Cancelled to give up on the upload, shared with the request through the clone it gets
+    #[serde(skip)]
+    pub cancel_upload: CancellationToken,
+}
+
+impl Attachment {
+    pub fn from_path(path: PathBuf) -> Self {
+        let mime = mime_guess::from_path(&path)
+            .first_or_octet_stream()
+            .to_string();
+        Self {
+            path,
+            mime,
+            state: AttachmentState::Local,
+            suspicious: None,
+            quarantined: false,
+            media_resolution: MediaResolution::Auto,
+            youtube: None,
+            cancel_upload: CancellationToken::new(),
+        }
+    }
+
+    pub fn youtube(video: YouTubeVideo) -> Self {
+        Self {
+            path: PathBuf::from(video.url()),
+            mime: "video/*".to_owned(),
+            youtube: Some(video),
+            ..Self::from_path(PathBuf::new())
+        }
+    }
+
+    /// Gives the attachment a new cancel token if the last one was used, a token stays
+    /// cancelled for good. Called before the attachment is handed to a request.
+    pub fn renew_cancel(&mut self) {
+        if self.cancel_upload.is_cancelled() {
+            self.cancel_upload = CancellationToken::new();
+        }
+    }
+}
+
+/// Whether the model can read files like `path`, as they are or converted.
+pub fn is_supported(path: &Path) -> bool {
+    let extension = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let formats = [IMAGE_FORMATS, VIDEO_FORMATS, TEXT_FORMATS, MUSIC_FORMATS];
+    formats
+        .iter()
+        .any(|formats| formats.contains(&extension.as_str()))
+        || mime_guess::from_path(path)
+            .first()
+            .is_some_and(|mime| mime.type_() == "text")
+}
+
+/// Where attachments are uploaded to with the File API.
+#[derive(Debug, Clone)]
+pub struct FileApi {
+    pub api_key: String,
+    pub proxy: Option<String>,
+}
+
+/// Uploads go here chunk by chunk, so that their progress can be shown.
+const UPLOAD_API: &str = "https://generativelanguage.googleapis.com/upload/v1beta/files";
+
+/// A multiple of 256 KiB, as resumable uploads want.
+const UPLOAD_CHUNK: usize = 4 * 1024 * 1024;
+
+/// Uploads `bytes` with the resumable protocol of the File API, telling `progress` how
+/// many bytes of how many are sent after each chunk.
+async fn upload_resumable(
+    api: &FileApi,
+    bytes: &[u8],
+    mime: &str,
+    display_name: &str,
+    progress: &(dyn Fn(u64, u64) + Sync),
+) -> Result<gemini_rust::File> {
+    let client = http_client(api.proxy.as_deref())?;
+    let total = bytes.len() as u64;
+    let start = client
+        .post(UPLOAD_API)
+        .header("x-goog-api-key", &api.api_key)
+        .header("X-Goog-Upload-Protocol", "resumable")
+        .header("X-Goog-Upload-Command", "start")
+        .header("X-Goog-Upload-Header-Content-Length", total)
+        .header("X-Goog-Upload-Header-Content-Type", mime)
+        .json(&serde_json::json!({ "file": { "displayName": display_name } }))
+        .send()
+        .await?
+        .error_for_status()?;
+    let url = start
+        .headers()
+        .get("x-goog-upload-url")
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| anyhow!("The File API didn't return an upload URL."))?
+        .to_owned();
+
+    progress(0, total);
+    let mut offset = 0;
+    loop {
+        let end = (offset + UPLOAD_CHUNK).min(bytes.len());
+        let last = end == bytes.len();
+        let response = client
+            .post(&url)
+            .header(
+                "X-Goog-Upload-Command",
+                if last { "upload, finalize" } else { "upload" },
+            )
+            .header("X-Goog-Upload-Offset", offset)
+            .body(bytes[offset..end].to_vec())
+            .send()
+            .await?
+            .error_for_status()?;
+        offset = end;
+        progress(offset as u64, total);
+        if last {
+            let mut body: serde_json::Value = response.json().await?;
+            return Ok(serde_json::from_value(body["file"].take())?);
+        }
+    }
+}
+
+/// Uploads `attachment` again after the File API dropped its earlier upload. `None` if the
+/// user cancelled it.
+pub async fn reupload(
+    connection: &Connection,
+    model: GeminiModel,
+    attachment: &Attachment,
+) -> Result<Option<gemini_rust::File>> {
+    let client = connection.client(model)?;
+    let api = FileApi {
+        api_key: connection.api_key.clone(),
+        proxy: connection.proxy_path.clone(),
+    };
+    let convert = convert_file_to_part(
+        &client,
+        &attachment.path,
+        Some(&api),
+        attachment.media_resolution,
+        &|_, _| (),
+    );
+    let result = tokio::select! {
+        result = convert => result?,
+        _ = attachment.cancel_upload.cancelled() => return Ok(None),
+    };
+    match result {
+        FileResult::UploadedFile(handle) => Ok(Some(handle.get_file_meta().clone())),
+        FileResult::InlinePart(_) => Err(anyhow!("The file wasn't uploaded.")),
+    }
+}
+
+/// Returns either a Part with inline data or a FileHandle of the uploaded file
+pub enum FileResult {
+    /// Inline data part for direct use
+    InlinePart(Part),
+    /// Handle of the uploaded file for use in the API
+    UploadedFile(FileHandle),
+}
+
+pub async fn convert_file_to_part(
+    client: &Gemini,
+    path: &Path,
+    upload: Option<&FileApi>,
+    resolution: MediaResolution,
+    progress: &(dyn Fn(u64, u64) + Sync),
+) -> Result<FileResult> {
+    const MAX_INLINE_SIZE: u64 = 20 * 1024 * 1024; // 20 MB
+
+    // Check file size first
+    let metadata = tokio::fs::metadata(path).await?;
+    if metadata.len() > MAX_INLINE_SIZE && upload.is_none() {
+        return Err(anyhow!(
+            "File is too large for inline transmission ({} bytes > 20MB limit). Please enable 'File API' in settings.",
+            metadata.len()
+        ));
+    }
+
+    // Read file into bytes asynchronously
+    let file_bytes = tokio::fs::read(path).await?;
+
+    // the hash of what's on disk now, a file edited since its upload misses the cache
+    let (key, file_bytes) = match upload {
+        Some(api) => {
+            let api_key = api.api_key.clone();
+            tokio::task::spawn_blocking(move || {
+                (Some(ContentKey::of(&file_bytes, &api_key)), file_bytes)
+            })
+            .await?
+        }
+        None => (None, file_bytes),
+    };
+    if let Some(key) = &key {
+        if let Ok(cache) = GLOBAL_FILE_CACHE.lock() {
+            if let Some(remote_file) = cache.get(key) {
+                if !is_expired(remote_file) {
+                    log::info!("Global cache hit for {}", path.display());
+                    return Ok(FileResult::UploadedFile(
+                        client.file_from_model(remote_file.clone()),
+                    ));
+                } else {
+                    log::info!("Global cache expired for {}", path.display());
+                }
+            }
+        }
+    }
+
+    let mime_type = mime_guess::from_path(path).first_or_octet_stream();
+    let mut mime_str = mime_type.to_string();
+
+    if (mime_str.starts_with("application") && mime_str != "application/pdf")
+        || (mime_str.starts_with("text") && mime_str != "text/plain")
+    {
+        mime_str = "text/plain".to_string();
+    }
+
+    log::info!(
+        "Processing file: {}, MIME type: {}",
+        path.display(),
+        mime_str
+    );
+
+    // Convert non-PNG/JPEG images to PNG
+    let final_bytes = if mime_type.type_() == "image" {
+        match ImageReader::new(Cursor::new(&file_bytes))
+            .with_guessed_format()?
+            .format()
+        {
+            Some(format) if !matches!(format, ImageFormat::Png | ImageFormat::Jpeg) => {
+                log::debug!("Got {format:?} image, converting to png");
+                mime_str = "image/png".to_string();
+
+                let img = image::load_from_memory(&file_bytes)?;
+                let mut buf = Vec::new();
+                img.write_to(&mut Cursor::new(&mut buf), ImageFormat::Png)?;
+                buf
+            }
+            _ => {
+                // Already PNG/JPEG or unknown image format, send as is
+                file_bytes
+            }
+        }
+    } else {
+        // Use source bytes for video, text, and other file types
+        file_bytes
+    };
+
+    // Check MIME type support
+    if !GEMINI_MIME.contains(&mime_str.as_str()) {
+        return Err(anyhow!(
+            "Unsupported MIME type: {}. Supported types are: {:?}",
+            mime_str,
+            GEMINI_MIME
+        ));
+    }
+
+    if let Some(api) = upload {
+        log::info!("Uploading file...");
+
+        let display_name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("uploaded_file");
+        let file = upload_resumable(api, &final_bytes, &mime_str, display_name, progress).await?;
+        let file_handle = client.file_from_model(file);
+
+        log::info!(
+            "File uploaded: {}, waiting for processing...",
+            file_handle.name()
+        );
+
+        let start_time = Instant::now();
+        let timeout = Duration::from_secs(300);
+
+        loop {
+            let fresh_file_handle = client.get_file(file_handle.name()).await?;
+            match fresh_file_handle.get_file_meta().state.clone() {
+                Some(FileState::Active) => {
+                    log::info!("File {} is ACTIVE and ready.", file_handle.name());
+
+                    // Update cache
+                    if let Some(key) = key {
+                        if let Ok(mut cache) = GLOBAL_FILE_CACHE.lock() {
+                            cache.insert(key, fresh_file_handle.get_file_meta().clone());
+                        }
+                        save_uploads().await;
+                    }
+
+                    break;
+                }
+                Some(FileState::Failed) => {
+                    return Err(anyhow!("File processing failed on Google server side."));
+                }
+                Some(FileState::Processing) | Some(FileState::StateUnspecified) | None => {
+                    if start_time.elapsed() > timeout {
+                        return Err(anyhow!("Timeout waiting for file to become ACTIVE"));
+                    }
+                    log::debug!("File still processing, waiting...");
+                    tokio::time::sleep(Duration::from_secs(2)).await;
+                }
+                _ => {
+                    if start_time.elapsed() > timeout {
+                        return Err(anyhow!("Timeout or unknown state"));
+                    }
+                    tokio::time::sleep(Duration::from_secs(2)).await;
+                }
+            }
+        }
+        // ---------------------------------------
+
+        // Return file handle only when it is ACTIVE
+        Ok(FileResult::UploadedFile(file_handle))
+    } else {
+        let base64 = base64::engine::general_purpose::STANDARD.encode(&final_bytes);
+        log::debug!(
+            "Converted file to {} bytes of base64 with mime type {}",
+            base64.len(),
+            mime_str
+        );
+
+        let blob = Blob::new(mime_str, base64);
+        let part = Part::InlineData {
+            inline_data: blob,
+            media_resolution: resolution.part(),
+        };
+
+        Ok(FileResult::InlinePart(part))
+    }
+}
+
+fn downloads_dir() -> Option<PathBuf> {
+    DATA_DIR.get().map(|dir| dir.join("downloads"))
+}
+
+fn http_client(proxy: Option<&str>) -> Result<reqwest::Client> {
+    let mut builder = reqwest::Client::builder();
+    if let Some(proxy) = proxy.filter(|p| !p.trim().is_empty()) {
+        builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+    }
+    Ok(builder.build()?)
+}
+
+/// What downloaded bytes are: what they look like, then what the server said, then the
+/// extension in the URL.
+fn sniff_mime(bytes: &[u8], content_type: Option<&str>, url: &url::Url) -> String {
+    if let Ok(format) = image::guess_format(bytes) {
+        return format.to_mime_type().to_owned();
+    }
+    if bytes.starts_with(b"%PDF-") {
+        return "application/pdf".to_owned();
+    }
+    let declared = content_type
+        .and_then(|value| value.split(';').next())
+        .map(|mime| mime.trim().to_lowercase())
+        .filter(|mime| !mime.is_empty() && mime != "application/octet-stream");
+    if let Some(mime) = declared {
+        return mime;
+    }
+    if let Some(mime) = mime_guess::from_path(url.path()).first() {
+        return mime.to_string();
+    }
+    if std::str::from_utf8(bytes).is_ok() {
+        "text/plain".to_owned()
+    } else {
+        "application/octet-stream".to_owned()
+    }
+}
+
+/// A file name for the download whose extension says `mime`, as attachments are typed by it.
+fn download_name(url: &url::Url, mime: &str) -> String {
+    let name: String = url
+        .path_segments()
+        .and_then(|mut segments| segments.rfind(|s| !s.is_empty()))
+        .unwrap_or("download")
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || "._-".contains(c) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(100)
+        .collect();
+    let guessed = mime_guess::from_path(&name).first();
+    if guessed.is_some_and(|guessed| guessed.essence_str() == mime) {
+        return name;
+    }
+    match mime_guess::get_mime_extensions_str(mime).and_then(|e| e.first()) {
+        Some(extension) => format!("{name}.{extension}"),
+        None => name,
+    }
+}
+
+/// Downloads `url` into the app data folder, to be attached like a local file.
+pub async fn download(url: &str, proxy: Option<&str>) -> Result<PathBuf> {
+    const MAX_DOWNLOAD_SIZE: usize = 200 * 1024 * 1024; // 200 MB
+
+    let url = url::Url::parse(url.trim())?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(anyhow!("Only http and https links can be downloaded."));
+    }
+    log::info!("downloading {url}");
+    let mut response = http_client(proxy)?
+        .get(url.clone())
+        .send()
+        .await?
+        .error_for_status()?;
+    if response
+        .content_length()
+        .is_some_and(|len| len as usize > MAX_DOWNLOAD_SIZE)
+    {
+        return Err(anyhow!("The file is larger than 200 MB."));
+    }
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_owned);
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        bytes.extend_from_slice(&chunk);
+        if bytes.len() > MAX_DOWNLOAD_SIZE {
+            return Err(anyhow!("The file is larger than 200 MB."));
+        }
+    }
+
+    let mime = sniff_mime(&bytes, content_type.as_deref(), &url);
+    let dir = downloads_dir().ok_or_else(|| anyhow!("no data folder"))?;
+    tokio::fs::create_dir_all(&dir).await?;
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let path = dir.join(format!("{stamp}-{}", download_name(&url, &mime)));
+    tokio::fs::write(&path, &bytes).await?;
+    log::info!(
+        "saved {} bytes of {mime} to {}",
+        bytes.len(),
+        path.display()
+    );
+    Ok(path)
+}
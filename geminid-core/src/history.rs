@@ -0,0 +1,30 @@
+use crate::model::FewShotExample;
+use gemini_rust::{Content, Part, Role};
+
+/// Turns few-shot examples into leading user/model turns.
+pub fn examples_to_history(examples: &[FewShotExample]) -> Vec<Content> {
+    examples
+        .iter()
+        .filter(|e| !e.input.is_empty() && !e.output.is_empty())
+        .flat_map(|e| {
+            [
+                Content {
+                    parts: Some(vec![Part::Text {
+                        text: e.input.clone(),
+                        thought: None,
+                        thought_signature: None,
+                    }]),
+                    role: Some(Role::User),
+                },
+                Content {
+                    parts: Some(vec![Part::Text {
+                        text: e.output.clone(),
+                        thought: None,
+                        thought_signature: None,
+                    }]),
+                    role: Some(Role::Model),
+                },
+            ]
+        })
+        .collect()
+}
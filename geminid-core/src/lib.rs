@@ -0,0 +1,15 @@
+//! The parts of GeminiD that don't depend on egui: the model types, the connection
+//! settings, attachments and the File API, the request pipeline, the few-shot history
+//! and the log channel.
+//!
+//! The app keeps the chats, their widgets and the streaming of a chat's answer. It hands
+//! its messages to [`pipeline::build_history`] through the [`pipeline::Turn`] trait and
+//! hears about uploads through [`pipeline::Event`]s.
+
+pub mod connection;
+pub mod files;
+pub mod history;
+pub mod logger;
+pub mod model;
+pub mod pipeline;
+pub mod youtube;
@@ -0,0 +1,221 @@
+use chrono::{DateTime, Local};
+use log::{Level, LevelFilter, Metadata, Record, SetLoggerError};
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::Write,
+    path::PathBuf,
+    sync::{mpsc, Mutex, OnceLock},
+};
+
+/// Entries kept in memory for the log viewer.
+const MAX_ENTRIES: usize = 5000;
+/// The log file is rotated once it grows past this.
+const MAX_FILE_SIZE: u64 = 2 * 1024 * 1024;
+/// Rotated files kept next to the current one, `geminid.log.1` being the newest.
+const KEEP_FILES: usize = 3;
+const FILE_NAME: &str = "geminid.log";
+
+pub struct LogEvent {
+    pub level: Level,
+    pub message: String,
+}
+
+/// A record kept for the log viewer.
+#[derive(Clone)]
+pub struct LogEntry {
+    pub time: DateTime<Local>,
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {:<5} {}: {}",
+            self.time.format("%Y-%m-%d %H:%M:%S%.3f"),
+            self.level,
+            self.target,
+            self.message
+        )
+    }
+}
+
+static LOG_RECEIVER: OnceLock<Mutex<mpsc::Receiver<LogEvent>>> = OnceLock::new();
+static ENTRIES: Mutex<VecDeque<LogEntry>> = Mutex::new(VecDeque::new());
+static LOG_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Appends to `geminid.log`, moving it to `geminid.log.1` when it gets too big.
+struct RollingFile {
+    dir: PathBuf,
+    file: File,
+    size: u64,
+}
+
+impl RollingFile {
+    fn open(dir: PathBuf) -> std::io::Result<Self> {
+        std::fs::create_dir_all(&dir)?;
+        let file = File::options()
+            .create(true)
+            .append(true)
+            .open(dir.join(FILE_NAME))?;
+        let size = file.metadata()?.len();
+        Ok(Self { dir, file, size })
+    }
+
+    fn write(&mut self, line: &str) {
+        if self.size + line.len() as u64 > MAX_FILE_SIZE {
+            if let Err(e) = self.rotate() {
+                eprintln!("failed to rotate the log file: {e}");
+            }
+        }
+        if self.file.write_all(line.as_bytes()).is_ok() {
+            self.size += line.len() as u64;
+        }
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        let path = |n: usize| match n {
+            0 => self.dir.join(FILE_NAME),
+            n => self.dir.join(format!("{FILE_NAME}.{n}")),
+        };
+        let _ = std::fs::remove_file(path(KEEP_FILES));
+        for n in (0..KEEP_FILES).rev() {
+            if path(n).exists() {
+                std::fs::rename(path(n), path(n + 1))?;
+            }
+        }
+        self.file = File::create(path(0))?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+struct GlobalLogger {
+    inner: env_logger::Logger,
+    sender: Mutex<mpsc::Sender<LogEvent>>,
+    file: Option<Mutex<RollingFile>>,
+}
+
+impl GlobalLogger {
+    /// Whether the viewer and the log file get the record. Our own debug output is
+    /// kept, dependencies only from info up, they are chatty.
+    fn captures(metadata: &Metadata) -> bool {
+        let own =
+            metadata.target().starts_with("GeminiD") || metadata.target().starts_with("geminid");
+        metadata.level() <= if own { Level::Debug } else { Level::Info }
+    }
+}
+
+impl log::Log for GlobalLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.enabled(metadata) || Self::captures(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        // Always forward to env_logger for console/terminal output
+        if self.inner.enabled(record.metadata()) {
+            self.inner.log(record);
+        }
+
+        if !Self::captures(record.metadata()) {
+            return;
+        }
+        let entry = LogEntry {
+            time: Local::now(),
+            level: record.level(),
+            target: record.target().to_owned(),
+            message: format!("{}", record.args()),
+        };
+        if let Some(file) = &self.file {
+            if let Ok(mut file) = file.lock() {
+                file.write(&format!("{entry}\n"));
+            }
+        }
+
+        // Capture Warn and Error logs for the UI
+        if record.level() <= Level::Warn {
+            // Send to the channel
+            if let Ok(sender) = self.sender.lock() {
+                let _ = sender.send(LogEvent {
+                    level: record.level(),
+                    message: entry.message.clone(),
+                });
+            }
+        }
+
+        if let Ok(mut entries) = ENTRIES.lock() {
+            if entries.len() == MAX_ENTRIES {
+                entries.pop_front();
+            }
+            entries.push_back(entry);
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+        if let Some(file) = &self.file {
+            if let Ok(mut file) = file.lock() {
+                let _ = file.file.flush();
+            }
+        }
+    }
+}
+
+/// Sets up logging to the terminal, the log viewer and, with `log_dir`, a rolling
+/// log file in that folder.
+pub fn init(log_dir: Option<PathBuf>) -> Result<(), SetLoggerError> {
+    let (tx, rx) = mpsc::channel();
+
+    // Store the receiver globally so the UI can access it later
+    if LOG_RECEIVER.set(Mutex::new(rx)).is_err() {
+        eprintln!("Logger already initialized");
+        return Ok(());
+    }
+
+    let file = log_dir.and_then(|dir| match RollingFile::open(dir.clone()) {
+        Ok(file) => {
+            let _ = LOG_DIR.set(dir);
+            Some(Mutex::new(file))
+        }
+        Err(e) => {
+            eprintln!("failed to open the log file in {}: {e}", dir.display());
+            None
+        }
+    });
+    let logger = GlobalLogger {
+        inner: env_logger::Builder::from_default_env().build(),
+        sender: Mutex::new(tx),
+        file,
+    };
+
+    log::set_max_level(logger.inner.filter().max(LevelFilter::Debug));
+    log::set_boxed_logger(Box::new(logger))
+}
+
+pub fn pop_logs() -> Vec<LogEvent> {
+    let mut logs = Vec::new();
+    if let Some(rx_mutex) = LOG_RECEIVER.get() {
+        if let Ok(rx) = rx_mutex.lock() {
+            while let Ok(log) = rx.try_recv() {
+                logs.push(log);
+            }
+        }
+    }
+    logs
+}
+
+/// Runs `f` with the entries kept in memory, oldest first.
+pub fn with_entries<R>(f: impl FnOnce(&VecDeque<LogEntry>) -> R) -> R {
+    match ENTRIES.lock() {
+        Ok(entries) => f(&entries),
+        Err(_) => f(&VecDeque::new()),
+    }
+}
+
+/// Folder of the log files, `None` when logging to a file failed.
+pub fn log_dir() -> Option<&'static PathBuf> {
+    LOG_DIR.get()
+}
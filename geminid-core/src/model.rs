@@ -0,0 +1,198 @@
+use gemini_rust::Model;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    collections::BTreeSet,
+    fmt,
+    sync::{Mutex, PoisonError},
+};
+
+/// Names of the listed models, leaked once each so [`GeminiModel`] stays `Copy`.
+static NAMES: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+
+#[derive(Default, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct FewShotExample {
+    pub input: String,
+    pub output: String,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuthMethod {
+    #[default]
+    ApiKey,
+    CodeAssist,
+}
+
+impl fmt::Display for AuthMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthMethod::ApiKey => write!(f, "API Key"),
+            AuthMethod::CodeAssist => write!(f, "Google Code Assist"),
+        }
+    }
+}
+
+/// Name of a model that isn't built in, as the API lists it without `models/`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelName(&'static str);
+
+impl ModelName {
+    pub fn new(name: &str) -> Self {
+        let mut names = NAMES.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(&known) = names.get(name) {
+            return Self(known);
+        }
+        let name: &'static str = Box::leak(name.to_owned().into_boxed_str());
+        names.insert(name);
+        Self(name)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        self.0
+    }
+}
+
+impl Serialize for ModelName {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for ModelName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(|name| Self::new(&name))
+    }
+}
+
+/// Listed models aren't known ahead, iterating [`GeminiModel`] yields only the
+/// built-in ones.
+impl enum_iterator::Sequence for ModelName {
+    const CARDINALITY: usize = 0;
+
+    fn next(&self) -> Option<Self> {
+        None
+    }
+
+    fn previous(&self) -> Option<Self> {
+        None
+    }
+
+    fn first() -> Option<Self> {
+        None
+    }
+
+    fn last() -> Option<Self> {
+        None
+    }
+}
+
+/// Represents the available Gemini models.
+#[derive(
+    Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, enum_iterator::Sequence,
+)]
+pub enum GeminiModel {
+    #[default]
+    #[serde(rename = "gemini-3-flash-preview")]
+    Gemini30Flash,
+    #[serde(rename = "gemini-3-pro-preview")]
+    Gemini30Pro,
+    #[serde(rename = "gemini-2.0-flash")]
+    Gemini20Flash,
+
+    #[serde(rename = "gemini-2.0-flash-lite")]
+    Gemini20FlashLite,
+
+    #[serde(rename = "gemini-2.5-pro")]
+    Gemini25Pro,
+
+    #[serde(rename = "gemini-2.5-flash")]
+    Gemini25Flash,
+
+    #[serde(rename = "gemini-1.5-flash")]
+    Gemini15Flash,
+
+    #[serde(rename = "gemini-1.5-flash-8b")]
+    Gemini15Flash8b,
+
+    #[serde(rename = "gemini-2.5-flash-preview-05-20")]
+    Gemini25FlashPreview0520,
+
+    #[serde(rename = "gemini-2.0-flash-thinking-exp-01-21")]
+    Gemini20FlashThinkingExp0121,
+
+    #[serde(rename = "gemini-2.0-flash-thinking-exp-1219")]
+    Gemini20FlashThinkingExp1219,
+
+    #[serde(rename = "gemma-3-1b-it")]
+    Gemma31bIt,
+
+    #[serde(rename = "gemma-3-4b-it")]
+    Gemma34bIt,
+
+    #[serde(rename = "gemma-3-12b-it")]
+    Gemma312bIt,
+
+    #[serde(rename = "gemma-3-27b-it")]
+    Gemma327bIt,
+
+    #[serde(rename = "gemma-3n-e4b-it")]
+    Gemma3nE4bIt,
+
+    #[serde(rename = "gemma-3n-e2b-it")]
+    Gemma3nE2bIt,
+
+    // Models for paid quota
+    #[serde(rename = "gemini-1.5-pro")]
+    Gemini15Pro,
+
+    #[serde(rename = "gemini-2.5-pro-preview-03-25")]
+    Gemini25ProPreview0325,
+
+    #[serde(rename = "gemini-2.5-pro-preview-05-06")]
+    Gemini25ProPreview0506,
+
+    #[serde(rename = "gemini-2.5-pro-preview-06-05")]
+    Gemini25ProPreview0605,
+    // To add a new model, simply add a new variant here
+    // and its corresponding `rename` attribute.
+    // #[serde(rename = "new-model-name")]
+    // NewModelName,
+    /// A model from the API's list that isn't one of the above. The variants above
+    /// are what's offered when the list can't be fetched.
+    #[serde(untagged)]
+    Listed(ModelName),
+}
+
+impl GeminiModel {
+    /// The built-in variant called `name`, or a listed model.
+    pub fn from_name(name: &str) -> Self {
+        enum_iterator::all::<Self>()
+            .find(|model| model.to_string() == name)
+            .unwrap_or_else(|| Self::Listed(ModelName::new(name)))
+    }
+}
+
+impl From<GeminiModel> for Model {
+    fn from(val: GeminiModel) -> Self {
+        let model_id = serde_json::to_value(val)
+            .expect("Failed to serialize model enum")
+            .as_str()
+            .expect("Model enum should serialize to string")
+            .to_string();
+
+        Model::Custom(format!("models/{}", model_id))
+    }
+}
+
+/// Allows the enum to be printed or converted to its string representation.
+impl fmt::Display for GeminiModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_string(self)
+                .expect("Failed to serialize model")
+                .trim_matches('"')
+        )
+    }
+}
@@ -0,0 +1,611 @@
+use crate::connection::Connection;
+use crate::files::{convert_file_to_part, Attachment, AttachmentState, FileApi, FileResult};
+use crate::model::{AuthMethod, GeminiModel};
+use anyhow::{anyhow, Result};
+use futures::StreamExt;
+use gemini_rust::{
+    Content, FileData, Gemini, GenerationConfig, GenerationResponse, Modality, Part, Role, Tool,
+    UsageMetadata,
+};
+use std::path::PathBuf;
+
+/// What happens while a request is put together, about the message at the index it is
+/// reported with.
+#[derive(Debug, Clone)]
+pub enum Event {
+    Status(String),
+    /// `sent` of `total` bytes of the file are uploaded, `total` is 0 until it's known
+    Uploading {
+        path: PathBuf,
+        sent: u64,
+        total: u64,
+    },
+    Uploaded {
+        path: PathBuf,
+        file: gemini_rust::File,
+    },
+    /// The user cancelled the upload, the file was left out of the request
+    UploadCancelled {
+        path: PathBuf,
+    },
+}
+
+/// Gets the [`Event`]s of a request along with the index of their message.
+pub type Report<'a> = &'a (dyn Fn(usize, Event) + Sync);
+
+/// A message of a chat, as a request sends it.
+pub trait Turn {
+    fn role(&self) -> Role;
+    /// Left out of the request, like thoughts and empty messages
+    fn skipped(&self) -> bool;
+    /// Sent before the parts
+    fn files(&self) -> &[Attachment];
+    /// Text, images and the calls that have an answer
+    fn parts(&self) -> Vec<Part>;
+    /// The answers of the calls among the parts, they follow as a user turn
+    fn responses(&self) -> Vec<Part>;
+}
+
+/// Parts sent as a user turn of their own, like an image to edit.
+pub struct Leading(pub Vec<Part>);
+
+impl Turn for Leading {
+    fn role(&self) -> Role {
+        Role::User
+    }
+
+    fn skipped(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn files(&self) -> &[Attachment] {
+        &[]
+    }
+
+    fn parts(&self) -> Vec<Part> {
+        self.0.clone()
+    }
+
+    fn responses(&self) -> Vec<Part> {
+        Vec::new()
+    }
+}
+
+/// Turns `messages` into request contents. The first `skip` are left out, a context
+/// cache already holds them.
+pub async fn build_history<T: Turn>(
+    gemini: &Gemini,
+    messages: &[T],
+    skip: usize,
+    extra_content: Option<(&str, &[Attachment])>,
+    file_api: Option<&FileApi>,
+    status_channel: Option<(usize, Report<'_>)>,
+) -> Result<Vec<Content>> {
+    let mut history: Vec<Content> = Vec::new();
+    let mut parts_buffer: Vec<Part> = Vec::new();
+    let mut active_role: Option<Role> = None;
+
+    // Process main messages
+    for (msg_idx, message) in messages.iter().enumerate().skip(skip) {
+        if message.skipped() {
+            continue;
+        }
+
+        let message_role = message.role();
+
+        if let Some(current_role) = &active_role {
+            if *current_role != message_role {
+                if !parts_buffer.is_empty() {
+                    history.push(Content {
+                        parts: Some(std::mem::take(&mut parts_buffer)),
+                        role: Some(current_role.clone()),
+                    });
+                }
+                active_role = Some(message_role);
+            }
+        } else {
+            active_role = Some(message_role);
+        }
+
+        process_attachments(
+            gemini,
+            message.files(),
+            &mut parts_buffer,
+            file_api,
+            status_channel,
+            msg_idx,
+        )
+        .await;
+
+        parts_buffer.extend(message.parts());
+
+        let responses = message.responses();
+        if !responses.is_empty() {
+            history.push(Content {
+                parts: Some(std::mem::replace(&mut parts_buffer, responses)),
+                role: Some(Role::Model),
+            });
+            active_role = Some(Role::User);
+        }
+    }
+
+    if !parts_buffer.is_empty() {
+        if let Some(role) = active_role {
+            history.push(Content {
+                parts: Some(std::mem::take(&mut parts_buffer)),
+                role: Some(role),
+            });
+        }
+    }
+
+    // Process extra content (e.g. from input box)
+    if let Some((text, files)) = extra_content {
+        let mut extra_parts = Vec::new();
+        process_attachments(
+            gemini,
+            files,
+            &mut extra_parts,
+            None, // Don't upload extra content files (usually local for preview/counting)
+            None, // No status updates for extra content (usually used for counting)
+            0,    // Index irrelevant when status_channel is None
+        )
+        .await;
+
+        if !text.is_empty() {
+            extra_parts.push(Part::Text {
+                text: text.to_string(),
+                thought: None,
+                thought_signature: None,
+            });
+        }
+
+        if !extra_parts.is_empty() {
+            history.push(Content {
+                parts: Some(extra_parts),
+                role: Some(Role::User),
+            });
+        }
+    }
+
+    // Clear status message if we have a handle
+    if let Some((index, report)) = status_channel {
+        report(index, Event::Status(String::new()));
+    }
+
+    Ok(history)
+}
+
+/// Attachments converted or uploaded at the same time.
+const PARALLEL_UPLOADS: usize = 4;
+
+async fn process_attachments(
+    gemini: &Gemini,
+    files: &[Attachment],
+    parts_buffer: &mut Vec<Part>,
+    file_api: Option<&FileApi>,
+    status_channel: Option<(usize, Report<'_>)>,
+    file_msg_index: usize,
+) {
+    let parts: Vec<Option<Part>> = futures::stream::iter(files)
+        .map(|attachment| {
+            process_attachment(gemini, attachment, file_api, status_channel, file_msg_index)
+        })
+        .buffered(PARALLEL_UPLOADS)
+        .collect()
+        .await;
+    parts_buffer.extend(parts.into_iter().flatten());
+}
+
+/// The part of one attachment, uploading it first if it isn't yet.
+async fn process_attachment(
+    gemini: &Gemini,
+    attachment: &Attachment,
+    file_api: Option<&FileApi>,
+    status_channel: Option<(usize, Report<'_>)>,
+    file_msg_index: usize,
+) -> Option<Part> {
+    let file_path = &attachment.path;
+    if attachment.quarantined {
+        log::info!("skipping quarantined {}", file_path.display());
+        return None;
+    }
+    if let Some(video) = &attachment.youtube {
+        // the API fetches it by itself
+        return Some(video.part());
+    }
+    let filename = file_path
+        .file_name()
+        .map(|s| s.to_string_lossy())
+        .unwrap_or_default();
+
+    if let AttachmentState::Uploaded(remote_file) = &attachment.state {
+        let is_expired = if let Some(exp) = remote_file.expiration_time {
+            exp < time::OffsetDateTime::now_utc()
+        } else {
+            false
+        };
+
+        if !is_expired {
+            if let Some(uri) = &remote_file.uri {
+                return Some(Part::FileData {
+                    file_data: FileData {
+                        file_uri: uri.to_string(),
+                        mime_type: remote_file.mime_type.clone().unwrap_or_default(),
+                    },
+                });
+            }
+        }
+    }
+
+    // If status_channel is None (e.g. counting), force inline (no upload)
+    let effective_upload = file_api.filter(|_| status_channel.is_some());
+
+    if let Some((status_idx, report)) = status_channel {
+        report(
+            status_idx,
+            Event::Status(format!("Processing file: {filename}...")),
+        );
+        // Trigger Uploading state in UI (target the message with the file)
+        if effective_upload.is_some() {
+            report(
+                file_msg_index,
+                Event::Uploading {
+                    path: file_path.clone(),
+                    sent: 0,
+                    total: 0,
+                },
+            );
+        }
+    }
+
+    let progress = |sent: u64, total: u64| {
+        if let Some((_, report)) = status_channel {
+            report(
+                file_msg_index,
+                Event::Uploading {
+                    path: file_path.clone(),
+                    sent,
+                    total,
+                },
+            );
+        }
+    };
+    let convert = convert_file_to_part(
+        gemini,
+        file_path,
+        effective_upload,
+        attachment.media_resolution,
+        &progress,
+    );
+    // dropping the conversion stops both the upload and the wait for the file to be ACTIVE
+    let result = tokio::select! {
+        result = convert => result,
+        _ = attachment.cancel_upload.cancelled(), if effective_upload.is_some() => {
+            log::info!("cancelled the upload of {}", file_path.display());
+            if let Some((_, report)) = status_channel {
+                report(
+                    file_msg_index,
+                    Event::UploadCancelled {
+                        path: file_path.clone(),
+                    },
+                );
+            }
+            return None;
+        }
+    };
+    match result {
+        Ok(FileResult::InlinePart(part)) => Some(part),
+        Ok(FileResult::UploadedFile(file_handle)) => {
+            if let Some((_, report)) = status_channel {
+                report(
+                    file_msg_index,
+                    Event::Uploaded {
+                        path: file_path.clone(),
+                        file: file_handle.get_file_meta().clone(),
+                    },
+                );
+            }
+            FileData::try_from(&file_handle)
+                .ok()
+                .map(|file_data| Part::FileData { file_data })
+        }
+        Err(e) => {
+            log::error!("Failed to process file {}: {}", file_path.display(), e);
+            None
+        }
+    }
+}
+
+/// Runs a single non-streaming request outside of any chat and returns the response text.
+pub async fn generate_once(
+    connection: &Connection,
+    model: GeminiModel,
+    system_prompt: Option<&str>,
+    input: &str,
+) -> Result<String> {
+    Ok(generate_response(connection, model, system_prompt, input)
+        .await?
+        .text())
+}
+
+/// Like [`generate_once`], with the whole response for its token usage.
+pub async fn generate_response(
+    connection: &Connection,
+    model: GeminiModel,
+    system_prompt: Option<&str>,
+    input: &str,
+) -> Result<GenerationResponse> {
+    generate_with_files(connection, model, system_prompt, input, &[]).await
+}
+
+/// Like [`generate_response`], with `files` sent inline before the input.
+pub async fn generate_with_files(
+    connection: &Connection,
+    model: GeminiModel,
+    system_prompt: Option<&str>,
+    input: &str,
+    files: &[Attachment],
+) -> Result<GenerationResponse> {
+    let request = GenerateRequest {
+        system_prompt,
+        input,
+        files,
+        ..Default::default()
+    };
+    generate(connection, model, request).await
+}
+
+/// Like [`generate_response`], with the model allowed to search the web.
+pub async fn generate_grounded(
+    connection: &Connection,
+    model: GeminiModel,
+    system_prompt: Option<&str>,
+    input: &str,
+) -> Result<GenerationResponse> {
+    let request = GenerateRequest {
+        system_prompt,
+        input,
+        tools: vec![Tool::google_search()],
+        ..Default::default()
+    };
+    generate(connection, model, request).await
+}
+
+/// Like [`generate_response`], with the sampling and output settings of `config`.
+pub async fn generate_configured(
+    connection: &Connection,
+    model: GeminiModel,
+    system_prompt: Option<&str>,
+    input: &str,
+    config: GenerationConfig,
+) -> Result<GenerationResponse> {
+    let request = GenerateRequest {
+        system_prompt,
+        input,
+        config: Some(config),
+        ..Default::default()
+    };
+    generate(connection, model, request).await
+}
+
+/// Asks an image model to change `image` as `instruction` says. The answer holds the
+/// edited image, and maybe a few words about it.
+pub async fn generate_image_edit(
+    connection: &Connection,
+    model: GeminiModel,
+    image: Part,
+    instruction: &str,
+) -> Result<GenerationResponse> {
+    let config = GenerationConfig {
+        response_modalities: Some(vec![Modality::Text, Modality::Image]),
+        ..Default::default()
+    };
+    let request = GenerateRequest {
+        input: instruction,
+        images: vec![image],
+        config: Some(config),
+        ..Default::default()
+    };
+    generate(connection, model, request).await
+}
+
+/// Like [`generate_configured`] with `files`, streamed: `on_text` gets the answer as it
+/// arrives, without the thoughts. Returns the token usage of the whole response.
+pub async fn generate_streamed(
+    connection: &Connection,
+    model: GeminiModel,
+    system_prompt: Option<&str>,
+    input: &str,
+    files: &[Attachment],
+    config: GenerationConfig,
+    mut on_text: impl FnMut(&str),
+) -> Result<Option<UsageMetadata>> {
+    let system_prompt = system_prompt.filter(|p| !p.trim().is_empty());
+    let mut usage = None;
+    let mut take = |response: GenerationResponse| {
+        if response.usage_metadata.is_some() {
+            usage = response.usage_metadata;
+        }
+        let parts = response
+            .candidates
+            .into_iter()
+            .next()
+            .and_then(|candidate| candidate.content.parts);
+        for part in parts.into_iter().flatten() {
+            if let Part::Text { text, thought, .. } = part {
+                if !thought.unwrap_or(false) {
+                    on_text(&text);
+                }
+            }
+        }
+    };
+    match connection.auth_method {
+        AuthMethod::ApiKey => {
+            if connection.api_key.is_empty() {
+                return Err(anyhow!("API key not set."));
+            }
+
+            let client = connection.client(model)?;
+
+            let mut builder = client.generate_content();
+            let contents =
+                build_history::<Leading>(&client, &[], 0, Some((input, files)), None, None).await?;
+            builder.contents.extend(contents);
+            if let Some(system_prompt) = system_prompt {
+                builder = builder.with_system_instruction(system_prompt);
+            }
+            let mut stream = builder
+                .with_generation_config(config)
+                .execute_stream()
+                .await?
+                .into_stream();
+            while let Some(response) = stream.next().await {
+                take(response?);
+            }
+        }
+        AuthMethod::CodeAssist => {
+            if connection.oauth_token.is_empty() || connection.project_id.is_empty() {
+                return Err(anyhow!(
+                    "OAuth token or Project ID not set. Please login in settings."
+                ));
+            }
+
+            let mut client = gemini_code_assist_adapter::CodeAssistClient::new(
+                connection.oauth_token.clone(),
+                connection.project_id.clone(),
+            )
+            .with_model(model.to_string());
+            match client.load_code_assist().await {
+                Ok(effective_proj) => client.set_project_id(effective_proj),
+                Err(e) => log::warn!("Code Assist handshake failed: {e}"),
+            }
+
+            let dummy_client = Gemini::new("")?;
+            let request = gemini_rust::GenerateContentRequest {
+                contents: build_history::<Leading>(
+                    &dummy_client,
+                    &[],
+                    0,
+                    Some((input, files)),
+                    None,
+                    None,
+                )
+                .await?,
+                generation_config: Some(config),
+                safety_settings: None,
+                tools: None,
+                tool_config: None,
+                system_instruction: system_prompt.map(Content::text),
+                cached_content: None,
+            };
+            let mut stream = client.generate_content_stream(&request).await?;
+            while let Some(response) = stream.next().await {
+                take(response?);
+            }
+        }
+    }
+    Ok(usage)
+}
+
+/// A single request outside of any chat.
+#[derive(Default)]
+pub struct GenerateRequest<'a> {
+    pub system_prompt: Option<&'a str>,
+    pub input: &'a str,
+    /// Sent inline before the input
+    pub files: &'a [Attachment],
+    /// Sent before the files, like an image to edit
+    pub images: Vec<Part>,
+    pub tools: Vec<Tool>,
+    pub config: Option<GenerationConfig>,
+}
+
+/// Sends `request` to `model` and returns the whole response.
+pub async fn generate(
+    connection: &Connection,
+    model: GeminiModel,
+    request: GenerateRequest<'_>,
+) -> Result<GenerationResponse> {
+    let GenerateRequest {
+        system_prompt,
+        input,
+        files,
+        images,
+        tools,
+        config,
+    } = request;
+    let system_prompt = system_prompt.filter(|p| !p.trim().is_empty());
+    let text_only = files.is_empty() && images.is_empty();
+    let leading = [Leading(images)];
+    let response = match connection.auth_method {
+        AuthMethod::ApiKey => {
+            if connection.api_key.is_empty() {
+                return Err(anyhow!("API key not set."));
+            }
+
+            let client = connection.client(model)?;
+
+            let mut builder = client.generate_content();
+            if text_only {
+                builder = builder.with_user_message(input);
+            } else {
+                let contents =
+                    build_history(&client, &leading, 0, Some((input, files)), None, None).await?;
+                builder.contents.extend(contents);
+            }
+            if let Some(system_prompt) = system_prompt {
+                builder = builder.with_system_instruction(system_prompt);
+            }
+            for tool in tools {
+                builder = builder.with_tool(tool);
+            }
+            if let Some(config) = config {
+                builder = builder.with_generation_config(config);
+            }
+            builder.execute().await?
+        }
+        AuthMethod::CodeAssist => {
+            if connection.oauth_token.is_empty() || connection.project_id.is_empty() {
+                return Err(anyhow!(
+                    "OAuth token or Project ID not set. Please login in settings."
+                ));
+            }
+
+            let mut client = gemini_code_assist_adapter::CodeAssistClient::new(
+                connection.oauth_token.clone(),
+                connection.project_id.clone(),
+            )
+            .with_model(model.to_string());
+            match client.load_code_assist().await {
+                Ok(effective_proj) => client.set_project_id(effective_proj),
+                Err(e) => log::warn!("Code Assist handshake failed: {e}"),
+            }
+
+            let contents = if text_only {
+                vec![Content {
+                    parts: Some(vec![Part::Text {
+                        text: input.to_owned(),
+                        thought: None,
+                        thought_signature: None,
+                    }]),
+                    role: Some(Role::User),
+                }]
+            } else {
+                let dummy_client = Gemini::new("")?;
+                build_history(&dummy_client, &leading, 0, Some((input, files)), None, None).await?
+            };
+            let request = gemini_rust::GenerateContentRequest {
+                contents,
+                generation_config: config,
+                safety_settings: None,
+                tools: (!tools.is_empty()).then_some(tools),
+                tool_config: None,
+                system_instruction: system_prompt.map(Content::text),
+                cached_content: None,
+            };
+            client.generate_content(&request).await?
+        }
+    };
+
+    Ok(response)
+}
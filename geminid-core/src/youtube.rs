@@ -0,0 +1,162 @@
+use gemini_rust::{FileData, Part};
+use std::ops::Range;
+
+/// What YouTube videos are sent as, the API fetches them by URL.
+const MIME: &str = "video/*";
+
+/// A public YouTube video the model watches itself, optionally only a part of it.
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct YouTubeVideo {
+    /// The 11 characters after `watch?v=`
+    pub id: String,
+    /// Seconds into the video the clip starts at
+    pub start: Option<u32>,
+    /// Seconds into the video the clip ends at
+    pub end: Option<u32>,
+}
+
+/// Seconds in `t=`, as `90`, `90s` or `1h2m30s`.
+fn parse_time(value: &str) -> Option<u32> {
+    if let Ok(secs) = value.parse() {
+        return Some(secs);
+    }
+    let mut total = 0;
+    let mut number = String::new();
+    for c in value.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let unit = match c {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        total += number.parse::<u32>().ok()? * unit;
+        number.clear();
+    }
+    number.is_empty().then_some(total)
+}
+
+/// `secs` as `m:ss`, like the lengths of recordings.
+pub fn format_time(secs: u32) -> String {
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+fn is_video_id(id: &str) -> bool {
+    id.len() == 11
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+impl YouTubeVideo {
+    /// The video a link points to, with the time it starts at if it has one.
+    pub fn parse(link: &str) -> Option<Self> {
+        let url = url::Url::parse(link).ok()?;
+        let host = url
+            .host_str()?
+            .trim_start_matches("www.")
+            .trim_start_matches("m.");
+        let mut segments = url.path_segments()?;
+        let id = match host {
+            "youtu.be" => segments.next()?.to_owned(),
+            "youtube.com" | "music.youtube.com" => match segments.next()? {
+                "watch" => url
+                    .query_pairs()
+                    .find(|(key, _)| key == "v")
+                    .map(|(_, value)| value.into_owned())?,
+                "shorts" | "live" | "embed" => segments.next()?.to_owned(),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        if !is_video_id(&id) {
+            return None;
+        }
+        let start = url
+            .query_pairs()
+            .find(|(key, _)| key == "t" || key == "start")
+            .and_then(|(_, value)| parse_time(&value))
+            .filter(|&secs| secs > 0);
+        Some(Self {
+            id,
+            start,
+            end: None,
+        })
+    }
+
+    pub fn url(&self) -> String {
+        format!("https://www.youtube.com/watch?v={}", self.id)
+    }
+
+    /// Shown on the attachment, e.g. `YouTube dQw4w9WgXcQ 0:40–1:20`.
+    pub fn label(&self) -> String {
+        let clip = match (self.start, self.end) {
+            (None, None) => String::new(),
+            (start, end) => format!(
+                " {}–{}",
+                format_time(start.unwrap_or(0)),
+                end.map(format_time).unwrap_or_default()
+            ),
+        };
+        format!("YouTube {}{clip}", self.id)
+    }
+
+    /// The part of the request, with the clip's offsets as the API spells them.
+    pub fn part(&self) -> Part {
+        let file_data = FileData {
+            file_uri: self.url(),
+            mime_type: MIME.to_owned(),
+        };
+        if self.start.is_none() && self.end.is_none() {
+            return Part::FileData { file_data };
+        }
+        let mut metadata = serde_json::Map::new();
+        if let Some(start) = self.start {
+            metadata.insert("startOffset".into(), format!("{start}s").into());
+        }
+        if let Some(end) = self.end {
+            metadata.insert("endOffset".into(), format!("{end}s").into());
+        }
+        let part = serde_json::json!({
+            "fileData": { "fileUri": file_data.file_uri, "mimeType": MIME },
+            "videoMetadata": metadata,
+        });
+        serde_json::from_value(part)
+            .map_err(|e| log::warn!("sending the whole video, the clip was ignored: {e}"))
+            .unwrap_or(Part::FileData { file_data })
+    }
+}
+
+/// The YouTube links in `text`, with where they are.
+pub fn find_links(text: &str) -> Vec<(Range<usize>, YouTubeVideo)> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+    for word in text.split_inclusive(char::is_whitespace) {
+        let start = offset;
+        offset += word.len();
+        let link = word.trim_end().trim_end_matches(['.', ',', ')', '>']);
+        if !link.starts_with("http") {
+            continue;
+        }
+        if let Some(video) = YouTubeVideo::parse(link) {
+            found.push((start..start + link.len(), video));
+        }
+    }
+    found
+}
+
+/// Takes the YouTube links out of `chatbox`, to be sent as videos instead.
+pub fn take_links(chatbox: &mut String) -> Vec<YouTubeVideo> {
+    let mut videos = Vec::new();
+    for (range, video) in find_links(chatbox).into_iter().rev() {
+        chatbox.replace_range(range, "");
+        videos.push(video);
+    }
+    videos.reverse();
+    *chatbox = chatbox.trim().to_owned();
+    videos
+}
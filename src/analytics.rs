@@ -0,0 +1,225 @@
+use crate::{
+    chat::Message,
+    i18n::{date_locale, format_number, tr},
+    widgets::GeminiModel,
+};
+use chrono::{DateTime, Local, Timelike, Utc};
+use eframe::egui::{self, vec2, RichText};
+use std::time::Duration;
+
+/// How one model was used.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+struct ModelUsage {
+    model: GeminiModel,
+    responses: u32,
+    errors: u32,
+    /// Summed over the responses that were timed
+    latency: Duration,
+    timed: u32,
+}
+
+impl ModelUsage {
+    fn average_latency(&self) -> Option<Duration> {
+        (self.timed > 0).then(|| self.latency / self.timed)
+    }
+}
+
+/// Usage patterns gathered on this machine only, nothing of it leaves it.
+#[derive(Debug, Default, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Analytics {
+    #[serde(skip)]
+    pub open: bool,
+    /// When collection started, or was last reset
+    since: Option<DateTime<Utc>>,
+    models: Vec<ModelUsage>,
+    /// Responses by the local hour they were asked for
+    hours: [u32; 24],
+}
+
+impl Analytics {
+    #[inline]
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    /// Counts a finished response.
+    pub fn record(&mut self, message: &Message) {
+        if message.is_user() || message.is_marker {
+            return;
+        }
+        self.since.get_or_insert(message.time);
+        let usage = match self.models.iter().position(|u| u.model == message.model) {
+            Some(i) => &mut self.models[i],
+            None => {
+                self.models.push(ModelUsage {
+                    model: message.model,
+                    responses: 0,
+                    errors: 0,
+                    latency: Duration::ZERO,
+                    timed: 0,
+                });
+                self.models.last_mut().expect("just pushed")
+            }
+        };
+        usage.responses += 1;
+        if message.is_error {
+            usage.errors += 1;
+        } else if let Some(time) = message.generation_time {
+            usage.latency += time;
+            usage.timed += 1;
+        }
+        let hour = message.time.with_timezone(&Local).hour() as usize;
+        self.hours[hour] += 1;
+    }
+
+    fn clear(&mut self) {
+        *self = Self {
+            open: self.open,
+            ..Default::default()
+        };
+    }
+
+    fn total(&self) -> u32 {
+        self.models.iter().map(|u| u.responses).sum()
+    }
+
+    fn average_latency(&self) -> Option<Duration> {
+        let timed: u32 = self.models.iter().map(|u| u.timed).sum();
+        let latency: Duration = self.models.iter().map(|u| u.latency).sum();
+        (timed > 0).then(|| latency / timed)
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, enabled: &mut bool) {
+        if !self.open {
+            return;
+        }
+        let mut open = true;
+        egui::Window::new(tr!("analytics-title"))
+            .open(&mut open)
+            .default_size([480.0, 420.0])
+            .show(ctx, |ui| self.show_inner(ui, enabled));
+        self.open = open;
+    }
+
+    fn show_inner(&mut self, ui: &mut egui::Ui, enabled: &mut bool) {
+        crate::widgets::toggle_setting(
+            ui,
+            enabled,
+            &tr!("analytics-enabled"),
+            &tr!("analytics-enabled-help"),
+        );
+        ui.separator();
+
+        let total = self.total();
+        if total == 0 {
+            ui.weak(if *enabled {
+                tr!("analytics-empty")
+            } else {
+                tr!("analytics-disabled")
+            });
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("analytics-responses", count = total));
+            if let Some(since) = self.since {
+                let since = since
+                    .with_timezone(&Local)
+                    .format_localized(&tr!("date-short"), date_locale())
+                    .to_string();
+                ui.weak(tr!("analytics-since", date = since));
+            }
+        });
+        if let Some(latency) = self.average_latency() {
+            ui.label(tr!(
+                "analytics-average-latency",
+                seconds = format_number(latency.as_secs_f64(), 1)
+            ));
+        }
+
+        ui.add_space(8.0);
+        ui.strong(tr!("analytics-models"));
+        let mut models: Vec<&ModelUsage> = self.models.iter().collect();
+        models.sort_by(|a, b| b.responses.cmp(&a.responses));
+        egui::Grid::new("analytics_models")
+            .num_columns(4)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.weak(tr!("analytics-model"));
+                ui.weak(tr!("analytics-count"));
+                ui.weak(tr!("analytics-latency"));
+                ui.weak(tr!("analytics-errors"));
+                ui.end_row();
+                for usage in models {
+                    ui.label(usage.model.to_string());
+                    let share = usage.responses as f32 / total as f32;
+                    ui.add(
+                        egui::ProgressBar::new(share)
+                            .desired_width(120.0)
+                            .text(usage.responses.to_string()),
+                    );
+                    ui.label(usage.average_latency().map_or_else(
+                        || "—".to_owned(),
+                        |l| format!("{}s", format_number(l.as_secs_f64(), 1)),
+                    ));
+                    ui.label(usage.errors.to_string());
+                    ui.end_row();
+                }
+            });
+
+        ui.add_space(8.0);
+        ui.strong(tr!("analytics-hours"));
+        self.show_hours(ui);
+
+        ui.add_space(8.0);
+        if ui.button(tr!("analytics-clear")).clicked() {
+            self.clear();
+        }
+    }
+
+    /// A bar per hour of the day, the busiest one highlighted.
+    fn show_hours(&self, ui: &mut egui::Ui) {
+        let busiest = (0..24).max_by_key(|&h| self.hours[h]).unwrap_or(0);
+        let max = self.hours[busiest].max(1) as f32;
+        let width = ui.available_width().min(480.0);
+        let (response, painter) = ui.allocate_painter(vec2(width, 80.0), egui::Sense::hover());
+        let rect = response.rect;
+        let bar = rect.width() / 24.0;
+        let visuals = ui.visuals();
+        for (hour, &count) in self.hours.iter().enumerate() {
+            let height = (rect.height() - 14.0) * count as f32 / max;
+            let left = rect.left() + bar * hour as f32;
+            let bar_rect = egui::Rect::from_min_max(
+                egui::pos2(left + 1.0, rect.bottom() - 14.0 - height),
+                egui::pos2(left + bar - 1.0, rect.bottom() - 14.0),
+            );
+            let color = if hour == busiest {
+                visuals.selection.bg_fill
+            } else {
+                visuals.widgets.inactive.bg_fill
+            };
+            painter.rect_filled(bar_rect, 1.0, color);
+            if hour % 6 == 0 {
+                painter.text(
+                    egui::pos2(left, rect.bottom()),
+                    egui::Align2::LEFT_BOTTOM,
+                    format!("{hour:02}"),
+                    egui::FontId::proportional(10.0),
+                    visuals.weak_text_color(),
+                );
+            }
+        }
+        if let Some(pos) = response.hover_pos() {
+            let hour = (((pos.x - rect.left()) / bar) as usize).min(23);
+            response.on_hover_text(tr!(
+                "analytics-hour",
+                hour = format!("{hour:02}:00"),
+                count = self.hours[hour]
+            ));
+        }
+        ui.label(
+            RichText::new(tr!("analytics-busiest", hour = format!("{busiest:02}:00"))).small(),
+        );
+    }
+}
@@ -0,0 +1,356 @@
+use crate::i18n::tr;
+use anyhow::{anyhow, Result};
+use eframe::egui;
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    path::{Component, Path, PathBuf},
+};
+use tokio::sync::oneshot;
+
+/// Archives aren't extracted past this many bytes, in case one is a zip bomb.
+const MAX_EXTRACTED_SIZE: u64 = 1024 * 1024 * 1024;
+
+fn extracted_dir() -> Option<PathBuf> {
+    eframe::storage_dir(crate::TITLE).map(|dir| dir.join("extracted"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl Kind {
+    fn of(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if name.ends_with(".tar") {
+            Some(Self::Tar)
+        } else {
+            None
+        }
+    }
+}
+
+/// Whether `path` is an archive that can be extracted.
+pub fn is_archive(path: &Path) -> bool {
+    Kind::of(path).is_some()
+}
+
+/// The path an entry is extracted to, `None` when it would land outside of the folder.
+fn safe_path(path: &Path) -> Option<PathBuf> {
+    let safe = path
+        .components()
+        .all(|part| matches!(part, Component::Normal(_) | Component::CurDir));
+    (safe && path.components().next().is_some()).then(|| path.to_owned())
+}
+
+/// A file in an archive.
+struct Entry {
+    path: PathBuf,
+    size: u64,
+    /// Whether the model can read it once it's extracted
+    supported: bool,
+    selected: bool,
+}
+
+impl Entry {
+    fn new(path: PathBuf, size: u64) -> Self {
+        let supported = crate::file_handler::is_supported(&path);
+        Self {
+            path,
+            size,
+            supported,
+            selected: supported,
+        }
+    }
+}
+
+fn tar_entries<R: Read>(reader: R) -> Result<Vec<Entry>> {
+    let mut archive = tar::Archive::new(reader);
+    let mut entries = Vec::new();
+    for entry in archive.entries()? {
+        let entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        if let Some(path) = safe_path(&entry.path()?) {
+            entries.push(Entry::new(path, entry.size()));
+        }
+    }
+    Ok(entries)
+}
+
+/// The files in the archive at `path`.
+fn list(path: &Path) -> Result<Vec<Entry>> {
+    let file = BufReader::new(File::open(path)?);
+    let mut entries = match Kind::of(path).ok_or_else(|| anyhow!("not an archive"))? {
+        Kind::Zip => {
+            let mut archive = zip::ZipArchive::new(file)?;
+            let mut entries = Vec::new();
+            for i in 0..archive.len() {
+                let file = archive.by_index(i)?;
+                if file.is_dir() {
+                    continue;
+                }
+                if let Some(path) = file.enclosed_name() {
+                    entries.push(Entry::new(path, file.size()));
+                }
+            }
+            entries
+        }
+        Kind::Tar => tar_entries(file)?,
+        Kind::TarGz => tar_entries(flate2::read::GzDecoder::new(file))?,
+    };
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(entries)
+}
+
+/// Copies `reader` to `dest/path`, counting the bytes against `budget`.
+fn write_entry(
+    reader: &mut impl Read,
+    dest: &Path,
+    path: &Path,
+    budget: &mut u64,
+) -> Result<PathBuf> {
+    let target = dest.join(path);
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut out = File::create(&target)?;
+    let written = std::io::copy(&mut reader.take(*budget + 1), &mut out)?;
+    if written > *budget {
+        drop(out);
+        let _ = std::fs::remove_file(&target);
+        return Err(anyhow!("The archive unpacks to more than 1 GB."));
+    }
+    *budget -= written;
+    Ok(target)
+}
+
+fn tar_extract<R: Read>(reader: R, wanted: &[PathBuf], dest: &Path) -> Result<Vec<PathBuf>> {
+    let mut archive = tar::Archive::new(reader);
+    let mut budget = MAX_EXTRACTED_SIZE;
+    let mut extracted = Vec::new();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let Some(path) = safe_path(&entry.path()?) else {
+            continue;
+        };
+        if entry.header().entry_type().is_file() && wanted.contains(&path) {
+            extracted.push(write_entry(&mut entry, dest, &path, &mut budget)?);
+        }
+    }
+    Ok(extracted)
+}
+
+/// Extracts the `wanted` files of the archive at `path` into the app data folder.
+fn extract(path: &Path, wanted: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let stem = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let stem = stem
+        .trim_end_matches(".zip")
+        .trim_end_matches(".tgz")
+        .trim_end_matches(".gz")
+        .trim_end_matches(".tar");
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let dest = extracted_dir()
+        .ok_or_else(|| anyhow!("no data folder"))?
+        .join(format!("{stem}-{stamp}"));
+    let file = BufReader::new(File::open(path)?);
+    let extracted = match Kind::of(path).ok_or_else(|| anyhow!("not an archive"))? {
+        Kind::Zip => {
+            let mut archive = zip::ZipArchive::new(file)?;
+            let mut budget = MAX_EXTRACTED_SIZE;
+            let mut extracted = Vec::new();
+            for i in 0..archive.len() {
+                let mut file = archive.by_index(i)?;
+                let Some(path) = file.enclosed_name() else {
+                    continue;
+                };
+                if !file.is_dir() && wanted.contains(&path) {
+                    extracted.push(write_entry(&mut file, &dest, &path, &mut budget)?);
+                }
+            }
+            extracted
+        }
+        Kind::Tar => tar_extract(file, wanted, &dest)?,
+        Kind::TarGz => tar_extract(flate2::read::GzDecoder::new(file), wanted, &dest)?,
+    };
+    log::info!(
+        "extracted {} file(s) of {} to {}",
+        extracted.len(),
+        path.display(),
+        dest.display()
+    );
+    Ok(extracted)
+}
+
+/// Runs `job` on a thread and hands its result to a receiver, repainting once it's done.
+fn spawn<T: Send + 'static>(
+    ctx: &egui::Context,
+    job: impl FnOnce() -> Result<T> + Send + 'static,
+) -> oneshot::Receiver<Result<T, String>> {
+    let (tx, rx) = oneshot::channel();
+    let ctx = ctx.clone();
+    std::thread::spawn(move || {
+        let _ = tx.send(job().map_err(|e| {
+            log::error!("failed to read the archive: {e}");
+            e.to_string()
+        }));
+        ctx.request_repaint();
+    });
+    rx
+}
+
+/// Lets the user pick which files of an attached archive to extract and attach instead.
+pub struct ArchivePicker {
+    /// The attached archive
+    pub path: PathBuf,
+    listing: Option<oneshot::Receiver<Result<Vec<Entry>, String>>>,
+    entries: Vec<Entry>,
+    extracting: Option<oneshot::Receiver<Result<Vec<PathBuf>, String>>>,
+    error: Option<String>,
+}
+
+/// What became of the archive.
+pub enum Picked {
+    /// These files replace the archive in the attachments
+    Extracted(Vec<PathBuf>),
+    /// The archive stays attached as it is
+    Kept,
+}
+
+impl ArchivePicker {
+    pub fn new(ctx: &egui::Context, path: PathBuf) -> Self {
+        let archive = path.clone();
+        Self {
+            path,
+            listing: Some(spawn(ctx, move || list(&archive))),
+            entries: Vec::new(),
+            extracting: None,
+            error: None,
+        }
+    }
+
+    fn poll(&mut self) -> Option<Vec<PathBuf>> {
+        if let Some(result) = self.listing.as_mut().and_then(|rx| rx.try_recv().ok()) {
+            self.listing = None;
+            match result {
+                Ok(entries) => self.entries = entries,
+                Err(e) => self.error = Some(e),
+            }
+        }
+        let result = self.extracting.as_mut()?.try_recv().ok()?;
+        self.extracting = None;
+        match result {
+            Ok(files) => return Some(files),
+            Err(e) => self.error = Some(e),
+        }
+        None
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<Picked> {
+        if let Some(files) = self.poll() {
+            return Some(Picked::Extracted(files));
+        }
+        let mut open = true;
+        let mut picked = None;
+        let name = self
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        egui::Window::new(tr!("archive-title", name = name))
+            .id(egui::Id::new("archive_picker").with(&self.path))
+            .open(&mut open)
+            .default_size([460.0, 420.0])
+            .show(ctx, |ui| picked = self.show_inner(ui));
+        if !open {
+            return Some(Picked::Kept);
+        }
+        picked
+    }
+
+    fn show_inner(&mut self, ui: &mut egui::Ui) -> Option<Picked> {
+        ui.weak(tr!("archive-help"));
+        if let Some(error) = &self.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+        if self.listing.is_some() {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(tr!("archive-reading"));
+            });
+            return None;
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button(tr!("archive-select-supported")).clicked() {
+                for entry in &mut self.entries {
+                    entry.selected = entry.supported;
+                }
+            }
+            if ui.button(tr!("archive-select-none")).clicked() {
+                for entry in &mut self.entries {
+                    entry.selected = false;
+                }
+            }
+        });
+        ui.separator();
+        egui::ScrollArea::vertical()
+            .max_height(ui.available_height() - 48.0)
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                for entry in &mut self.entries {
+                    ui.horizontal(|ui| {
+                        let label = entry.path.display().to_string();
+                        ui.add_enabled(
+                            entry.supported,
+                            egui::Checkbox::new(&mut entry.selected, label),
+                        )
+                        .on_disabled_hover_text(tr!("archive-unsupported"));
+                        ui.weak(format!("{:.1} KB", entry.size as f64 / 1024.0));
+                    });
+                }
+            });
+        ui.separator();
+
+        let wanted: Vec<PathBuf> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.selected && entry.supported)
+            .map(|entry| entry.path.clone())
+            .collect();
+        let mut picked = None;
+        ui.horizontal(|ui| {
+            if self.extracting.is_some() {
+                ui.spinner();
+                ui.label(tr!("archive-extracting"));
+                return;
+            }
+            if ui
+                .add_enabled(
+                    !wanted.is_empty(),
+                    egui::Button::new(tr!("archive-extract", count = wanted.len())),
+                )
+                .clicked()
+            {
+                let archive = self.path.clone();
+                self.error = None;
+                self.extracting = Some(spawn(ui.ctx(), move || extract(&archive, &wanted)));
+            }
+            if ui.button(tr!("archive-keep")).clicked() {
+                picked = Some(Picked::Kept);
+            }
+        });
+        picked
+    }
+}
@@ -0,0 +1,270 @@
+use crate::{chat::Chat, i18n::tr};
+use eframe::egui;
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    io::Read,
+    path::{Path, PathBuf},
+};
+use tokio::sync::oneshot::{self, error::TryRecvError};
+
+/// Only the beginning of large text files is indexed.
+const MAX_EXTRACT_BYTES: u64 = 256 * 1024;
+
+const MAX_RESULTS: usize = 200;
+
+struct FileEntry {
+    chat_idx: usize,
+    msg_idx: usize,
+    path: PathBuf,
+    name: String,
+}
+
+/// A message that carried a file matching the query.
+pub struct FileHit {
+    pub chat_idx: usize,
+    pub msg_idx: usize,
+    pub name: String,
+    /// Extracted text of the file, when the match was inside it rather than in the name
+    text_match: Option<PathBuf>,
+}
+
+/// Index over attachment file names and the text of text-like attachments.
+#[derive(Default)]
+pub struct FileIndex {
+    entries: Vec<FileEntry>,
+    /// Extracted text per path, read from disk once. `None` for binary or missing files.
+    extracted: HashMap<PathBuf, Option<String>>,
+    /// Text of new attachments being read on a thread
+    extracting: Option<oneshot::Receiver<Vec<(PathBuf, Option<String>)>>>,
+    stamp: u64,
+}
+
+fn is_text_like(mime: &str) -> bool {
+    mime.starts_with("text/")
+        || matches!(
+            mime,
+            "application/json" | "application/xml" | "application/javascript" | "application/toml"
+        )
+}
+
+fn extract_text(path: &Path) -> Option<String> {
+    let mut bytes = Vec::new();
+    std::fs::File::open(path)
+        .and_then(|file| file.take(MAX_EXTRACT_BYTES).read_to_end(&mut bytes))
+        .map_err(|e| log::debug!("can't index `{}`: {e}", path.display()))
+        .ok()?;
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+impl FileIndex {
+    fn stamp(chats: &[Chat]) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        for chat in chats {
+            chat.id.hash(&mut hasher);
+            chat.messages.len().hash(&mut hasher);
+            for msg in &chat.messages {
+                for file in &msg.files {
+                    file.path.hash(&mut hasher);
+                }
+            }
+        }
+        hasher.finish()
+    }
+
+    /// Rebuilds the index if attachments changed, the text of new files is read on a
+    /// thread and repaints once it's in. Returns whether the index changed.
+    pub fn refresh(&mut self, ctx: &egui::Context, chats: &[Chat]) -> bool {
+        let mut changed = false;
+        if let Some(rx) = &mut self.extracting {
+            match rx.try_recv() {
+                Ok(texts) => {
+                    self.extracted.extend(texts);
+                    self.extracting = None;
+                    changed = true;
+                }
+                Err(TryRecvError::Empty) => (),
+                Err(TryRecvError::Closed) => self.extracting = None,
+            }
+        }
+        let stamp = Self::stamp(chats);
+        // files added meanwhile are picked up once the running extraction is done
+        if stamp == self.stamp || self.extracting.is_some() {
+            return changed;
+        }
+
+        self.entries.clear();
+        let mut to_read = Vec::new();
+        for (chat_idx, chat) in chats.iter().enumerate() {
+            for (msg_idx, msg) in chat.messages.iter().enumerate() {
+                for file in &msg.files {
+                    if !self.extracted.contains_key(&file.path) && !to_read.contains(&file.path) {
+                        if is_text_like(&file.mime) {
+                            to_read.push(file.path.clone());
+                        } else {
+                            self.extracted.insert(file.path.clone(), None);
+                        }
+                    }
+                    self.entries.push(FileEntry {
+                        chat_idx,
+                        msg_idx,
+                        path: file.path.clone(),
+                        name: file
+                            .path
+                            .file_name()
+                            .map(|n| n.to_string_lossy().into_owned())
+                            .unwrap_or_else(|| file.path.display().to_string()),
+                    });
+                }
+            }
+        }
+        log::debug!(
+            "indexed {} attachment(s), reading {} new",
+            self.entries.len(),
+            to_read.len()
+        );
+        if !to_read.is_empty() {
+            let (tx, rx) = oneshot::channel();
+            let ctx = ctx.clone();
+            std::thread::spawn(move || {
+                let texts = to_read
+                    .into_iter()
+                    .map(|path| {
+                        let text = extract_text(&path);
+                        (path, text)
+                    })
+                    .collect();
+                let _ = tx.send(texts);
+                ctx.request_repaint();
+            });
+            self.extracting = Some(rx);
+        }
+        self.stamp = stamp;
+        true
+    }
+
+    /// Files whose name or text contains every term. An empty query lists every attachment.
+    pub fn query(&self, terms: &[String]) -> Vec<FileHit> {
+        let contains_all =
+            |haystack: &str| terms.iter().all(|t| haystack.contains(t.as_str()));
+        self.entries
+            .iter()
+            .filter_map(|entry| {
+                let text_match = if contains_all(&entry.name.to_lowercase()) {
+                    None
+                } else {
+                    let text = self.extracted.get(&entry.path)?.as_deref()?;
+                    if !contains_all(&text.to_lowercase()) {
+                        return None;
+                    }
+                    Some(entry.path.clone())
+                };
+                Some(FileHit {
+                    chat_idx: entry.chat_idx,
+                    msg_idx: entry.msg_idx,
+                    name: entry.name.clone(),
+                    text_match,
+                })
+            })
+            .take(MAX_RESULTS)
+            .collect()
+    }
+
+    fn text(&self, path: &Path) -> Option<&str> {
+        self.extracted.get(path)?.as_deref()
+    }
+}
+
+fn preview_line(ui: &mut egui::Ui, prefix: &str, content: &str) {
+    let line = content.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+    ui.add_enabled(
+        false,
+        egui::Label::new(format!("{prefix}: {line}"))
+            .selectable(false)
+            .truncate(),
+    );
+}
+
+/// Lists hits grouped by chat with the surrounding messages as preview.
+/// Returns the message to jump to when a hit is clicked.
+pub fn show_results(
+    ui: &mut egui::Ui,
+    chats: &[Chat],
+    index: &FileIndex,
+    hits: &[FileHit],
+    terms: &[String],
+) -> Option<(usize, usize)> {
+    if hits.is_empty() {
+        ui.weak("No matching attachments");
+        return None;
+    }
+    let chat_count = {
+        let mut ids: Vec<usize> = hits.iter().map(|h| h.chat_idx).collect();
+        ids.dedup();
+        ids.len()
+    };
+    ui.weak(format!("{} file(s) in {chat_count} chat(s)", hits.len()));
+
+    let mut jump = None;
+    egui::ScrollArea::vertical().auto_shrink(false).show(ui, |ui| {
+        let mut last_chat = None;
+        for hit in hits {
+            let Some(chat) = chats.get(hit.chat_idx) else {
+                continue;
+            };
+            if last_chat != Some(hit.chat_idx) {
+                last_chat = Some(hit.chat_idx);
+                ui.add_space(4.0);
+                ui.strong(chat.title());
+            }
+
+            let resp = egui::Frame::group(ui.style())
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width());
+                    ui.horizontal(|ui| {
+                        ui.label(format!("📎 {}", hit.name));
+                        if let Some(msg) = chat.messages.get(hit.msg_idx) {
+                            ui.weak(
+                                msg.time
+                                    .with_timezone(&chrono::Local)
+                                    .format("%Y-%m-%d %H:%M")
+                                    .to_string(),
+                            );
+                        }
+                    });
+                    if let Some(text) = hit.text_match.as_deref().and_then(|p| index.text(p)) {
+                        ui.add(
+                            egui::Label::new(crate::search::snippet(ui, text, terms))
+                                .selectable(false),
+                        );
+                    }
+                    if let Some(msg) = hit
+                        .msg_idx
+                        .checked_sub(1)
+                        .and_then(|i| chat.messages.get(i))
+                    {
+                        preview_line(ui, if msg.is_user() { "You" } else { "Model" }, &msg.content);
+                    }
+                    if let Some(msg) = chat.messages.get(hit.msg_idx) {
+                        preview_line(ui, "Sent with", &msg.content);
+                    }
+                    if let Some(msg) = chat.messages.get(hit.msg_idx + 1) {
+                        preview_line(ui, if msg.is_user() { "You" } else { "Model" }, &msg.content);
+                    }
+                })
+                .response;
+            let resp = ui.interact(
+                resp.rect,
+                ui.id().with(("file_hit", hit.chat_idx, hit.msg_idx, &hit.name)),
+                egui::Sense::click(),
+            );
+            if resp.hovered() {
+                ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+            }
+            if resp.clicked() {
+                jump = Some((hit.chat_idx, hit.msg_idx));
+            }
+        }
+    });
+    jump
+}
@@ -0,0 +1,413 @@
+use crate::i18n::tr;
+use base64::Engine;
+use eframe::egui::{self, Id};
+use gemini_rust::{Part, SpeechConfig};
+use std::{sync::Arc, time::Duration};
+
+/// Prebuilt voices of the speech models.
+const VOICES: [&str; 30] = [
+    "Zephyr",
+    "Puck",
+    "Charon",
+    "Kore",
+    "Fenrir",
+    "Leda",
+    "Orus",
+    "Aoede",
+    "Callirrhoe",
+    "Autonoe",
+    "Enceladus",
+    "Iapetus",
+    "Umbriel",
+    "Algieba",
+    "Despina",
+    "Erinome",
+    "Algenib",
+    "Rasalgethi",
+    "Laomedeia",
+    "Achernar",
+    "Alnilam",
+    "Schedar",
+    "Gacrux",
+    "Pulcherrima",
+    "Achird",
+    "Zubenelgenubi",
+    "Vindemiatrix",
+    "Sadachbia",
+    "Sadaltager",
+    "Sulafat",
+];
+
+/// What the speech models send when the MIME type doesn't say.
+const DEFAULT_RATE: u32 = 24_000;
+
+/// Speech the model answered with, kept with the message as the API sent it.
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct GeneratedAudio {
+    /// e.g. `audio/L16;codec=pcm;rate=24000`
+    pub mime_type: String,
+    /// Base64, as in the response
+    pub data: String,
+}
+
+/// Decoded audio, shared by the player and the save button.
+struct Decoded {
+    /// Raw 16-bit mono PCM, `None` when the audio came in a container like OGG
+    pcm: Option<(Vec<i16>, u32)>,
+    bytes: Vec<u8>,
+}
+
+impl GeneratedAudio {
+    /// The audio in `part`, if it holds some.
+    pub fn from_part(part: &Part) -> Option<Self> {
+        let Part::InlineData { inline_data, .. } = part else {
+            return None;
+        };
+        inline_data.mime_type.starts_with("audio/").then(|| Self {
+            mime_type: inline_data.mime_type.clone(),
+            data: inline_data.data.clone(),
+        })
+    }
+
+    /// Streamed answers come in pieces, raw PCM of the same format just continues.
+    pub fn append(&mut self, other: Self) -> Result<(), Self> {
+        if !self.is_pcm() || other.mime_type != self.mime_type {
+            return Err(other);
+        }
+        let engine = &base64::engine::general_purpose::STANDARD;
+        match (engine.decode(&self.data), engine.decode(&other.data)) {
+            (Ok(mut bytes), Ok(more)) => {
+                bytes.extend(more);
+                self.data = engine.encode(bytes);
+                Ok(())
+            }
+            _ => Err(other),
+        }
+    }
+
+    fn is_pcm(&self) -> bool {
+        let mime = self.mime_type.to_ascii_lowercase();
+        mime.starts_with("audio/l16") || mime.contains("codec=pcm")
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.mime_type
+            .split(';')
+            .find_map(|param| param.trim().strip_prefix("rate="))
+            .and_then(|rate| rate.parse().ok())
+            .unwrap_or(DEFAULT_RATE)
+    }
+
+    /// The file extension of what [`Decoded::file_bytes`] gives.
+    fn extension(&self) -> &str {
+        if self.is_pcm() {
+            return "wav";
+        }
+        match self.mime_type.strip_prefix("audio/") {
+            Some("mpeg") => "mp3",
+            Some(subtype) => subtype.split(';').next().unwrap_or("ogg"),
+            None => "ogg",
+        }
+    }
+
+    fn decode(&self) -> Option<Decoded> {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(&self.data)
+            .map_err(|e| log::error!("failed to decode generated audio: {e}"))
+            .ok()?;
+        // L16 is big-endian by its RFC, the API sends little-endian like WAV
+        let pcm = self.is_pcm().then(|| {
+            let samples = bytes
+                .chunks_exact(2)
+                .map(|pair| i16::from_le_bytes([pair[0], pair[1]]))
+                .collect();
+            (samples, self.sample_rate())
+        });
+        Some(Decoded { pcm, bytes })
+    }
+}
+
+impl Decoded {
+    fn duration(&self) -> Option<Duration> {
+        let (samples, rate) = self.pcm.as_ref()?;
+        Some(Duration::from_secs_f64(samples.len() as f64 / *rate as f64))
+    }
+
+    /// What a file gets, raw PCM wrapped into WAV.
+    fn file_bytes(&self) -> Vec<u8> {
+        match &self.pcm {
+            Some((samples, rate)) => wav(samples, *rate),
+            None => self.bytes.clone(),
+        }
+    }
+}
+
+/// A mono 16-bit WAV file of `samples`.
+pub fn wav(samples: &[i16], rate: u32) -> Vec<u8> {
+    let data_len = (samples.len() * 2) as u32;
+    let mut out = Vec::with_capacity(44 + data_len as usize);
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&(36 + data_len).to_le_bytes());
+    out.extend_from_slice(b"WAVEfmt ");
+    out.extend_from_slice(&16u32.to_le_bytes());
+    out.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    out.extend_from_slice(&1u16.to_le_bytes()); // mono
+    out.extend_from_slice(&rate.to_le_bytes());
+    out.extend_from_slice(&(rate * 2).to_le_bytes());
+    out.extend_from_slice(&2u16.to_le_bytes());
+    out.extend_from_slice(&16u16.to_le_bytes());
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        out.extend_from_slice(&sample.to_le_bytes());
+    }
+    out
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+#[cfg(feature = "audio")]
+mod player {
+    use std::{cell::RefCell, time::Duration};
+
+    struct Playback {
+        key: String,
+        sink: rodio::Sink,
+        // dropping it stops the sound
+        _stream: rodio::OutputStream,
+    }
+
+    thread_local! {
+        /// What plays now, one clip at a time.
+        static PLAYING: RefCell<Option<Playback>> = const { RefCell::new(None) };
+    }
+
+    pub fn start(key: &str, samples: &[i16], rate: u32) {
+        stop();
+        let stream = match rodio::OutputStreamBuilder::open_default_stream() {
+            Ok(mut stream) => {
+                stream.log_on_drop(false);
+                stream
+            }
+            Err(e) => {
+                log::error!("failed to open the audio output: {e}");
+                return;
+            }
+        };
+        let sink = rodio::Sink::connect_new(stream.mixer());
+        let samples: Vec<f32> = samples
+            .iter()
+            .map(|&s| s as f32 / i16::MAX as f32)
+            .collect();
+        sink.append(rodio::buffer::SamplesBuffer::new(1, rate, samples));
+        PLAYING.with_borrow_mut(|playing| {
+            *playing = Some(Playback {
+                key: key.to_owned(),
+                sink,
+                _stream: stream,
+            })
+        });
+    }
+
+    pub fn stop() {
+        PLAYING.with_borrow_mut(|playing| *playing = None);
+    }
+
+    /// How far `key` got, `None` when it isn't playing or has finished.
+    pub fn position(key: &str) -> Option<Duration> {
+        PLAYING.with_borrow(|playing| {
+            playing
+                .as_ref()
+                .filter(|p| p.key == key && !p.sink.empty())
+                .map(|p| p.sink.get_pos())
+        })
+    }
+}
+
+/// Shows a small player for each clip of a message, with a button saving it. `time` is
+/// when the message was sent and tells the clips of different messages apart.
+pub fn show_audio(
+    ui: &mut egui::Ui,
+    time: chrono::DateTime<chrono::Utc>,
+    clips: &[GeneratedAudio],
+) {
+    for (n, clip) in clips.iter().enumerate() {
+        // streamed clips grow, the length keeps the decoded copy current
+        let key = format!("{}-{n}-{}", time.timestamp_micros(), clip.data.len());
+        let id = Id::new(("generated_audio", &key));
+        let decoded = match ui.data(|d| d.get_temp::<Arc<Decoded>>(id)) {
+            Some(decoded) => decoded,
+            None => {
+                let Some(decoded) = clip.decode() else {
+                    ui.colored_label(ui.visuals().warn_fg_color, tr!("audio-invalid"));
+                    continue;
+                };
+                let decoded = Arc::new(decoded);
+                ui.data_mut(|d| d.insert_temp(id, decoded.clone()));
+                decoded
+            }
+        };
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.horizontal(|ui| show_player(ui, &key, &decoded, clip));
+        });
+    }
+}
+
+fn show_player(ui: &mut egui::Ui, key: &str, decoded: &Decoded, clip: &GeneratedAudio) {
+    let duration = decoded.duration();
+    #[cfg(feature = "audio")]
+    {
+        let position = player::position(key);
+        if let Some(position) = position {
+            if ui.button("⏹").on_hover_text(tr!("audio-stop")).clicked() {
+                player::stop();
+            }
+            ui.ctx().request_repaint_after(Duration::from_millis(100));
+            let progress = duration.map_or(0.0, |d| {
+                (position.as_secs_f32() / d.as_secs_f32().max(f32::EPSILON)).min(1.0)
+            });
+            ui.add(
+                egui::ProgressBar::new(progress)
+                    .desired_width(160.0)
+                    .text(format_duration(position)),
+            );
+        } else if let Some((samples, rate)) = &decoded.pcm {
+            if ui.button("▶").on_hover_text(tr!("audio-play")).clicked() {
+                player::start(key, samples, *rate);
+            }
+        }
+    }
+    #[cfg(not(feature = "audio"))]
+    {
+        let _ = key;
+        if ui
+            .button("▶")
+            .on_hover_text(tr!("audio-play-external"))
+            .clicked()
+        {
+            play_external(decoded.file_bytes(), clip.extension());
+        }
+    }
+    if let Some(duration) = duration {
+        ui.weak(format_duration(duration));
+    }
+    if ui
+        .button(tr!("audio-save"))
+        .on_hover_text(tr!("audio-save-hint"))
+        .clicked()
+    {
+        save(ui.ctx(), decoded.file_bytes(), clip.extension());
+    }
+}
+
+/// Plays the clip in the default app, without the `audio` feature there is no player.
+#[cfg(not(feature = "audio"))]
+fn play_external(bytes: Vec<u8>, extension: &str) {
+    use std::io::Write;
+
+    let suffix = format!(".{extension}");
+    tokio::task::spawn_blocking(move || {
+        // a new file only this user can read, kept as the player opens it later
+        let written = tempfile::Builder::new()
+            .prefix("geminid-speech-")
+            .suffix(&suffix)
+            .tempfile()
+            .and_then(|mut file| file.write_all(&bytes).map(|()| file))
+            .and_then(|file| file.keep().map_err(|e| e.error));
+        let path = match written {
+            Ok((_, path)) => path,
+            Err(e) => {
+                log::error!("failed to write the speech clip: {e}");
+                return;
+            }
+        };
+        if let Err(e) = open::that_detached(&path) {
+            log::error!("failed to open {}: {e}", path.display());
+        }
+    });
+}
+
+fn save(ctx: &egui::Context, bytes: Vec<u8>, extension: &str) {
+    let file_name = format!("geminid-speech.{extension}");
+    let ctx = ctx.clone();
+    tokio::spawn(async move {
+        let Some(file) = rfd::AsyncFileDialog::new()
+            .set_file_name(file_name)
+            .save_file()
+            .await
+        else {
+            return;
+        };
+        match tokio::fs::write(file.path(), bytes).await {
+            Ok(()) => log::info!("saved the audio to {}", file.path().display()),
+            Err(e) => log::error!("failed to save the audio: {e}"),
+        }
+        ctx.request_repaint();
+    });
+}
+
+/// Voice of the speech models, per model picker.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct SpeechSettings {
+    /// Asks for spoken answers, only the speech models give them
+    pub enabled: bool,
+    pub voice: String,
+    /// BCP-47 code like `en-US`, empty lets the model tell from the text
+    pub language: String,
+}
+
+impl Default for SpeechSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            voice: VOICES[3].to_owned(),
+            language: String::new(),
+        }
+    }
+}
+
+impl SpeechSettings {
+    /// The speech config of the requests, `None` while spoken answers are off.
+    pub fn config(&self) -> Option<SpeechConfig> {
+        if !self.enabled {
+            return None;
+        }
+        let mut json = serde_json::json!({
+            "voiceConfig": { "prebuiltVoiceConfig": { "voiceName": self.voice } }
+        });
+        if !self.language.trim().is_empty() {
+            json["languageCode"] = self.language.trim().into();
+        }
+        serde_json::from_value(json)
+            .map_err(|e| log::warn!("ignoring the speech config: {e}"))
+            .ok()
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.add_enabled_ui(self.enabled, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr!("audio-voice"));
+                egui::ComboBox::from_id_salt("speech_voice")
+                    .selected_text(&self.voice)
+                    .show_ui(ui, |ui| {
+                        for voice in VOICES {
+                            ui.selectable_value(&mut self.voice, voice.to_owned(), voice);
+                        }
+                    });
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr!("audio-language"));
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.language)
+                        .hint_text(tr!("audio-language-hint"))
+                        .desired_width(80.0),
+                );
+            });
+        });
+    }
+}
@@ -0,0 +1,367 @@
+use crate::{chat::ChatExportFormat, deeplink::DeepLink, instance::OpenRequest};
+use eframe::egui;
+use serde_json::{json, Value};
+use std::{
+    path::PathBuf,
+    sync::mpsc::{self, Receiver, Sender},
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader},
+    sync::oneshot,
+};
+
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// Errors of the app itself, like an unknown chat id
+const APP_ERROR: i64 = -32000;
+
+/// Where automation requests come from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Transport {
+    /// `--automation`: one request per line on stdin, responses on stdout
+    Stdio,
+    /// `--automation-socket <path>`: the same over a Unix socket, any number of clients.
+    /// Relative paths are taken from `$XDG_RUNTIME_DIR`.
+    #[cfg(unix)]
+    Socket(PathBuf),
+}
+
+/// Command line flags.
+#[derive(Debug, Default)]
+pub struct Options {
+    pub transport: Option<Transport>,
+    /// `--headless`: start with the window hidden, for scripts
+    pub headless: bool,
+    /// `--new-instance`: don't hand the arguments to an instance that is already running
+    pub new_instance: bool,
+    /// Links, files and prompt text given without a flag
+    pub open: OpenRequest,
+}
+
+impl Options {
+    pub fn from_args() -> Self {
+        let mut options = Self::default();
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--automation" => options.transport = Some(Transport::Stdio),
+                #[cfg(unix)]
+                "--automation-socket" => match args.next() {
+                    Some(path) => options.transport = Some(Transport::Socket(socket_path(path))),
+                    None => log::error!("--automation-socket needs a path"),
+                },
+                "--headless" => options.headless = true,
+                "--new-instance" => options.new_instance = true,
+                link if DeepLink::is_link(link) => options.open.links.push(arg.clone()),
+                flag if flag.starts_with("--") => log::warn!("unknown argument `{arg}`"),
+                _ => match std::fs::canonicalize(&arg) {
+                    Ok(path) => options.open.files.push(path),
+                    Err(_) => {
+                        let prompt = options.open.prompt.get_or_insert_with(String::new);
+                        if !prompt.is_empty() {
+                            prompt.push(' ');
+                        }
+                        prompt.push_str(&arg);
+                    }
+                },
+            }
+        }
+        if options.headless && options.transport.is_none() {
+            log::warn!("--headless without --automation, the window can't be shown again");
+        }
+        options
+    }
+
+    /// Whether this launch should defer to an already running instance. Automation
+    /// runs are left alone, scripts start them on purpose.
+    pub fn single_instance(&self) -> bool {
+        !self.new_instance && self.transport.is_none()
+    }
+}
+
+#[derive(Debug)]
+pub struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    /// An error of the app itself, like an unknown chat id.
+    pub fn app(message: impl Into<String>) -> Self {
+        Self {
+            code: APP_ERROR,
+            message: message.into(),
+        }
+    }
+}
+
+/// Methods of the automation interface, `params` are named.
+#[derive(Debug, serde::Deserialize)]
+#[serde(tag = "method", content = "params", rename_all = "snake_case")]
+pub enum Method {
+    /// Ids and titles of every chat
+    ListChats {},
+    CreateChat {
+        #[serde(default)]
+        title: Option<String>,
+    },
+    SelectChat {
+        chat_id: usize,
+    },
+    /// Sends to `chat_id`, or the selected chat. With `wait` the response comes
+    /// once the model has answered and holds the answer.
+    SendMessage {
+        #[serde(default)]
+        chat_id: Option<usize>,
+        text: String,
+        #[serde(default)]
+        wait: bool,
+    },
+    /// Tools of the loaded plugins
+    ListTools {},
+    CallTool {
+        name: String,
+        #[serde(default)]
+        args: Value,
+    },
+    /// Writes the chat to `path`, or returns it as `content` without one.
+    ExportChat {
+        chat_id: usize,
+        #[serde(default)]
+        format: ChatExportFormat,
+        #[serde(default)]
+        path: Option<PathBuf>,
+    },
+}
+
+const METHODS: &[&str] = &[
+    "list_chats",
+    "create_chat",
+    "select_chat",
+    "send_message",
+    "list_tools",
+    "call_tool",
+    "export_chat",
+];
+
+impl Method {
+    fn parse(method: &str, params: Value) -> Result<Self, RpcError> {
+        if !METHODS.contains(&method) {
+            return Err(RpcError {
+                code: METHOD_NOT_FOUND,
+                message: format!("unknown method `{method}`"),
+            });
+        }
+        let params = if params.is_null() { json!({}) } else { params };
+        serde_json::from_value(json!({ "method": method, "params": params })).map_err(|e| {
+            RpcError {
+                code: INVALID_PARAMS,
+                message: e.to_string(),
+            }
+        })
+    }
+}
+
+/// A request waiting for the UI thread. Dropping it without a reply answers with an error.
+pub struct Call {
+    pub method: Method,
+    reply: oneshot::Sender<Result<Value, RpcError>>,
+}
+
+impl Call {
+    pub fn reply(self, result: Result<Value, RpcError>) {
+        let _ = self.reply.send(result);
+    }
+}
+
+/// JSON-RPC 2.0 interface for scripts and editor plugins. Requests are read on
+/// background tasks and queued until the next frame, like tray menu clicks.
+pub struct Automation {
+    calls: Receiver<Call>,
+}
+
+impl Automation {
+    pub fn start(ctx: &egui::Context, transport: Transport) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let ctx = ctx.clone();
+        match transport {
+            Transport::Stdio => {
+                log::info!("accepting automation requests on stdin");
+                tokio::spawn(serve(tokio::io::stdin(), tokio::io::stdout(), tx, ctx));
+            }
+            #[cfg(unix)]
+            Transport::Socket(path) => {
+                tokio::spawn(listen(path, tx, ctx));
+            }
+        }
+        Self { calls: rx }
+    }
+
+    pub fn poll(&self) -> Option<Call> {
+        self.calls.try_recv().ok()
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct Request {
+    /// Missing for notifications, which get no response
+    #[serde(default)]
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+fn response(id: Value, result: Result<Value, RpcError>) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(e) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": e.code, "message": e.message },
+        }),
+    }
+}
+
+async fn serve(
+    reader: impl AsyncRead + Unpin + Send + 'static,
+    mut writer: impl AsyncWrite + Unpin + Send + 'static,
+    calls: Sender<Call>,
+    ctx: egui::Context,
+) {
+    let (responses, mut outgoing) = tokio::sync::mpsc::unbounded_channel::<Value>();
+    tokio::spawn(async move {
+        while let Some(response) = outgoing.recv().await {
+            let line = format!("{response}\n");
+            if writer.write_all(line.as_bytes()).await.is_err() || writer.flush().await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let request: Request = match serde_json::from_str(&line) {
+            Ok(request) => request,
+            Err(e) => {
+                let error = RpcError {
+                    code: PARSE_ERROR,
+                    message: e.to_string(),
+                };
+                let _ = responses.send(response(Value::Null, Err(error)));
+                continue;
+            }
+        };
+        let id = request.id;
+        let (reply, replied) = oneshot::channel();
+        match Method::parse(&request.method, request.params) {
+            Ok(method) => {
+                if calls.send(Call { method, reply }).is_err() {
+                    break;
+                }
+                ctx.request_repaint();
+            }
+            Err(e) => {
+                let _ = reply.send(Err(e));
+            }
+        }
+
+        // answered on its own task, so a `send_message` that waits doesn't hold up the rest
+        let responses = responses.clone();
+        tokio::spawn(async move {
+            let result = replied
+                .await
+                .unwrap_or_else(|_| Err(RpcError::app("the request was dropped")));
+            if let Some(id) = id {
+                let _ = responses.send(response(id, result));
+            }
+        });
+    }
+    log::debug!("automation client disconnected");
+}
+
+/// Puts relative socket paths in the user's runtime directory, which only they can enter.
+#[cfg(unix)]
+fn socket_path(path: String) -> PathBuf {
+    let path = PathBuf::from(path);
+    if path.is_absolute() {
+        return path;
+    }
+    match std::env::var_os("XDG_RUNTIME_DIR") {
+        Some(dir) => PathBuf::from(dir).join(path),
+        None => {
+            log::warn!(
+                "XDG_RUNTIME_DIR isn't set, the automation socket goes to the current directory"
+            );
+            path
+        }
+    }
+}
+
+/// Binds the socket at `path` so only the current user can connect. A socket left
+/// behind by an instance that didn't exit cleanly is replaced, anything else is left alone.
+#[cfg(unix)]
+fn bind(path: &std::path::Path) -> std::io::Result<tokio::net::UnixListener> {
+    use std::io::{Error, ErrorKind};
+    use std::os::unix::fs::{DirBuilderExt, FileTypeExt, PermissionsExt};
+
+    if let Ok(metadata) = std::fs::symlink_metadata(path) {
+        if !metadata.file_type().is_socket() {
+            return Err(Error::new(
+                ErrorKind::AlreadyExists,
+                "the path isn't a socket",
+            ));
+        }
+        if std::os::unix::net::UnixStream::connect(path).is_ok() {
+            return Err(Error::new(
+                ErrorKind::AddrInUse,
+                "another instance is listening on it",
+            ));
+        }
+        std::fs::remove_file(path)?;
+    }
+    // bound in a folder only this user can enter and made private there, before it's
+    // moved to where others could reach it
+    let private = path.with_file_name(format!(".{}", std::process::id()));
+    std::fs::DirBuilder::new().mode(0o700).create(&private)?;
+    let staged = private.join("socket");
+    let bound = tokio::net::UnixListener::bind(&staged).and_then(|listener| {
+        std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o600))?;
+        std::fs::rename(&staged, path)?;
+        Ok(listener)
+    });
+    let _ = std::fs::remove_file(&staged);
+    let _ = std::fs::remove_dir(&private);
+    bound
+}
+
+#[cfg(unix)]
+async fn listen(path: PathBuf, calls: Sender<Call>, ctx: egui::Context) {
+    let listener = match bind(&path) {
+        Ok(listener) => listener,
+        Err(e) => {
+            log::error!(
+                "failed to open the automation socket {}: {e}",
+                path.display()
+            );
+            return;
+        }
+    };
+    log::info!("accepting automation requests on {}", path.display());
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                let (reader, writer) = stream.into_split();
+                tokio::spawn(serve(reader, writer, calls.clone(), ctx.clone()));
+            }
+            Err(e) => {
+                log::error!("automation socket failed: {e}");
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,943 @@
+use crate::{
+    i18n::{format_number, tr},
+    prompt_library::SavedPrompt,
+    retry::{RetrySettings, Step},
+    widgets::{AuthMethod, GeminiModel, ModelPicker, Settings},
+};
+use anyhow::{anyhow, Context as _, Result};
+use eframe::egui;
+use gemini_rust::GenerationConfig;
+use serde_json::{json, Value};
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use tokio::sync::oneshot;
+
+/// Pacing never slows down past one request per this.
+const MAX_INTERVAL: Duration = Duration::from_secs(120);
+
+const API: &str = "https://generativelanguage.googleapis.com/v1beta";
+/// Batch API jobs take minutes to hours, no point asking more often.
+const BATCH_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Input rows with their column names. CSV files need a header row, in JSON lines
+/// every key is a column.
+struct Table {
+    columns: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    fn read(path: &Path) -> Result<Self> {
+        let is_jsonl = path
+            .extension()
+            .is_some_and(|ext| ext == "jsonl" || ext == "ndjson");
+        if is_jsonl {
+            Self::read_jsonl(path)
+        } else {
+            Self::read_csv(path)
+        }
+    }
+
+    fn read_csv(path: &Path) -> Result<Self> {
+        let mut reader = csv::ReaderBuilder::new().flexible(true).from_path(path)?;
+        let columns: Vec<String> = reader.headers()?.iter().map(str::to_owned).collect();
+        let mut rows = Vec::new();
+        for record in reader.records() {
+            let record = record?;
+            rows.push(
+                (0..columns.len())
+                    .map(|i| record.get(i).unwrap_or_default().to_owned())
+                    .collect(),
+            );
+        }
+        Ok(Self { columns, rows })
+    }
+
+    fn read_jsonl(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        let mut objects = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let object: serde_json::Map<String, serde_json::Value> = serde_json::from_str(line)
+                .with_context(|| format!("line {} isn't a JSON object", i + 1))?;
+            objects.push(object);
+        }
+        let mut columns: Vec<String> = Vec::new();
+        for key in objects.iter().flat_map(|o| o.keys()) {
+            if !columns.contains(key) {
+                columns.push(key.clone());
+            }
+        }
+        let rows = objects
+            .iter()
+            .map(|object| {
+                columns
+                    .iter()
+                    .map(|column| match object.get(column) {
+                        None | Some(serde_json::Value::Null) => String::new(),
+                        Some(serde_json::Value::String(s)) => s.clone(),
+                        Some(value) => value.to_string(),
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(Self { columns, rows })
+    }
+}
+
+/// Names of the `{{column}}` placeholders in `template`.
+fn placeholders(template: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        names.push(rest[start + 2..start + 2 + end].trim());
+        rest = &rest[start + 2 + end + 2..];
+    }
+    names
+}
+
+/// The template with every `{{column}}` replaced by the row's value.
+fn fill(template: &str, columns: &[String], row: &[String]) -> String {
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + len + 2;
+        let name = rest[start + 2..end - 2].trim();
+        text.push_str(&rest[..start]);
+        match columns.iter().position(|c| c == name) {
+            Some(i) => text.push_str(&row[i]),
+            None => text.push_str(&rest[start..end]),
+        }
+        rest = &rest[end..];
+    }
+    text.push_str(rest);
+    text
+}
+
+fn is_rate_limit(error: &anyhow::Error) -> bool {
+    let message = error.to_string();
+    message.contains("429") || message.contains("RESOURCE_EXHAUSTED")
+}
+
+#[derive(Default)]
+struct RowResult {
+    output: String,
+    error: Option<String>,
+    prompt_tokens: u64,
+    output_tokens: u64,
+}
+
+impl RowResult {
+    fn failed(error: impl ToString) -> Self {
+        Self {
+            error: Some(error.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// A `GenerateContentResponse` as the Batch API returns it, thoughts left out.
+    fn from_json(response: &Value) -> Self {
+        let output = response["candidates"][0]["content"]["parts"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|part| !part["thought"].as_bool().unwrap_or(false))
+            .filter_map(|part| part["text"].as_str())
+            .collect();
+        let usage = &response["usageMetadata"];
+        Self {
+            output,
+            error: None,
+            prompt_tokens: usage["promptTokenCount"].as_u64().unwrap_or(0),
+            output_tokens: usage["candidatesTokenCount"].as_u64().unwrap_or(0),
+        }
+    }
+}
+
+/// Results file, CSV or JSON lines after its extension. Rows are written as they
+/// finish so a cancelled or crashed run keeps what it got.
+enum ResultWriter {
+    Csv(csv::Writer<File>),
+    Jsonl(BufWriter<File>),
+}
+
+impl ResultWriter {
+    const EXTRA_COLUMNS: [&str; 4] = ["output", "error", "prompt_tokens", "output_tokens"];
+
+    fn create(path: &Path, columns: &[String]) -> Result<Self> {
+        let file =
+            File::create(path).with_context(|| format!("failed to create {}", path.display()))?;
+        if path.extension().is_some_and(|ext| ext == "csv") {
+            let mut writer = csv::Writer::from_writer(file);
+            writer.write_record(
+                columns
+                    .iter()
+                    .map(String::as_str)
+                    .chain(Self::EXTRA_COLUMNS),
+            )?;
+            writer.flush()?;
+            Ok(Self::Csv(writer))
+        } else {
+            Ok(Self::Jsonl(BufWriter::new(file)))
+        }
+    }
+
+    fn write(&mut self, columns: &[String], row: &[String], result: &RowResult) -> Result<()> {
+        match self {
+            Self::Csv(writer) => {
+                let tokens = [
+                    result.prompt_tokens.to_string(),
+                    result.output_tokens.to_string(),
+                ];
+                writer.write_record(
+                    row.iter()
+                        .map(String::as_str)
+                        .chain([
+                            result.output.as_str(),
+                            result.error.as_deref().unwrap_or_default(),
+                        ])
+                        .chain(tokens.iter().map(String::as_str)),
+                )?;
+                writer.flush()?;
+            }
+            Self::Jsonl(writer) => {
+                let mut object: serde_json::Map<_, _> = columns
+                    .iter()
+                    .cloned()
+                    .zip(row.iter().cloned().map(serde_json::Value::from))
+                    .collect();
+                object.insert("output".into(), result.output.clone().into());
+                object.insert("error".into(), result.error.clone().into());
+                object.insert("prompt_tokens".into(), result.prompt_tokens.into());
+                object.insert("output_tokens".into(), result.output_tokens.into());
+                writeln!(writer, "{}", serde_json::Value::Object(object))?;
+                writer.flush()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Job {
+    table: Table,
+    output: PathBuf,
+    template: String,
+    system_prompt: String,
+    model: GeminiModel,
+    config: GenerationConfig,
+    requests_per_minute: u32,
+    retries: u32,
+    batch_api: bool,
+}
+
+enum BatchEvent {
+    /// The Batch API took the job, with its name and state
+    Submitted {
+        name: String,
+        state: String,
+    },
+    Row {
+        index: usize,
+        failed: bool,
+        tokens: u64,
+        /// The error, or the start of the output
+        summary: String,
+    },
+    Finished(Result<(), String>),
+}
+
+async fn run_row(
+    job: &Job,
+    settings: &Settings,
+    prompt: &str,
+    interval: &mut Duration,
+    last_request: &mut Option<Instant>,
+    cancel: &AtomicBool,
+) -> RowResult {
+    // the batch's own count of retries, with the fallback model of the settings
+    let retry = RetrySettings {
+        enabled: true,
+        attempts: job.retries,
+        fallback: settings.retry.fallback,
+    };
+    let connection = settings.connection();
+    let mut model = job.model;
+    let mut attempt = 0;
+    loop {
+        if let Some(last) = *last_request {
+            tokio::time::sleep_until((last + *interval).into()).await;
+        }
+        *last_request = Some(Instant::now());
+        let system_prompt = Some(job.system_prompt.as_str());
+        let error = match geminid_core::pipeline::generate_configured(
+            &connection,
+            model,
+            system_prompt,
+            prompt,
+            job.config.clone(),
+        )
+        .await
+        {
+            Ok(response) => {
+                let usage = response.usage_metadata.as_ref();
+                return RowResult {
+                    output: response.text(),
+                    error: None,
+                    prompt_tokens: usage.and_then(|u| u.prompt_token_count).unwrap_or(0) as u64,
+                    output_tokens: usage.and_then(|u| u.candidates_token_count).unwrap_or(0) as u64,
+                };
+            }
+            Err(e) => e,
+        };
+        if cancel.load(Ordering::SeqCst) {
+            return RowResult::failed(error);
+        }
+        if is_rate_limit(&error) {
+            *interval = (*interval * 2).clamp(Duration::from_secs(1), MAX_INTERVAL);
+            log::warn!(
+                "rate limited, slowing the batch down to one request every {}s",
+                interval.as_secs()
+            );
+        }
+        match retry.next(&error.to_string(), attempt, model, true) {
+            Step::Wait(delay) => {
+                attempt += 1;
+                log::info!("retrying the row in {delay:?}, attempt {attempt}");
+                if !crate::retry::wait(delay, || cancel.load(Ordering::SeqCst)).await {
+                    return RowResult::failed(error);
+                }
+            }
+            Step::FallBack(fallback) => {
+                log::info!("falling back from {model} to {fallback}");
+                model = fallback;
+                attempt = 0;
+            }
+            Step::GiveUp => return RowResult::failed(error),
+        }
+    }
+}
+
+/// Writes the result of row `index` and tells the window about it.
+fn report(
+    writer: &mut ResultWriter,
+    job: &Job,
+    index: usize,
+    result: &RowResult,
+    events: &Sender<BatchEvent>,
+    ctx: &egui::Context,
+) -> Result<()> {
+    writer.write(&job.table.columns, &job.table.rows[index], result)?;
+    let summary = match &result.error {
+        Some(error) => error.clone(),
+        None => result.output.lines().next().unwrap_or_default().to_owned(),
+    };
+    let _ = events.send(BatchEvent::Row {
+        index,
+        failed: result.error.is_some(),
+        tokens: result.prompt_tokens + result.output_tokens,
+        summary,
+    });
+    ctx.request_repaint();
+    Ok(())
+}
+
+/// Sends `request` with the API key and reads the answer, or the message of the error.
+async fn send(request: reqwest::RequestBuilder, api_key: &str) -> Result<Value> {
+    let response = request.header("x-goog-api-key", api_key).send().await?;
+    let status = response.status();
+    let body: Value = response.json().await.unwrap_or_default();
+    if status.is_success() {
+        return Ok(body);
+    }
+    Err(anyhow!(body["error"]["message"]
+        .as_str()
+        .map(str::to_owned)
+        .unwrap_or_else(|| status.to_string())))
+}
+
+/// Runs the rows one request at a time, paced and retried.
+async fn run_direct(
+    job: &Job,
+    settings: &Settings,
+    writer: &mut ResultWriter,
+    events: &Sender<BatchEvent>,
+    cancel: &AtomicBool,
+    ctx: &egui::Context,
+) -> Result<()> {
+    let mut interval = Duration::from_secs(60) / job.requests_per_minute.max(1);
+    let mut last_request = None;
+    for (index, row) in job.table.rows.iter().enumerate() {
+        if cancel.load(Ordering::SeqCst) {
+            break;
+        }
+        let prompt = fill(&job.template, &job.table.columns, row);
+        let result = run_row(
+            job,
+            settings,
+            &prompt,
+            &mut interval,
+            &mut last_request,
+            cancel,
+        )
+        .await;
+        report(writer, job, index, &result, events, ctx)?;
+    }
+    Ok(())
+}
+
+/// Hands all rows to the Batch API as one job and waits for it. Half the price of
+/// regular requests, but the job may take up to a day.
+async fn run_batch_api(
+    job: &Job,
+    settings: &Settings,
+    writer: &mut ResultWriter,
+    events: &Sender<BatchEvent>,
+    cancel: &AtomicBool,
+    ctx: &egui::Context,
+) -> Result<()> {
+    if settings.auth_method != AuthMethod::ApiKey || settings.api_key.is_empty() {
+        return Err(anyhow!(tr!("batch-api-needs-key")));
+    }
+    let mut builder = reqwest::Client::builder();
+    if let Some(proxy) = settings
+        .proxy_path
+        .as_deref()
+        .filter(|p| !p.trim().is_empty())
+    {
+        builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+    }
+    let client = builder.build()?;
+
+    let config = serde_json::to_value(&job.config)?;
+    let requests: Vec<Value> = job
+        .table
+        .rows
+        .iter()
+        .enumerate()
+        .map(|(index, row)| {
+            let prompt = fill(&job.template, &job.table.columns, row);
+            let mut request = json!({
+                "contents": [{ "role": "user", "parts": [{ "text": prompt }] }],
+                "generationConfig": config,
+            });
+            if !job.system_prompt.trim().is_empty() {
+                request["systemInstruction"] = json!({ "parts": [{ "text": job.system_prompt }] });
+            }
+            json!({ "request": request, "metadata": { "key": index.to_string() } })
+        })
+        .collect();
+    let body = json!({
+        "batch": {
+            "displayName": format!("{} batch", crate::TITLE),
+            "inputConfig": { "requests": { "requests": requests } },
+        }
+    });
+    let url = format!("{API}/models/{}:batchGenerateContent", job.model);
+    let mut operation = send(client.post(url).json(&body), &settings.api_key).await?;
+    let name = operation["name"]
+        .as_str()
+        .map(str::to_owned)
+        .ok_or_else(|| anyhow!("the Batch API sent no job name"))?;
+    log::info!("submitted batch job {name}");
+
+    while !operation["done"].as_bool().unwrap_or(false) {
+        let state = operation["metadata"]["state"].as_str().unwrap_or_default();
+        let _ = events.send(BatchEvent::Submitted {
+            name: name.clone(),
+            state: state.to_owned(),
+        });
+        ctx.request_repaint();
+        let asked = Instant::now();
+        while asked.elapsed() < BATCH_POLL_INTERVAL {
+            if cancel.load(Ordering::SeqCst) {
+                send(
+                    client.post(format!("{API}/{name}:cancel")),
+                    &settings.api_key,
+                )
+                .await?;
+                log::info!("cancelled batch job {name}");
+                return Ok(());
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+        operation = send(client.get(format!("{API}/{name}")), &settings.api_key).await?;
+    }
+    if let Some(message) = operation["error"]["message"].as_str() {
+        return Err(anyhow!(message.to_owned()));
+    }
+
+    let inlined = &operation["response"]["inlinedResponses"];
+    let responses = inlined["inlinedResponses"]
+        .as_array()
+        .or_else(|| inlined.as_array())
+        .cloned()
+        .unwrap_or_default();
+    let mut results: Vec<Option<RowResult>> = job.table.rows.iter().map(|_| None).collect();
+    for (position, response) in responses.iter().enumerate() {
+        // the order isn't promised, the key says which row it was
+        let index = response["metadata"]["key"]
+            .as_str()
+            .and_then(|key| key.parse().ok())
+            .unwrap_or(position);
+        let result = match response["error"]["message"].as_str() {
+            Some(error) => RowResult::failed(error),
+            None => RowResult::from_json(&response["response"]),
+        };
+        if let Some(slot) = results.get_mut(index) {
+            *slot = Some(result);
+        }
+    }
+    for (index, result) in results.into_iter().enumerate() {
+        let result = result.unwrap_or_else(|| RowResult::failed(tr!("batch-no-result")));
+        report(writer, job, index, &result, events, ctx)?;
+    }
+    Ok(())
+}
+
+async fn run(
+    job: Job,
+    settings: Settings,
+    events: Sender<BatchEvent>,
+    cancel: Arc<AtomicBool>,
+    ctx: egui::Context,
+) {
+    let result = async {
+        let mut writer = ResultWriter::create(&job.output, &job.table.columns)?;
+        if job.batch_api {
+            run_batch_api(&job, &settings, &mut writer, &events, &cancel, &ctx).await
+        } else {
+            run_direct(&job, &settings, &mut writer, &events, &cancel, &ctx).await
+        }
+    }
+    .await;
+    if let Err(e) = &result {
+        log::error!("batch run failed: {e}");
+    }
+    let _ = events.send(BatchEvent::Finished(result.map_err(|e| e.to_string())));
+    ctx.request_repaint();
+}
+
+struct Progress {
+    total: usize,
+    done: usize,
+    failed: usize,
+    tokens: u64,
+    started: Instant,
+    /// Per input row, `None` until it's done: whether it failed, and the error or output
+    rows: Vec<Option<(bool, String)>>,
+    /// Name and state of the Batch API job
+    job: Option<(String, String)>,
+    cancel: Arc<AtomicBool>,
+    events: Receiver<BatchEvent>,
+    finished: Option<Result<(), String>>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum PickTarget {
+    Input,
+    Output,
+}
+
+/// Runs a prompt template over every row of a CSV or JSON lines file.
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct BatchRunner {
+    pub input: String,
+    pub output: String,
+    /// Prompt with `{{column}}` placeholders
+    pub template: String,
+    pub system_prompt: String,
+    pub model: GeminiModel,
+    pub requests_per_minute: u32,
+    pub retries: u32,
+    /// Send the rows through the Batch API, cheaper but slower
+    pub batch_api: bool,
+    #[serde(skip)]
+    pub open: bool,
+    #[serde(skip)]
+    progress: Option<Progress>,
+    #[serde(skip)]
+    picking: Option<(PickTarget, oneshot::Receiver<Option<PathBuf>>)>,
+    #[serde(skip)]
+    error: Option<String>,
+}
+
+impl Default for BatchRunner {
+    fn default() -> Self {
+        Self {
+            input: String::new(),
+            output: String::new(),
+            template: String::new(),
+            system_prompt: String::new(),
+            model: GeminiModel::default(),
+            requests_per_minute: 10,
+            retries: 3,
+            batch_api: false,
+            open: false,
+            progress: None,
+            picking: None,
+            error: None,
+        }
+    }
+}
+
+impl BatchRunner {
+    #[inline]
+    pub fn is_running(&self) -> bool {
+        self.progress.as_ref().is_some_and(|p| p.finished.is_none())
+    }
+
+    fn start(
+        &mut self,
+        ctx: &egui::Context,
+        settings: &Settings,
+        picker: &ModelPicker,
+    ) -> Result<()> {
+        let input = PathBuf::from(self.input.trim());
+        let output = PathBuf::from(self.output.trim());
+        if output.as_os_str().is_empty() {
+            return Err(anyhow!(tr!("batch-no-output")));
+        }
+        let table =
+            Table::read(&input).with_context(|| format!("failed to read {}", input.display()))?;
+        if table.rows.is_empty() {
+            return Err(anyhow!(tr!("batch-empty")));
+        }
+        let missing: Vec<&str> = placeholders(&self.template)
+            .into_iter()
+            .filter(|name| !table.columns.iter().any(|c| c == name))
+            .collect();
+        if !missing.is_empty() {
+            return Err(anyhow!(tr!(
+                "batch-missing-columns",
+                columns = missing.join(", ")
+            )));
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.progress = Some(Progress {
+            total: table.rows.len(),
+            done: 0,
+            failed: 0,
+            tokens: 0,
+            started: Instant::now(),
+            rows: vec![None; table.rows.len()],
+            job: None,
+            cancel: cancel.clone(),
+            events: rx,
+            finished: None,
+        });
+        let job = Job {
+            table,
+            output,
+            template: self.template.clone(),
+            system_prompt: self.system_prompt.clone(),
+            model: self.model,
+            config: picker.get_generation_config(),
+            requests_per_minute: self.requests_per_minute,
+            retries: self.retries,
+            batch_api: self.batch_api,
+        };
+        tokio::spawn(run(job, settings.clone(), tx, cancel, ctx.clone()));
+        Ok(())
+    }
+
+    fn poll(&mut self) {
+        if let Some((target, rx)) = &mut self.picking {
+            if let Ok(path) = rx.try_recv() {
+                if let Some(path) = path {
+                    let path = path.display().to_string();
+                    match target {
+                        PickTarget::Input => self.input = path,
+                        PickTarget::Output => self.output = path,
+                    }
+                }
+                self.picking = None;
+            }
+        }
+        let Some(progress) = &mut self.progress else {
+            return;
+        };
+        while let Ok(event) = progress.events.try_recv() {
+            match event {
+                BatchEvent::Submitted { name, state } => progress.job = Some((name, state)),
+                BatchEvent::Row {
+                    index,
+                    failed,
+                    tokens,
+                    summary,
+                } => {
+                    progress.done += 1;
+                    progress.failed += failed as usize;
+                    progress.tokens += tokens;
+                    if let Some(row) = progress.rows.get_mut(index) {
+                        *row = Some((failed, summary));
+                    }
+                }
+                BatchEvent::Finished(result) => progress.finished = Some(result),
+            }
+        }
+    }
+
+    fn pick(&mut self, ctx: &egui::Context, target: PickTarget) {
+        let (tx, rx) = oneshot::channel();
+        self.picking = Some((target, rx));
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            let dialog = rfd::AsyncFileDialog::new();
+            let file = match target {
+                PickTarget::Input => {
+                    dialog
+                        .add_filter("CSV / JSON lines", &["csv", "jsonl", "ndjson"])
+                        .pick_file()
+                        .await
+                }
+                PickTarget::Output => {
+                    dialog
+                        .add_filter("JSON lines", &["jsonl"])
+                        .add_filter("CSV", &["csv"])
+                        .set_file_name("results.jsonl")
+                        .save_file()
+                        .await
+                }
+            };
+            let _ = tx.send(file.map(|f| f.path().to_owned()));
+            ctx.request_repaint();
+        });
+    }
+
+    fn show_progress(ui: &mut egui::Ui, progress: &Progress) {
+        let fraction = progress.done as f32 / progress.total.max(1) as f32;
+        ui.add(egui::ProgressBar::new(fraction).show_percentage());
+        ui.label(tr!(
+            "batch-progress",
+            done = progress.done,
+            total = progress.total
+        ));
+        if progress.failed > 0 {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                tr!("batch-failed", count = progress.failed),
+            );
+        }
+        ui.label(tr!("batch-tokens", count = progress.tokens));
+        match &progress.finished {
+            None if progress.job.is_some() && progress.done == 0 => {
+                let (name, state) = progress.job.as_ref().expect("checked above");
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(tr!("batch-api-waiting", state = state.as_str()));
+                })
+                .response
+                .on_hover_text(name);
+            }
+            None if progress.done > 0 => {
+                let per_row = progress.started.elapsed() / progress.done as u32;
+                let left = per_row * (progress.total - progress.done) as u32;
+                let minutes = format_number(left.as_secs_f64() / 60.0, 1);
+                ui.weak(tr!("batch-eta", minutes = minutes));
+            }
+            None => (),
+            Some(Ok(())) if progress.cancel.load(Ordering::SeqCst) => {
+                ui.label(tr!("batch-cancelled"));
+            }
+            Some(Ok(())) => {
+                ui.label(tr!("batch-finished"));
+            }
+            Some(Err(e)) => {
+                ui.colored_label(ui.visuals().error_fg_color, e);
+            }
+        }
+        Self::show_rows(ui, progress);
+    }
+
+    fn show_rows(ui: &mut egui::Ui, progress: &Progress) {
+        egui::CollapsingHeader::new(tr!("batch-rows"))
+            .id_salt("batch_rows")
+            .show(ui, |ui| {
+                egui::ScrollArea::vertical()
+                    .max_height(240.0)
+                    .auto_shrink([false, true])
+                    .show_rows(
+                        ui,
+                        ui.text_style_height(&egui::TextStyle::Body),
+                        progress.rows.len(),
+                        |ui, range| {
+                            for index in range {
+                                ui.horizontal(|ui| {
+                                    ui.weak(format!("{}", index + 1));
+                                    match &progress.rows[index] {
+                                        None => {
+                                            ui.weak("⏳");
+                                        }
+                                        Some((false, output)) => {
+                                            ui.label("✔");
+                                            ui.add(egui::Label::new(output).truncate());
+                                        }
+                                        Some((true, error)) => {
+                                            ui.colored_label(ui.visuals().warn_fg_color, "⚠");
+                                            ui.add(
+                                                egui::Label::new(
+                                                    egui::RichText::new(error)
+                                                        .color(ui.visuals().warn_fg_color),
+                                                )
+                                                .truncate(),
+                                            );
+                                        }
+                                    }
+                                });
+                            }
+                        },
+                    );
+            });
+    }
+
+    /// `picker` is the current chat's, its inference settings apply to every row.
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        settings: &Settings,
+        picker: &ModelPicker,
+        prompts: &[SavedPrompt],
+    ) {
+        self.poll();
+        if !self.open {
+            return;
+        }
+        let mut open = true;
+        egui::Window::new(tr!("batch-title"))
+            .open(&mut open)
+            .default_width(480.0)
+            .show(ctx, |ui| {
+                let running = self.is_running();
+                ui.add_enabled_ui(!running, |ui| {
+                    self.show_form(ui, prompts);
+                });
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if running {
+                        if let Some(progress) = &self.progress {
+                            if ui.button(tr!("batch-cancel")).clicked() {
+                                progress.cancel.store(true, Ordering::SeqCst);
+                            }
+                        }
+                    } else if ui.button(tr!("batch-run")).clicked() {
+                        self.error = self
+                            .start(ctx, settings, picker)
+                            .err()
+                            .map(|e| format!("{e:#}"));
+                    }
+                });
+                if let Some(error) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+                if let Some(progress) = &self.progress {
+                    Self::show_progress(ui, progress);
+                }
+            });
+        self.open = open;
+    }
+
+    fn show_form(&mut self, ui: &mut egui::Ui, prompts: &[SavedPrompt]) {
+        egui::Grid::new("batch_grid").num_columns(2).show(ui, |ui| {
+            for (target, label, hint) in [
+                (
+                    PickTarget::Input,
+                    tr!("batch-input"),
+                    tr!("batch-input-hint"),
+                ),
+                (
+                    PickTarget::Output,
+                    tr!("batch-output"),
+                    tr!("batch-output-hint"),
+                ),
+            ] {
+                ui.label(label);
+                ui.horizontal(|ui| {
+                    let path = match target {
+                        PickTarget::Input => &mut self.input,
+                        PickTarget::Output => &mut self.output,
+                    };
+                    ui.add(egui::TextEdit::singleline(path).hint_text(hint));
+                    if ui
+                        .add_enabled(self.picking.is_none(), egui::Button::new("📂"))
+                        .clicked()
+                    {
+                        self.pick(ui.ctx(), target);
+                    }
+                });
+                ui.end_row();
+            }
+
+            ui.label(tr!("batch-model"));
+            crate::widgets::model_selector(ui, "batch_model", &mut self.model);
+            ui.end_row();
+
+            ui.label("");
+            ui.checkbox(&mut self.batch_api, tr!("batch-api"))
+                .on_hover_text(tr!("batch-api-hint"));
+            ui.end_row();
+
+            ui.label(tr!("batch-rate"));
+            ui.add_enabled(
+                !self.batch_api,
+                egui::DragValue::new(&mut self.requests_per_minute).range(1..=600),
+            );
+            ui.end_row();
+
+            ui.label(tr!("batch-retries"));
+            ui.add_enabled(
+                !self.batch_api,
+                egui::DragValue::new(&mut self.retries).range(0..=10),
+            );
+            ui.end_row();
+        });
+        ui.weak(tr!("batch-config-hint"));
+
+        ui.add_space(4.0);
+        ui.horizontal(|ui| {
+            ui.label(tr!("batch-template"));
+            if !prompts.is_empty() {
+                egui::ComboBox::from_id_salt("batch_prompt")
+                    .selected_text(tr!("batch-from-prompt"))
+                    .show_ui(ui, |ui| {
+                        for prompt in prompts {
+                            if ui.selectable_label(false, &prompt.name).clicked() {
+                                self.template = prompt.latest().to_owned();
+                            }
+                        }
+                    });
+            }
+        });
+        ui.add(
+            egui::TextEdit::multiline(&mut self.template)
+                .hint_text(tr!("batch-template-hint"))
+                .desired_rows(5)
+                .desired_width(f32::INFINITY),
+        );
+        ui.add(
+            egui::TextEdit::multiline(&mut self.system_prompt)
+                .hint_text(tr!("batch-system-prompt"))
+                .desired_rows(2)
+                .desired_width(f32::INFINITY),
+        );
+    }
+}
@@ -0,0 +1,325 @@
+use eframe::egui::{self, TextStyle};
+use egui_commonmark::{CommonMarkCache, CommonMarkViewer};
+use unicode_bidi::{bidi_class, BidiClass, BidiInfo, Level};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Ltr,
+    Rtl,
+}
+
+/// Direction of the first strong character, `None` for text without one (numbers, emoji...).
+pub fn detect(text: &str) -> Option<Direction> {
+    text.chars().find_map(|c| match bidi_class(c) {
+        BidiClass::L => Some(Direction::Ltr),
+        BidiClass::R | BidiClass::AL => Some(Direction::Rtl),
+        _ => None,
+    })
+}
+
+pub fn has_rtl(text: &str) -> bool {
+    text.chars()
+        .any(|c| matches!(bidi_class(c), BidiClass::R | BidiClass::AL))
+}
+
+/// Reorders one line from logical to display order, with a right-to-left base direction.
+pub fn visual_order(line: &str) -> String {
+    let info = BidiInfo::new(line, Some(Level::rtl()));
+    info.paragraphs
+        .iter()
+        .map(|para| info.reorder_line(para, para.range.clone()))
+        .collect()
+}
+
+/// Splits markdown into runs of lines with the same direction. Code blocks are always
+/// left-to-right, lines without strong characters continue the previous run.
+fn blocks(content: &str) -> Vec<(Direction, &str)> {
+    let mut blocks: Vec<(Direction, usize, usize)> = Vec::new();
+    let mut in_fence = false;
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        let is_fence = matches!(line.trim_start().get(..3), Some("```" | "~~~"));
+        let direction = if in_fence || is_fence {
+            Some(Direction::Ltr)
+        } else {
+            detect(line)
+        };
+        if is_fence {
+            in_fence = !in_fence;
+        }
+        let end = offset + line.len();
+        match blocks.last_mut() {
+            Some(last) if direction.is_none_or(|d| d == last.0) => last.2 = end,
+            _ => blocks.push((direction.unwrap_or(Direction::Ltr), offset, end)),
+        }
+        offset = end;
+    }
+    blocks
+        .into_iter()
+        .map(|(direction, start, end)| (direction, &content[start..end]))
+        .collect()
+}
+
+/// Width left unused at the end of a right-to-left row, so the viewer doesn't wrap it again
+/// when bold or code runs come out a little wider than measured.
+const ROW_SLACK: f32 = 8.0;
+
+/// The delimiter closing an inline span that `word` opens but doesn't close, when `after`
+/// closes it later on the same line. A lone `*` as in `2 * 3` opens nothing.
+fn unclosed_span(word: &str, after: &str) -> Option<&'static str> {
+    let line = after.split('\n').next().unwrap_or_default();
+    for (open, close) in [
+        ("**", "**"),
+        ("__", "__"),
+        ("~~", "~~"),
+        ("`", "`"),
+        ("*", "*"),
+        ("_", "_"),
+        ("[", "]("),
+    ] {
+        if let Some(rest) = word.strip_prefix(open) {
+            let opens = !rest.is_empty() && !rest.contains(close) && line.contains(close);
+            return opens.then_some(close);
+        }
+    }
+    None
+}
+
+/// Splits inline markdown into words, keeping spans like `**two words**` or `[a link](url)`
+/// whole so they can be moved around without breaking their syntax.
+fn atoms(text: &str) -> Vec<String> {
+    let mut atoms: Vec<String> = Vec::new();
+    let mut open = None;
+    let mut rest = text;
+    while let Some(start) = rest.find(|c: char| !c.is_whitespace()) {
+        let tail = &rest[start..];
+        let (word, after) = tail.split_at(tail.find(char::is_whitespace).unwrap_or(tail.len()));
+        rest = after;
+        if let (Some(close), Some(last)) = (open, atoms.last_mut()) {
+            last.push(' ');
+            last.push_str(word);
+            if word.contains(close) {
+                open = None;
+            }
+            continue;
+        }
+        open = unclosed_span(word, after);
+        atoms.push(word.to_owned());
+    }
+    atoms
+}
+
+/// What a piece of inline markdown shows, for measuring it.
+fn plain(markdown: &str) -> String {
+    let mut text = markdown.to_owned();
+    while let Some(start) = text.find("](") {
+        let end = text[start..]
+            .find(')')
+            .map_or(text.len(), |i| start + i + 1);
+        text.replace_range(start..end, "");
+    }
+    text.replace(['*', '_', '~', '`', '['], "")
+}
+
+/// Puts the text of one word or span in display order, leaving its markdown syntax in place.
+fn reorder_atom(atom: &str) -> String {
+    if !has_rtl(atom) {
+        return atom.to_owned();
+    }
+    // only the link text is reordered, the target stays as written
+    if let (Some(open), Some(mid)) = (atom.find('['), atom.find("](")) {
+        if open < mid {
+            let close = atom[mid..].find(')').map_or(atom.len(), |i| mid + i + 1);
+            return format!(
+                "{}[{}{}{}",
+                visual_order(&atom[close..]),
+                visual_order(&atom[open + 1..mid]),
+                &atom[mid..close],
+                visual_order(&atom[..open]),
+            );
+        }
+    }
+    let is_mark = |c: char| matches!(c, '*' | '_' | '~' | '`');
+    // punctuation after a span ends up on its left
+    let body = atom.trim_end_matches(|c: char| c.is_ascii_punctuation() && !is_mark(c));
+    if body.len() < atom.len() && body.ends_with(is_mark) {
+        return format!(
+            "{}{}",
+            visual_order(&atom[body.len()..]),
+            reorder_atom(body)
+        );
+    }
+    let core = atom.trim_matches(is_mark);
+    if core.is_empty() {
+        return atom.to_owned();
+    }
+    let start = atom.len() - atom.trim_start_matches(is_mark).len();
+    format!(
+        "{}{}{}",
+        &atom[..start],
+        visual_order(core),
+        &atom[start + core.len()..]
+    )
+}
+
+/// Lays out a row of words right to left: the row is reversed, except runs of
+/// left-to-right words which keep their order.
+fn reorder_row(atoms: &[String]) -> String {
+    let directions: Vec<_> = atoms.iter().map(|a| detect(&plain(a))).collect();
+    let is_ltr = |i: usize| match directions[i] {
+        Some(direction) => direction == Direction::Ltr,
+        // numbers and symbols between two left-to-right words belong to them
+        None => {
+            let before = directions[..i].iter().rev().find_map(|d| *d);
+            let after = directions[i + 1..].iter().find_map(|d| *d);
+            before == Some(Direction::Ltr) && after == Some(Direction::Ltr)
+        }
+    };
+
+    let mut runs = Vec::new();
+    let mut i = 0;
+    while i < atoms.len() {
+        if is_ltr(i) {
+            let start = i;
+            while i < atoms.len() && is_ltr(i) {
+                i += 1;
+            }
+            runs.push(atoms[start..i].join(" "));
+        } else {
+            runs.push(reorder_atom(&atoms[i]));
+            i += 1;
+        }
+    }
+    runs.reverse();
+    runs.join(" ")
+}
+
+/// Greedy wrap of words into rows no wider than `width`, egui would otherwise break an
+/// already reordered row from the wrong end.
+fn wrap(ui: &egui::Ui, atoms: Vec<String>, style: &TextStyle, width: f32) -> Vec<Vec<String>> {
+    let font_id = style.resolve(ui.style());
+    let color = ui.visuals().text_color();
+    let measure = |row: &[String]| {
+        ui.painter()
+            .layout_no_wrap(plain(&row.join(" ")), font_id.clone(), color)
+            .size()
+            .x
+    };
+
+    let mut rows = Vec::new();
+    let mut row: Vec<String> = Vec::new();
+    for atom in atoms {
+        row.push(atom);
+        if row.len() > 1 && measure(&row) > width {
+            let atom = row.pop();
+            rows.push(std::mem::replace(&mut row, atom.into_iter().collect()));
+        }
+    }
+    rows.push(row);
+    rows
+}
+
+/// Shows one reordered row right-aligned, through the markdown viewer so inline
+/// formatting and links keep working.
+fn show_rtl_row(
+    ui: &mut egui::Ui,
+    cache: &mut CommonMarkCache,
+    prefix: &str,
+    row: &str,
+    style: &TextStyle,
+) {
+    let width = ui
+        .painter()
+        .layout_no_wrap(
+            plain(row),
+            style.resolve(ui.style()),
+            ui.visuals().text_color(),
+        )
+        .size()
+        .x;
+    ui.horizontal(|ui| {
+        ui.add_space((ui.available_width() - width - ROW_SLACK).max(0.0));
+        CommonMarkViewer::new().show(ui, cache, &format!("{prefix}{row}"));
+    });
+}
+
+/// Right-aligned markdown in display order. Each line is wrapped and reordered by words,
+/// list markers move to the right and tables are left as they are.
+fn show_rtl_block(ui: &mut egui::Ui, cache: &mut CommonMarkCache, block: &str) {
+    ui.vertical(|ui| {
+        for line in block.lines() {
+            let line = line.trim();
+            if line.is_empty() {
+                ui.add_space(TextStyle::Body.resolve(ui.style()).size / 2.0);
+                continue;
+            }
+            if line.starts_with('|') {
+                CommonMarkViewer::new().show(ui, cache, line);
+                continue;
+            }
+
+            let (prefix, style, text) = match line.find(' ') {
+                Some(i) if line[..i].chars().all(|c| c == '#') => {
+                    (&line[..=i], TextStyle::Heading, &line[i + 1..])
+                }
+                Some(1) if line.starts_with('>') => (&line[..2], TextStyle::Body, &line[2..]),
+                _ => ("", TextStyle::Body, line),
+            };
+            let (marker, text) = match text.split_once(' ') {
+                Some(("-" | "*" | "+", item)) => (" •".to_owned(), item),
+                Some((number, item))
+                    if number.ends_with('.')
+                        && number.len() > 1
+                        && number[..number.len() - 1]
+                            .chars()
+                            .all(|c| c.is_ascii_digit()) =>
+                {
+                    (format!(" {}", visual_order(number)), item)
+                }
+                _ => (String::new(), text),
+            };
+
+            let width = ui.available_width() - ROW_SLACK;
+            for (i, row) in wrap(ui, atoms(text), &style, width).into_iter().enumerate() {
+                let mut row = reorder_row(&row);
+                if i == 0 {
+                    row.push_str(&marker);
+                }
+                // a reordered row could start like a list item or a quote
+                if row.starts_with(['-', '+', '*', '>']) && row[1..].starts_with(' ') {
+                    row.insert(0, '\\');
+                }
+                show_rtl_row(ui, cache, prefix, &row, &style);
+            }
+        }
+    });
+}
+
+/// Renders a message, with right-to-left paragraphs right-aligned and in display order.
+pub fn show_markdown(
+    ui: &mut egui::Ui,
+    cache: &mut CommonMarkCache,
+    content: &str,
+    max_image_width: Option<usize>,
+) {
+    let rendered = crate::plugins::render_blocks(ui.ctx(), content);
+    let content = rendered.as_deref().unwrap_or(content);
+    if !has_rtl(content) {
+        CommonMarkViewer::new()
+            .max_image_width(max_image_width)
+            .show(ui, cache, content);
+        return;
+    }
+    ui.vertical(|ui| {
+        for (direction, block) in blocks(content) {
+            match direction {
+                Direction::Ltr => {
+                    CommonMarkViewer::new()
+                        .max_image_width(max_image_width)
+                        .show(ui, cache, block);
+                }
+                Direction::Rtl => show_rtl_block(ui, cache, block),
+            }
+        }
+    });
+}
@@ -2,48 +2,49 @@
 use crate::sessions::SharedTts;
 
 use crate::{
+    archive::{ArchivePicker, Picked},
+    audio::GeneratedAudio,
+    context_cache::{BannerAction, CacheRequest, ContextCache},
     easymark::MemoizedEasymarkHighlighter,
-    file_handler::{Attachment, AttachmentState},
-    widgets::{self, GeminiModel, ModelPicker, Settings},
+    find::{FindAction, FindBar, Highlights},
+    folders::Project,
+    functions::{Call, CallAction, FunctionDecl},
+    grounding::Grounding,
+    i18n::{format_number, tr},
+    images::GeneratedImage,
+    file_handler::{Attachment, AttachmentState, FileApi},
+    knowledge::Citation,
+    persona::Persona,
+    prompt_improver::PromptImprovement,
+    reading::ReadingSettings,
+    safety::{SafetyCategory, SafetyReport, SafetySettings},
+    scripting::{Hook, ScriptEffects},
+    spellcheck::SpellTarget,
+    timestamps::Timestamps,
+    verify::Verification,
+    widgets::{self, FewShotExample, GeminiModel, ModelPicker, ParamOverride, Settings},
 };
 use anyhow::{Context, Result};
 use eframe::egui::{
-    self, Align, Color32, CornerRadius, Frame, Id, Key, KeyboardShortcut, Layout, Margin, Modifiers, Pos2, Rect, Stroke, TextStyle, pos2, vec2
+    self, Align, Color32, CornerRadius, Frame, Id, Key, Layout, Margin, Modifiers, Pos2, Rect, Stroke, TextStyle, pos2, vec2
 };
-use egui_commonmark::{CommonMarkCache, CommonMarkViewer};
+use egui_commonmark::CommonMarkCache;
 use egui_modal::{Icon, Modal};
 use egui_robust_scroll::RobustVirtualScroll;
 use flowync::{error::Compact, CompactFlower, CompactHandle};
 use futures_util::TryStreamExt;
 use gemini_rust::{
-    Gemini, GenerationConfig, HarmBlockThreshold, HarmCategory, Part, SafetySetting, UsageMetadata,
+    Candidate, Content, Gemini, GenerationConfig, GenerationResponse, Part, SafetySetting, Tool,
+    UsageMetadata,
 };
 use std::{
     hash::{DefaultHasher, Hash, Hasher}, io::Write, path::PathBuf, sync::{
         Arc, atomic::{AtomicBool, Ordering}
     }, time::{Duration, Instant}
 };
+use tokio::sync::oneshot;
 use tokio_stream::StreamExt;
 
-const SAFETY_SETTINGS: [SafetySetting; 4] = [
-    SafetySetting {
-        category: HarmCategory::Harassment,
-        threshold: HarmBlockThreshold::BlockNone,
-    },
-    SafetySetting {
-        category: HarmCategory::HateSpeech,
-        threshold: HarmBlockThreshold::BlockNone,
-    },
-    SafetySetting {
-        category: HarmCategory::SexuallyExplicit,
-        threshold: HarmBlockThreshold::BlockNone,
-    },
-    SafetySetting {
-        category: HarmCategory::DangerousContent,
-        threshold: HarmBlockThreshold::BlockNone,
-    },
-];
-
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum MessageRole {
     User,
@@ -70,9 +71,47 @@ pub struct Message {
     pub files: Vec<Attachment>,
     pub is_prepending: bool,
     pub is_thought: bool,
+    /// Visual separator (e.g. persona switch), never sent to the model
+    pub is_marker: bool,
     pub usage: Option<UsageMetadata>,
     #[serde(skip)]
     pub status_message: Option<String>,
+    /// Knowledge excerpts the answer was given
+    pub sources: Vec<Citation>,
+    /// Functions the model asked to call
+    pub calls: Vec<Call>,
+    /// Images the model drew, with image output on
+    pub images: Vec<GeneratedImage>,
+    /// Speech the model answered with, with spoken answers on
+    pub audio: Vec<GeneratedAudio>,
+    /// Web pages the answer was grounded in, found by search or linked in the prompt
+    pub grounding: Option<Grounding>,
+    /// Why the answer was blocked or cut short by the safety filters
+    pub safety: Option<SafetyReport>,
+    /// Parts of the streamed answer were lost
+    pub degraded: bool,
+    /// The answer stopped at the output token limit
+    pub truncated: bool,
+    /// The user stopped the answer before it was finished
+    pub interrupted: bool,
+    /// Signature of the model's thinking that came with the text, it goes back with the
+    /// text so the model keeps its reasoning across turns and function calls
+    pub signature: Option<String>,
+    /// A second model's fact-check of the answer
+    pub verification: Option<Verification>,
+    #[serde(skip)]
+    pub is_verifying: bool,
+    /// Answered in JSON mode, shown as a tree
+    pub structured: bool,
+    /// The schema the JSON answer was asked to follow, it is checked against it
+    pub schema: Option<serde_json::Value>,
+    /// Other answers the model gave alongside this one, when asked for several
+    pub alternatives: Vec<String>,
+    /// Which answer is shown, 0 is the one the conversation goes on from
+    #[serde(skip)]
+    pub shown_candidate: usize,
+    /// The earlier messages this one summarizes, put back when the summary is reverted
+    pub compacted: Vec<Message>,
 }
 
 impl Default for Message {
@@ -90,18 +129,75 @@ impl Default for Message {
             files: Vec::new(),
             is_prepending: false,
             is_thought: false,
+            is_marker: false,
             generation_time: None,
             usage: None,
             status_message: None,
+            sources: Vec::new(),
+            calls: Vec::new(),
+            images: Vec::new(),
+            audio: Vec::new(),
+            grounding: None,
+            safety: None,
+            degraded: false,
+            truncated: false,
+            interrupted: false,
+            signature: None,
+            verification: None,
+            is_verifying: false,
+            structured: false,
+            schema: None,
+            alternatives: Vec::new(),
+            shown_candidate: 0,
+            compacted: Vec::new(),
         }
     }
 }
 
+/// Widest the conversation gets in focus mode.
+const READABLE_WIDTH: f32 = 760.0;
+
+/// Sent after an answer the output token limit cut off, it isn't kept in the chat.
+const CONTINUE_PROMPT: &str = "Continue exactly where your last answer stopped, \
+    without repeating anything or adding an introduction.";
+
+/// Translation ids of the starter prompts shown in empty chats, each has a `-title` and `-subtitle`.
+const SUGGESTIONS: [&str; 4] = [
+    "suggestion-fact",
+    "suggestion-code",
+    "suggestion-joke",
+    "suggestion-ideas",
+];
+
+/// Runs `add_contents` in a centered column of [`READABLE_WIDTH`] if `enabled`.
+fn readable_column<R>(ui: &mut egui::Ui, enabled: bool, add_contents: impl FnOnce(&mut egui::Ui) -> R) -> R {
+    if !enabled {
+        return add_contents(ui);
+    }
+    let rect = ui.available_rect_before_wrap();
+    let column = Rect::from_center_size(
+        rect.center(),
+        vec2(rect.width().min(READABLE_WIDTH), rect.height()),
+    );
+    ui.scope_builder(egui::UiBuilder::new().max_rect(column), add_contents)
+        .inner
+}
+
 #[cfg(feature = "tts")]
-fn tts_control(tts: SharedTts, text: String, speak: bool) {
+fn tts_control(tts: SharedTts, text: String, speak: bool, voice: Option<String>) {
     std::thread::spawn(move || {
         if let Some(tts) = tts {
             if speak {
+                if let Some(id) = voice {
+                    let mut tts = tts.write();
+                    if let Some(v) = tts.voices().ok().and_then(|voices| {
+                        voices.into_iter().find(|v| v.id() == id)
+                    }) {
+                        let _ = tts
+                            .set_voice(&v)
+                            .map_err(|e| log::error!("failed to set voice: {e}"));
+                    }
+                }
                 let _ = tts
                     .write()
                     .speak(widgets::sanitize_text_for_tts(&text), true)
@@ -134,7 +230,32 @@ enum MessageAction {
     None,
     Retry(usize),
     Regenerate(usize),
+    RegenerateWith(usize, GeminiModel),
     Delete(usize),
+    Continue(usize),
+    RestoreSummarized(usize),
+    EditImage(GeneratedImage),
+    RelaxSafety(Vec<SafetyCategory>),
+    Quarantine(String),
+    Verify(usize),
+    AnswerCalls,
+    RunCommand { message: usize, call: usize },
+    Reupload { message: usize, path: PathBuf },
+}
+
+/// What every message of a chat is shown with in a frame.
+#[derive(Clone)]
+struct MessageView<'a> {
+    #[cfg(feature = "tts")]
+    tts: SharedTts,
+    #[cfg(feature = "tts")]
+    voice: Option<&'a str>,
+    reading: &'a ReadingSettings,
+    timestamps: Timestamps,
+    editor_command: &'a str,
+    safety: &'a SafetySettings,
+    /// Functions whose calls can be run locally, `None` unless that's allowed
+    commands: Option<&'a [FunctionDecl]>,
 }
 
 impl Message {
@@ -161,8 +282,89 @@ impl Message {
         }
     }
 
+    /// Makes the shown alternative the answer the conversation goes on from.
+    fn promote_candidate(&mut self) {
+        let Some(alternative) = self
+            .shown_candidate
+            .checked_sub(1)
+            .and_then(|i| self.alternatives.get_mut(i))
+        else {
+            return;
+        };
+        std::mem::swap(&mut self.content, alternative);
+        self.shown_candidate = 0;
+        // both only held for the answer they were made for
+        self.verification = None;
+        self.grounding = None;
+    }
+
+    /// The answer as JSON, if it was asked for in JSON mode and came out valid.
+    fn json_content(&self) -> Option<serde_json::Value> {
+        if !self.structured || self.is_generating {
+            return None;
+        }
+        serde_json::from_str(&self.content).ok()
+    }
+
+    #[inline]
+    fn marker(content: String) -> Self {
+        Self {
+            content,
+            role: MessageRole::Assistant,
+            is_marker: true,
+            ..Default::default()
+        }
+    }
+
+    /// A summary in place of earlier messages, with what it replaced and a way back.
+    fn show_summary(
+        &self,
+        ui: &mut egui::Ui,
+        commonmark_cache: &mut CommonMarkCache,
+        idx: usize,
+    ) -> MessageAction {
+        let mut action = MessageAction::None;
+        let replaced: Vec<&Message> = self
+            .compacted
+            .iter()
+            .filter(|m| !m.is_marker && !m.is_thought)
+            .collect();
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.strong(tr!("summary-title", count = replaced.len()));
+                if ui
+                    .small_button(tr!("summary-restore"))
+                    .on_hover_text(tr!("summary-restore-hint"))
+                    .clicked()
+                {
+                    action = MessageAction::RestoreSummarized(idx);
+                }
+            });
+            egui::CollapsingHeader::new(tr!("summary-text"))
+                .id_salt(("summary_text", idx))
+                .default_open(true)
+                .show(ui, |ui| {
+                    crate::bidi::show_markdown(ui, commonmark_cache, &self.content, None);
+                });
+            egui::CollapsingHeader::new(tr!("summary-replaced"))
+                .id_salt(("summary_replaced", idx))
+                .show(ui, |ui| {
+                    for message in replaced {
+                        ui.horizontal(|ui| {
+                            ui.label(if message.is_user() { "👤" } else { "✨" });
+                            let preview = message.content.lines().next().unwrap_or_default();
+                            ui.add(egui::Label::new(preview).truncate())
+                                .on_hover_text(&message.content);
+                        });
+                    }
+                });
+        });
+        ui.add_space(12.0);
+        action
+    }
+
     #[inline]
-    const fn is_user(&self) -> bool {
+    pub const fn is_user(&self) -> bool {
         matches!(self.role, MessageRole::User)
     }
 
@@ -170,16 +372,43 @@ impl Message {
         &mut self,
         ui: &mut egui::Ui,
         commonmark_cache: &mut CommonMarkCache,
-        #[cfg(feature = "tts")] tts: SharedTts,
+        view: MessageView,
         idx: usize,
+        is_last: bool,
         prepend_buf: &mut String,
+        highlights: Option<&Highlights>,
     ) -> MessageAction {
+        let MessageView {
+            #[cfg(feature = "tts")]
+            tts,
+            #[cfg(feature = "tts")]
+            voice,
+            reading,
+            timestamps,
+            editor_command,
+            safety,
+            commands,
+        } = view;
+        if self.is_marker {
+            ui.separator();
+            ui.vertical_centered(|ui| {
+                ui.weak(&self.content);
+            });
+            ui.add_space(12.0);
+            return MessageAction::None;
+        }
+        if !self.compacted.is_empty() {
+            return self.show_summary(ui, commonmark_cache, idx);
+        }
+
         // message role
         let message_offset = ui
             .horizontal(|ui| {
                 if self.is_user() {
                     let f = ui.label("👤").rect.left();
-                    ui.label("You").rect.left() - f
+                    let offset = ui.label(tr!("you")).rect.left() - f;
+                    timestamps.label(ui, self.time);
+                    offset
                 } else {
                     let f = ui.label("✨").rect.left();
                     let offset = ui
@@ -189,19 +418,21 @@ impl Message {
                         .left()
                         - f;
                     // ui.add_enabled(false, egui::Label::new(&self.model.to_string())); //? todo redundant?
+                    timestamps.label(ui, self.time);
                     if let Some(duration) = self.generation_time {
-                        ui.weak(format!("({:.1}s)", duration.as_secs_f64()))
-                            .on_hover_text("Generation time");
+                        ui.weak(format!("({}s)", format_number(duration.as_secs_f64(), 1)))
+                            .on_hover_text(tr!("generation-time"));
                     }
                     if let Some(usage) = &self.usage {
                         let total = usage.total_token_count.unwrap_or(0);
-                        let text = format!(
-                            "In: {} / Out: {} / Total: {}",
-                            usage.prompt_token_count.unwrap_or(0),
-                            usage.candidates_token_count.unwrap_or(0),
-                            total
+                        let text = tr!(
+                            "token-usage",
+                            input = usage.prompt_token_count.unwrap_or(0),
+                            output = usage.candidates_token_count.unwrap_or(0),
+                            total = total,
                         );
-                        ui.weak(format!("{} ᵗ", total)).on_hover_text(text);
+                        ui.weak(format!("{} ᵗ", format_number(total as f64, 0)))
+                            .on_hover_text(text);
                     }
                     offset
                 }
@@ -213,8 +444,23 @@ impl Message {
             ui.add_space(-TextStyle::Body.resolve(ui.style()).size + 4.0);
         }
 
+        if !self.alternatives.is_empty() && !self.is_generating {
+            ui.horizontal(|ui| {
+                ui.add_space(message_offset);
+                for candidate in 0..=self.alternatives.len() {
+                    let label = if candidate == 0 {
+                        tr!("candidates-current")
+                    } else {
+                        tr!("candidates-tab", number = candidate + 1)
+                    };
+                    ui.selectable_value(&mut self.shown_candidate, candidate, label);
+                }
+            });
+        }
+
         // message content / spinner
         let mut action = MessageAction::None;
+        let mut promote = false;
         ui.horizontal(|ui| {
             ui.add_space(message_offset);
             if self.content.is_empty() && self.is_generating && !self.is_error {
@@ -236,22 +482,19 @@ impl Message {
                 });
             } else if self.is_error {
                 ui.vertical(|ui| {
-                    CommonMarkViewer::new().show(ui, commonmark_cache, &self.content);
+                    crate::bidi::show_markdown(ui, commonmark_cache, &self.content, None);
                     ui.add_space(8.0);
                     if ui
-                        .button("🔄 Retry Generation")
-                        .on_hover_text(
-                            "Try to generate a response again. Make sure you have a valid API Key and stable connection.",
-                        )
+                        .button(tr!("retry-generation"))
+                        .on_hover_text(tr!("retry-generation-hint"))
                         .clicked()
                     {
                         action = MessageAction::Retry(idx);
                     }
                 });
             } else if self.is_prepending {
-                let textedit = ui.add(
-                    egui::TextEdit::multiline(prepend_buf).hint_text("Prepend text to response…"),
-                );
+                let textedit =
+                    ui.add(egui::TextEdit::multiline(prepend_buf).hint_text(tr!("prepend-hint")));
                 macro_rules! cancel_prepend {
                     () => {
                         self.is_prepending = false;
@@ -263,11 +506,8 @@ impl Message {
                 }
                 ui.vertical(|ui| {
                     if ui
-                        .button("🔄 Regenerate")
-                        .on_hover_text(
-                            "Generate the response again, \
-                            the LLM will start after any prepended text",
-                        )
+                        .button(tr!("regenerate-button"))
+                        .on_hover_text(tr!("regenerate-hint"))
                         .clicked()
                     {
                         self.content = prepend_buf.clone();
@@ -277,16 +517,14 @@ impl Message {
                     }
                     if !prepend_buf.is_empty()
                         && ui
-                            .button("\u{270f} Edit")
-                            .on_hover_text(
-                                "Edit the message in the context, but don't regenerate it",
-                            )
+                            .button(tr!("edit-message-button"))
+                            .on_hover_text(tr!("edit-message-hint"))
                             .clicked()
                     {
                         self.content = prepend_buf.clone();
                         cancel_prepend!();
                     }
-                    if ui.button("❌ Cancel").clicked() {
+                    if ui.button(tr!("cancel-button")).clicked() {
                         cancel_prepend!();
                     }
                 });
@@ -310,24 +548,64 @@ impl Message {
                                         );
                                     })
                                     .show(ui, |ui| {
-                                        CommonMarkViewer::new().show(
+                                        crate::bidi::show_markdown(
                                             ui,
                                             commonmark_cache,
                                             &self.content,
+                                            None,
                                         );
                                     });
                             });
                     });
                     ui.add_space(4.0);
-                } else {
-                    CommonMarkViewer::new().max_image_width(Some(512)).show(
+                } else if let Some(alternative) = self
+                    .shown_candidate
+                    .checked_sub(1)
+                    .and_then(|i| self.alternatives.get(i))
+                {
+                    ui.vertical(|ui| {
+                        crate::bidi::show_markdown(ui, commonmark_cache, alternative, Some(512));
+                        promote = ui
+                            .button(tr!("candidates-promote"))
+                            .on_hover_text(tr!("candidates-promote-hint"))
+                            .clicked();
+                    });
+                } else if let Some(highlights) = highlights {
+                    crate::find::show_markdown(
                         ui,
                         commonmark_cache,
                         &self.content,
+                        highlights,
+                        Some(512),
                     );
+                } else if let Some(json) = self.json_content() {
+                    crate::structured::show_json(ui, idx, &json, self.schema.as_ref());
+                } else if self.structured && !self.is_generating {
+                    ui.vertical(|ui| {
+                        if let Err(e) = serde_json::from_str::<serde_json::Value>(&self.content) {
+                            let error = tr!("structured-not-json", error = e.to_string());
+                            ui.colored_label(ui.visuals().error_fg_color, error);
+                        }
+                        crate::bidi::show_markdown(ui, commonmark_cache, &self.content, Some(512));
+                    });
+                } else {
+                    let annotated = match &self.verification {
+                        Some(verification) => verification.annotate(&self.content),
+                        None => std::borrow::Cow::Borrowed(self.content.as_str()),
+                    };
+                    let content =
+                        crate::knowledge::footnote_markers(&annotated, self.sources.len());
+                    if reading.enabled && !self.is_user() {
+                        crate::reading::show_message(ui, commonmark_cache, &content, reading);
+                    } else {
+                        crate::bidi::show_markdown(ui, commonmark_cache, &content, Some(512));
+                    }
                 }
             }
         });
+        if promote {
+            self.promote_candidate();
+        }
 
         // files
         if !self.files.is_empty() {
@@ -338,13 +616,137 @@ impl Message {
                 ui.add_space(message_offset);
                 egui::ScrollArea::horizontal().id_salt(idx).show(ui, |ui| {
                     ui.horizontal(|ui| {
-                        crate::file_handler::show_files(ui, &mut self.files, false);
+                        if let Some(path) =
+                            crate::file_handler::show_files(ui, &mut self.files, false)
+                        {
+                            action = MessageAction::Reupload { message: idx, path };
+                        }
                     });
                 })
             });
             ui.add_space(8.0);
         }
 
+        if !self.images.is_empty() {
+            ui.horizontal(|ui| {
+                ui.add_space(message_offset);
+                if let Some(image) = crate::images::show_images(ui, self.time, &self.images, true) {
+                    action = MessageAction::EditImage(image);
+                }
+            });
+            ui.add_space(4.0);
+        }
+
+        if !self.audio.is_empty() {
+            ui.horizontal(|ui| {
+                ui.add_space(message_offset);
+                ui.vertical(|ui| crate::audio::show_audio(ui, self.time, &self.audio));
+            });
+            ui.add_space(4.0);
+        }
+
+        if !self.sources.is_empty() {
+            ui.horizontal(|ui| {
+                ui.add_space(message_offset);
+                if let Some(source) =
+                    crate::knowledge::show_citations(ui, idx, &self.sources, editor_command)
+                {
+                    action = MessageAction::Quarantine(source);
+                }
+            });
+            ui.add_space(4.0);
+        }
+
+        if !self.calls.is_empty() {
+            ui.horizontal(|ui| {
+                ui.add_space(message_offset);
+                let calls = ui
+                    .vertical(|ui| crate::functions::show_calls(ui, &mut self.calls, idx, commands))
+                    .inner;
+                match calls {
+                    CallAction::None => (),
+                    // calls still running answer themselves once they're done
+                    CallAction::Send if self.calls.iter().all(|c| c.response.is_some()) => {
+                        action = MessageAction::AnswerCalls;
+                    }
+                    CallAction::Send => (),
+                    CallAction::Run(call) => {
+                        action = MessageAction::RunCommand { message: idx, call };
+                    }
+                }
+            });
+            ui.add_space(4.0);
+        }
+
+        if let Some(grounding) = &self.grounding {
+            ui.horizontal(|ui| {
+                ui.add_space(message_offset);
+                ui.vertical(|ui| grounding.show(ui, idx));
+            });
+            ui.add_space(4.0);
+        }
+
+        if self.degraded && !self.is_generating {
+            ui.horizontal(|ui| {
+                ui.add_space(message_offset);
+                ui.colored_label(ui.visuals().warn_fg_color, tr!("stream-degraded"))
+                    .on_hover_text(tr!("stream-degraded-hint"));
+                if ui.small_button(tr!("retry-generation")).clicked() {
+                    action = MessageAction::Retry(idx);
+                }
+            });
+            ui.add_space(4.0);
+        }
+
+        if (self.truncated || self.interrupted) && !self.is_generating {
+            ui.horizontal(|ui| {
+                ui.add_space(message_offset);
+                if self.truncated {
+                    ui.colored_label(ui.visuals().warn_fg_color, tr!("answer-truncated"));
+                } else {
+                    ui.colored_label(ui.visuals().warn_fg_color, tr!("answer-interrupted"))
+                        .on_hover_text(tr!("answer-interrupted-hint"));
+                }
+                if is_last
+                    && !self.is_thought
+                    && !self.content.is_empty()
+                    && ui
+                        .small_button(tr!("continue-generating"))
+                        .on_hover_text(tr!("continue-generating-hint"))
+                        .clicked()
+                {
+                    action = MessageAction::Continue(idx);
+                }
+            });
+            ui.add_space(4.0);
+        }
+
+        if let Some(report) = &self.safety {
+            ui.horizontal(|ui| {
+                ui.add_space(message_offset);
+                let relax = ui.vertical(|ui| report.show(ui, safety)).inner;
+                if !relax.is_empty() {
+                    action = MessageAction::RelaxSafety(relax);
+                }
+            });
+            ui.add_space(4.0);
+        }
+
+        if self.is_verifying {
+            ui.horizontal(|ui| {
+                ui.add_space(message_offset);
+                ui.spinner();
+                ui.weak(tr!("verify-running"));
+            });
+            ui.add_space(4.0);
+        } else if let Some(verification) = &self.verification {
+            ui.horizontal(|ui| {
+                ui.add_space(message_offset);
+                ui.vertical(|ui| verification.show(ui, idx));
+            });
+            ui.add_space(4.0);
+        }
+
         if self.is_prepending {
             return action;
         }
@@ -364,9 +766,9 @@ impl Message {
                                 .fill(egui::Color32::TRANSPARENT),
                         )
                         .on_hover_text(if self.clicked_copy {
-                            "Copied!"
+                            tr!("copied")
                         } else {
-                            "Copy message"
+                            tr!("copy-message")
                         });
                     if copy.clicked() {
                         ui.ctx().copy_text(self.content.clone());
@@ -383,19 +785,19 @@ impl Message {
                                 .small()
                                 .fill(egui::Color32::TRANSPARENT),
                         )
-                        .on_hover_text("Read the message out loud. Right click to repeat");
+                        .on_hover_text(tr!("read-aloud-hint"));
 
                     if speak.clicked() {
                         if self.is_speaking {
                             self.is_speaking = false;
-                            tts_control(tts, String::new(), false);
+                            tts_control(tts, String::new(), false, None);
                         } else {
                             self.is_speaking = true;
-                            tts_control(tts, self.content.clone(), true);
+                            tts_control(tts, self.content.clone(), true, voice.map(str::to_owned));
                         }
-                    } else if speak.secondary_clicked() {
+                    } else if speak.secondary_clicked() || widgets::context_menu_pressed(&speak) {
                         self.is_speaking = true;
-                        tts_control(tts, self.content.clone(), true);
+                        tts_control(tts, self.content.clone(), true, voice.map(str::to_owned));
                     }
                 }
 
@@ -405,7 +807,7 @@ impl Message {
                             .small()
                             .fill(egui::Color32::TRANSPARENT),
                     )
-                    .on_hover_text("Remove")
+                    .on_hover_text(tr!("remove"))
                     .clicked()
                 {
                     action = MessageAction::Delete(idx);
@@ -420,12 +822,46 @@ impl Message {
                                 .small()
                                 .fill(egui::Color32::TRANSPARENT),
                         )
-                        .on_hover_text("Regenerate")
+                        .on_hover_text(tr!("regenerate"))
                         .clicked()
                 {
                     prepend_buf.clear();
                     self.is_prepending = true;
                 }
+
+                if !self.is_user() && !self.is_thought && prepend_buf.is_empty() {
+                    ui.menu_button("🔀", |ui| {
+                        ui.weak(tr!("regenerate-with"));
+                        for model in crate::models::available() {
+                            if ui
+                                .selectable_label(model == self.model, model.to_string())
+                                .clicked()
+                            {
+                                action = MessageAction::RegenerateWith(idx, model);
+                                ui.close();
+                            }
+                        }
+                    })
+                    .response
+                    .on_hover_text(tr!("regenerate-with-hint"));
+                }
+
+                if !self.is_user()
+                    && !self.is_thought
+                    && !self.is_verifying
+                    && !self.content.is_empty()
+                    && ui
+                        .add(
+                            egui::Button::new("🔍")
+                                .small()
+                                .fill(egui::Color32::TRANSPARENT),
+                        )
+                        .on_hover_text(tr!("verify-answer"))
+                        .clicked()
+                {
+                    self.is_verifying = true;
+                    action = MessageAction::Verify(idx);
+                }
             });
         }
         ui.add_space(12.0);
@@ -449,13 +885,36 @@ pub enum ChatProgress {
     Status {
         message: String,
     },
+    /// `sent` of `total` bytes of the file are uploaded, `total` is 0 until it's known
     FileUploading {
         path: PathBuf,
+        sent: u64,
+        total: u64,
     },
     FileUploaded {
         path: PathBuf,
         file: gemini_rust::File,
     },
+    /// The user cancelled the upload, the file was left out of the request
+    FileUploadCancelled {
+        path: PathBuf,
+    },
+    /// Knowledge excerpts put into the prompt
+    Sources(Vec<Citation>),
+    /// Search results and fetched pages the answer was grounded in
+    Grounding(Grounding),
+    /// The safety filters blocked the answer
+    Safety(SafetyReport),
+    /// Chunks of the stream were lost, the answer may be incomplete
+    Degraded,
+    /// The answer hit the output token limit
+    Truncated,
+    /// The user stopped the answer, what arrived so far is kept
+    Interrupted,
+    /// The answers of the other candidates
+    Alternatives(Vec<String>),
+    /// The retries ran out and this model answers instead
+    FellBack(GeminiModel),
 }
 
 pub type CompletionFlower =
@@ -473,24 +932,129 @@ pub struct Chat {
     pub model_picker: ModelPicker,
     pub files: Vec<Attachment>,
     pub prepend_buf: String,
+    pub persona: Option<u64>,
+    pub tags: Vec<u64>,
+    pub folder: Option<u64>,
+    /// Hidden from the main list, shown under "Archived" instead
+    pub archived: bool,
+    /// Knowledge collection relevant excerpts are looked up in
+    pub knowledge: Option<u64>,
+    /// Project of the folder the chat is in, kept up to date by the sessions
+    #[serde(skip)]
+    pub project: Option<Project>,
+    /// Cache holding the start of the chat, requests send only what came after
+    pub context_cache: Option<ContextCache>,
+    /// The chat got big enough that caching it is suggested
+    #[serde(skip)]
+    offer_cache: bool,
+    #[serde(skip)]
+    cache_declined: bool,
+    /// Whether the cache no longer matches the chat, with the state of the messages it
+    /// was checked against
+    #[serde(skip)]
+    cache_stale: Option<(u64, bool)>,
+    #[serde(skip)]
+    pub is_caching: bool,
+    /// Older messages are being condensed into a summary
+    #[serde(skip)]
+    pub is_summarizing: bool,
+    /// The user turned down summarizing the chat, or it failed
+    #[serde(skip)]
+    pub summarize_declined: bool,
+    /// The user sends the YouTube links of the chatbox as text
+    #[serde(skip)]
+    youtube_declined: bool,
+    /// Asks which files of an attached archive to extract
+    #[serde(skip)]
+    archive_picker: Option<ArchivePicker>,
+    /// Archives the user attached as they are
+    #[serde(skip)]
+    kept_archives: Vec<PathBuf>,
 
     #[serde(default = "generate_id")]
     pub id: u64,
+    /// Tokens of the context and the message being written
     #[serde(skip)]
     pub token_count: Option<u32>,
     #[serde(skip)]
     pub last_content_hash: u64,
+    /// When the changed content gets counted, once the typing pauses
     #[serde(skip)]
-    pub last_token_check: Option<Instant>,
+    pub token_count_due: Option<Instant>,
 
     #[serde(skip)]
     pub chatbox_height: f32,
     #[serde(skip)]
     pub flower: CompletionFlower,
+    /// Requests spawned and finished so far. They run one at a time, so the n-th
+    /// finish is the n-th request's.
+    #[serde(skip)]
+    requests_started: u64,
+    #[serde(skip)]
+    requests_finished: u64,
     #[serde(skip)]
     pub retry_message_idx: Option<usize>,
+    /// Safety category the user chose to relax from a blocked answer
+    #[serde(skip)]
+    relax_safety: Option<Vec<SafetyCategory>>,
+    /// Knowledge source the user quarantined or released
+    #[serde(skip)]
+    quarantine_source: Option<String>,
+    /// Image the user chose to edit in the image editor
+    #[serde(skip)]
+    edit_image: Option<GeneratedImage>,
+    /// Answer the user asked to fact-check
+    #[serde(skip)]
+    verify_message: Option<usize>,
+    /// Function call the user asked to answer with its local command
+    #[serde(skip)]
+    run_command: Option<(usize, usize)>,
+    /// Attachment whose expired upload the user asked to upload again
+    #[serde(skip)]
+    reupload: Option<(usize, PathBuf)>,
     #[serde(skip)]
     pub chatbox_highlighter: MemoizedEasymarkHighlighter,
+    #[serde(skip)]
+    pub prompt_improvement: Option<PromptImprovement>,
+    #[serde(skip)]
+    pub is_improving_prompt: bool,
+    /// The link typed into the attach-from-URL bar, `None` while it's closed
+    #[serde(skip)]
+    pub attach_url: Option<String>,
+    #[serde(skip)]
+    pub is_downloading: bool,
+    /// Message index to scroll to on the next frames (e.g. from search results)
+    #[serde(skip)]
+    pub jump_to_message: Option<usize>,
+    #[serde(skip)]
+    highlighted_message: Option<(usize, Instant)>,
+    /// Message to give keyboard focus once it is laid out
+    #[serde(skip)]
+    focus_message: Option<usize>,
+    #[serde(skip)]
+    last_content_height: f32,
+    #[serde(skip)]
+    find: Option<FindBar>,
+    #[serde(skip)]
+    focus_chatbox: bool,
+    /// Misspelled word the chatbox context menu was opened on
+    #[serde(skip)]
+    spell_target: Option<SpellTarget>,
+    /// An input method has uncommitted pre-edit text in the chatbox
+    #[serde(skip)]
+    ime_composing: bool,
+    /// Tag names from script hooks, turned into `tags` by the sessions
+    #[serde(skip)]
+    pub pending_tags: Vec<String>,
+    /// Sampling settings of the next request only
+    #[serde(skip)]
+    param_override: ParamOverride,
+    #[cfg(feature = "microphone")]
+    #[serde(skip)]
+    recorder: crate::recorder::Recorder,
+    /// Text a plugin's slash command is making for the chatbox
+    #[serde(skip)]
+    slash_command: Option<oneshot::Receiver<Result<String, String>>>,
 }
 
 impl Default for Chat {
@@ -500,7 +1064,15 @@ impl Default for Chat {
             chatbox_height: 0.0,
             messages: Vec::new(),
             flower: CompletionFlower::new(1),
+            requests_started: 0,
+            requests_finished: 0,
             retry_message_idx: None,
+            relax_safety: None,
+            quarantine_source: None,
+            edit_image: None,
+            verify_message: None,
+            run_command: None,
+            reupload: None,
             id: generate_id(),
             summary: String::new(),
             chatbox_highlighter: MemoizedEasymarkHighlighter::default(),
@@ -508,10 +1080,118 @@ impl Default for Chat {
             model_picker: ModelPicker::default(),
             files: Vec::new(),
             prepend_buf: String::new(),
+            persona: None,
+            tags: Vec::new(),
+            folder: None,
+            archived: false,
+            knowledge: None,
+            project: None,
+            context_cache: None,
+            offer_cache: false,
+            cache_declined: false,
+            cache_stale: None,
+            is_caching: false,
+            is_summarizing: false,
+            summarize_declined: false,
+            youtube_declined: false,
+            archive_picker: None,
+            kept_archives: Vec::new(),
+            prompt_improvement: None,
+            is_improving_prompt: false,
+            attach_url: None,
+            is_downloading: false,
+            jump_to_message: None,
+            highlighted_message: None,
+            focus_message: None,
+            last_content_height: 0.0,
+            find: None,
+            focus_chatbox: false,
+            spell_target: None,
+            ime_composing: false,
             token_count: None,
             last_content_hash: 0,
-            last_token_check: None,
+            token_count_due: None,
+            pending_tags: Vec::new(),
+            param_override: ParamOverride::default(),
+            #[cfg(feature = "microphone")]
+            recorder: Default::default(),
+            slash_command: None,
+        }
+    }
+}
+
+/// Whether a stream error is a chunk that didn't parse, after which the stream may go on.
+fn is_malformed_chunk(error: &(dyn std::error::Error + 'static)) -> bool {
+    let mut source = Some(error);
+    while let Some(e) = source {
+        if e.is::<serde_json::Error>() {
+            return true;
+        }
+        source = e.source();
+    }
+    false
+}
+
+/// Tells the UI the answer may be incomplete, once per answer.
+fn mark_degraded(handle: &CompletionFlowerHandle, index: usize, degraded: &mut bool) {
+    if !*degraded {
+        *degraded = true;
+        handle.send((index, ChatProgress::Degraded));
+    }
+}
+
+/// Tells the UI when the safety filters withheld or cut short the answer.
+fn report_safety(handle: &CompletionFlowerHandle, index: usize, response: &GenerationResponse) {
+    if let Some(report) = SafetyReport::from_response(response) {
+        log::warn!("the answer was blocked: {}", report.reason);
+        handle.send((index, ChatProgress::Safety(report)));
+    }
+}
+
+/// Tells the UI when the answer stopped at the output token limit.
+fn report_finish(handle: &CompletionFlowerHandle, index: usize, response: &GenerationResponse) {
+    let reason = serde_json::to_value(response).ok().and_then(|json| {
+        json.pointer("/candidates/0/finishReason")
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_owned)
+    });
+    if reason.as_deref() == Some("MAX_TOKENS") {
+        log::info!("the answer stopped at the output token limit");
+        handle.send((index, ChatProgress::Truncated));
+    }
+}
+
+/// The candidate that becomes the message. The text of the others is added to
+/// `alternatives`, by their index.
+fn primary_candidate<'a>(
+    candidates: &'a [Candidate],
+    alternatives: &mut Vec<String>,
+) -> Option<&'a Candidate> {
+    let mut primary = None;
+    for (position, candidate) in candidates.iter().enumerate() {
+        let index = candidate.index.map_or(position, |i| i as usize);
+        if index == 0 {
+            primary = Some(candidate);
+            continue;
+        }
+        if alternatives.len() < index {
+            alternatives.resize(index, String::new());
         }
+        for part in candidate.content.parts.iter().flatten() {
+            if let Part::Text { text, thought, .. } = part {
+                if !thought.unwrap_or(false) {
+                    alternatives[index - 1].push_str(text);
+                }
+            }
+        }
+    }
+    primary
+}
+
+/// Passes on the search results the answer was grounded in.
+fn report_grounding(handle: &CompletionFlowerHandle, index: usize, response: &GenerationResponse) {
+    if let Some(grounding) = Grounding::from_response(response) {
+        handle.send((index, ChatProgress::Grounding(grounding)));
     }
 }
 
@@ -522,22 +1202,33 @@ async fn request_completion(
     stop_generating: Arc<AtomicBool>,
     index: usize,
     use_streaming: bool,
-    public_file_upload: bool,
+    file_api: Option<FileApi>,
     generation_config: GenerationConfig,
+    safety_settings: Vec<SafetySetting>,
+    tools: Vec<Tool>,
+    // name of the context cache and how many messages it holds
+    cached_content: Option<(String, usize)>,
+    system_prompt: Option<String>,
+    examples: Vec<FewShotExample>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     log::info!(
         "Requesting completion... (history length: {})",
         messages.len()
     );
 
-    let history = crate::chat_completion::build_history(
+    let (mut history, cached_messages) = match &cached_content {
+        Some((_, cached_messages)) => (Vec::new(), *cached_messages),
+        None => (geminid_core::history::examples_to_history(&examples), 0),
+    };
+    history.extend(crate::chat_completion::build_history(
         &gemini,
         &messages,
+        cached_messages,
         None,
-        public_file_upload,
+        file_api.as_ref(),
         Some((index, handle)),
     )
-    .await?;
+    .await?);
 
     // 2. Prepare the request builder
     let mut content_builder = gemini.generate_content();
@@ -545,13 +1236,32 @@ async fn request_completion(
     // Inject constructed history
     content_builder.contents.extend(history);
 
+    match cached_content {
+        // the cache holds the system prompt and the tools, a request can't repeat them
+        Some((name, _)) => {
+            log::info!("using the context cache {name}");
+            content_builder =
+                content_builder.with_cached_content(&gemini.get_cached_content(&name));
+        }
+        None => {
+            if let Some(system_prompt) = system_prompt.filter(|p| !p.trim().is_empty()) {
+                content_builder = content_builder.with_system_instruction(system_prompt);
+            }
+            for tool in tools {
+                content_builder = content_builder.with_tool(tool);
+            }
+        }
+    }
+
     // Apply configuration
     let content_builder_final = content_builder
-        .with_safety_settings(SAFETY_SETTINGS.to_vec())
+        .with_safety_settings(safety_settings)
         .with_generation_config(generation_config);
 
     let mut response_text = String::new();
     let mut final_usage = None;
+    let mut degraded = false;
+    let mut alternatives = Vec::new();
 
     // Helper closure for cancellation polling
     let check_cancellation = || async {
@@ -577,6 +1287,7 @@ async fn request_completion(
             Some(Ok(s)) => s.into_stream(),
             Some(Err(e)) => return Err(e.into()),
             None => {
+                handle.send((index, ChatProgress::Interrupted));
                 handle.success((index, response_text, final_usage));
                 return Ok(());
             }
@@ -589,18 +1300,22 @@ async fn request_completion(
             tokio::select! {
                 _ = check_cancellation() => {
                     log::info!("Streaming generation cancelled by user.");
+                    handle.send((index, ChatProgress::Interrupted));
                     break;
                 }
                 next_item = stream.next() => {
                     match next_item {
                         Some(Ok(res)) => {
+                            report_safety(handle, index, &res);
+                            report_grounding(handle, index, &res);
+                            report_finish(handle, index, &res);
                             // Capture usage metadata if available
                             if let Some(usage) = res.usage_metadata {
                                 final_usage = Some(usage);
                             }
 
                             // Process candidates
-                            if let Some(candidate) = res.candidates.first() {
+                            if let Some(candidate) = primary_candidate(&res.candidates, &mut alternatives) {
                                 if let Some(parts) = &candidate.content.parts {
                                     for part in parts {
                                         // Send intermediate part to UI
@@ -614,6 +1329,18 @@ async fn request_completion(
                                 }
                             }
                         }
+                        // the client parses every event on its own, so what a broken one
+                        // held is lost. Skip it and flag the answer rather than abort
+                        Some(Err(e)) if is_malformed_chunk(&e) => {
+                            log::warn!("skipping a malformed stream chunk: {e}");
+                            mark_degraded(handle, index, &mut degraded);
+                        }
+                        // keep what arrived rather than losing the whole answer
+                        Some(Err(e)) if !response_text.is_empty() => {
+                            log::warn!("the stream broke off: {e}");
+                            mark_degraded(handle, index, &mut degraded);
+                            break;
+                        }
                         Some(Err(e)) => return Err(e.into()),
                         None => break, // Stream exhausted
                     }
@@ -624,14 +1351,19 @@ async fn request_completion(
         log::info!("Sending non-streaming request...");
 
         tokio::select! {
-            _ = check_cancellation() => {}
+            _ = check_cancellation() => {
+                handle.send((index, ChatProgress::Interrupted));
+            }
             result = content_builder_final.execute() => {
                 match result {
                     Ok(response) => {
                         log::info!("Non-streaming response received.");
+                        report_safety(handle, index, &response);
+                        report_grounding(handle, index, &response);
+                        report_finish(handle, index, &response);
                         final_usage = response.usage_metadata;
 
-                        if let Some(candidate) = response.candidates.first() {
+                        if let Some(candidate) = primary_candidate(&response.candidates, &mut alternatives) {
                             if let Some(parts) = &candidate.content.parts {
                                 for part in parts {
                                     handle.send((index, ChatProgress::Part(part.clone())));
@@ -652,6 +1384,9 @@ async fn request_completion(
         "Completion request finished. Total response length: {}",
         response_text.len()
     );
+    if !alternatives.is_empty() {
+        handle.send((index, ChatProgress::Alternatives(alternatives)));
+    }
 
     // Notify UI of success
     handle.success((index, response_text, final_usage));
@@ -667,6 +1402,10 @@ async fn request_completion_code_assist(
     index: usize,
     use_streaming: bool,
     generation_config: GenerationConfig,
+    safety_settings: Vec<SafetySetting>,
+    tools: Vec<Tool>,
+    system_prompt: Option<String>,
+    examples: Vec<FewShotExample>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     log::info!(
         "Requesting completion via Code Assist... (history length: {})",
@@ -675,27 +1414,35 @@ async fn request_completion_code_assist(
 
     let dummy_client = Gemini::new("")?;
 
-    let history = crate::chat_completion::build_history(
-        &dummy_client,
-        &messages,
-        None,
-        false,
-        Some((index, handle)),
-    )
-    .await?;
+    let mut history = geminid_core::history::examples_to_history(&examples);
+    history.extend(
+        crate::chat_completion::build_history(
+            &dummy_client,
+            &messages,
+            0,
+            None,
+            None,
+            Some((index, handle)),
+        )
+        .await?,
+    );
 
     let gemini_request = gemini_rust::GenerateContentRequest {
         contents: history,
         generation_config: Some(generation_config),
-        safety_settings: Some(SAFETY_SETTINGS.to_vec()),
-        tools: None,
+        safety_settings: Some(safety_settings),
+        tools: (!tools.is_empty()).then_some(tools),
         tool_config: None,
-        system_instruction: None,
+        system_instruction: system_prompt
+            .filter(|p| !p.trim().is_empty())
+            .map(Content::text),
         cached_content: None,
     };
 
     let mut response_text = String::new();
     let mut final_usage = None;
+    let mut degraded = false;
+    let mut alternatives = Vec::new();
 
     let check_cancellation = || async {
         loop {
@@ -717,15 +1464,19 @@ async fn request_completion_code_assist(
             tokio::select! {
                 _ = check_cancellation() => {
                     log::info!("Code Assist generation cancelled.");
+                    handle.send((index, ChatProgress::Interrupted));
                     break;
                 }
                 next_item = futures::StreamExt::next(&mut stream) => {
                     match next_item {
                         Some(Ok(res)) => {
+                            report_safety(handle, index, &res);
+                            report_grounding(handle, index, &res);
+                            report_finish(handle, index, &res);
                             if let Some(usage) = res.usage_metadata {
                                 final_usage = Some(usage);
                             }
-                            if let Some(candidate) = res.candidates.first() {
+                            if let Some(candidate) = primary_candidate(&res.candidates, &mut alternatives) {
                                 if let Some(parts) = &candidate.content.parts {
                                     for part in parts {
                                         handle.send((index, ChatProgress::Part(part.clone())));
@@ -736,6 +1487,16 @@ async fn request_completion_code_assist(
                                 }
                             }
                         }
+                        // the adapter already put split events back together, this one was garbage
+                        Some(Err(e)) if is_malformed_chunk(&e) => {
+                            log::warn!("skipping a malformed stream chunk: {e}");
+                            mark_degraded(handle, index, &mut degraded);
+                        }
+                        Some(Err(e)) if !response_text.is_empty() => {
+                            log::warn!("the stream broke off: {e}");
+                            mark_degraded(handle, index, &mut degraded);
+                            break;
+                        }
                         Some(Err(e)) => return Err(e.into()),
                         None => break,
                     }
@@ -746,12 +1507,16 @@ async fn request_completion_code_assist(
         tokio::select! {
             _ = check_cancellation() => {
                 log::info!("Code Assist generation cancelled.");
+                handle.send((index, ChatProgress::Interrupted));
             }
             result = client.generate_content(&gemini_request) => {
                 match result {
                     Ok(response) => {
+                        report_safety(handle, index, &response);
+                        report_grounding(handle, index, &response);
+                        report_finish(handle, index, &response);
                         final_usage = response.usage_metadata;
-                        if let Some(candidate) = response.candidates.first() {
+                        if let Some(candidate) = primary_candidate(&response.candidates, &mut alternatives) {
                             if let Some(parts) = &candidate.content.parts {
                                 for part in parts {
                                     handle.send((index, ChatProgress::Part(part.clone())));
@@ -768,6 +1533,9 @@ async fn request_completion_code_assist(
         }
     }
 
+    if !alternatives.is_empty() {
+        handle.send((index, ChatProgress::Alternatives(alternatives)));
+    }
     handle.success((index, response_text, final_usage));
     Ok(())
 }
@@ -799,26 +1567,14 @@ impl ChatExportFormat {
     }
 }
 
-pub async fn export_messages(
-    messages: Vec<Message>,
+pub fn write_messages(
+    mut f: impl Write,
+    messages: &[Message],
     format: ChatExportFormat,
-    task: impl std::future::Future<Output = Option<rfd::FileHandle>>,
-) -> Result<egui_notify::Toast> {
-    let Some(file) = task.await else {
-        log::info!("export cancelled");
-        return Ok(egui_notify::Toast::info("Export cancelled"));
-    };
-    log::info!(
-        "exporting {} messages to {file:?} (format: {format:?})...",
-        messages.len()
-    );
-
-    let f = std::fs::File::create(file.path())?;
-    let mut f = std::io::BufWriter::new(f);
-
+) -> Result<()> {
     match format {
         ChatExportFormat::Plaintext => {
-            for msg in &messages {
+            for msg in messages {
                 writeln!(
                     f,
                     "{} - {:?} ({}): {}",
@@ -830,13 +1586,32 @@ pub async fn export_messages(
             }
         }
         ChatExportFormat::Json => {
-            serde_json::to_writer_pretty(&mut f, &messages)?;
+            serde_json::to_writer_pretty(&mut f, messages)?;
         }
         ChatExportFormat::Ron => {
-            ron::Options::default().to_io_writer_pretty(&mut f, &messages, Default::default())?;
+            ron::Options::default().to_io_writer_pretty(&mut f, messages, Default::default())?;
         }
     }
+    Ok(())
+}
+
+pub async fn export_messages(
+    messages: Vec<Message>,
+    format: ChatExportFormat,
+    task: impl std::future::Future<Output = Option<rfd::FileHandle>>,
+) -> Result<egui_notify::Toast> {
+    let Some(file) = task.await else {
+        log::info!("export cancelled");
+        return Ok(egui_notify::Toast::info(tr!("export-cancelled")));
+    };
+    log::info!(
+        "exporting {} messages to {file:?} (format: {format:?})...",
+        messages.len()
+    );
 
+    let f = std::fs::File::create(file.path())?;
+    let mut f = std::io::BufWriter::new(f);
+    write_messages(&mut f, &messages, format)?;
     f.flush().context("failed to flush writer")?;
 
     log::info!("export complete");
@@ -867,10 +1642,34 @@ fn make_summary(prompt: &str) -> String {
     summary
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum ChatAction {
     None,
     PickFiles { id: usize },
+    AttachUrl { id: usize, url: String },
+    AttachFolder { id: usize },
+    #[cfg(feature = "screenshots")]
+    CaptureScreen { id: usize },
+    ImprovePrompt { id: usize },
+    PreviewRequest { id: usize },
+    CacheContext { id: usize },
+    DropCache(String),
+    Summarize { id: usize },
+    IgnoreWord(String),
+    RelaxSafety(Vec<SafetyCategory>),
+    Quarantine(String),
+    EditImage(GeneratedImage),
+    Verify { id: usize, message: usize },
+    RunCommand {
+        id: usize,
+        message: usize,
+        call: usize,
+    },
+    Reupload {
+        id: usize,
+        message: usize,
+        path: PathBuf,
+    },
 }
 
 impl Chat {
@@ -888,15 +1687,31 @@ impl Chat {
         self.id as usize
     }
 
-    fn send_message(&mut self, settings: &Settings) {
+    /// The summary, or a placeholder while the chat doesn't have one.
+    pub fn title(&self) -> String {
+        if self.summary.is_empty() {
+            tr!("new-chat")
+        } else {
+            self.summary.clone()
+        }
+    }
+
+    /// Returns whether a request was started, scripts may cancel it.
+    fn send_message(&mut self, settings: &Settings) -> bool {
         if self.chatbox.is_empty() && self.files.is_empty() {
-            return;
+            return false;
         }
 
         // remove old error messages
         self.messages.retain(|m| !m.is_error);
 
         let prompt = self.chatbox.trim_end().to_string();
+        let mut effects = settings.scripting.run(Hook::BeforeSend, self, &prompt);
+        if effects.cancel {
+            return false;
+        }
+        let prompt = effects.text.take().unwrap_or(prompt);
+        self.apply_script_effects(effects);
         let model = self.model_picker.selected;
         self.messages
             .push(Message::user(prompt.clone(), model, self.files.clone()));
@@ -910,15 +1725,72 @@ impl Chat {
 
         self.messages.push(Message::assistant(String::new(), model));
 
+        self.offer_cache = settings.cache.offer
+            && settings.auth_method == widgets::AuthMethod::ApiKey
+            && !self.cache_declined
+            && self.context_cache.is_none()
+            && crate::context_cache::worth_caching(self.system_prompt().as_deref(), &self.messages);
+
         self.spawn_completion(settings, None);
+        true
     }
 
-    fn spawn_completion(&self, settings: &Settings, target_index: Option<usize>) {
-        let handle = self.flower.handle();
-        let stop_generation = self.stop_generating.clone();
-        let mut messages = self.messages.clone();
-        let index = target_index.unwrap_or(self.messages.len() - 1);
+    /// Asks for the next answer without a new message, after the user answered the
+    /// model's function calls.
+    pub fn continue_generation(&mut self, settings: &Settings) {
+        if self.flower_active() {
+            return;
+        }
+        self.messages.retain(|m| !m.is_error);
+        let model = self.model_picker.selected;
+        self.messages.push(Message::assistant(String::new(), model));
+        self.spawn_completion(settings, None);
+    }
+
+    /// Asks the model to go on with the answer at `idx`, which the output token limit or
+    /// the user cut off. The continuation is stitched onto the same message.
+    fn continue_response(&mut self, settings: &Settings, idx: usize) {
+        if self.flower_active() || idx + 1 != self.messages.len() {
+            return;
+        }
+        let message = &mut self.messages[idx];
+        message.truncated = false;
+        message.interrupted = false;
+        message.is_generating = true;
+        message.requested_at = Instant::now();
+        let model = message.model;
+        self.spawn_request(settings, Some(idx), model, true);
+    }
+
+    /// Applies what a script hook returned, apart from the text which depends on the hook.
+    pub fn apply_script_effects(&mut self, effects: ScriptEffects) {
+        if let Some(title) = effects.title {
+            self.summary = title;
+        }
+        if let Some(model) = effects.model {
+            self.model_picker.selected = model;
+        }
+        self.pending_tags.extend(effects.tags);
+    }
 
+    /// Passes the finished answer through the `on_response` script hooks.
+    pub fn run_response_hook(&mut self, settings: &Settings) {
+        let Some(last) = self.messages.last().filter(|m| !m.is_user() && !m.is_error) else {
+            return;
+        };
+        let mut effects = settings.scripting.run(Hook::Response, self, &last.content);
+        if let Some(text) = effects.text.take() {
+            if let Some(last) = self.messages.last_mut() {
+                last.content = text;
+            }
+        }
+        self.apply_script_effects(effects);
+    }
+
+    /// The messages as they go into the request, with the thoughts turned into text
+    /// when they are kept in the history.
+    fn outgoing_messages(&self, settings: &Settings) -> Vec<Message> {
+        let mut messages = self.messages.clone();
         if settings.include_thoughts_in_history {
             for msg in &mut messages {
                 if msg.is_thought {
@@ -929,50 +1801,227 @@ impl Chat {
                 }
             }
         }
+        messages
+    }
+
+    /// The system prompt, after the instructions of the project.
+    pub fn system_prompt(&self) -> Option<String> {
+        let system_prompt = self.model_picker.system_prompt.clone();
+        let Some(project) = self
+            .project
+            .as_ref()
+            .filter(|p| !p.instructions.trim().is_empty())
+        else {
+            return system_prompt;
+        };
+        Some(match system_prompt {
+            Some(prompt) if !prompt.trim().is_empty() => {
+                format!("{}\n\n{prompt}", project.instructions)
+            }
+            _ => project.instructions.clone(),
+        })
+    }
+
+    /// Tools sent with the requests, the model's own and the declared functions.
+    fn tools(&self, settings: &Settings) -> Vec<Tool> {
+        let mut tools = self.model_picker.tools();
+        tools.extend(crate::functions::to_tool(&settings.functions));
+        tools.extend(crate::plugins::to_tool());
+        tools
+    }
+
+    fn cache_fingerprint(&self, settings: &Settings, messages: usize) -> u64 {
+        crate::context_cache::fingerprint(
+            self.model_picker.selected,
+            self.system_prompt().as_deref(),
+            &self.tools(settings),
+            &self.model_picker.examples,
+            &self.messages[..messages.min(self.messages.len())],
+        )
+    }
+
+    /// The cache, if requests can use it: it is for the selected model, hasn't expired
+    /// and still holds what the chat starts with.
+    fn usable_cache(&self, settings: &Settings) -> Option<&ContextCache> {
+        let cache = self.context_cache.as_ref()?;
+        let usable = settings.auth_method == widgets::AuthMethod::ApiKey
+            && !cache.expired()
+            && cache.model == self.model_picker.selected
+            // the excerpts go into the system prompt, which is cached
+            && self.knowledge_collection().is_none()
+            && cache.messages <= self.messages.len()
+            && self.cache_fingerprint(settings, cache.messages) == cache.fingerprint;
+        usable.then_some(cache)
+    }
+
+    /// Whether the cache no longer holds what the chat starts with. The fingerprint is
+    /// only taken again once the messages or the cache change.
+    fn is_cache_stale(&mut self, settings: &Settings) -> bool {
+        let Some(cache) = &self.context_cache else {
+            return false;
+        };
+        // what the fingerprint looks at of the messages
+        let mut hasher = DefaultHasher::new();
+        cache.name.hash(&mut hasher);
+        for message in &self.messages {
+            message.time.hash(&mut hasher);
+            message.content.len().hash(&mut hasher);
+            for file in &message.files {
+                file.path.hash(&mut hasher);
+            }
+        }
+        let key = hasher.finish();
+        if let Some((checked, stale)) = self.cache_stale {
+            if checked == key {
+                return stale;
+            }
+        }
+        let stale = cache.messages > self.messages.len()
+            || self.cache_fingerprint(settings, cache.messages) != cache.fingerprint;
+        self.cache_stale = Some((key, stale));
+        stale
+    }
+
+    /// What caching the chat as it is now would store.
+    pub fn cache_request(&self, settings: &Settings) -> CacheRequest {
+        CacheRequest {
+            display_name: self.title(),
+            model: self.model_picker.selected,
+            system_prompt: self.system_prompt(),
+            tools: self.tools(settings),
+            examples: self.model_picker.examples.clone(),
+            messages: self.outgoing_messages(settings),
+            fingerprint: self.cache_fingerprint(settings, self.messages.len()),
+            replaces: self.context_cache.as_ref().map(|c| c.name.clone()),
+        }
+    }
+
+    pub fn set_context_cache(&mut self, cache: ContextCache) {
+        self.context_cache = Some(cache);
+        self.offer_cache = false;
+    }
+
+    /// Knowledge collection of the chat, or of its project.
+    fn knowledge_collection(&self) -> Option<u64> {
+        self.knowledge
+            .or_else(|| self.project.as_ref().and_then(|p| p.knowledge))
+    }
+
+    /// What sending the chatbox now would put into the request.
+    pub fn draft_request(&self, settings: &Settings) -> crate::request_preview::Draft {
+        let mut messages = self.outgoing_messages(settings);
+        let input = self.chatbox.trim_end();
+        if !input.is_empty() || !self.files.is_empty() {
+            messages.push(Message::user(
+                input.to_owned(),
+                self.model_picker.selected,
+                self.files.clone(),
+            ));
+        }
+        let query = messages
+            .iter()
+            .rev()
+            .find(|m| m.is_user())
+            .map(|m| m.content.clone());
+        crate::request_preview::Draft {
+            chat_id: self.id(),
+            model_picker: self.model_picker.clone(),
+            system_prompt: self.system_prompt(),
+            examples: self.model_picker.examples.clone(),
+            knowledge: self.knowledge_collection().zip(query),
+            messages,
+        }
+    }
+
+    fn spawn_completion(&mut self, settings: &Settings, target_index: Option<usize>) {
+        self.spawn_request(settings, target_index, self.model_picker.selected, false);
+    }
+
+    /// Requests the answer at `target_index`, the last message by default, from `model`.
+    /// With `continuation` the model is asked to go on from where that answer stopped.
+    fn spawn_request(
+        &mut self,
+        settings: &Settings,
+        target_index: Option<usize>,
+        model: GeminiModel,
+        continuation: bool,
+    ) {
+        let handle = self.flower.handle();
+        self.requests_started += 1;
+        let stop_generation = self.stop_generating.clone();
+        // a cancel that came too late for the last upload of an attachment
+        for attachment in self.messages.iter_mut().flat_map(|m| m.files.iter_mut()) {
+            attachment.renew_cancel();
+        }
+        let mut messages = self.outgoing_messages(settings);
+        let index = target_index.unwrap_or(self.messages.len() - 1);
 
         let use_streaming = settings.use_streaming;
         let public_file_upload = settings.public_file_upload;
-        let generation_config = self.model_picker.get_generation_config();
+        let mut generation_config = self.model_picker.get_generation_config();
+        std::mem::take(&mut self.param_override).apply(&mut generation_config);
+        if continuation {
+            // thoughts would split the answer into another message
+            if let Some(thinking) = &mut generation_config.thinking_config {
+                thinking.include_thoughts = None;
+            }
+            messages.truncate(index + 1);
+            messages.push(Message::user(CONTINUE_PROMPT.to_owned(), model, Vec::new()));
+        }
+        let safety_settings = self.model_picker.safety_settings(&settings.safety).to_api();
+        let tools = self.tools(settings);
+        let cached_content = self
+            .usable_cache(settings)
+            .filter(|cache| cache.model == model)
+            .map(|cache| (cache.name.clone(), cache.messages));
         let auth_method = settings.auth_method;
         let api_key = settings.api_key.clone();
         let oauth_token = settings.oauth_token.clone();
         let project_id = settings.project_id.clone();
         let proxy_path = settings.proxy_path.clone();
         let model_picker = self.model_picker.clone();
+        let retry = settings.retry.clone();
+        let mut system_prompt = self.system_prompt();
+        let examples = self.model_picker.examples.clone();
+        let knowledge = self.knowledge_collection().zip(
+            self.messages[..index]
+                .iter()
+                .rev()
+                .find(|m| m.is_user())
+                .map(|m| m.content.clone()),
+        );
+        let knowledge_settings = knowledge.is_some().then(|| settings.clone());
 
         tokio::spawn(async move {
             handle.activate();
 
+            if let (Some((collection, query)), Some(settings)) = (knowledge, knowledge_settings) {
+                handle.send((
+                    index,
+                    ChatProgress::Status {
+                        message: tr!("knowledge-searching"),
+                    },
+                ));
+                match crate::knowledge::retrieve(&settings, collection, &query).await {
+                    Ok(citations) => {
+                        if citations.iter().any(|c| !c.quarantined) {
+                            system_prompt = Some(crate::knowledge::with_context(
+                                system_prompt.take(),
+                                &citations,
+                            ));
+                        }
+                        handle.send((index, ChatProgress::Sources(citations)));
+                    }
+                    Err(e) => log::warn!("failed to search the knowledge collection: {e}"),
+                }
+            }
+
             match auth_method {
                 crate::widgets::AuthMethod::ApiKey => {
                     if api_key.is_empty() {
                         handle.error((index, "API key not set.".to_string()));
                         return;
                     }
-
-                    match model_picker.create_client(&api_key, proxy_path) {
-                        Ok(gemini) => {
-                            let _ = request_completion(
-                                gemini,
-                                messages,
-                                &handle,
-                                stop_generation,
-                                index,
-                                use_streaming,
-                                public_file_upload,
-                                generation_config,
-                            )
-                            .await
-                            .map_err(|e| {
-                                log::error!("failed to request completion: {e}");
-                                handle.error((index, e.to_string()));
-                            });
-                        }
-                        Err(e) => {
-                            log::error!("failed to create client: {e}");
-                            handle.error((index, format!("Failed to create client: {}", e)));
-                        }
-                    }
                 }
                 crate::widgets::AuthMethod::CodeAssist => {
                     if oauth_token.is_empty() || project_id.is_empty() {
@@ -983,48 +2032,211 @@ impl Chat {
                         ));
                         return;
                     }
+                }
+            }
 
-                    let mut client =
-                        gemini_code_assist_adapter::CodeAssistClient::new(oauth_token, project_id)
-                            .with_model(model_picker.selected.to_string());
-
-                    // Handshake
-                    match client.load_code_assist().await {
-                        Ok(effective_proj) => {
-                            client.set_project_id(effective_proj);
+            let mut model = model;
+            let mut attempt = 0;
+            loop {
+                let result = match auth_method {
+                    crate::widgets::AuthMethod::ApiKey => {
+                        let mut picker = model_picker.clone();
+                        picker.selected = model;
+                        match picker.create_client(&api_key, proxy_path.clone()) {
+                            Ok(gemini) => request_completion(
+                                gemini,
+                                messages.clone(),
+                                &handle,
+                                stop_generation.clone(),
+                                index,
+                                use_streaming,
+                                public_file_upload.then(|| FileApi {
+                                    api_key: api_key.clone(),
+                                    proxy: proxy_path.clone(),
+                                }),
+                                generation_config.clone(),
+                                safety_settings.clone(),
+                                tools.clone(),
+                                cached_content.clone(),
+                                system_prompt.clone(),
+                                examples.clone(),
+                            )
+                            .await
+                            .map_err(|e| e.to_string()),
+                            Err(e) => {
+                                log::error!("failed to create client: {e}");
+                                handle.error((index, format!("Failed to create client: {}", e)));
+                                return;
+                            }
                         }
-                        Err(e) => log::warn!("Code Assist handshake failed: {e}"),
                     }
+                    crate::widgets::AuthMethod::CodeAssist => {
+                        let mut client = gemini_code_assist_adapter::CodeAssistClient::new(
+                            oauth_token.clone(),
+                            project_id.clone(),
+                        )
+                        .with_model(model.to_string());
 
-                    if let Err(e) = client.onboard_user().await {
-                        log::warn!("Code Assist onboarding warning: {e}");
-                    }
+                        // Handshake
+                        match client.load_code_assist().await {
+                            Ok(effective_proj) => {
+                                client.set_project_id(effective_proj);
+                            }
+                            Err(e) => log::warn!("Code Assist handshake failed: {e}"),
+                        }
 
-                    let _ = request_completion_code_assist(
-                        client,
-                        messages,
-                        &handle,
-                        stop_generation,
-                        index,
-                        use_streaming,
-                        generation_config,
-                    )
-                    .await
-                    .map_err(|e| {
-                        log::error!("failed to request completion via Code Assist: {e}");
-                        handle.error((index, e.to_string()));
-                    });
+                        if let Err(e) = client.onboard_user().await {
+                            log::warn!("Code Assist onboarding warning: {e}");
+                        }
+
+                        request_completion_code_assist(
+                            client,
+                            messages.clone(),
+                            &handle,
+                            stop_generation.clone(),
+                            index,
+                            use_streaming,
+                            generation_config.clone(),
+                            safety_settings.clone(),
+                            tools.clone(),
+                            system_prompt.clone(),
+                            examples.clone(),
+                        )
+                        .await
+                        .map_err(|e| e.to_string())
+                    }
+                };
+                let Err(error) = result else {
+                    return;
+                };
+                log::error!("failed to request completion from {model}: {error}");
+
+                // a context cache belongs to the model it was made for
+                match retry.next(&error, attempt, model, cached_content.is_none()) {
+                    crate::retry::Step::Wait(delay) => {
+                        attempt += 1;
+                        log::info!("retrying in {delay:?}, attempt {attempt}");
+                        handle.send((
+                            index,
+                            ChatProgress::Status {
+                                message: tr!(
+                                    "retry-waiting",
+                                    seconds = delay.as_secs(),
+                                    attempt = attempt,
+                                    attempts = retry.attempts
+                                ),
+                            },
+                        ));
+                        let stopped = || stop_generation.swap(false, Ordering::SeqCst);
+                        if !crate::retry::wait(delay, stopped).await {
+                            handle.send((index, ChatProgress::Interrupted));
+                            handle.success((index, String::new(), None));
+                            return;
+                        }
+                    }
+                    crate::retry::Step::FallBack(fallback) => {
+                        log::info!("falling back from {model} to {fallback}");
+                        model = fallback;
+                        attempt = 0;
+                        handle.send((index, ChatProgress::FellBack(fallback)));
+                        handle.send((
+                            index,
+                            ChatProgress::Status {
+                                message: tr!("retry-falling-back", model = fallback.to_string()),
+                            },
+                        ));
+                    }
+                    crate::retry::Step::GiveUp => {
+                        handle.error((index, error));
+                        return;
+                    }
                 }
             }
         });
     }
 
-    fn regenerate_response(&mut self, settings: &Settings, idx: usize) {
+    /// Generates the answer at `idx` again with `model`, which may differ from the chat's.
+    fn regenerate_response(&mut self, settings: &Settings, idx: usize, model: GeminiModel) {
         // todo: regenerate works weird
         self.messages[idx].content = self.prepend_buf.clone();
+        self.messages[idx].model = model;
+        self.messages[idx].requested_at = Instant::now();
+        self.messages[idx].safety = None;
+        self.messages[idx].grounding = None;
+        self.messages[idx].degraded = false;
+        self.messages[idx].truncated = false;
+        self.messages[idx].interrupted = false;
+        self.messages[idx].signature = None;
+        self.messages[idx].verification = None;
+        self.messages[idx].structured = false;
+        self.messages[idx].schema = None;
+        self.messages[idx].alternatives.clear();
+        self.messages[idx].images.clear();
+        self.messages[idx].audio.clear();
+        self.messages[idx].shown_candidate = 0;
+        self.prepend_buf.clear();
+
+        self.spawn_request(settings, Some(idx), model, false);
+    }
+
+    /// Regenerates the last response, if the chat ends with one and nothing is generating.
+    pub fn regenerate_last(&mut self, settings: &Settings) {
+        if self.flower_active() {
+            return;
+        }
+        let Some(idx) = self.messages.len().checked_sub(1) else {
+            return;
+        };
+        let message = &mut self.messages[idx];
+        if message.is_user() || message.is_marker {
+            return;
+        }
+        message.is_generating = true;
         self.prepend_buf.clear();
+        self.regenerate_response(settings, idx, self.model_picker.selected);
+    }
 
-        self.spawn_completion(settings, Some(idx));
+    /// Reads the last response aloud with the voice of the chat's persona.
+    #[cfg(feature = "tts")]
+    pub fn speak_last(&mut self, tts: SharedTts, personas: &[Persona]) {
+        let voice = crate::persona::find(personas, self.persona).and_then(|p| p.voice.clone());
+        let Some(message) = self.messages.last_mut() else {
+            return;
+        };
+        if message.is_user() || message.is_error || message.content.is_empty() {
+            return;
+        }
+        message.is_speaking = true;
+        tts_control(tts, message.content.clone(), true, voice);
+    }
+
+    #[inline]
+    pub fn stop(&self) {
+        self.stop_generating.store(true, Ordering::SeqCst);
+    }
+
+    pub fn toggle_find(&mut self) {
+        self.find = match self.find {
+            Some(_) => None,
+            None => Some(FindBar::new()),
+        };
+    }
+
+    #[inline]
+    pub fn focus_chatbox(&mut self) {
+        self.focus_chatbox = true;
+    }
+
+    /// Moves the highlighted message by `delta`, starting from the end of the chat.
+    pub fn step_message(&mut self, delta: isize) {
+        let Some(last) = self.messages.len().checked_sub(1) else {
+            return;
+        };
+        let current = self
+            .highlighted_message
+            .map_or(self.messages.len(), |(idx, _)| idx);
+        let target = (current as isize).saturating_add(delta).clamp(0, last as isize) as usize;
+        self.jump_to_message = Some(target);
     }
 
     fn show_chatbox(
@@ -1033,21 +2245,32 @@ impl Chat {
         is_max_height: bool,
         is_generating: bool,
         settings: &Settings,
+        roomy: bool,
     ) -> ChatAction {
         let mut action = ChatAction::None;
         if let Some(idx) = self.retry_message_idx.take() {
-            self.chatbox = self.messages[idx - 1].content.clone();
-            self.files = self.messages[idx - 1].files.clone();
-            self.messages.remove(idx);
-            self.messages.remove(idx - 1);
-            self.send_message(settings);
+            if self.messages[idx - 1].is_user() {
+                self.chatbox = self.messages[idx - 1].content.clone();
+                self.files = self.messages[idx - 1].files.clone();
+                self.messages.remove(idx);
+                self.messages.remove(idx - 1);
+                self.send_message(settings);
+            } else {
+                // answers to function calls aren't messages, ask again after them
+                self.messages.remove(idx);
+                self.continue_generation(settings);
+            }
         }
 
         if is_max_height {
             ui.add_space(8.0);
         }
 
+        self.show_archive_picker(ui.ctx());
+        self.poll_slash_command();
+
         let images_height = if !self.files.is_empty() {
+            crate::injection::scan_attachments(&settings.injection, &mut self.files);
             ui.add_space(8.0);
             let height = ui
                 .horizontal(|ui| {
@@ -1061,18 +2284,235 @@ impl Chat {
             0.0
         };
 
-        ui.horizontal_centered(|ui| {
+        let links = crate::youtube::find_links(&self.chatbox).len();
+        if links == 0 {
+            self.youtube_declined = false;
+        }
+        let youtube_height = if links > 0 && !self.youtube_declined {
+            ui.add_space(8.0);
+            let resp = ui.scope(|ui| crate::youtube::show_banner(ui, links));
+            match resp.inner {
+                Some(true) => {
+                    let videos = crate::youtube::take_links(&mut self.chatbox);
+                    self.files
+                        .extend(videos.into_iter().map(Attachment::youtube));
+                }
+                Some(false) => self.youtube_declined = true,
+                None => (),
+            }
+            resp.response.rect.height() + 8.0
+        } else {
+            0.0
+        };
+
+        let url_height = if self.attach_url.is_some() {
+            ui.add_space(8.0);
+            let resp = ui.scope(|ui| self.show_url_bar(ui));
+            if let Some(url) = resp.inner {
+                self.is_downloading = true;
+                action = ChatAction::AttachUrl { id: self.id(), url };
+            }
+            resp.response.rect.height() + 8.0
+        } else {
+            0.0
+        };
+
+        let improvement_height = if let Some(improvement) = &mut self.prompt_improvement {
+            ui.add_space(8.0);
+            let resp = ui.scope(|ui| improvement.show(ui));
+            match resp.inner {
+                Some(true) => {
+                    self.chatbox = improvement.result();
+                    self.prompt_improvement = None;
+                }
+                Some(false) => self.prompt_improvement = None,
+                None => (),
+            }
+            resp.response.rect.height() + 8.0
+        } else {
+            0.0
+        };
+
+        let cache_height = if self.is_caching || self.offer_cache || self.context_cache.is_some() {
+            ui.add_space(8.0);
+            let stale = self.is_cache_stale(settings);
+            let resp = ui.scope(|ui| {
+                crate::context_cache::show_banner(
+                    ui,
+                    self.context_cache.as_ref(),
+                    stale,
+                    self.is_caching,
+                    !is_generating,
+                )
+            });
+            match resp.inner {
+                Some(BannerAction::Create) => {
+                    action = ChatAction::CacheContext { id: self.id() };
+                }
+                Some(BannerAction::Dismiss) => {
+                    self.offer_cache = false;
+                    self.cache_declined = true;
+                }
+                Some(BannerAction::Drop) => {
+                    if let Some(cache) = self.context_cache.take() {
+                        action = ChatAction::DropCache(cache.name);
+                    }
+                }
+                None => (),
+            }
+            resp.response.rect.height() + 8.0
+        } else {
+            0.0
+        };
+
+        let summarize_height = if self.is_summarizing || self.offers_summary(settings) {
+            ui.add_space(8.0);
+            let resp = ui.scope(|ui| self.show_summarize_banner(ui, !is_generating));
+            match resp.inner {
+                Some(true) => action = ChatAction::Summarize { id: self.id() },
+                Some(false) => self.summarize_declined = true,
+                None => (),
+            }
+            resp.response.rect.height() + 8.0
+        } else {
+            0.0
+        };
+        if settings.summarize.auto
+            && !is_generating
+            && !self.is_summarizing
+            && self.offers_summary(settings)
+        {
+            action = ChatAction::Summarize { id: self.id() };
+        }
+
+        let override_height = if self.param_override.open {
+            ui.add_space(8.0);
+            let resp = ui.scope(|ui| self.param_override.show(ui));
+            resp.response.rect.height() + 8.0
+        } else {
+            0.0
+        };
+
+        // egui lays text edits out left-to-right only, so show right-to-left text as it reads
+        let preview_height = if crate::bidi::has_rtl(&self.chatbox) {
+            ui.add_space(8.0);
+            let resp = ui.with_layout(Layout::top_down(Align::Max), |ui| {
+                for line in self.chatbox.lines().filter(|l| !l.trim().is_empty()) {
+                    ui.label(egui::RichText::new(crate::bidi::visual_order(line)).weak());
+                }
+            });
+            resp.response
+                .on_hover_text(tr!("rtl-preview-hint"))
+                .rect
+                .height()
+                + 8.0
+        } else {
+            0.0
+        };
+        let direction = crate::bidi::detect(&self.chatbox).unwrap_or(if settings.rtl_layout {
+            crate::bidi::Direction::Rtl
+        } else {
+            crate::bidi::Direction::Ltr
+        });
+
+        let row = |ui: &mut egui::Ui| {
+            if ui
+                .add(
+                    egui::Button::new("➕")
+                        .min_size(vec2(32.0, 32.0))
+                        .corner_radius(CornerRadius::same(u8::MAX)),
+                )
+                .on_hover_text_at_pointer(tr!("pick-files"))
+                .clicked()
+            {
+                action = ChatAction::PickFiles { id: self.id() };
+            }
+            if ui
+                .add(
+                    egui::Button::new("🔗")
+                        .selected(self.attach_url.is_some())
+                        .min_size(vec2(32.0, 32.0))
+                        .corner_radius(CornerRadius::same(u8::MAX)),
+                )
+                .on_hover_text_at_pointer(tr!("attach-url"))
+                .clicked()
+            {
+                self.attach_url = match self.attach_url {
+                    Some(_) => None,
+                    None => Some(String::new()),
+                };
+            }
+            if ui
+                .add(
+                    egui::Button::new("📁")
+                        .min_size(vec2(32.0, 32.0))
+                        .corner_radius(CornerRadius::same(u8::MAX)),
+                )
+                .on_hover_text_at_pointer(tr!("bundle-attach-hint"))
+                .clicked()
+            {
+                action = ChatAction::AttachFolder { id: self.id() };
+            }
+            #[cfg(feature = "screenshots")]
+            if ui
+                .add(
+                    egui::Button::new("📷")
+                        .min_size(vec2(32.0, 32.0))
+                        .corner_radius(CornerRadius::same(u8::MAX)),
+                )
+                .on_hover_text_at_pointer(tr!("screenshot-capture"))
+                .clicked()
+            {
+                action = ChatAction::CaptureScreen { id: self.id() };
+            }
+            #[cfg(feature = "microphone")]
+            if let Some(path) = self.recorder.show(ui) {
+                self.files.push(Attachment::from_path(path));
+            }
+            if self.is_improving_prompt {
+                ui.add_sized(vec2(32.0, 32.0), egui::Spinner::new());
+            } else if ui
+                .add_enabled(
+                    !self.chatbox.trim().is_empty(),
+                    egui::Button::new("🪄")
+                        .min_size(vec2(32.0, 32.0))
+                        .corner_radius(CornerRadius::same(u8::MAX)),
+                )
+                .on_hover_text_at_pointer(tr!("improve-prompt"))
+                .clicked()
+            {
+                self.is_improving_prompt = true;
+                action = ChatAction::ImprovePrompt { id: self.id() };
+            }
             if ui
                 .add(
-                    egui::Button::new("➕")
+                    egui::Button::new("👁")
                         .min_size(vec2(32.0, 32.0))
                         .corner_radius(CornerRadius::same(u8::MAX)),
                 )
-                .on_hover_text_at_pointer("Pick files")
+                .on_hover_text_at_pointer(tr!("preview-request"))
                 .clicked()
             {
-                action = ChatAction::PickFiles { id: self.id() };
+                action = ChatAction::PreviewRequest { id: self.id() };
+            }
+            let overridden = self.param_override.is_set();
+            if ui
+                .add(
+                    egui::Button::new("🎛")
+                        .selected(overridden || self.param_override.open)
+                        .min_size(vec2(32.0, 32.0))
+                        .corner_radius(CornerRadius::same(u8::MAX)),
+                )
+                .on_hover_text_at_pointer(if overridden {
+                    tr!("param-override-active")
+                } else {
+                    tr!("param-override-hint")
+                })
+                .clicked()
+            {
+                self.param_override.open = !self.param_override.open;
             }
+            self.show_token_count(ui);
             ui.with_layout(
                 Layout::left_to_right(Align::Center).with_main_justify(true),
                 |ui| {
@@ -1087,26 +2527,141 @@ impl Chat {
                         // ui.fonts(|f| f.layout_job(layout_job)) // todo
                     };
 
-                    let text_edit_resp = ui.add(
-                        egui::TextEdit::multiline(&mut self.chatbox)
-                            .return_key(KeyboardShortcut::new(Modifiers::SHIFT, Key::Enter))
-                            .hint_text("Ask me anything…")
-                            // .layouter(&mut layouter) // todo that
-                            .lock_focus(true)
-                            .desired_width(f32::INFINITY),
-                    );
+                    let align = match direction {
+                        crate::bidi::Direction::Ltr => Align::LEFT,
+                        crate::bidi::Direction::Rtl => Align::RIGHT,
+                    };
+                    let ime_composing = self.ime_composing;
+                    let mut spell_layouter =
+                        |ui: &egui::Ui, buffer: &dyn egui::TextBuffer, wrap_width: f32| {
+                            let text = buffer.as_str();
+                            // pre-edit text isn't a word yet
+                            let misspelled = if ime_composing {
+                                Vec::new()
+                            } else {
+                                settings.spellcheck.misspelled(ui.ctx(), text)
+                            };
+                            let job = crate::spellcheck::layout_job(
+                                ui,
+                                text,
+                                &misspelled,
+                                wrap_width,
+                                align,
+                            );
+                            ui.painter().layout_job(job)
+                        };
+
+                    let mut edit = egui::TextEdit::multiline(&mut self.chatbox)
+                        .return_key(settings.keymap.newline_shortcut())
+                        .hint_text(tr!("chatbox-hint"))
+                        .horizontal_align(align)
+                        // .layouter(&mut layouter) // todo that
+                        .desired_rows(if roomy { 8 } else { 4 })
+                        .lock_focus(true)
+                        .desired_width(f32::INFINITY);
+                    if settings.spellcheck.enabled {
+                        edit = edit.layouter(&mut spell_layouter);
+                    }
+                    let mut output = edit.show(ui);
+                    let text_edit_resp = output.response.clone();
+
+                    if settings.spellcheck.enabled && text_edit_resp.secondary_clicked() {
+                        self.spell_target =
+                            ui.input(|i| i.pointer.interact_pos()).and_then(|pos| {
+                                let cursor = output.galley.cursor_from_pos(pos - output.galley_pos);
+                                settings
+                                    .spellcheck
+                                    .target_at(ui.ctx(), &self.chatbox, cursor.index)
+                            });
+                    }
+                    if let Some(target) = self.spell_target.clone() {
+                        text_edit_resp.context_menu(|ui| {
+                            // the text may have changed since the menu was opened
+                            let valid = self.chatbox.get(target.range.clone())
+                                == Some(target.word.as_str());
+                            if target.suggestions.is_empty() {
+                                ui.weak(tr!("spellcheck-no-suggestions"));
+                            }
+                            for suggestion in &target.suggestions {
+                                if ui
+                                    .add_enabled(valid, egui::Button::new(suggestion))
+                                    .clicked()
+                                {
+                                    self.chatbox.replace_range(target.range.clone(), suggestion);
+                                    self.spell_target = None;
+                                    ui.close();
+                                }
+                            }
+                            ui.separator();
+                            if ui.button(tr!("spellcheck-add-word")).clicked() {
+                                action = ChatAction::IgnoreWord(target.word.clone());
+                                self.spell_target = None;
+                                ui.close();
+                            }
+                        });
+                    }
+
+                    if text_edit_resp.changed() {
+                        if let Some(cursor) = output.cursor_range.map(|r| r.primary.index) {
+                            if let Some(new_cursor) =
+                                crate::snippets::expand(&mut self.chatbox, cursor, &settings.snippets)
+                            {
+                                output.state.cursor.set_char_range(Some(
+                                    egui::text::CCursorRange::one(egui::text::CCursor::new(
+                                        new_cursor,
+                                    )),
+                                ));
+                                output.state.store(ui.ctx(), text_edit_resp.id);
+                            }
+                        }
+                    }
 
-                    self.chatbox_height = text_edit_resp.rect.height() + images_height;
+                    self.chatbox_height = text_edit_resp.rect.height()
+                        + images_height
+                        + youtube_height
+                        + url_height
+                        + improvement_height
+                        + cache_height
+                        + summarize_height
+                        + override_height
+                        + preview_height;
+
+                    if std::mem::take(&mut self.focus_chatbox) {
+                        text_edit_resp.request_focus();
+                    }
 
+                    // the Enter that confirms a composition must not send
+                    let ime_busy = text_edit_resp.has_focus() && self.track_ime(ui);
                     if !is_generating
+                        && !ime_busy
+                        && self.slash_command.is_none()
                         && text_edit_resp.has_focus()
-                        && ui.input(|i| i.key_pressed(Key::Enter) && i.modifiers.is_none())
+                        && settings.keymap.send_pressed(ui)
                     {
-                        self.send_message(settings);
+                        // plugin commands replace the text so it can be checked before sending
+                        match crate::plugins::run_slash_command(&self.chatbox) {
+                            Some(command) => {
+                                let (tx, rx) = oneshot::channel();
+                                let ctx = ui.ctx().clone();
+                                tokio::spawn(async move {
+                                    let _ = tx.send(command.await.map_err(|e| e.to_string()));
+                                    ctx.request_repaint();
+                                });
+                                self.slash_command = Some(rx);
+                            }
+                            None => {
+                                self.send_message(settings);
+                            }
+                        }
                     }
                 },
             );
-        });
+        };
+        if settings.rtl_layout {
+            ui.with_layout(Layout::right_to_left(Align::Center), row);
+        } else {
+            ui.horizontal_centered(row);
+        }
 
         if is_max_height {
             ui.add_space(8.0);
@@ -1115,6 +2670,231 @@ impl Chat {
         action
     }
 
+    /// Puts the text of a finished slash command into the chatbox.
+    fn poll_slash_command(&mut self) {
+        let Some(rx) = &mut self.slash_command else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(Ok(text)) => self.chatbox = text,
+            Ok(Err(e)) => log::error!("slash command failed: {e}"),
+            Err(oneshot::error::TryRecvError::Empty) => return,
+            Err(oneshot::error::TryRecvError::Closed) => (),
+        }
+        self.slash_command = None;
+    }
+
+    /// Input tokens the selected model takes, if known.
+    fn context_limit(&self) -> Option<u64> {
+        crate::models::info(self.model_picker.selected)
+            .and_then(|info| info.input_tokens)
+            .filter(|&limit| limit > 0)
+    }
+
+    /// Whether the chat fills enough of the context window to suggest summarizing its
+    /// older messages, and has any to summarize.
+    fn offers_summary(&self, settings: &Settings) -> bool {
+        !self.summarize_declined
+            && self
+                .token_count
+                .is_some_and(|count| settings.summarize.is_due(count, self.context_limit()))
+            && crate::summarize::split(&self.messages).is_some()
+    }
+
+    /// Offers to extract the first archive among the attachments, and puts the extracted
+    /// files in its place.
+    fn show_archive_picker(&mut self, ctx: &egui::Context) {
+        if self.archive_picker.is_none() {
+            let archive = self.files.iter().find(|file| {
+                file.youtube.is_none()
+                    && crate::archive::is_archive(&file.path)
+                    && !self.kept_archives.contains(&file.path)
+            });
+            if let Some(file) = archive {
+                self.archive_picker = Some(ArchivePicker::new(ctx, file.path.clone()));
+            }
+        }
+        let Some(picker) = &mut self.archive_picker else {
+            return;
+        };
+        let Some(idx) = self.files.iter().position(|file| file.path == picker.path) else {
+            // removed from the attachments meanwhile
+            self.archive_picker = None;
+            return;
+        };
+        match picker.show(ctx) {
+            Some(Picked::Extracted(files)) => {
+                self.files
+                    .splice(idx..=idx, files.into_iter().map(Attachment::from_path));
+                self.archive_picker = None;
+            }
+            Some(Picked::Kept) => {
+                self.kept_archives.push(picker.path.clone());
+                self.archive_picker = None;
+            }
+            None => (),
+        }
+    }
+
+    /// The attach-from-URL bar. Returns the link to download once it's submitted.
+    fn show_url_bar(&mut self, ui: &mut egui::Ui) -> Option<String> {
+        let mut submitted = None;
+        let mut close = false;
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.horizontal(|ui| {
+                let Some(url) = &mut self.attach_url else {
+                    return;
+                };
+                if self.is_downloading {
+                    ui.spinner();
+                    ui.label(tr!("attach-url-downloading"));
+                    return;
+                }
+                let valid = url::Url::parse(url.trim())
+                    .is_ok_and(|url| matches!(url.scheme(), "http" | "https"));
+                let edit = ui.add(
+                    egui::TextEdit::singleline(url)
+                        .hint_text(tr!("attach-url-hint"))
+                        .desired_width(ui.available_width() - 160.0),
+                );
+                let entered = edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                if ui
+                    .add_enabled(valid, egui::Button::new(tr!("attach-url-download")))
+                    .clicked()
+                    || (entered && valid)
+                {
+                    submitted = Some(url.trim().to_owned());
+                }
+                if ui.button(tr!("attach-url-cancel")).clicked() {
+                    close = true;
+                }
+            });
+        });
+        if close {
+            self.attach_url = None;
+        }
+        submitted
+    }
+
+    /// Offers to summarize the older messages. Returns whether the user accepted or
+    /// dismissed the offer.
+    fn show_summarize_banner(&self, ui: &mut egui::Ui, can_summarize: bool) -> Option<bool> {
+        let mut choice = None;
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                if self.is_summarizing {
+                    ui.spinner();
+                    ui.label(tr!("summarize-running"));
+                    return;
+                }
+                let percent = match (self.token_count, self.context_limit()) {
+                    (Some(count), Some(limit)) => count as f64 / limit as f64 * 100.0,
+                    _ => 0.0,
+                };
+                ui.colored_label(
+                    ui.visuals().warn_fg_color,
+                    tr!("summarize-offer", percent = format_number(percent, 0)),
+                );
+                if ui
+                    .add_enabled(can_summarize, egui::Button::new(tr!("summarize-now")))
+                    .on_hover_text(tr!("summarize-now-hint"))
+                    .clicked()
+                {
+                    choice = Some(true);
+                }
+                if ui.button(tr!("summarize-dismiss")).clicked() {
+                    choice = Some(false);
+                }
+            });
+        });
+        choice
+    }
+
+    /// Condenses the messages before the one sent at `kept_from` into `summary`. Nothing
+    /// happens if that message is gone meanwhile.
+    pub fn apply_summary(&mut self, kept_from: chrono::DateTime<chrono::Utc>, summary: String) {
+        let Some(split) = self.messages.iter().position(|m| m.time == kept_from) else {
+            log::warn!("the chat changed while it was summarized, the summary is dropped");
+            return;
+        };
+        let compacted: Vec<Message> = self.messages.drain(..split).collect();
+        let model = compacted
+            .last()
+            .map_or(self.model_picker.selected, |m| m.model);
+        self.messages.insert(
+            0,
+            Message {
+                compacted,
+                ..Message::user(summary, model, Vec::new())
+            },
+        );
+        // the old count would offer to summarize again until the chat is recounted
+        self.token_count = None;
+    }
+
+    /// Puts the messages the summary at `idx` stands for back in its place.
+    fn restore_summarized(&mut self, idx: usize) {
+        let Some(message) = self.messages.get_mut(idx) else {
+            return;
+        };
+        let compacted = std::mem::take(&mut message.compacted);
+        self.messages.splice(idx..=idx, compacted);
+        self.summarize_declined = true;
+    }
+
+    /// The tokens the next request would send, against the context window of the model.
+    fn show_token_count(&self, ui: &mut egui::Ui) {
+        let Some(count) = self.token_count else {
+            return;
+        };
+        let limit = self.context_limit();
+        let used = limit.map_or(0.0, |limit| count as f64 / limit as f64);
+        let mut text = egui::RichText::new(format_number(count as f64, 0)).small();
+        text = if used >= 1.0 {
+            text.color(ui.visuals().error_fg_color)
+        } else if used >= 0.8 {
+            text.color(ui.visuals().warn_fg_color)
+        } else {
+            text.weak()
+        };
+        // still counting what was just typed
+        if self.token_count_due.is_some() {
+            text = text.italics();
+        }
+        let hover = match limit {
+            Some(limit) => tr!(
+                "token-count-limit",
+                count = format_number(count as f64, 0),
+                limit = format_number(limit as f64, 0),
+                percent = format_number(used * 100.0, 0)
+            ),
+            None => tr!("token-count-help"),
+        };
+        ui.label(text).on_hover_text(hover);
+    }
+
+    /// Follows the input method state, true while composing and on the frame the text is committed.
+    fn track_ime(&mut self, ui: &egui::Ui) -> bool {
+        let was_composing = self.ime_composing;
+        let mut committed = false;
+        ui.input(|i| {
+            for event in &i.events {
+                match event {
+                    egui::Event::Ime(egui::ImeEvent::Preedit(text)) => {
+                        self.ime_composing = !text.is_empty();
+                    }
+                    egui::Event::Ime(egui::ImeEvent::Commit(_)) => {
+                        self.ime_composing = false;
+                        committed = true;
+                    }
+                    egui::Event::Ime(egui::ImeEvent::Disabled) => self.ime_composing = false,
+                    _ => (),
+                }
+            }
+        });
+        was_composing || committed || self.ime_composing
+    }
+
     #[inline]
     pub fn flower_active(&self) -> bool {
         self.flower.is_active()
@@ -1140,12 +2920,22 @@ impl Chat {
                             msg.status_message = Some(message);
                         }
                     }
-                    ChatProgress::FileUploading { path } => {
+                    ChatProgress::FileUploading { path, sent, total } => {
+                        if let Some(msg) = self.messages.get_mut(idx) {
+                            if let Some(attachment) =
+                                msg.files.iter_mut().find(|a| a.path == path)
+                            {
+                                attachment.state = AttachmentState::Uploading { sent, total };
+                            }
+                        }
+                    }
+                    ChatProgress::FileUploadCancelled { path } => {
                         if let Some(msg) = self.messages.get_mut(idx) {
                             if let Some(attachment) =
                                 msg.files.iter_mut().find(|a| a.path == path)
                             {
-                                attachment.state = AttachmentState::Uploading;
+                                attachment.state = AttachmentState::Local;
+                                attachment.renew_cancel();
                             }
                         }
                     }
@@ -1162,9 +2952,56 @@ impl Chat {
                             }
                         }
                     }
+                    ChatProgress::Sources(sources) => {
+                        if let Some(msg) = self.messages.get_mut(idx) {
+                            msg.sources = sources;
+                        }
+                    }
+                    ChatProgress::Grounding(grounding) => {
+                        if let Some(msg) = self.messages.get_mut(idx) {
+                            match &mut msg.grounding {
+                                Some(known) => known.merge(grounding),
+                                None => msg.grounding = Some(grounding),
+                            }
+                        }
+                    }
+                    ChatProgress::Safety(report) => {
+                        if let Some(msg) = self.messages.last_mut() {
+                            msg.safety = Some(report);
+                        }
+                    }
+                    ChatProgress::Degraded => {
+                        if let Some(msg) = self.messages.get_mut(idx) {
+                            msg.degraded = true;
+                        }
+                    }
+                    ChatProgress::Truncated => {
+                        if let Some(msg) = self.messages.last_mut() {
+                            msg.truncated = true;
+                        }
+                    }
+                    ChatProgress::Interrupted => {
+                        if let Some(msg) = self.messages.last_mut() {
+                            msg.interrupted = true;
+                        }
+                    }
+                    ChatProgress::Alternatives(alternatives) => {
+                        if let Some(msg) = self.messages.last_mut() {
+                            msg.alternatives = alternatives;
+                        }
+                    }
+                    ChatProgress::FellBack(model) => {
+                        if let Some(msg) = self.messages.get_mut(idx) {
+                            msg.model = model;
+                        }
+                    }
                     ChatProgress::Part(part) => {
                         match part {
-                            Part::Text { text, thought, .. } => {
+                            Part::Text {
+                                text,
+                                thought,
+                                thought_signature,
+                            } => {
                                 // Safely use unwrap, as we always add
                                 // a placeholder message in send_message before running.
                                 let current_response_msg = self.messages.last_mut().unwrap();
@@ -1190,25 +3027,74 @@ impl Chat {
                                         let model = current_response_msg.model;
                                         let mut answer_message = Message::assistant(text.into(), model);
                                         answer_message.is_generating = true; // It has its own spinner.
+                                        answer_message.signature = thought_signature;
                                         self.messages.push(answer_message);
                                     } else {
                                         // Either there were no "thoughts", or this is a continuation of the answer.
                                         // Just append the text to the current last message.
                                         current_response_msg.content.push_str(&text);
+                                        // the signature comes with one of the chunks, often an empty last one
+                                        if thought_signature.is_some() {
+                                            current_response_msg.signature = thought_signature;
+                                        }
+                                    }
+                                }
+                            }
+                            part => {
+                                let image = GeneratedImage::from_part(&part);
+                                let audio = GeneratedAudio::from_part(&part);
+                                let call = crate::functions::Call::from_part(&part);
+                                if image.is_some() || audio.is_some() || call.is_some() {
+                                    let current_response_msg = self.messages.last_mut().unwrap();
+                                    if current_response_msg.is_thought {
+                                        // same as when the answer starts after the thoughts
+                                        current_response_msg.is_generating = false;
+                                        current_response_msg.generation_time =
+                                            Some(current_response_msg.requested_at.elapsed());
+                                        let model = current_response_msg.model;
+                                        self.messages
+                                            .push(Message::assistant(String::new(), model));
+                                    }
+                                    if let Some(message) = self.messages.last_mut() {
+                                        if let Some(image) = image {
+                                            log::info!("the model sent an image ({})", image.mime_type);
+                                            message.images.push(image);
+                                        }
+                                        if let Some(audio) = audio {
+                                            let appended = match message.audio.last_mut() {
+                                                Some(last) => last.append(audio),
+                                                None => Err(audio),
+                                            };
+                                            if let Err(audio) = appended {
+                                                log::info!("the model sent audio ({})", audio.mime_type);
+                                                message.audio.push(audio);
+                                            }
+                                        }
+                                        if let Some(call) = call {
+                                            log::info!("the model called `{}`", call.name);
+                                            message.calls.push(call);
+                                        }
                                     }
                                 }
                             }
-                            _ => {} // Handle other parts if needed
                         }
                     }
                 }
             })
             .finalize(|result| {
+                self.requests_finished += 1;
                 if let Ok((idx, _, usage)) = result {
                     if let Some(message) = self.messages.get_mut(idx) {
                         message.usage = usage;
                         message.status_message = None;
                     }
+                    // thoughts come as their own message, the answer is the last one
+                    if self.model_picker.json_output() {
+                        if let Some(answer) = self.messages.last_mut().filter(|m| !m.is_user()) {
+                            answer.structured = true;
+                            answer.schema = self.model_picker.response_schema();
+                        }
+                    }
                 } else if let Err(e) = result {
                     let (idx, msg) = match e {
                         Compact::Panicked(e) => {
@@ -1295,13 +3181,30 @@ impl Chat {
             });
     }
 
+    /// Assigns a persona to the chat, adopting its model settings and leaving a
+    /// visible marker in the history if the conversation already started.
+    pub fn set_persona(&mut self, persona: Option<&Persona>) {
+        self.persona = persona.map(|p| p.id);
+        if let Some(persona) = persona {
+            self.model_picker = persona.picker.clone();
+        }
+
+        if !self.messages.is_empty() {
+            let text = match persona {
+                Some(p) => tr!("persona-switched", persona = p.label()),
+                None => tr!("persona-removed"),
+            };
+            self.messages.push(Message::marker(text));
+        }
+    }
+
     pub fn last_message_contents(&self) -> Option<String> {
         for message in self.messages.iter().rev() {
-            if message.content.is_empty() {
+            if message.content.is_empty() || message.is_marker {
                 continue;
             }
             return Some(if message.is_user() {
-                format!("You: {}", message.content)
+                tr!("last-message-you", content = message.content.as_str())
             } else {
                 message.content.to_string()
             });
@@ -1355,17 +3258,31 @@ impl Chat {
         settings: &Settings,
         commonmark_cache: &mut CommonMarkCache,
         #[cfg(feature = "tts")] tts: SharedTts,
+        #[cfg(feature = "tts")] voice: Option<&str>,
     ) -> Option<usize> {
         let mut new_speaker: Option<usize> = None;
         let mut any_prepending = false;
         let mut regenerate_response_idx = None;
+        let mut continue_response_idx = None;
         let mut message_to_delete_idx: Option<usize> = None;
-        egui::ScrollArea::vertical()
+        let mut restore_summarized_idx = None;
+        let mut answered_calls = false;
+        let mut scroll_area = egui::ScrollArea::vertical()
             .animated(false)
             .id_salt(self.id())
             .stick_to_bottom(true)
-            .auto_shrink(false)
-            .show(ui, |ui| {
+            .auto_shrink(false);
+        if let Some(target) = self.jump_to_message {
+            if target >= self.messages.len() {
+                self.jump_to_message = None;
+            } else {
+                // rough estimate so the virtual scroll renders the target row,
+                // the exact position is fixed up once the row is laid out
+                let fraction = target as f32 / self.messages.len() as f32;
+                scroll_area = scroll_area.vertical_scroll_offset(self.last_content_height * fraction);
+            }
+        }
+        let output = scroll_area.show(ui, |ui| {
                 let scrollbar_width = ui.style().spacing.scroll.bar_width + 8.0;
 
                 // todo: cache it
@@ -1390,6 +3307,19 @@ impl Chat {
                 let anch_indices: Vec<usize> = anchors_map.keys().cloned().collect();
                 // anch_indices.sort_unstable();
 
+                let view = MessageView {
+                    #[cfg(feature = "tts")]
+                    tts,
+                    #[cfg(feature = "tts")]
+                    voice,
+                    reading: &settings.reading,
+                    timestamps: settings.timestamps,
+                    editor_command: &settings.editor_command,
+                    safety: self.model_picker.safety_settings(&settings.safety),
+                    commands: settings
+                        .run_commands
+                        .then_some(settings.functions.as_slice()),
+                };
                 RobustVirtualScroll::new(Id::new(self.id()))
                     .anchors(anch_indices, |index| { // TODO! maybe any ref?
                         anchors_map.get(&index).cloned().unwrap_or_default() // bruh
@@ -1402,6 +3332,7 @@ impl Chat {
                 {
                     ui.set_width(ui.available_width() - scrollbar_width);
                     // println!("Rendering: '{index}'");
+                    let message_count = self.messages.len();
                     let message = &mut self.messages[index];
                     let prev_speaking = message.is_speaking;
 
@@ -1409,14 +3340,16 @@ impl Chat {
                         message.is_prepending = false;
                     }
 
-                    ui.push_id(index, |ui| {
+                    let highlights = self.find.as_ref().and_then(|f| f.highlights(index));
+                    let row = ui.push_id(index, |ui| {
                         let action = message.show(
                             ui,
                             commonmark_cache,
-                            #[cfg(feature = "tts")]
-                            tts.clone(),
+                            view.clone(),
                             index,
+                            index + 1 == message_count,
                             &mut self.prepend_buf,
+                            highlights.as_ref(),
                         );
                         match action {
                             MessageAction::None => (),
@@ -1424,14 +3357,114 @@ impl Chat {
                                 self.retry_message_idx = Some(idx);
                             }
                             MessageAction::Regenerate(idx) => {
-                                regenerate_response_idx = Some(idx);
+                                regenerate_response_idx = Some((idx, self.model_picker.selected));
+                            }
+                            MessageAction::RegenerateWith(idx, model) => {
+                                regenerate_response_idx = Some((idx, model));
+                            }
+                            MessageAction::Continue(idx) => {
+                                continue_response_idx = Some(idx);
                             }
                             MessageAction::Delete(idx) => {
                                 message_to_delete_idx = Some(idx);
                             }
+                            MessageAction::RestoreSummarized(idx) => {
+                                restore_summarized_idx = Some(idx);
+                            }
+                            MessageAction::RelaxSafety(categories) => {
+                                self.relax_safety = Some(categories);
+                            }
+                            MessageAction::Quarantine(source) => {
+                                self.quarantine_source = Some(source);
+                            }
+                            MessageAction::EditImage(image) => {
+                                self.edit_image = Some(image);
+                            }
+                            MessageAction::Verify(idx) => {
+                                self.verify_message = Some(idx);
+                            }
+                            MessageAction::AnswerCalls => {
+                                answered_calls = true;
+                            }
+                            MessageAction::RunCommand { message, call } => {
+                                self.run_command = Some((message, call));
+                            }
+                            MessageAction::Reupload { message, path } => {
+                                self.reupload = Some((message, path));
+                            }
                         }
                     });
 
+                    // a Tab stop for the whole message, arrows move between messages
+                    // and Ctrl+C copies the focused one
+                    let focus = ui.interact(
+                        row.response.rect,
+                        Id::new(("message_focus", self.id, index)),
+                        egui::Sense::focusable_noninteractive(),
+                    );
+                    focus.widget_info(|| {
+                        egui::WidgetInfo::labeled(egui::WidgetType::Label, true, &message.content)
+                    });
+                    if self.focus_message == Some(index) {
+                        focus.request_focus();
+                        self.focus_message = None;
+                    }
+                    if focus.has_focus() {
+                        let (up, down, copy) = ui.input_mut(|i| {
+                            (
+                                i.consume_key(Modifiers::NONE, Key::ArrowUp),
+                                i.consume_key(Modifiers::NONE, Key::ArrowDown),
+                                i.events.contains(&egui::Event::Copy),
+                            )
+                        });
+                        if up || down {
+                            let target = if up {
+                                index.saturating_sub(1)
+                            } else {
+                                (index + 1).min(message_count - 1)
+                            };
+                            self.jump_to_message = Some(target);
+                            self.focus_message = Some(target);
+                        }
+                        if copy {
+                            ui.ctx().copy_text(message.content.clone());
+                        }
+                        ui.painter().rect_stroke(
+                            row.response.rect.expand(4.0),
+                            6.0,
+                            ui.visuals().selection.stroke,
+                            egui::StrokeKind::Outside,
+                        );
+                    }
+
+                    // the message may have scrolled to the paragraph holding the match itself
+                    let scrolled_to_match = highlights.as_ref().is_some_and(|h| h.scrolled.get());
+                    if scrolled_to_match
+                        || (highlights.is_some() && self.jump_to_message == Some(index))
+                    {
+                        if let Some(find) = &mut self.find {
+                            find.scrolled();
+                        }
+                    }
+                    if self.jump_to_message == Some(index) {
+                        if !scrolled_to_match {
+                            ui.scroll_to_rect(row.response.rect, Some(Align::Center));
+                        }
+                        self.jump_to_message = None;
+                        self.highlighted_message = Some((index, Instant::now()));
+                    }
+                    if let Some((highlighted, since)) = self.highlighted_message {
+                        if highlighted == index && since.elapsed() < Duration::from_secs(2) {
+                            ui.painter().rect_stroke(
+                                row.response.rect.expand(4.0),
+                                6.0,
+                                Stroke::new(2.0, ui.visuals().selection.bg_fill),
+                                egui::StrokeKind::Outside,
+                            );
+                            ui.ctx().request_repaint();
+                        }
+                    }
+
                     any_prepending |= message.is_prepending;
 
                     if !prev_speaking && message.is_speaking {
@@ -1441,18 +3474,50 @@ impl Chat {
 
                 ui.add_space(12.0);
             });
-        if let Some(regenerate_idx) = regenerate_response_idx {
-            self.regenerate_response(settings, regenerate_idx);
+        self.last_content_height = output.content_size.y;
+        if let Some((regenerate_idx, model)) = regenerate_response_idx {
+            self.messages[regenerate_idx].is_generating = true;
+            self.regenerate_response(settings, regenerate_idx, model);
+        }
+        if let Some(idx) = continue_response_idx {
+            self.continue_response(settings, idx);
         }
         if let Some(idx) = message_to_delete_idx {
             self.messages.remove(idx);
         }
+        if let Some(idx) = restore_summarized_idx {
+            self.restore_summarized(idx);
+        }
+        if answered_calls {
+            self.continue_generation(settings);
+        }
         new_speaker
     }
 
-    fn send_text(&mut self, settings: &Settings, text: &str) {
-        self.chatbox = text.to_owned();
-        self.send_message(settings);
+    /// Sends `text` as if it was typed into the chatbox, keeping whatever the user typed there.
+    /// Returns the request to wait for with `request_finished`, if one was started.
+    pub fn send_text(&mut self, settings: &Settings, text: &str) -> Option<u64> {
+        self.send_with_files(settings, text, Vec::new())
+    }
+
+    /// Like `send_text`, with `files` attached.
+    pub fn send_with_files(
+        &mut self,
+        settings: &Settings,
+        text: &str,
+        files: Vec<Attachment>,
+    ) -> Option<u64> {
+        let typed = std::mem::replace(&mut self.chatbox, text.to_owned());
+        let typed_files = std::mem::replace(&mut self.files, files);
+        let sent = self.send_message(settings);
+        self.chatbox = typed;
+        self.files = typed_files;
+        sent.then_some(self.requests_started)
+    }
+
+    /// Whether the answer of a request `send_text` started has come in or failed.
+    pub fn request_finished(&self, request: u64) -> bool {
+        self.requests_finished >= request
     }
 
     fn show_suggestions(&mut self, ui: &mut egui::Ui, settings: &Settings) {
@@ -1468,36 +3533,17 @@ impl Chat {
                     .spacing(vec2(6.0, 6.0))
                     .show(ui, |ui| {
                         // TODO change it
-                        if widgets::suggestion(ui, "Tell me a fun fact", "about the Roman empire")
-                            .clicked()
-                        {
-                            self.send_text(settings, "Tell me a fun fact about the Roman empire");
-                        }
-                        if widgets::suggestion(
-                            ui,
-                            "Show me a code snippet",
-                            "of a web server in Rust",
-                        )
-                        .clicked()
-                        {
-                            self.send_text(
-                                settings,
-                                "Show me a code snippet of a web server in Rust",
-                            );
-                        }
-                        widgets::dummy(ui);
-                        ui.end_row();
-
-                        if widgets::suggestion(ui, "Tell me a joke", "about crabs").clicked() {
-                            self.send_text(settings, "Tell me a joke about crabs");
-                        }
-                        if widgets::suggestion(ui, "Give me ideas", "for a birthday present")
-                            .clicked()
-                        {
-                            self.send_text(settings, "Give me ideas for a birthday present");
+                        for (i, id) in SUGGESTIONS.iter().enumerate() {
+                            let title = tr!(&format!("{id}-title"));
+                            let subtitle = tr!(&format!("{id}-subtitle"));
+                            if widgets::suggestion(ui, &title, &subtitle).clicked() {
+                                self.send_text(settings, &format!("{title} {subtitle}"));
+                            }
+                            if i % 2 == 1 {
+                                widgets::dummy(ui);
+                                ui.end_row();
+                            }
                         }
-                        widgets::dummy(ui);
-                        ui.end_row();
                     });
             });
         });
@@ -1510,38 +3556,83 @@ impl Chat {
         #[cfg(feature = "tts")] tts: SharedTts,
         #[cfg(feature = "tts")] stopped_speaking: bool,
         commonmark_cache: &mut CommonMarkCache,
+        focus_mode: bool,
+    ) -> ChatAction {
+        let mut action = ChatAction::None;
+        egui::CentralPanel::default()
+            .frame(Frame::NONE)
+            .show(ctx, |ui| {
+                action = self.show_inside(
+                    ui,
+                    settings,
+                    #[cfg(feature = "tts")]
+                    tts,
+                    #[cfg(feature = "tts")]
+                    stopped_speaking,
+                    commonmark_cache,
+                    focus_mode,
+                );
+            });
+        action
+    }
+
+    /// Shows the chat inside an arbitrary region, e.g. a split view pane or an embedded window.
+    /// `focus_mode` narrows the conversation to a readable column and enlarges the chatbox.
+    pub fn show_inside(
+        &mut self,
+        ui: &mut egui::Ui,
+        settings: &Settings,
+        #[cfg(feature = "tts")] tts: SharedTts,
+        #[cfg(feature = "tts")] stopped_speaking: bool,
+        commonmark_cache: &mut CommonMarkCache,
+        focus_mode: bool,
     ) -> ChatAction {
-        let avail = ctx.available_rect();
-        let max_height = avail.height() * 0.4 + 24.0;
+        let avail = ui.max_rect();
+        let max_height = avail.height() * if focus_mode { 0.5 } else { 0.4 } + 24.0;
         let chatbox_panel_height = self.chatbox_height + 24.0;
         let actual_chatbox_panel_height = chatbox_panel_height.min(max_height);
         let is_generating = self.flower_active();
         let mut action = ChatAction::None;
 
-        egui::TopBottomPanel::bottom("chatbox_panel")
+        egui::TopBottomPanel::bottom(Id::new(("chatbox_panel", self.id)))
             .exact_height(actual_chatbox_panel_height)
-            .show(ctx, |ui| {
-                egui::ScrollArea::vertical().show(ui, |ui| {
-                    action = self.show_chatbox(
-                        ui,
-                        chatbox_panel_height >= max_height,
-                        is_generating,
-                        settings,
-                    );
+            .show_inside(ui, |ui| {
+                readable_column(ui, focus_mode, |ui| {
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        action = self.show_chatbox(
+                            ui,
+                            chatbox_panel_height >= max_height,
+                            is_generating,
+                            settings,
+                            focus_mode,
+                        );
+                    });
                 });
             });
 
+        if let Some(find) = &mut self.find {
+            let mut action = FindAction::None;
+            egui::TopBottomPanel::top(Id::new(("find_bar_panel", self.id))).show_inside(ui, |ui| {
+                action = find.show(ui, &self.messages);
+            });
+            match action {
+                FindAction::None => (),
+                FindAction::Close => self.find = None,
+                FindAction::Jump(idx) => self.jump_to_message = Some(idx),
+            }
+        }
+
         #[cfg(feature = "tts")]
         let mut new_speaker: Option<usize> = None;
 
         egui::CentralPanel::default()
-            .frame(Frame::central_panel(&ctx.style()).inner_margin(Margin {
+            .frame(Frame::central_panel(ui.style()).inner_margin(Margin {
                 left: 16,
                 right: 16,
                 top: 0,
                 bottom: 3,
             }))
-            .show(ctx, |ui| {
+            .show_inside(ui, |ui| readable_column(ui, focus_mode, |ui| {
                 // ui.ctx().set_debug_on_hover(true); // TODO DEBUG
                 if self.messages.is_empty() {
                     self.show_suggestions(ui, settings);
@@ -1553,6 +3644,9 @@ impl Chat {
                         commonmark_cache,
                         #[cfg(feature = "tts")]
                         tts,
+                        #[cfg(feature = "tts")]
+                        crate::persona::find(&settings.personas, self.persona)
+                            .and_then(|p| p.voice.as_deref()),
                     ) {
                         #[cfg(feature = "tts")]
                         {
@@ -1572,7 +3666,7 @@ impl Chat {
                         );
                     }
                 }
-            });
+            }));
 
         #[cfg(feature = "tts")]
         {
@@ -1593,6 +3687,35 @@ impl Chat {
             }
         }
 
+        if let Some(categories) = self.relax_safety.take() {
+            action = ChatAction::RelaxSafety(categories);
+        }
+        if let Some(source) = self.quarantine_source.take() {
+            action = ChatAction::Quarantine(source);
+        }
+        if let Some(image) = self.edit_image.take() {
+            action = ChatAction::EditImage(image);
+        }
+        if let Some(message) = self.verify_message.take() {
+            action = ChatAction::Verify {
+                id: self.id(),
+                message,
+            };
+        }
+        if let Some((message, call)) = self.run_command.take() {
+            action = ChatAction::RunCommand {
+                id: self.id(),
+                message,
+                call,
+            };
+        }
+        if let Some((message, path)) = self.reupload.take() {
+            action = ChatAction::Reupload {
+                id: self.id(),
+                message,
+                path,
+            };
+        }
         action
     }
 }
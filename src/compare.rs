@@ -0,0 +1,374 @@
+use crate::{
+    file_handler::Attachment,
+    i18n::tr,
+    widgets::{GeminiModel, Settings},
+};
+use anyhow::{anyhow, Result};
+use eframe::egui::{self, Color32, RichText};
+use std::path::{Path, PathBuf};
+use tokio::sync::oneshot;
+
+const COMPARE_PROMPT: &str = "You compare two versions of a document, like a contract or a \
+specification. The first attached file is the original, the second the revised version. Find every \
+clause, section or requirement that was added, removed or changed in meaning; ignore formatting and \
+renumbering. Reply with JSON only, in this shape: {\"summary\": string, \"changes\": [{\"kind\": \
+\"added\" | \"removed\" | \"changed\", \"clause\": string, \"original\": string, \"revised\": \
+string, \"original_page\": number | null, \"revised_page\": number | null, \"note\": string}]}. \
+`clause` names the clause or section, `original` and `revised` quote the relevant text and are empty \
+for added and removed clauses respectively, pages count from 1 and are null for documents without \
+pages, `note` explains the effect of the change in one sentence.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl ChangeKind {
+    const ALL: [Self; 3] = [Self::Added, Self::Removed, Self::Changed];
+
+    fn label(self) -> String {
+        tr!(match self {
+            Self::Added => "compare-added",
+            Self::Removed => "compare-removed",
+            Self::Changed => "compare-changed",
+        })
+    }
+
+    fn color(self) -> Color32 {
+        match self {
+            Self::Added => Color32::from_rgb(141, 189, 156),
+            Self::Removed => Color32::from_rgb(201, 141, 141),
+            Self::Changed => Color32::from_rgb(214, 185, 120),
+        }
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct Change {
+    kind: ChangeKind,
+    #[serde(default)]
+    clause: String,
+    #[serde(default)]
+    original: String,
+    #[serde(default)]
+    revised: String,
+    #[serde(default)]
+    original_page: Option<u32>,
+    #[serde(default)]
+    revised_page: Option<u32>,
+    #[serde(default)]
+    note: String,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct Comparison {
+    #[serde(default)]
+    summary: String,
+    #[serde(default)]
+    changes: Vec<Change>,
+}
+
+impl Comparison {
+    /// Reads the model's answer, which may come wrapped in a code block.
+    fn parse(text: &str) -> Result<Self> {
+        let start = text.find('{');
+        let end = text.rfind('}');
+        let (Some(start), Some(end)) = (start, end) else {
+            return Err(anyhow!(tr!("compare-invalid")));
+        };
+        serde_json::from_str(&text[start..=end])
+            .map_err(|e| anyhow!("{}: {e}", tr!("compare-invalid")))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Original,
+    Revised,
+}
+
+/// Opens `path` at `page`. PDF viewers take the page from the URL fragment, others
+/// just open the file.
+fn open_page(path: &Path, page: Option<u32>) {
+    let target = match (url::Url::from_file_path(path), page) {
+        (Ok(mut url), Some(page)) => {
+            url.set_fragment(Some(&format!("page={page}")));
+            url.to_string()
+        }
+        _ => path.display().to_string(),
+    };
+    if let Err(e) = open::that(&target) {
+        log::error!("failed to open {target}: {e}");
+    }
+}
+
+/// Compares two versions of a document and lists the added, removed and changed clauses.
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct DocumentCompare {
+    pub original: String,
+    pub revised: String,
+    pub model: GeminiModel,
+    /// What to pay attention to, added to the prompt
+    pub focus: String,
+    #[serde(skip)]
+    pub open: bool,
+    #[serde(skip)]
+    picking: Option<(Side, oneshot::Receiver<Option<PathBuf>>)>,
+    #[serde(skip)]
+    pending: Option<oneshot::Receiver<Result<Comparison, String>>>,
+    /// The comparison with the paths it was made from, page links open those
+    #[serde(skip)]
+    result: Option<(PathBuf, PathBuf, Comparison)>,
+    #[serde(skip)]
+    error: Option<String>,
+}
+
+impl Default for DocumentCompare {
+    fn default() -> Self {
+        Self {
+            original: String::new(),
+            revised: String::new(),
+            model: GeminiModel::default(),
+            focus: String::new(),
+            open: false,
+            picking: None,
+            pending: None,
+            result: None,
+            error: None,
+        }
+    }
+}
+
+impl DocumentCompare {
+    fn poll(&mut self) {
+        if let Some((side, rx)) = &mut self.picking {
+            if let Ok(path) = rx.try_recv() {
+                if let Some(path) = path {
+                    let path = path.display().to_string();
+                    match side {
+                        Side::Original => self.original = path,
+                        Side::Revised => self.revised = path,
+                    }
+                }
+                self.picking = None;
+            }
+        }
+        let Some(rx) = &mut self.pending else {
+            return;
+        };
+        if let Ok(result) = rx.try_recv() {
+            self.pending = None;
+            match result {
+                Ok(comparison) => {
+                    let original = PathBuf::from(self.original.trim());
+                    let revised = PathBuf::from(self.revised.trim());
+                    self.result = Some((original, revised, comparison));
+                    self.error = None;
+                }
+                Err(e) => self.error = Some(e),
+            }
+        }
+    }
+
+    fn pick(&mut self, ctx: &egui::Context, side: Side) {
+        let (tx, rx) = oneshot::channel();
+        self.picking = Some((side, rx));
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            let file = rfd::AsyncFileDialog::new().pick_file().await;
+            let _ = tx.send(file.map(|f| f.path().to_owned()));
+            ctx.request_repaint();
+        });
+    }
+
+    fn compare(&mut self, ctx: &egui::Context, settings: &Settings) {
+        let files = vec![
+            Attachment::from_path(PathBuf::from(self.original.trim())),
+            Attachment::from_path(PathBuf::from(self.revised.trim())),
+        ];
+        let mut input = String::from("Compare the two attached versions.");
+        if !self.focus.trim().is_empty() {
+            input.push_str("\n\nPay particular attention to: ");
+            input.push_str(self.focus.trim());
+        }
+        let model = self.model;
+        let settings = settings.clone();
+        let ctx = ctx.clone();
+        let (tx, rx) = oneshot::channel();
+        self.pending = Some(rx);
+        tokio::spawn(async move {
+            let result = geminid_core::pipeline::generate_with_files(
+                &settings.connection(),
+                model,
+                Some(COMPARE_PROMPT),
+                &input,
+                &files,
+            )
+            .await
+            .and_then(|response| Comparison::parse(&response.text()));
+            if let Err(e) = &result {
+                log::error!("failed to compare the documents: {e}");
+            }
+            let _ = tx.send(result.map_err(|e| e.to_string()));
+            ctx.request_repaint();
+        });
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, settings: &Settings) {
+        self.poll();
+        if !self.open {
+            return;
+        }
+        let mut open = true;
+        egui::Window::new(tr!("compare-title"))
+            .open(&mut open)
+            .default_width(720.0)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| self.show_inner(ui, settings));
+            });
+        self.open = open;
+    }
+
+    fn show_inner(&mut self, ui: &mut egui::Ui, settings: &Settings) {
+        ui.weak(tr!("compare-help"));
+        egui::Grid::new("compare_grid")
+            .num_columns(2)
+            .show(ui, |ui| {
+                for (side, label) in [
+                    (Side::Original, tr!("compare-original")),
+                    (Side::Revised, tr!("compare-revised")),
+                ] {
+                    ui.label(label);
+                    ui.horizontal(|ui| {
+                        let path = match side {
+                            Side::Original => &mut self.original,
+                            Side::Revised => &mut self.revised,
+                        };
+                        ui.add(
+                            egui::TextEdit::singleline(path).hint_text(tr!("compare-path-hint")),
+                        );
+                        if ui
+                            .add_enabled(self.picking.is_none(), egui::Button::new("📂"))
+                            .clicked()
+                        {
+                            self.pick(ui.ctx(), side);
+                        }
+                    });
+                    ui.end_row();
+                }
+
+                ui.label(tr!("compare-model"));
+                crate::widgets::model_selector(ui, "compare_model", &mut self.model);
+                ui.end_row();
+
+                ui.label(tr!("compare-focus"));
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.focus)
+                        .hint_text(tr!("compare-focus-hint")),
+                );
+                ui.end_row();
+            });
+
+        ui.horizontal(|ui| {
+            let busy = self.pending.is_some();
+            let ready = [&self.original, &self.revised]
+                .iter()
+                .all(|p| Path::new(p.trim()).is_file());
+            if ui
+                .add_enabled(!busy && ready, egui::Button::new(tr!("compare-run")))
+                .clicked()
+            {
+                self.compare(ui.ctx(), settings);
+            }
+            if busy {
+                ui.spinner();
+            }
+        });
+        if let Some(error) = &self.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+
+        let Some((original, revised, comparison)) = &self.result else {
+            return;
+        };
+        ui.separator();
+        if !comparison.summary.is_empty() {
+            ui.label(&comparison.summary);
+            ui.add_space(4.0);
+        }
+        if comparison.changes.is_empty() {
+            ui.weak(tr!("compare-no-changes"));
+            return;
+        }
+        for kind in ChangeKind::ALL {
+            let changes: Vec<&Change> = comparison
+                .changes
+                .iter()
+                .filter(|c| c.kind == kind)
+                .collect();
+            if changes.is_empty() {
+                continue;
+            }
+            let title = RichText::new(format!("{} ({})", kind.label(), changes.len()))
+                .color(kind.color())
+                .strong();
+            egui::CollapsingHeader::new(title)
+                .id_salt(("compare_kind", kind as u8))
+                .default_open(true)
+                .show(ui, |ui| {
+                    Self::show_table(ui, kind, &changes, original, revised);
+                });
+        }
+    }
+
+    fn show_table(
+        ui: &mut egui::Ui,
+        kind: ChangeKind,
+        changes: &[&Change],
+        original: &Path,
+        revised: &Path,
+    ) {
+        let text_width = 220.0;
+        egui::Grid::new(("compare_table", kind as u8))
+            .striped(true)
+            .num_columns(4)
+            .show(ui, |ui| {
+                ui.strong(tr!("compare-clause"));
+                ui.strong(tr!("compare-original"));
+                ui.strong(tr!("compare-revised"));
+                ui.strong(tr!("compare-note"));
+                ui.end_row();
+                for change in changes {
+                    ui.label(&change.clause);
+                    for (text, path, page) in [
+                        (&change.original, original, change.original_page),
+                        (&change.revised, revised, change.revised_page),
+                    ] {
+                        ui.vertical(|ui| {
+                            ui.set_max_width(text_width);
+                            if !text.is_empty() {
+                                ui.label(text);
+                            }
+                            let link = match page {
+                                Some(page) => tr!("compare-page", page = page),
+                                None if text.is_empty() => return,
+                                None => tr!("compare-open"),
+                            };
+                            if ui.small_button(link).clicked() {
+                                open_page(path, page);
+                            }
+                        });
+                    }
+                    ui.vertical(|ui| {
+                        ui.set_max_width(text_width);
+                        ui.label(&change.note);
+                    });
+                    ui.end_row();
+                }
+            });
+    }
+}
@@ -0,0 +1,498 @@
+use crate::{
+    chat::Message,
+    i18n::{format_number, tr},
+    widgets::{FewShotExample, GeminiModel, Settings},
+};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Local, Utc};
+use eframe::egui;
+use gemini_rust::Tool;
+use serde_json::{json, Value};
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    time::Duration,
+};
+use tokio::sync::oneshot;
+
+const API: &str = "https://generativelanguage.googleapis.com/v1beta";
+
+/// Attachments and instructions past this size are worth caching, roughly 32k tokens.
+const OFFER_BYTES: u64 = 128 * 1024;
+
+/// A cache holding the start of a chat, kept with the chat.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ContextCache {
+    /// `cachedContents/…`
+    pub name: String,
+    pub model: GeminiModel,
+    pub expire_time: DateTime<Utc>,
+    pub tokens: Option<u32>,
+    /// Messages at the start of the chat it holds
+    pub messages: usize,
+    /// Of what went into it, see [`fingerprint`]
+    pub fingerprint: u64,
+}
+
+impl ContextCache {
+    pub fn expired(&self) -> bool {
+        self.expire_time <= Utc::now()
+    }
+}
+
+/// What goes into a new cache, gathered from a chat.
+pub struct CacheRequest {
+    pub display_name: String,
+    pub model: GeminiModel,
+    pub system_prompt: Option<String>,
+    pub tools: Vec<Tool>,
+    pub examples: Vec<FewShotExample>,
+    pub messages: Vec<Message>,
+    pub fingerprint: u64,
+    /// The cache the new one takes the place of
+    pub replaces: Option<String>,
+}
+
+/// Tells whether the start of a chat still is what was cached. Only the lengths of
+/// the messages are hashed, so it is cheap enough to check every frame.
+pub fn fingerprint(
+    model: GeminiModel,
+    system_prompt: Option<&str>,
+    tools: &[Tool],
+    examples: &[FewShotExample],
+    messages: &[Message],
+) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    model.to_string().hash(&mut hasher);
+    system_prompt.hash(&mut hasher);
+    serde_json::to_string(tools)
+        .unwrap_or_default()
+        .hash(&mut hasher);
+    for example in examples {
+        example.input.hash(&mut hasher);
+        example.output.hash(&mut hasher);
+    }
+    for message in messages {
+        message.time.hash(&mut hasher);
+        message.content.len().hash(&mut hasher);
+        for file in &message.files {
+            file.path.hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+/// Whether a chat sends enough attachments and instructions with every message that
+/// caching them pays off.
+pub fn worth_caching(system_prompt: Option<&str>, messages: &[Message]) -> bool {
+    let files: u64 = messages
+        .iter()
+        .flat_map(|m| &m.files)
+        .filter(|f| !f.quarantined)
+        .filter_map(|f| std::fs::metadata(&f.path).ok())
+        .map(|m| m.len())
+        .sum();
+    files + system_prompt.map_or(0, |p| p.len() as u64) >= OFFER_BYTES
+}
+
+/// A cache as the API lists it.
+#[derive(Debug, Clone)]
+pub struct CacheInfo {
+    pub name: String,
+    pub display_name: String,
+    pub model: String,
+    pub expire_time: DateTime<Utc>,
+    pub tokens: Option<u32>,
+}
+
+impl CacheInfo {
+    fn from_json(value: &Value) -> Option<Self> {
+        Some(Self {
+            name: value["name"].as_str()?.to_owned(),
+            display_name: value["displayName"].as_str().unwrap_or_default().to_owned(),
+            model: value["model"]
+                .as_str()
+                .unwrap_or_default()
+                .trim_start_matches("models/")
+                .to_owned(),
+            expire_time: value["expireTime"].as_str()?.parse().ok()?,
+            tokens: value["usageMetadata"]["totalTokenCount"]
+                .as_u64()
+                .map(|t| t as u32),
+        })
+    }
+}
+
+fn client(settings: &Settings) -> Result<reqwest::Client> {
+    let mut builder = reqwest::Client::builder();
+    if let Some(proxy) = settings
+        .proxy_path
+        .as_deref()
+        .filter(|p| !p.trim().is_empty())
+    {
+        builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+    }
+    Ok(builder.build()?)
+}
+
+/// Sends `request` and reads the answer, or the message of the error.
+async fn send(request: reqwest::RequestBuilder, api_key: &str) -> Result<Value> {
+    let response = request.header("x-goog-api-key", api_key).send().await?;
+    let status = response.status();
+    let body: Value = response.json().await.unwrap_or_default();
+    if status.is_success() {
+        return Ok(body);
+    }
+    Err(anyhow!(body["error"]["message"]
+        .as_str()
+        .map(str::to_owned)
+        .unwrap_or_else(|| status.to_string())))
+}
+
+/// Caches what `request` holds for as long as the settings say.
+pub async fn create(settings: Settings, request: CacheRequest) -> Result<ContextCache> {
+    log::info!(
+        "caching {} messages for {}",
+        request.messages.len(),
+        request.model
+    );
+    let gemini = crate::widgets::build_client(
+        &settings.api_key,
+        settings.proxy_path.clone(),
+        request.model.into(),
+    )?;
+    let mut contents = geminid_core::history::examples_to_history(&request.examples);
+    contents.extend(
+        crate::chat_completion::build_history(&gemini, &request.messages, 0, None, None, None)
+            .await?,
+    );
+    let mut body = json!({
+        "model": format!("models/{}", request.model),
+        "displayName": request.display_name,
+        "contents": contents,
+        "ttl": format!("{}s", settings.cache.ttl().as_secs()),
+    });
+    if let Some(prompt) = request.system_prompt.filter(|p| !p.trim().is_empty()) {
+        body["systemInstruction"] = json!({ "parts": [{ "text": prompt }] });
+    }
+    if !request.tools.is_empty() {
+        body["tools"] = serde_json::to_value(&request.tools)?;
+    }
+
+    if let Some(old) = &request.replaces {
+        if let Err(e) = delete(&settings, old).await {
+            log::warn!("failed to delete the old cache {old}: {e}");
+        }
+    }
+    let response = send(
+        client(&settings)?
+            .post(format!("{API}/cachedContents"))
+            .json(&body),
+        &settings.api_key,
+    )
+    .await?;
+    let info = CacheInfo::from_json(&response).ok_or_else(|| anyhow!(tr!("cache-invalid")))?;
+    Ok(ContextCache {
+        name: info.name,
+        model: request.model,
+        expire_time: info.expire_time,
+        tokens: info.tokens,
+        messages: request.messages.len(),
+        fingerprint: request.fingerprint,
+    })
+}
+
+pub async fn list(settings: &Settings) -> Result<Vec<CacheInfo>> {
+    let response = send(
+        client(settings)?.get(format!("{API}/cachedContents?pageSize=100")),
+        &settings.api_key,
+    )
+    .await?;
+    Ok(response["cachedContents"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(CacheInfo::from_json)
+        .collect())
+}
+
+pub async fn delete(settings: &Settings, name: &str) -> Result<()> {
+    send(
+        client(settings)?.delete(format!("{API}/{name}")),
+        &settings.api_key,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Makes the cache last for the configured time from now.
+async fn extend(settings: &Settings, name: &str) -> Result<()> {
+    send(
+        client(settings)?
+            .patch(format!("{API}/{name}?updateMask=ttl"))
+            .json(&json!({ "ttl": format!("{}s", settings.cache.ttl().as_secs()) })),
+        &settings.api_key,
+    )
+    .await?;
+    Ok(())
+}
+
+/// What the user did with the cache of a chat.
+pub enum BannerAction {
+    Create,
+    Dismiss,
+    Drop,
+}
+
+/// Shows the cache of a chat above the chatbox, or offers to make one. `stale` when
+/// the start of the chat changed since it was cached, `can_create` unless an answer is
+/// being generated.
+pub fn show_banner(
+    ui: &mut egui::Ui,
+    cache: Option<&ContextCache>,
+    stale: bool,
+    busy: bool,
+    can_create: bool,
+) -> Option<BannerAction> {
+    let mut action = None;
+    egui::Frame::group(ui.style()).show(ui, |ui| {
+        ui.horizontal_wrapped(|ui| {
+            ui.label("🗄");
+            if busy {
+                ui.spinner();
+                ui.label(tr!("cache-creating"));
+                return;
+            }
+            match cache {
+                Some(cache) if cache.expired() || stale => {
+                    let text = if stale {
+                        tr!("cache-stale")
+                    } else {
+                        tr!("cache-expired")
+                    };
+                    ui.colored_label(ui.visuals().warn_fg_color, text);
+                    let button = egui::Button::new(tr!("cache-recreate")).small();
+                    if ui.add_enabled(can_create, button).clicked() {
+                        action = Some(BannerAction::Create);
+                    }
+                    if ui.small_button(tr!("cache-drop")).clicked() {
+                        action = Some(BannerAction::Drop);
+                    }
+                }
+                Some(cache) => {
+                    let until = cache.expire_time.with_timezone(&Local).format("%H:%M");
+                    ui.label(tr!(
+                        "cache-active",
+                        count = cache.messages,
+                        time = until.to_string()
+                    ));
+                    if let Some(tokens) = cache.tokens {
+                        ui.weak(tr!("token-count", count = tokens));
+                    }
+                    if ui
+                        .small_button("✖")
+                        .on_hover_text(tr!("cache-drop"))
+                        .clicked()
+                    {
+                        action = Some(BannerAction::Drop);
+                    }
+                }
+                None => {
+                    ui.label(tr!("cache-offer-banner"));
+                    let button = egui::Button::new(tr!("cache-create")).small();
+                    if ui.add_enabled(can_create, button).clicked() {
+                        action = Some(BannerAction::Create);
+                    }
+                    if ui.small_button(tr!("cache-dismiss")).clicked() {
+                        action = Some(BannerAction::Dismiss);
+                    }
+                }
+            }
+        });
+    });
+    action
+}
+
+/// How chats are cached.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct CacheSettings {
+    /// Suggests caching chats with large attachments or instructions
+    pub offer: bool,
+    /// How long a cache lives, in minutes
+    pub ttl_minutes: u32,
+}
+
+impl Default for CacheSettings {
+    fn default() -> Self {
+        Self {
+            offer: true,
+            ttl_minutes: 60,
+        }
+    }
+}
+
+impl CacheSettings {
+    fn ttl(&self) -> Duration {
+        Duration::from_secs(u64::from(self.ttl_minutes.max(1)) * 60)
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.offer, tr!("cache-offer"))
+            .on_hover_text(tr!("cache-offer-help"));
+        ui.horizontal(|ui| {
+            ui.label(tr!("cache-ttl"));
+            ui.add(
+                egui::DragValue::new(&mut self.ttl_minutes)
+                    .range(1..=1440)
+                    .suffix(" min"),
+            );
+        });
+    }
+}
+
+/// A change the user made in the cache list.
+enum Change {
+    Delete(String),
+    Extend(String),
+}
+
+/// Lists the caches of the account, they cost storage for as long as they live.
+#[derive(Default)]
+pub struct CachePanel {
+    pub open: bool,
+    pending: Option<oneshot::Receiver<Result<Vec<CacheInfo>, String>>>,
+    caches: Vec<CacheInfo>,
+    error: Option<String>,
+    loaded: bool,
+}
+
+impl CachePanel {
+    #[inline]
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    /// Applies `change`, if any, then lists the caches again.
+    fn start(&mut self, ctx: &egui::Context, settings: &Settings, change: Option<Change>) {
+        let (tx, rx) = oneshot::channel();
+        self.pending = Some(rx);
+        self.loaded = true;
+        let settings = settings.clone();
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            let changed = match &change {
+                Some(Change::Delete(name)) => delete(&settings, name).await,
+                Some(Change::Extend(name)) => extend(&settings, name).await,
+                None => Ok(()),
+            };
+            let result = match changed {
+                Ok(()) => list(&settings).await,
+                Err(e) => Err(e),
+            }
+            .map_err(|e| {
+                log::error!("failed to manage the context caches: {e}");
+                e.to_string()
+            });
+            let _ = tx.send(result);
+            ctx.request_repaint();
+        });
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, settings: &Settings) {
+        if let Some(rx) = &mut self.pending {
+            if let Ok(result) = rx.try_recv() {
+                match result {
+                    Ok(caches) => {
+                        self.caches = caches;
+                        self.error = None;
+                    }
+                    Err(e) => self.error = Some(e),
+                }
+                self.pending = None;
+            }
+        }
+        if !self.open {
+            return;
+        }
+        if !self.loaded {
+            self.start(ctx, settings, None);
+        }
+        let mut open = true;
+        egui::Window::new(tr!("cache-title"))
+            .open(&mut open)
+            .default_width(520.0)
+            .show(ctx, |ui| self.show_inner(ui, settings));
+        self.open = open;
+        if !open {
+            // list them afresh next time, chats may have made new ones
+            self.loaded = false;
+        }
+    }
+
+    fn show_inner(&mut self, ui: &mut egui::Ui, settings: &Settings) {
+        ui.weak(tr!("cache-help"));
+        let busy = self.pending.is_some();
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(!busy, egui::Button::new(tr!("cache-refresh")))
+                .clicked()
+            {
+                self.start(ui.ctx(), settings, None);
+            }
+            if busy {
+                ui.spinner();
+            }
+        });
+        if let Some(error) = &self.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+        ui.separator();
+        if self.caches.is_empty() {
+            ui.weak(tr!("cache-none"));
+            return;
+        }
+        let mut change = None;
+        egui::Grid::new("context_caches")
+            .num_columns(5)
+            .striped(true)
+            .show(ui, |ui| {
+                for cache in &self.caches {
+                    let name = if cache.display_name.is_empty() {
+                        &cache.name
+                    } else {
+                        &cache.display_name
+                    };
+                    ui.label(name).on_hover_text(&cache.name);
+                    ui.weak(&cache.model);
+                    ui.label(
+                        cache
+                            .tokens
+                            .map_or_else(String::new, |t| tr!("token-count", count = t)),
+                    );
+                    let left = (cache.expire_time - Utc::now()).num_minutes().max(0);
+                    ui.label(tr!(
+                        "cache-expires",
+                        minutes = format_number(left as f64, 0)
+                    ));
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(!busy, egui::Button::new(tr!("cache-extend")))
+                            .clicked()
+                        {
+                            change = Some(Change::Extend(cache.name.clone()));
+                        }
+                        if ui
+                            .add_enabled(!busy, egui::Button::new(tr!("cache-delete")))
+                            .clicked()
+                        {
+                            change = Some(Change::Delete(cache.name.clone()));
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+        if change.is_some() {
+            self.start(ui.ctx(), settings, change);
+        }
+    }
+}
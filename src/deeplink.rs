@@ -0,0 +1,156 @@
+use crate::{i18n::tr, widgets::GeminiModel};
+use anyhow::{anyhow, Result};
+use eframe::egui;
+
+pub const SCHEME: &str = "geminid";
+
+/// A `geminid://` link, handed to the app by the system when one is opened.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeepLink {
+    /// `geminid://new?prompt=...&model=...`, the prompt is left in the chatbox as any
+    /// web page can open such a link
+    NewChat {
+        prompt: String,
+        /// Only models the app knows or the API listed
+        model: Option<GeminiModel>,
+    },
+    /// `geminid://chat/<id>`
+    OpenChat(u64),
+}
+
+impl DeepLink {
+    #[inline]
+    pub fn is_link(arg: &str) -> bool {
+        arg.starts_with(&format!("{SCHEME}://"))
+    }
+
+    pub fn parse(link: &str) -> Result<Self> {
+        let url = url::Url::parse(link)?;
+        if url.scheme() != SCHEME {
+            return Err(anyhow!("not a {SCHEME}:// link"));
+        }
+        match url.host_str() {
+            Some("new") => {
+                let mut prompt = String::new();
+                let mut model = None;
+                for (key, value) in url.query_pairs() {
+                    match &*key {
+                        "prompt" => prompt = value.into_owned(),
+                        "model" => {
+                            model = crate::models::available()
+                                .into_iter()
+                                .find(|m| m.to_string() == value);
+                            if model.is_none() {
+                                log::warn!("unknown model `{value}` in a {SCHEME}:// link");
+                            }
+                        }
+                        _ => log::warn!("unknown parameter `{key}` in a {SCHEME}:// link"),
+                    }
+                }
+                Ok(Self::NewChat { prompt, model })
+            }
+            Some("chat") => {
+                let id = url.path().trim_matches('/');
+                id.parse()
+                    .map(Self::OpenChat)
+                    .map_err(|_| anyhow!("invalid chat id `{id}`"))
+            }
+            _ => Err(anyhow!("unknown link {link}")),
+        }
+    }
+}
+
+/// Makes this executable the handler of `geminid://` links for the current user.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn register() -> Result<()> {
+    const DESKTOP_FILE: &str = "geminid-url-handler.desktop";
+
+    let exe = std::env::current_exe()?;
+    let data_dir = std::env::var_os("XDG_DATA_HOME")
+        .map(std::path::PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME").map(|home| std::path::Path::new(&home).join(".local/share"))
+        })
+        .ok_or_else(|| anyhow!("no home folder"))?;
+    let dir = data_dir.join("applications");
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(
+        dir.join(DESKTOP_FILE),
+        format!(
+            "[Desktop Entry]\n\
+             Type=Application\n\
+             Name={}\n\
+             Exec=\"{}\" %u\n\
+             NoDisplay=true\n\
+             MimeType=x-scheme-handler/{SCHEME};\n",
+            crate::TITLE,
+            exe.display()
+        ),
+    )?;
+    let status = std::process::Command::new("xdg-mime")
+        .args(["default", DESKTOP_FILE])
+        .arg(format!("x-scheme-handler/{SCHEME}"))
+        .status()?;
+    if !status.success() {
+        return Err(anyhow!("xdg-mime failed with {status}"));
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+fn register() -> Result<()> {
+    let exe = std::env::current_exe()?;
+    let key = format!(r"HKCU\Software\Classes\{SCHEME}");
+    let command = format!("\"{}\" \"%1\"", exe.display());
+    let entries = [
+        (key.clone(), None, format!("URL:{}", crate::TITLE)),
+        (key.clone(), Some("URL Protocol"), String::new()),
+        (format!(r"{key}\shell\open\command"), None, command),
+    ];
+    for (key, name, data) in entries {
+        let mut reg = std::process::Command::new("reg");
+        reg.args(["add", &key, "/f", "/d", &data]);
+        match name {
+            Some(name) => reg.args(["/v", name]),
+            None => reg.arg("/ve"),
+        };
+        let status = reg.status()?;
+        if !status.success() {
+            return Err(anyhow!("reg add {key} failed with {status}"));
+        }
+    }
+    Ok(())
+}
+
+/// On macOS the scheme is declared in the app bundle's `Info.plist` instead.
+#[cfg(not(any(windows, all(unix, not(target_os = "macos")))))]
+fn register() -> Result<()> {
+    Err(anyhow!(tr!("deeplink-unsupported")))
+}
+
+/// The settings button that registers the link handler, with the outcome next to it.
+pub fn show_register_button(ui: &mut egui::Ui) {
+    let id = egui::Id::new("deeplink_register_result");
+    ui.horizontal(|ui| {
+        if ui
+            .button(tr!("deeplink-register"))
+            .on_hover_text(tr!("deeplink-register-help"))
+            .clicked()
+        {
+            let result = register().map_err(|e| {
+                log::error!("failed to register {SCHEME}:// links: {e}");
+                e.to_string()
+            });
+            ui.data_mut(|d| d.insert_temp(id, result));
+        }
+        match ui.data(|d| d.get_temp::<Result<(), String>>(id)) {
+            Some(Ok(())) => {
+                ui.weak(tr!("deeplink-registered"));
+            }
+            Some(Err(e)) => {
+                ui.colored_label(ui.visuals().error_fg_color, e);
+            }
+            None => (),
+        }
+    });
+}
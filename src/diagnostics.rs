@@ -0,0 +1,439 @@
+use crate::{
+    i18n::tr,
+    widgets::{AuthMethod, Settings},
+};
+use bytesize::ByteSize;
+use eframe::egui::{self, Color32, RichText};
+use std::{
+    fmt::Write,
+    path::Path,
+    time::{Duration, Instant},
+};
+use tokio::sync::oneshot;
+
+const API_ENDPOINT: &str = "https://generativelanguage.googleapis.com";
+const CODE_ASSIST_ENDPOINT: &str = "https://cloudcode-pa.googleapis.com";
+const TIMEOUT: Duration = Duration::from_secs(10);
+/// Round trips measured for the latency check.
+const LATENCY_SAMPLES: usize = 3;
+/// Below this much free space the chat store is at risk.
+const LOW_DISK_SPACE: u64 = 1024 * 1024 * 1024;
+const CRITICAL_DISK_SPACE: u64 = 100 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Pass,
+    Warn,
+    Fail,
+    Skipped,
+}
+
+impl Status {
+    fn icon(self) -> &'static str {
+        match self {
+            Self::Pass => "✔",
+            Self::Warn => "⚠",
+            Self::Fail => "❌",
+            Self::Skipped => "➖",
+        }
+    }
+
+    /// Untranslated, the report ends up in bug reports.
+    fn marker(self) -> &'static str {
+        match self {
+            Self::Pass => "OK",
+            Self::Warn => "WARN",
+            Self::Fail => "FAIL",
+            Self::Skipped => "SKIP",
+        }
+    }
+
+    fn color(self, ui: &egui::Ui) -> Color32 {
+        match self {
+            Self::Pass => Color32::from_rgb(141, 189, 156),
+            Self::Warn => ui.visuals().warn_fg_color,
+            Self::Fail => ui.visuals().error_fg_color,
+            Self::Skipped => ui.visuals().weak_text_color(),
+        }
+    }
+}
+
+struct Check {
+    name: String,
+    status: Status,
+    detail: String,
+}
+
+impl Check {
+    fn new(name: &str, status: Status, detail: String) -> Self {
+        Self {
+            name: tr!(name),
+            status,
+            detail,
+        }
+    }
+}
+
+fn millis(duration: Duration) -> u64 {
+    duration.as_millis() as u64
+}
+
+/// The proxy without the credentials it may carry, fit for a report.
+fn redacted_proxy(proxy: &str) -> String {
+    match url::Url::parse(proxy) {
+        Ok(url) => format!(
+            "{}://{}:{}",
+            url.scheme(),
+            url.host_str().unwrap_or_default(),
+            url.port_or_known_default().unwrap_or(1080)
+        ),
+        Err(_) => tr!("diagnostics-invalid-proxy"),
+    }
+}
+
+fn http_client(proxy: Option<&str>) -> reqwest::Client {
+    let mut builder = reqwest::Client::builder().timeout(TIMEOUT);
+    if let Some(proxy) = proxy.and_then(|p| reqwest::Proxy::all(p).ok()) {
+        builder = builder.proxy(proxy);
+    }
+    builder.build().unwrap_or_default()
+}
+
+async fn check_proxy(proxy: Option<&str>) -> Check {
+    const NAME: &str = "diagnostics-proxy";
+    let Some(proxy) = proxy else {
+        return Check::new(NAME, Status::Skipped, tr!("diagnostics-no-proxy"));
+    };
+    let url = match url::Url::parse(proxy) {
+        Ok(url) => url,
+        Err(e) => return Check::new(NAME, Status::Fail, format!("{proxy}: {e}")),
+    };
+    let host = url.host_str().unwrap_or_default().to_owned();
+    let port = url.port_or_known_default().unwrap_or(1080);
+    let start = Instant::now();
+    let connect = tokio::net::TcpStream::connect((host.as_str(), port));
+    match tokio::time::timeout(TIMEOUT, connect).await {
+        Ok(Ok(_)) => Check::new(
+            NAME,
+            Status::Pass,
+            tr!(
+                "diagnostics-reachable",
+                target = redacted_proxy(proxy),
+                ms = millis(start.elapsed())
+            ),
+        ),
+        Ok(Err(e)) => Check::new(
+            NAME,
+            Status::Fail,
+            format!("{}: {e}", redacted_proxy(proxy)),
+        ),
+        Err(_) => Check::new(
+            NAME,
+            Status::Fail,
+            tr!("diagnostics-timeout", target = redacted_proxy(proxy)),
+        ),
+    }
+}
+
+/// Any HTTP answer counts, the root path itself doesn't serve anything.
+async fn check_endpoint(client: &reqwest::Client, endpoint: &str) -> Check {
+    const NAME: &str = "diagnostics-endpoint";
+    let start = Instant::now();
+    match client.get(endpoint).send().await {
+        Ok(_) => Check::new(
+            NAME,
+            Status::Pass,
+            tr!(
+                "diagnostics-reachable",
+                target = endpoint,
+                ms = millis(start.elapsed())
+            ),
+        ),
+        Err(e) if e.is_timeout() => Check::new(
+            NAME,
+            Status::Fail,
+            tr!("diagnostics-timeout", target = endpoint),
+        ),
+        Err(e) => Check::new(NAME, Status::Fail, format!("{endpoint}: {e}")),
+    }
+}
+
+/// The message of an API error response, or its status.
+async fn error_message(response: reqwest::Response) -> String {
+    let status = response.status();
+    response
+        .json::<serde_json::Value>()
+        .await
+        .ok()
+        .and_then(|json| json["error"]["message"].as_str().map(str::to_owned))
+        .unwrap_or_else(|| status.to_string())
+}
+
+async fn check_credentials(settings: &Settings, client: &reqwest::Client) -> Check {
+    const NAME: &str = "diagnostics-credentials";
+    match settings.auth_method {
+        AuthMethod::ApiKey => {
+            if settings.api_key.is_empty() {
+                return Check::new(NAME, Status::Fail, tr!("diagnostics-no-api-key"));
+            }
+            let response = client
+                .get(format!("{API_ENDPOINT}/v1beta/models?pageSize=1"))
+                .header("x-goog-api-key", &settings.api_key)
+                .send()
+                .await;
+            match response {
+                Ok(r) if r.status().is_success() => {
+                    Check::new(NAME, Status::Pass, tr!("diagnostics-api-key-valid"))
+                }
+                Ok(r) if r.status().is_client_error() => Check::new(
+                    NAME,
+                    Status::Fail,
+                    tr!("diagnostics-rejected", reason = error_message(r).await),
+                ),
+                Ok(r) => Check::new(NAME, Status::Warn, error_message(r).await),
+                Err(e) => Check::new(NAME, Status::Warn, e.to_string()),
+            }
+        }
+        AuthMethod::CodeAssist => {
+            if settings.oauth_token.is_empty() || settings.project_id.is_empty() {
+                return Check::new(NAME, Status::Fail, tr!("diagnostics-not-logged-in"));
+            }
+            let client = gemini_code_assist_adapter::CodeAssistClient::new(
+                settings.oauth_token.clone(),
+                settings.project_id.clone(),
+            );
+            match tokio::time::timeout(TIMEOUT, client.load_code_assist()).await {
+                Ok(Ok(project)) => Check::new(
+                    NAME,
+                    Status::Pass,
+                    tr!("diagnostics-code-assist-valid", project = project),
+                ),
+                Ok(Err(e)) => Check::new(
+                    NAME,
+                    Status::Fail,
+                    tr!("diagnostics-rejected", reason = e.to_string()),
+                ),
+                Err(_) => Check::new(
+                    NAME,
+                    Status::Fail,
+                    tr!("diagnostics-timeout", target = CODE_ASSIST_ENDPOINT),
+                ),
+            }
+        }
+    }
+}
+
+async fn check_latency(client: &reqwest::Client, endpoint: &str) -> Check {
+    const NAME: &str = "diagnostics-latency";
+    let mut samples = Vec::with_capacity(LATENCY_SAMPLES);
+    for _ in 0..LATENCY_SAMPLES {
+        let start = Instant::now();
+        if client.get(endpoint).send().await.is_ok() {
+            samples.push(millis(start.elapsed()));
+        }
+    }
+    if samples.is_empty() {
+        return Check::new(
+            NAME,
+            Status::Skipped,
+            tr!("diagnostics-timeout", target = endpoint),
+        );
+    }
+    samples.sort_unstable();
+    let median = samples[samples.len() / 2];
+    let status = if median < 1000 {
+        Status::Pass
+    } else {
+        Status::Warn
+    };
+    Check::new(
+        NAME,
+        status,
+        tr!(
+            "diagnostics-latency-result",
+            min = samples[0],
+            median = median,
+            max = samples[samples.len() - 1]
+        ),
+    )
+}
+
+/// Size of the files right in `dir`, the chats and settings. Logs and models have
+/// folders of their own.
+fn store_size(dir: &Path) -> u64 {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|e| e.metadata().ok())
+                .filter(|m| m.is_file())
+                .map(|m| m.len())
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+fn check_disk() -> Check {
+    const NAME: &str = "diagnostics-disk";
+    let Some(dir) = eframe::storage_dir(crate::TITLE) else {
+        return Check::new(NAME, Status::Fail, tr!("diagnostics-no-storage"));
+    };
+    // the folder doesn't exist before the first save
+    let existing = dir.ancestors().find(|d| d.exists()).unwrap_or(&dir);
+    let free = match fs4::available_space(existing) {
+        Ok(free) => free,
+        Err(e) => return Check::new(NAME, Status::Warn, format!("{}: {e}", dir.display())),
+    };
+    let status = if free < CRITICAL_DISK_SPACE {
+        Status::Fail
+    } else if free < LOW_DISK_SPACE {
+        Status::Warn
+    } else {
+        Status::Pass
+    };
+    Check::new(
+        NAME,
+        status,
+        tr!(
+            "diagnostics-disk-result",
+            free = ByteSize(free).to_string(),
+            used = ByteSize(store_size(&dir)).to_string(),
+            path = dir.display().to_string()
+        ),
+    )
+}
+
+async fn run(settings: Settings) -> Vec<Check> {
+    let proxy = settings.proxy_path.clone().filter(|p| !p.trim().is_empty());
+    let client = http_client(proxy.as_deref());
+    let endpoint = match settings.auth_method {
+        AuthMethod::ApiKey => API_ENDPOINT,
+        AuthMethod::CodeAssist => CODE_ASSIST_ENDPOINT,
+    };
+    vec![
+        check_endpoint(&client, endpoint).await,
+        check_credentials(&settings, &client).await,
+        check_proxy(proxy.as_deref()).await,
+        check_latency(&client, endpoint).await,
+        check_disk(),
+    ]
+}
+
+/// Checks whether chatting can work at all, for when it doesn't.
+#[derive(Default)]
+pub struct Diagnostics {
+    pub open: bool,
+    pending: Option<oneshot::Receiver<Vec<Check>>>,
+    checks: Vec<Check>,
+    /// Proxy and auth method the checks ran with, for the report
+    context: String,
+}
+
+impl Diagnostics {
+    #[inline]
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    fn start(&mut self, ctx: &egui::Context, settings: &Settings) {
+        let proxy = settings
+            .proxy_path
+            .as_deref()
+            .filter(|p| !p.trim().is_empty())
+            .map_or_else(|| tr!("diagnostics-no-proxy"), redacted_proxy);
+        self.context = format!("{}, {proxy}", settings.auth_method);
+        let (tx, rx) = oneshot::channel();
+        self.pending = Some(rx);
+        let settings = settings.clone();
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            let checks = run(settings).await;
+            for check in checks.iter().filter(|c| c.status == Status::Fail) {
+                log::warn!("diagnostics: {} failed: {}", check.name, check.detail);
+            }
+            let _ = tx.send(checks);
+            ctx.request_repaint();
+        });
+    }
+
+    fn report(&self) -> String {
+        let mut text = format!(
+            "{} {} on {} {}\n{}\n\n",
+            crate::TITLE,
+            env!("CARGO_PKG_VERSION"),
+            std::env::consts::OS,
+            std::env::consts::ARCH,
+            self.context
+        );
+        for check in &self.checks {
+            let _ = writeln!(
+                text,
+                "[{}] {}: {}",
+                check.status.marker(),
+                check.name,
+                check.detail
+            );
+        }
+        text
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, settings: &Settings) {
+        if let Some(rx) = &mut self.pending {
+            if let Ok(checks) = rx.try_recv() {
+                self.checks = checks;
+                self.pending = None;
+            }
+        }
+        if !self.open {
+            return;
+        }
+        if self.checks.is_empty() && self.pending.is_none() {
+            self.start(ctx, settings);
+        }
+        let mut open = true;
+        egui::Window::new(tr!("diagnostics-title"))
+            .open(&mut open)
+            .default_width(520.0)
+            .show(ctx, |ui| self.show_inner(ui, settings));
+        self.open = open;
+    }
+
+    fn show_inner(&mut self, ui: &mut egui::Ui, settings: &Settings) {
+        ui.horizontal(|ui| {
+            let busy = self.pending.is_some();
+            if ui
+                .add_enabled(!busy, egui::Button::new(tr!("diagnostics-run")))
+                .clicked()
+            {
+                self.start(ui.ctx(), settings);
+            }
+            if busy {
+                ui.spinner();
+                ui.label(tr!("diagnostics-running"));
+            }
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui
+                    .add_enabled(
+                        !self.checks.is_empty(),
+                        egui::Button::new(tr!("diagnostics-copy")),
+                    )
+                    .clicked()
+                {
+                    ui.ctx().copy_text(self.report());
+                }
+            });
+        });
+        ui.separator();
+        egui::Grid::new("diagnostics_grid")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                for check in &self.checks {
+                    ui.label(RichText::new(check.status.icon()).color(check.status.color(ui)));
+                    ui.strong(&check.name);
+                    ui.add(egui::Label::new(&check.detail).wrap());
+                    ui.end_row();
+                }
+            });
+    }
+}
@@ -0,0 +1,357 @@
+use crate::{
+    i18n::{format_number, tr},
+    semantic,
+    widgets::Settings,
+};
+use eframe::egui::{self, Margin};
+use gemini_rust::TaskType;
+use tokio::sync::oneshot;
+
+/// More than this doesn't fit in the similarity table anyway
+const MAX_ITEMS: usize = 100;
+const MAX_CLUSTER_ITERATIONS: usize = 50;
+/// Item labels in the table are cut to this many characters
+const LABEL_CHARS: usize = 24;
+
+/// What the embeddings are optimized for, passed to the API as the task type.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum Task {
+    #[default]
+    Similarity,
+    Clustering,
+    Classification,
+    Document,
+    Query,
+}
+
+impl Task {
+    const ALL: [Self; 5] = [
+        Self::Similarity,
+        Self::Clustering,
+        Self::Classification,
+        Self::Document,
+        Self::Query,
+    ];
+
+    fn label(self) -> String {
+        tr!(match self {
+            Self::Similarity => "embedding-task-similarity",
+            Self::Clustering => "embedding-task-clustering",
+            Self::Classification => "embedding-task-classification",
+            Self::Document => "embedding-task-document",
+            Self::Query => "embedding-task-query",
+        })
+    }
+
+    fn task_type(self) -> TaskType {
+        match self {
+            Self::Similarity => TaskType::SemanticSimilarity,
+            Self::Clustering => TaskType::Clustering,
+            Self::Classification => TaskType::Classification,
+            Self::Document => TaskType::RetrievalDocument,
+            Self::Query => TaskType::RetrievalQuery,
+        }
+    }
+}
+
+struct Embedded {
+    items: Vec<String>,
+    vectors: Vec<Vec<f32>>,
+}
+
+/// Index of the centroid closest to `vector`.
+fn nearest(centroids: &[Vec<f32>], vector: &[f32]) -> usize {
+    centroids
+        .iter()
+        .map(|c| semantic::cosine(c, vector))
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map_or(0, |(i, _)| i)
+}
+
+/// k-means by cosine similarity, starting from the items farthest apart so the
+/// result doesn't change between runs. Returns the cluster of every item.
+fn cluster(vectors: &[Vec<f32>], k: usize) -> Vec<usize> {
+    let Some(first) = vectors.first() else {
+        return Vec::new();
+    };
+    let k = k.clamp(1, vectors.len());
+    let mut centroids = vec![first.clone()];
+    while centroids.len() < k {
+        let farthest = vectors
+            .iter()
+            .min_by(|a, b| {
+                let closeness = |v: &[f32]| {
+                    centroids
+                        .iter()
+                        .map(|c| semantic::cosine(c, v))
+                        .fold(f32::MIN, f32::max)
+                };
+                closeness(a).total_cmp(&closeness(b))
+            })
+            .unwrap_or(first);
+        centroids.push(farthest.clone());
+    }
+
+    let mut assignment = vec![0; vectors.len()];
+    for _ in 0..MAX_CLUSTER_ITERATIONS {
+        let next: Vec<usize> = vectors.iter().map(|v| nearest(&centroids, v)).collect();
+        let changed = next != assignment;
+        assignment = next;
+        for (idx, centroid) in centroids.iter_mut().enumerate() {
+            let members: Vec<&Vec<f32>> = vectors
+                .iter()
+                .zip(&assignment)
+                .filter(|(_, &c)| c == idx)
+                .map(|(v, _)| v)
+                .collect();
+            if members.is_empty() {
+                continue;
+            }
+            for (dim, value) in centroid.iter_mut().enumerate() {
+                *value = members.iter().map(|m| m[dim]).sum::<f32>() / members.len() as f32;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    assignment
+}
+
+fn short_label(item: &str) -> String {
+    if item.chars().count() <= LABEL_CHARS {
+        return item.to_owned();
+    }
+    let cut: String = item.chars().take(LABEL_CHARS - 1).collect();
+    format!("{cut}…")
+}
+
+/// A window for trying out the embedding model: similarities between texts and
+/// clusters of a list.
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct EmbeddingPlayground {
+    /// One item per line
+    pub text: String,
+    pub task: Task,
+    pub clusters: usize,
+    #[serde(skip)]
+    pub open: bool,
+    #[serde(skip)]
+    pending: Option<oneshot::Receiver<Result<Embedded, String>>>,
+    #[serde(skip)]
+    result: Option<Embedded>,
+    /// Cluster of every item of `result`
+    #[serde(skip)]
+    assignment: Option<Vec<usize>>,
+    #[serde(skip)]
+    error: Option<String>,
+}
+
+impl Default for EmbeddingPlayground {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            task: Task::default(),
+            clusters: 3,
+            open: false,
+            pending: None,
+            result: None,
+            assignment: None,
+            error: None,
+        }
+    }
+}
+
+impl EmbeddingPlayground {
+    fn items(&self) -> Vec<String> {
+        self.text
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .take(MAX_ITEMS)
+            .map(str::to_owned)
+            .collect()
+    }
+
+    fn poll(&mut self) {
+        let Some(rx) = &mut self.pending else {
+            return;
+        };
+        if let Ok(result) = rx.try_recv() {
+            self.pending = None;
+            match result {
+                Ok(embedded) => {
+                    self.result = Some(embedded);
+                    self.error = None;
+                }
+                Err(e) => self.error = Some(e),
+            }
+        }
+    }
+
+    fn embed(&mut self, ctx: &egui::Context, settings: &Settings) {
+        let items = self.items();
+        let task = self.task;
+        let settings = settings.clone();
+        let ctx = ctx.clone();
+        let (tx, rx) = oneshot::channel();
+        self.pending = Some(rx);
+        self.assignment = None;
+        tokio::spawn(async move {
+            let mut vectors = Vec::with_capacity(items.len());
+            let mut result = Ok(());
+            for batch in items.chunks(semantic::BATCH_SIZE) {
+                match semantic::embed(&settings, batch, task.task_type()).await {
+                    Ok(batch) => vectors.extend(batch),
+                    Err(e) => {
+                        log::error!("failed to embed the playground items: {e}");
+                        result = Err(e.to_string());
+                        break;
+                    }
+                }
+            }
+            let _ = tx.send(result.map(|()| Embedded { items, vectors }));
+            ctx.request_repaint();
+        });
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, settings: &Settings) {
+        self.poll();
+        if !self.open {
+            return;
+        }
+        let mut open = true;
+        egui::Window::new(tr!("embedding-title"))
+            .open(&mut open)
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| self.show_inner(ui, settings));
+            });
+        self.open = open;
+    }
+
+    fn show_inner(&mut self, ui: &mut egui::Ui, settings: &Settings) {
+        ui.weak(tr!("embedding-help"));
+        ui.add(
+            egui::TextEdit::multiline(&mut self.text)
+                .hint_text(tr!("embedding-items-hint"))
+                .desired_rows(8)
+                .desired_width(f32::INFINITY),
+        );
+        ui.horizontal(|ui| {
+            ui.label(tr!("embedding-task"));
+            egui::ComboBox::from_id_salt("embedding_task")
+                .selected_text(self.task.label())
+                .show_ui(ui, |ui| {
+                    for task in Task::ALL {
+                        ui.selectable_value(&mut self.task, task, task.label());
+                    }
+                });
+            let busy = self.pending.is_some();
+            if ui
+                .add_enabled(
+                    !busy && !self.items().is_empty(),
+                    egui::Button::new(tr!("embedding-run")),
+                )
+                .clicked()
+            {
+                self.embed(ui.ctx(), settings);
+            }
+            if busy {
+                ui.spinner();
+            }
+        });
+        if let Some(error) = &self.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+
+        let Some(result) = &self.result else {
+            return;
+        };
+        ui.separator();
+        ui.horizontal(|ui| {
+            let dimensions = result.vectors.first().map_or(0, Vec::len);
+            ui.label(tr!(
+                "embedding-summary",
+                count = result.vectors.len(),
+                dimensions = dimensions
+            ));
+            if ui.button(tr!("embedding-copy")).clicked() {
+                let json: Vec<_> = result
+                    .items
+                    .iter()
+                    .zip(&result.vectors)
+                    .map(|(text, vector)| serde_json::json!({ "text": text, "embedding": vector }))
+                    .collect();
+                ui.ctx()
+                    .copy_text(serde_json::Value::Array(json).to_string());
+            }
+        });
+
+        egui::CollapsingHeader::new(tr!("embedding-similarity"))
+            .default_open(true)
+            .show(ui, |ui| {
+                egui::ScrollArea::horizontal().show(ui, |ui| {
+                    Self::show_similarity(ui, result);
+                });
+            });
+
+        egui::CollapsingHeader::new(tr!("embedding-clusters"))
+            .default_open(true)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr!("embedding-cluster-count"));
+                    ui.add(egui::DragValue::new(&mut self.clusters).range(2..=10));
+                    if ui.button(tr!("embedding-cluster")).clicked() {
+                        self.assignment = Some(cluster(&result.vectors, self.clusters));
+                    }
+                });
+                let Some(assignment) = &self.assignment else {
+                    return;
+                };
+                let count = assignment.iter().max().map_or(0, |max| max + 1);
+                for group in 0..count {
+                    ui.strong(tr!("embedding-group", number = group + 1));
+                    for (item, _) in result
+                        .items
+                        .iter()
+                        .zip(assignment)
+                        .filter(|(_, &c)| c == group)
+                    {
+                        ui.label(format!("• {item}"));
+                    }
+                    ui.add_space(4.0);
+                }
+            });
+    }
+
+    /// Cosine similarity of every pair, the more similar the stronger the cell color.
+    fn show_similarity(ui: &mut egui::Ui, result: &Embedded) {
+        let fill = ui.visuals().selection.bg_fill;
+        egui::Grid::new("embedding_similarity")
+            .spacing([2.0, 2.0])
+            .show(ui, |ui| {
+                ui.label("");
+                for i in 0..result.items.len() {
+                    ui.strong(format!("{}", i + 1));
+                }
+                ui.end_row();
+                for (i, (item, a)) in result.items.iter().zip(&result.vectors).enumerate() {
+                    ui.label(format!("{} {}", i + 1, short_label(item)))
+                        .on_hover_text(item);
+                    for b in &result.vectors {
+                        let similarity = semantic::cosine(a, b);
+                        egui::Frame::NONE
+                            .fill(fill.gamma_multiply(similarity.clamp(0.0, 1.0)))
+                            .inner_margin(Margin::symmetric(4, 1))
+                            .show(ui, |ui| {
+                                ui.label(format_number(similarity as f64, 2));
+                            });
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+}
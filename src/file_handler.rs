@@ -1,262 +1,41 @@
-use anyhow::{anyhow, Result};
-use base64::Engine;
+use crate::i18n::tr;
 use eframe::egui::{self, vec2, Color32, RichText, Stroke};
-use gemini_rust::{prelude::*, Blob, FileState, Part};
-use image::{ImageFormat, ImageReader};
-use std::collections::HashMap;
-use std::io::Cursor;
-use std::path::{Path, PathBuf};
-use std::sync::{LazyLock, Mutex};
-use std::time::{Duration, Instant};
-
-static GLOBAL_FILE_CACHE: LazyLock<Mutex<HashMap<PathBuf, gemini_rust::File>>> =
-    LazyLock::new(|| Mutex::new(HashMap::new()));
-
-// Supported Gemini MIME types
-const GEMINI_MIME: &[&str] = &[
-    "image/png",
-    "image/jpeg",
-    "image/webp",
-    "image/heic",
-    "image/heif",
-    "video/mp4",
-    "video/mpeg",
-    "video/quicktime",
-    "video/avi",
-    "video/x-flv",
-    "video/mpg",
-    "video/webm",
-    "video/wmv",
-    "video/3gpp",
-    "audio/wav",
-    "audio/mp3",
-    "audio/aiff",
-    "audio/aac",
-    "audio/ogg",
-    "audio/flac",
-    "text/plain",
-    "text/html",
-    "text/css",
-    "text/javascript",
-    "text/typescript",
-    "application/x-javascript",
-    "application/json",
-    "text/xml",
-    "application/rtf",
-    "text/rtf",
-    "application/pdf",
-];
-
-#[derive(Debug, Default, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
-pub enum AttachmentState {
-    #[default]
-    Local,
-    Uploading,
-    Uploaded(gemini_rust::File),
-    Failed(String),
-}
-
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct Attachment {
-    pub path: PathBuf,
-    pub mime: String,
-    #[serde(skip)]
-    pub state: AttachmentState,
-}
-
-impl Attachment {
-    pub fn from_path(path: PathBuf) -> Self {
-        let mime = mime_guess::from_path(&path)
-            .first_or_octet_stream()
-            .to_string();
-        Self {
-            path,
-            mime,
-            state: AttachmentState::Local,
-        }
-    }
-}
-
-/// Returns either a Part with inline data or a FileHandle of the uploaded file
-pub enum FileResult {
-    /// Inline data part for direct use
-    InlinePart(Part),
-    /// Handle of the uploaded file for use in the API
-    UploadedFile(FileHandle),
+use std::path::PathBuf;
+use std::time::Duration;
+
+pub use geminid_core::files::*;
+
+/// The name of `resolution` in the pickers and on the attachments.
+pub fn resolution_label(resolution: MediaResolution) -> String {
+    tr!(match resolution {
+        MediaResolution::Auto => "media-resolution-auto",
+        MediaResolution::Low => "media-resolution-low",
+        MediaResolution::Medium => "media-resolution-medium",
+        MediaResolution::High => "media-resolution-high",
+    })
 }
 
-pub async fn convert_file_to_part(
-    client: &Gemini,
-    path: &Path,
-    upload: bool,
-) -> Result<FileResult> {
-    const MAX_INLINE_SIZE: u64 = 20 * 1024 * 1024; // 20 MB
-
-    // Check file size first
-    let metadata = tokio::fs::metadata(path).await?;
-    if metadata.len() > MAX_INLINE_SIZE && !upload {
-        return Err(anyhow!(
-            "File is too large for inline transmission ({} bytes > 20MB limit). Please enable 'File API' in settings.",
-            metadata.len()
-        ));
-    }
-
-    if upload {
-        if let Ok(cache) = GLOBAL_FILE_CACHE.lock() {
-            if let Some(remote_file) = cache.get(path) {
-                // Check expiration
-                let is_expired = if let Some(exp) = remote_file.expiration_time {
-                    exp < time::OffsetDateTime::now_utc()
-                } else {
-                    false
-                };
-
-                if !is_expired {
-                    log::info!("Global cache hit for {}", path.display());
-                    return Ok(FileResult::UploadedFile(
-                        client.file_from_model(remote_file.clone()),
-                    ));
-                } else {
-                    log::info!("Global cache expired for {}", path.display());
-                }
-            }
-        }
-    }
-
-    // Read file into bytes asynchronously
-    let file_bytes = tokio::fs::read(path).await?;
-
-    let mime_type = mime_guess::from_path(path).first_or_octet_stream();
-    let mut mime_str = mime_type.to_string();
-
-    if (mime_str.starts_with("application") && mime_str != "application/pdf")
-        || (mime_str.starts_with("text") && mime_str != "text/plain")
-    {
-        mime_str = "text/plain".to_string();
-    }
-
-    log::info!(
-        "Processing file: {}, MIME type: {}",
-        path.display(),
-        mime_str
-    );
-
-    // Convert non-PNG/JPEG images to PNG
-    let final_bytes = if mime_type.type_() == "image" {
-        match ImageReader::new(Cursor::new(&file_bytes))
-            .with_guessed_format()?
-            .format()
-        {
-            Some(format) if !matches!(format, ImageFormat::Png | ImageFormat::Jpeg) => {
-                log::debug!("Got {format:?} image, converting to png");
-                mime_str = "image/png".to_string();
-
-                let img = image::load_from_memory(&file_bytes)?;
-                let mut buf = Vec::new();
-                img.write_to(&mut Cursor::new(&mut buf), ImageFormat::Png)?;
-                buf
+pub fn resolution_picker(ui: &mut egui::Ui, id: impl std::hash::Hash, value: &mut MediaResolution) {
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(resolution_label(*value))
+        .show_ui(ui, |ui| {
+            for resolution in MediaResolution::ALL {
+                ui.selectable_value(value, resolution, resolution_label(resolution));
             }
-            _ => {
-                // Already PNG/JPEG or unknown image format, send as is
-                file_bytes
-            }
-        }
-    } else {
-        // Use source bytes for video, text, and other file types
-        file_bytes
-    };
-
-    // Check MIME type support
-    if !GEMINI_MIME.contains(&mime_str.as_str()) {
-        return Err(anyhow!(
-            "Unsupported MIME type: {}. Supported types are: {:?}",
-            mime_str,
-            GEMINI_MIME
-        ));
-    }
-
-    if upload {
-        log::info!("Uploading file...");
-
-        let file_handle = client
-            .create_file(final_bytes)
-            .display_name(
-                path.file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("uploaded_file"),
-            )
-            .with_mime_type(mime_str.parse()?)
-            .upload()
-            .await?;
-
-        log::info!(
-            "File uploaded: {}, waiting for processing...",
-            file_handle.name()
-        );
-
-        let start_time = Instant::now();
-        let timeout = Duration::from_secs(300);
-
-        loop {
-            let fresh_file_handle = client.get_file(file_handle.name()).await?;
-            match fresh_file_handle.get_file_meta().state.clone() {
-                Some(FileState::Active) => {
-                    log::info!("File {} is ACTIVE and ready.", file_handle.name());
-
-                    // Update cache
-                    if let Ok(mut cache) = GLOBAL_FILE_CACHE.lock() {
-                        cache.insert(
-                            path.to_path_buf(),
-                            fresh_file_handle.get_file_meta().clone(),
-                        );
-                    }
-
-                    break;
-                }
-                Some(FileState::Failed) => {
-                    return Err(anyhow!("File processing failed on Google server side."));
-                }
-                Some(FileState::Processing) | Some(FileState::StateUnspecified) | None => {
-                    if start_time.elapsed() > timeout {
-                        return Err(anyhow!("Timeout waiting for file to become ACTIVE"));
-                    }
-                    log::debug!("File still processing, waiting...");
-                    tokio::time::sleep(Duration::from_secs(2)).await;
-                }
-                _ => {
-                    if start_time.elapsed() > timeout {
-                        return Err(anyhow!("Timeout or unknown state"));
-                    }
-                    tokio::time::sleep(Duration::from_secs(2)).await;
-                }
-            }
-        }
-        // ---------------------------------------
-
-        // Return file handle only when it is ACTIVE
-        Ok(FileResult::UploadedFile(file_handle))
-    } else {
-        let base64 = base64::engine::general_purpose::STANDARD.encode(&final_bytes);
-        log::debug!(
-            "Converted file to {} bytes of base64 with mime type {}",
-            base64.len(),
-            mime_str
-        );
-
-        let blob = Blob::new(mime_str, base64);
-        let part = Part::InlineData {
-            inline_data: blob,
-            media_resolution: None,
-        };
+        });
+}
 
-        Ok(FileResult::InlinePart(part))
-    }
+fn format_megabytes(bytes: u64) -> String {
+    format!("{:.1}", bytes as f64 / 1024.0 / 1024.0)
 }
 
-pub fn show_files(ui: &mut egui::Ui, files: &mut Vec<Attachment>, mutate: bool) {
+/// Shows the attachments. Returns the path of the one to upload again, as its upload has
+/// expired.
+pub fn show_files(ui: &mut egui::Ui, files: &mut Vec<Attachment>, mutate: bool) -> Option<PathBuf> {
     const MAX_PREVIEW_HEIGHT: f32 = 128.0;
     let pointer_pos = ui.input(|i| i.pointer.interact_pos());
     let mut showing_x = false;
+    let mut reupload = None;
 
     files.retain_mut(|file| {
         let file_path = &mut file.path;
@@ -265,7 +44,7 @@ pub fn show_files(ui: &mut egui::Ui, files: &mut Vec<Attachment>, mutate: bool)
         // todo: Use cached mime?
         let mime_type = &file.mime;
 
-        let is_exist = file_path.exists();
+        let is_exist = file.youtube.is_some() || file_path.exists();
         let frame_color = match file.state {
             AttachmentState::Local => {
                 if is_exist {
@@ -274,7 +53,10 @@ pub fn show_files(ui: &mut egui::Ui, files: &mut Vec<Attachment>, mutate: bool)
                     egui::Color32::from_rgb(201, 178, 141)
                 }
             }
-            AttachmentState::Uploading => egui::Color32::from_rgb(141, 164, 201),
+            AttachmentState::Uploading { .. } => egui::Color32::from_rgb(141, 164, 201),
+            AttachmentState::Uploaded(ref remote) if is_expired(remote) => {
+                egui::Color32::from_rgb(201, 178, 141)
+            }
             AttachmentState::Uploaded(_) => egui::Color32::from_rgb(141, 189, 156),
             AttachmentState::Failed(_) => egui::Color32::from_rgb(201, 141, 141),
         };
@@ -282,6 +64,8 @@ pub fn show_files(ui: &mut egui::Ui, files: &mut Vec<Attachment>, mutate: bool)
         let custom_frame =
             egui::Frame::group(ui.style()).stroke(egui::Stroke::new(1.0, frame_color));
 
+        let mut cancel_rect = None;
+        let mut reupload_rect = None;
         let resp = custom_frame
             .show(ui, |ui| {
                 ui.vertical(|ui| {
@@ -304,7 +88,9 @@ pub fn show_files(ui: &mut egui::Ui, files: &mut Vec<Attachment>, mutate: bool)
 
                                 // Center the icon inside this frame
                                 ui.centered_and_justified(|ui| {
-                                    let icon = if !is_exist {
+                                    let icon = if file.youtube.is_some() {
+                                        "▶"
+                                    } else if !is_exist {
                                         "⚠"
                                     } else {
                                         match mime_type.split('/').next().unwrap_or("") {
@@ -321,20 +107,78 @@ pub fn show_files(ui: &mut egui::Ui, files: &mut Vec<Attachment>, mutate: bool)
                         }
                     }
 
-                    let mut text = file_path.file_name().unwrap_or_default().to_string_lossy();
+                    let mut text = match &file.youtube {
+                        Some(video) => video.label().into(),
+                        None => file_path.file_name().unwrap_or_default().to_string_lossy(),
+                    };
                     if !is_exist {
-                        text.to_mut().push_str(" (FILE NOT FOUND)");
+                        text.to_mut()
+                            .push_str(&format!(" {}", tr!("attachment-missing")));
                     }
                     ui.add(egui::Label::new(RichText::new(text).small()).truncate());
 
+                    if file.media_resolution != MediaResolution::Auto {
+                        ui.label(
+                            RichText::new(format!("◫ {}", resolution_label(file.media_resolution)))
+                                .small()
+                                .weak(),
+                        )
+                        .on_hover_text(tr!("media-resolution-attachment"));
+                    }
+
+                    if let Some(found) = &file.suspicious {
+                        ui.horizontal(|ui| {
+                            if crate::injection::badge(ui, found, file.quarantined) {
+                                file.quarantined = !file.quarantined;
+                            }
+                        });
+                    }
+
                     if let AttachmentState::Failed(err) = &file.state {
-                        ui.colored_label(Color32::RED, "Failed");
+                        ui.colored_label(Color32::RED, tr!("upload-failed"));
                         ui.label(RichText::new(err).small().color(Color32::RED));
-                    } else if matches!(file.state, AttachmentState::Uploading) {
+                    } else if let AttachmentState::Uploading { sent, total } = file.state {
                         ui.horizontal(|ui| {
-                            ui.spinner();
-                            ui.label("Uploading...");
+                            if total > 0 {
+                                let fraction = sent as f32 / total as f32;
+                                ui.add(
+                                    egui::ProgressBar::new(fraction)
+                                        .desired_width(MAX_PREVIEW_HEIGHT * 1.2 - 24.0)
+                                        .text(tr!(
+                                            "upload-progress",
+                                            sent = format_megabytes(sent),
+                                            total = format_megabytes(total)
+                                        )),
+                                );
+                            } else {
+                                ui.spinner();
+                                ui.label(tr!("upload-uploading"));
+                            }
+                            // the button goes on top of the tile's own click area later
+                            cancel_rect = Some(ui.allocate_space(vec2(18.0, 18.0)).1);
                         });
+                    } else if let AttachmentState::Uploaded(remote) = &file.state {
+                        if is_expired(remote) {
+                            ui.colored_label(
+                                ui.visuals().warn_fg_color,
+                                RichText::new(tr!("upload-expired")).small(),
+                            );
+                            if !mutate {
+                                reupload_rect =
+                                    Some(ui.allocate_space(vec2(MAX_PREVIEW_HEIGHT * 1.2, 18.0)).1);
+                            }
+                        } else if let Some(exp) = remote.expiration_time {
+                            let left = exp - time::OffsetDateTime::now_utc();
+                            let text = tr!(
+                                "upload-expires",
+                                hours = left.whole_hours(),
+                                minutes = left.whole_minutes() % 60
+                            );
+                            ui.label(RichText::new(format!("⏳ {text}")).small().weak())
+                                .on_hover_text(tr!("upload-expires-hint"));
+                            // the countdown is in minutes
+                            ui.ctx().request_repaint_after(Duration::from_secs(30));
+                        }
                     }
                 });
             })
@@ -343,6 +187,78 @@ pub fn show_files(ui: &mut egui::Ui, files: &mut Vec<Attachment>, mutate: bool)
         let interact_resp = ui
             .interact(resp.rect, resp.id.with("interact"), egui::Sense::click())
             .on_hover_text(&path_string);
+        interact_resp.widget_info(|| {
+            let name = match &file.youtube {
+                Some(video) => video.label(),
+                None => file_path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .into_owned(),
+            };
+            let label = match &file.state {
+                _ if !is_exist => tr!("attachment-info-missing", name = name),
+                AttachmentState::Uploading { sent, total } if *total > 0 => tr!(
+                    "attachment-info-uploading-percent",
+                    name = name,
+                    percent = sent * 100 / total
+                ),
+                AttachmentState::Uploading { .. } => tr!("attachment-info-uploading", name = name),
+                AttachmentState::Failed(err) => {
+                    tr!("attachment-info-failed", name = name, error = err.as_str())
+                }
+                AttachmentState::Uploaded(remote) if is_expired(remote) => {
+                    tr!("attachment-info-expired", name = name)
+                }
+                _ => name,
+            };
+            egui::WidgetInfo::labeled(egui::WidgetType::Button, mutate || is_exist, label)
+        });
+        if let Some(rect) = cancel_rect {
+            if file.cancel_upload.is_cancelled() {
+                ui.put(rect, egui::Spinner::new())
+                    .on_hover_text(tr!("upload-cancelling"));
+            } else if ui
+                .put(rect, egui::Button::new("✖").small())
+                .on_hover_text(tr!("upload-cancel"))
+                .clicked()
+            {
+                file.cancel_upload.cancel();
+            }
+        }
+        if let Some(rect) = reupload_rect {
+            if ui
+                .put(rect, egui::Button::new(tr!("upload-reupload")).small())
+                .on_hover_text(tr!("upload-reupload-hint"))
+                .clicked()
+            {
+                reupload = Some(file_path.clone());
+            }
+        }
+        if mutate {
+            crate::widgets::describe(&interact_resp, &tr!("attachment-remove-hint"));
+            if MediaResolution::applies_to(mime_type) {
+                interact_resp.context_menu(|ui| {
+                    if let Some(video) = &mut file.youtube {
+                        crate::youtube::show_clip(video, ui);
+                        ui.separator();
+                    }
+                    ui.label(tr!("media-resolution-attachment"));
+                    for resolution in MediaResolution::ALL {
+                        if ui
+                            .selectable_value(
+                                &mut file.media_resolution,
+                                resolution,
+                                resolution_label(resolution),
+                            )
+                            .clicked()
+                        {
+                            ui.close();
+                        }
+                    }
+                });
+            }
+        }
         if interact_resp.hovered() {
             ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
         }
@@ -354,45 +270,54 @@ pub fn show_files(ui: &mut egui::Ui, files: &mut Vec<Attachment>, mutate: bool)
             }
         }
 
-        if !mutate || showing_x {
+        if !mutate {
+            return true;
+        }
+        // the focused tile is removed with Delete, as the ❌ is only reachable with a pointer
+        if interact_resp.has_focus()
+            && ui.input(|i| i.key_pressed(egui::Key::Delete) || i.key_pressed(egui::Key::Backspace))
+        {
+            return false;
+        }
+        if showing_x {
             return true;
         }
 
-        if let Some(pos) = pointer_pos {
-            if resp.rect.expand(8.0).contains(pos) {
-                showing_x = true;
-
-                // render an ❌ in a red circle
-                let top = resp.rect.right_top();
-                let x_rect = egui::Rect::from_center_size(top, vec2(16.0, 16.0));
-                let contains_pointer = x_rect.contains(pos);
-
-                ui.painter()
-                    .circle_filled(top, 10.0, ui.visuals().window_fill);
-                ui.painter().circle_filled(
-                    top,
-                    8.0,
-                    if contains_pointer {
-                        ui.visuals().gray_out(ui.visuals().error_fg_color)
-                    } else {
-                        ui.visuals().error_fg_color
-                    },
-                );
-                ui.painter().line_segment(
-                    [top - vec2(3.0, 3.0), top + vec2(3.0, 3.0)],
-                    Stroke::new(2.0, Color32::WHITE),
-                );
-                ui.painter().line_segment(
-                    [top - vec2(3.0, -3.0), top + vec2(3.0, -3.0)],
-                    Stroke::new(2.0, Color32::WHITE),
-                );
-
-                if contains_pointer && ui.input(|i| i.pointer.primary_clicked()) {
-                    return false;
-                }
+        let near_pointer = pointer_pos.is_some_and(|pos| resp.rect.expand(8.0).contains(pos));
+        if near_pointer || interact_resp.has_focus() {
+            showing_x = true;
+
+            // render an ❌ in a red circle
+            let top = resp.rect.right_top();
+            let x_rect = egui::Rect::from_center_size(top, vec2(16.0, 16.0));
+            let contains_pointer = pointer_pos.is_some_and(|pos| x_rect.contains(pos));
+
+            ui.painter()
+                .circle_filled(top, 10.0, ui.visuals().window_fill);
+            ui.painter().circle_filled(
+                top,
+                8.0,
+                if contains_pointer {
+                    ui.visuals().gray_out(ui.visuals().error_fg_color)
+                } else {
+                    ui.visuals().error_fg_color
+                },
+            );
+            ui.painter().line_segment(
+                [top - vec2(3.0, 3.0), top + vec2(3.0, 3.0)],
+                Stroke::new(2.0, Color32::WHITE),
+            );
+            ui.painter().line_segment(
+                [top - vec2(3.0, -3.0), top + vec2(3.0, -3.0)],
+                Stroke::new(2.0, Color32::WHITE),
+            );
+
+            if contains_pointer && ui.input(|i| i.pointer.primary_clicked()) {
+                return false;
             }
         }
 
         true
     });
+    reupload
 }
@@ -0,0 +1,283 @@
+use crate::{chat::Message, i18n::tr};
+use eframe::egui::{self, Key};
+use egui_commonmark::CommonMarkCache;
+use std::{
+    cell::Cell,
+    hash::{DefaultHasher, Hash, Hasher},
+    ops::Range,
+};
+
+pub struct FindMatch {
+    pub message: usize,
+    pub range: Range<usize>,
+}
+
+/// Matches to highlight inside a single message.
+pub struct Highlights {
+    pub ranges: Vec<Range<usize>>,
+    /// Index into `ranges` of the currently selected match
+    pub current: Option<usize>,
+    /// The find bar just moved to the current match, scroll it into view
+    pub scroll: bool,
+    /// Set by [`show_markdown`] once it has scrolled to the current match
+    pub scrolled: Cell<bool>,
+}
+
+pub enum FindAction {
+    None,
+    Close,
+    Jump(usize),
+}
+
+/// In-chat find bar (Ctrl+F).
+#[derive(Default)]
+pub struct FindBar {
+    query: String,
+    case_sensitive: bool,
+    use_regex: bool,
+    matches: Vec<FindMatch>,
+    current: usize,
+    error: Option<String>,
+    stamp: u64,
+    request_focus: bool,
+    /// Set when moving to a match, until the message holding it has scrolled to it
+    scroll: bool,
+}
+
+impl FindBar {
+    pub fn new() -> Self {
+        Self {
+            request_focus: true,
+            ..Default::default()
+        }
+    }
+
+    fn stamp(&self, messages: &[Message]) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.query.hash(&mut hasher);
+        self.case_sensitive.hash(&mut hasher);
+        self.use_regex.hash(&mut hasher);
+        messages.len().hash(&mut hasher);
+        for msg in messages {
+            msg.is_marker.hash(&mut hasher);
+            msg.content.hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    fn update(&mut self, messages: &[Message]) {
+        let stamp = self.stamp(messages);
+        if stamp == self.stamp {
+            return;
+        }
+        self.stamp = stamp;
+        self.matches.clear();
+        self.error = None;
+
+        if self.query.is_empty() {
+            return;
+        }
+
+        let pattern = if self.use_regex {
+            self.query.clone()
+        } else {
+            regex::escape(&self.query)
+        };
+        let re = match regex::RegexBuilder::new(&pattern)
+            .case_insensitive(!self.case_sensitive)
+            .build()
+        {
+            Ok(re) => re,
+            Err(e) => {
+                self.error = Some(e.to_string());
+                return;
+            }
+        };
+
+        for (idx, msg) in messages.iter().enumerate() {
+            if msg.is_marker {
+                continue;
+            }
+            self.matches.extend(
+                re.find_iter(&msg.content)
+                    .filter(|m| !m.is_empty())
+                    .map(|m| FindMatch {
+                        message: idx,
+                        range: m.range(),
+                    }),
+            );
+        }
+        self.current = self.current.min(self.matches.len().saturating_sub(1));
+    }
+
+    pub fn highlights(&self, message: usize) -> Option<Highlights> {
+        let mut current = None;
+        let ranges: Vec<_> = self
+            .matches
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| m.message == message)
+            .enumerate()
+            .map(|(i, (global, m))| {
+                if global == self.current {
+                    current = Some(i);
+                }
+                m.range.clone()
+            })
+            .collect();
+        let scroll = self.scroll && current.is_some();
+        (!ranges.is_empty()).then_some(Highlights {
+            ranges,
+            current,
+            scroll,
+            scrolled: Cell::new(false),
+        })
+    }
+
+    /// Called once the current match has been scrolled into view.
+    #[inline]
+    pub fn scrolled(&mut self) {
+        self.scroll = false;
+    }
+
+    fn step(&mut self, forward: bool) -> FindAction {
+        if self.matches.is_empty() {
+            return FindAction::None;
+        }
+        let len = self.matches.len();
+        self.current = if forward {
+            (self.current + 1) % len
+        } else {
+            (self.current + len - 1) % len
+        };
+        self.scroll = true;
+        FindAction::Jump(self.matches[self.current].message)
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui, messages: &[Message]) -> FindAction {
+        let mut action = FindAction::None;
+        ui.horizontal(|ui| {
+            let resp = ui.add(
+                egui::TextEdit::singleline(&mut self.query)
+                    .hint_text(tr!("find-hint"))
+                    .desired_width(240.0),
+            );
+            if std::mem::take(&mut self.request_focus) {
+                resp.request_focus();
+            }
+
+            ui.toggle_value(&mut self.case_sensitive, "Aa")
+                .on_hover_text(tr!("find-match-case"));
+            ui.toggle_value(&mut self.use_regex, ".*")
+                .on_hover_text(tr!("find-regex"));
+
+            self.update(messages);
+            if resp.changed() && !self.matches.is_empty() {
+                self.current = 0;
+                self.scroll = true;
+                action = FindAction::Jump(self.matches[0].message);
+            }
+
+            if let Some(err) = &self.error {
+                ui.colored_label(ui.visuals().error_fg_color, tr!("find-invalid-pattern"))
+                    .on_hover_text(err);
+            } else if self.matches.is_empty() {
+                if !self.query.is_empty() {
+                    ui.weak(tr!("no-matches"));
+                }
+            } else {
+                ui.weak(tr!(
+                    "find-position",
+                    current = self.current + 1,
+                    total = self.matches.len(),
+                ));
+            }
+
+            if ui.button("⏶").on_hover_text(tr!("find-previous")).clicked() {
+                action = self.step(false);
+            }
+            if ui.button("⏷").on_hover_text(tr!("find-next")).clicked() {
+                action = self.step(true);
+            }
+
+            if resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)) {
+                let forward = !ui.input(|i| i.modifiers.shift);
+                action = self.step(forward);
+                resp.request_focus();
+            }
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button("❌").on_hover_text(tr!("find-close")).clicked()
+                    || ui.input(|i| i.key_pressed(Key::Escape))
+                {
+                    action = FindAction::Close;
+                }
+            });
+        });
+        action
+    }
+}
+
+/// Splits markdown at blank lines outside code fences, so each part can be rendered and
+/// tinted on its own.
+fn paragraphs(content: &str) -> Vec<Range<usize>> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut offset = 0;
+    let mut in_fence = false;
+    for line in content.split_inclusive('\n') {
+        if matches!(line.trim_start().get(..3), Some("```" | "~~~")) {
+            in_fence = !in_fence;
+        }
+        offset += line.len();
+        if !in_fence && line.trim().is_empty() {
+            if !content[start..offset].trim().is_empty() {
+                parts.push(start..offset);
+            }
+            start = offset;
+        }
+    }
+    if !content[start..].trim().is_empty() {
+        parts.push(start..content.len());
+    }
+    parts
+}
+
+/// Renders message markdown with the paragraphs holding find matches tinted, the one with
+/// the current match stronger. Scrolls to that paragraph when `highlights.scroll` is set.
+pub fn show_markdown(
+    ui: &mut egui::Ui,
+    cache: &mut CommonMarkCache,
+    content: &str,
+    highlights: &Highlights,
+    max_image_width: Option<usize>,
+) {
+    let match_bg = ui.visuals().selection.bg_fill.gamma_multiply(0.25);
+    let current_bg = ui.visuals().selection.bg_fill.gamma_multiply(0.5);
+    let current = highlights.current.and_then(|i| highlights.ranges.get(i));
+    ui.vertical(|ui| {
+        for part in paragraphs(content) {
+            let hits = |range: &Range<usize>| range.start < part.end && part.start < range.end;
+            let background = ui.painter().add(egui::Shape::Noop);
+            let rect = ui
+                .scope(|ui| {
+                    crate::bidi::show_markdown(ui, cache, &content[part.clone()], max_image_width);
+                })
+                .response
+                .rect;
+
+            let is_current = current.is_some_and(hits);
+            if is_current || highlights.ranges.iter().any(hits) {
+                let fill = if is_current { current_bg } else { match_bg };
+                ui.painter().set(
+                    background,
+                    egui::Shape::rect_filled(rect.expand(2.0), 4.0, fill),
+                );
+            }
+            if is_current && highlights.scroll {
+                ui.scroll_to_rect(rect, Some(egui::Align::Center));
+                highlights.scrolled.set(true);
+            }
+        }
+    });
+}
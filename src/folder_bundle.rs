@@ -0,0 +1,470 @@
+use crate::{
+    i18n::tr,
+    widgets::{GeminiModel, Settings},
+};
+use anyhow::{anyhow, Result};
+use eframe::egui;
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+use tokio::sync::oneshot::{self, error::TryRecvError};
+
+/// Bytes looked at to tell text files from binary ones.
+const SNIFF_LEN: usize = 8 * 1024;
+
+fn bundles_dir() -> Option<PathBuf> {
+    eframe::storage_dir(crate::TITLE).map(|dir| dir.join("bundles"))
+}
+
+/// Which files of a folder go into its bundle.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct FolderFilter {
+    /// Globs of the files to take, one per line, every file when empty
+    pub include: String,
+    /// Globs of the files to leave out, one per line
+    pub exclude: String,
+    /// Larger files are left out, in KB
+    pub max_file_size: u32,
+    /// Leaves out what `.gitignore` and `.ignore` files do
+    pub gitignore: bool,
+}
+
+impl Default for FolderFilter {
+    fn default() -> Self {
+        Self {
+            include: String::new(),
+            exclude: "*.lock\n*.min.js\n*.svg".to_owned(),
+            max_file_size: 256,
+            gitignore: true,
+        }
+    }
+}
+
+fn globs(text: &str) -> impl Iterator<Item = &str> {
+    text.split(['\n', ','])
+        .map(str::trim)
+        .filter(|glob| !glob.is_empty())
+}
+
+impl FolderFilter {
+    fn overrides(&self, root: &Path) -> Result<ignore::overrides::Override> {
+        let mut builder = ignore::overrides::OverrideBuilder::new(root);
+        for glob in globs(&self.include) {
+            builder.add(glob)?;
+        }
+        for glob in globs(&self.exclude) {
+            builder.add(&format!("!{glob}"))?;
+        }
+        Ok(builder.build()?)
+    }
+
+    fn show(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("folder_filter")
+            .num_columns(2)
+            .spacing([8.0, 6.0])
+            .show(ui, |ui| {
+                ui.label(tr!("bundle-include"))
+                    .on_hover_text(tr!("bundle-globs-help"));
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.include)
+                        .hint_text("src/**/*.rs\n*.md")
+                        .desired_rows(2)
+                        .desired_width(f32::INFINITY),
+                );
+                ui.end_row();
+                ui.label(tr!("bundle-exclude"))
+                    .on_hover_text(tr!("bundle-globs-help"));
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.exclude)
+                        .hint_text("target/**\n*.lock")
+                        .desired_rows(2)
+                        .desired_width(f32::INFINITY),
+                );
+                ui.end_row();
+                ui.label(tr!("bundle-max-size"));
+                ui.add(
+                    egui::DragValue::new(&mut self.max_file_size)
+                        .range(1..=10_240)
+                        .suffix(" KB"),
+                );
+                ui.end_row();
+            });
+        ui.checkbox(&mut self.gitignore, tr!("bundle-gitignore"));
+    }
+}
+
+/// A file that made it into the bundle.
+struct BundledFile {
+    /// Relative to the folder, with `/` between the parts
+    path: String,
+    size: u64,
+}
+
+/// The text files of a folder, put together into one text.
+struct Bundle {
+    files: Vec<BundledFile>,
+    text: String,
+    too_large: usize,
+    binary: usize,
+    /// `None` when they couldn't be counted
+    tokens: Option<u32>,
+}
+
+/// The content of `path` if it's text.
+fn read_text(path: &Path) -> Result<Option<String>> {
+    let mut bytes = Vec::new();
+    std::fs::File::open(path)?.read_to_end(&mut bytes)?;
+    if bytes[..bytes.len().min(SNIFF_LEN)].contains(&0) {
+        return Ok(None);
+    }
+    Ok(String::from_utf8(bytes).ok())
+}
+
+/// Walks `root` and bundles the text files `filter` lets through.
+fn collect(root: &Path, filter: &FolderFilter) -> Result<Bundle> {
+    let walker = ignore::WalkBuilder::new(root)
+        .git_ignore(filter.gitignore)
+        .git_global(filter.gitignore)
+        .git_exclude(filter.gitignore)
+        .ignore(filter.gitignore)
+        .overrides(filter.overrides(root)?)
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .build();
+    let max_size = filter.max_file_size as u64 * 1024;
+    let name = root
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut bundle = Bundle {
+        files: Vec::new(),
+        text: format!("<folder name=\"{name}\">\n"),
+        too_large: 0,
+        binary: 0,
+        tokens: None,
+    };
+    for entry in walker {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                log::debug!("skipping part of {}: {e}", root.display());
+                continue;
+            }
+        };
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        let size = entry.metadata().map(|m| m.len()).unwrap_or_default();
+        if size > max_size {
+            bundle.too_large += 1;
+            continue;
+        }
+        let content = match read_text(entry.path()) {
+            Ok(Some(content)) => content,
+            Ok(None) => {
+                bundle.binary += 1;
+                continue;
+            }
+            Err(e) => {
+                log::warn!("failed to read {}: {e}", entry.path().display());
+                continue;
+            }
+        };
+        let path = entry
+            .path()
+            .strip_prefix(root)
+            .unwrap_or(entry.path())
+            .components()
+            .map(|part| part.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        bundle.text.push_str(&format!("<file path=\"{path}\">\n"));
+        bundle.text.push_str(&content);
+        if !content.ends_with('\n') {
+            bundle.text.push('\n');
+        }
+        bundle.text.push_str("</file>\n");
+        bundle.files.push(BundledFile { path, size });
+    }
+    bundle.text.push_str("</folder>\n");
+    Ok(bundle)
+}
+
+/// What the bundle costs with `model`.
+async fn count_tokens(settings: &Settings, model: GeminiModel, text: &str) -> Result<u32> {
+    let client =
+        crate::widgets::build_client(&settings.api_key, settings.proxy_path.clone(), model.into())?;
+    let mut builder = client.generate_content();
+    builder.contents.push(gemini_rust::Content::text(text));
+    Ok(builder.count_tokens().await?.total_tokens)
+}
+
+async fn scan(
+    settings: Settings,
+    model: GeminiModel,
+    root: PathBuf,
+    filter: FolderFilter,
+) -> Result<Bundle> {
+    log::info!("bundling {}", root.display());
+    let mut bundle = tokio::task::spawn_blocking(move || collect(&root, &filter)).await??;
+    if bundle.files.is_empty() {
+        return Err(anyhow!("No text file matches the filter."));
+    }
+    bundle.tokens = count_tokens(&settings, model, &bundle.text)
+        .await
+        .map_err(|e| log::warn!("failed to count the tokens of the bundle: {e}"))
+        .ok();
+    Ok(bundle)
+}
+
+/// Writes the bundle where the chats can keep referring to it.
+fn save(root: &Path, text: &str) -> Result<PathBuf> {
+    let dir = bundles_dir().ok_or_else(|| anyhow!("no data folder"))?;
+    std::fs::create_dir_all(&dir)?;
+    let name = root
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "folder".to_owned());
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let path = dir.join(format!("{name}-{stamp}.txt"));
+    std::fs::write(&path, text)?;
+    log::info!(
+        "saved the bundle of {} to {}",
+        root.display(),
+        path.display()
+    );
+    Ok(path)
+}
+
+fn format_size(bytes: u64) -> String {
+    if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", bytes as f64 / 1024.0 / 1024.0)
+    }
+}
+
+/// Attaches the text files of a folder as a single text, filtered by globs.
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct FolderBundler {
+    pub filter: FolderFilter,
+    /// The chat the bundle goes to, and the model its tokens are counted with
+    #[serde(skip)]
+    target: Option<(usize, GeminiModel)>,
+    #[serde(skip)]
+    root: Option<PathBuf>,
+    /// A folder dialog was asked for, opened on the next frame
+    #[serde(skip)]
+    requested: bool,
+    #[serde(skip)]
+    picking: Option<oneshot::Receiver<PathBuf>>,
+    #[serde(skip)]
+    scanning: Option<oneshot::Receiver<Result<Bundle, String>>>,
+    #[serde(skip)]
+    bundle: Option<Bundle>,
+    /// The filter the bundle was made with, to tell when it's out of date
+    #[serde(skip)]
+    scanned_with: Option<FolderFilter>,
+    #[serde(skip)]
+    error: Option<String>,
+}
+
+impl FolderBundler {
+    /// Asks for a folder to attach to `chat_id`.
+    pub fn start(&mut self, chat_id: usize, model: GeminiModel) {
+        self.target = Some((chat_id, model));
+        self.requested = true;
+    }
+
+    fn pick(&mut self, ctx: &egui::Context) {
+        self.requested = false;
+        let (tx, rx) = oneshot::channel();
+        self.picking = Some(rx);
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            if let Some(folder) = rfd::AsyncFileDialog::new().pick_folder().await {
+                let _ = tx.send(folder.path().to_owned());
+            }
+            ctx.request_repaint();
+        });
+    }
+
+    fn scan(&mut self, ctx: &egui::Context, settings: &Settings) {
+        let (Some(root), Some((_, model))) = (self.root.clone(), self.target) else {
+            return;
+        };
+        let (tx, rx) = oneshot::channel();
+        self.scanning = Some(rx);
+        self.error = None;
+        self.scanned_with = Some(self.filter.clone());
+        let settings = settings.clone();
+        let filter = self.filter.clone();
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            let result = scan(settings, model, root, filter).await.map_err(|e| {
+                log::error!("failed to bundle the folder: {e}");
+                e.to_string()
+            });
+            let _ = tx.send(result);
+            ctx.request_repaint();
+        });
+    }
+
+    fn poll(&mut self, ctx: &egui::Context, settings: &Settings) {
+        if let Some(rx) = &mut self.picking {
+            match rx.try_recv() {
+                Ok(root) => {
+                    self.picking = None;
+                    self.root = Some(root);
+                    self.bundle = None;
+                    self.scan(ctx, settings);
+                }
+                // no folder was picked
+                Err(TryRecvError::Closed) => {
+                    self.picking = None;
+                    if self.root.is_none() {
+                        self.target = None;
+                    }
+                }
+                Err(TryRecvError::Empty) => (),
+            }
+        }
+        if let Some(rx) = &mut self.scanning {
+            if let Ok(result) = rx.try_recv() {
+                self.scanning = None;
+                match result {
+                    Ok(bundle) => self.bundle = Some(bundle),
+                    Err(e) => {
+                        self.bundle = None;
+                        self.error = Some(e);
+                    }
+                }
+            }
+        }
+    }
+
+    fn close(&mut self) {
+        self.target = None;
+        self.root = None;
+        self.bundle = None;
+        self.scanning = None;
+        self.error = None;
+    }
+
+    /// Shows the filter of the picked folder and what it lets through. Returns the chat
+    /// and the saved bundle once the user attaches it.
+    pub fn show(&mut self, ctx: &egui::Context, settings: &Settings) -> Option<(usize, PathBuf)> {
+        if self.requested {
+            self.pick(ctx);
+        }
+        self.poll(ctx, settings);
+        let root = self.root.clone()?;
+        let mut open = true;
+        let mut attached = None;
+        egui::Window::new(tr!("bundle-title"))
+            .open(&mut open)
+            .default_size([520.0, 480.0])
+            .show(ctx, |ui| attached = self.show_inner(ui, settings, &root));
+        if !open {
+            self.close();
+        }
+        let chat_id = self.target.map(|(chat_id, _)| chat_id);
+        let attached = attached.zip(chat_id).map(|(path, chat_id)| (chat_id, path));
+        if attached.is_some() {
+            self.close();
+        }
+        attached
+    }
+
+    fn show_inner(
+        &mut self,
+        ui: &mut egui::Ui,
+        settings: &Settings,
+        root: &Path,
+    ) -> Option<PathBuf> {
+        let scanning = self.scanning.is_some();
+        ui.horizontal(|ui| {
+            ui.label(format!("📁 {}", root.display()));
+            if ui
+                .add_enabled(
+                    !scanning && self.picking.is_none(),
+                    egui::Button::new(tr!("bundle-change")),
+                )
+                .clicked()
+            {
+                self.pick(ui.ctx());
+            }
+        });
+        ui.separator();
+        self.filter.show(ui);
+
+        let stale = self.scanned_with.as_ref() != Some(&self.filter);
+        ui.horizontal(|ui| {
+            if scanning {
+                ui.spinner();
+                ui.label(tr!("bundle-scanning"));
+            } else if ui
+                .add_enabled(stale, egui::Button::new(tr!("bundle-rescan")))
+                .clicked()
+            {
+                self.scan(ui.ctx(), settings);
+            }
+        });
+        if let Some(error) = &self.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+        let bundle = self.bundle.as_ref()?;
+
+        ui.separator();
+        let size: u64 = bundle.files.iter().map(|f| f.size).sum();
+        ui.label(tr!(
+            "bundle-summary",
+            files = bundle.files.len(),
+            size = format_size(size)
+        ));
+        match bundle.tokens {
+            Some(tokens) => ui.label(tr!("bundle-tokens", tokens = tokens)),
+            None => ui.weak(tr!("bundle-tokens-unknown")),
+        };
+        if bundle.too_large + bundle.binary > 0 {
+            ui.weak(tr!(
+                "bundle-skipped",
+                large = bundle.too_large,
+                binary = bundle.binary
+            ));
+        }
+        egui::CollapsingHeader::new(tr!("bundle-files"))
+            .id_salt("folder_files")
+            .show(ui, |ui| {
+                egui::ScrollArea::vertical()
+                    .max_height(200.0)
+                    .show(ui, |ui| {
+                        for file in &bundle.files {
+                            ui.horizontal(|ui| {
+                                ui.label(&file.path);
+                                ui.weak(format_size(file.size));
+                            });
+                        }
+                    });
+            });
+
+        ui.add_space(4.0);
+        let mut attached = None;
+        if ui
+            .add_enabled(!scanning && !stale, egui::Button::new(tr!("bundle-attach")))
+            .on_disabled_hover_text(tr!("bundle-attach-stale"))
+            .clicked()
+        {
+            match save(root, &bundle.text) {
+                Ok(path) => attached = Some(path),
+                Err(e) => {
+                    log::error!("failed to save the bundle: {e}");
+                    self.error = Some(e.to_string());
+                }
+            }
+        }
+        attached
+    }
+}
@@ -0,0 +1,222 @@
+use crate::{i18n::tr, knowledge::Collection, prompt_library::PromptLibrary, widgets::ModelPicker};
+use eframe::egui::{self, Color32, Layout};
+
+/// Something being dragged around the chat sidebar.
+#[derive(Clone, Copy)]
+pub enum DragItem {
+    Chat(usize),
+    Folder(u64),
+}
+
+/// What the chats inside a project folder share, applied on every request.
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Project {
+    /// Put in front of the system prompt of every chat inside
+    pub instructions: String,
+    /// Knowledge collection of the chats that don't pick their own
+    pub knowledge: Option<u64>,
+}
+
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Folder {
+    pub id: u64,
+    pub name: String,
+    /// Containing folder, `None` for the top level
+    pub parent: Option<u64>,
+    pub collapsed: bool,
+    /// Model settings new chats in this folder start with
+    pub template: Option<ModelPicker>,
+    /// Makes the folder a project, `None` for a plain folder
+    pub project: Option<Project>,
+}
+
+impl Default for Folder {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            name: tr!("folder-new-name"),
+            parent: None,
+            collapsed: false,
+            template: None,
+            project: None,
+        }
+    }
+}
+
+#[inline]
+pub fn find(folders: &[Folder], id: Option<u64>) -> Option<&Folder> {
+    id.and_then(|id| folders.iter().find(|f| f.id == id))
+}
+
+pub fn add(folders: &mut Vec<Folder>, parent: Option<u64>) -> u64 {
+    let id = folders.iter().map(|f| f.id).max().unwrap_or(0) + 1;
+    folders.push(Folder {
+        id,
+        parent,
+        ..Default::default()
+    });
+    id
+}
+
+/// Removes a folder and lifts its subfolders one level up.
+/// Returns the folder its chats should be moved to.
+pub fn remove(folders: &mut Vec<Folder>, id: u64) -> Option<u64> {
+    let parent = find(folders, Some(id)).and_then(|f| f.parent);
+    folders.retain(|f| f.id != id);
+    for folder in folders.iter_mut().filter(|f| f.parent == Some(id)) {
+        folder.parent = parent;
+    }
+    parent
+}
+
+/// Returns whether `id` is `ancestor` itself or nested somewhere inside it.
+pub fn is_within(folders: &[Folder], id: u64, ancestor: u64) -> bool {
+    let mut current = Some(id);
+    // bounded, a hand-edited save could contain a cycle
+    for _ in 0..=folders.len() {
+        match current {
+            Some(c) if c == ancestor => return true,
+            Some(c) => current = find(folders, Some(c)).and_then(|f| f.parent),
+            None => return false,
+        }
+    }
+    false
+}
+
+/// Folders shown at the top level: those without a parent, and, so that none of them vanish,
+/// those whose parent is gone or that sit in a cycle a hand-edited save could contain.
+pub fn top_level(folders: &[Folder]) -> Vec<u64> {
+    folders
+        .iter()
+        .filter(|f| match f.parent {
+            None => true,
+            Some(parent) => {
+                find(folders, Some(parent)).is_none() || is_within(folders, parent, f.id)
+            }
+        })
+        .map(|f| f.id)
+        .collect()
+}
+
+/// Template of the folder or of its closest ancestor that has one.
+pub fn template(folders: &[Folder], id: Option<u64>) -> Option<&ModelPicker> {
+    let mut current = find(folders, id);
+    for _ in 0..=folders.len() {
+        let folder = current?;
+        if let Some(template) = &folder.template {
+            return Some(template);
+        }
+        current = find(folders, folder.parent);
+    }
+    None
+}
+
+/// The project of the folder or of its closest ancestor that is one.
+pub fn project(folders: &[Folder], id: Option<u64>) -> Option<&Project> {
+    let mut current = find(folders, id);
+    for _ in 0..=folders.len() {
+        let folder = current?;
+        if let Some(project) = &folder.project {
+            return Some(project);
+        }
+        current = find(folders, folder.parent);
+    }
+    None
+}
+
+/// Folder ids in display order, paired with their nesting depth.
+pub fn flatten(folders: &[Folder]) -> Vec<(usize, u64)> {
+    fn walk(folders: &[Folder], parent: Option<u64>, depth: usize, out: &mut Vec<(usize, u64)>) {
+        for folder in folders.iter().filter(|f| f.parent == parent) {
+            if out.iter().any(|&(_, id)| id == folder.id) {
+                continue;
+            }
+            out.push((depth, folder.id));
+            walk(folders, Some(folder.id), depth + 1, out);
+        }
+    }
+
+    let mut out = Vec::with_capacity(folders.len());
+    for id in top_level(folders) {
+        if out.iter().any(|&(_, seen)| seen == id) {
+            continue;
+        }
+        out.push((0, id));
+        walk(folders, Some(id), 1, &mut out);
+    }
+    out
+}
+
+/// Rename and template editor. Returns `true` when the editor should be closed.
+pub fn show_editor(
+    ui: &mut egui::Ui,
+    folder: &mut Folder,
+    default_picker: &ModelPicker,
+    collections: &[Collection],
+    library: &mut PromptLibrary,
+) -> bool {
+    let mut close = false;
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::TextEdit::singleline(&mut folder.name)
+                .hint_text(tr!("folder-name-hint"))
+                .desired_width(f32::INFINITY),
+        );
+
+        ui.with_layout(Layout::right_to_left(egui::Align::Min), |ui| {
+            if ui
+                .add(
+                    egui::Button::new("❌")
+                        .fill(Color32::TRANSPARENT)
+                        .frame(false),
+                )
+                .on_hover_text(tr!("close"))
+                .clicked()
+            {
+                close = true;
+            }
+        });
+    });
+
+    egui::CollapsingHeader::new(tr!("project-title"))
+        .default_open(true)
+        .show(ui, |ui| {
+            ui.label(tr!("project-help"));
+            let mut enabled = folder.project.is_some();
+            if ui.checkbox(&mut enabled, tr!("project-enable")).changed() {
+                folder.project = enabled.then(Project::default);
+            }
+            if let Some(project) = &mut folder.project {
+                ui.add(
+                    egui::TextEdit::multiline(&mut project.instructions)
+                        .hint_text(tr!("project-instructions-hint"))
+                        .desired_rows(4)
+                        .desired_width(f32::INFINITY),
+                );
+                ui.horizontal(|ui| {
+                    ui.label(tr!("project-knowledge"));
+                    crate::knowledge::picker(ui, collections, &mut project.knowledge);
+                });
+            }
+        });
+
+    egui::CollapsingHeader::new(tr!("folder-template"))
+        .default_open(true)
+        .show(ui, |ui| {
+            ui.label(tr!("folder-template-help"));
+            let mut enabled = folder.template.is_some();
+            if ui
+                .checkbox(&mut enabled, tr!("folder-template-enable"))
+                .changed()
+            {
+                folder.template = enabled.then(|| default_picker.clone());
+            }
+            if let Some(template) = &mut folder.template {
+                template.show(ui, library, &mut |_| {});
+            }
+        });
+
+    close
+}
@@ -0,0 +1,444 @@
+use crate::i18n::tr;
+use eframe::egui::{self, RichText};
+use gemini_rust::{Part, Tool};
+use serde_json::{json, Value};
+use std::{process::Stdio, time::Duration};
+use tokio::{io::AsyncWriteExt, process::Command};
+
+/// How long a local command may take to answer a call.
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Output of a command past this many bytes is cut, it all goes to the model.
+const MAX_OUTPUT: usize = 32 * 1024;
+
+/// Shown in the parameters editor of a new function.
+const EXAMPLE_SCHEMA: &str = r#"{
+  "type": "object",
+  "properties": {
+    "location": {
+      "type": "string",
+      "description": "City name, e.g. Berlin"
+    }
+  },
+  "required": ["location"]
+}"#;
+
+/// A function the model may ask to call, declared by the user.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct FunctionDecl {
+    pub name: String,
+    pub description: String,
+    /// JSON schema of the arguments, as typed
+    pub parameters: String,
+    /// Sent with every request
+    pub enabled: bool,
+    /// Shell command that answers the calls once confirmed, empty to answer by hand
+    pub command: String,
+}
+
+impl Default for FunctionDecl {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            description: String::new(),
+            parameters: EXAMPLE_SCHEMA.to_owned(),
+            enabled: true,
+            command: String::new(),
+        }
+    }
+}
+
+impl FunctionDecl {
+    /// The declaration as the API takes it, or what is wrong with it.
+    pub fn declaration(&self) -> Result<Value, String> {
+        let valid_name = self.name.len() <= 64
+            && self
+                .name
+                .starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && self
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'));
+        if !valid_name {
+            return Err(tr!("functions-invalid-name"));
+        }
+        let mut declaration = json!({
+            "name": self.name,
+            "description": self.description,
+        });
+        if !self.parameters.trim().is_empty() {
+            let schema: Value = serde_json::from_str(&self.parameters)
+                .map_err(|e| tr!("functions-invalid-schema", error = e.to_string()))?;
+            if schema.get("type").and_then(Value::as_str) != Some("object") {
+                return Err(tr!("functions-schema-not-object"));
+            }
+            declaration["parameters"] = schema;
+        }
+        Ok(declaration)
+    }
+}
+
+/// The enabled functions as a request tool, `None` if there are none.
+pub fn to_tool(functions: &[FunctionDecl]) -> Option<Tool> {
+    let declarations: Vec<Value> = functions
+        .iter()
+        .filter(|f| f.enabled)
+        .filter_map(|f| {
+            f.declaration()
+                .map_err(|e| log::warn!("skipping function `{}`: {e}", f.name))
+                .ok()
+        })
+        .collect();
+    if declarations.is_empty() {
+        return None;
+    }
+    serde_json::from_value(json!({ "functionDeclarations": declarations }))
+        .map_err(|e| log::error!("failed to declare the functions: {e}"))
+        .ok()
+}
+
+/// A function call the model asked for, and what the user answered.
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Call {
+    pub id: Option<String>,
+    pub name: String,
+    pub args: Value,
+    /// Has to go back with the call when the model was thinking
+    pub signature: Option<String>,
+    pub response: Option<String>,
+    #[serde(skip)]
+    pub draft: String,
+    /// The local command is answering it
+    #[serde(skip)]
+    pub running: bool,
+}
+
+impl Call {
+    /// The call in `part`, if it is one. Read from the JSON form, which is what
+    /// goes over the wire.
+    pub fn from_part(part: &Part) -> Option<Self> {
+        let json = serde_json::to_value(part).ok()?;
+        let call = json.get("functionCall")?;
+        Some(Self {
+            id: call.get("id").and_then(Value::as_str).map(str::to_owned),
+            name: call.get("name")?.as_str()?.to_owned(),
+            args: call.get("args").cloned().unwrap_or_else(|| json!({})),
+            signature: json
+                .get("thoughtSignature")
+                .and_then(Value::as_str)
+                .map(str::to_owned),
+            response: None,
+            draft: String::new(),
+            running: false,
+        })
+    }
+
+    /// The call as the model made it, for the history.
+    pub fn call_part(&self) -> Option<Part> {
+        let mut call = json!({ "name": self.name, "args": self.args });
+        if let Some(id) = &self.id {
+            call["id"] = json!(id);
+        }
+        let mut part = json!({ "functionCall": call });
+        if let Some(signature) = &self.signature {
+            part["thoughtSignature"] = json!(signature);
+        }
+        serde_json::from_value(part)
+            .map_err(|e| log::error!("failed to replay the call of `{}`: {e}", self.name))
+            .ok()
+    }
+
+    /// The user's answer. A JSON object goes as it is, anything else is wrapped.
+    pub fn response_part(&self) -> Option<Part> {
+        let text = self.response.as_ref()?;
+        let response = match serde_json::from_str::<Value>(text) {
+            Ok(value @ Value::Object(_)) => value,
+            _ => json!({ "result": text }),
+        };
+        let mut function_response = json!({ "name": self.name, "response": response });
+        if let Some(id) = &self.id {
+            function_response["id"] = json!(id);
+        }
+        serde_json::from_value(json!({ "functionResponse": function_response }))
+            .map_err(|e| log::error!("failed to send the response of `{}`: {e}", self.name))
+            .ok()
+    }
+}
+
+/// The command that answers calls of `name`, if one was set.
+pub fn command_for<'a>(functions: &'a [FunctionDecl], name: &str) -> Option<&'a str> {
+    functions
+        .iter()
+        .find(|f| f.name == name)
+        .map(|f| f.command.trim())
+        .filter(|c| !c.is_empty())
+}
+
+/// Runs `command` in the shell to answer a call of `name`. The arguments come as
+/// JSON on stdin and in `GEMINID_ARGS`, what it prints is the response.
+pub async fn run_command(command: String, name: String, args: Value) -> anyhow::Result<String> {
+    log::info!("running `{command}` for a call of `{name}`");
+    let args = args.to_string();
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    let mut child = shell
+        .arg(&command)
+        .env("GEMINID_FUNCTION", &name)
+        .env("GEMINID_ARGS", &args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // a command that doesn't read its input closes the pipe early
+        let _ = stdin.write_all(args.as_bytes()).await;
+    }
+    let output = tokio::time::timeout(COMMAND_TIMEOUT, child.wait_with_output())
+        .await
+        .map_err(|_| {
+            anyhow::anyhow!(tr!(
+                "functions-command-timeout",
+                seconds = COMMAND_TIMEOUT.as_secs()
+            ))
+        })??;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("{}: {}", output.status, stderr.trim());
+    }
+    let mut stdout = String::from_utf8_lossy(&output.stdout)
+        .trim_end()
+        .to_owned();
+    if stdout.len() > MAX_OUTPUT {
+        let mut end = MAX_OUTPUT;
+        while !stdout.is_char_boundary(end) {
+            end -= 1;
+        }
+        stdout.truncate(end);
+        stdout.push_str("\n[output truncated]");
+    }
+    Ok(stdout)
+}
+
+/// What the user did with the calls of a message.
+pub enum CallAction {
+    None,
+    /// Sent the typed answers
+    Send,
+    /// Asked to run the command of the call at this index
+    Run(usize),
+}
+
+/// Shows the calls as cards, with fields for the unanswered ones. `commands` are the
+/// declared functions whose commands may run, `None` when local commands are off. Calls
+/// of plugin tools can always run, the plugins are sandboxed.
+pub fn show_calls(
+    ui: &mut egui::Ui,
+    calls: &mut [Call],
+    idx: usize,
+    commands: Option<&[FunctionDecl]>,
+) -> CallAction {
+    let mut action = CallAction::None;
+    for (i, call) in calls.iter_mut().enumerate() {
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("🔧");
+                ui.strong(&call.name);
+                if call.response.is_some() {
+                    ui.weak(tr!("functions-answered"));
+                }
+            });
+            let args = serde_json::to_string_pretty(&call.args).unwrap_or_default();
+            ui.label(RichText::new(args).monospace().small());
+            match &call.response {
+                Some(response) => {
+                    ui.label(RichText::new(format!("↩ {response}")).monospace().small());
+                }
+                None if call.running => {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.weak(tr!("functions-running"));
+                    });
+                }
+                None => {
+                    ui.add(
+                        egui::TextEdit::multiline(&mut call.draft)
+                            .id_salt(("function_response", idx, i))
+                            .hint_text(tr!("functions-response-hint"))
+                            .code_editor()
+                            .desired_rows(2),
+                    );
+                    let command = commands.and_then(|f| command_for(f, &call.name));
+                    let hint = match command {
+                        Some(command) => Some(RichText::new(command).monospace()),
+                        None => crate::plugins::tool_plugin(&call.name).map(|plugin| {
+                            RichText::new(tr!("functions-run-plugin", plugin = plugin))
+                        }),
+                    };
+                    if let Some(hint) = hint {
+                        if ui
+                            .button(tr!("functions-run"))
+                            .on_hover_text(hint)
+                            .clicked()
+                        {
+                            action = CallAction::Run(i);
+                        }
+                    }
+                }
+            }
+        });
+    }
+    let pending: Vec<&mut Call> = calls
+        .iter_mut()
+        .filter(|c| c.response.is_none() && !c.running)
+        .collect();
+    if !pending.is_empty() {
+        let ready = pending.iter().all(|c| !c.draft.trim().is_empty());
+        if ui
+            .add_enabled(ready, egui::Button::new(tr!("functions-send")))
+            .on_disabled_hover_text(tr!("functions-send-disabled"))
+            .clicked()
+        {
+            for call in pending {
+                call.response = Some(std::mem::take(&mut call.draft));
+            }
+            action = CallAction::Send;
+        }
+    }
+    action
+}
+
+/// Where the user declares the functions the model can call.
+#[derive(Default)]
+pub struct FunctionsPanel {
+    pub open: bool,
+    selected: Option<usize>,
+}
+
+impl FunctionsPanel {
+    #[inline]
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        functions: &mut Vec<FunctionDecl>,
+        run_commands: &mut bool,
+    ) {
+        if !self.open {
+            return;
+        }
+        let mut open = true;
+        egui::Window::new(tr!("functions-title"))
+            .open(&mut open)
+            .default_size([640.0, 420.0])
+            .show(ctx, |ui| self.show_inner(ui, functions, run_commands));
+        self.open = open;
+    }
+
+    fn show_inner(
+        &mut self,
+        ui: &mut egui::Ui,
+        functions: &mut Vec<FunctionDecl>,
+        run_commands: &mut bool,
+    ) {
+        ui.weak(tr!("functions-help"));
+        crate::widgets::toggle_setting(
+            ui,
+            run_commands,
+            &tr!("functions-run-commands"),
+            &tr!("functions-run-commands-help"),
+        );
+        ui.separator();
+        egui::SidePanel::left("functions_list")
+            .resizable(true)
+            .default_width(180.0)
+            .show_inside(ui, |ui| {
+                for (i, function) in functions.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut function.enabled, "")
+                            .on_hover_text(tr!("functions-enabled"));
+                        let name = if function.name.is_empty() {
+                            tr!("functions-unnamed")
+                        } else {
+                            function.name.clone()
+                        };
+                        let label = if function.declaration().is_err() {
+                            RichText::new(name).color(ui.visuals().warn_fg_color)
+                        } else {
+                            RichText::new(name)
+                        };
+                        if ui
+                            .selectable_label(self.selected == Some(i), label)
+                            .clicked()
+                        {
+                            self.selected = Some(i);
+                        }
+                    });
+                }
+                if ui.button(tr!("functions-add")).clicked() {
+                    functions.push(FunctionDecl::default());
+                    self.selected = Some(functions.len() - 1);
+                }
+            });
+
+        let Some(i) = self.selected.filter(|&i| i < functions.len()) else {
+            ui.weak(tr!("functions-none-selected"));
+            return;
+        };
+        let mut remove = false;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            let function = &mut functions[i];
+            egui::Grid::new("function_editor")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label(tr!("functions-name"));
+                    ui.add(egui::TextEdit::singleline(&mut function.name).hint_text("get_weather"));
+                    ui.end_row();
+                    ui.label(tr!("functions-description"));
+                    ui.add(
+                        egui::TextEdit::multiline(&mut function.description)
+                            .hint_text(tr!("functions-description-hint"))
+                            .desired_rows(2),
+                    );
+                    ui.end_row();
+                    ui.label(tr!("functions-command"));
+                    ui.add_enabled(
+                        *run_commands,
+                        egui::TextEdit::singleline(&mut function.command)
+                            .hint_text(tr!("functions-command-hint"))
+                            .code_editor(),
+                    )
+                    .on_disabled_hover_text(tr!("functions-command-disabled"));
+                    ui.end_row();
+                });
+            ui.label(tr!("functions-parameters"));
+            ui.add(
+                egui::TextEdit::multiline(&mut function.parameters)
+                    .code_editor()
+                    .desired_rows(10)
+                    .desired_width(f32::INFINITY),
+            );
+            match function.declaration() {
+                Ok(_) => ui.weak(tr!("functions-valid")),
+                Err(e) => ui.colored_label(ui.visuals().warn_fg_color, e),
+            };
+            remove = ui.button(tr!("functions-remove")).clicked();
+        });
+        if remove {
+            functions.remove(i);
+            self.selected = None;
+        }
+    }
+}
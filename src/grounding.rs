@@ -0,0 +1,232 @@
+use crate::i18n::tr;
+use eframe::egui::{self, RichText};
+use gemini_rust::GenerationResponse;
+use serde_json::Value;
+
+/// Snippets shown per source, the answer may lean on one page many times.
+const MAX_SNIPPETS: usize = 3;
+
+/// A web page the answer was grounded in.
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct GroundingSource {
+    /// Usually the domain of the page
+    pub title: String,
+    pub uri: String,
+    /// Parts of the answer this page supports
+    pub snippets: Vec<String>,
+}
+
+/// A page the URL context tool fetched for the answer.
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct RetrievedPage {
+    pub url: String,
+    /// As the API reports it, e.g. `URL_RETRIEVAL_STATUS_SUCCESS`
+    pub status: String,
+}
+
+impl RetrievedPage {
+    fn succeeded(&self) -> bool {
+        self.status == "URL_RETRIEVAL_STATUS_SUCCESS"
+    }
+
+    /// The status without the prefix, for showing.
+    fn status_label(&self) -> String {
+        let status = self.status.trim_start_matches("URL_RETRIEVAL_STATUS_");
+        status.replace('_', " ").to_lowercase()
+    }
+}
+
+/// What Google Search and the URL context tool turned up for an answer, kept with
+/// the message.
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Grounding {
+    /// The searches the model ran
+    pub queries: Vec<String>,
+    pub sources: Vec<GroundingSource>,
+    /// Pages from the prompt the model fetched
+    pub pages: Vec<RetrievedPage>,
+}
+
+impl Grounding {
+    /// The grounding and URL context metadata of `response`, `None` when the answer
+    /// wasn't grounded. Read from the JSON form like the safety ratings, the client
+    /// library doesn't type all of it.
+    pub fn from_response(response: &GenerationResponse) -> Option<Self> {
+        let json = serde_json::to_value(response).ok()?;
+        let candidate = json.get("candidates")?.get(0)?;
+        let pages: Vec<RetrievedPage> = candidate
+            .get("urlContextMetadata")
+            .and_then(|m| m.get("urlMetadata"))
+            .and_then(Value::as_array)
+            .map(|pages| {
+                pages
+                    .iter()
+                    .filter_map(|page| {
+                        Some(RetrievedPage {
+                            url: page.get("retrievedUrl")?.as_str()?.to_owned(),
+                            status: page
+                                .get("urlRetrievalStatus")
+                                .and_then(Value::as_str)
+                                .unwrap_or("URL_RETRIEVAL_STATUS_UNSPECIFIED")
+                                .to_owned(),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let Some(metadata) = candidate.get("groundingMetadata") else {
+            return (!pages.is_empty()).then(|| Self {
+                pages,
+                ..Default::default()
+            });
+        };
+        let strings = |value: Option<&Value>| -> Vec<String> {
+            value
+                .and_then(Value::as_array)
+                .map(|a| {
+                    a.iter()
+                        .filter_map(Value::as_str)
+                        .map(str::to_owned)
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        let queries = strings(metadata.get("webSearchQueries"));
+        let mut sources: Vec<GroundingSource> = metadata
+            .get("groundingChunks")
+            .and_then(Value::as_array)
+            .map(|chunks| {
+                chunks
+                    .iter()
+                    .map(|chunk| {
+                        let page = chunk.get("web").or_else(|| chunk.get("retrievedContext"));
+                        let field = |name: &str| {
+                            page.and_then(|p| p.get(name))
+                                .and_then(Value::as_str)
+                                .unwrap_or_default()
+                                .to_owned()
+                        };
+                        GroundingSource {
+                            title: field("title"),
+                            uri: field("uri"),
+                            snippets: Vec::new(),
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let supports = metadata.get("groundingSupports").and_then(Value::as_array);
+        for support in supports.into_iter().flatten() {
+            let Some(text) = support
+                .get("segment")
+                .and_then(|s| s.get("text"))
+                .and_then(Value::as_str)
+            else {
+                continue;
+            };
+            let indices = support
+                .get("groundingChunkIndices")
+                .and_then(Value::as_array);
+            for index in indices.into_iter().flatten().filter_map(Value::as_u64) {
+                if let Some(source) = sources.get_mut(index as usize) {
+                    if !source.snippets.iter().any(|s| s == text) {
+                        source.snippets.push(text.to_owned());
+                    }
+                }
+            }
+        }
+
+        sources.retain(|s| !s.uri.is_empty());
+        let found = !queries.is_empty() || !sources.is_empty() || !pages.is_empty();
+        found.then_some(Self {
+            queries,
+            sources,
+            pages,
+        })
+    }
+
+    /// Adds what a later chunk of a streamed answer brought.
+    pub fn merge(&mut self, other: Self) {
+        for query in other.queries {
+            if !self.queries.contains(&query) {
+                self.queries.push(query);
+            }
+        }
+        for source in other.sources {
+            match self.sources.iter_mut().find(|s| s.uri == source.uri) {
+                Some(known) => {
+                    for snippet in source.snippets {
+                        if !known.snippets.contains(&snippet) {
+                            known.snippets.push(snippet);
+                        }
+                    }
+                }
+                None => self.sources.push(source),
+            }
+        }
+        for page in other.pages {
+            match self.pages.iter_mut().find(|p| p.url == page.url) {
+                Some(known) => known.status = page.status,
+                None => self.pages.push(page),
+            }
+        }
+    }
+
+    /// Lists the sources as links, with the parts of the answer they back, and the
+    /// pages that were fetched.
+    pub fn show(&self, ui: &mut egui::Ui, idx: usize) {
+        if !self.pages.is_empty() {
+            self.show_pages(ui, idx);
+        }
+        if self.sources.is_empty() && self.queries.is_empty() {
+            return;
+        }
+        egui::CollapsingHeader::new(tr!("grounding-sources", count = self.sources.len()))
+            .id_salt(("grounding", idx))
+            .default_open(true)
+            .show(ui, |ui| {
+                if !self.queries.is_empty() {
+                    ui.weak(tr!("grounding-queries", queries = self.queries.join(", ")));
+                }
+                for (i, source) in self.sources.iter().enumerate() {
+                    let title = if source.title.is_empty() {
+                        &source.uri
+                    } else {
+                        &source.title
+                    };
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label(format!("{}.", i + 1));
+                        ui.hyperlink_to(title, &source.uri)
+                            .on_hover_text(&source.uri);
+                    });
+                    for snippet in source.snippets.iter().take(MAX_SNIPPETS) {
+                        ui.label(RichText::new(format!("“{snippet}”")).small().weak());
+                    }
+                }
+            });
+    }
+    fn show_pages(&self, ui: &mut egui::Ui, idx: usize) {
+        let failed = self.pages.iter().filter(|p| !p.succeeded()).count();
+        egui::CollapsingHeader::new(tr!("url-context-pages", count = self.pages.len()))
+            .id_salt(("url_context", idx))
+            .default_open(failed > 0)
+            .show(ui, |ui| {
+                for page in &self.pages {
+                    ui.horizontal_wrapped(|ui| {
+                        if page.succeeded() {
+                            ui.label("✔");
+                        } else {
+                            ui.colored_label(ui.visuals().warn_fg_color, "⚠")
+                                .on_hover_text(page.status_label());
+                        }
+                        ui.hyperlink_to(&page.url, &page.url);
+                    });
+                }
+            });
+    }
+}
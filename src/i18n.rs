@@ -0,0 +1,225 @@
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource, FluentValue};
+use std::sync::{LazyLock, RwLock};
+use unic_langid::LanguageIdentifier;
+
+/// UI languages with a translation in `assets/locales`.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    serde::Deserialize,
+    serde::Serialize,
+    enum_iterator::Sequence,
+)]
+pub enum Language {
+    #[default]
+    English,
+    Russian,
+    German,
+    Spanish,
+}
+
+impl Language {
+    fn code(self) -> &'static str {
+        match self {
+            Self::English => "en-US",
+            Self::Russian => "ru",
+            Self::German => "de",
+            Self::Spanish => "es",
+        }
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Self::English => include_str!("../assets/locales/en.ftl"),
+            Self::Russian => include_str!("../assets/locales/ru.ftl"),
+            Self::German => include_str!("../assets/locales/de.ftl"),
+            Self::Spanish => include_str!("../assets/locales/es.ftl"),
+        }
+    }
+
+    /// Name of the language in itself, so it can be found without understanding the current one.
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::Russian => "Русский",
+            Self::German => "Deutsch",
+            Self::Spanish => "Español",
+        }
+    }
+
+    /// Locale for month and weekday names in dates.
+    pub fn date_locale(self) -> chrono::Locale {
+        match self {
+            Self::English => chrono::Locale::en_US,
+            Self::Russian => chrono::Locale::ru_RU,
+            Self::German => chrono::Locale::de_DE,
+            Self::Spanish => chrono::Locale::es_ES,
+        }
+    }
+
+    /// Locale name as used by hunspell dictionaries.
+    pub fn locale_code(self) -> &'static str {
+        match self {
+            Self::English => "en_US",
+            Self::Russian => "ru_RU",
+            Self::German => "de_DE",
+            Self::Spanish => "es_ES",
+        }
+    }
+
+    /// Digit group and decimal separators.
+    fn number_separators(self) -> (&'static str, &'static str) {
+        match self {
+            Self::English => (",", "."),
+            Self::Russian => ("\u{a0}", ","),
+            Self::German => (".", ","),
+            Self::Spanish => (".", ","),
+        }
+    }
+
+    /// Best match for the OS locale, English if there is none.
+    pub fn system() -> Self {
+        *SYSTEM_LANGUAGE
+    }
+}
+
+type Bundle = FluentBundle<FluentResource>;
+
+static SYSTEM_LANGUAGE: LazyLock<Language> = LazyLock::new(|| {
+    let locale = sys_locale::get_locale().unwrap_or_default();
+    enum_iterator::all::<Language>()
+        .find(|l| locale.starts_with(&l.code()[..2]))
+        .unwrap_or_default()
+});
+
+static FALLBACK: LazyLock<Bundle> = LazyLock::new(|| load_bundle(Language::English));
+static CURRENT: RwLock<Option<(Language, Bundle)>> = RwLock::new(None);
+/// Copy of the current language for number formatting, which also runs while `CURRENT` is locked.
+static NUMBER_LANGUAGE: RwLock<Language> = RwLock::new(Language::English);
+
+fn load_bundle(language: Language) -> Bundle {
+    let langid: LanguageIdentifier = language.code().parse().expect("valid language code");
+    let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+    // egui draws the bidi isolation marks around arguments as boxes
+    bundle.set_use_isolating(false);
+    bundle.set_formatter(Some(format_value));
+    let resource = FluentResource::try_new(language.source().to_owned()).unwrap_or_else(
+        |(resource, errors)| {
+            log::error!("errors in the {language:?} translation: {errors:?}");
+            resource
+        },
+    );
+    if let Err(errors) = bundle.add_resource(resource) {
+        log::error!("duplicate messages in the {language:?} translation: {errors:?}");
+    }
+    bundle
+}
+
+/// Switches the UI language, does nothing if it is already active.
+pub fn set_language(language: Language) {
+    let Ok(mut current) = CURRENT.write() else {
+        return;
+    };
+    if current.as_ref().is_some_and(|(l, _)| *l == language) {
+        return;
+    }
+    log::debug!("switching UI language to {language:?}");
+    *current = Some((language, load_bundle(language)));
+    if let Ok(mut number_language) = NUMBER_LANGUAGE.write() {
+        *number_language = language;
+    }
+}
+
+pub fn language() -> Language {
+    CURRENT
+        .read()
+        .ok()
+        .and_then(|current| current.as_ref().map(|(l, _)| *l))
+        .unwrap_or_default()
+}
+
+/// Locale of the current language, for `format_localized`.
+pub fn date_locale() -> chrono::Locale {
+    language().date_locale()
+}
+
+/// `value` with the digit grouping and decimal separator of the current language,
+/// e.g. 12,345.6 in English and 12 345,6 in Russian.
+pub fn format_number(value: f64, fraction_digits: usize) -> String {
+    let language = NUMBER_LANGUAGE.read().map(|l| *l).unwrap_or_default();
+    let (group, decimal) = language.number_separators();
+    let text = format!("{:.*}", fraction_digits, value.abs());
+    let (int, fraction) = text.split_once('.').unwrap_or((&text, ""));
+    // Spanish leaves four digit numbers alone
+    let min_grouped = if language == Language::Spanish { 5 } else { 4 };
+
+    let mut formatted = String::new();
+    if value < 0.0 && text.chars().any(|c| c.is_ascii_digit() && c != '0') {
+        formatted.push('-');
+    }
+    for (i, c) in int.chars().enumerate() {
+        if i > 0 && int.len() >= min_grouped && (int.len() - i) % 3 == 0 {
+            formatted.push_str(group);
+        }
+        formatted.push(c);
+    }
+    if !fraction.is_empty() {
+        formatted.push_str(decimal);
+        formatted.push_str(fraction);
+    }
+    formatted
+}
+
+/// Fluent formats numbers without grouping, so every translation goes through [`format_number`].
+fn format_value<M>(value: &FluentValue, _: &M) -> Option<String> {
+    match value {
+        FluentValue::Number(number) => Some(format_number(
+            number.value,
+            number.options.minimum_fraction_digits.unwrap_or(0),
+        )),
+        _ => None,
+    }
+}
+
+fn format(bundle: &Bundle, id: &str, args: Option<&FluentArgs>) -> Option<String> {
+    let pattern = bundle.get_message(id)?.value()?;
+    let mut errors = Vec::new();
+    let text = bundle.format_pattern(pattern, args, &mut errors);
+    if !errors.is_empty() {
+        log::debug!("failed to format message `{id}`: {errors:?}");
+    }
+    Some(text.into_owned())
+}
+
+/// Text of the message `id` in the current language. Falls back to English, then to the id itself.
+pub fn tr(id: &str, args: Option<&FluentArgs>) -> String {
+    let translated = CURRENT.read().ok().and_then(|current| {
+        current
+            .as_ref()
+            .and_then(|(_, bundle)| format(bundle, id, args))
+    });
+    translated
+        .or_else(|| format(&FALLBACK, id, args))
+        .unwrap_or_else(|| {
+            log::debug!("missing translation for `{id}`");
+            id.to_owned()
+        })
+}
+
+/// `tr!("id")` or `tr!("id", count = n, name = "…")`. Arguments take
+/// anything Fluent can format, numbers also select plural variants.
+macro_rules! tr {
+    ($id:expr) => {
+        $crate::i18n::tr($id, None)
+    };
+    ($id:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = fluent_bundle::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::i18n::tr($id, Some(&args))
+    }};
+}
+pub(crate) use tr;
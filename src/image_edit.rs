@@ -0,0 +1,300 @@
+use crate::{
+    i18n::tr,
+    images::GeneratedImage,
+    widgets::{GeminiModel, Settings},
+};
+use anyhow::{anyhow, Result};
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use eframe::egui;
+use gemini_rust::{GenerationResponse, Part};
+use geminid_core::pipeline::generate_image_edit;
+use std::path::PathBuf;
+use tokio::sync::oneshot::{self, error::TryRecvError};
+
+/// Picked for editing until the user chooses another image model.
+const DEFAULT_MODEL: &str = "gemini-2.5-flash-image";
+
+/// An image in the chain of edits.
+struct Step {
+    /// What was asked for, empty for the image the chain starts from
+    instruction: String,
+    image: GeneratedImage,
+    /// What the model said about the edit
+    note: String,
+    /// Tells the images apart in egui's memory
+    time: DateTime<Utc>,
+}
+
+impl Step {
+    fn new(instruction: String, image: GeneratedImage, note: String) -> Self {
+        Self {
+            instruction,
+            image,
+            note,
+            time: Utc::now(),
+        }
+    }
+}
+
+async fn load(path: PathBuf) -> Result<GeneratedImage> {
+    let mime_type = mime_guess::from_path(&path)
+        .first_or_octet_stream()
+        .to_string();
+    if !mime_type.starts_with("image/") {
+        return Err(anyhow!("{} isn't an image", path.display()));
+    }
+    let bytes = tokio::fs::read(&path).await?;
+    Ok(GeneratedImage {
+        mime_type,
+        data: base64::engine::general_purpose::STANDARD.encode(bytes),
+    })
+}
+
+/// The edited image and the text that came with it.
+fn edited(response: &GenerationResponse) -> Result<(GeneratedImage, String)> {
+    let parts = response
+        .candidates
+        .first()
+        .and_then(|candidate| candidate.content.parts.as_ref());
+    let mut image = None;
+    let mut note = String::new();
+    for part in parts.into_iter().flatten() {
+        match part {
+            Part::Text { text, thought, .. } if !thought.unwrap_or(false) => note.push_str(text),
+            part => image = image.or_else(|| GeneratedImage::from_part(part)),
+        }
+    }
+    match image {
+        Some(image) => Ok((image, note.trim().to_owned())),
+        None if note.trim().is_empty() => Err(anyhow!("The model didn't answer with an image.")),
+        None => Err(anyhow!("{}", note.trim())),
+    }
+}
+
+/// Edits an image step by step with an image model, each edit starting from the last.
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct ImageEditor {
+    pub model: GeminiModel,
+    #[serde(skip)]
+    pub open: bool,
+    #[serde(skip)]
+    steps: Vec<Step>,
+    /// The step the next edit starts from
+    #[serde(skip)]
+    current: usize,
+    #[serde(skip)]
+    instruction: String,
+    #[serde(skip)]
+    picking: Option<oneshot::Receiver<Result<GeneratedImage, String>>>,
+    #[serde(skip)]
+    running: Option<oneshot::Receiver<Result<(GeneratedImage, String), String>>>,
+    #[serde(skip)]
+    error: Option<String>,
+}
+
+impl Default for ImageEditor {
+    fn default() -> Self {
+        Self {
+            model: GeminiModel::from_name(DEFAULT_MODEL),
+            open: false,
+            steps: Vec::new(),
+            current: 0,
+            instruction: String::new(),
+            picking: None,
+            running: None,
+            error: None,
+        }
+    }
+}
+
+impl ImageEditor {
+    /// Opens the editor with `image` as the start of a new chain of edits.
+    pub fn start_from(&mut self, image: GeneratedImage) {
+        self.steps = vec![Step::new(String::new(), image, String::new())];
+        self.current = 0;
+        self.running = None;
+        self.error = None;
+        self.open = true;
+    }
+
+    fn poll(&mut self) {
+        if let Some(rx) = &mut self.picking {
+            match rx.try_recv() {
+                Ok(result) => {
+                    self.picking = None;
+                    match result {
+                        Ok(image) => self.start_from(image),
+                        Err(e) => self.error = Some(e),
+                    }
+                }
+                // no file was picked
+                Err(TryRecvError::Closed) => self.picking = None,
+                Err(TryRecvError::Empty) => (),
+            }
+        }
+        if let Some(rx) = &mut self.running {
+            if let Ok(result) = rx.try_recv() {
+                self.running = None;
+                match result {
+                    Ok((image, note)) => {
+                        // editing an earlier step drops the edits that came after it
+                        self.steps.truncate(self.current + 1);
+                        let instruction = std::mem::take(&mut self.instruction);
+                        self.steps.push(Step::new(instruction, image, note));
+                        self.current = self.steps.len() - 1;
+                    }
+                    Err(e) => self.error = Some(e),
+                }
+            }
+        }
+    }
+
+    fn pick(&mut self, ctx: &egui::Context) {
+        let (tx, rx) = oneshot::channel();
+        self.picking = Some(rx);
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            let Some(file) = rfd::AsyncFileDialog::new()
+                .add_filter("Image", &["png", "jpg", "jpeg", "webp", "heic", "heif"])
+                .pick_file()
+                .await
+            else {
+                return;
+            };
+            let result = load(file.path().to_owned()).await.map_err(|e| {
+                log::error!("failed to open the image: {e}");
+                e.to_string()
+            });
+            let _ = tx.send(result);
+            ctx.request_repaint();
+        });
+    }
+
+    /// Asks the model to apply the instruction to the current step.
+    fn apply(&mut self, ctx: &egui::Context, settings: &Settings) {
+        let Some(step) = self.steps.get(self.current) else {
+            return;
+        };
+        let (tx, rx) = oneshot::channel();
+        self.running = Some(rx);
+        self.error = None;
+        let settings = settings.clone();
+        let model = self.model;
+        let image = step.image.clone();
+        let instruction = self.instruction.trim().to_owned();
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            log::info!("editing an image with {model}");
+            let connection = settings.connection();
+            let result = generate_image_edit(&connection, model, image.part(), &instruction)
+                .await
+                .and_then(|response| edited(&response))
+                .map_err(|e| {
+                    log::error!("failed to edit the image: {e}");
+                    e.to_string()
+                });
+            let _ = tx.send(result);
+            ctx.request_repaint();
+        });
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, settings: &Settings) {
+        self.poll();
+        if !self.open {
+            return;
+        }
+        let mut open = true;
+        egui::Window::new(tr!("image-edit-title"))
+            .open(&mut open)
+            .default_size([720.0, 640.0])
+            .show(ctx, |ui| self.show_inner(ui, settings));
+        self.open = open;
+    }
+
+    fn show_inner(&mut self, ui: &mut egui::Ui, settings: &Settings) {
+        ui.weak(tr!("image-edit-help"));
+        let running = self.running.is_some();
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("image-edit-model"));
+            crate::widgets::model_selector(ui, "image_edit_model", &mut self.model);
+            if ui
+                .add_enabled(
+                    self.picking.is_none() && !running,
+                    egui::Button::new(tr!("image-edit-open")),
+                )
+                .clicked()
+            {
+                self.pick(ui.ctx());
+            }
+        });
+        if let Some(error) = &self.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+        if self.steps.is_empty() {
+            ui.add_space(8.0);
+            ui.weak(tr!("image-edit-empty"));
+            return;
+        }
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            let can_apply = !running && !self.instruction.trim().is_empty();
+            let edit = ui.add(
+                egui::TextEdit::singleline(&mut self.instruction)
+                    .hint_text(tr!("image-edit-instruction-hint"))
+                    .desired_width(ui.available_width() - 120.0),
+            );
+            let submitted = edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if running {
+                ui.spinner();
+            } else if ui
+                .add_enabled(can_apply, egui::Button::new(tr!("image-edit-apply")))
+                .clicked()
+                || (submitted && can_apply)
+            {
+                self.apply(ui.ctx(), settings);
+            }
+        });
+
+        ui.columns(2, |columns| {
+            let [history, preview] = columns else {
+                return;
+            };
+            egui::ScrollArea::vertical()
+                .id_salt("image_edit_steps")
+                .show(history, |ui| {
+                    ui.strong(tr!("image-edit-steps"));
+                    for (n, step) in self.steps.iter().enumerate() {
+                        let label = if step.instruction.is_empty() {
+                            tr!("image-edit-original")
+                        } else {
+                            format!("{n}. {}", step.instruction)
+                        };
+                        if ui
+                            .selectable_label(self.current == n, label)
+                            .on_hover_text(tr!("image-edit-step-hint"))
+                            .clicked()
+                            && !running
+                        {
+                            self.current = n;
+                        }
+                    }
+                });
+            egui::ScrollArea::vertical()
+                .id_salt("image_edit_preview")
+                .show(preview, |ui| {
+                    let Some(step) = self.steps.get(self.current) else {
+                        return;
+                    };
+                    let image = std::slice::from_ref(&step.image);
+                    crate::images::show_images(ui, step.time, image, false);
+                    if !step.note.is_empty() {
+                        ui.weak(&step.note);
+                    }
+                });
+        });
+    }
+}
@@ -0,0 +1,139 @@
+use crate::i18n::tr;
+use base64::Engine;
+use eframe::egui::{self, load::Bytes, Id};
+use gemini_rust::{Blob, Part};
+use std::sync::Arc;
+
+/// Height of a generated image in the chat, saving it keeps the full size.
+const MAX_HEIGHT: f32 = 384.0;
+
+/// An image the model answered with, kept with the message as the API sent it.
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct GeneratedImage {
+    pub mime_type: String,
+    /// Base64, as in the response
+    pub data: String,
+}
+
+impl GeneratedImage {
+    /// The image in `part`, if it holds one.
+    pub fn from_part(part: &Part) -> Option<Self> {
+        let Part::InlineData { inline_data, .. } = part else {
+            return None;
+        };
+        inline_data.mime_type.starts_with("image/").then(|| Self {
+            mime_type: inline_data.mime_type.clone(),
+            data: inline_data.data.clone(),
+        })
+    }
+
+    /// Sent back with the history, so the model can edit what it drew.
+    pub fn part(&self) -> Part {
+        Part::InlineData {
+            inline_data: Blob::new(self.mime_type.clone(), self.data.clone()),
+            media_resolution: None,
+        }
+    }
+
+    fn extension(&self) -> &str {
+        match self.mime_type.strip_prefix("image/") {
+            Some("jpeg") => "jpg",
+            Some(subtype) => subtype,
+            None => "png",
+        }
+    }
+
+    /// The decoded bytes, kept in egui's memory so they aren't decoded every frame.
+    fn bytes(&self, ctx: &egui::Context, id: Id) -> Option<Arc<[u8]>> {
+        if let Some(bytes) = ctx.data(|d| d.get_temp::<Arc<[u8]>>(id)) {
+            return Some(bytes);
+        }
+        let bytes: Arc<[u8]> = base64::engine::general_purpose::STANDARD
+            .decode(&self.data)
+            .map_err(|e| log::error!("failed to decode a generated image: {e}"))
+            .ok()?
+            .into();
+        ctx.data_mut(|d| d.insert_temp(id, bytes.clone()));
+        Some(bytes)
+    }
+}
+
+/// Shows the images of a message, right-click saves or copies one, and edits it if
+/// `editable`. `time` is when the message was sent and tells the images of different
+/// messages apart. Returns the image the user chose to edit.
+pub fn show_images(
+    ui: &mut egui::Ui,
+    time: chrono::DateTime<chrono::Utc>,
+    images: &[GeneratedImage],
+    editable: bool,
+) -> Option<GeneratedImage> {
+    let mut edit = None;
+    ui.horizontal_wrapped(|ui| {
+        for (n, image) in images.iter().enumerate() {
+            let key = format!("{}-{n}", time.timestamp_micros());
+            let Some(bytes) = image.bytes(ui.ctx(), Id::new(("generated_image", &key))) else {
+                ui.colored_label(ui.visuals().warn_fg_color, tr!("image-invalid"));
+                continue;
+            };
+            let uri = format!("bytes://generated/{key}.{}", image.extension());
+            let resp = ui
+                .add(
+                    egui::Image::from_bytes(uri, Bytes::Shared(bytes.clone()))
+                        .max_height(MAX_HEIGHT)
+                        .fit_to_original_size(1.0)
+                        .corner_radius(4.0)
+                        .sense(egui::Sense::click()),
+                )
+                .on_hover_text(tr!("image-hint"));
+            resp.context_menu(|ui| {
+                if ui.button(tr!("image-save")).clicked() {
+                    save(ui.ctx(), bytes.clone(), image.extension());
+                    ui.close();
+                }
+                if ui.button(tr!("image-copy")).clicked() {
+                    copy(ui.ctx(), &bytes);
+                    ui.close();
+                }
+                if editable && ui.button(tr!("image-edit")).clicked() {
+                    edit = Some(image.clone());
+                    ui.close();
+                }
+            });
+        }
+    });
+    edit
+}
+
+fn save(ctx: &egui::Context, bytes: Arc<[u8]>, extension: &str) {
+    let file_name = format!("geminid-image.{extension}");
+    let ctx = ctx.clone();
+    tokio::spawn(async move {
+        let Some(file) = rfd::AsyncFileDialog::new()
+            .set_file_name(file_name)
+            .save_file()
+            .await
+        else {
+            return;
+        };
+        match tokio::fs::write(file.path(), &bytes).await {
+            Ok(()) => log::info!("saved the image to {}", file.path().display()),
+            Err(e) => log::error!("failed to save the image: {e}"),
+        }
+        ctx.request_repaint();
+    });
+}
+
+fn copy(ctx: &egui::Context, bytes: &[u8]) {
+    match image::load_from_memory(bytes) {
+        Ok(decoded) => {
+            let rgba = decoded.to_rgba8();
+            let size = [rgba.width() as usize, rgba.height() as usize];
+            ctx.copy_image(egui::ColorImage::from_rgba_unmultiplied(
+                size,
+                rgba.as_raw(),
+            ));
+        }
+        Err(e) => log::error!("failed to copy the image: {e}"),
+    }
+}
@@ -0,0 +1,178 @@
+use crate::{file_handler::Attachment, i18n::tr, knowledge::Citation};
+use eframe::egui::{self, RichText};
+use regex::Regex;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{LazyLock, Mutex},
+};
+
+/// Attachments bigger than this are scanned by their beginning only.
+const MAX_SCAN_BYTES: usize = 512 * 1024;
+/// Context shown around a match.
+const SNIPPET_CHARS: usize = 80;
+
+/// Phrases that address the model rather than the reader. Documents rarely talk like
+/// this, injected instructions almost always do.
+static PATTERNS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r"(?i)",
+        r"\b(ignore|disregard|forget|override|bypass)\b[^.\n]{0,40}\b(previous|prior|above|earlier|preceding|all|any|your|system)\b[^.\n]{0,20}\b(instructions?|prompts?|rules|directives|guidelines|context)\b",
+        r"|\bnew (system )?instructions?\s*:",
+        r"|\byou are now\b[^.\n]{0,40}\b(mode|assistant|ai|model|dan)\b",
+        r"|\b(reveal|print|repeat|output|show)\b[^.\n]{0,20}\b(system prompt|hidden instructions|initial instructions)\b",
+        r"|\b(do not|don't|never)\b[^.\n]{0,20}\b(tell|inform|mention (this )?to|reveal (this )?to)\b[^.\n]{0,10}\bthe user\b",
+        r"|\b(ai|llm|language model|assistant)s?\b[^.\n]{0,30}\b(reading|processing|summari[sz]ing) this\b",
+        r"|<\|im_start\|>|<\|system\|>|\[/?INST\]|<</?SYS>>|</?system>",
+    ))
+    .expect("valid injection patterns")
+});
+
+/// Scanned attachments, so the chatbox doesn't read them every frame.
+static FILE_CACHE: LazyLock<Mutex<HashMap<PathBuf, Option<String>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// The instruction-like passage in `text`, if there is one.
+pub fn scan(text: &str) -> Option<String> {
+    let found = PATTERNS.find(text)?;
+    let start = text[..found.start()]
+        .char_indices()
+        .rev()
+        .nth(SNIPPET_CHARS / 4)
+        .map_or(0, |(i, _)| i);
+    let snippet: String = text[start..].chars().take(SNIPPET_CHARS).collect();
+    let snippet = snippet.split_whitespace().collect::<Vec<_>>().join(" ");
+    Some(if start > 0 {
+        format!("…{snippet}…")
+    } else {
+        format!("{snippet}…")
+    })
+}
+
+/// Scans the text of the file at `path`. Binary files and the ones that can't be read
+/// come back clean, the model reads those itself.
+fn scan_file(path: &Path) -> Option<String> {
+    if let Some(found) = FILE_CACHE.lock().ok().and_then(|c| c.get(path).cloned()) {
+        return found;
+    }
+    let is_text = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e != "pdf" && crate::TEXT_FORMATS.contains(&e.to_lowercase().as_str()));
+    let found = is_text
+        .then(|| std::fs::read(path).ok())
+        .flatten()
+        .and_then(|bytes| {
+            let bytes = &bytes[..bytes.len().min(MAX_SCAN_BYTES)];
+            scan(&String::from_utf8_lossy(bytes))
+        });
+    if let Some(found) = &found {
+        log::warn!("possible prompt injection in {}: {found}", path.display());
+    }
+    if let Ok(mut cache) = FILE_CACHE.lock() {
+        cache.insert(path.to_owned(), found.clone());
+    }
+    found
+}
+
+/// Checks new attachments, leaving the suspicious ones out of the prompt when
+/// `settings` say so.
+pub fn scan_attachments(settings: &InjectionSettings, files: &mut [Attachment]) {
+    if !settings.enabled {
+        return;
+    }
+    for file in files.iter_mut().filter(|f| f.suspicious.is_none()) {
+        if let Some(found) = scan_file(&file.path) {
+            file.suspicious = Some(found);
+            file.quarantined = settings.quarantine;
+        }
+    }
+}
+
+/// Marks the suspicious excerpts and quarantines them as `settings` say.
+pub fn scan_citations(settings: &InjectionSettings, citations: &mut [Citation]) {
+    for citation in citations.iter_mut() {
+        if settings.enabled {
+            citation.suspicious = scan(&citation.excerpt);
+            if let Some(found) = &citation.suspicious {
+                log::warn!("possible prompt injection in {}: {found}", citation.source);
+            }
+        }
+        citation.quarantined = settings.quarantined.contains(&citation.source)
+            || (settings.quarantine && citation.suspicious.is_some());
+    }
+}
+
+/// A warning badge for a suspicious source. Returns whether the quarantine toggle was
+/// clicked.
+pub fn badge(ui: &mut egui::Ui, found: &str, quarantined: bool) -> bool {
+    let warn = ui.visuals().warn_fg_color;
+    let text = if quarantined {
+        tr!("injection-quarantined")
+    } else {
+        tr!("injection-suspicious")
+    };
+    ui.label(RichText::new(text).small().color(warn))
+        .on_hover_text(tr!("injection-found", snippet = found));
+    let toggle = if quarantined {
+        tr!("injection-release")
+    } else {
+        tr!("injection-quarantine")
+    };
+    ui.small_button(toggle)
+        .on_hover_text(tr!("injection-quarantine-hint"))
+        .clicked()
+}
+
+/// Scanning fetched and attached content for instructions aimed at the model.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct InjectionSettings {
+    pub enabled: bool,
+    /// Leave suspicious sources out of the prompt without asking
+    pub quarantine: bool,
+    /// Knowledge sources the user quarantined
+    pub quarantined: Vec<String>,
+}
+
+impl Default for InjectionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            quarantine: false,
+            quarantined: Vec::new(),
+        }
+    }
+}
+
+impl InjectionSettings {
+    /// Quarantines `source`, or releases it if it already is.
+    pub fn toggle(&mut self, source: String) {
+        if let Some(i) = self.quarantined.iter().position(|s| *s == source) {
+            self.quarantined.remove(i);
+        } else {
+            self.quarantined.push(source);
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.enabled, tr!("injection-enabled"))
+            .on_hover_text(tr!("injection-enabled-help"));
+        ui.add_enabled(
+            self.enabled,
+            egui::Checkbox::new(&mut self.quarantine, tr!("injection-auto-quarantine")),
+        );
+        if self.quarantined.is_empty() {
+            return;
+        }
+        ui.label(tr!("injection-quarantined-sources"));
+        self.quarantined.retain(|source| {
+            ui.horizontal(|ui| {
+                let release = ui.small_button(tr!("injection-release")).clicked();
+                ui.add(egui::Label::new(source).truncate());
+                !release
+            })
+            .inner
+        });
+    }
+}
@@ -0,0 +1,259 @@
+use eframe::egui;
+use fs4::fs_std::FileExt;
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+};
+
+/// How long a launch waits for the running instance to answer.
+const FORWARD_TIMEOUT: Duration = Duration::from_millis(500);
+/// How long a launch keeps trying an instance that holds the lock but may not be
+/// listening yet, as it has only just started.
+const STARTUP_WAIT: Duration = Duration::from_secs(5);
+const RETRY_INTERVAL: Duration = Duration::from_millis(100);
+/// A connection that hasn't sent its request by then is dropped.
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+const ACK: &str = "ok";
+/// Longer lines are cut off, a launch only sends paths and a prompt.
+const MAX_REQUEST_SIZE: u64 = 1024 * 1024;
+
+/// What a launch asked the app to open, handed to the running instance if there is one.
+#[derive(Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct OpenRequest {
+    /// `geminid://` links
+    pub links: Vec<String>,
+    /// Files attached to a new chat, absolute so the other process finds them
+    pub files: Vec<PathBuf>,
+    /// Text put into the chatbox of a new chat
+    pub prompt: Option<String>,
+}
+
+/// A forwarded request with the token of the port file, which only this user can read,
+/// so other local processes can't make the app attach their choice of files.
+#[derive(serde::Deserialize, serde::Serialize)]
+struct Forwarded<R> {
+    token: String,
+    #[serde(flatten)]
+    request: R,
+}
+
+/// Locked by the running instance for as long as it runs.
+fn lock_file() -> Option<PathBuf> {
+    eframe::storage_dir(crate::TITLE).map(|dir| dir.join("instance.lock"))
+}
+
+/// The exclusive lock of the running instance, the system releases it when the process
+/// exits, even if it crashes.
+pub struct InstanceLock {
+    _file: std::fs::File,
+}
+
+/// Takes the instance lock. `Ok(None)` if another instance holds it, this launch should
+/// forward its arguments then.
+pub fn lock() -> std::io::Result<Option<InstanceLock>> {
+    let path = lock_file().ok_or_else(|| std::io::Error::other("no storage folder"))?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&path)?;
+    Ok(file
+        .try_lock_exclusive()?
+        .then_some(InstanceLock { _file: file }))
+}
+
+/// The running instance writes the port it listens on and its token here.
+fn port_file() -> Option<PathBuf> {
+    eframe::storage_dir(crate::TITLE).map(|dir| dir.join("instance.port"))
+}
+
+/// Port and token of the running instance.
+fn read_port_file() -> Option<(u16, String)> {
+    let contents = std::fs::read_to_string(port_file()?).ok()?;
+    let (port, token) = contents.trim().split_once(' ')?;
+    Some((port.parse().ok()?, token.to_owned()))
+}
+
+/// Writes `contents` to a file only the current user can read.
+fn write_private(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        // an older file keeps its mode otherwise
+        if path.exists() {
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+        }
+    }
+    options.open(path)?.write_all(contents.as_bytes())
+}
+
+fn new_token() -> Option<String> {
+    let mut bytes = [0u8; 32];
+    getrandom::getrandom(&mut bytes)
+        .map_err(|e| log::error!("no randomness for the instance token: {e}"))
+        .ok()?;
+    Some(bytes.iter().map(|b| format!("{b:02x}")).collect())
+}
+
+/// Hands `request` to the instance holding the lock, retrying until it has written its
+/// port file. Returns false if it didn't answer in time.
+pub async fn forward(request: &OpenRequest) -> bool {
+    let deadline = Instant::now() + STARTUP_WAIT;
+    loop {
+        // a port file left by a crashed instance fails until the new one replaces it
+        if let Some((port, token)) = read_port_file() {
+            if send(port, token, request).await {
+                return true;
+            }
+        }
+        if Instant::now() >= deadline {
+            return false;
+        }
+        tokio::time::sleep(RETRY_INTERVAL).await;
+    }
+}
+
+async fn send(port: u16, token: String, request: &OpenRequest) -> bool {
+    let result = tokio::time::timeout(FORWARD_TIMEOUT, async {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).await?;
+        let line = format!(
+            "{}\n",
+            serde_json::to_string(&Forwarded { token, request })?
+        );
+        stream.write_all(line.as_bytes()).await?;
+        let mut ack = String::new();
+        BufReader::new(stream).read_line(&mut ack).await?;
+        // a stale port file can point at some other program
+        anyhow::Ok(ack.trim() == ACK)
+    })
+    .await;
+    match result {
+        Ok(Ok(forwarded)) => forwarded,
+        Ok(Err(e)) => {
+            log::debug!("no running instance on port {port}: {e}");
+            false
+        }
+        Err(_) => {
+            log::warn!("the instance on port {port} didn't answer");
+            false
+        }
+    }
+}
+
+/// The socket later launches forward their arguments to, bound before the window opens
+/// by the launch holding the lock.
+pub struct Listener {
+    listener: TcpListener,
+    token: String,
+}
+
+impl Listener {
+    pub async fn bind() -> Option<Self> {
+        let listener = match TcpListener::bind(("127.0.0.1", 0)).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                log::error!("failed to open the instance socket: {e}");
+                return None;
+            }
+        };
+        let port = listener.local_addr().ok()?.port();
+        let token = new_token()?;
+        let path = port_file()?;
+        if let Err(e) = write_private(&path, &format!("{port} {token}")) {
+            log::error!("failed to write {}: {e}", path.display());
+            return None;
+        }
+        log::debug!("accepting forwarded launches on port {port}");
+        Some(Self { listener, token })
+    }
+
+    pub fn start(self, ctx: &egui::Context) -> Instance {
+        let (tx, rx) = mpsc::channel();
+        tokio::spawn(accept(self.listener, self.token.into(), tx, ctx.clone()));
+        Instance { requests: rx }
+    }
+}
+
+/// Keeps a second launch from opening the chat store next to this one. Forwarded
+/// requests are queued until the next frame, like tray menu clicks.
+pub struct Instance {
+    requests: Receiver<OpenRequest>,
+}
+
+impl Instance {
+    pub fn poll(&self) -> Option<OpenRequest> {
+        self.requests.try_recv().ok()
+    }
+}
+
+async fn accept(
+    listener: TcpListener,
+    token: Arc<str>,
+    requests: Sender<OpenRequest>,
+    ctx: egui::Context,
+) {
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                log::error!("instance socket failed: {e}");
+                break;
+            }
+        };
+        // one client that never writes mustn't hold up the others
+        tokio::spawn(serve(stream, token.clone(), requests.clone(), ctx.clone()));
+    }
+}
+
+async fn serve(
+    mut stream: TcpStream,
+    token: Arc<str>,
+    requests: Sender<OpenRequest>,
+    ctx: egui::Context,
+) {
+    let (reader, mut writer) = stream.split();
+    let mut line = String::new();
+    let read = BufReader::new(reader)
+        .take(MAX_REQUEST_SIZE)
+        .read_line(&mut line);
+    match tokio::time::timeout(READ_TIMEOUT, read).await {
+        Ok(Ok(_)) => (),
+        Ok(Err(e)) => {
+            log::warn!("failed to read a forwarded launch: {e}");
+            return;
+        }
+        Err(_) => {
+            log::warn!("a forwarded launch timed out");
+            return;
+        }
+    }
+    match serde_json::from_str::<Forwarded<OpenRequest>>(&line) {
+        Ok(forwarded) if *forwarded.token == *token => {
+            if requests.send(forwarded.request).is_ok() {
+                ctx.request_repaint();
+                let _ = writer.write_all(format!("{ACK}\n").as_bytes()).await;
+            }
+        }
+        Ok(_) => log::warn!("rejected a forwarded launch with a wrong token"),
+        Err(e) => log::warn!("invalid forwarded launch: {e}"),
+    }
+}
@@ -0,0 +1,273 @@
+use crate::i18n::tr;
+use eframe::egui::{self, Color32, Key, KeyboardShortcut, Modifiers};
+use std::collections::HashMap;
+
+/// Everything that can be bound to a shortcut.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize, enum_iterator::Sequence,
+)]
+pub enum Command {
+    Send,
+    NewChat,
+    Regenerate,
+    Stop,
+    FindInChat,
+    GlobalSearch,
+    CommandPalette,
+    Settings,
+    NextModel,
+    PreviousModel,
+    ToggleSidebar,
+    FocusMode,
+    Timeline,
+    Back,
+    Forward,
+    ReopenClosed,
+    Logs,
+    Diagnostics,
+    Statistics,
+    Tools,
+    ContextCaches,
+}
+
+impl Command {
+    pub fn label(self) -> String {
+        let id = match self {
+            Self::Send => "command-send",
+            Self::NewChat => "command-new-chat",
+            Self::Regenerate => "command-regenerate",
+            Self::Stop => "command-stop",
+            Self::FindInChat => "command-find-in-chat",
+            Self::GlobalSearch => "command-global-search",
+            Self::CommandPalette => "command-palette",
+            Self::Settings => "command-settings",
+            Self::NextModel => "command-next-model",
+            Self::PreviousModel => "command-previous-model",
+            Self::ToggleSidebar => "command-toggle-sidebar",
+            Self::FocusMode => "command-focus-mode",
+            Self::Timeline => "command-timeline",
+            Self::Back => "command-back",
+            Self::Forward => "command-forward",
+            Self::ReopenClosed => "command-reopen-closed",
+            Self::Logs => "command-logs",
+            Self::Diagnostics => "command-diagnostics",
+            Self::Statistics => "command-statistics",
+            Self::Tools => "command-tools",
+            Self::ContextCaches => "command-context-caches",
+        };
+        tr!(id)
+    }
+
+    pub fn default_shortcut(self) -> Option<KeyboardShortcut> {
+        let (modifiers, key) = match self {
+            Self::Send => (Modifiers::NONE, Key::Enter),
+            Self::NewChat => (Modifiers::COMMAND, Key::N),
+            Self::Regenerate => (Modifiers::COMMAND, Key::R),
+            Self::Stop => (Modifiers::COMMAND, Key::Period),
+            Self::FindInChat => (Modifiers::COMMAND, Key::F),
+            Self::GlobalSearch => (Modifiers::COMMAND | Modifiers::SHIFT, Key::F),
+            Self::CommandPalette => (Modifiers::COMMAND, Key::K),
+            Self::Settings => (Modifiers::COMMAND, Key::Comma),
+            Self::NextModel => (Modifiers::COMMAND, Key::CloseBracket),
+            Self::PreviousModel => (Modifiers::COMMAND, Key::OpenBracket),
+            Self::ToggleSidebar => (Modifiers::COMMAND, Key::B),
+            Self::FocusMode => (Modifiers::COMMAND | Modifiers::SHIFT, Key::M),
+            Self::Timeline
+            | Self::Logs
+            | Self::Diagnostics
+            | Self::Statistics
+            | Self::Tools
+            | Self::ContextCaches => return None,
+            Self::Back => (Modifiers::ALT, Key::ArrowLeft),
+            Self::Forward => (Modifiers::ALT, Key::ArrowRight),
+            Self::ReopenClosed => (Modifiers::COMMAND | Modifiers::SHIFT, Key::T),
+        };
+        Some(KeyboardShortcut::new(modifiers, key))
+    }
+}
+
+#[derive(Clone, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Keymap {
+    /// Bindings changed from the defaults, `None` when unbound
+    overrides: HashMap<Command, Option<KeyboardShortcut>>,
+    /// j/k/g/G move through the history and i focuses the chatbox while nothing is being typed
+    pub vim_mode: bool,
+    /// Enter inserts a newline and Ctrl+Enter sends, for long prompts and IME users
+    pub ctrl_enter_sends: bool,
+    #[serde(skip)]
+    recording: Option<Command>,
+}
+
+impl Keymap {
+    pub fn shortcut(&self, command: Command) -> Option<KeyboardShortcut> {
+        match self.overrides.get(&command) {
+            Some(shortcut) => *shortcut,
+            None => command.default_shortcut(),
+        }
+    }
+
+    /// Consumes the shortcut of `command` if it was pressed this frame.
+    pub fn consume(&self, ctx: &egui::Context, command: Command) -> bool {
+        if self.recording.is_some() {
+            return false;
+        }
+        self.shortcut(command)
+            .is_some_and(|s| ctx.input_mut(|i| i.consume_shortcut(&s)))
+    }
+
+    /// Like [`Self::consume`], but requires the exact modifiers and leaves the event
+    /// in place, for keys a focused text edit also reacts to.
+    pub fn pressed_exact(&self, ui: &egui::Ui, command: Command) -> bool {
+        self.shortcut(command).is_some_and(|s| {
+            ui.input(|i| i.key_pressed(s.logical_key) && i.modifiers.matches_exact(s.modifiers))
+        })
+    }
+
+    /// Whether the chatbox should send this frame. Follows [`Self::ctrl_enter_sends`].
+    pub fn send_pressed(&self, ui: &egui::Ui) -> bool {
+        if self.ctrl_enter_sends {
+            ui.input(|i| i.key_pressed(Key::Enter) && i.modifiers.matches_exact(Modifiers::COMMAND))
+        } else {
+            self.pressed_exact(ui, Command::Send)
+        }
+    }
+
+    /// The key that inserts a newline in the chatbox.
+    pub fn newline_shortcut(&self) -> KeyboardShortcut {
+        if self.ctrl_enter_sends {
+            KeyboardShortcut::new(Modifiers::NONE, Key::Enter)
+        } else {
+            KeyboardShortcut::new(Modifiers::SHIFT, Key::Enter)
+        }
+    }
+
+    /// Hover text suffix such as " (Ctrl+N)", empty when unbound.
+    pub fn hint(&self, ctx: &egui::Context, command: Command) -> String {
+        self.shortcut(command)
+            .map(|s| format!(" ({})", ctx.format_shortcut(&s)))
+            .unwrap_or_default()
+    }
+
+    /// Other commands bound to the same shortcut as `command`.
+    pub fn conflicts(&self, command: Command) -> Vec<Command> {
+        let Some(shortcut) = self.shortcut(command) else {
+            return Vec::new();
+        };
+        enum_iterator::all::<Command>()
+            .filter(|&c| c != command && self.shortcut(c) == Some(shortcut))
+            .collect()
+    }
+
+    fn record(&mut self, ctx: &egui::Context) {
+        let Some(command) = self.recording else {
+            return;
+        };
+        let pressed = ctx.input_mut(|i| {
+            let pressed = i.events.iter().find_map(|e| match e {
+                egui::Event::Key {
+                    key,
+                    pressed: true,
+                    modifiers,
+                    ..
+                } => Some((*key, *modifiers)),
+                _ => None,
+            });
+            if pressed.is_some() {
+                // don't let the recorded key trigger anything else
+                i.events.clear();
+            }
+            pressed
+        });
+        match pressed {
+            Some((Key::Escape, Modifiers::NONE)) => self.recording = None,
+            Some((key, modifiers)) => {
+                self.set(command, Some(KeyboardShortcut::new(modifiers, key)));
+                self.recording = None;
+            }
+            None => (),
+        }
+    }
+
+    fn set(&mut self, command: Command, shortcut: Option<KeyboardShortcut>) {
+        if shortcut == command.default_shortcut() {
+            self.overrides.remove(&command);
+        } else {
+            self.overrides.insert(command, shortcut);
+        }
+    }
+
+    pub fn show_editor(&mut self, ui: &mut egui::Ui) {
+        self.record(ui.ctx());
+
+        egui::Grid::new("keymap_grid")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                for command in enum_iterator::all::<Command>() {
+                    ui.label(command.label());
+
+                    let text = if self.recording == Some(command) {
+                        tr!("shortcut-press-key")
+                    } else {
+                        self.shortcut(command)
+                            .map(|s| ui.ctx().format_shortcut(&s))
+                            .unwrap_or_else(|| tr!("shortcut-unbound"))
+                    };
+                    let conflicts = self.conflicts(command);
+                    let mut button = egui::Button::new(text).min_size(egui::vec2(120.0, 0.0));
+                    if !conflicts.is_empty() {
+                        button = button.stroke(egui::Stroke::new(1.0, Color32::RED));
+                    }
+                    let mut resp = ui
+                        .add(button.selected(self.recording == Some(command)))
+                        .on_hover_text(tr!("shortcut-record-hint"));
+                    if !conflicts.is_empty() {
+                        let names: Vec<_> = conflicts.iter().map(|c| c.label()).collect();
+                        resp = resp
+                            .on_hover_text(tr!("shortcut-conflicts", commands = names.join(", ")));
+                    }
+                    if resp.clicked() {
+                        self.recording = Some(command);
+                    }
+
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(self.shortcut(command).is_some(), egui::Button::new("❌"))
+                            .on_hover_text(tr!("shortcut-unbind"))
+                            .clicked()
+                        {
+                            self.set(command, None);
+                        }
+                        if ui
+                            .add_enabled(
+                                self.overrides.contains_key(&command),
+                                egui::Button::new("↺"),
+                            )
+                            .on_hover_text(tr!("shortcut-reset"))
+                            .clicked()
+                        {
+                            self.overrides.remove(&command);
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+
+        crate::widgets::toggle_setting(
+            ui,
+            &mut self.vim_mode,
+            &tr!("vim-mode"),
+            &tr!("vim-mode-help"),
+        );
+        crate::widgets::toggle_setting(
+            ui,
+            &mut self.ctrl_enter_sends,
+            &tr!("ctrl-enter-sends"),
+            &tr!("ctrl-enter-sends-help"),
+        );
+        if ui.button(tr!("shortcut-reset-all")).clicked() {
+            self.overrides.clear();
+        }
+    }
+}
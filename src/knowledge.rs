@@ -0,0 +1,1667 @@
+use crate::{
+    chat::Chat,
+    i18n::{format_number, tr},
+    semantic::{self, Embedder},
+    widgets::{GeminiModel, Settings},
+};
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Local, Utc};
+use eframe::egui::{self, Color32, Layout, RichText, Stroke};
+use gemini_rust::TaskType;
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    fmt::Write,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender},
+        Arc, LazyLock, Mutex,
+    },
+    time::{Duration, Instant, SystemTime},
+};
+use tokio::sync::oneshot;
+
+/// Characters per chunk, a few paragraphs.
+const CHUNK_CHARS: usize = 1500;
+/// Characters repeated at the start of the next chunk, so a sentence cut in two stays findable
+const CHUNK_OVERLAP: usize = 200;
+/// BM25 term frequency saturation
+const BM25_K1: f32 = 1.2;
+/// BM25 document length normalization
+const BM25_B: f32 = 0.75;
+/// Chunks below the cut shown by the retrieval debug view, to see what almost made it
+const DEBUG_EXTRA_HITS: usize = 5;
+
+const MEMORY_PROMPT: &str = "You extract lasting knowledge from conversations. List the facts, \
+decisions, preferences and conclusions from the conversation below that will still matter in later \
+conversations about the same project. Write each one as a standalone statement on its own line \
+starting with \"- \", with enough context to be understood without the conversation. Leave out small \
+talk, open questions and anything that was only true for the moment. Reply with the list only, or \
+with NONE when nothing is worth keeping.";
+
+/// Model that mines chats for memories, cheap and fast is all we need here.
+const MEMORY_MODEL: GeminiModel = GeminiModel::Gemini25Flash;
+/// Bigger files are skipped, they're rarely worth reading as text
+const MAX_FILE_SIZE: u64 = 4 * 1024 * 1024;
+/// Lines per chunk of source code, about one function
+const CODE_CHUNK_LINES: usize = 60;
+/// Bigger files in a repository are usually generated or minified
+const MAX_CODE_FILE_SIZE: u64 = 512 * 1024;
+/// Generated files that `.gitignore` keeps, too long and too repetitive to be worth a chunk
+const SKIPPED_FILES: &[&str] = &[
+    "Cargo.lock",
+    "package-lock.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "poetry.lock",
+    "go.sum",
+];
+/// How often indexed collections are checked for changed files
+const WATCH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Where the documents of a collection come from.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum Source {
+    /// A file, or a folder read recursively
+    Path(PathBuf),
+    Url(String),
+    /// A code repository, read with its `.gitignore` rules and cited by line
+    Repository(PathBuf),
+    /// Facts and decisions taken from a chat
+    Memory {
+        chat: u64,
+        time: DateTime<Utc>,
+        title: String,
+        text: String,
+    },
+}
+
+impl Source {
+    pub fn label(&self) -> String {
+        match self {
+            Self::Path(path) | Self::Repository(path) => path.display().to_string(),
+            Self::Url(url) => url.clone(),
+            Self::Memory { time, title, .. } => format!("{title}, {}", format_time(*time)),
+        }
+    }
+}
+
+/// A named set of documents, embedded into a vector index stored next to the app data.
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Collection {
+    pub id: u64,
+    pub name: String,
+    pub sources: Vec<Source>,
+    /// Size of the index, zero before the first indexing
+    pub chunks: usize,
+    pub indexed: Option<DateTime<Utc>>,
+    /// Re-embed changed files in the background
+    pub watch: bool,
+    pub embedder: Embedder,
+    /// Error of the last indexing run
+    #[serde(skip)]
+    pub error: Option<String>,
+    /// Local files changed since the indexing, `None` until the first check
+    #[serde(skip)]
+    pub pending: Option<usize>,
+    #[serde(skip)]
+    pub checked: Option<DateTime<Utc>>,
+}
+
+/// A piece of a document with its embedding.
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+struct Chunk {
+    source: String,
+    text: String,
+    /// First and last line, for source code
+    #[serde(default)]
+    lines: Option<(usize, usize)>,
+    vector: Vec<f32>,
+}
+
+/// A chunk put into the prompt, listed under the answer.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Citation {
+    pub source: String,
+    pub excerpt: String,
+    #[serde(default)]
+    pub lines: Option<(usize, usize)>,
+    /// Instruction-like text found in the excerpt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suspicious: Option<String>,
+    /// Left out of the prompt
+    #[serde(default)]
+    pub quarantined: bool,
+}
+
+impl Citation {
+    /// Whether the source is a file or page, rather than a memory.
+    fn openable(&self) -> bool {
+        Path::new(&self.source).is_file() || url::Url::parse(&self.source).is_ok()
+    }
+
+    /// The source, with the line range for code.
+    pub fn label(&self) -> String {
+        match self.lines {
+            Some((start, end)) => format!("{}:{start}-{end}", self.source),
+            None => self.source.clone(),
+        }
+    }
+}
+
+/// How chunks are picked for the prompt.
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct RetrievalSettings {
+    /// Chunks put into the prompt
+    pub top_k: usize,
+    /// Less relevant chunks are left out even when there are fewer than `top_k` others
+    pub min_score: f32,
+    /// Share of keyword matching in the score, the rest is embedding similarity
+    pub keyword_weight: f32,
+}
+
+impl Default for RetrievalSettings {
+    fn default() -> Self {
+        Self {
+            top_k: 5,
+            min_score: 0.35,
+            keyword_weight: 0.3,
+        }
+    }
+}
+
+impl RetrievalSettings {
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("retrieval_grid")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label(tr!("retrieval-top-k"))
+                    .on_hover_text(tr!("retrieval-top-k-help"));
+                ui.add(egui::DragValue::new(&mut self.top_k).range(1..=20));
+                ui.end_row();
+                ui.label(tr!("retrieval-min-score"))
+                    .on_hover_text(tr!("retrieval-min-score-help"));
+                ui.add(egui::Slider::new(&mut self.min_score, 0.0..=1.0));
+                ui.end_row();
+                ui.label(tr!("retrieval-keyword-weight"))
+                    .on_hover_text(tr!("retrieval-keyword-weight-help"));
+                ui.add(egui::Slider::new(&mut self.keyword_weight, 0.0..=1.0));
+                ui.end_row();
+            });
+    }
+}
+
+/// The stored vectors of a collection.
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+struct Index {
+    /// Modification time of every local file as of its indexing, by path. Files that
+    /// gave no chunks are listed too, so they don't look changed.
+    files: HashMap<String, SystemTime>,
+    chunks: Vec<Chunk>,
+    /// What made the vectors, queries are embedded with the same
+    embedder: Embedder,
+}
+
+/// Indexes written before file times were kept are a plain list of chunks.
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum StoredIndex {
+    Chunks(Vec<Chunk>),
+    Index(Index),
+}
+
+/// Indexes read so far, by collection id.
+static INDEXES: LazyLock<Mutex<HashMap<u64, Arc<Index>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn index_path(id: u64) -> Option<PathBuf> {
+    eframe::storage_dir(crate::TITLE).map(|dir| dir.join("knowledge").join(format!("{id}.json")))
+}
+
+fn load_index(id: u64) -> Result<Arc<Index>> {
+    if let Some(index) = INDEXES.lock().ok().and_then(|i| i.get(&id).cloned()) {
+        return Ok(index);
+    }
+    let path = index_path(id).ok_or_else(|| anyhow!("no storage folder"))?;
+    let file =
+        std::fs::File::open(&path).with_context(|| format!("failed to read {}", path.display()))?;
+    let index = match serde_json::from_reader(std::io::BufReader::new(file))? {
+        StoredIndex::Chunks(chunks) => Index {
+            files: HashMap::new(),
+            chunks,
+            embedder: Embedder::Gemini,
+        },
+        StoredIndex::Index(index) => index,
+    };
+    let index = Arc::new(index);
+    if let Ok(mut indexes) = INDEXES.lock() {
+        indexes.insert(id, index.clone());
+    }
+    Ok(index)
+}
+
+fn save_index(id: u64, index: Index) -> Result<()> {
+    let path = index_path(id).ok_or_else(|| anyhow!("no storage folder"))?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let file = std::fs::File::create(&path)?;
+    serde_json::to_writer(std::io::BufWriter::new(file), &index)?;
+    if let Ok(mut indexes) = INDEXES.lock() {
+        indexes.insert(id, Arc::new(index));
+    }
+    Ok(())
+}
+
+fn remove_index(id: u64) {
+    if let Ok(mut indexes) = INDEXES.lock() {
+        indexes.remove(&id);
+    }
+    if let Some(path) = index_path(id).filter(|p| p.exists()) {
+        if let Err(e) = std::fs::remove_file(&path) {
+            log::error!("failed to remove {}: {e}", path.display());
+        }
+    }
+}
+
+/// Lowercase words of `text`, identifiers kept whole.
+fn terms(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|t| t.chars().nth(1).is_some())
+        .map(str::to_lowercase)
+}
+
+/// Okapi BM25 score of every chunk for `query`, scaled so the best one is 1.
+fn keyword_scores(chunks: &[Chunk], query: &str) -> Vec<f32> {
+    let mut query: Vec<String> = terms(query).collect();
+    query.sort_unstable();
+    query.dedup();
+    if query.is_empty() || chunks.is_empty() {
+        return vec![0.0; chunks.len()];
+    }
+
+    // occurrences of every query term in every chunk, and the chunk lengths
+    let mut lengths = Vec::with_capacity(chunks.len());
+    let counts: Vec<Vec<f32>> = chunks
+        .iter()
+        .map(|chunk| {
+            let mut counts = vec![0.0; query.len()];
+            let mut length = 0;
+            for term in terms(&chunk.text) {
+                length += 1;
+                if let Ok(i) = query.binary_search(&term) {
+                    counts[i] += 1.0;
+                }
+            }
+            lengths.push(length as f32);
+            counts
+        })
+        .collect();
+    let total = chunks.len() as f32;
+    let average_length = (lengths.iter().sum::<f32>() / total).max(1.0);
+    let idf: Vec<f32> = (0..query.len())
+        .map(|i| {
+            let containing = counts.iter().filter(|c| c[i] > 0.0).count() as f32;
+            ((total - containing + 0.5) / (containing + 0.5) + 1.0).ln()
+        })
+        .collect();
+
+    let scores: Vec<f32> = counts
+        .iter()
+        .zip(&lengths)
+        .map(|(counts, length)| {
+            let norm = BM25_K1 * (1.0 - BM25_B + BM25_B * length / average_length);
+            counts
+                .iter()
+                .zip(&idf)
+                .map(|(tf, idf)| idf * tf * (BM25_K1 + 1.0) / (tf + norm))
+                .sum()
+        })
+        .collect();
+    let best = scores.iter().copied().fold(0.0, f32::max);
+    if best > 0.0 {
+        scores.into_iter().map(|s| s / best).collect()
+    } else {
+        scores
+    }
+}
+
+/// A chunk with how well it matches a question.
+pub struct Hit {
+    pub citation: Citation,
+    /// Cosine similarity of the embeddings
+    pub similarity: f32,
+    /// BM25 score relative to the best chunk
+    pub keywords: f32,
+    /// Both, weighted by `RetrievalSettings::keyword_weight`
+    pub score: f32,
+    /// Whether it makes it into the prompt
+    pub injected: bool,
+}
+
+/// The `limit` chunks of collection `id` that match `query` best, best first.
+async fn rank(settings: &Settings, id: u64, query: &str, limit: usize) -> Result<Vec<Hit>> {
+    let retrieval = &settings.retrieval;
+    let index = load_index(id)?;
+    let vector = semantic::embed_with(
+        settings,
+        index.embedder,
+        &[query.to_owned()],
+        TaskType::RetrievalQuery,
+    )
+    .await?
+    .pop()
+    .unwrap_or_default();
+    let keywords = keyword_scores(&index.chunks, query);
+    let mut scored: Vec<(f32, f32, f32, &Chunk)> = index
+        .chunks
+        .iter()
+        .zip(keywords)
+        .map(|(chunk, keywords)| {
+            let similarity = semantic::cosine(&chunk.vector, &vector);
+            let score =
+                (1.0 - retrieval.keyword_weight) * similarity + retrieval.keyword_weight * keywords;
+            (score, similarity, keywords, chunk)
+        })
+        .collect();
+    scored.sort_unstable_by(|a, b| b.0.total_cmp(&a.0));
+    Ok(scored
+        .into_iter()
+        .take(limit)
+        .enumerate()
+        .map(|(i, (score, similarity, keywords, chunk))| Hit {
+            citation: Citation {
+                source: chunk.source.clone(),
+                excerpt: chunk.text.clone(),
+                lines: chunk.lines,
+                suspicious: None,
+                quarantined: false,
+            },
+            similarity,
+            keywords,
+            score,
+            injected: i < retrieval.top_k && score >= retrieval.min_score,
+        })
+        .collect())
+}
+
+/// The chunks of collection `id` that go into the prompt for `query`, best first,
+/// the quarantined ones marked.
+pub async fn retrieve(settings: &Settings, id: u64, query: &str) -> Result<Vec<Citation>> {
+    let hits = rank(settings, id, query, settings.retrieval.top_k).await?;
+    let mut citations: Vec<Citation> = hits
+        .into_iter()
+        .filter(|h| h.injected)
+        .map(|h| h.citation)
+        .collect();
+    crate::injection::scan_citations(&settings.injection, &mut citations);
+    Ok(citations)
+}
+
+/// `system_prompt` followed by the numbered excerpts of `citations`. Quarantined
+/// excerpts keep their number but are left out.
+pub fn with_context(system_prompt: Option<String>, citations: &[Citation]) -> String {
+    let context = context_prompt(citations);
+    match system_prompt {
+        Some(prompt) if !prompt.trim().is_empty() => format!("{prompt}\n\n{context}"),
+        _ => context,
+    }
+}
+
+/// Instructions with the numbered excerpts, added to the system prompt.
+fn context_prompt(citations: &[Citation]) -> String {
+    let mut prompt = String::from(
+        "Answer using the numbered excerpts below where they're relevant and cite them like [1]. \
+         Say so when they don't cover the question.\n",
+    );
+    for (i, citation) in citations.iter().enumerate().filter(|(_, c)| !c.quarantined) {
+        let _ = write!(
+            prompt,
+            "\n[{}] {}\n{}\n",
+            i + 1,
+            citation.label(),
+            citation.excerpt
+        );
+    }
+    prompt
+}
+
+/// Splits `text` into chunks of whole paragraphs where it can.
+fn chunk(text: &str) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    for paragraph in text.split("\n\n").map(str::trim).filter(|p| !p.is_empty()) {
+        let chars: Vec<char> = paragraph.chars().collect();
+        for piece in chars.chunks(CHUNK_CHARS) {
+            let piece: String = piece.iter().collect();
+            if !current.is_empty() && current.chars().count() + piece.chars().count() > CHUNK_CHARS
+            {
+                let overlap = tail(&current, CHUNK_OVERLAP).to_owned();
+                chunks.push(std::mem::replace(&mut current, overlap));
+            }
+            if !current.is_empty() {
+                current.push_str("\n\n");
+            }
+            current.push_str(&piece);
+        }
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Splits source code into runs of whole lines, cut before a top level item where
+/// there is one in the second half. Returns the first and last line (from one) with the text.
+fn chunk_code(text: &str) -> Vec<(usize, usize, String)> {
+    let lines: Vec<&str> = text.lines().collect();
+    // a flush-left line after a blank one starts a function, class or the like in most languages
+    let starts_item = |i: usize| {
+        lines[i].starts_with(|c: char| !c.is_whitespace() && c != '}' && c != ')')
+            && lines[i - 1].trim().is_empty()
+    };
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < lines.len() {
+        let mut end = (start + CODE_CHUNK_LINES).min(lines.len());
+        if end < lines.len() {
+            let half = start + CODE_CHUNK_LINES / 2;
+            end = (half..end)
+                .rev()
+                .find(|&i| starts_item(i))
+                .or_else(|| (half..end).rev().find(|&i| lines[i].trim().is_empty()))
+                .unwrap_or(end);
+        }
+        let text = lines[start..end].join("\n");
+        if !text.trim().is_empty() {
+            chunks.push((start + 1, end, text));
+        }
+        start = end;
+    }
+    chunks
+}
+
+/// About the last `chars` characters of `text`, starting at a word.
+fn tail(text: &str, chars: usize) -> &str {
+    let skip = text.chars().count().saturating_sub(chars);
+    let start = text.char_indices().nth(skip).map_or(text.len(), |(i, _)| i);
+    let tail = &text[start..];
+    match tail.split_once(char::is_whitespace) {
+        Some((_, rest)) if skip > 0 => rest.trim_start(),
+        _ => tail,
+    }
+}
+
+/// Rough text of a web page: scripts, styles and tags dropped, common entities decoded.
+fn html_to_text(html: &str) -> String {
+    const BLOCKS: &[&str] = &[
+        "p", "/p", "br", "div", "/div", "li", "tr", "h1", "h2", "h3", "h4", "h5", "h6",
+    ];
+
+    // ASCII lowercasing keeps the byte offsets
+    let lower = html.to_ascii_lowercase();
+    let mut text = String::with_capacity(html.len() / 2);
+    let mut pos = 0;
+    while let Some(offset) = lower[pos..].find('<') {
+        let start = pos + offset;
+        text.push_str(&html[pos..start]);
+        let tag = &lower[start + 1..];
+        let name = tag
+            .split(|c: char| c.is_whitespace() || c == '>' || c == '/' && !tag.starts_with('/'))
+            .next()
+            .unwrap_or_default();
+        let end = match name {
+            "script" | "style" => tag.find(&format!("</{name}>")).map(|i| i + name.len() + 3),
+            _ => tag.find('>').map(|i| i + 1),
+        };
+        let Some(end) = end else {
+            pos = html.len();
+            break;
+        };
+        if BLOCKS.contains(&name) {
+            text.push_str("\n\n");
+        }
+        pos = start + 1 + end;
+    }
+    text.push_str(&html[pos..]);
+
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    let mut out = String::new();
+    let mut blank = false;
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() {
+            blank = !out.is_empty();
+            continue;
+        }
+        if blank {
+            out.push('\n');
+            blank = false;
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Text files under `dir`, hidden folders aside.
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.flatten().map(|e| e.path()) {
+        let hidden = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        if path.is_dir() {
+            if !hidden {
+                collect_files(&path, files);
+            }
+        } else if path.extension().is_some_and(|ext| {
+            let ext = ext.to_string_lossy().to_lowercase();
+            // PDFs need the model to read them
+            ext != "pdf" && crate::TEXT_FORMATS.contains(&ext.as_str())
+        }) {
+            files.push(path);
+        }
+    }
+}
+
+/// Files of the repository at `root`, as `.gitignore` and friends leave them.
+fn collect_repository(root: &Path, files: &mut Vec<PathBuf>) {
+    for entry in ignore::WalkBuilder::new(root).build() {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                log::debug!("skipping part of {}: {e}", root.display());
+                continue;
+            }
+        };
+        let skipped = entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| SKIPPED_FILES.contains(&name));
+        if !skipped && entry.file_type().is_some_and(|t| t.is_file()) {
+            files.push(entry.into_path());
+        }
+    }
+}
+
+/// The local files of `source`, and whether they're code. Reading them happens later,
+/// this is also how changes are looked for.
+fn local_files(source: &Source) -> Vec<(PathBuf, bool)> {
+    let mut files = Vec::new();
+    let code = matches!(source, Source::Repository(_));
+    match source {
+        Source::Path(path) if path.is_dir() => collect_files(path, &mut files),
+        Source::Path(path) if path.is_file() => files.push(path.clone()),
+        Source::Repository(root) => collect_repository(root, &mut files),
+        Source::Path(_) | Source::Url(_) | Source::Memory { .. } => (),
+    }
+    files.sort();
+    files.into_iter().map(|file| (file, code)).collect()
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// How many local files of collection `id` changed, appeared or went away since its indexing.
+fn pending_files(id: u64, sources: &[Source], embedder: Embedder) -> Result<usize> {
+    let index = load_index(id)?;
+    if index.embedder != embedder {
+        // everything is embedded again
+        return Ok(index.files.len().max(1));
+    }
+    let mut listed = HashSet::new();
+    let mut pending = 0;
+    for (path, _) in sources.iter().flat_map(local_files) {
+        let label = path.display().to_string();
+        if !listed.insert(label.clone()) {
+            continue;
+        }
+        if modified_time(&path).is_some_and(|m| index.files.get(&label) != Some(&m)) {
+            pending += 1;
+        }
+    }
+    let removed = index.files.keys().filter(|f| !listed.contains(*f)).count();
+    Ok(pending + removed)
+}
+
+/// A text read from a source, before chunking.
+struct Document {
+    source: String,
+    text: String,
+    /// Chunked by lines and cited with them
+    code: bool,
+}
+
+impl Document {
+    fn new(source: String, text: String) -> Self {
+        Self {
+            source,
+            text,
+            code: false,
+        }
+    }
+}
+
+/// A chunk waiting for its embedding.
+struct Piece {
+    source: String,
+    text: String,
+    lines: Option<(usize, usize)>,
+}
+
+impl Piece {
+    /// Code is embedded with its path, a file name says a lot about what's in it.
+    fn embedded_text(&self) -> String {
+        match self.lines {
+            Some((start, end)) => format!("{}:{start}-{end}\n{}", self.source, self.text),
+            None => self.text.clone(),
+        }
+    }
+}
+
+/// Reads a local file, `None` when it's skipped.
+fn read_file(path: &Path, code: bool) -> Option<Document> {
+    let max_size = if code {
+        MAX_CODE_FILE_SIZE
+    } else {
+        MAX_FILE_SIZE
+    };
+    if std::fs::metadata(path).is_ok_and(|m| m.len() > max_size) {
+        // big files are common in repositories and rarely hand-written
+        if code {
+            log::debug!("skipping {}, it's too big", path.display());
+        } else {
+            log::warn!("skipping {}, it's too big", path.display());
+        }
+        return None;
+    }
+    match std::fs::read_to_string(path) {
+        Ok(text) if !code => Some(Document::new(path.display().to_string(), text)),
+        // images, archives and the like don't read as UTF-8
+        Ok(text) if !text.contains('\0') => Some(Document {
+            source: path.display().to_string(),
+            text,
+            code: true,
+        }),
+        Ok(_) => None,
+        Err(e) if code => {
+            log::debug!("skipping {}: {e}", path.display());
+            None
+        }
+        Err(e) => {
+            log::warn!("skipping {}: {e}", path.display());
+            None
+        }
+    }
+}
+
+async fn fetch_page(url: &str, client: &reqwest::Client) -> Result<Document> {
+    let resp = client.get(url).send().await?.error_for_status()?;
+    let is_html = resp
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.contains("html"));
+    let body = resp.text().await?;
+    let text = if is_html { html_to_text(&body) } else { body };
+    Ok(Document::new(url.to_owned(), text))
+}
+
+enum IndexEvent {
+    Progress {
+        done: usize,
+        total: usize,
+    },
+    /// The number of chunks, or the error
+    Finished(Result<usize, String>),
+}
+
+/// Embeds the documents of `sources` into the index of collection `id`. Unless `full`,
+/// the chunks of local files that didn't change since the last run are kept, and so
+/// are pages fetched before, as long as they were embedded by `embedder` too.
+async fn index(
+    id: u64,
+    sources: Vec<Source>,
+    embedder: Embedder,
+    full: bool,
+    settings: Settings,
+    events: Sender<IndexEvent>,
+    cancel: Arc<AtomicBool>,
+    ctx: egui::Context,
+) {
+    let result = async {
+        let mut client = reqwest::Client::builder();
+        if let Some(proxy) = settings.proxy_path.as_ref().filter(|p| !p.is_empty()) {
+            client = client.proxy(reqwest::Proxy::all(proxy)?);
+        }
+        let client = client.build()?;
+        let previous = if full {
+            None
+        } else {
+            load_index(id).ok().filter(|p| p.embedder == embedder)
+        };
+
+        let mut files = HashMap::new();
+        // sources still in the collection, and the ones read again
+        let mut listed = HashSet::new();
+        let mut changed = HashSet::new();
+        let mut documents = Vec::new();
+        for (path, code) in sources.iter().flat_map(local_files) {
+            let label = path.display().to_string();
+            if !listed.insert(label.clone()) {
+                continue;
+            }
+            let modified = modified_time(&path);
+            let unchanged = modified.is_some()
+                && previous
+                    .as_ref()
+                    .is_some_and(|p| p.files.get(&label) == modified.as_ref());
+            if let Some(modified) = modified {
+                files.insert(label.clone(), modified);
+            }
+            if !unchanged {
+                changed.insert(label);
+                documents.extend(read_file(&path, code));
+            }
+        }
+        for source in &sources {
+            if matches!(source, Source::Path(_) | Source::Repository(_)) {
+                continue;
+            }
+            let label = source.label();
+            listed.insert(label.clone());
+            if previous
+                .as_ref()
+                .is_some_and(|p| p.chunks.iter().any(|c| c.source == label))
+            {
+                continue;
+            }
+            changed.insert(label.clone());
+            let document = match source {
+                Source::Memory { text, .. } => Ok(Document::new(label.clone(), text.clone())),
+                _ => fetch_page(&label, &client).await,
+            };
+            match document {
+                Ok(document) => documents.push(document),
+                Err(e) => log::warn!("skipping {label}: {e}"),
+            }
+        }
+        let mut chunks: Vec<Chunk> = previous
+            .map(|p| {
+                p.chunks
+                    .iter()
+                    .filter(|c| listed.contains(&c.source) && !changed.contains(&c.source))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let pieces: Vec<Piece> = documents
+            .iter()
+            .flat_map(|doc| {
+                let piece = |text, lines| Piece {
+                    source: doc.source.clone(),
+                    text,
+                    lines,
+                };
+                if doc.code {
+                    chunk_code(&doc.text)
+                        .into_iter()
+                        .map(|(start, end, text)| piece(text, Some((start, end))))
+                        .collect::<Vec<_>>()
+                } else {
+                    chunk(&doc.text)
+                        .into_iter()
+                        .map(|text| piece(text, None))
+                        .collect()
+                }
+            })
+            .collect();
+        if pieces.is_empty() && chunks.is_empty() {
+            return Err(anyhow!(tr!("knowledge-nothing-to-index")));
+        }
+
+        let total = pieces.len();
+        let mut done = 0;
+        for batch in pieces.chunks(semantic::BATCH_SIZE) {
+            if cancel.load(Ordering::SeqCst) {
+                return Err(anyhow!(tr!("knowledge-cancelled")));
+            }
+            let texts: Vec<String> = batch.iter().map(Piece::embedded_text).collect();
+            let vectors =
+                semantic::embed_with(&settings, embedder, &texts, TaskType::RetrievalDocument)
+                    .await?;
+            chunks.extend(batch.iter().zip(vectors).map(|(piece, vector)| Chunk {
+                source: piece.source.clone(),
+                text: piece.text.clone(),
+                lines: piece.lines,
+                vector,
+            }));
+            done += batch.len();
+            let _ = events.send(IndexEvent::Progress { done, total });
+            ctx.request_repaint();
+        }
+        let count = chunks.len();
+        save_index(
+            id,
+            Index {
+                files,
+                chunks,
+                embedder,
+            },
+        )?;
+        anyhow::Ok(count)
+    }
+    .await;
+    if let Err(e) = &result {
+        log::error!("failed to index a knowledge collection: {e}");
+    }
+    let _ = events.send(IndexEvent::Finished(result.map_err(|e| e.to_string())));
+    ctx.request_repaint();
+}
+
+struct Indexing {
+    id: u64,
+    done: usize,
+    total: usize,
+    cancel: Arc<AtomicBool>,
+    events: Receiver<IndexEvent>,
+}
+
+/// Indexes `collection` in the background, from scratch when `full`.
+fn start_indexing(
+    collection: &mut Collection,
+    full: bool,
+    settings: &Settings,
+    ctx: &egui::Context,
+) -> Indexing {
+    let (tx, rx) = mpsc::channel();
+    let cancel = Arc::new(AtomicBool::new(false));
+    collection.error = None;
+    tokio::spawn(index(
+        collection.id,
+        collection.sources.clone(),
+        collection.embedder,
+        full,
+        settings.clone(),
+        tx,
+        cancel.clone(),
+        ctx.clone(),
+    ));
+    Indexing {
+        id: collection.id,
+        done: 0,
+        total: 0,
+        cancel,
+        events: rx,
+    }
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
+
+/// Tries a question against a collection and lists the chunks it would get, with
+/// their scores, for tuning the retrieval settings.
+#[derive(Default)]
+struct RetrievalDebug {
+    query: String,
+    pending: Option<oneshot::Receiver<Result<Vec<Hit>, String>>>,
+    /// Collection id and the outcome
+    hits: Option<(u64, Result<Vec<Hit>, String>)>,
+}
+
+impl RetrievalDebug {
+    fn show(&mut self, ui: &mut egui::Ui, id: u64, settings: &Settings) {
+        if let Some(rx) = &mut self.pending {
+            if let Ok(hits) = rx.try_recv() {
+                self.pending = None;
+                self.hits = Some((id, hits));
+            }
+        }
+        ui.horizontal(|ui| {
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.query)
+                    .hint_text(tr!("knowledge-debug-hint"))
+                    .desired_width(320.0),
+            );
+            let entered = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            let ready = self.pending.is_none() && !self.query.trim().is_empty();
+            if ready && (ui.button(tr!("knowledge-debug-run")).clicked() || entered) {
+                let (tx, rx) = oneshot::channel();
+                self.pending = Some(rx);
+                let query = self.query.trim().to_owned();
+                let settings = settings.clone();
+                let ctx = ui.ctx().clone();
+                tokio::spawn(async move {
+                    let limit = settings.retrieval.top_k + DEBUG_EXTRA_HITS;
+                    let hits = rank(&settings, id, &query, limit)
+                        .await
+                        .map_err(|e| e.to_string());
+                    let _ = tx.send(hits);
+                    ctx.request_repaint();
+                });
+            }
+            if self.pending.is_some() {
+                ui.spinner();
+            }
+        });
+
+        let Some((hits_id, hits)) = &self.hits else {
+            return;
+        };
+        if *hits_id != id {
+            return;
+        }
+        let hits = match hits {
+            Ok(hits) if hits.is_empty() => {
+                ui.weak(tr!("knowledge-debug-none"));
+                return;
+            }
+            Ok(hits) => hits,
+            Err(e) => {
+                ui.colored_label(ui.visuals().error_fg_color, e);
+                return;
+            }
+        };
+        egui::Grid::new("retrieval_debug")
+            .striped(true)
+            .num_columns(5)
+            .show(ui, |ui| {
+                ui.strong(tr!("knowledge-debug-source"));
+                ui.strong(tr!("knowledge-debug-similarity"));
+                ui.strong(tr!("knowledge-debug-keywords"));
+                ui.strong(tr!("knowledge-debug-score"));
+                ui.strong(tr!("knowledge-debug-injected"));
+                ui.end_row();
+                for hit in hits {
+                    let excerpt: String = hit.citation.excerpt.chars().take(600).collect();
+                    ui.label(hit.citation.label()).on_hover_text(excerpt);
+                    ui.label(format_number(hit.similarity as f64, 2));
+                    ui.label(format_number(hit.keywords as f64, 2));
+                    ui.label(format_number(hit.score as f64, 2));
+                    ui.label(if hit.injected { "✔" } else { "—" });
+                    ui.end_row();
+                }
+            });
+    }
+}
+
+/// Collections chats can pull relevant passages from.
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Knowledge {
+    pub collections: Vec<Collection>,
+    #[serde(skip)]
+    selected: Option<u64>,
+    #[serde(skip)]
+    indexing: Option<Indexing>,
+    /// Collection id and the picked files or folders
+    #[serde(skip)]
+    picking: Option<(u64, oneshot::Receiver<Vec<Source>>)>,
+    #[serde(skip)]
+    new_url: String,
+    /// Changed file counts by collection id, being worked out
+    #[serde(skip)]
+    scanning: Option<oneshot::Receiver<Vec<(u64, usize)>>>,
+    #[serde(skip)]
+    last_scan: Option<Instant>,
+    #[serde(skip)]
+    debug: RetrievalDebug,
+    /// Collection id and the memories of a chat, being extracted
+    #[serde(skip)]
+    remembering: Option<oneshot::Receiver<Result<(u64, Source), String>>>,
+}
+
+impl Knowledge {
+    #[inline]
+    pub fn has_selection(&self) -> bool {
+        self.selected.is_some()
+    }
+
+    fn poll(&mut self) {
+        let Some(indexing) = &mut self.indexing else {
+            return;
+        };
+        while let Ok(event) = indexing.events.try_recv() {
+            match event {
+                IndexEvent::Progress { done, total } => {
+                    indexing.done = done;
+                    indexing.total = total;
+                }
+                IndexEvent::Finished(result) => {
+                    let id = indexing.id;
+                    self.indexing = None;
+                    if let Some(collection) = self.collections.iter_mut().find(|c| c.id == id) {
+                        match result {
+                            Ok(chunks) => {
+                                collection.chunks = chunks;
+                                collection.indexed = Some(Utc::now());
+                                collection.error = None;
+                                collection.pending = Some(0);
+                                collection.checked = collection.indexed;
+                            }
+                            Err(e) => collection.error = Some(e),
+                        }
+                    }
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Checks indexed collections for changed files every `WATCH_INTERVAL`, and updates
+    /// the index of a watched one that has some. Called every frame, whatever tab is open.
+    pub fn watch(&mut self, ctx: &egui::Context, settings: &Settings) {
+        self.poll();
+        self.poll_memories(ctx, settings);
+        if let Some(rx) = &mut self.scanning {
+            match rx.try_recv() {
+                Ok(counts) => {
+                    self.scanning = None;
+                    let now = Utc::now();
+                    for (id, pending) in counts {
+                        if let Some(collection) = self.collections.iter_mut().find(|c| c.id == id) {
+                            collection.pending = Some(pending);
+                            collection.checked = Some(now);
+                        }
+                    }
+                }
+                Err(oneshot::error::TryRecvError::Empty) => return,
+                Err(oneshot::error::TryRecvError::Closed) => self.scanning = None,
+            }
+        }
+
+        if self.indexing.is_none() {
+            // a failed update waits for the user instead of failing again on every check
+            let stale = self
+                .collections
+                .iter_mut()
+                .find(|c| c.watch && c.error.is_none() && c.pending.is_some_and(|n| n > 0));
+            if let Some(collection) = stale {
+                log::debug!("updating the knowledge collection {}", collection.name);
+                self.indexing = Some(start_indexing(collection, false, settings, ctx));
+            }
+        }
+        ctx.request_repaint_after(WATCH_INTERVAL);
+        if self.indexing.is_some()
+            || self
+                .last_scan
+                .is_some_and(|last| last.elapsed() < WATCH_INTERVAL)
+        {
+            return;
+        }
+        self.last_scan = Some(Instant::now());
+
+        let indexed: Vec<(u64, Vec<Source>, Embedder)> = self
+            .collections
+            .iter()
+            .filter(|c| c.indexed.is_some())
+            .map(|c| (c.id, c.sources.clone(), c.embedder))
+            .collect();
+        if indexed.is_empty() {
+            return;
+        }
+        let (tx, rx) = oneshot::channel();
+        self.scanning = Some(rx);
+        let ctx = ctx.clone();
+        // walking a big repository takes a while
+        tokio::task::spawn_blocking(move || {
+            let counts = indexed
+                .into_iter()
+                .filter_map(
+                    |(id, sources, embedder)| match pending_files(id, &sources, embedder) {
+                        Ok(pending) => Some((id, pending)),
+                        Err(e) => {
+                            log::debug!("failed to check knowledge collection {id}: {e}");
+                            None
+                        }
+                    },
+                )
+                .collect();
+            let _ = tx.send(counts);
+            ctx.request_repaint();
+        });
+    }
+
+    #[inline]
+    pub fn is_remembering(&self) -> bool {
+        self.remembering.is_some()
+    }
+
+    /// Has the model pick the lasting facts and decisions out of `chat`, and adds them to
+    /// collection `id`. Remembering a chat again replaces what was taken from it before.
+    pub fn remember(&mut self, chat: &Chat, id: u64, settings: &Settings, ctx: &egui::Context) {
+        let mut conversation = String::new();
+        for message in &chat.messages {
+            if message.is_thought || message.is_error || message.is_marker {
+                continue;
+            }
+            let role = if message.is_user() {
+                "User"
+            } else {
+                "Assistant"
+            };
+            let _ = write!(conversation, "{role}: {}\n\n", message.content.trim());
+        }
+        let title = if chat.summary.is_empty() {
+            tr!("new-chat")
+        } else {
+            chat.summary.clone()
+        };
+        let chat = chat.id;
+        let settings = settings.clone();
+        let ctx = ctx.clone();
+        let (tx, rx) = oneshot::channel();
+        self.remembering = Some(rx);
+        tokio::spawn(async move {
+            let result = geminid_core::pipeline::generate_once(
+                &settings.connection(),
+                MEMORY_MODEL,
+                Some(MEMORY_PROMPT),
+                &conversation,
+            )
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|text| {
+                let text = text.trim();
+                if text.is_empty() || text == "NONE" {
+                    Err(tr!("knowledge-memory-empty"))
+                } else {
+                    Ok(text.to_owned())
+                }
+            });
+            if let Err(e) = &result {
+                log::warn!("failed to remember the chat: {e}");
+            }
+            let _ = tx.send(result.map(|text| {
+                let memory = Source::Memory {
+                    chat,
+                    time: Utc::now(),
+                    title,
+                    text,
+                };
+                (id, memory)
+            }));
+            ctx.request_repaint();
+        });
+    }
+
+    fn poll_memories(&mut self, ctx: &egui::Context, settings: &Settings) {
+        let Some(rx) = &mut self.remembering else {
+            return;
+        };
+        let Ok(result) = rx.try_recv() else {
+            return;
+        };
+        self.remembering = None;
+        let Ok((id, memory)) = result else {
+            return;
+        };
+        let Some(collection) = self.collections.iter_mut().find(|c| c.id == id) else {
+            return;
+        };
+        if let Source::Memory { chat, .. } = &memory {
+            collection
+                .sources
+                .retain(|s| !matches!(s, Source::Memory { chat: c, .. } if c == chat));
+        }
+        collection.sources.push(memory);
+        if self.indexing.is_none() {
+            let full = collection.indexed.is_none();
+            self.indexing = Some(start_indexing(collection, full, settings, ctx));
+        } else {
+            collection.pending = Some(collection.pending.unwrap_or(0) + 1);
+        }
+    }
+
+    /// Shows the collection list. Returns the id of a removed collection, so chats can let go of it.
+    pub fn show_sidebar(&mut self, ui: &mut egui::Ui) -> Option<u64> {
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add(
+                    egui::Button::new(format!("➕ {}", tr!("knowledge-add")))
+                        .min_size(egui::vec2(0.0, 24.0)),
+                )
+                .clicked()
+            {
+                let id = self.collections.iter().map(|c| c.id).max().unwrap_or(0) + 1;
+                self.collections.push(Collection {
+                    id,
+                    name: tr!("knowledge-new"),
+                    watch: true,
+                    ..Default::default()
+                });
+                self.selected = Some(id);
+            }
+        });
+        ui.add_space(2.0);
+
+        if self.collections.is_empty() {
+            ui.weak(tr!("knowledge-none"));
+        }
+        let mut remove = None;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for collection in &self.collections {
+                ui.horizontal(|ui| {
+                    if ui
+                        .selectable_label(
+                            self.selected == Some(collection.id),
+                            format!("📚 {}", collection.name),
+                        )
+                        .on_hover_text(tr!("knowledge-chunks", count = collection.chunks))
+                        .clicked()
+                    {
+                        self.selected = Some(collection.id);
+                    }
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui
+                            .add(
+                                egui::Button::new("❌")
+                                    .small()
+                                    .fill(Color32::TRANSPARENT)
+                                    .stroke(Stroke::NONE),
+                            )
+                            .on_hover_text(tr!("knowledge-remove"))
+                            .clicked()
+                        {
+                            remove = Some(collection.id);
+                        }
+                    });
+                });
+            }
+        });
+
+        if let Some(id) = remove {
+            if let Some(indexing) = self.indexing.as_ref().filter(|i| i.id == id) {
+                indexing.cancel.store(true, Ordering::SeqCst);
+                self.indexing = None;
+            }
+            self.collections.retain(|c| c.id != id);
+            remove_index(id);
+            if self.selected == Some(id) {
+                self.selected = None;
+            }
+        }
+        remove
+    }
+
+    pub fn show_editor(&mut self, ui: &mut egui::Ui, settings: &Settings) {
+        let Some(collection) = self
+            .selected
+            .and_then(|id| self.collections.iter_mut().find(|c| c.id == id))
+        else {
+            return;
+        };
+        if let Some((id, rx)) = &mut self.picking {
+            if *id != collection.id {
+                self.picking = None;
+            } else if let Ok(sources) = rx.try_recv() {
+                for source in sources {
+                    if !collection.sources.contains(&source) {
+                        collection.sources.push(source);
+                    }
+                }
+                self.picking = None;
+            }
+        }
+
+        ui.add(
+            egui::TextEdit::singleline(&mut collection.name)
+                .hint_text(tr!("knowledge-name"))
+                .desired_width(f32::INFINITY),
+        );
+        ui.weak(tr!("knowledge-help"));
+        ui.add_space(8.0);
+
+        ui.label(tr!("knowledge-sources"));
+        let mut remove = None;
+        for (i, source) in collection.sources.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if ui.small_button("❌").clicked() {
+                    remove = Some(i);
+                }
+                let icon = match source {
+                    Source::Path(path) if path.is_dir() => "📁",
+                    Source::Path(_) => "📄",
+                    Source::Url(_) => "🌐",
+                    Source::Repository(_) => "🗂",
+                    Source::Memory { .. } => "🧠",
+                };
+                let label = ui.label(format!("{icon} {}", source.label()));
+                if let Source::Memory { text, .. } = source {
+                    label.on_hover_text(text);
+                }
+            });
+        }
+        if let Some(i) = remove {
+            collection.sources.remove(i);
+        }
+
+        ui.horizontal(|ui| {
+            let picking = self.picking.is_some();
+            let folder = ui
+                .add_enabled(!picking, egui::Button::new(tr!("knowledge-add-folder")))
+                .clicked();
+            let files = ui
+                .add_enabled(!picking, egui::Button::new(tr!("knowledge-add-files")))
+                .clicked();
+            let repository = ui
+                .add_enabled(!picking, egui::Button::new(tr!("knowledge-add-repository")))
+                .on_hover_text(tr!("knowledge-add-repository-help"))
+                .clicked();
+            if folder || files || repository {
+                let (tx, rx) = oneshot::channel();
+                self.picking = Some((collection.id, rx));
+                let ctx = ui.ctx().clone();
+                tokio::spawn(async move {
+                    let dialog = rfd::AsyncFileDialog::new();
+                    let paths = if files {
+                        dialog.pick_files().await.unwrap_or_default()
+                    } else {
+                        dialog.pick_folder().await.into_iter().collect()
+                    };
+                    let paths = paths.iter().map(|f| f.path().to_owned());
+                    let sources = if repository {
+                        paths.map(Source::Repository).collect()
+                    } else {
+                        paths.map(Source::Path).collect()
+                    };
+                    let _ = tx.send(sources);
+                    ctx.request_repaint();
+                });
+            }
+        });
+        ui.horizontal(|ui| {
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.new_url)
+                    .hint_text(tr!("knowledge-url-hint"))
+                    .desired_width(320.0),
+            );
+            let entered = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            let url = self.new_url.trim();
+            let valid = url::Url::parse(url).is_ok_and(|u| matches!(u.scheme(), "http" | "https"));
+            if (ui
+                .add_enabled(valid, egui::Button::new(tr!("knowledge-add-url")))
+                .clicked()
+                || entered && valid)
+                && !collection.sources.contains(&Source::Url(url.to_owned()))
+            {
+                collection.sources.push(Source::Url(url.to_owned()));
+                self.new_url.clear();
+            }
+        });
+
+        ui.add_space(8.0);
+        ui.separator();
+        match collection.indexed {
+            Some(time) => ui.weak(tr!(
+                "knowledge-status",
+                count = collection.chunks,
+                time = format_time(time)
+            )),
+            None => ui.weak(tr!("knowledge-not-indexed")),
+        };
+        if collection.indexed.is_some() {
+            match collection.pending {
+                Some(0) => {
+                    let time = collection.checked.map(format_time).unwrap_or_default();
+                    ui.weak(tr!("knowledge-up-to-date", time = time));
+                }
+                Some(count) => {
+                    ui.weak(tr!("knowledge-pending", count = count));
+                }
+                None => (),
+            }
+        }
+        ui.horizontal(|ui| {
+            ui.label(tr!("knowledge-embedder"))
+                .on_hover_text(tr!("knowledge-embedder-help"));
+            let before = collection.embedder;
+            egui::ComboBox::from_id_salt("knowledge_embedder")
+                .selected_text(collection.embedder.label())
+                .show_ui(ui, |ui| {
+                    for embedder in Embedder::ALL {
+                        ui.selectable_value(&mut collection.embedder, embedder, embedder.label());
+                    }
+                });
+            if collection.embedder != before && collection.indexed.is_some() {
+                // the next scan counts the exact number
+                collection.pending = Some(collection.pending.unwrap_or(0).max(1));
+            }
+        });
+        #[cfg(not(feature = "local-embeddings"))]
+        if collection.embedder == Embedder::Local {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                tr!("embedder-local-unsupported"),
+            );
+        }
+        crate::widgets::toggle_setting(
+            ui,
+            &mut collection.watch,
+            &tr!("knowledge-watch"),
+            &tr!("knowledge-watch-help"),
+        );
+        if let Some(error) = &collection.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+
+        let busy = self.indexing.as_ref().map(|i| i.id);
+        if let Some(indexing) = self.indexing.as_ref().filter(|i| i.id == collection.id) {
+            ui.horizontal(|ui| {
+                let fraction = if indexing.total == 0 {
+                    0.0
+                } else {
+                    indexing.done as f32 / indexing.total as f32
+                };
+                ui.add(
+                    egui::ProgressBar::new(fraction)
+                        .desired_width(240.0)
+                        .text(tr!(
+                            "knowledge-progress",
+                            done = indexing.done,
+                            total = indexing.total
+                        )),
+                );
+                if ui.button(tr!("cancel-button")).clicked() {
+                    indexing.cancel.store(true, Ordering::SeqCst);
+                }
+            });
+        } else {
+            let label = if collection.indexed.is_some() {
+                tr!("knowledge-reindex")
+            } else {
+                tr!("knowledge-index")
+            };
+            let idle = busy.is_none() && !collection.sources.is_empty();
+            ui.horizontal(|ui| {
+                if ui.add_enabled(idle, egui::Button::new(label)).clicked() {
+                    self.indexing = Some(start_indexing(collection, true, settings, ui.ctx()));
+                }
+                if collection.pending.is_some_and(|n| n > 0)
+                    && ui
+                        .add_enabled(idle, egui::Button::new(tr!("knowledge-update")))
+                        .on_hover_text(tr!("knowledge-update-help"))
+                        .clicked()
+                {
+                    self.indexing = Some(start_indexing(collection, false, settings, ui.ctx()));
+                }
+            });
+        }
+
+        if collection.indexed.is_some() {
+            ui.add_space(8.0);
+            egui::CollapsingHeader::new(tr!("knowledge-debug"))
+                .default_open(false)
+                .show(ui, |ui| {
+                    ui.weak(tr!("knowledge-debug-help"));
+                    self.debug.show(ui, collection.id, settings);
+                });
+        }
+    }
+}
+
+/// Combobox for the collection a chat draws from.
+pub fn picker(ui: &mut egui::Ui, collections: &[Collection], selected: &mut Option<u64>) {
+    let selected_text = selected
+        .and_then(|id| collections.iter().find(|c| c.id == id))
+        .map_or_else(|| tr!("knowledge-off"), |c| c.name.clone());
+    egui::ComboBox::from_id_salt("knowledge_picker")
+        .selected_text(selected_text)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(selected, None, tr!("knowledge-off"));
+            for collection in collections {
+                ui.selectable_value(selected, Some(collection.id), &collection.name);
+            }
+        });
+}
+
+/// Opens the cited file with `editor_command` at the cited line, or with the default app.
+fn open_citation(citation: &Citation, editor_command: &str) {
+    let local = Path::new(&citation.source).is_file();
+    let result = match editor_command.split_whitespace().next() {
+        Some(program) if local => {
+            let line = citation.lines.map_or(1, |(start, _)| start).to_string();
+            std::process::Command::new(program)
+                .args(editor_command.split_whitespace().skip(1).map(|arg| {
+                    arg.replace("{path}", &citation.source)
+                        .replace("{line}", &line)
+                }))
+                .spawn()
+                .map(drop)
+        }
+        _ => open::that(&citation.source),
+    };
+    if let Err(e) = result {
+        log::error!("failed to open {}: {e}", citation.source);
+    }
+}
+
+fn superscript(n: usize) -> String {
+    const DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+    n.to_string()
+        .bytes()
+        .map(|b| DIGITS[usize::from(b - b'0')])
+        .collect()
+}
+
+/// Turns the `[1]` markers of an answer into footnote numbers matching the citations
+/// under it. Code and markdown links like `[1](...)` are left alone, as are numbers
+/// past `count`.
+pub fn footnote_markers(content: &str, count: usize) -> Cow<'_, str> {
+    if count == 0 || !content.contains('[') {
+        return Cow::Borrowed(content);
+    }
+    let mut out = String::with_capacity(content.len());
+    let mut in_fence = false;
+    for line in content.split_inclusive('\n') {
+        if matches!(line.trim_start().get(..3), Some("```" | "~~~")) {
+            in_fence = !in_fence;
+        }
+        if in_fence || line.starts_with("    ") {
+            out.push_str(line);
+            continue;
+        }
+        let mut in_code = false;
+        let mut rest = line;
+        while let Some(c) = rest.chars().next() {
+            if c == '`' {
+                in_code = !in_code;
+            }
+            let marker = (c == '[' && !in_code)
+                .then(|| rest[1..].split_once(']'))
+                .flatten()
+                .and_then(|(number, after)| {
+                    let n = number.parse::<usize>().ok()?;
+                    let link = after.starts_with(['(', '[', ':']);
+                    ((1..=count).contains(&n) && !link).then_some((n, number.len() + 2))
+                });
+            match marker {
+                Some((n, len)) => {
+                    out.push_str(&superscript(n));
+                    rest = &rest[len..];
+                }
+                None => {
+                    out.push(c);
+                    rest = &rest[c.len_utf8()..];
+                }
+            }
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// The sources an answer drew from, as footnotes under the message. Each one expands
+/// to the excerpt that went into the prompt.
+/// Lists the excerpts an answer was given. Returns the source whose quarantine was
+/// toggled.
+pub fn show_citations(
+    ui: &mut egui::Ui,
+    idx: usize,
+    citations: &[Citation],
+    editor_command: &str,
+) -> Option<String> {
+    let mut toggled = None;
+    egui::CollapsingHeader::new(tr!("knowledge-citations", count = citations.len()))
+        .id_salt(("citations", idx))
+        .default_open(true)
+        .show(ui, |ui| {
+            for (i, citation) in citations.iter().enumerate() {
+                let mut title =
+                    RichText::new(format!("{} {}", superscript(i + 1), citation.label()));
+                if citation.quarantined {
+                    title = title.strikethrough().weak();
+                } else if citation.suspicious.is_some() {
+                    title = title.color(ui.visuals().warn_fg_color);
+                }
+                egui::CollapsingHeader::new(title)
+                    .id_salt(("citation", idx, i))
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        if let Some(found) = &citation.suspicious {
+                            ui.horizontal(|ui| {
+                                if crate::injection::badge(ui, found, citation.quarantined) {
+                                    toggled = Some(citation.source.clone());
+                                }
+                            });
+                        } else if citation.quarantined
+                            && ui.small_button(tr!("injection-release")).clicked()
+                        {
+                            toggled = Some(citation.source.clone());
+                        }
+                        if citation.openable() && ui.link(tr!("knowledge-open-source")).clicked() {
+                            open_citation(citation, editor_command);
+                        }
+                        egui::ScrollArea::vertical()
+                            .id_salt(("citation_excerpt", idx, i))
+                            .max_height(240.0)
+                            .show(ui, |ui| {
+                                let excerpt = RichText::new(&citation.excerpt);
+                                // code keeps its indentation
+                                if citation.lines.is_some() {
+                                    ui.label(excerpt.monospace());
+                                } else {
+                                    ui.label(excerpt);
+                                }
+                            });
+                    });
+            }
+        });
+    toggled
+}
@@ -0,0 +1,182 @@
+use crate::i18n::tr;
+use eframe::egui::{self, Color32, RichText};
+use geminid_core::logger::{self, LogEntry};
+use log::Level;
+use std::{fmt::Write, time::Duration};
+
+/// How often the open viewer picks up new entries.
+const REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+const LEVELS: [Level; 4] = [Level::Error, Level::Warn, Level::Info, Level::Debug];
+
+fn level_color(ui: &egui::Ui, level: Level) -> Color32 {
+    match level {
+        Level::Error => ui.visuals().error_fg_color,
+        Level::Warn => ui.visuals().warn_fg_color,
+        Level::Info => ui.visuals().text_color(),
+        Level::Debug | Level::Trace => ui.visuals().weak_text_color(),
+    }
+}
+
+/// Everything kept in memory as plain text, with the version and platform on top
+/// for bug reports.
+fn report() -> String {
+    let mut text = format!(
+        "{} {} on {} {}\n\n",
+        crate::TITLE,
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+    logger::with_entries(|entries| {
+        for entry in entries {
+            let _ = writeln!(text, "{entry}");
+        }
+    });
+    text
+}
+
+/// The app's log, filtered by level and text, for finding out what went wrong.
+pub struct LogViewer {
+    pub open: bool,
+    /// Least severe level shown
+    level: Level,
+    search: String,
+    /// Keep scrolled to the newest entry
+    follow: bool,
+}
+
+impl Default for LogViewer {
+    fn default() -> Self {
+        Self {
+            open: false,
+            level: Level::Info,
+            search: String::new(),
+            follow: true,
+        }
+    }
+}
+
+impl LogViewer {
+    #[inline]
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    fn export(ctx: &egui::Context) {
+        let text = report();
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            let Some(file) = rfd::AsyncFileDialog::new()
+                .set_file_name("geminid-logs.txt")
+                .save_file()
+                .await
+            else {
+                return;
+            };
+            match tokio::fs::write(file.path(), text).await {
+                Ok(()) => log::info!("exported the logs to {}", file.path().display()),
+                Err(e) => log::error!("failed to export the logs: {e}"),
+            }
+            ctx.request_repaint();
+        });
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        if !self.open {
+            return;
+        }
+        ctx.request_repaint_after(REFRESH_INTERVAL);
+        let mut open = true;
+        egui::Window::new(tr!("logs-title"))
+            .open(&mut open)
+            .default_size([720.0, 420.0])
+            .show(ctx, |ui| self.show_inner(ui));
+        self.open = open;
+    }
+
+    fn show_inner(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt("logs_level")
+                .selected_text(self.level.as_str())
+                .show_ui(ui, |ui| {
+                    for level in LEVELS {
+                        ui.selectable_value(&mut self.level, level, level.as_str());
+                    }
+                });
+            ui.add(
+                egui::TextEdit::singleline(&mut self.search)
+                    .hint_text(tr!("logs-search"))
+                    .desired_width(200.0),
+            );
+            ui.checkbox(&mut self.follow, tr!("logs-follow"));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button(tr!("logs-export")).clicked() {
+                    Self::export(ui.ctx());
+                }
+                if ui.button(tr!("logs-copy")).clicked() {
+                    ui.ctx().copy_text(report());
+                }
+                if let Some(dir) = logger::log_dir() {
+                    if ui
+                        .button(tr!("logs-open-folder"))
+                        .on_hover_text(dir.display().to_string())
+                        .clicked()
+                    {
+                        if let Err(e) = open::that(dir) {
+                            log::error!("failed to open {}: {e}", dir.display());
+                        }
+                    }
+                }
+            });
+        });
+        ui.separator();
+
+        let search = self.search.to_lowercase();
+        // copied out, logging while the entries are locked would deadlock
+        let entries: Vec<LogEntry> = logger::with_entries(|entries| {
+            entries
+                .iter()
+                .filter(|e| e.level <= self.level)
+                .filter(|e| {
+                    search.is_empty()
+                        || e.message.to_lowercase().contains(&search)
+                        || e.target.to_lowercase().contains(&search)
+                })
+                .cloned()
+                .collect()
+        });
+        if entries.is_empty() {
+            ui.weak(tr!("logs-empty"));
+            return;
+        }
+        let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+        egui::ScrollArea::vertical()
+            .auto_shrink(false)
+            .stick_to_bottom(self.follow)
+            .show_rows(ui, row_height, entries.len(), |ui, rows| {
+                for entry in &entries[rows] {
+                    let color = level_color(ui, entry.level);
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new(entry.time.format("%H:%M:%S").to_string())
+                                .monospace()
+                                .weak(),
+                        );
+                        ui.label(
+                            RichText::new(format!("{:<5}", entry.level))
+                                .monospace()
+                                .color(color),
+                        );
+                        ui.add(
+                            egui::Label::new(
+                                RichText::new(format!("{}: {}", entry.target, entry.message))
+                                    .monospace()
+                                    .color(color),
+                            )
+                            .truncate(),
+                        );
+                    });
+                }
+            });
+    }
+}
@@ -3,28 +3,77 @@
 
 use eframe::egui;
 use sessions::Sessions;
+mod analytics;
+mod archive;
+mod attachment_search;
+mod audio;
+mod automation;
+mod batch;
+mod bidi;
 mod chat;
 mod chat_completion;
+mod compare;
+mod context_cache;
+mod deeplink;
+mod diagnostics;
 mod easymark;
+mod embedding_playground;
 mod file_handler;
-mod logger;
+mod find;
+mod folder_bundle;
+mod folders;
+mod functions;
+mod grounding;
+mod i18n;
+mod image_edit;
+mod images;
+mod injection;
+mod instance;
+mod keymap;
+mod knowledge;
+mod logs;
+mod model_compare;
+mod models;
+#[cfg(feature = "notifications")]
+mod notifications;
+mod onboarding;
+mod palette;
+mod persona;
+mod plugins;
+mod prompt_improver;
+mod prompt_library;
+mod reading;
+#[cfg(feature = "microphone")]
+mod recorder;
+mod request_preview;
+mod retry;
+mod safety;
+mod scheduler;
+#[cfg(feature = "screenshots")]
+mod screenshot;
+mod scripting;
+mod search;
+mod semantic;
 mod sessions;
+mod snippets;
+mod spellcheck;
 mod style;
+mod structured;
+mod summarize;
+mod tags;
+mod theme;
+mod timeline;
+mod timestamps;
+#[cfg(feature = "tray")]
+mod tray;
+mod verify;
+mod video;
 mod widgets;
+mod youtube;
+
+use geminid_core::files::{IMAGE_FORMATS, MUSIC_FORMATS, TEXT_FORMATS, VIDEO_FORMATS};
 
 const TITLE: &str = "GeminiD";
-const IMAGE_FORMATS: &[&str] = &[
-    "bmp", "dds", "ff", "gif", "hdr", "ico", "jpeg", "jpg", "exr", "png", "pnm", "qoi", "tga",
-    "tiff", "webp",
-];
-const VIDEO_FORMATS: &[&str] = &["mp4", "mpeg", "mov", "avi", "flv", "webm"];
-const TEXT_FORMATS: &[&str] = &[
-    "txt", "md", "rs", "py", "js", "html", "css", "json", "toml", "yaml", "log", "csv", "xml",
-    "pdf",
-];
-const MUSIC_FORMATS: &[&str] = &[
-    "aac", "flac", "mp3", "m4a", "mpeg", "mpga", "opus", "pcm", "wav", "webm", "aiff", "ogg",
-];
 
 fn load_icon() -> egui::IconData {
     let (icon_rgba, icon_width, icon_height) = {
@@ -46,15 +95,40 @@ fn load_icon() -> egui::IconData {
 
 #[tokio::main]
 async fn main() {
-    logger::init().expect("failed to initialize logger");
+    geminid_core::logger::init(eframe::storage_dir(TITLE).map(|dir| dir.join("logs")))
+        .expect("failed to initialize logger");
+    geminid_core::files::init(eframe::storage_dir(TITLE));
+    let automation = automation::Options::from_args();
+    // held until the app exits, later launches find it taken and forward to this one
+    let (_lock, listener) = if automation.single_instance() {
+        match instance::lock() {
+            Ok(Some(lock)) => (Some(lock), instance::Listener::bind().await),
+            Ok(None) => {
+                if instance::forward(&automation.open).await {
+                    log::info!("handed the arguments to the running instance");
+                } else {
+                    log::error!("the running instance didn't answer, not starting another one");
+                }
+                return;
+            }
+            Err(e) => {
+                log::error!("failed to take the instance lock, starting without it: {e}");
+                (None, None)
+            }
+        }
+    } else {
+        (None, None)
+    };
     let native_options = eframe::NativeOptions {
-        viewport: egui::ViewportBuilder::default().with_icon(load_icon()),
+        viewport: egui::ViewportBuilder::default()
+            .with_icon(load_icon())
+            .with_visible(!automation.headless),
         ..Default::default()
     };
     eframe::run_native(
         TITLE,
         native_options,
-        Box::new(|cc| Ok(Box::new(Geminid::new(cc)))),
+        Box::new(|cc| Ok(Box::new(Geminid::new(cc, automation, listener)))),
     )
     .expect("failed to run app");
 }
@@ -65,7 +139,11 @@ struct Geminid {
 }
 
 impl Geminid {
-    fn new(cc: &eframe::CreationContext<'_>) -> Self {
+    fn new(
+        cc: &eframe::CreationContext<'_>,
+        automation: automation::Options,
+        listener: Option<instance::Listener>,
+    ) -> Self {
         style::set_style(&cc.egui_ctx);
         egui_extras::install_image_loaders(&cc.egui_ctx);
 
@@ -74,19 +152,35 @@ impl Geminid {
             eframe::storage_dir(TITLE)
         );
 
-        if let Some(storage) = cc.storage {
-            if let Some(app_state) = eframe::get_value::<Self>(storage, eframe::APP_KEY) {
-                log::info!("app state successfully restored from storage");
-                return app_state;
-            }
-        }
-
+        let mut app = cc
+            .storage
+            .and_then(|storage| eframe::get_value::<Self>(storage, eframe::APP_KEY))
+            .inspect(|_| log::info!("app state successfully restored from storage"))
+            .unwrap_or_else(|| {
+                let mut app = Self::default();
+                if app.sessions.try_restore_autosave() {
+                    log::error!("app state is not saved in storage. This is a bug!");
+                    log::info!("Disaster recovery successful.");
+                }
+                app
+            });
 
-        let mut app = Self::default();
-        if app.sessions.try_restore_autosave() {
-            log::error!("app state is not saved in storage. This is a bug!");
-            log::info!("Disaster recovery successful.");
+        // before anything builds translated text outside of a frame, like the tray menu
+        i18n::set_language(
+            app.sessions
+                .settings
+                .language
+                .unwrap_or_else(i18n::Language::system),
+        );
+        #[cfg(feature = "tray")]
+        app.sessions.init_tray(&cc.egui_ctx);
+        if let Some(transport) = automation.transport {
+            app.sessions.init_automation(&cc.egui_ctx, transport);
+        }
+        if let Some(listener) = listener {
+            app.sessions.init_instance(&cc.egui_ctx, listener);
         }
+        app.sessions.open_request(&cc.egui_ctx, automation.open);
 
         app
     }
@@ -0,0 +1,318 @@
+use crate::{
+    file_handler::Attachment,
+    i18n::{format_number, tr},
+    widgets::{GeminiModel, ModelPicker, Settings},
+};
+use eframe::egui;
+use egui_commonmark::CommonMarkCache;
+use gemini_rust::UsageMetadata;
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+use tokio::sync::{mpsc, oneshot};
+
+/// Most models answering side by side, more columns get too narrow to read.
+const MAX_MODELS: usize = 3;
+
+enum Update {
+    Text(String),
+    Done(Result<Option<UsageMetadata>, String>),
+}
+
+/// One model's answer.
+struct Column {
+    model: GeminiModel,
+    text: String,
+    started: Instant,
+    elapsed: Option<Duration>,
+    usage: Option<UsageMetadata>,
+    error: Option<String>,
+    task: tokio::task::AbortHandle,
+}
+
+impl Column {
+    fn is_running(&self) -> bool {
+        self.elapsed.is_none()
+    }
+}
+
+/// Sends the same prompt to a few models at once and shows their streamed answers
+/// next to each other.
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct ModelCompare {
+    pub models: Vec<GeminiModel>,
+    pub prompt: String,
+    #[serde(skip)]
+    pub open: bool,
+    #[serde(skip)]
+    files: Vec<Attachment>,
+    #[serde(skip)]
+    picking: Option<oneshot::Receiver<Vec<PathBuf>>>,
+    #[serde(skip)]
+    columns: Vec<Column>,
+    #[serde(skip)]
+    updates: Option<mpsc::UnboundedReceiver<(usize, Update)>>,
+}
+
+impl Default for ModelCompare {
+    fn default() -> Self {
+        Self {
+            models: vec![GeminiModel::Gemini30Flash, GeminiModel::Gemini30Pro],
+            prompt: String::new(),
+            open: false,
+            files: Vec::new(),
+            picking: None,
+            columns: Vec::new(),
+            updates: None,
+        }
+    }
+}
+
+impl ModelCompare {
+    fn poll(&mut self) {
+        if let Some(rx) = &mut self.picking {
+            if let Ok(paths) = rx.try_recv() {
+                self.files
+                    .extend(paths.into_iter().map(Attachment::from_path));
+                self.picking = None;
+            }
+        }
+        let Some(rx) = &mut self.updates else {
+            return;
+        };
+        while let Ok((index, update)) = rx.try_recv() {
+            let Some(column) = self.columns.get_mut(index) else {
+                continue;
+            };
+            match update {
+                Update::Text(text) => column.text.push_str(&text),
+                Update::Done(result) => {
+                    column.elapsed = Some(column.started.elapsed());
+                    match result {
+                        Ok(usage) => column.usage = usage,
+                        Err(e) => column.error = Some(e),
+                    }
+                }
+            }
+        }
+        if !self.columns.iter().any(Column::is_running) {
+            self.updates = None;
+        }
+    }
+
+    fn pick(&mut self, ctx: &egui::Context) {
+        let (tx, rx) = oneshot::channel();
+        self.picking = Some(rx);
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            let files = rfd::AsyncFileDialog::new()
+                .pick_files()
+                .await
+                .unwrap_or_default();
+            let _ = tx.send(files.iter().map(|f| f.path().to_owned()).collect());
+            ctx.request_repaint();
+        });
+    }
+
+    /// Asks every model at once, with the system prompt and inference settings of `picker`.
+    fn run(&mut self, ctx: &egui::Context, settings: &Settings, picker: &ModelPicker) {
+        self.stop();
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.updates = Some(rx);
+        self.columns = self
+            .models
+            .iter()
+            .enumerate()
+            .map(|(index, &model)| {
+                let settings = settings.clone();
+                let system_prompt = picker.system_prompt.clone();
+                let config = picker.get_generation_config();
+                let prompt = self.prompt.clone();
+                let files = self.files.clone();
+                let tx = tx.clone();
+                let ctx = ctx.clone();
+                let task = tokio::spawn(async move {
+                    let result = geminid_core::pipeline::generate_streamed(
+                        &settings.connection(),
+                        model,
+                        system_prompt.as_deref(),
+                        &prompt,
+                        &files,
+                        config,
+                        |text| {
+                            let _ = tx.send((index, Update::Text(text.to_owned())));
+                            ctx.request_repaint();
+                        },
+                    )
+                    .await;
+                    if let Err(e) = &result {
+                        log::error!("{model} failed to answer the comparison: {e}");
+                    }
+                    let _ = tx.send((index, Update::Done(result.map_err(|e| e.to_string()))));
+                    ctx.request_repaint();
+                });
+                Column {
+                    model,
+                    text: String::new(),
+                    started: Instant::now(),
+                    elapsed: None,
+                    usage: None,
+                    error: None,
+                    task: task.abort_handle(),
+                }
+            })
+            .collect();
+    }
+
+    fn stop(&mut self) {
+        for column in self.columns.iter_mut().filter(|c| c.is_running()) {
+            column.task.abort();
+            column.elapsed = Some(column.started.elapsed());
+        }
+        self.updates = None;
+    }
+
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        settings: &Settings,
+        picker: &ModelPicker,
+        commonmark_cache: &mut CommonMarkCache,
+    ) {
+        self.poll();
+        if !self.open {
+            return;
+        }
+        let mut open = true;
+        egui::Window::new(tr!("model-compare-title"))
+            .open(&mut open)
+            .default_size([960.0, 640.0])
+            .show(ctx, |ui| {
+                self.show_inner(ui, settings, picker, commonmark_cache)
+            });
+        self.open = open;
+    }
+
+    fn show_inner(
+        &mut self,
+        ui: &mut egui::Ui,
+        settings: &Settings,
+        picker: &ModelPicker,
+        commonmark_cache: &mut CommonMarkCache,
+    ) {
+        ui.weak(tr!("model-compare-help"));
+        let running = self.columns.iter().any(Column::is_running);
+
+        ui.horizontal(|ui| {
+            // at least two stay to compare
+            let removable = self.models.len() > 2;
+            let mut removed = None;
+            for (index, model) in self.models.iter_mut().enumerate() {
+                crate::widgets::model_selector(ui, ("model_compare", index), model);
+                if removable && ui.small_button("🗙").clicked() {
+                    removed = Some(index);
+                }
+            }
+            if let Some(index) = removed {
+                self.models.remove(index);
+            }
+            if self.models.len() < MAX_MODELS
+                && ui
+                    .small_button("➕")
+                    .on_hover_text(tr!("model-compare-add"))
+                    .clicked()
+            {
+                let next = self.models.last().copied().unwrap_or_default();
+                self.models.push(next);
+            }
+        });
+
+        ui.add(
+            egui::TextEdit::multiline(&mut self.prompt)
+                .hint_text(tr!("model-compare-prompt-hint"))
+                .desired_rows(3)
+                .desired_width(f32::INFINITY),
+        );
+        if !self.files.is_empty() {
+            ui.horizontal(|ui| {
+                egui::ScrollArea::horizontal()
+                    .id_salt("model_compare_files")
+                    .show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            crate::file_handler::show_files(ui, &mut self.files, true);
+                        });
+                    });
+            });
+        }
+
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(self.picking.is_none(), egui::Button::new("📎"))
+                .on_hover_text(tr!("model-compare-attach"))
+                .clicked()
+            {
+                self.pick(ui.ctx());
+            }
+            let ready = !self.prompt.trim().is_empty() || !self.files.is_empty();
+            if running {
+                if ui.button(tr!("model-compare-stop")).clicked() {
+                    self.stop();
+                }
+                ui.spinner();
+            } else if ui
+                .add_enabled(ready, egui::Button::new(tr!("model-compare-run")))
+                .clicked()
+            {
+                self.run(ui.ctx(), settings, picker);
+            }
+        });
+
+        if self.columns.is_empty() {
+            return;
+        }
+        ui.separator();
+        let columns = &self.columns;
+        ui.columns(columns.len(), |uis| {
+            for (index, (ui, column)) in uis.iter_mut().zip(columns).enumerate() {
+                Self::show_column(ui, index, column, commonmark_cache);
+            }
+        });
+    }
+
+    fn show_column(
+        ui: &mut egui::Ui,
+        index: usize,
+        column: &Column,
+        commonmark_cache: &mut CommonMarkCache,
+    ) {
+        ui.horizontal(|ui| {
+            ui.strong(column.model.to_string());
+            let elapsed = column
+                .elapsed
+                .unwrap_or_else(|| column.started.elapsed())
+                .as_secs_f64();
+            ui.weak(format!("{}s", format_number(elapsed, 1)));
+            if let Some(usage) = &column.usage {
+                ui.weak(tr!(
+                    "model-compare-tokens",
+                    tokens = usage.candidates_token_count.unwrap_or(0)
+                ));
+            }
+            if column.is_running() {
+                ui.spinner();
+            }
+        });
+        if let Some(error) = &column.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+        egui::ScrollArea::vertical()
+            .id_salt(("model_compare_column", index))
+            .auto_shrink(false)
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                crate::bidi::show_markdown(ui, commonmark_cache, &column.text, None);
+            });
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{
+    i18n::{format_number, tr},
+    widgets::{AuthMethod, GeminiModel, Settings},
+};
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use std::sync::{PoisonError, RwLock};
+
+const API: &str = "https://generativelanguage.googleapis.com/v1beta/models";
+
+/// A model as `models.list` describes it.
+#[derive(Debug, Clone)]
+pub struct ModelInfo {
+    pub model: GeminiModel,
+    pub display_name: String,
+    pub description: String,
+    /// Context window in tokens
+    pub input_tokens: Option<u64>,
+    pub output_tokens: Option<u64>,
+    /// e.g. `generateContent`, `countTokens`, `createCachedContent`
+    pub methods: Vec<String>,
+}
+
+/// Models listed for the current auth method. Empty until the list arrives, or when
+/// it couldn't be fetched, and then the built-in models are offered.
+static LISTED: RwLock<Vec<ModelInfo>> = RwLock::new(Vec::new());
+
+impl ModelInfo {
+    fn from_json(json: &Value) -> Option<Self> {
+        let name = json.get("name")?.as_str()?;
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_owned()
+        };
+        Some(Self {
+            model: GeminiModel::from_name(name.trim_start_matches("models/")),
+            display_name: string("displayName"),
+            description: string("description"),
+            input_tokens: json.get("inputTokenLimit").and_then(Value::as_u64),
+            output_tokens: json.get("outputTokenLimit").and_then(Value::as_u64),
+            methods: json
+                .get("supportedGenerationMethods")
+                .and_then(Value::as_array)
+                .map(|methods| {
+                    methods
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .map(str::to_owned)
+                        .collect()
+                })
+                .unwrap_or_default(),
+        })
+    }
+
+    fn can_chat(&self) -> bool {
+        self.methods.iter().any(|m| m == "generateContent")
+    }
+
+    /// The tooltip of the model in the pickers.
+    pub fn hover_text(&self) -> String {
+        let tokens = |limit: Option<u64>| {
+            limit.map_or_else(|| "?".to_owned(), |t| format_number(t as f64, 0))
+        };
+        let mut lines = Vec::new();
+        if !self.display_name.is_empty() {
+            lines.push(self.display_name.clone());
+        }
+        if !self.description.is_empty() {
+            lines.push(self.description.clone());
+        }
+        lines.push(tr!(
+            "models-context",
+            input = tokens(self.input_tokens),
+            output = tokens(self.output_tokens)
+        ));
+        lines.push(tr!("models-methods", methods = self.methods.join(", ")));
+        lines.join("\n")
+    }
+}
+
+/// Models to choose from: the listed ones, or the built-in ones while there is no list.
+pub fn available() -> Vec<GeminiModel> {
+    let listed = LISTED.read().unwrap_or_else(PoisonError::into_inner);
+    if listed.is_empty() {
+        enum_iterator::all::<GeminiModel>().collect()
+    } else {
+        listed.iter().map(|info| info.model).collect()
+    }
+}
+
+/// What the list says about `model`, `None` for models it doesn't have.
+pub fn info(model: GeminiModel) -> Option<ModelInfo> {
+    let listed = LISTED.read().unwrap_or_else(PoisonError::into_inner);
+    listed.iter().find(|info| info.model == model).cloned()
+}
+
+pub fn set_listed(models: Vec<ModelInfo>) {
+    log::info!("{} models listed", models.len());
+    *LISTED.write().unwrap_or_else(PoisonError::into_inner) = models;
+}
+
+/// Lists the models the API key can chat with. Code Assist has no such endpoint and
+/// keeps the built-in models, so it gets an empty list.
+pub async fn fetch(settings: &Settings) -> Result<Vec<ModelInfo>> {
+    if settings.auth_method != AuthMethod::ApiKey || settings.api_key.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut builder = reqwest::Client::builder();
+    if let Some(proxy) = settings
+        .proxy_path
+        .as_deref()
+        .filter(|p| !p.trim().is_empty())
+    {
+        builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+    }
+    let client = builder.build()?;
+
+    let mut models = Vec::new();
+    let mut page_token: Option<String> = None;
+    loop {
+        let mut request = client
+            .get(API)
+            .query(&[("pageSize", "1000")])
+            .header("x-goog-api-key", &settings.api_key);
+        if let Some(token) = &page_token {
+            request = request.query(&[("pageToken", token)]);
+        }
+        let response = request.send().await?;
+        let status = response.status();
+        let body: Value = response.json().await.unwrap_or_default();
+        if !status.is_success() {
+            return Err(anyhow!(body["error"]["message"]
+                .as_str()
+                .map(str::to_owned)
+                .unwrap_or_else(|| status.to_string())));
+        }
+        models.extend(
+            body.get("models")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(ModelInfo::from_json)
+                .filter(ModelInfo::can_chat),
+        );
+        page_token = body
+            .get("nextPageToken")
+            .and_then(Value::as_str)
+            .filter(|t| !t.is_empty())
+            .map(str::to_owned);
+        if page_token.is_none() {
+            return Ok(models);
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use eframe::egui;
+use notify_rust::Notification;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+/// Longer responses are cut in the notification body.
+const MAX_BODY_CHARS: usize = 160;
+
+/// Native notifications for finished generations. Clicking one (where the platform
+/// reports it) focuses the window, and the chat is selected on the next frame.
+pub struct Notifier {
+    #[cfg_attr(not(all(unix, not(target_os = "macos"))), allow(dead_code))]
+    clicked_tx: Sender<usize>,
+    clicked_rx: Receiver<usize>,
+}
+
+impl Default for Notifier {
+    fn default() -> Self {
+        let (clicked_tx, clicked_rx) = mpsc::channel();
+        Self {
+            clicked_tx,
+            clicked_rx,
+        }
+    }
+}
+
+/// Whether the user is looking somewhere else and should be told.
+pub fn window_inactive(ctx: &egui::Context) -> bool {
+    ctx.input(|i| {
+        let viewport = i.viewport();
+        viewport.minimized == Some(true) || viewport.focused == Some(false)
+    })
+}
+
+impl Notifier {
+    #[cfg_attr(not(all(unix, not(target_os = "macos"))), allow(unused_variables))]
+    pub fn notify(&self, ctx: &egui::Context, chat_id: usize, title: &str, content: &str) {
+        let line = content.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+        let mut body: String = line.chars().take(MAX_BODY_CHARS).collect();
+        if body.len() < line.len() {
+            body.push('…');
+        }
+
+        let mut notification = Notification::new();
+        notification.appname(crate::TITLE).summary(title).body(&body);
+        #[cfg(all(unix, not(target_os = "macos")))]
+        notification.action("default", &crate::i18n::tr!("notification-open-chat"));
+        let handle = match notification.show() {
+            Ok(handle) => handle,
+            Err(e) => {
+                log::debug!("failed to show notification: {e}");
+                return;
+            }
+        };
+
+        // only the freedesktop backend reports clicks
+        #[cfg(all(unix, not(target_os = "macos")))]
+        {
+            let tx = self.clicked_tx.clone();
+            let ctx = ctx.clone();
+            std::thread::spawn(move || {
+                handle.wait_for_action(|action| {
+                    if action == "default" {
+                        ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+                        ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+                        ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+                        let _ = tx.send(chat_id);
+                        ctx.request_repaint();
+                    }
+                });
+            });
+        }
+        #[cfg(not(all(unix, not(target_os = "macos"))))]
+        drop(handle);
+    }
+
+    /// Id of a chat whose notification was clicked.
+    #[inline]
+    pub fn poll_clicked(&self) -> Option<usize> {
+        self.clicked_rx.try_recv().ok()
+    }
+}
@@ -0,0 +1,285 @@
+use crate::{
+    i18n::tr,
+    widgets::{AuthMethod, Settings},
+};
+use anyhow::{anyhow, Result};
+use eframe::egui::{self, Align2, Color32, RichText};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    #[default]
+    Welcome,
+    Auth,
+    Credentials,
+    Model,
+    Proxy,
+}
+
+impl Step {
+    const ALL: [Self; 5] = [
+        Self::Welcome,
+        Self::Auth,
+        Self::Credentials,
+        Self::Model,
+        Self::Proxy,
+    ];
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|&s| s == self).unwrap_or(0)
+    }
+}
+
+#[derive(Default)]
+pub enum KeyCheck {
+    #[default]
+    Unchecked,
+    Checking,
+    Valid,
+    Invalid(String),
+}
+
+pub enum OnboardingAction {
+    None,
+    ValidateKey,
+    LoginGoogle,
+    /// The wizard was completed or skipped and shouldn't be shown again
+    Finish,
+}
+
+/// First launch setup: authentication, default model and proxy.
+#[derive(Default)]
+pub struct Onboarding {
+    step: Step,
+    pub key_check: KeyCheck,
+    pub logging_in: bool,
+    /// Whether the wizard was opened this session
+    pub shown: bool,
+}
+
+/// Checks the key by listing the available models, which doesn't use any quota.
+pub async fn validate_api_key(api_key: String, proxy: Option<String>) -> Result<()> {
+    let mut builder = reqwest::Client::builder();
+    if let Some(proxy) = proxy {
+        builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+    }
+    let resp = builder
+        .build()?
+        .get("https://generativelanguage.googleapis.com/v1beta/models")
+        .header("x-goog-api-key", api_key)
+        .send()
+        .await?;
+    if resp.status().is_success() {
+        return Ok(());
+    }
+    let status = resp.status();
+    let body: serde_json::Value = resp.json().await.unwrap_or_default();
+    let message = body["error"]["message"]
+        .as_str()
+        .map(str::to_owned)
+        .unwrap_or_else(|| status.to_string());
+    Err(anyhow!(message))
+}
+
+fn explanation(ui: &mut egui::Ui, text: &str) {
+    ui.label(RichText::new(text).weak());
+    ui.add_space(6.0);
+}
+
+impl Onboarding {
+    fn can_continue(&self, settings: &Settings) -> bool {
+        match self.step {
+            Step::Credentials => match settings.auth_method {
+                AuthMethod::ApiKey => !settings.api_key.trim().is_empty(),
+                AuthMethod::CodeAssist => {
+                    !settings.oauth_token.is_empty() && !settings.project_id.is_empty()
+                }
+            },
+            _ => true,
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, settings: &mut Settings) -> OnboardingAction {
+        self.shown = true;
+        let mut action = OnboardingAction::None;
+        egui::Window::new(tr!("onboarding-title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                ui.weak(tr!(
+                    "onboarding-step",
+                    step = self.step.index() + 1,
+                    total = Step::ALL.len(),
+                ));
+                ui.add_space(4.0);
+
+                match self.step {
+                    Step::Welcome => {
+                        ui.heading(tr!("onboarding-welcome"));
+                        explanation(ui, &tr!("onboarding-welcome-text"));
+                    }
+                    Step::Auth => {
+                        ui.heading(tr!("onboarding-auth"));
+                        ui.radio_value(
+                            &mut settings.auth_method,
+                            AuthMethod::ApiKey,
+                            tr!("onboarding-auth-api-key"),
+                        );
+                        explanation(ui, &tr!("onboarding-auth-api-key-text"));
+                        ui.radio_value(
+                            &mut settings.auth_method,
+                            AuthMethod::CodeAssist,
+                            tr!("onboarding-auth-google"),
+                        );
+                        explanation(ui, &tr!("onboarding-auth-google-text"));
+                    }
+                    Step::Credentials => match settings.auth_method {
+                        AuthMethod::ApiKey => {
+                            ui.heading(tr!("onboarding-paste-key"));
+                            ui.horizontal(|ui| {
+                                ui.label(tr!("onboarding-get-key"));
+                                ui.hyperlink("https://aistudio.google.com/apikey");
+                            });
+                            let resp = ui.add(
+                                egui::TextEdit::singleline(&mut settings.api_key)
+                                    .password(true)
+                                    .hint_text("AIza…")
+                                    .desired_width(f32::INFINITY),
+                            );
+                            if resp.changed() {
+                                self.key_check = KeyCheck::Unchecked;
+                            }
+                            ui.horizontal(|ui| {
+                                let checking = matches!(self.key_check, KeyCheck::Checking);
+                                if ui
+                                    .add_enabled(
+                                        !checking && !settings.api_key.trim().is_empty(),
+                                        egui::Button::new(tr!("onboarding-check-key")),
+                                    )
+                                    .clicked()
+                                {
+                                    self.key_check = KeyCheck::Checking;
+                                    action = OnboardingAction::ValidateKey;
+                                }
+                                match &self.key_check {
+                                    KeyCheck::Unchecked => (),
+                                    KeyCheck::Checking => {
+                                        ui.spinner();
+                                    }
+                                    KeyCheck::Valid => {
+                                        ui.colored_label(
+                                            Color32::GREEN,
+                                            tr!("onboarding-key-valid"),
+                                        );
+                                    }
+                                    KeyCheck::Invalid(e) => {
+                                        ui.colored_label(ui.visuals().error_fg_color, "❌")
+                                            .on_hover_text(e);
+                                        ui.label(RichText::new(e).small());
+                                    }
+                                }
+                            });
+                            explanation(ui, &tr!("onboarding-key-text"));
+                        }
+                        AuthMethod::CodeAssist => {
+                            ui.heading(tr!("onboarding-login"));
+                            ui.horizontal(|ui| {
+                                if ui
+                                    .add_enabled(
+                                        !self.logging_in,
+                                        egui::Button::new(tr!("settings-login-google")),
+                                    )
+                                    .clicked()
+                                {
+                                    self.logging_in = true;
+                                    action = OnboardingAction::LoginGoogle;
+                                }
+                                if self.logging_in {
+                                    ui.spinner();
+                                    ui.label(tr!("onboarding-login-browser"));
+                                } else if !settings.oauth_token.is_empty() {
+                                    ui.label(tr!("settings-logged-in"));
+                                }
+                            });
+                            if !settings.available_projects.is_empty() {
+                                egui::ComboBox::from_label(tr!("settings-project"))
+                                    .selected_text(settings.project_id.clone())
+                                    .show_ui(ui, |ui| {
+                                        for project in settings.available_projects.clone() {
+                                            ui.selectable_value(
+                                                &mut settings.project_id,
+                                                project.clone(),
+                                                project,
+                                            );
+                                        }
+                                    });
+                            } else if !settings.oauth_token.is_empty() {
+                                ui.text_edit_singleline(&mut settings.project_id)
+                                    .on_hover_text(tr!("onboarding-project-id"));
+                            }
+                            explanation(ui, &tr!("onboarding-login-text"));
+                        }
+                    },
+                    Step::Model => {
+                        ui.heading(tr!("onboarding-model"));
+                        crate::widgets::model_selector(
+                            ui,
+                            "onboarding_model",
+                            &mut settings.model_picker.selected,
+                        );
+                        explanation(ui, &tr!("onboarding-model-text"));
+                    }
+                    Step::Proxy => {
+                        ui.heading(tr!("onboarding-network"));
+                        let mut enabled = settings.proxy_path.is_some();
+                        ui.checkbox(&mut enabled, tr!("settings-proxy"));
+                        if !enabled {
+                            settings.proxy_path = None;
+                        } else if settings.proxy_path.is_none() {
+                            settings.proxy_path = Some("socks5://127.0.0.1:2080".to_owned());
+                        }
+                        if let Some(proxy) = &mut settings.proxy_path {
+                            ui.add(
+                                egui::TextEdit::singleline(proxy)
+                                    .hint_text("http://your_proxy_address:port"),
+                            );
+                        }
+                        explanation(ui, &tr!("onboarding-network-text"));
+                    }
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr!("onboarding-skip")).clicked() {
+                        action = OnboardingAction::Finish;
+                    }
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        let last = self.step.index() + 1 == Step::ALL.len();
+                        if ui
+                            .add_enabled(
+                                self.can_continue(settings),
+                                egui::Button::new(if last {
+                                    tr!("onboarding-finish")
+                                } else {
+                                    tr!("onboarding-next")
+                                }),
+                            )
+                            .clicked()
+                        {
+                            if last {
+                                action = OnboardingAction::Finish;
+                            } else {
+                                self.step = Step::ALL[self.step.index() + 1];
+                            }
+                        }
+                        if self.step != Step::Welcome && ui.button(tr!("onboarding-back")).clicked()
+                        {
+                            self.step = Step::ALL[self.step.index() - 1];
+                        }
+                    });
+                });
+            });
+        action
+    }
+}
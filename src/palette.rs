@@ -0,0 +1,144 @@
+use crate::{i18n::tr, keymap::Command, widgets::GeminiModel};
+use eframe::egui::{self, Align2, Frame, Key};
+
+const MAX_VISIBLE: usize = 12;
+
+#[derive(Clone, Copy)]
+pub enum PaletteAction {
+    Command(Command),
+    SwitchModel(GeminiModel),
+    /// Index into the chat list
+    JumpToChat(usize),
+    /// Id of a saved prompt to use as the current chat's system prompt
+    UsePrompt(u64),
+    ToggleStreaming,
+}
+
+pub struct PaletteEntry {
+    pub label: String,
+    /// Shown dimmed on the right, e.g. a shortcut or "Chat"
+    pub detail: String,
+    pub action: PaletteAction,
+}
+
+/// Ctrl+K launcher that fuzzy-searches every app action.
+#[derive(Default)]
+pub struct CommandPalette {
+    pub open: bool,
+    query: String,
+    selected: usize,
+}
+
+/// Subsequence match of `query` in `text`, higher is better.
+/// Consecutive characters and matches at word starts score extra.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    if query.is_empty() {
+        return Some(0);
+    }
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut prev_match: Option<usize> = None;
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = text[pos..].iter().position(|&c| c == q)? + pos;
+        score += 1;
+        if prev_match.is_some_and(|p| p + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        score -= (found - pos).min(5) as i32;
+        prev_match = Some(found);
+        pos = found + 1;
+    }
+    Some(score)
+}
+
+impl CommandPalette {
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.query.clear();
+        self.selected = 0;
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, entries: Vec<PaletteEntry>) -> Option<PaletteAction> {
+        if !self.open {
+            return None;
+        }
+
+        let mut ranked: Vec<(i32, PaletteEntry)> = entries
+            .into_iter()
+            .filter_map(|e| fuzzy_score(&self.query, &e.label).map(|s| (s, e)))
+            .collect();
+        // stable, so equal scores keep their natural order
+        ranked.sort_by(|a, b| b.0.cmp(&a.0));
+        ranked.truncate(MAX_VISIBLE);
+        self.selected = self.selected.min(ranked.len().saturating_sub(1));
+
+        let (up, down, enter, escape) = ctx.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::NONE, Key::ArrowUp),
+                i.consume_key(egui::Modifiers::NONE, Key::ArrowDown),
+                i.consume_key(egui::Modifiers::NONE, Key::Enter),
+                i.consume_key(egui::Modifiers::NONE, Key::Escape),
+            )
+        });
+        if up {
+            self.selected = self.selected.saturating_sub(1);
+        }
+        if down && self.selected + 1 < ranked.len() {
+            self.selected += 1;
+        }
+
+        let mut chosen = enter
+            .then(|| ranked.get(self.selected).map(|(_, e)| e.action))
+            .flatten();
+        let resp = egui::Area::new(egui::Id::new("command_palette"))
+            .order(egui::Order::Foreground)
+            .anchor(Align2::CENTER_TOP, egui::vec2(0.0, 60.0))
+            .show(ctx, |ui| {
+                Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_width(480.0);
+                    let edit = ui.add(
+                        egui::TextEdit::singleline(&mut self.query)
+                            .hint_text(tr!("palette-hint"))
+                            .desired_width(f32::INFINITY),
+                    );
+                    edit.request_focus();
+                    if edit.changed() {
+                        self.selected = 0;
+                    }
+                    ui.separator();
+
+                    if ranked.is_empty() {
+                        ui.weak(tr!("palette-nothing"));
+                    }
+                    for (i, (_, entry)) in ranked.iter().enumerate() {
+                        let resp = ui
+                            .horizontal(|ui| {
+                                let resp = ui.selectable_label(i == self.selected, &entry.label);
+                                ui.with_layout(
+                                    egui::Layout::right_to_left(egui::Align::Center),
+                                    |ui| ui.weak(&entry.detail),
+                                );
+                                resp
+                            })
+                            .inner;
+                        if resp.clicked() {
+                            chosen = Some(entry.action);
+                        }
+                        if resp.hovered() && ui.input(|i| i.pointer.delta() != egui::Vec2::ZERO) {
+                            self.selected = i;
+                        }
+                    }
+                });
+            })
+            .response;
+
+        if chosen.is_some() || escape || resp.clicked_elsewhere() {
+            self.open = false;
+        }
+        chosen
+    }
+}
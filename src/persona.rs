@@ -0,0 +1,163 @@
+use crate::{
+    i18n::tr,
+    prompt_library::PromptLibrary,
+    widgets::{ModelPicker, RequestInfoType},
+};
+use eframe::egui::{self, Layout};
+
+/// A reusable bundle of avatar, system prompt, model, inference preset and voice
+/// that can be assigned to a chat.
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Persona {
+    pub id: u64,
+    pub name: String,
+    pub avatar: String,
+    pub picker: ModelPicker,
+    /// TTS voice id, only used when the `tts` feature is enabled
+    pub voice: Option<String>,
+}
+
+impl Default for Persona {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            name: tr!("persona-new-name"),
+            avatar: "🤖".to_owned(),
+            picker: ModelPicker::default(),
+            voice: None,
+        }
+    }
+}
+
+impl Persona {
+    #[inline]
+    pub fn label(&self) -> String {
+        format!("{} {}", self.avatar, self.name)
+    }
+}
+
+pub fn find(personas: &[Persona], id: Option<u64>) -> Option<&Persona> {
+    id.and_then(|id| personas.iter().find(|p| p.id == id))
+}
+
+/// Combobox for picking a persona (or none). Returns the new selection if it changed.
+pub fn picker(ui: &mut egui::Ui, personas: &[Persona], selected: Option<u64>) -> Option<Option<u64>> {
+    let mut changed = None;
+    let selected_text = find(personas, selected)
+        .map(Persona::label)
+        .unwrap_or_else(|| tr!("none"));
+
+    egui::ComboBox::from_id_salt("persona_picker_combobox")
+        .selected_text(selected_text)
+        .show_ui(ui, |ui| {
+            if ui.selectable_label(selected.is_none(), tr!("none")).clicked() && selected.is_some() {
+                changed = Some(None);
+            }
+            for persona in personas {
+                if ui
+                    .selectable_label(selected == Some(persona.id), persona.label())
+                    .clicked()
+                    && selected != Some(persona.id)
+                {
+                    changed = Some(Some(persona.id));
+                }
+            }
+        });
+
+    changed
+}
+
+#[cfg(feature = "tts")]
+fn voice_picker(ui: &mut egui::Ui, voice: &mut Option<String>, tts: &crate::sessions::SharedTts) {
+    let voices = tts
+        .as_ref()
+        .and_then(|tts| tts.read().voices().ok())
+        .unwrap_or_default();
+
+    let selected_text = voice
+        .as_ref()
+        .and_then(|id| voices.iter().find(|v| &v.id() == id).map(|v| v.name()))
+        .unwrap_or_else(|| tr!("persona-voice-default"));
+
+    egui::ComboBox::from_label(tr!("persona-voice"))
+        .id_salt("persona_voice_combobox")
+        .selected_text(selected_text)
+        .show_ui(ui, |ui| {
+            if ui
+                .selectable_label(voice.is_none(), tr!("persona-voice-default"))
+                .clicked()
+            {
+                *voice = None;
+            }
+            for v in &voices {
+                let id = v.id();
+                if ui
+                    .selectable_label(voice.as_ref() == Some(&id), v.name())
+                    .clicked()
+                {
+                    *voice = Some(id);
+                }
+            }
+        });
+}
+
+/// Persona manager shown in the global settings.
+pub fn show_personas(
+    ui: &mut egui::Ui,
+    personas: &mut Vec<Persona>,
+    library: &mut PromptLibrary,
+    #[cfg(feature = "tts")] tts: &crate::sessions::SharedTts,
+) {
+    ui.label(tr!("personas-help"));
+
+    let mut remove = None;
+    for (i, persona) in personas.iter_mut().enumerate() {
+        ui.push_id(persona.id, |ui| {
+            egui::CollapsingHeader::new(persona.label())
+                .id_salt(persona.id)
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut persona.avatar)
+                                .char_limit(2)
+                                .desired_width(24.0),
+                        )
+                        .on_hover_text(tr!("persona-avatar"));
+                        ui.add(
+                            egui::TextEdit::singleline(&mut persona.name)
+                                .hint_text(tr!("persona-name-hint")),
+                        );
+                        ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui
+                                .button("🗑")
+                                .on_hover_text(tr!("persona-remove"))
+                                .clicked()
+                            {
+                                remove = Some(i);
+                            }
+                        });
+                    });
+
+                    #[cfg(feature = "tts")]
+                    voice_picker(ui, &mut persona.voice, tts);
+
+                    persona
+                        .picker
+                        .show(ui, library, &mut |_: RequestInfoType| {});
+                });
+        });
+    }
+
+    if let Some(i) = remove {
+        personas.remove(i);
+    }
+
+    if ui.button(tr!("persona-add")).clicked() {
+        let id = personas.iter().map(|p| p.id).max().unwrap_or(0) + 1;
+        personas.push(Persona {
+            id,
+            ..Default::default()
+        });
+    }
+}
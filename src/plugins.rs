@@ -0,0 +1,619 @@
+use crate::{chat::Message, i18n::tr};
+use anyhow::{anyhow, Result};
+use eframe::egui;
+use gemini_rust::Tool;
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    future::Future,
+    hash::{DefaultHasher, Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::{Arc, LazyLock, Mutex},
+};
+
+/// A plugin call taking longer than this is aborted.
+#[cfg(feature = "plugins")]
+const CALL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+const MANIFEST_FILE: &str = "plugin.json";
+
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(default)]
+pub struct SlashCommand {
+    /// Typed as `/name args`
+    pub name: String,
+    pub description: String,
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct ToolSpec {
+    pub name: String,
+    pub description: String,
+    /// JSON schema of the arguments
+    pub parameters: Value,
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(default)]
+pub struct ExporterSpec {
+    pub name: String,
+    /// File extension without the dot
+    pub extension: String,
+}
+
+/// What a plugin may reach outside of its sandbox.
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Permissions {
+    /// Hosts for the http host function, `*.example.com` wildcards work
+    pub hosts: Vec<String>,
+    /// Folders mounted into the sandbox at the same path
+    pub paths: Vec<PathBuf>,
+}
+
+/// `plugin.json` in the plugin's folder.
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(default)]
+pub struct PluginManifest {
+    pub name: String,
+    pub version: String,
+    pub description: String,
+    /// Wasm module, relative to the manifest
+    pub module: PathBuf,
+    pub slash_commands: Vec<SlashCommand>,
+    pub tools: Vec<ToolSpec>,
+    /// Languages of the fenced code blocks the plugin renders, e.g. `mermaid`
+    pub renderers: Vec<String>,
+    pub exporters: Vec<ExporterSpec>,
+    /// Requested permissions, nothing is given without the user granting it
+    pub permissions: Permissions,
+}
+
+/// A loaded plugin. Every call takes and returns JSON, so other backends than
+/// Wasm only need to implement [`Extension::call`].
+pub trait Extension: Send {
+    fn manifest(&self) -> &PluginManifest;
+
+    /// Runs the exported `function` with `input`.
+    fn call(&mut self, function: &str, input: Value) -> Result<String>;
+
+    /// Text that replaces the command in the chatbox.
+    fn slash_command(&mut self, name: &str, args: &str) -> Result<String> {
+        self.call("slash_command", json!({ "name": name, "args": args }))
+    }
+
+    fn call_tool(&mut self, name: &str, args: Value) -> Result<Value> {
+        let output = self.call("call_tool", json!({ "name": name, "args": args }))?;
+        Ok(serde_json::from_str(&output)?)
+    }
+
+    /// Markdown shown instead of the code block.
+    fn render(&mut self, language: &str, code: &str) -> Result<String> {
+        self.call("render", json!({ "language": language, "code": code }))
+    }
+
+    fn export(&mut self, exporter: &str, messages: &[Message]) -> Result<String> {
+        self.call(
+            "export",
+            json!({ "exporter": exporter, "messages": messages }),
+        )
+    }
+}
+
+#[cfg(feature = "plugins")]
+struct WasmPlugin {
+    manifest: PluginManifest,
+    plugin: extism::Plugin,
+}
+
+#[cfg(feature = "plugins")]
+impl WasmPlugin {
+    /// Instantiates the module with the permissions that were both requested and granted.
+    fn load(dir: &Path, manifest: PluginManifest, granted: &Permissions) -> Result<Self> {
+        let requested = &manifest.permissions;
+        let hosts = requested
+            .hosts
+            .iter()
+            .filter(|host| granted.hosts.contains(host))
+            .cloned();
+        let mut sandbox = extism::Manifest::new([extism::Wasm::file(dir.join(&manifest.module))])
+            .with_allowed_hosts(hosts)
+            .with_timeout(CALL_TIMEOUT);
+        for path in requested.paths.iter().filter(|p| granted.paths.contains(p)) {
+            sandbox = sandbox.with_allowed_path(path.to_string_lossy().into_owned(), path);
+        }
+        let plugin = extism::Plugin::new(&sandbox, [], true)?;
+        log::info!("loaded the `{}` plugin", manifest.name);
+        Ok(Self { manifest, plugin })
+    }
+}
+
+#[cfg(feature = "plugins")]
+impl Extension for WasmPlugin {
+    fn manifest(&self) -> &PluginManifest {
+        &self.manifest
+    }
+
+    fn call(&mut self, function: &str, input: Value) -> Result<String> {
+        if !self.plugin.function_exists(function) {
+            anyhow::bail!(
+                "the `{}` plugin doesn't export `{function}`",
+                self.manifest.name
+            );
+        }
+        Ok(self
+            .plugin
+            .call::<String, String>(function, input.to_string())?)
+    }
+}
+
+#[cfg(not(feature = "plugins"))]
+fn load(_: &Path, _: PluginManifest, _: &Permissions) -> Result<Box<dyn Extension>> {
+    Err(anyhow!(tr!("plugins-unsupported")))
+}
+
+#[cfg(feature = "plugins")]
+fn load(dir: &Path, manifest: PluginManifest, granted: &Permissions) -> Result<Box<dyn Extension>> {
+    Ok(Box::new(WasmPlugin::load(dir, manifest, granted)?))
+}
+
+struct Found {
+    dir: PathBuf,
+    manifest: PluginManifest,
+    /// Why it isn't loaded although enabled
+    error: Option<String>,
+}
+
+/// A loaded plugin. The manifest is kept out of the lock, so listing tools or renderers
+/// doesn't wait for a call that is running.
+struct Loaded {
+    manifest: PluginManifest,
+    extension: Mutex<Box<dyn Extension>>,
+}
+
+#[derive(Default)]
+struct Registry {
+    found: Vec<Found>,
+    loaded: Vec<Arc<Loaded>>,
+    /// Settings the plugins were loaded with, `None` before the first scan
+    loaded_with: Option<PluginSettings>,
+    /// Rendered code blocks by plugin, language and code hash, `None` while rendering
+    rendered: Lru<Option<String>>,
+    /// Messages with their blocks rendered by content hash, `None` when none is
+    messages: Lru<Option<Arc<str>>>,
+}
+
+/// Entries kept by each render cache. A streamed message leaves one per frame.
+const MAX_CACHED: usize = 256;
+
+/// Keeps the [`MAX_CACHED`] most recently used entries.
+#[derive(Default)]
+struct Lru<V> {
+    entries: HashMap<u64, (V, u64)>,
+    tick: u64,
+}
+
+impl<V: Clone> Lru<V> {
+    fn get(&mut self, key: u64) -> Option<V> {
+        self.tick += 1;
+        let (value, used) = self.entries.get_mut(&key)?;
+        *used = self.tick;
+        Some(value.clone())
+    }
+
+    fn insert(&mut self, key: u64, value: V) {
+        self.tick += 1;
+        self.entries.insert(key, (value, self.tick));
+        if self.entries.len() > MAX_CACHED {
+            // dropping the older half at once keeps the sorting rare
+            let mut used: Vec<u64> = self.entries.values().map(|(_, used)| *used).collect();
+            used.sort_unstable();
+            let cutoff = used[used.len() / 2];
+            self.entries.retain(|_, (_, used)| *used > cutoff);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+static REGISTRY: LazyLock<Mutex<Registry>> = LazyLock::new(Default::default);
+
+fn plugins_dir() -> Option<PathBuf> {
+    eframe::storage_dir(crate::TITLE).map(|dir| dir.join("plugins"))
+}
+
+/// Reads the manifests of every folder in the plugins folder.
+fn scan() -> Vec<Found> {
+    let Some(entries) = plugins_dir().and_then(|dir| std::fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+    let mut found = Vec::new();
+    for dir in entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()) {
+        let manifest = std::fs::read_to_string(dir.join(MANIFEST_FILE))
+            .map_err(anyhow::Error::from)
+            .and_then(|text| Ok(serde_json::from_str::<PluginManifest>(&text)?));
+        match manifest {
+            Ok(manifest) if !manifest.name.is_empty() => found.push(Found {
+                dir,
+                manifest,
+                error: None,
+            }),
+            Ok(_) => log::warn!("plugin in {} has no name", dir.display()),
+            Err(e) => log::warn!("no valid {MANIFEST_FILE} in {}: {e}", dir.display()),
+        }
+    }
+    found.sort_by(|a, b| a.manifest.name.cmp(&b.manifest.name));
+    found
+}
+
+impl Registry {
+    fn reload(&mut self, settings: &PluginSettings) {
+        self.loaded.clear();
+        self.rendered.clear();
+        self.messages.clear();
+        for found in &mut self.found {
+            found.error = None;
+            if !settings.enabled.contains(&found.manifest.name) {
+                continue;
+            }
+            let granted = settings
+                .granted
+                .get(&found.manifest.name)
+                .cloned()
+                .unwrap_or_default();
+            match load(&found.dir, found.manifest.clone(), &granted) {
+                Ok(extension) => self.loaded.push(Arc::new(Loaded {
+                    manifest: found.manifest.clone(),
+                    extension: Mutex::new(extension),
+                })),
+                Err(e) => {
+                    log::error!("failed to load the `{}` plugin: {e}", found.manifest.name);
+                    found.error = Some(e.to_string());
+                }
+            }
+        }
+        self.loaded_with = Some(settings.clone());
+    }
+}
+
+/// Third party extensions from the `plugins` folder next to the app data.
+#[derive(Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct PluginSettings {
+    /// Names of the enabled plugins
+    pub enabled: Vec<String>,
+    /// Permissions granted to each plugin by name
+    pub granted: HashMap<String, Permissions>,
+}
+
+impl PluginSettings {
+    /// Loads the enabled plugins on the first call and again when the settings change.
+    pub fn apply_if_changed(&self) {
+        let Ok(mut registry) = REGISTRY.lock() else {
+            return;
+        };
+        if registry.loaded_with.is_none() {
+            registry.found = scan();
+        }
+        if registry.loaded_with.as_ref() != Some(self) {
+            registry.reload(self);
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        #[cfg(not(feature = "plugins"))]
+        ui.colored_label(ui.visuals().warn_fg_color, tr!("plugins-unsupported"));
+        let Ok(mut registry) = REGISTRY.lock() else {
+            return;
+        };
+        ui.horizontal(|ui| {
+            if let Some(dir) = plugins_dir() {
+                ui.label(tr!("plugins-folder", path = dir.display().to_string()));
+            }
+            if ui.button(tr!("plugins-rescan")).clicked() {
+                registry.found = scan();
+                registry.reload(self);
+            }
+        });
+        if registry.found.is_empty() {
+            ui.weak(tr!("plugins-none"));
+        }
+
+        for found in &registry.found {
+            let manifest = &found.manifest;
+            let name = &manifest.name;
+            ui.separator();
+            let mut enabled = self.enabled.contains(name);
+            crate::widgets::toggle_setting(
+                ui,
+                &mut enabled,
+                &format!("{name} {}", manifest.version),
+                &manifest.description,
+            );
+            if enabled != self.enabled.contains(name) {
+                if enabled {
+                    self.enabled.push(name.clone());
+                } else {
+                    self.enabled.retain(|n| n != name);
+                }
+            }
+            if let Some(error) = &found.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+
+            ui.horizontal_wrapped(|ui| {
+                for command in &manifest.slash_commands {
+                    ui.weak(format!("/{}", command.name))
+                        .on_hover_text(&command.description);
+                }
+                for tool in &manifest.tools {
+                    ui.weak(format!("🔧 {}", tool.name))
+                        .on_hover_text(&tool.description);
+                }
+                for language in &manifest.renderers {
+                    ui.weak(format!("```{language}"))
+                        .on_hover_text(tr!("plugins-renderer"));
+                }
+                for exporter in &manifest.exporters {
+                    ui.weak(format!("💾 {}", exporter.name))
+                        .on_hover_text(tr!("plugins-exporter"));
+                }
+            });
+
+            let requested = &manifest.permissions;
+            if requested.hosts.is_empty() && requested.paths.is_empty() {
+                continue;
+            }
+            ui.label(tr!("plugins-permissions"));
+            let granted = self.granted.entry(name.clone()).or_default();
+            for host in &requested.hosts {
+                let mut on = granted.hosts.contains(host);
+                if ui
+                    .checkbox(&mut on, tr!("plugins-host", host = host.as_str()))
+                    .changed()
+                {
+                    granted.hosts.retain(|h| h != host);
+                    if on {
+                        granted.hosts.push(host.clone());
+                    }
+                }
+            }
+            for path in &requested.paths {
+                let mut on = granted.paths.contains(path);
+                let text = tr!("plugins-path", path = path.display().to_string());
+                if ui.checkbox(&mut on, text).changed() {
+                    granted.paths.retain(|p| p != path);
+                    if on {
+                        granted.paths.push(path.clone());
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The loaded plugin `pick` chooses.
+fn find(pick: impl Fn(&PluginManifest) -> bool) -> Option<Arc<Loaded>> {
+    let registry = REGISTRY.lock().ok()?;
+    registry.loaded.iter().find(|p| pick(&p.manifest)).cloned()
+}
+
+/// Runs `job` with the plugin on a blocking thread, as a call can take up to
+/// [`CALL_TIMEOUT`].
+async fn run<T: Send + 'static>(
+    plugin: Arc<Loaded>,
+    job: impl FnOnce(&mut dyn Extension) -> Result<T> + Send + 'static,
+) -> Result<T> {
+    tokio::task::spawn_blocking(move || {
+        let mut extension = plugin
+            .extension
+            .lock()
+            .map_err(|_| anyhow!("the `{}` plugin panicked", plugin.manifest.name))?;
+        job(extension.as_mut())
+    })
+    .await?
+}
+
+/// Runs `/name args` if a loaded plugin has that command, resolving to what replaces
+/// the chatbox text. `None` when no plugin has it.
+pub fn run_slash_command(text: &str) -> Option<impl Future<Output = Result<String>>> {
+    let command = text.trim().strip_prefix('/')?;
+    let (name, args) = command
+        .split_once(char::is_whitespace)
+        .unwrap_or((command, ""));
+    let plugin = find(|m| m.slash_commands.iter().any(|c| c.name == name))?;
+    let (name, args) = (name.to_owned(), args.trim().to_owned());
+    Some(run(plugin, move |extension| {
+        extension.slash_command(&name, &args)
+    }))
+}
+
+/// Tools of the loaded plugins with the name of the plugin.
+pub fn tools() -> Vec<(String, ToolSpec)> {
+    REGISTRY.lock().map_or_else(
+        |_| Vec::new(),
+        |registry| {
+            registry
+                .loaded
+                .iter()
+                .flat_map(|p| {
+                    p.manifest
+                        .tools
+                        .iter()
+                        .map(|t| (p.manifest.name.clone(), t.clone()))
+                })
+                .collect()
+        },
+    )
+}
+
+/// The tools of the loaded plugins as a request tool, `None` if there are none.
+pub fn to_tool() -> Option<Tool> {
+    let declarations: Vec<Value> = tools()
+        .into_iter()
+        .map(|(_, tool)| {
+            let mut declaration = json!({ "name": tool.name, "description": tool.description });
+            if tool.parameters.is_object() {
+                declaration["parameters"] = tool.parameters;
+            }
+            declaration
+        })
+        .collect();
+    if declarations.is_empty() {
+        return None;
+    }
+    serde_json::from_value(json!({ "functionDeclarations": declarations }))
+        .map_err(|e| log::error!("failed to declare the plugin tools: {e}"))
+        .ok()
+}
+
+/// The name of the plugin that answers calls of the tool `name`.
+pub fn tool_plugin(name: &str) -> Option<String> {
+    find(|m| m.tools.iter().any(|t| t.name == name)).map(|p| p.manifest.name.clone())
+}
+
+pub async fn call_tool(name: String, args: Value) -> Result<Value> {
+    let plugin = find(|m| m.tools.iter().any(|t| t.name == name))
+        .ok_or_else(|| anyhow!("no plugin has the `{name}` tool"))?;
+    run(plugin, move |extension| extension.call_tool(&name, args)).await
+}
+
+/// Exporters of the loaded plugins with the name of the plugin.
+pub fn exporters() -> Vec<(String, ExporterSpec)> {
+    REGISTRY.lock().map_or_else(
+        |_| Vec::new(),
+        |registry| {
+            registry
+                .loaded
+                .iter()
+                .flat_map(|p| {
+                    p.manifest
+                        .exporters
+                        .iter()
+                        .map(|e| (p.manifest.name.clone(), e.clone()))
+                })
+                .collect()
+        },
+    )
+}
+
+pub async fn export(plugin: String, exporter: String, messages: Vec<Message>) -> Result<String> {
+    let loaded =
+        find(|m| m.name == plugin).ok_or_else(|| anyhow!("the `{plugin}` plugin isn't loaded"))?;
+    run(loaded, move |extension| {
+        extension.export(&exporter, &messages)
+    })
+    .await
+}
+
+fn hash(value: impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Renders a block in the background, the code is shown as it is until it's done.
+fn start_render(ctx: &egui::Context, plugin: Arc<Loaded>, key: u64, language: &str, code: &str) {
+    let (language, code) = (language.to_owned(), code.to_owned());
+    let ctx = ctx.clone();
+    tokio::spawn(async move {
+        let (lang, source) = (language.clone(), code.clone());
+        let rendered = run(plugin, move |extension| extension.render(&lang, &source))
+            .await
+            .unwrap_or_else(|e| {
+                log::error!("failed to render a {language} block: {e}");
+                format!("```{language}\n{code}```\n")
+            });
+        if let Ok(mut registry) = REGISTRY.lock() {
+            registry.rendered.insert(key, Some(rendered));
+            // the messages with this block change
+            registry.messages.clear();
+        }
+        ctx.request_repaint();
+    });
+}
+
+/// `content` with the fenced code blocks a loaded plugin renders replaced by its
+/// output, `None` when there are none. Runs for every visible message on every frame,
+/// so the result is kept per content.
+pub fn render_blocks(ctx: &egui::Context, content: &str) -> Option<Arc<str>> {
+    if !content.contains("```") {
+        return None;
+    }
+    let mut registry = REGISTRY.lock().ok()?;
+    if registry
+        .loaded
+        .iter()
+        .all(|p| p.manifest.renderers.is_empty())
+    {
+        return None;
+    }
+    let content_key = hash(content);
+    if let Some(output) = registry.messages.get(content_key) {
+        return output;
+    }
+
+    let mut output = String::with_capacity(content.len());
+    let mut lines = content.split_inclusive('\n');
+    let mut changed = false;
+    let mut pending = false;
+    while let Some(line) = lines.next() {
+        let language = line.trim().strip_prefix("```").map(str::trim);
+        let Some(plugin) = language.filter(|l| !l.is_empty()).and_then(|language| {
+            registry
+                .loaded
+                .iter()
+                .find(|p| p.manifest.renderers.iter().any(|r| r == language))
+                .cloned()
+        }) else {
+            output.push_str(line);
+            continue;
+        };
+        let language = language.unwrap_or_default();
+        let mut code = String::new();
+        let mut closed = false;
+        for line in lines.by_ref() {
+            if line.trim() == "```" {
+                closed = true;
+                break;
+            }
+            code.push_str(line);
+        }
+        // still streaming in, the code is shown as is until the block is complete
+        if !closed {
+            output.push_str(line);
+            output.push_str(&code);
+            continue;
+        }
+
+        let key = hash((&plugin.manifest.name, language, &code));
+        let rendered = match registry.rendered.get(key) {
+            Some(rendered) => rendered,
+            None => {
+                registry.rendered.insert(key, None);
+                start_render(ctx, plugin, key, language, &code);
+                None
+            }
+        };
+        let Some(rendered) = rendered else {
+            pending = true;
+            output.push_str(line);
+            output.push_str(&code);
+            output.push_str("```\n");
+            continue;
+        };
+        output.push_str(&rendered);
+        if !rendered.ends_with('\n') {
+            output.push('\n');
+        }
+        changed = true;
+    }
+    let output: Option<Arc<str>> = changed.then(|| output.into());
+    // kept once every block is done, the finished renders clear it anyway
+    if !pending {
+        registry.messages.insert(content_key, output.clone());
+    }
+    output
+}
@@ -0,0 +1,212 @@
+use crate::widgets::{GeminiModel, Settings};
+use anyhow::{anyhow, Result};
+use eframe::egui::{self, Color32, RichText};
+
+const META_PROMPT: &str = "You are a prompt engineering assistant. Rewrite the user's draft prompt \
+so that it is clearer, more specific and better structured, while keeping the original intent, \
+language and any concrete details. Keep paragraphs separated by blank lines. \
+Reply with the improved prompt only, without any commentary or surrounding quotes.";
+
+/// Model used for rewriting drafts, cheap and fast is all we need here.
+const IMPROVER_MODEL: GeminiModel = GeminiModel::Gemini25Flash;
+
+pub async fn request_improvement(settings: Settings, draft: String) -> Result<String> {
+    log::info!("requesting prompt improvement ({} chars)", draft.len());
+    let text = geminid_core::pipeline::generate_once(
+        &settings.connection(),
+        IMPROVER_MODEL,
+        Some(META_PROMPT),
+        &draft,
+    )
+    .await?;
+
+    if text.trim().is_empty() {
+        return Err(anyhow!("The model returned an empty suggestion."));
+    }
+    Ok(text.trim().to_owned())
+}
+
+/// A run of paragraphs that either matches between the draft and the suggestion,
+/// or was changed by it.
+#[derive(Debug, Clone)]
+enum Hunk {
+    Same(Vec<String>),
+    Changed {
+        original: Vec<String>,
+        suggested: Vec<String>,
+        accepted: bool,
+    },
+}
+
+/// Paragraph-level diff between the user's draft and the suggested rewrite.
+#[derive(Debug, Clone, Default)]
+pub struct PromptImprovement {
+    hunks: Vec<Hunk>,
+}
+
+fn split_paragraphs(text: &str) -> Vec<String> {
+    text.split("\n\n")
+        .map(|p| p.trim().to_owned())
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+impl PromptImprovement {
+    pub fn new(draft: &str, suggestion: &str) -> Self {
+        let a = split_paragraphs(draft);
+        let b = split_paragraphs(suggestion);
+
+        // longest common subsequence over paragraphs
+        let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+        for i in (0..a.len()).rev() {
+            for j in (0..b.len()).rev() {
+                lcs[i][j] = if a[i] == b[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+
+        let mut hunks = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        let (mut original, mut suggested) = (Vec::new(), Vec::new());
+        let mut same = Vec::new();
+
+        macro_rules! flush_changed {
+            () => {
+                if !original.is_empty() || !suggested.is_empty() {
+                    hunks.push(Hunk::Changed {
+                        original: std::mem::take(&mut original),
+                        suggested: std::mem::take(&mut suggested),
+                        accepted: true,
+                    });
+                }
+            };
+        }
+
+        while i < a.len() || j < b.len() {
+            if i < a.len() && j < b.len() && a[i] == b[j] {
+                flush_changed!();
+                same.push(a[i].clone());
+                i += 1;
+                j += 1;
+                continue;
+            }
+            if !same.is_empty() {
+                hunks.push(Hunk::Same(std::mem::take(&mut same)));
+            }
+            if j < b.len() && (i == a.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+                suggested.push(b[j].clone());
+                j += 1;
+            } else {
+                original.push(a[i].clone());
+                i += 1;
+            }
+        }
+        flush_changed!();
+        if !same.is_empty() {
+            hunks.push(Hunk::Same(same));
+        }
+
+        Self { hunks }
+    }
+
+    #[inline]
+    pub fn has_changes(&self) -> bool {
+        self.hunks.iter().any(|h| matches!(h, Hunk::Changed { .. }))
+    }
+
+    /// Builds the final prompt from the accepted and rejected changes.
+    pub fn result(&self) -> String {
+        let mut paragraphs: Vec<&str> = Vec::new();
+        for hunk in &self.hunks {
+            match hunk {
+                Hunk::Same(p) => paragraphs.extend(p.iter().map(String::as_str)),
+                Hunk::Changed {
+                    original,
+                    suggested,
+                    accepted,
+                } => {
+                    let chosen = if *accepted { suggested } else { original };
+                    paragraphs.extend(chosen.iter().map(String::as_str));
+                }
+            }
+        }
+        paragraphs.join("\n\n")
+    }
+
+    /// Shows the diff with per-paragraph accept/reject toggles.
+    /// Returns `Some(true)` when the result should be applied, `Some(false)` when dismissed.
+    pub fn show(&mut self, ui: &mut egui::Ui) -> Option<bool> {
+        let mut outcome = None;
+        let removed_color = Color32::from_rgb(201, 141, 141);
+        let added_color = Color32::from_rgb(141, 189, 156);
+
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.strong("✨ Suggested prompt");
+                if !self.has_changes() {
+                    ui.weak("(no changes suggested)");
+                }
+            });
+
+            egui::ScrollArea::vertical()
+                .id_salt("prompt_improvement_diff")
+                .max_height(240.0)
+                .show(ui, |ui| {
+                    for (i, hunk) in self.hunks.iter_mut().enumerate() {
+                        match hunk {
+                            Hunk::Same(paragraphs) => {
+                                for p in paragraphs.iter() {
+                                    ui.weak(p);
+                                }
+                            }
+                            Hunk::Changed {
+                                original,
+                                suggested,
+                                accepted,
+                            } => {
+                                ui.push_id(i, |ui| {
+                                    ui.horizontal(|ui| {
+                                        ui.selectable_value(accepted, true, "✔ Accept");
+                                        ui.selectable_value(accepted, false, "❌ Reject");
+                                    });
+                                    for p in original.iter() {
+                                        let mut text = RichText::new(format!("- {p}"));
+                                        text = if *accepted {
+                                            text.strikethrough().color(removed_color)
+                                        } else {
+                                            text.color(removed_color)
+                                        };
+                                        ui.label(text);
+                                    }
+                                    for p in suggested.iter() {
+                                        let mut text = RichText::new(format!("+ {p}"));
+                                        text = if *accepted {
+                                            text.color(added_color)
+                                        } else {
+                                            text.strikethrough().color(added_color)
+                                        };
+                                        ui.label(text);
+                                    }
+                                });
+                            }
+                        }
+                        ui.add_space(4.0);
+                    }
+                });
+
+            ui.horizontal(|ui| {
+                if ui.button("Apply").clicked() {
+                    outcome = Some(true);
+                }
+                if ui.button("Dismiss").clicked() {
+                    outcome = Some(false);
+                }
+            });
+        });
+
+        outcome
+    }
+}
@@ -0,0 +1,587 @@
+use crate::{
+    i18n::tr,
+    widgets::{self, FewShotExample, GeminiModel},
+};
+use eframe::egui::{self, Color32, Layout, Stroke};
+
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct PromptVersion {
+    pub text: String,
+    pub created: chrono::DateTime<chrono::Utc>,
+}
+
+impl Default for PromptVersion {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            created: chrono::Utc::now(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum AbChoice {
+    A,
+    B,
+    #[default]
+    Tie,
+}
+
+/// Outcome of a single A/B run, kept so preferences can be compared over time.
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+pub struct AbRecord {
+    pub version_a: usize,
+    pub model_a: GeminiModel,
+    pub version_b: usize,
+    pub model_b: GeminiModel,
+    pub input: String,
+    pub preferred: AbChoice,
+    pub time: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct SavedPrompt {
+    pub id: u64,
+    pub name: String,
+    /// Every saved edit, oldest first. Never empty.
+    pub versions: Vec<PromptVersion>,
+    /// Few-shot examples a model picker takes along when it uses this prompt
+    pub examples: Vec<FewShotExample>,
+    pub ab_records: Vec<AbRecord>,
+    #[serde(skip)]
+    draft: Option<String>,
+}
+
+impl Default for SavedPrompt {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            name: tr!("prompt-library-new-name"),
+            versions: vec![PromptVersion::default()],
+            examples: Vec::new(),
+            ab_records: Vec::new(),
+            draft: None,
+        }
+    }
+}
+
+impl SavedPrompt {
+    #[inline]
+    pub fn latest(&self) -> &str {
+        self.versions.last().map(|v| v.text.as_str()).unwrap_or_default()
+    }
+
+    /// Records `text` as a new version if it differs from the latest one.
+    pub fn commit(&mut self, text: String) -> bool {
+        if self.latest() == text {
+            return false;
+        }
+        self.versions.push(PromptVersion {
+            text,
+            ..Default::default()
+        });
+        true
+    }
+
+    fn version_label(&self, idx: usize) -> String {
+        self.versions
+            .get(idx)
+            .map(|v| {
+                format!(
+                    "v{} ({})",
+                    idx + 1,
+                    v.created
+                        .with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M")
+                )
+            })
+            .unwrap_or_else(|| "?".to_owned())
+    }
+}
+
+/// A single side of an A/B run.
+#[derive(Clone, Copy)]
+struct AbSide {
+    version: usize,
+    model: GeminiModel,
+}
+
+#[derive(Default)]
+struct AbRunner {
+    prompt_id: u64,
+    a: Option<AbSide>,
+    b: Option<AbSide>,
+    input: String,
+    running: bool,
+    outputs: Option<(Result<String, String>, Result<String, String>)>,
+}
+
+/// What the library wants the backend to execute.
+pub struct AbRequest {
+    pub prompt_id: u64,
+    pub input: String,
+    pub a: (String, GeminiModel),
+    pub b: (String, GeminiModel),
+}
+
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct PromptLibrary {
+    pub prompts: Vec<SavedPrompt>,
+    #[serde(skip)]
+    selected: Option<u64>,
+    #[serde(skip)]
+    runner: AbRunner,
+}
+
+impl Clone for PromptLibrary {
+    fn clone(&self) -> Self {
+        Self {
+            prompts: self.prompts.clone(),
+            selected: self.selected,
+            runner: AbRunner::default(),
+        }
+    }
+}
+
+impl PromptLibrary {
+    #[inline]
+    pub fn has_selection(&self) -> bool {
+        self.selected.is_some()
+    }
+
+    pub fn add(&mut self, name: String, text: String) -> u64 {
+        let id = self.prompts.iter().map(|p| p.id).max().unwrap_or(0) + 1;
+        self.prompts.push(SavedPrompt {
+            id,
+            name,
+            versions: vec![PromptVersion {
+                text,
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+        id
+    }
+
+    /// Saves `text` and `examples` under `name`, as a new version of the prompt of that name
+    /// if there is one.
+    pub fn save_as(&mut self, name: &str, text: &str, examples: &[FewShotExample]) {
+        let id = match self.prompts.iter_mut().find(|p| p.name == name) {
+            Some(prompt) => {
+                prompt.commit(text.to_owned());
+                prompt.id
+            }
+            None => self.add(name.to_owned(), text.to_owned()),
+        };
+        if let Some(prompt) = self.prompts.iter_mut().find(|p| p.id == id) {
+            prompt.examples = examples.to_vec();
+        }
+    }
+
+    /// Writes every saved prompt into a JSON file the user picks.
+    fn export(&self, ctx: &egui::Context) {
+        let json = match serde_json::to_string_pretty(&self.prompts) {
+            Ok(json) => json,
+            Err(e) => {
+                log::error!("failed to serialize the saved prompts: {e}");
+                return;
+            }
+        };
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            let Some(file) = rfd::AsyncFileDialog::new()
+                .set_file_name("system-prompts.json")
+                .add_filter("JSON", &["json"])
+                .save_file()
+                .await
+            else {
+                return;
+            };
+            match tokio::fs::write(file.path(), json).await {
+                Ok(()) => log::info!("exported the saved prompts to {}", file.path().display()),
+                Err(e) => log::error!("failed to export the saved prompts: {e}"),
+            }
+            ctx.request_repaint();
+        });
+    }
+
+    /// Picks saved prompts into `prompt` and `examples` and saves them as one, for the system
+    /// prompt section of the model pickers. Several can be shown at once, so the name being
+    /// typed is kept per place in the UI.
+    pub fn show_picker(
+        &mut self,
+        ui: &mut egui::Ui,
+        prompt: &mut String,
+        examples: &mut Vec<FewShotExample>,
+    ) {
+        let name_id = ui.make_persistent_id("system_preset_name");
+        let mut name = ui.data_mut(|d| d.get_temp::<String>(name_id).unwrap_or_default());
+
+        ui.horizontal(|ui| {
+            let current = self
+                .prompts
+                .iter()
+                .find(|p| p.latest() == prompt.as_str())
+                .map_or_else(|| tr!("presets-pick"), |p| p.name.clone());
+            egui::ComboBox::from_id_salt(ui.make_persistent_id("system_preset_pick"))
+                .selected_text(current)
+                .show_ui(ui, |ui| {
+                    if self.prompts.is_empty() {
+                        ui.weak(tr!("presets-empty"));
+                    }
+                    for saved in &self.prompts {
+                        if ui
+                            .selectable_label(saved.latest() == prompt.as_str(), &saved.name)
+                            .on_hover_text(saved.latest())
+                            .clicked()
+                        {
+                            *prompt = saved.latest().to_owned();
+                            *examples = saved.examples.clone();
+                            name = saved.name.clone();
+                        }
+                    }
+                });
+
+            ui.add(
+                egui::TextEdit::singleline(&mut name)
+                    .hint_text(tr!("presets-name-hint"))
+                    .desired_width(140.0),
+            );
+            let exists = self.prompts.iter().any(|p| p.name == name.trim());
+            if ui
+                .add_enabled(
+                    !name.trim().is_empty() && !prompt.trim().is_empty(),
+                    egui::Button::new(tr!("presets-save")),
+                )
+                .on_hover_text(if exists {
+                    tr!("presets-overwrite")
+                } else {
+                    tr!("presets-save-hint")
+                })
+                .clicked()
+            {
+                self.save_as(name.trim(), prompt, examples);
+            }
+        });
+        ui.data_mut(|d| d.insert_temp(name_id, name));
+
+        if self.prompts.is_empty() {
+            return;
+        }
+        egui::CollapsingHeader::new(tr!("presets-manage"))
+            .id_salt(ui.make_persistent_id("system_presets_manage"))
+            .show(ui, |ui| self.show_manager(ui));
+    }
+
+    fn show_manager(&mut self, ui: &mut egui::Ui) {
+        let mut remove = None;
+        for saved in &mut self.prompts {
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut saved.name)
+                        .hint_text(tr!("presets-name-hint"))
+                        .desired_width(160.0),
+                )
+                .on_hover_text(saved.latest());
+                ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui
+                        .add(
+                            egui::Button::new("❌")
+                                .small()
+                                .fill(Color32::TRANSPARENT)
+                                .stroke(Stroke::NONE),
+                        )
+                        .on_hover_text(tr!("presets-remove"))
+                        .clicked()
+                    {
+                        remove = Some(saved.id);
+                    }
+                });
+            });
+        }
+        if let Some(id) = remove {
+            self.prompts.retain(|p| p.id != id);
+            if self.selected == Some(id) {
+                self.selected = None;
+            }
+        }
+        if ui.button(tr!("presets-export")).clicked() {
+            self.export(ui.ctx());
+        }
+    }
+
+    pub fn set_ab_outputs(
+        &mut self,
+        prompt_id: u64,
+        a: Result<String, String>,
+        b: Result<String, String>,
+    ) {
+        if self.runner.prompt_id == prompt_id {
+            self.runner.running = false;
+            self.runner.outputs = Some((a, b));
+        }
+    }
+
+    pub fn show_sidebar(&mut self, ui: &mut egui::Ui) {
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add(egui::Button::new(tr!("prompt-library-new")).min_size(egui::vec2(0.0, 24.0)))
+                .clicked()
+            {
+                let id = self.add(tr!("prompt-library-new-name"), String::new());
+                self.selected = Some(id);
+            }
+        });
+        ui.add_space(2.0);
+
+        let mut remove = None;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for prompt in &self.prompts {
+                ui.horizontal(|ui| {
+                    if ui
+                        .selectable_label(self.selected == Some(prompt.id), &prompt.name)
+                        .on_hover_text(tr!(
+                            "prompt-library-versions",
+                            count = prompt.versions.len()
+                        ))
+                        .clicked()
+                    {
+                        self.selected = Some(prompt.id);
+                    }
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui
+                            .add(
+                                egui::Button::new("❌")
+                                    .small()
+                                    .fill(Color32::TRANSPARENT)
+                                    .stroke(Stroke::NONE),
+                            )
+                            .on_hover_text(tr!("prompt-library-remove"))
+                            .clicked()
+                        {
+                            remove = Some(prompt.id);
+                        }
+                    });
+                });
+            }
+        });
+
+        if let Some(id) = remove {
+            self.prompts.retain(|p| p.id != id);
+            if self.selected == Some(id) {
+                self.selected = None;
+            }
+        }
+    }
+
+    /// Shows the editor for the selected prompt. Returns an A/B run to execute, if requested.
+    pub fn show_editor(&mut self, ui: &mut egui::Ui, default_model: GeminiModel) -> Option<AbRequest> {
+        let Some(prompt) = self
+            .selected
+            .and_then(|id| self.prompts.iter_mut().find(|p| p.id == id))
+        else {
+            ui.weak(tr!("prompt-library-select"));
+            return None;
+        };
+        if prompt.versions.is_empty() {
+            prompt.versions.push(PromptVersion::default());
+        }
+
+        ui.add(
+            egui::TextEdit::singleline(&mut prompt.name)
+                .hint_text(tr!("prompt-library-name-hint"))
+                .desired_width(f32::INFINITY),
+        );
+        ui.add_space(4.0);
+
+        let draft = prompt
+            .draft
+            .get_or_insert_with(|| prompt.versions.last().map(|v| v.text.clone()).unwrap_or_default());
+        ui.add(
+            egui::TextEdit::multiline(draft)
+                .hint_text(tr!("prompt-library-text-hint"))
+                .desired_rows(8)
+                .desired_width(f32::INFINITY),
+        );
+
+        let dirty = prompt.draft.as_deref() != Some(prompt.latest());
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(dirty, egui::Button::new(tr!("prompt-library-save-version")))
+                .clicked()
+            {
+                if let Some(draft) = prompt.draft.clone() {
+                    prompt.commit(draft);
+                }
+            }
+            if ui
+                .add_enabled(dirty, egui::Button::new(tr!("prompt-library-discard")))
+                .clicked()
+            {
+                prompt.draft = None;
+            }
+        });
+
+        ui.collapsing(tr!("few-shot-title"), |ui| {
+            widgets::examples_editor(ui, ("prompt_examples", prompt.id), &mut prompt.examples);
+        });
+
+        let history = tr!("prompt-library-history", count = prompt.versions.len());
+        ui.collapsing(history, |ui| {
+            for idx in (0..prompt.versions.len()).rev() {
+                ui.horizontal(|ui| {
+                    ui.label(prompt.version_label(idx));
+                    if ui
+                        .small_button(tr!("prompt-library-restore"))
+                        .on_hover_text(tr!("prompt-library-restore-hint"))
+                        .clicked()
+                    {
+                        prompt.draft = Some(prompt.versions[idx].text.clone());
+                    }
+                });
+                ui.add_enabled(
+                    false,
+                    egui::Label::new(&prompt.versions[idx].text).truncate(),
+                );
+            }
+        });
+
+        let runner = &mut self.runner;
+        if runner.prompt_id != prompt.id {
+            *runner = AbRunner {
+                prompt_id: prompt.id,
+                ..Default::default()
+            };
+        }
+        let last = prompt.versions.len() - 1;
+        let a = runner.a.get_or_insert(AbSide {
+            version: last.saturating_sub(1),
+            model: default_model,
+        });
+        a.version = a.version.min(last);
+        let b = runner.b.get_or_insert(AbSide {
+            version: last,
+            model: default_model,
+        });
+        b.version = b.version.min(last);
+
+        let mut request = None;
+        egui::CollapsingHeader::new(tr!("prompt-library-ab"))
+            .default_open(true)
+            .show(ui, |ui| {
+                ui.label(tr!("prompt-library-ab-help"));
+                egui::Grid::new("ab_sides_grid").num_columns(3).show(ui, |ui| {
+                    for (name, side) in [("A", runner.a.as_mut()), ("B", runner.b.as_mut())] {
+                        let Some(side) = side else { continue };
+                        ui.strong(name);
+                        egui::ComboBox::from_id_salt(("ab_version", name))
+                            .selected_text(prompt.version_label(side.version))
+                            .show_ui(ui, |ui| {
+                                for idx in (0..prompt.versions.len()).rev() {
+                                    ui.selectable_value(
+                                        &mut side.version,
+                                        idx,
+                                        prompt.version_label(idx),
+                                    );
+                                }
+                            });
+                        widgets::model_selector(ui, ("ab_model", name), &mut side.model);
+                        ui.end_row();
+                    }
+                });
+
+                ui.add(
+                    egui::TextEdit::multiline(&mut runner.input)
+                        .hint_text(tr!("prompt-library-test-input"))
+                        .desired_rows(3)
+                        .desired_width(f32::INFINITY),
+                );
+
+                ui.horizontal(|ui| {
+                    let can_run = !runner.running && !runner.input.trim().is_empty();
+                    if ui.add_enabled(can_run, egui::Button::new(tr!("prompt-library-run"))).clicked() {
+                        if let (Some(a), Some(b)) = (runner.a, runner.b) {
+                            runner.running = true;
+                            runner.outputs = None;
+                            request = Some(AbRequest {
+                                prompt_id: prompt.id,
+                                input: runner.input.clone(),
+                                a: (prompt.versions[a.version].text.clone(), a.model),
+                                b: (prompt.versions[b.version].text.clone(), b.model),
+                            });
+                        }
+                    }
+                    if runner.running {
+                        ui.spinner();
+                    }
+                });
+
+                if let Some((out_a, out_b)) = &runner.outputs {
+                    let mut choice = None;
+                    ui.columns(2, |columns| {
+                        for (ui, (name, out, pick)) in columns.iter_mut().zip([
+                            ("A", out_a, AbChoice::A),
+                            ("B", out_b, AbChoice::B),
+                        ]) {
+                            ui.strong(name);
+                            match out {
+                                Ok(text) => ui.label(text),
+                                Err(e) => ui.colored_label(ui.visuals().error_fg_color, e),
+                            };
+                            if ui.button(tr!("prompt-library-prefer", side = name)).clicked() {
+                                choice = Some(pick);
+                            }
+                        }
+                    });
+                    if ui.button(tr!("prompt-library-tie")).clicked() {
+                        choice = Some(AbChoice::Tie);
+                    }
+
+                    if let (Some(preferred), Some(a), Some(b)) = (choice, runner.a, runner.b) {
+                        prompt.ab_records.push(AbRecord {
+                            version_a: a.version,
+                            model_a: a.model,
+                            version_b: b.version,
+                            model_b: b.model,
+                            input: runner.input.clone(),
+                            preferred,
+                            time: chrono::Utc::now(),
+                        });
+                        runner.outputs = None;
+                    }
+                }
+
+                if !prompt.ab_records.is_empty() {
+                    let results = tr!("prompt-library-results", count = prompt.ab_records.len());
+                    ui.collapsing(results, |ui| {
+                        egui::Grid::new("ab_records_grid")
+                            .num_columns(3)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for record in prompt.ab_records.iter().rev() {
+                                    ui.label(format!(
+                                        "v{} {} vs v{} {}",
+                                        record.version_a + 1,
+                                        record.model_a,
+                                        record.version_b + 1,
+                                        record.model_b
+                                    ));
+                                    ui.label(match record.preferred {
+                                        AbChoice::A => "A".to_owned(),
+                                        AbChoice::B => "B".to_owned(),
+                                        AbChoice::Tie => tr!("prompt-library-tie"),
+                                    });
+                                    ui.add(egui::Label::new(&record.input).truncate());
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                }
+            });
+
+        request
+    }
+}
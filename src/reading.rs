@@ -0,0 +1,328 @@
+use crate::i18n::tr;
+use eframe::egui::{self, FontFamily, RichText, TextStyle};
+use egui_commonmark::{CommonMarkCache, CommonMarkViewer};
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use std::path::PathBuf;
+
+/// Reading aids for assistant messages. Code blocks keep the monospace font and spacing.
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct ReadingSettings {
+    pub enabled: bool,
+    /// Font file for assistant text, e.g. OpenDyslexic
+    pub font_path: String,
+    /// Multiple of the font size
+    pub line_height: f32,
+    /// Extra points between letters
+    pub letter_spacing: f32,
+}
+
+impl Default for ReadingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            font_path: String::new(),
+            line_height: 1.0,
+            letter_spacing: 0.0,
+        }
+    }
+}
+
+impl ReadingSettings {
+    /// The font file to install, if the aids are on and it exists.
+    pub fn font_file(&self) -> Option<PathBuf> {
+        Some(PathBuf::from(self.font_path.trim())).filter(|path| self.enabled && path.is_file())
+    }
+
+    fn spaced(&self) -> bool {
+        self.line_height != 1.0 || self.letter_spacing != 0.0
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.enabled, tr!("reading-enabled"))
+            .on_hover_text(tr!("reading-enabled-help"));
+        ui.add_enabled_ui(self.enabled, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr!("reading-font"));
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.font_path)
+                        .hint_text(tr!("reading-font-hint")),
+                );
+            });
+            ui.add(
+                egui::Slider::new(&mut self.line_height, 1.0..=2.5)
+                    .step_by(0.1)
+                    .text(tr!("reading-line-height")),
+            );
+            ui.add(
+                egui::Slider::new(&mut self.letter_spacing, 0.0..=4.0)
+                    .step_by(0.25)
+                    .text(tr!("reading-letter-spacing")),
+            );
+        });
+    }
+}
+
+/// Splits markdown into prose and fenced code blocks.
+fn blocks(content: &str) -> Vec<(bool, &str)> {
+    let mut blocks: Vec<(bool, usize, usize)> = Vec::new();
+    let mut in_fence = false;
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        let is_fence = matches!(line.trim_start().get(..3), Some("```" | "~~~"));
+        let code = in_fence || is_fence;
+        if is_fence {
+            in_fence = !in_fence;
+        }
+        let end = offset + line.len();
+        match blocks.last_mut() {
+            Some(last) if last.0 == code => last.2 = end,
+            _ => blocks.push((code, offset, end)),
+        }
+        offset = end;
+    }
+    blocks
+        .into_iter()
+        .map(|(code, start, end)| (code, &content[start..end]))
+        .collect()
+}
+
+/// A piece of inline text with the formatting the markdown gave it.
+#[derive(Default)]
+struct Run {
+    text: String,
+    strong: bool,
+    italics: bool,
+    strikethrough: bool,
+    code: bool,
+    link: Option<String>,
+}
+
+/// Inline formatting and block nesting while walking the markdown events.
+#[derive(Default)]
+struct Prose {
+    runs: Vec<Run>,
+    strong: bool,
+    italics: bool,
+    strikethrough: bool,
+    link: Option<String>,
+    heading: Option<HeadingLevel>,
+    /// Next number of each open list, `None` for bullet lists
+    lists: Vec<Option<u64>>,
+    /// Marker of a list item whose first line hasn't been shown yet
+    marker: Option<String>,
+    quotes: usize,
+}
+
+impl Prose {
+    fn push(&mut self, text: &str, code: bool) {
+        self.runs.push(Run {
+            text: text.to_owned(),
+            strong: self.strong || self.heading.is_some(),
+            italics: self.italics,
+            strikethrough: self.strikethrough,
+            code,
+            link: self.link.clone(),
+        });
+    }
+
+    /// Shows the collected runs as one wrapped paragraph, the way the markdown viewer lays
+    /// them out, with the reading spacing on every run.
+    fn flush(&mut self, ui: &mut egui::Ui, settings: &ReadingSettings) {
+        if self.runs.is_empty() && self.marker.is_none() {
+            return;
+        }
+        let style = match self.heading {
+            Some(HeadingLevel::H1 | HeadingLevel::H2) => TextStyle::Heading,
+            _ => TextStyle::Body,
+        };
+        let size = style.resolve(ui.style()).size;
+        let indent = (self.lists.len().saturating_sub(1) + self.quotes) as f32 * size;
+        let marker = self.marker.take();
+        let runs = std::mem::take(&mut self.runs);
+        ui.horizontal_wrapped(|ui| {
+            ui.spacing_mut().item_spacing.x = 0.0;
+            ui.add_space(indent);
+            if self.quotes > 0 {
+                ui.weak("▏ ");
+            }
+            if let Some(marker) = marker {
+                ui.label(RichText::new(format!("{marker} ")).text_style(style.clone()));
+            }
+            for run in runs {
+                let mut text = RichText::new(run.text)
+                    .text_style(style.clone())
+                    .line_height(Some(size * settings.line_height));
+                if run.code {
+                    text = text.code();
+                } else {
+                    text = text.extra_letter_spacing(settings.letter_spacing);
+                }
+                if run.strong {
+                    text = text.strong();
+                }
+                if run.italics {
+                    text = text.italics();
+                }
+                if run.strikethrough {
+                    text = text.strikethrough();
+                }
+                match run.link {
+                    Some(url) => {
+                        ui.hyperlink_to(text, url);
+                    }
+                    None => {
+                        ui.label(text);
+                    }
+                }
+            }
+        });
+        ui.add_space(size * (settings.line_height - 1.0).max(0.0));
+    }
+}
+
+/// Renders prose with the reading spacing. Paragraphs, headings, lists and quotes are laid
+/// out here run by run, since the markdown viewer can't pass spacing to its text. Anything
+/// else, like tables and images, goes to the viewer as it is.
+fn show_prose(
+    ui: &mut egui::Ui,
+    cache: &mut CommonMarkCache,
+    block: &str,
+    settings: &ReadingSettings,
+) {
+    let options = Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TABLES;
+    let mut prose = Prose::default();
+    // nesting of the block the viewer is showing, its events are skipped
+    let mut delegated = 0;
+    let mut depth = 0;
+    for (event, range) in Parser::new_ext(block, options).into_offset_iter() {
+        if delegated > 0 {
+            match event {
+                Event::Start(_) => delegated += 1,
+                Event::End(_) => delegated -= 1,
+                _ => (),
+            }
+            continue;
+        }
+        match event {
+            Event::Start(tag) => {
+                let own = match &tag {
+                    Tag::Paragraph => !block[range.clone()].contains("!["),
+                    Tag::Heading { .. }
+                    | Tag::List(_)
+                    | Tag::Item
+                    | Tag::BlockQuote(_)
+                    | Tag::Emphasis
+                    | Tag::Strong
+                    | Tag::Strikethrough
+                    | Tag::Link { .. } => true,
+                    _ => false,
+                };
+                if !own {
+                    prose.flush(ui, settings);
+                    CommonMarkViewer::new().max_image_width(Some(512)).show(
+                        ui,
+                        cache,
+                        &block[range],
+                    );
+                    delegated = 1;
+                    continue;
+                }
+                depth += 1;
+                match tag {
+                    Tag::Heading { level, .. } => prose.heading = Some(level),
+                    Tag::List(start) => {
+                        // the item the list is nested in shows its text first
+                        prose.flush(ui, settings);
+                        prose.lists.push(start);
+                    }
+                    Tag::Item => {
+                        prose.flush(ui, settings);
+                        prose.marker = Some(match prose.lists.last_mut() {
+                            Some(Some(number)) => {
+                                *number += 1;
+                                format!("{}.", *number - 1)
+                            }
+                            _ => "•".to_owned(),
+                        });
+                    }
+                    Tag::BlockQuote(_) => prose.quotes += 1,
+                    Tag::Emphasis => prose.italics = true,
+                    Tag::Strong => prose.strong = true,
+                    Tag::Strikethrough => prose.strikethrough = true,
+                    Tag::Link { dest_url, .. } => prose.link = Some(dest_url.to_string()),
+                    _ => (),
+                }
+            }
+            Event::End(tag) => {
+                depth -= 1;
+                match tag {
+                    TagEnd::Paragraph | TagEnd::Item => prose.flush(ui, settings),
+                    TagEnd::Heading(_) => {
+                        prose.flush(ui, settings);
+                        prose.heading = None;
+                    }
+                    TagEnd::List(_) => {
+                        prose.flush(ui, settings);
+                        prose.lists.pop();
+                    }
+                    TagEnd::BlockQuote(_) => {
+                        prose.flush(ui, settings);
+                        prose.quotes = prose.quotes.saturating_sub(1);
+                    }
+                    TagEnd::Emphasis => prose.italics = false,
+                    TagEnd::Strong => prose.strong = false,
+                    TagEnd::Strikethrough => prose.strikethrough = false,
+                    TagEnd::Link => prose.link = None,
+                    _ => (),
+                }
+                if depth == 0 {
+                    ui.add_space(TextStyle::Body.resolve(ui.style()).size / 2.0);
+                }
+            }
+            Event::Text(text) => prose.push(&text, false),
+            Event::Code(text) => prose.push(&text, true),
+            Event::SoftBreak => prose.push(" ", false),
+            Event::HardBreak => prose.push("\n", false),
+            Event::Rule => {
+                prose.flush(ui, settings);
+                ui.separator();
+            }
+            _ => prose.push(&block[range], false),
+        }
+    }
+    prose.flush(ui, settings);
+}
+
+/// Renders an assistant message with the reading aids. The reading font reaches the markdown
+/// viewer through the text styles, custom spacing is applied by [`show_prose`].
+pub fn show_message(
+    ui: &mut egui::Ui,
+    cache: &mut CommonMarkCache,
+    content: &str,
+    settings: &ReadingSettings,
+) {
+    let rendered = crate::plugins::render_blocks(ui.ctx(), content);
+    let content = rendered.as_deref().unwrap_or(content);
+    ui.scope(|ui| {
+        // without a reading font the family is the same as the proportional one
+        for font_id in ui.style_mut().text_styles.values_mut() {
+            if font_id.family == FontFamily::Proportional {
+                font_id.family = FontFamily::Name(crate::style::READING_FAMILY.into());
+            }
+        }
+        if !settings.spaced() {
+            crate::bidi::show_markdown(ui, cache, content, Some(512));
+            return;
+        }
+        ui.vertical(|ui| {
+            for (code, block) in blocks(content) {
+                if code {
+                    CommonMarkViewer::new().show(ui, cache, block);
+                } else {
+                    show_prose(ui, cache, block, settings);
+                }
+            }
+        });
+    });
+}
@@ -0,0 +1,241 @@
+use crate::i18n::tr;
+use anyhow::{anyhow, Result};
+use cpal::{
+    traits::{DeviceTrait, HostTrait, StreamTrait},
+    Sample,
+};
+use eframe::egui::{self, vec2, CornerRadius};
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+use tokio::sync::oneshot::{self, error::TryRecvError};
+
+/// Recordings stop by themselves after this long, so a forgotten one doesn't fill the memory.
+const MAX_DURATION: Duration = Duration::from_secs(30 * 60);
+
+fn recordings_dir() -> Option<PathBuf> {
+    eframe::storage_dir(crate::TITLE).map(|dir| dir.join("recordings"))
+}
+
+/// Mono samples from the default input device, at its own rate.
+struct Capture {
+    samples: Arc<Mutex<Vec<f32>>>,
+    rate: u32,
+    // dropping it stops the recording
+    _stream: cpal::Stream,
+}
+
+fn open_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    samples: Arc<Mutex<Vec<f32>>>,
+) -> Result<cpal::Stream>
+where
+    T: cpal::SizedSample,
+    f32: cpal::FromSample<T>,
+{
+    let channels = config.channels.max(1) as usize;
+    let stream = device.build_input_stream(
+        config,
+        move |data: &[T], _| {
+            // one channel is enough for speech
+            let mono = data.chunks(channels).map(|frame| {
+                frame.iter().map(|s| s.to_sample::<f32>()).sum::<f32>() / frame.len() as f32
+            });
+            if let Ok(mut samples) = samples.lock() {
+                samples.extend(mono);
+            }
+        },
+        |e| log::error!("microphone stream failed: {e}"),
+        None,
+    )?;
+    Ok(stream)
+}
+
+fn start_capture() -> Result<Capture> {
+    let device = cpal::default_host()
+        .default_input_device()
+        .ok_or_else(|| anyhow!("no microphone found"))?;
+    let supported = device.default_input_config()?;
+    let format = supported.sample_format();
+    let config: cpal::StreamConfig = supported.into();
+    let samples = Arc::new(Mutex::new(Vec::new()));
+    let stream = match format {
+        cpal::SampleFormat::F32 => open_stream::<f32>(&device, &config, samples.clone())?,
+        cpal::SampleFormat::I16 => open_stream::<i16>(&device, &config, samples.clone())?,
+        cpal::SampleFormat::U16 => open_stream::<u16>(&device, &config, samples.clone())?,
+        format => return Err(anyhow!("unsupported sample format {format}")),
+    };
+    stream.play()?;
+    log::info!(
+        "recording from {} at {} Hz",
+        device.name().unwrap_or_default(),
+        config.sample_rate.0
+    );
+    Ok(Capture {
+        samples,
+        rate: config.sample_rate.0,
+        _stream: stream,
+    })
+}
+
+/// Writes the recording as WAV where the chats can keep referring to it.
+fn save(samples: &[f32], rate: u32) -> Result<PathBuf> {
+    if samples.is_empty() {
+        return Err(anyhow!("nothing was recorded"));
+    }
+    let dir = recordings_dir().ok_or_else(|| anyhow!("no data folder"))?;
+    std::fs::create_dir_all(&dir)?;
+    let name = chrono::Local::now().format("recording-%Y%m%d-%H%M%S.wav");
+    let path = dir.join(name.to_string());
+    let pcm: Vec<i16> = samples
+        .iter()
+        .map(|s| (s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)
+        .collect();
+    std::fs::write(&path, crate::audio::wav(&pcm, rate))?;
+    log::info!("saved a recording to {}", path.display());
+    Ok(path)
+}
+
+/// Records until `stop` is set, then saves unless `discard` is set too.
+fn record(stop: &AtomicBool, discard: &AtomicBool) -> Result<Option<PathBuf>> {
+    let capture = start_capture()?;
+    let started = Instant::now();
+    while !stop.load(Ordering::Relaxed) && started.elapsed() < MAX_DURATION {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    let Capture { samples, rate, .. } = capture;
+    if discard.load(Ordering::Relaxed) {
+        return Ok(None);
+    }
+    let samples = samples
+        .lock()
+        .map(|mut samples| std::mem::take(&mut *samples))
+        .unwrap_or_default();
+    save(&samples, rate).map(Some)
+}
+
+struct Recording {
+    started: Instant,
+    stop: Arc<AtomicBool>,
+    discard: Arc<AtomicBool>,
+    done: oneshot::Receiver<Result<Option<PathBuf>, String>>,
+}
+
+/// The microphone button of the chatbox, records into a WAV attachment.
+#[derive(Default)]
+pub struct Recorder {
+    recording: Option<Recording>,
+    /// Why the last recording failed
+    error: Option<String>,
+}
+
+impl Recorder {
+    fn start(&mut self, ctx: &egui::Context) {
+        let stop = Arc::new(AtomicBool::new(false));
+        let discard = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = oneshot::channel();
+        self.error = None;
+        self.recording = Some(Recording {
+            started: Instant::now(),
+            stop: stop.clone(),
+            discard: discard.clone(),
+            done: rx,
+        });
+        let ctx = ctx.clone();
+        // the stream may not leave the thread it was made on
+        std::thread::spawn(move || {
+            let result = record(&stop, &discard).map_err(|e| {
+                log::error!("failed to record audio: {e}");
+                e.to_string()
+            });
+            let _ = tx.send(result);
+            ctx.request_repaint();
+        });
+    }
+
+    fn stop(&mut self, discard: bool) {
+        if let Some(recording) = &self.recording {
+            recording.discard.store(discard, Ordering::Relaxed);
+            recording.stop.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// The saved recording, once there is one.
+    fn poll(&mut self) -> Option<PathBuf> {
+        let recording = self.recording.as_mut()?;
+        let result = match recording.done.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Closed) => Ok(None),
+        };
+        self.recording = None;
+        match result {
+            Ok(path) => path,
+            Err(e) => {
+                self.error = Some(e);
+                None
+            }
+        }
+    }
+
+    /// Shows the record button, or the stop and discard buttons while recording. Returns
+    /// the recording to attach.
+    pub fn show(&mut self, ui: &mut egui::Ui) -> Option<PathBuf> {
+        let saved = self.poll();
+        let button = |text: egui::RichText| {
+            egui::Button::new(text)
+                .min_size(vec2(32.0, 32.0))
+                .corner_radius(CornerRadius::same(u8::MAX))
+        };
+        match &self.recording {
+            Some(recording) if !recording.stop.load(Ordering::Relaxed) => {
+                let elapsed = crate::audio::format_duration(recording.started.elapsed());
+                let text =
+                    egui::RichText::new(format!("⏹ {elapsed}")).color(ui.visuals().error_fg_color);
+                if ui
+                    .add(button(text))
+                    .on_hover_text_at_pointer(tr!("record-stop"))
+                    .clicked()
+                {
+                    self.stop(false);
+                }
+                if ui
+                    .add(button("🗑".into()))
+                    .on_hover_text_at_pointer(tr!("record-discard"))
+                    .clicked()
+                {
+                    self.stop(true);
+                }
+                ui.ctx().request_repaint_after(Duration::from_millis(250));
+            }
+            // being saved
+            Some(_) => {
+                ui.add_sized(vec2(32.0, 32.0), egui::Spinner::new());
+            }
+            None => {
+                let mut text = egui::RichText::new("🎤");
+                if self.error.is_some() {
+                    text = text.color(ui.visuals().error_fg_color);
+                }
+                let hint = match &self.error {
+                    Some(error) => tr!("record-failed", error = error.as_str()),
+                    None => tr!("record-start"),
+                };
+                if ui
+                    .add(button(text))
+                    .on_hover_text_at_pointer(hint)
+                    .clicked()
+                {
+                    self.start(ui.ctx());
+                }
+            }
+        }
+        saved
+    }
+}
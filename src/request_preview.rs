@@ -0,0 +1,355 @@
+use crate::{
+    chat::Message,
+    i18n::tr,
+    widgets::{AuthMethod, FewShotExample, ModelPicker, Settings},
+};
+use anyhow::Result;
+use eframe::egui::{self, RichText};
+use gemini_rust::{Content, Gemini, GenerateContentRequest, Part, Role};
+use serde_json::Value;
+
+/// Longer texts are cut in the window, the copied JSON has them whole.
+const MAX_PREVIEW_CHARS: usize = 2000;
+/// Base64 longer than this is replaced with its size in the copied JSON.
+const MAX_INLINE_DATA: usize = 64;
+
+/// Everything about a chat that goes into its next request, taken on the UI thread.
+pub struct Draft {
+    pub chat_id: usize,
+    pub model_picker: ModelPicker,
+    pub system_prompt: Option<String>,
+    pub examples: Vec<FewShotExample>,
+    /// Collection and the question it's searched for
+    pub knowledge: Option<(u64, String)>,
+    /// The history, ending with the text in the chatbox
+    pub messages: Vec<Message>,
+}
+
+/// A part of the request with its size.
+struct Section {
+    title: String,
+    contents: Vec<Content>,
+    /// `None` when counting failed or isn't available
+    tokens: Option<u32>,
+}
+
+pub struct Preview {
+    chat_id: usize,
+    model: String,
+    sections: Vec<Section>,
+    /// Excerpts of the knowledge collection put into the system instruction
+    citations: usize,
+    thoughts_in_history: bool,
+    generation_config: String,
+    safety_settings: String,
+    /// The whole request as JSON, with the file data shortened
+    request: String,
+}
+
+async fn count_tokens(client: &Gemini, contents: &[Content]) -> Option<u32> {
+    if contents.is_empty() {
+        return Some(0);
+    }
+    let mut builder = client.generate_content();
+    builder.contents.extend(contents.iter().cloned());
+    match builder.count_tokens().await {
+        Ok(resp) => Some(resp.total_tokens),
+        Err(e) => {
+            log::warn!("failed to count the tokens of the request preview: {e}");
+            None
+        }
+    }
+}
+
+/// Replaces inline file data with its size, nobody wants to scroll through base64.
+fn shorten_data(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                match value {
+                    Value::String(data) if key == "data" && data.len() > MAX_INLINE_DATA => {
+                        let bytes = data.len() / 4 * 3;
+                        *data = format!("<{} of base64>", bytesize::ByteSize(bytes as u64));
+                    }
+                    _ => shorten_data(value),
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(shorten_data),
+        _ => (),
+    }
+}
+
+fn pretty(value: impl serde::Serialize) -> String {
+    serde_json::to_value(value)
+        .and_then(|v| serde_json::to_string_pretty(&v))
+        .unwrap_or_default()
+}
+
+/// Assembles the request `draft` would send, the same way sending does, and counts
+/// the tokens of every section. Counting needs an API key.
+pub async fn build(settings: Settings, draft: Draft) -> Result<Preview> {
+    let mut system_prompt = draft.system_prompt.filter(|p| !p.trim().is_empty());
+    let mut citations = 0;
+    if let Some((collection, query)) = &draft.knowledge {
+        match crate::knowledge::retrieve(&settings, *collection, query).await {
+            Ok(found) if found.iter().any(|c| !c.quarantined) => {
+                citations = found.iter().filter(|c| !c.quarantined).count();
+                system_prompt = Some(crate::knowledge::with_context(system_prompt, &found));
+            }
+            Ok(_) => (),
+            Err(e) => log::warn!("failed to search the knowledge collection: {e}"),
+        }
+    }
+
+    let counting =
+        matches!(settings.auth_method, AuthMethod::ApiKey) && !settings.api_key.is_empty();
+    let client = if counting {
+        draft
+            .model_picker
+            .create_client(&settings.api_key, settings.proxy_path.clone())?
+    } else {
+        Gemini::new("")?
+    };
+    let examples = geminid_core::history::examples_to_history(&draft.examples);
+    let mut history =
+        crate::chat_completion::build_history(&client, &draft.messages, 0, None, None, None)
+            .await?;
+    let prompt: Vec<Content> = match history.last() {
+        Some(last) if last.role == Some(Role::User) => history.pop().into_iter().collect(),
+        _ => Vec::new(),
+    };
+    let system: Vec<Content> = system_prompt.map(Content::text).into_iter().collect();
+
+    let (system_tokens, example_tokens, history_tokens, prompt_tokens) = if counting {
+        futures::join!(
+            count_tokens(&client, &system),
+            count_tokens(&client, &examples),
+            count_tokens(&client, &history),
+            count_tokens(&client, &prompt),
+        )
+    } else {
+        (None, None, None, None)
+    };
+
+    let generation_config = draft.model_picker.get_generation_config();
+    let mut tools = draft.model_picker.tools();
+    tools.extend(crate::functions::to_tool(&settings.functions));
+    let safety_settings = draft
+        .model_picker
+        .safety_settings(&settings.safety)
+        .to_api();
+    let request = GenerateContentRequest {
+        contents: examples
+            .iter()
+            .chain(&history)
+            .chain(&prompt)
+            .cloned()
+            .collect(),
+        generation_config: Some(draft.model_picker.get_generation_config()),
+        safety_settings: Some(safety_settings.clone()),
+        tools: (!tools.is_empty()).then_some(tools),
+        tool_config: None,
+        system_instruction: system.first().cloned(),
+        cached_content: None,
+    };
+    let mut request = serde_json::to_value(&request)?;
+    shorten_data(&mut request);
+
+    let section = |title: &str, contents: Vec<Content>, tokens| Section {
+        title: tr!(title),
+        contents,
+        tokens,
+    };
+    Ok(Preview {
+        chat_id: draft.chat_id,
+        model: draft.model_picker.selected.to_string(),
+        sections: vec![
+            section("preview-system", system, system_tokens),
+            section("preview-examples", examples, example_tokens),
+            section("preview-history", history, history_tokens),
+            section("preview-prompt", prompt, prompt_tokens),
+        ],
+        citations,
+        thoughts_in_history: settings.include_thoughts_in_history,
+        generation_config: pretty(&generation_config),
+        safety_settings: pretty(&safety_settings),
+        request: serde_json::to_string_pretty(&request)?,
+    })
+}
+
+fn cut(text: &str) -> String {
+    if text.chars().count() <= MAX_PREVIEW_CHARS {
+        return text.to_owned();
+    }
+    let cut: String = text.chars().take(MAX_PREVIEW_CHARS).collect();
+    format!("{cut}…")
+}
+
+fn show_content(ui: &mut egui::Ui, content: &Content) {
+    let role = match content.role {
+        Some(Role::Model) => tr!("preview-role-model"),
+        _ => tr!("preview-role-user"),
+    };
+    ui.strong(role);
+    for part in content.parts.iter().flatten() {
+        match part {
+            Part::Text { text, .. } => {
+                ui.label(cut(text));
+            }
+            Part::InlineData { inline_data } => {
+                let bytes = inline_data.data.len() / 4 * 3;
+                ui.label(tr!(
+                    "preview-inline-file",
+                    mime = inline_data.mime_type.clone(),
+                    size = bytesize::ByteSize(bytes as u64).to_string()
+                ));
+            }
+            Part::FileData { file_data } => {
+                ui.label(tr!(
+                    "preview-uploaded-file",
+                    mime = file_data.mime_type.clone(),
+                    uri = file_data.file_uri.clone()
+                ));
+            }
+            _ => {
+                ui.weak(pretty(part));
+            }
+        }
+    }
+    ui.add_space(4.0);
+}
+
+/// Shows the request a chat is about to send, before sending it.
+#[derive(Default)]
+pub struct RequestPreview {
+    pub open: bool,
+    /// Id of the chat being assembled
+    loading: Option<usize>,
+    result: Option<Result<Preview, String>>,
+}
+
+impl RequestPreview {
+    pub fn start(&mut self, chat_id: usize) {
+        self.open = true;
+        self.loading = Some(chat_id);
+        self.result = None;
+    }
+
+    pub fn finish(&mut self, result: Result<Preview, String>) {
+        let chat_id = match &result {
+            Ok(preview) => Some(preview.chat_id),
+            Err(_) => self.loading,
+        };
+        // a preview of another chat was asked for meanwhile
+        if self.loading != chat_id {
+            return;
+        }
+        self.loading = None;
+        self.result = Some(result);
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        if !self.open {
+            return;
+        }
+        let mut open = true;
+        egui::Window::new(tr!("preview-title"))
+            .open(&mut open)
+            .default_width(560.0)
+            .default_height(480.0)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| self.show_inner(ui));
+            });
+        self.open = open;
+    }
+
+    fn show_inner(&mut self, ui: &mut egui::Ui) {
+        if self.loading.is_some() {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(tr!("preview-loading"));
+            });
+            return;
+        }
+        let Some(result) = &self.result else {
+            return;
+        };
+        let preview = match result {
+            Ok(preview) => preview,
+            Err(e) => {
+                ui.colored_label(ui.visuals().error_fg_color, e);
+                return;
+            }
+        };
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("preview-model", model = preview.model.clone()));
+            let total: Option<u32> = preview.sections.iter().map(|s| s.tokens).sum();
+            if let Some(total) = total {
+                ui.separator();
+                ui.label(tr!("token-count", count = total));
+            }
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button(tr!("preview-copy")).clicked() {
+                    ui.ctx().copy_text(preview.request.clone());
+                }
+            });
+        });
+        if preview.sections.iter().any(|s| s.tokens.is_none()) {
+            ui.weak(tr!("preview-no-count"));
+        }
+        if preview.citations > 0 {
+            ui.weak(tr!("preview-citations", count = preview.citations));
+        }
+        ui.weak(if preview.thoughts_in_history {
+            tr!("preview-thoughts-kept")
+        } else {
+            tr!("preview-thoughts-dropped")
+        });
+        ui.separator();
+
+        for (i, section) in preview.sections.iter().enumerate() {
+            let mut title = RichText::new(&section.title).strong();
+            if section.contents.is_empty() {
+                title = RichText::new(&section.title).weak();
+            }
+            egui::CollapsingHeader::new(title)
+                .id_salt(("preview_section", i))
+                .default_open(i == 0 || i == preview.sections.len() - 1)
+                .show(ui, |ui| {
+                    if let Some(tokens) = section.tokens {
+                        ui.weak(tr!("token-count", count = tokens));
+                    }
+                    if section.contents.is_empty() {
+                        ui.weak(tr!("preview-empty"));
+                    }
+                    for content in &section.contents {
+                        show_content(ui, content);
+                    }
+                });
+        }
+        for (id, title, json) in [
+            (
+                "preview_config",
+                tr!("preview-config"),
+                &preview.generation_config,
+            ),
+            (
+                "preview_safety",
+                tr!("preview-safety"),
+                &preview.safety_settings,
+            ),
+        ] {
+            egui::CollapsingHeader::new(RichText::new(title).strong())
+                .id_salt(id)
+                .show(ui, |ui| {
+                    ui.label(RichText::new(json).monospace());
+                });
+        }
+        ui.horizontal(|ui| {
+            ui.strong(tr!("preview-tools"));
+            ui.weak(tr!("preview-empty"));
+        });
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{i18n::tr, widgets::GeminiModel};
+use eframe::egui;
+use std::time::Duration;
+
+/// First wait after a failed request, doubled on every retry.
+const BASE_DELAY: Duration = Duration::from_secs(2);
+/// Waits never get longer than this, even when the API asks for more.
+const MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// How failed requests are retried.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct RetrySettings {
+    /// Retries after rate limits and overloaded servers
+    pub enabled: bool,
+    /// Retries before giving up, or falling back
+    pub attempts: u32,
+    /// Asked once the retries of the chosen model ran out
+    pub fallback: Option<GeminiModel>,
+}
+
+impl Default for RetrySettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            attempts: 3,
+            fallback: None,
+        }
+    }
+}
+
+/// What to do after a request failed.
+pub enum Step {
+    Wait(Duration),
+    FallBack(GeminiModel),
+    GiveUp,
+}
+
+/// Rate limits and overloaded servers, which pass if one waits.
+fn is_transient(error: &str) -> bool {
+    [
+        "429",
+        "503",
+        "RESOURCE_EXHAUSTED",
+        "UNAVAILABLE",
+        "overloaded",
+    ]
+    .iter()
+    .any(|code| error.contains(code))
+}
+
+/// The wait the API suggests, as in `Please retry in 12.5s` or `"retryDelay": "12s"`.
+fn suggested_delay(error: &str) -> Option<Duration> {
+    let rest = ["retry in ", "\"retryDelay\": \"", "\"retryDelay\":\""]
+        .iter()
+        .find_map(|prefix| error.find(prefix).map(|at| &error[at + prefix.len()..]))?;
+    let number: String = rest
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    let secs: f64 = number.parse().ok()?;
+    match rest[number.len()..].chars().next() {
+        Some('s') => Duration::try_from_secs_f64(secs).ok(),
+        _ => None,
+    }
+}
+
+impl RetrySettings {
+    /// The next step after `error`, the `attempt`th retry of `model`. `can_switch` is
+    /// false when the request can't move to another model, like with a context cache.
+    pub fn next(&self, error: &str, attempt: u32, model: GeminiModel, can_switch: bool) -> Step {
+        if !self.enabled || !is_transient(error) {
+            return Step::GiveUp;
+        }
+        if attempt < self.attempts {
+            let backoff = BASE_DELAY * 2u32.saturating_pow(attempt);
+            let delay = suggested_delay(error).map_or(backoff, |d| d.max(backoff));
+            return Step::Wait(delay.min(MAX_DELAY));
+        }
+        match self.fallback {
+            Some(fallback) if fallback != model && can_switch => Step::FallBack(fallback),
+            _ => Step::GiveUp,
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.enabled, tr!("retry-enabled"))
+            .on_hover_text(tr!("retry-enabled-help"));
+        ui.add_enabled_ui(self.enabled, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr!("retry-attempts"));
+                ui.add(egui::DragValue::new(&mut self.attempts).range(1..=10));
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr!("retry-fallback"))
+                    .on_hover_text(tr!("retry-fallback-help"));
+                let selected = self
+                    .fallback
+                    .map_or_else(|| tr!("retry-no-fallback"), |m| m.to_string());
+                egui::ComboBox::from_id_salt("retry_fallback")
+                    .selected_text(selected)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.fallback, None, tr!("retry-no-fallback"));
+                        for model in crate::models::available() {
+                            ui.selectable_value(&mut self.fallback, Some(model), model.to_string());
+                        }
+                    });
+            });
+        });
+    }
+}
+
+/// Sleeps for `delay`, false if `stopped` says the user stopped the request meanwhile.
+pub async fn wait(delay: Duration, mut stopped: impl FnMut() -> bool) -> bool {
+    let step = Duration::from_millis(100);
+    let mut waited = Duration::ZERO;
+    while waited < delay {
+        if stopped() {
+            log::info!("stopped while waiting to retry");
+            return false;
+        }
+        tokio::time::sleep(step).await;
+        waited += step;
+    }
+    true
+}
@@ -0,0 +1,352 @@
+use crate::i18n::tr;
+use eframe::egui::{self, RichText};
+use gemini_rust::{GenerationResponse, HarmBlockThreshold, HarmCategory, SafetySetting};
+use serde_json::Value;
+
+/// Finish and block reasons that mean the answer was withheld for its content.
+const BLOCK_REASONS: [&str; 6] = [
+    "SAFETY",
+    "PROHIBITED_CONTENT",
+    "BLOCKLIST",
+    "SPII",
+    "IMAGE_SAFETY",
+    "RECITATION",
+];
+
+/// The harm categories the API lets clients configure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum SafetyCategory {
+    Harassment,
+    HateSpeech,
+    SexuallyExplicit,
+    DangerousContent,
+}
+
+impl SafetyCategory {
+    const ALL: [Self; 4] = [
+        Self::Harassment,
+        Self::HateSpeech,
+        Self::SexuallyExplicit,
+        Self::DangerousContent,
+    ];
+
+    pub fn label(self) -> String {
+        tr!(match self {
+            Self::Harassment => "safety-harassment",
+            Self::HateSpeech => "safety-hate-speech",
+            Self::SexuallyExplicit => "safety-sexually-explicit",
+            Self::DangerousContent => "safety-dangerous-content",
+        })
+    }
+
+    /// How the API names it in safety ratings.
+    fn api_name(self) -> &'static str {
+        match self {
+            Self::Harassment => "HARM_CATEGORY_HARASSMENT",
+            Self::HateSpeech => "HARM_CATEGORY_HATE_SPEECH",
+            Self::SexuallyExplicit => "HARM_CATEGORY_SEXUALLY_EXPLICIT",
+            Self::DangerousContent => "HARM_CATEGORY_DANGEROUS_CONTENT",
+        }
+    }
+
+    fn from_api_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.api_name() == name)
+    }
+
+    fn harm_category(self) -> HarmCategory {
+        match self {
+            Self::Harassment => HarmCategory::Harassment,
+            Self::HateSpeech => HarmCategory::HateSpeech,
+            Self::SexuallyExplicit => HarmCategory::SexuallyExplicit,
+            Self::DangerousContent => HarmCategory::DangerousContent,
+        }
+    }
+}
+
+/// From which probability of harm a category blocks the answer.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum Threshold {
+    /// Nothing is blocked for this category, apart from what Google always blocks
+    #[default]
+    BlockNone,
+    OnlyHigh,
+    MediumAndAbove,
+    LowAndAbove,
+}
+
+impl Threshold {
+    const ALL: [Self; 4] = [
+        Self::BlockNone,
+        Self::OnlyHigh,
+        Self::MediumAndAbove,
+        Self::LowAndAbove,
+    ];
+
+    fn label(self) -> String {
+        tr!(match self {
+            Self::BlockNone => "safety-block-none",
+            Self::OnlyHigh => "safety-block-high",
+            Self::MediumAndAbove => "safety-block-medium",
+            Self::LowAndAbove => "safety-block-low",
+        })
+    }
+
+    fn api(self) -> HarmBlockThreshold {
+        match self {
+            Self::BlockNone => HarmBlockThreshold::BlockNone,
+            Self::OnlyHigh => HarmBlockThreshold::BlockOnlyHigh,
+            Self::MediumAndAbove => HarmBlockThreshold::BlockMediumAndAbove,
+            Self::LowAndAbove => HarmBlockThreshold::BlockLowAndAbove,
+        }
+    }
+}
+
+/// Safety filter thresholds sent with every chat request.
+#[derive(Debug, Default, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct SafetySettings {
+    pub harassment: Threshold,
+    pub hate_speech: Threshold,
+    pub sexually_explicit: Threshold,
+    pub dangerous_content: Threshold,
+}
+
+impl SafetySettings {
+    pub fn get(&self, category: SafetyCategory) -> Threshold {
+        match category {
+            SafetyCategory::Harassment => self.harassment,
+            SafetyCategory::HateSpeech => self.hate_speech,
+            SafetyCategory::SexuallyExplicit => self.sexually_explicit,
+            SafetyCategory::DangerousContent => self.dangerous_content,
+        }
+    }
+
+    fn get_mut(&mut self, category: SafetyCategory) -> &mut Threshold {
+        match category {
+            SafetyCategory::Harassment => &mut self.harassment,
+            SafetyCategory::HateSpeech => &mut self.hate_speech,
+            SafetyCategory::SexuallyExplicit => &mut self.sexually_explicit,
+            SafetyCategory::DangerousContent => &mut self.dangerous_content,
+        }
+    }
+
+    /// Stops `category` from blocking answers.
+    pub fn relax(&mut self, category: SafetyCategory) {
+        *self.get_mut(category) = Threshold::BlockNone;
+    }
+
+    pub fn to_api(&self) -> Vec<SafetySetting> {
+        SafetyCategory::ALL
+            .into_iter()
+            .map(|category| SafetySetting {
+                category: category.harm_category(),
+                threshold: self.get(category).api(),
+            })
+            .collect()
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.weak(tr!("safety-help"));
+        egui::Grid::new("safety_grid")
+            .num_columns(2)
+            .show(ui, |ui| {
+                for category in SafetyCategory::ALL {
+                    ui.label(category.label());
+                    let threshold = self.get_mut(category);
+                    egui::ComboBox::from_id_salt(("safety_threshold", category.api_name()))
+                        .selected_text(threshold.label())
+                        .show_ui(ui, |ui| {
+                            for option in Threshold::ALL {
+                                ui.selectable_value(threshold, option, option.label());
+                            }
+                        });
+                    ui.end_row();
+                }
+            });
+    }
+}
+
+/// The API's verdict on one category.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Rating {
+    /// As the API names it, e.g. `HARM_CATEGORY_HARASSMENT`
+    pub category: String,
+    /// `NEGLIGIBLE`, `LOW`, `MEDIUM` or `HIGH`
+    pub probability: String,
+    /// Whether this category stopped the answer
+    pub blocked: bool,
+}
+
+impl Rating {
+    fn from_json(value: &Value) -> Option<Self> {
+        Some(Self {
+            category: value.get("category")?.as_str()?.to_owned(),
+            probability: value
+                .get("probability")
+                .and_then(Value::as_str)
+                .unwrap_or("HARM_PROBABILITY_UNSPECIFIED")
+                .to_owned(),
+            blocked: value
+                .get("blocked")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+        })
+    }
+
+    fn label(&self) -> String {
+        SafetyCategory::from_api_name(&self.category).map_or_else(
+            || {
+                let name = self.category.trim_start_matches("HARM_CATEGORY_");
+                name.replace('_', " ").to_lowercase()
+            },
+            SafetyCategory::label,
+        )
+    }
+
+    /// Whether the API considers the content likely harmful in this category.
+    fn is_likely(&self) -> bool {
+        matches!(self.probability.as_str(), "MEDIUM" | "HIGH")
+    }
+}
+
+/// Why an answer was blocked or cut short, kept with the message.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct SafetyReport {
+    /// The finish or block reason, e.g. `SAFETY` or `PROHIBITED_CONTENT`
+    pub reason: String,
+    /// The prompt was refused as a whole, rather than the answer stopped
+    pub prompt_blocked: bool,
+    pub ratings: Vec<Rating>,
+}
+
+impl SafetyReport {
+    /// The report for `response`, `None` when nothing was withheld. Read from the JSON
+    /// form so ratings of categories newer than the client library come through too.
+    pub fn from_response(response: &GenerationResponse) -> Option<Self> {
+        let json = serde_json::to_value(response).ok()?;
+        let ratings = |value: Option<&Value>| -> Vec<Rating> {
+            value
+                .and_then(Value::as_array)
+                .map(|ratings| ratings.iter().filter_map(Rating::from_json).collect())
+                .unwrap_or_default()
+        };
+
+        let feedback = json.get("promptFeedback");
+        if let Some(reason) = feedback
+            .and_then(|f| f.get("blockReason"))
+            .and_then(Value::as_str)
+        {
+            return Some(Self {
+                reason: reason.to_owned(),
+                prompt_blocked: true,
+                ratings: ratings(feedback.and_then(|f| f.get("safetyRatings"))),
+            });
+        }
+
+        let candidate = json.get("candidates")?.get(0)?;
+        let reason = candidate.get("finishReason")?.as_str()?;
+        BLOCK_REASONS.contains(&reason).then(|| Self {
+            reason: reason.to_owned(),
+            prompt_blocked: false,
+            ratings: ratings(candidate.get("safetyRatings")),
+        })
+    }
+
+    /// Categories that blocked the answer. Ratings don't always say, then the ones
+    /// rated likely harmful are the suspects.
+    fn triggered(&self) -> Vec<&Rating> {
+        let blocked: Vec<&Rating> = self.ratings.iter().filter(|r| r.blocked).collect();
+        if !blocked.is_empty() {
+            return blocked;
+        }
+        self.ratings.iter().filter(|r| r.is_likely()).collect()
+    }
+
+    fn explanation(&self) -> String {
+        let id = match self.reason.as_str() {
+            "SAFETY" | "IMAGE_SAFETY" => "safety-reason-safety",
+            "PROHIBITED_CONTENT" | "BLOCKLIST" | "SPII" => "safety-reason-prohibited",
+            "RECITATION" => "safety-reason-recitation",
+            _ => "safety-reason-other",
+        };
+        tr!(id)
+    }
+
+    /// Shows the ratings and offers to relax the categories that blocked the answer.
+    /// Returns the categories the user chose to relax, empty when none.
+    pub fn show(&self, ui: &mut egui::Ui, settings: &SafetySettings) -> Vec<SafetyCategory> {
+        let mut relax = Vec::new();
+        let warn = ui.visuals().warn_fg_color;
+        egui::Frame::group(ui.style())
+            .stroke(egui::Stroke::new(1.0, warn))
+            .show(ui, |ui| {
+                let title = if self.prompt_blocked {
+                    tr!("safety-prompt-blocked", reason = self.reason.clone())
+                } else {
+                    tr!("safety-response-blocked", reason = self.reason.clone())
+                };
+                ui.label(RichText::new(title).color(warn).strong());
+                ui.label(self.explanation());
+
+                let triggered = self.triggered();
+                if !triggered.is_empty() {
+                    let categories: Vec<String> = triggered
+                        .iter()
+                        .map(|r| format!("{} ({})", r.label(), r.category))
+                        .collect();
+                    ui.label(tr!("safety-blocked-by", categories = categories.join(", ")));
+                }
+                if !self.ratings.is_empty() {
+                    egui::Grid::new("safety_ratings")
+                        .num_columns(2)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for rating in &self.ratings {
+                                let text = RichText::new(rating.label());
+                                if triggered.contains(&rating) {
+                                    ui.label(text.strong().color(warn));
+                                } else {
+                                    ui.label(text);
+                                }
+                                ui.label(rating.probability.to_lowercase());
+                                ui.end_row();
+                            }
+                        });
+                }
+
+                if self.reason != "SAFETY" {
+                    return;
+                }
+                let adjustable: Vec<SafetyCategory> = triggered
+                    .iter()
+                    .filter_map(|r| SafetyCategory::from_api_name(&r.category))
+                    .filter(|&c| settings.get(c) != Threshold::BlockNone)
+                    .collect();
+                if adjustable.is_empty() {
+                    ui.weak(tr!("safety-not-adjustable"));
+                    return;
+                }
+                ui.add_space(4.0);
+                ui.colored_label(warn, tr!("safety-relax-warning"));
+                ui.horizontal_wrapped(|ui| {
+                    if adjustable.len() > 1
+                        && ui
+                            .button(tr!("safety-relax-all"))
+                            .on_hover_text(tr!("safety-relax-all-hint"))
+                            .clicked()
+                    {
+                        relax = adjustable.clone();
+                    }
+                    for &category in &adjustable {
+                        if ui
+                            .button(tr!("safety-relax", category = category.label()))
+                            .clicked()
+                        {
+                            relax = vec![category];
+                        }
+                    }
+                });
+            });
+        relax
+    }
+}
@@ -0,0 +1,377 @@
+use crate::{i18n::tr, prompt_library::SavedPrompt};
+use chrono::{DateTime, Local, Utc};
+use eframe::egui::{self, Color32, Layout, Stroke};
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+use tokio::sync::oneshot;
+
+/// Due schedules are looked for this often.
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A prompt sent into a chat on a cron schedule.
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Schedule {
+    pub id: u64,
+    pub name: String,
+    pub enabled: bool,
+    /// Minute, hour, day of month, month and day of week, in local time
+    pub cron: String,
+    /// Saved prompt whose latest version is sent, `text` is sent without one
+    pub prompt_id: Option<u64>,
+    pub text: String,
+    pub files: Vec<PathBuf>,
+    /// Chat the answers go to, created on the first run when missing
+    pub chat_id: Option<u64>,
+    /// Notify about the answer even while the window is focused
+    pub notify: bool,
+    pub created: DateTime<Utc>,
+    pub last_run: Option<DateTime<Utc>>,
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            name: tr!("schedule-new"),
+            enabled: true,
+            cron: "0 8 * * *".to_owned(),
+            prompt_id: None,
+            text: String::new(),
+            files: Vec::new(),
+            chat_id: None,
+            notify: true,
+            created: Utc::now(),
+            last_run: None,
+        }
+    }
+}
+
+impl Schedule {
+    /// The first run after the last one. Runs missed while the app was closed are
+    /// made up for once.
+    pub fn next_run(&self) -> Result<DateTime<Utc>, String> {
+        let cron = croner::Cron::new(self.cron.trim())
+            .parse()
+            .map_err(|e| e.to_string())?;
+        let since = self.last_run.unwrap_or(self.created).with_timezone(&Local);
+        cron.find_next_occurrence(&since, false)
+            .map(|time| time.with_timezone(&Utc))
+            .map_err(|e| e.to_string())
+    }
+
+    /// The text to send, the latest version of the saved prompt if one is picked.
+    pub fn prompt(&self, prompts: &[SavedPrompt]) -> String {
+        match self.prompt_id {
+            Some(id) => prompts
+                .iter()
+                .find(|p| p.id == id)
+                .map(|p| p.latest().to_owned())
+                .unwrap_or_default(),
+            None => self.text.clone(),
+        }
+    }
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
+
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Scheduler {
+    pub schedules: Vec<Schedule>,
+    #[serde(skip)]
+    selected: Option<u64>,
+    #[serde(skip)]
+    last_check: Option<Instant>,
+    /// Chat id and schedule id of the runs waiting for an answer
+    #[serde(skip)]
+    running: Vec<(u64, u64)>,
+    /// Schedule id and the files picked for it
+    #[serde(skip)]
+    picking: Option<(u64, oneshot::Receiver<Vec<PathBuf>>)>,
+}
+
+impl Scheduler {
+    #[inline]
+    pub fn has_selection(&self) -> bool {
+        self.selected.is_some()
+    }
+
+    #[inline]
+    pub fn get(&self, id: u64) -> Option<&Schedule> {
+        self.schedules.iter().find(|s| s.id == id)
+    }
+
+    /// Ids of the enabled schedules whose next run has come, checked every few seconds.
+    pub fn due(&mut self, now: DateTime<Utc>) -> Vec<u64> {
+        if self
+            .last_check
+            .is_some_and(|last| last.elapsed() < CHECK_INTERVAL)
+        {
+            return Vec::new();
+        }
+        self.last_check = Some(Instant::now());
+        self.schedules
+            .iter()
+            .filter(|s| s.enabled && s.next_run().is_ok_and(|next| next <= now))
+            .map(|s| s.id)
+            .collect()
+    }
+
+    /// The earliest upcoming run of the enabled schedules.
+    pub fn next_run(&self) -> Option<DateTime<Utc>> {
+        self.schedules
+            .iter()
+            .filter(|s| s.enabled)
+            .filter_map(|s| s.next_run().ok())
+            .min()
+    }
+
+    /// Records that `schedule_id` was sent into `chat_id`.
+    pub fn started(&mut self, schedule_id: u64, chat_id: u64) {
+        if let Some(schedule) = self.schedules.iter_mut().find(|s| s.id == schedule_id) {
+            schedule.last_run = Some(Utc::now());
+            schedule.chat_id = Some(chat_id);
+        }
+        self.running.push((chat_id, schedule_id));
+    }
+
+    /// Marks a schedule as run without sending anything, so a broken one doesn't retry every check.
+    pub fn skipped(&mut self, schedule_id: u64) {
+        if let Some(schedule) = self.schedules.iter_mut().find(|s| s.id == schedule_id) {
+            schedule.last_run = Some(Utc::now());
+        }
+    }
+
+    /// The schedule that sent the answer `chat_id` just finished, if any.
+    pub fn finished(&mut self, chat_id: u64) -> Option<&Schedule> {
+        let pos = self.running.iter().position(|&(chat, _)| chat == chat_id)?;
+        let (_, schedule_id) = self.running.remove(pos);
+        self.get(schedule_id)
+    }
+
+    pub fn show_sidebar(&mut self, ui: &mut egui::Ui) {
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add(
+                    egui::Button::new(format!("➕ {}", tr!("schedule-add")))
+                        .min_size(egui::vec2(0.0, 24.0)),
+                )
+                .clicked()
+            {
+                let id = self.schedules.iter().map(|s| s.id).max().unwrap_or(0) + 1;
+                self.schedules.push(Schedule {
+                    id,
+                    ..Default::default()
+                });
+                self.selected = Some(id);
+            }
+        });
+        ui.add_space(2.0);
+
+        if self.schedules.is_empty() {
+            ui.weak(tr!("schedule-none"));
+        }
+        let mut remove = None;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for schedule in &self.schedules {
+                ui.horizontal(|ui| {
+                    let label = if schedule.enabled {
+                        egui::RichText::new(&schedule.name)
+                    } else {
+                        egui::RichText::new(&schedule.name).weak()
+                    };
+                    let hover = match schedule.next_run() {
+                        Ok(next) if schedule.enabled => {
+                            tr!("schedule-next-run", time = format_time(next))
+                        }
+                        Ok(_) => tr!("schedule-disabled"),
+                        Err(e) => e,
+                    };
+                    if ui
+                        .selectable_label(self.selected == Some(schedule.id), label)
+                        .on_hover_text(hover)
+                        .clicked()
+                    {
+                        self.selected = Some(schedule.id);
+                    }
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui
+                            .add(
+                                egui::Button::new("❌")
+                                    .small()
+                                    .fill(Color32::TRANSPARENT)
+                                    .stroke(Stroke::NONE),
+                            )
+                            .on_hover_text(tr!("schedule-remove"))
+                            .clicked()
+                        {
+                            remove = Some(schedule.id);
+                        }
+                    });
+                });
+            }
+        });
+
+        if let Some(id) = remove {
+            self.schedules.retain(|s| s.id != id);
+            if self.selected == Some(id) {
+                self.selected = None;
+            }
+        }
+    }
+
+    /// Shows the editor for the selected schedule. Returns its id when "Run now" was clicked.
+    pub fn show_editor(
+        &mut self,
+        ui: &mut egui::Ui,
+        prompts: &[SavedPrompt],
+        chats: &[(u64, String)],
+    ) -> Option<u64> {
+        let Some(schedule) = self
+            .selected
+            .and_then(|id| self.schedules.iter_mut().find(|s| s.id == id))
+        else {
+            return None;
+        };
+        if let Some((id, rx)) = &mut self.picking {
+            if *id != schedule.id {
+                self.picking = None;
+            } else if let Ok(files) = rx.try_recv() {
+                schedule.files.extend(files);
+                self.picking = None;
+            }
+        }
+
+        ui.add(
+            egui::TextEdit::singleline(&mut schedule.name)
+                .hint_text(tr!("schedule-name"))
+                .desired_width(f32::INFINITY),
+        );
+        crate::widgets::toggle_setting(
+            ui,
+            &mut schedule.enabled,
+            &tr!("schedule-enabled"),
+            &tr!("schedule-enabled-help"),
+        );
+
+        egui::Grid::new("schedule_grid")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label(tr!("schedule-cron"))
+                    .on_hover_text(tr!("schedule-cron-help"));
+                ui.add(egui::TextEdit::singleline(&mut schedule.cron).hint_text("0 8 * * 1-5"));
+                ui.end_row();
+
+                ui.label("");
+                match schedule.next_run() {
+                    Ok(next) => ui.weak(tr!("schedule-next-run", time = format_time(next))),
+                    Err(e) => ui.colored_label(ui.visuals().error_fg_color, e),
+                };
+                ui.end_row();
+
+                ui.label(tr!("schedule-chat"));
+                let chat_title = schedule
+                    .chat_id
+                    .and_then(|id| chats.iter().find(|(chat, _)| *chat == id))
+                    .map_or_else(|| tr!("schedule-new-chat"), |(_, title)| title.clone());
+                egui::ComboBox::from_id_salt("schedule_chat")
+                    .selected_text(chat_title)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut schedule.chat_id, None, tr!("schedule-new-chat"));
+                        for (id, title) in chats {
+                            ui.selectable_value(&mut schedule.chat_id, Some(*id), title);
+                        }
+                    });
+                ui.end_row();
+
+                ui.label(tr!("schedule-prompt"));
+                let prompt_name = schedule
+                    .prompt_id
+                    .and_then(|id| prompts.iter().find(|p| p.id == id))
+                    .map_or_else(|| tr!("schedule-custom-text"), |p| p.name.clone());
+                egui::ComboBox::from_id_salt("schedule_prompt")
+                    .selected_text(prompt_name)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(
+                            &mut schedule.prompt_id,
+                            None,
+                            tr!("schedule-custom-text"),
+                        );
+                        for prompt in prompts {
+                            ui.selectable_value(
+                                &mut schedule.prompt_id,
+                                Some(prompt.id),
+                                &prompt.name,
+                            );
+                        }
+                    });
+                ui.end_row();
+            });
+
+        if schedule.prompt_id.is_none() {
+            ui.add(
+                egui::TextEdit::multiline(&mut schedule.text)
+                    .hint_text(tr!("schedule-text-hint"))
+                    .desired_rows(6)
+                    .desired_width(f32::INFINITY),
+            );
+        } else {
+            ui.weak(schedule.prompt(prompts));
+        }
+
+        ui.label(tr!("schedule-files"));
+        let mut remove = None;
+        for (i, file) in schedule.files.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if ui.small_button("❌").clicked() {
+                    remove = Some(i);
+                }
+                ui.label(file.display().to_string());
+            });
+        }
+        if let Some(i) = remove {
+            schedule.files.remove(i);
+        }
+        if ui
+            .add_enabled(
+                self.picking.is_none(),
+                egui::Button::new(tr!("schedule-add-files")),
+            )
+            .clicked()
+        {
+            let (tx, rx) = oneshot::channel();
+            self.picking = Some((schedule.id, rx));
+            let ctx = ui.ctx().clone();
+            tokio::spawn(async move {
+                let files = rfd::AsyncFileDialog::new()
+                    .pick_files()
+                    .await
+                    .unwrap_or_default();
+                let _ = tx.send(files.iter().map(|f| f.path().to_owned()).collect());
+                ctx.request_repaint();
+            });
+        }
+
+        ui.add_space(8.0);
+        crate::widgets::toggle_setting(
+            ui,
+            &mut schedule.notify,
+            &tr!("schedule-notify"),
+            &tr!("schedule-notify-help"),
+        );
+        if let Some(last) = schedule.last_run {
+            ui.weak(tr!("schedule-last-run", time = format_time(last)));
+        }
+        ui.add_space(8.0);
+        ui.button(tr!("schedule-run-now"))
+            .clicked()
+            .then_some(schedule.id)
+    }
+}
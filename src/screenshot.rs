@@ -0,0 +1,220 @@
+use crate::i18n::tr;
+use anyhow::{anyhow, Result};
+use eframe::egui::{self, Color32, Pos2, Rect, Stroke, ViewportCommand};
+use image::RgbaImage;
+use std::{path::PathBuf, time::Duration};
+use tokio::sync::oneshot;
+
+/// Time the window gets to disappear before the screen is captured.
+const HIDE_DELAY: Duration = Duration::from_millis(400);
+
+/// Selections smaller than this, in points, are taken for a click.
+const MIN_SIZE: f32 = 8.0;
+
+fn screenshots_dir() -> Option<PathBuf> {
+    eframe::storage_dir(crate::TITLE).map(|dir| dir.join("screenshots"))
+}
+
+/// The primary monitor, as it looks without the window on it.
+fn capture() -> Result<RgbaImage> {
+    std::thread::sleep(HIDE_DELAY);
+    let monitors = xcap::Monitor::all()?;
+    let primary = monitors
+        .iter()
+        .find(|m| m.is_primary().unwrap_or(false))
+        .or(monitors.first())
+        .ok_or_else(|| anyhow!("no monitor found"))?;
+    Ok(primary.capture_image()?)
+}
+
+/// Writes `image` where the chats can keep referring to it.
+fn save(image: &RgbaImage) -> Result<PathBuf> {
+    let dir = screenshots_dir().ok_or_else(|| anyhow!("no data folder"))?;
+    std::fs::create_dir_all(&dir)?;
+    let name = chrono::Local::now().format("screenshot-%Y%m%d-%H%M%S.png");
+    let path = dir.join(name.to_string());
+    image.save(&path)?;
+    log::info!("saved a screenshot to {}", path.display());
+    Ok(path)
+}
+
+/// A capture the user is picking a region of.
+struct Selection {
+    image: RgbaImage,
+    texture: egui::TextureHandle,
+    start: Option<Pos2>,
+    rect: Option<Rect>,
+}
+
+impl Selection {
+    /// The part of the capture under `rect`, given in points of the full-screen window.
+    fn crop(&self, rect: Rect, screen: Rect) -> RgbaImage {
+        let sx = self.image.width() as f32 / screen.width();
+        let sy = self.image.height() as f32 / screen.height();
+        let x = ((rect.min.x - screen.min.x) * sx).max(0.0) as u32;
+        let y = ((rect.min.y - screen.min.y) * sy).max(0.0) as u32;
+        let width = ((rect.width() * sx) as u32).min(self.image.width() - x);
+        let height = ((rect.height() * sy) as u32).min(self.image.height() - y);
+        image::imageops::crop_imm(&self.image, x, y, width, height).to_image()
+    }
+}
+
+/// Hides the window, captures the screen and lets the user select the region to attach.
+#[derive(Default)]
+pub struct ScreenCapture {
+    /// The chat the capture goes to
+    chat_id: usize,
+    /// A capture asked for, started on the next frame
+    requested: bool,
+    capturing: Option<oneshot::Receiver<Result<RgbaImage, String>>>,
+    selecting: Option<Selection>,
+}
+
+impl ScreenCapture {
+    pub fn is_busy(&self) -> bool {
+        self.requested || self.capturing.is_some() || self.selecting.is_some()
+    }
+
+    /// Asks for a screenshot attached to `chat_id`.
+    pub fn start(&mut self, chat_id: usize) {
+        if self.is_busy() {
+            return;
+        }
+        self.chat_id = chat_id;
+        self.requested = true;
+    }
+
+    /// Hides the window and captures the screen.
+    fn capture(&mut self, ctx: &egui::Context) {
+        self.requested = false;
+        let (tx, rx) = oneshot::channel();
+        self.capturing = Some(rx);
+        ctx.send_viewport_cmd(ViewportCommand::Visible(false));
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let result = capture().map_err(|e| {
+                log::error!("failed to capture the screen: {e}");
+                e.to_string()
+            });
+            let _ = tx.send(result);
+            // the selection covers the whole screen, like the capture does
+            ctx.send_viewport_cmd(ViewportCommand::Visible(true));
+            ctx.send_viewport_cmd(ViewportCommand::Fullscreen(true));
+            ctx.send_viewport_cmd(ViewportCommand::Focus);
+            ctx.request_repaint();
+        });
+    }
+
+    fn finish(&mut self, ctx: &egui::Context) {
+        self.selecting = None;
+        ctx.send_viewport_cmd(ViewportCommand::Fullscreen(false));
+    }
+
+    /// Shows the capture over everything while a region is selected. Returns the chat
+    /// and the saved screenshot once the user picked a region.
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<(usize, Result<PathBuf, String>)> {
+        if self.requested {
+            self.capture(ctx);
+        }
+        if let Some(rx) = &mut self.capturing {
+            if let Ok(result) = rx.try_recv() {
+                self.capturing = None;
+                match result {
+                    Ok(image) => {
+                        let size = [image.width() as usize, image.height() as usize];
+                        let texture = ctx.load_texture(
+                            "screenshot",
+                            egui::ColorImage::from_rgba_unmultiplied(size, image.as_raw()),
+                            egui::TextureOptions::LINEAR,
+                        );
+                        self.selecting = Some(Selection {
+                            image,
+                            texture,
+                            start: None,
+                            rect: None,
+                        });
+                    }
+                    Err(e) => {
+                        ctx.send_viewport_cmd(ViewportCommand::Fullscreen(false));
+                        return Some((self.chat_id, Err(e)));
+                    }
+                }
+            }
+        }
+        let selection = self.selecting.as_mut()?;
+
+        let screen = ctx.screen_rect();
+        let mut chosen = None;
+        let mut cancelled = ctx.input(|i| i.key_pressed(egui::Key::Escape));
+        if ctx.input(|i| i.key_pressed(egui::Key::Enter)) {
+            chosen = Some(screen);
+        }
+        egui::Area::new(egui::Id::new("screenshot_selection"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(screen.min)
+            .show(ctx, |ui| {
+                let response = ui.allocate_rect(screen, egui::Sense::drag());
+                let painter = ui.painter();
+                let full_uv = Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0));
+                painter.image(selection.texture.id(), screen, full_uv, Color32::WHITE);
+                painter.rect_filled(screen, 0.0, Color32::from_black_alpha(120));
+
+                if response.drag_started() {
+                    selection.start = response.interact_pointer_pos();
+                }
+                if let (Some(start), Some(pos)) = (selection.start, response.interact_pointer_pos())
+                {
+                    selection.rect = Some(Rect::from_two_pos(start, pos).intersect(screen));
+                }
+                if let Some(rect) = selection.rect {
+                    // the selected part is shown undimmed
+                    let uv = Rect::from_min_max(
+                        ((rect.min - screen.min) / screen.size()).to_pos2(),
+                        ((rect.max - screen.min) / screen.size()).to_pos2(),
+                    );
+                    painter.image(selection.texture.id(), rect, uv, Color32::WHITE);
+                    painter.rect_stroke(
+                        rect,
+                        0.0,
+                        Stroke::new(2.0, ui.visuals().selection.stroke.color),
+                        egui::StrokeKind::Outside,
+                    );
+                }
+                if response.drag_stopped() {
+                    match selection.rect.take() {
+                        Some(rect) if rect.width() >= MIN_SIZE && rect.height() >= MIN_SIZE => {
+                            chosen = Some(rect);
+                        }
+                        _ => selection.start = None,
+                    }
+                }
+                if response.secondary_clicked() {
+                    cancelled = true;
+                }
+
+                let help = egui::Area::new(egui::Id::new("screenshot_help"))
+                    .order(egui::Order::Tooltip)
+                    .anchor(egui::Align2::CENTER_TOP, [0.0, 16.0]);
+                help.show(ui.ctx(), |ui| {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.label(tr!("screenshot-help"));
+                    });
+                });
+            });
+        ctx.request_repaint();
+
+        if let Some(rect) = chosen {
+            let image = selection.crop(rect, screen);
+            self.finish(ctx);
+            let saved = save(&image).map_err(|e| {
+                log::error!("failed to save the screenshot: {e}");
+                e.to_string()
+            });
+            return Some((self.chat_id, saved));
+        }
+        if cancelled {
+            self.finish(ctx);
+        }
+        None
+    }
+}
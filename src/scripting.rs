@@ -0,0 +1,333 @@
+use crate::{chat::Chat, i18n::tr, widgets::GeminiModel};
+use eframe::egui;
+use std::path::PathBuf;
+#[cfg(feature = "scripting")]
+use std::{
+    sync::{LazyLock, Mutex},
+    time::{Duration, Instant, SystemTime},
+};
+
+/// Keeps a runaway loop in a script from freezing the UI
+#[cfg(feature = "scripting")]
+const MAX_OPERATIONS: u64 = 1_000_000;
+#[cfg(feature = "scripting")]
+const RESCAN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Points where scripts are called, each is a function with the hook's name
+/// taking the text and a map describing the chat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hook {
+    /// Gets the prompt before it is sent
+    BeforeSend,
+    /// Gets the answer once the model is done
+    Response,
+    /// Gets an empty string, a returned text fills the chatbox
+    ChatCreated,
+}
+
+impl Hook {
+    pub fn function(self) -> &'static str {
+        match self {
+            Self::BeforeSend => "on_before_send",
+            Self::Response => "on_response",
+            Self::ChatCreated => "on_chat_created",
+        }
+    }
+}
+
+/// What the hooks returned, applied by the caller.
+#[derive(Debug, Default)]
+pub struct ScriptEffects {
+    /// Replaces the text the hook was called with
+    pub text: Option<String>,
+    pub title: Option<String>,
+    pub model: Option<GeminiModel>,
+    /// Tag names, created when missing
+    pub tags: Vec<String>,
+    /// Stops the message from being sent, only for `on_before_send`
+    pub cancel: bool,
+}
+
+fn scripts_dir() -> Option<PathBuf> {
+    eframe::storage_dir(crate::TITLE).map(|dir| dir.join("scripts"))
+}
+
+#[cfg(feature = "scripting")]
+struct Script {
+    path: PathBuf,
+    modified: SystemTime,
+    ast: Option<rhai::AST>,
+    /// Compile error, or the error of the last call
+    error: Option<String>,
+}
+
+#[cfg(feature = "scripting")]
+impl Script {
+    fn name(&self) -> String {
+        self.path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    fn defines(&self, hook: Hook) -> bool {
+        self.ast.as_ref().is_some_and(|ast| {
+            ast.iter_functions()
+                .any(|f| f.name == hook.function() && f.params.len() == 2)
+        })
+    }
+}
+
+#[cfg(feature = "scripting")]
+struct Scripts {
+    engine: rhai::Engine,
+    /// In file name order, which is the order the hooks run in
+    loaded: Vec<Script>,
+    last_scan: Option<Instant>,
+}
+
+#[cfg(feature = "scripting")]
+static SCRIPTS: LazyLock<Mutex<Scripts>> = LazyLock::new(|| {
+    let mut engine = rhai::Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.on_print(|text| log::info!("script: {text}"));
+    Mutex::new(Scripts {
+        engine,
+        loaded: Vec::new(),
+        last_scan: None,
+    })
+});
+
+#[cfg(feature = "scripting")]
+impl Scripts {
+    /// Recompiles the scripts that changed on disk, at most once a second.
+    fn refresh(&mut self) {
+        if self
+            .last_scan
+            .is_some_and(|last| last.elapsed() < RESCAN_INTERVAL)
+        {
+            return;
+        }
+        self.last_scan = Some(Instant::now());
+
+        let mut files: Vec<(PathBuf, SystemTime)> = scripts_dir()
+            .and_then(|dir| std::fs::read_dir(dir).ok())
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "rhai"))
+            .filter_map(|p| {
+                let modified = std::fs::metadata(&p).and_then(|m| m.modified()).ok()?;
+                Some((p, modified))
+            })
+            .collect();
+        files.sort();
+        let unchanged = files.len() == self.loaded.len()
+            && files
+                .iter()
+                .zip(&self.loaded)
+                .all(|((path, modified), s)| *path == s.path && *modified == s.modified);
+        if unchanged {
+            return;
+        }
+
+        let mut previous = std::mem::take(&mut self.loaded);
+        for (path, modified) in files {
+            if let Some(pos) = previous
+                .iter()
+                .position(|s| s.path == path && s.modified == modified)
+            {
+                self.loaded.push(previous.swap_remove(pos));
+                continue;
+            }
+            let mut script = Script {
+                path,
+                modified,
+                ast: None,
+                error: None,
+            };
+            match self.engine.compile_file(script.path.clone()) {
+                Ok(ast) => {
+                    log::info!("loaded the {} script", script.name());
+                    script.ast = Some(ast);
+                }
+                Err(e) => {
+                    log::error!("failed to compile the {} script: {e}", script.name());
+                    script.error = Some(e.to_string());
+                }
+            }
+            self.loaded.push(script);
+        }
+    }
+
+    fn run(&mut self, hook: Hook, chat: &Chat, input: &str, effects: &mut ScriptEffects) {
+        self.refresh();
+        let mut text = input.to_owned();
+        for script in self.loaded.iter_mut().filter(|s| s.defines(hook)) {
+            let Some(ast) = &script.ast else {
+                continue;
+            };
+            let result = self.engine.call_fn::<rhai::Dynamic>(
+                &mut rhai::Scope::new(),
+                ast,
+                hook.function(),
+                (text.clone(), chat_map(chat)),
+            );
+            match result {
+                Ok(value) => {
+                    script.error = None;
+                    apply(value, &mut text, effects);
+                }
+                Err(e) => {
+                    log::error!(
+                        "{} in the {} script failed: {e}",
+                        hook.function(),
+                        script.name()
+                    );
+                    script.error = Some(e.to_string());
+                }
+            }
+            if effects.cancel {
+                break;
+            }
+        }
+        if text != input {
+            effects.text = Some(text);
+        }
+    }
+}
+
+/// The second argument of every hook.
+#[cfg(feature = "scripting")]
+fn chat_map(chat: &Chat) -> rhai::Map {
+    let mut map = rhai::Map::new();
+    map.insert("id".into(), (chat.id as rhai::INT).into());
+    map.insert("title".into(), chat.summary.clone().into());
+    map.insert(
+        "model".into(),
+        chat.model_picker.selected.to_string().into(),
+    );
+    map.insert(
+        "message_count".into(),
+        (chat.messages.len() as rhai::INT).into(),
+    );
+    map
+}
+
+/// A hook returns nothing, the new text, or a map with any of
+/// `text`, `title`, `model`, `tags` and `cancel`.
+#[cfg(feature = "scripting")]
+fn apply(value: rhai::Dynamic, text: &mut String, effects: &mut ScriptEffects) {
+    if value.is_unit() {
+        return;
+    }
+    if value.is_string() {
+        *text = value.into_string().unwrap_or_default();
+        return;
+    }
+    let Some(map) = value.try_cast::<rhai::Map>() else {
+        log::warn!("a script hook returned something other than a string or a map");
+        return;
+    };
+    for (key, value) in map {
+        match key.as_str() {
+            "text" => {
+                if let Ok(new_text) = value.into_string() {
+                    *text = new_text;
+                }
+            }
+            "title" => effects.title = value.into_string().ok(),
+            "model" => {
+                let name = value.into_string().unwrap_or_default();
+                let model = crate::models::available()
+                    .into_iter()
+                    .find(|m| m.to_string() == name);
+                match model {
+                    Some(model) => effects.model = Some(model),
+                    None => log::warn!("a script asked for the unknown model `{name}`"),
+                }
+            }
+            "tags" => {
+                if let Some(tags) = value.try_cast::<rhai::Array>() {
+                    effects
+                        .tags
+                        .extend(tags.into_iter().filter_map(|t| t.into_string().ok()));
+                }
+            }
+            "cancel" => effects.cancel = value.as_bool().unwrap_or(false),
+            other => log::warn!("a script hook returned the unknown key `{other}`"),
+        }
+    }
+}
+
+/// Rhai scripts from the `scripts` folder next to the app data, reloaded when they change.
+#[derive(Clone, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct ScriptSettings {
+    pub enabled: bool,
+}
+
+impl ScriptSettings {
+    /// Calls `hook` in every script that defines it, each one getting the text
+    /// returned by the one before.
+    pub fn run(&self, hook: Hook, chat: &Chat, input: &str) -> ScriptEffects {
+        let mut effects = ScriptEffects::default();
+        if !self.enabled {
+            return effects;
+        }
+        #[cfg(feature = "scripting")]
+        if let Ok(mut scripts) = SCRIPTS.lock() {
+            scripts.run(hook, chat, input, &mut effects);
+        }
+        #[cfg(not(feature = "scripting"))]
+        let _ = (hook, chat, input);
+        effects
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        #[cfg(not(feature = "scripting"))]
+        ui.colored_label(ui.visuals().warn_fg_color, tr!("scripting-unsupported"));
+        crate::widgets::toggle_setting(
+            ui,
+            &mut self.enabled,
+            &tr!("scripting-enabled"),
+            &tr!("scripting-enabled-help"),
+        );
+        if let Some(dir) = scripts_dir() {
+            ui.horizontal(|ui| {
+                ui.label(tr!("scripting-folder", path = dir.display().to_string()));
+                if ui.button(tr!("scripting-open-folder")).clicked() {
+                    if let Err(e) = std::fs::create_dir_all(&dir).and_then(|_| open::that(&dir)) {
+                        log::error!("failed to open {}: {e}", dir.display());
+                    }
+                }
+            });
+        }
+
+        #[cfg(feature = "scripting")]
+        if self.enabled {
+            let Ok(mut scripts) = SCRIPTS.lock() else {
+                return;
+            };
+            scripts.refresh();
+            if scripts.loaded.is_empty() {
+                ui.weak(tr!("scripting-none"));
+            }
+            for script in &scripts.loaded {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(format!("📜 {}", script.name()));
+                    for hook in [Hook::BeforeSend, Hook::Response, Hook::ChatCreated] {
+                        if script.defines(hook) {
+                            ui.weak(hook.function());
+                        }
+                    }
+                });
+                if let Some(error) = &script.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+            }
+        }
+    }
+}
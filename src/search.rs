@@ -0,0 +1,649 @@
+use crate::{
+    attachment_search::{FileHit, FileIndex},
+    chat::{Chat, Message},
+    i18n::tr,
+    semantic::Embedder,
+    tags::Tag,
+    widgets::GeminiModel,
+};
+use eframe::egui::{self, text::LayoutJob, Color32, TextFormat};
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+const SNIPPET_CONTEXT: usize = 60;
+const MAX_RESULTS: usize = 200;
+
+pub fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// Inverted index over every message of every chat, rebuilt when the chat store changes.
+#[derive(Default)]
+pub struct SearchIndex {
+    postings: HashMap<String, Vec<(usize, usize)>>,
+    stamp: u64,
+}
+
+impl SearchIndex {
+    fn stamp(chats: &[Chat]) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        for chat in chats {
+            chat.id.hash(&mut hasher);
+            chat.messages.len().hash(&mut hasher);
+            // the contents, an edit can keep the length
+            for msg in &chat.messages {
+                msg.is_marker.hash(&mut hasher);
+                msg.content.hash(&mut hasher);
+            }
+        }
+        hasher.finish()
+    }
+
+    pub fn refresh(&mut self, chats: &[Chat]) {
+        let stamp = Self::stamp(chats);
+        if stamp == self.stamp && !self.postings.is_empty() {
+            return;
+        }
+
+        log::debug!("rebuilding search index over {} chats", chats.len());
+        self.postings.clear();
+        for (chat_idx, chat) in chats.iter().enumerate() {
+            for (msg_idx, msg) in chat.messages.iter().enumerate() {
+                if msg.is_marker {
+                    continue;
+                }
+                let mut seen = HashSet::new();
+                for token in tokenize(&msg.content) {
+                    if seen.insert(token.clone()) {
+                        self.postings
+                            .entry(token)
+                            .or_default()
+                            .push((chat_idx, msg_idx));
+                    }
+                }
+            }
+        }
+        self.stamp = stamp;
+    }
+
+    /// Returns messages containing every query term (terms match as prefixes).
+    pub fn query(&self, terms: &[String]) -> Vec<(usize, usize)> {
+        let mut result: Option<HashSet<(usize, usize)>> = None;
+        for term in terms {
+            let matches: HashSet<(usize, usize)> = self
+                .postings
+                .iter()
+                .filter(|(token, _)| token.starts_with(term.as_str()))
+                .flat_map(|(_, postings)| postings.iter().copied())
+                .collect();
+            result = Some(match result {
+                Some(prev) => prev.intersection(&matches).copied().collect(),
+                None => matches,
+            });
+        }
+
+        let mut hits: Vec<_> = result.unwrap_or_default().into_iter().collect();
+        hits.sort_unstable();
+        hits
+    }
+}
+
+/// Builds a short excerpt around the first match with every term highlighted.
+pub fn snippet(ui: &egui::Ui, content: &str, terms: &[String]) -> LayoutJob {
+    let lower = content.to_lowercase();
+    // lowercasing may change byte offsets for some scripts, fall back to the plain text then
+    let comparable = lower.len() == content.len();
+
+    let first = terms
+        .iter()
+        .filter_map(|t| comparable.then(|| lower.find(t.as_str())).flatten())
+        .min()
+        .unwrap_or(0);
+
+    let floor = |mut i: usize| {
+        while !content.is_char_boundary(i) {
+            i -= 1;
+        }
+        i
+    };
+    let start = floor(first.saturating_sub(SNIPPET_CONTEXT));
+    let end = floor((first + SNIPPET_CONTEXT * 2).min(content.len()));
+    let excerpt = &content[start..end];
+
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    if comparable {
+        let lower_excerpt = &lower[start..end];
+        for term in terms.iter().filter(|t| !t.is_empty()) {
+            let mut from = 0;
+            while let Some(pos) = lower_excerpt[from..].find(term.as_str()) {
+                let s = from + pos;
+                ranges.push((s, s + term.len()));
+                from = s + term.len();
+            }
+        }
+    }
+    ranges.sort_unstable();
+
+    let style = ui.style();
+    let font_id = egui::TextStyle::Body.resolve(style);
+    let normal = TextFormat::simple(font_id.clone(), style.visuals.text_color());
+    let highlight = TextFormat {
+        background: style.visuals.selection.bg_fill,
+        color: style.visuals.strong_text_color(),
+        ..TextFormat::simple(font_id, Color32::WHITE)
+    };
+
+    let mut job = LayoutJob::default();
+    if start > 0 {
+        job.append("…", 0.0, normal.clone());
+    }
+    let mut cursor = 0;
+    for (s, e) in ranges {
+        if s < cursor || !excerpt.is_char_boundary(s) || !excerpt.is_char_boundary(e) {
+            continue;
+        }
+        job.append(&excerpt[cursor..s].replace('\n', " "), 0.0, normal.clone());
+        job.append(&excerpt[s..e], 0.0, highlight.clone());
+        cursor = e;
+    }
+    job.append(&excerpt[cursor..].replace('\n', " "), 0.0, normal.clone());
+    if end < content.len() {
+        job.append("…", 0.0, normal);
+    }
+    job.wrap.max_rows = 2;
+    job
+}
+
+/// Parses `2024`, `2024-06` or `2024-06-15`, missing parts default to the first day.
+fn parse_partial_date(s: &str) -> Option<chrono::NaiveDate> {
+    let mut parts = s.splitn(3, '-').map(str::parse::<u32>);
+    let year = parts.next()?.ok()?;
+    let month = parts.next().map_or(Some(1), Result::ok)?;
+    let day = parts.next().map_or(Some(1), Result::ok)?;
+    chrono::NaiveDate::from_ymd_opt(year as i32, month, day)
+}
+
+/// Search query split into free text and `key:value` filters,
+/// e.g. `lifetimes model:gemini-2.5-pro has:attachment after:2024-06 tag:work`.
+#[derive(Default)]
+pub struct ParsedQuery {
+    /// Free text with the filters removed
+    pub text: String,
+    pub terms: Vec<String>,
+    models: Vec<String>,
+    has_attachment: bool,
+    after: Option<chrono::NaiveDate>,
+    before: Option<chrono::NaiveDate>,
+    tags: Vec<String>,
+}
+
+impl ParsedQuery {
+    pub fn parse(query: &str) -> Self {
+        let mut parsed = Self::default();
+        let mut text = Vec::new();
+        for word in query.split_whitespace() {
+            match word.split_once(':') {
+                Some(("model", v)) if !v.is_empty() => parsed.models.push(v.to_lowercase()),
+                Some(("has", "attachment" | "attachments" | "file" | "files")) => {
+                    parsed.has_attachment = true;
+                }
+                Some(("after", v)) if parse_partial_date(v).is_some() => {
+                    parsed.after = parse_partial_date(v);
+                }
+                Some(("before", v)) if parse_partial_date(v).is_some() => {
+                    parsed.before = parse_partial_date(v);
+                }
+                Some(("tag", v)) if !v.is_empty() => parsed.tags.push(v.to_lowercase()),
+                _ => {
+                    text.push(word);
+                    parsed.terms.extend(tokenize(word));
+                }
+            }
+        }
+        parsed.text = text.join(" ");
+        parsed
+    }
+
+    #[inline]
+    fn has_message_filters(&self) -> bool {
+        !self.models.is_empty()
+            || self.has_attachment
+            || self.after.is_some()
+            || self.before.is_some()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty() && self.tags.is_empty() && !self.has_message_filters()
+    }
+
+    fn message_matches(&self, msg: &Message) -> bool {
+        let date = msg.time.date_naive();
+        let model = msg.model.to_string().to_lowercase();
+        self.models.iter().all(|m| model.contains(m.as_str()))
+            && (!self.has_attachment || !msg.files.is_empty())
+            && self.after.map_or(true, |d| date >= d)
+            && self.before.map_or(true, |d| date < d)
+    }
+
+    fn tags_match(&self, chat: &Chat, tags: &[Tag]) -> bool {
+        self.tags.iter().all(|name| {
+            tags.iter()
+                .any(|t| t.name.to_lowercase() == *name && chat.tags.contains(&t.id))
+        })
+    }
+
+    /// Whole-chat match used by saved searches: every filter must hold for some
+    /// message and every term must appear somewhere in the chat.
+    pub fn matches_chat(&self, chat: &Chat, tags: &[Tag]) -> bool {
+        if self.is_empty() || !self.tags_match(chat, tags) {
+            return false;
+        }
+        if self.has_message_filters() && !chat.messages.iter().any(|m| self.message_matches(m)) {
+            return false;
+        }
+        if self.terms.is_empty() {
+            return true;
+        }
+        let mut haystack = chat.summary.to_lowercase();
+        for msg in chat.messages.iter().filter(|m| !m.is_marker) {
+            haystack.push('\n');
+            haystack.push_str(&msg.content.to_lowercase());
+        }
+        self.terms.iter().all(|t| haystack.contains(t.as_str()))
+    }
+}
+
+/// A query pinned to the sidebar as a virtual folder.
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct SavedSearch {
+    pub id: u64,
+    pub name: String,
+    pub query: String,
+    pub collapsed: bool,
+}
+
+impl Default for SavedSearch {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            name: tr!("search-saved-name"),
+            query: String::new(),
+            collapsed: false,
+        }
+    }
+}
+
+pub enum SearchAction {
+    None,
+    Jump { chat_idx: usize, msg_idx: usize },
+    /// Embed the query and rank messages against the embedding index
+    Semantic(String),
+    /// Pin the query to the sidebar
+    Save(String),
+}
+
+/// Global search window (Ctrl+Shift+F) over all chats.
+#[derive(Default)]
+pub struct GlobalSearch {
+    pub open: bool,
+    query: String,
+    after: String,
+    before: String,
+    model: Option<GeminiModel>,
+    chat: Option<u64>,
+    index: SearchIndex,
+    results: Vec<(usize, usize)>,
+    dirty: bool,
+    request_focus: bool,
+    /// Rank by embedding similarity instead of keywords
+    pub semantic: bool,
+    /// What the semantic index is built with, the owner keeps the index in step
+    pub embedder: Embedder,
+    semantic_results: Vec<(usize, usize, f32)>,
+    semantic_pending: bool,
+    /// Messages embedded so far and total, filled in by the owner
+    pub index_progress: (usize, usize),
+    /// Search attachment names and contents instead of messages
+    files_mode: bool,
+    file_index: FileIndex,
+    file_hits: Vec<FileHit>,
+}
+
+fn parse_date(s: &str) -> Option<chrono::NaiveDate> {
+    chrono::NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").ok()
+}
+
+impl GlobalSearch {
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.request_focus = self.open;
+        self.dirty = true;
+    }
+
+    pub fn set_semantic_results(&mut self, results: Vec<(usize, usize, f32)>) {
+        self.semantic_results = results;
+        self.semantic_pending = false;
+    }
+
+    pub fn semantic_failed(&mut self) {
+        self.semantic = false;
+        self.semantic_pending = false;
+    }
+
+    fn matches_filters(&self, chats: &[Chat], chat_idx: usize, msg_idx: usize) -> bool {
+        let Some(chat) = chats.get(chat_idx) else {
+            return false;
+        };
+        let Some(msg) = chat.messages.get(msg_idx) else {
+            return false;
+        };
+        let date = msg.time.date_naive();
+        self.chat.map_or(true, |id| chat.id == id)
+            && self.model.map_or(true, |m| msg.model == m)
+            && parse_date(&self.after).map_or(true, |d| date >= d)
+            && parse_date(&self.before).map_or(true, |d| date <= d)
+    }
+
+    /// Whether a hit passes both the filter widgets and the filters typed into the query.
+    fn matches_query(
+        &self,
+        parsed: &ParsedQuery,
+        chats: &[Chat],
+        tags: &[Tag],
+        chat_idx: usize,
+        msg_idx: usize,
+    ) -> bool {
+        self.matches_filters(chats, chat_idx, msg_idx)
+            && chats.get(chat_idx).is_some_and(|chat| {
+                parsed.tags_match(chat, tags)
+                    && chat
+                        .messages
+                        .get(msg_idx)
+                        .is_some_and(|m| parsed.message_matches(m))
+            })
+    }
+
+    fn run(&mut self, chats: &[Chat], tags: &[Tag]) {
+        self.index.refresh(chats);
+        let parsed = ParsedQuery::parse(&self.query);
+        if parsed.is_empty() {
+            self.results.clear();
+            return;
+        }
+
+        // with filters only, every message is a candidate
+        let candidates = if parsed.terms.is_empty() {
+            chats
+                .iter()
+                .enumerate()
+                .flat_map(|(c, chat)| {
+                    chat.messages
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, m)| !m.is_marker)
+                        .map(move |(m, _)| (c, m))
+                })
+                .collect()
+        } else {
+            self.index.query(&parsed.terms)
+        };
+        self.results = candidates
+            .into_iter()
+            .filter(|&(c, m)| self.matches_query(&parsed, chats, tags, c, m))
+            .take(MAX_RESULTS)
+            .collect();
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, chats: &[Chat], tags: &[Tag]) -> SearchAction {
+        let mut action = SearchAction::None;
+        if !self.open {
+            return action;
+        }
+
+        let mut open = self.open;
+        egui::Window::new(tr!("search-title"))
+            .open(&mut open)
+            .default_width(480.0)
+            .default_height(420.0)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                let resp = ui.add(
+                    egui::TextEdit::singleline(&mut self.query)
+                        .hint_text(if self.semantic {
+                            tr!("search-hint-semantic")
+                        } else if self.files_mode {
+                            tr!("search-hint-files")
+                        } else {
+                            tr!("search-hint")
+                        })
+                        .desired_width(f32::INFINITY),
+                )
+                .on_hover_text(tr!("search-syntax-help"));
+                if std::mem::take(&mut self.request_focus) {
+                    resp.request_focus();
+                }
+                self.dirty |= resp.changed();
+                let mut run_semantic =
+                    resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+                ui.horizontal(|ui| {
+                    run_semantic |= ui
+                        .toggle_value(&mut self.semantic, tr!("search-semantic"))
+                        .on_hover_text(tr!("search-semantic-help"))
+                        .changed();
+                    if self.semantic {
+                        egui::ComboBox::from_id_salt("search_embedder")
+                            .selected_text(self.embedder.label())
+                            .show_ui(ui, |ui| {
+                                for embedder in Embedder::ALL {
+                                    if ui
+                                        .selectable_value(
+                                            &mut self.embedder,
+                                            embedder,
+                                            embedder.label(),
+                                        )
+                                        .changed()
+                                    {
+                                        // the old scores came from the other index
+                                        self.semantic_results.clear();
+                                    }
+                                }
+                            });
+                    }
+                    if ui
+                        .toggle_value(&mut self.files_mode, tr!("search-files"))
+                        .on_hover_text(tr!("search-files-help"))
+                        .changed()
+                    {
+                        self.dirty = true;
+                    }
+                    if ui
+                        .add_enabled(
+                            !self.query.trim().is_empty(),
+                            egui::Button::new(tr!("search-save")),
+                        )
+                        .on_hover_text(tr!("search-save-help"))
+                        .clicked()
+                    {
+                        action = SearchAction::Save(self.query.trim().to_owned());
+                    }
+                    if self.semantic {
+                        let (indexed, total) = self.index_progress;
+                        if indexed < total {
+                            ui.spinner();
+                            ui.weak(tr!("search-indexing", indexed = indexed, total = total));
+                        }
+                    }
+                });
+                let parsed = ParsedQuery::parse(&self.query);
+                if self.semantic && run_semantic && !parsed.text.trim().is_empty() {
+                    self.semantic_pending = true;
+                    action = SearchAction::Semantic(parsed.text.clone());
+                }
+
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(tr!("search-after"));
+                    self.dirty |= ui
+                        .add(
+                            egui::TextEdit::singleline(&mut self.after)
+                                .hint_text("YYYY-MM-DD")
+                                .desired_width(80.0),
+                        )
+                        .changed();
+                    ui.label(tr!("search-before"));
+                    self.dirty |= ui
+                        .add(
+                            egui::TextEdit::singleline(&mut self.before)
+                                .hint_text("YYYY-MM-DD")
+                                .desired_width(80.0),
+                        )
+                        .changed();
+
+                    egui::ComboBox::from_id_salt("search_model_filter")
+                        .selected_text(self.model.map_or(tr!("search-any-model"), |m| m.to_string()))
+                        .show_ui(ui, |ui| {
+                            self.dirty |= ui
+                                .selectable_value(&mut self.model, None, tr!("search-any-model"))
+                                .changed();
+                            for model in crate::models::available() {
+                                self.dirty |= ui
+                                    .selectable_value(&mut self.model, Some(model), model.to_string())
+                                    .changed();
+                            }
+                        });
+
+                    let chat_name = |chat: &Chat| chat.title();
+                    egui::ComboBox::from_id_salt("search_chat_filter")
+                        .selected_text(
+                            self.chat
+                                .and_then(|id| chats.iter().find(|c| c.id == id))
+                                .map_or(tr!("search-any-chat"), chat_name),
+                        )
+                        .show_ui(ui, |ui| {
+                            self.dirty |= ui
+                                .selectable_value(&mut self.chat, None, tr!("search-any-chat"))
+                                .changed();
+                            for chat in chats {
+                                self.dirty |= ui
+                                    .selectable_value(&mut self.chat, Some(chat.id), chat_name(chat))
+                                    .changed();
+                            }
+                        });
+                });
+
+                // the two modes are exclusive, whichever was just turned on wins
+                if self.semantic && self.files_mode {
+                    if run_semantic {
+                        self.files_mode = false;
+                    } else {
+                        self.semantic = false;
+                    }
+                }
+
+                if self.files_mode {
+                    let rebuilt = self.file_index.refresh(ui.ctx(), chats);
+                    if std::mem::take(&mut self.dirty) || rebuilt {
+                        self.file_hits = self
+                            .file_index
+                            .query(&parsed.terms)
+                            .into_iter()
+                            .filter(|h| self.matches_query(&parsed, chats, tags, h.chat_idx, h.msg_idx))
+                            .collect();
+                    }
+                    ui.separator();
+                    if let Some((chat_idx, msg_idx)) = crate::attachment_search::show_results(
+                        ui,
+                        chats,
+                        &self.file_index,
+                        &self.file_hits,
+                        &parsed.terms,
+                    ) {
+                        action = SearchAction::Jump { chat_idx, msg_idx };
+                    }
+                    return;
+                }
+
+                if std::mem::take(&mut self.dirty) {
+                    self.run(chats, tags);
+                }
+
+                ui.separator();
+                if self.semantic && self.semantic_pending {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.weak(tr!("search-searching"));
+                    });
+                    return;
+                }
+                let hits: Vec<(usize, usize, Option<f32>)> = if self.semantic {
+                    self.semantic_results
+                        .iter()
+                        .filter(|&&(c, m, _)| self.matches_query(&parsed, chats, tags, c, m))
+                        .map(|&(c, m, score)| (c, m, Some(score)))
+                        .collect()
+                } else {
+                    self.results.iter().map(|&(c, m)| (c, m, None)).collect()
+                };
+                if hits.is_empty() {
+                    if !self.query.trim().is_empty() {
+                        ui.weak(tr!("no-matches"));
+                    }
+                    return;
+                }
+                ui.weak(tr!("search-match-count", count = hits.len()));
+
+                let terms = parsed.terms;
+                egui::ScrollArea::vertical().auto_shrink(false).show(ui, |ui| {
+                    for &(chat_idx, msg_idx, score) in &hits {
+                        let (Some(chat), Some(msg)) = (
+                            chats.get(chat_idx),
+                            chats.get(chat_idx).and_then(|c| c.messages.get(msg_idx)),
+                        ) else {
+                            continue;
+                        };
+                        let resp = egui::Frame::group(ui.style())
+                            .show(ui, |ui| {
+                                ui.set_width(ui.available_width());
+                                ui.horizontal(|ui| {
+                                    ui.strong(chat.title());
+                                    ui.weak(format!(
+                                        "{} · {}",
+                                        if msg.is_user() { tr!("you") } else { msg.model.to_string() },
+                                        msg.time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+                                    ));
+                                    if let Some(score) = score {
+                                        ui.weak(format!("{:.0}%", score * 100.0))
+                                            .on_hover_text(tr!("search-similarity"));
+                                    }
+                                });
+                                ui.add(
+                                    egui::Label::new(snippet(ui, &msg.content, &terms))
+                                        .selectable(false),
+                                );
+                            })
+                            .response;
+                        let resp = ui.interact(
+                            resp.rect,
+                            ui.id().with(("search_hit", chat_idx, msg_idx)),
+                            egui::Sense::click(),
+                        );
+                        if resp.hovered() {
+                            ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                        }
+                        if resp.clicked() {
+                            action = SearchAction::Jump { chat_idx, msg_idx };
+                        }
+                    }
+                });
+            });
+        self.open = open;
+
+        action
+    }
+}
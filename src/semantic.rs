@@ -0,0 +1,266 @@
+use crate::{
+    chat::Chat,
+    i18n::tr,
+    widgets::{AuthMethod, Settings},
+};
+use anyhow::{anyhow, Result};
+use gemini_rust::{Model, TaskType};
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    path::PathBuf,
+};
+
+const EMBEDDING_MODEL: &str = "models/gemini-embedding-001";
+
+/// Messages embedded per request while indexing.
+pub const BATCH_SIZE: usize = 32;
+
+/// Longer messages are cut, the embedding model only looks at the first couple thousand tokens anyway.
+const MAX_EMBED_CHARS: usize = 6000;
+
+const MAX_RESULTS: usize = 30;
+
+/// What turns text into vectors. Vectors of different embedders can't be compared,
+/// so an index is always queried with the embedder that built it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum Embedder {
+    /// The Gemini embedding model, needs an API key and counts against its quota
+    #[default]
+    Gemini,
+    /// A small multilingual model run on this machine, downloaded once on first use
+    Local,
+}
+
+impl Embedder {
+    pub const ALL: [Self; 2] = [Self::Gemini, Self::Local];
+
+    pub fn label(self) -> String {
+        tr!(match self {
+            Self::Gemini => "embedder-gemini",
+            Self::Local => "embedder-local",
+        })
+    }
+}
+
+/// Embeddings of message contents, keyed by a hash of the content so edits and
+/// duplicates are handled for free. Stored next to the autosave, outside of the app state.
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct EmbeddingStore {
+    /// Built the vectors, switching it starts the index over
+    pub embedder: Embedder,
+    vectors: HashMap<u64, Vec<f32>>,
+}
+
+/// Hash used as the store key. Not guaranteed to be stable across toolchains,
+/// which at worst means re-embedding everything once.
+pub fn content_key(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+pub fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    let (mut dot, mut na, mut nb) = (0.0, 0.0, 0.0);
+    for (x, y) in a.iter().zip(b) {
+        dot += x * y;
+        na += x * x;
+        nb += y * y;
+    }
+    if na == 0.0 || nb == 0.0 {
+        0.0
+    } else {
+        dot / (na.sqrt() * nb.sqrt())
+    }
+}
+
+impl EmbeddingStore {
+    fn path() -> Option<PathBuf> {
+        eframe::storage_dir(crate::TITLE).map(|p| p.join("embeddings.json"))
+    }
+
+    pub fn load() -> Self {
+        let Some(path) = Self::path().filter(|p| p.exists()) else {
+            return Self::default();
+        };
+        match std::fs::File::open(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|f| Ok(serde_json::from_reader(std::io::BufReader::new(f))?))
+        {
+            Ok(store) => store,
+            Err(e) => {
+                log::error!("failed to read `{}`: {e}", path.display());
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self) {
+        let Some(path) = Self::path() else {
+            return;
+        };
+        if let Ok(file) = std::fs::File::create(&path) {
+            if let Err(e) = serde_json::to_writer(std::io::BufWriter::new(file), self) {
+                log::error!("failed to write `{}`: {e}", path.display());
+            }
+        }
+    }
+
+    /// Drops the index when it was built by another embedder.
+    pub fn set_embedder(&mut self, embedder: Embedder) {
+        if self.embedder != embedder {
+            log::info!("re-indexing messages with {embedder:?}");
+            *self = Self {
+                embedder,
+                ..Self::default()
+            };
+        }
+    }
+
+    pub fn insert(&mut self, vectors: Vec<(u64, Vec<f32>)>) {
+        self.vectors.extend(vectors);
+    }
+
+    /// Forgets the vectors of messages that were edited or deleted since.
+    pub fn prune(&mut self, chats: &[Chat]) {
+        let keys: std::collections::HashSet<u64> = chats
+            .iter()
+            .flat_map(|c| &c.messages)
+            .map(|m| content_key(&m.content))
+            .collect();
+        self.vectors.retain(|key, _| keys.contains(key));
+    }
+
+    /// Contents that still need embedding, at most `limit` of them,
+    /// along with the number of messages indexed so far and the total.
+    pub fn missing(&self, chats: &[Chat], limit: usize) -> (Vec<(u64, String)>, (usize, usize)) {
+        let mut batch: Vec<(u64, String)> = Vec::new();
+        let (mut indexed, mut total) = (0, 0);
+        for msg in chats.iter().flat_map(|c| &c.messages) {
+            if msg.is_marker || msg.content.trim().is_empty() {
+                continue;
+            }
+            total += 1;
+            let key = content_key(&msg.content);
+            if self.vectors.contains_key(&key) {
+                indexed += 1;
+            } else if batch.len() < limit && !batch.iter().any(|(k, _)| *k == key) {
+                batch.push((key, msg.content.chars().take(MAX_EMBED_CHARS).collect()));
+            }
+        }
+        (batch, (indexed, total))
+    }
+
+    /// Ranks conversations by their best matching message.
+    /// Returns `(chat_idx, msg_idx, score)`, best first.
+    pub fn rank(&self, chats: &[Chat], query: &[f32]) -> Vec<(usize, usize, f32)> {
+        let mut results: Vec<(usize, usize, f32)> = chats
+            .iter()
+            .enumerate()
+            .filter_map(|(chat_idx, chat)| {
+                chat.messages
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, m)| !m.is_marker)
+                    .filter_map(|(msg_idx, m)| {
+                        let vector = self.vectors.get(&content_key(&m.content))?;
+                        Some((chat_idx, msg_idx, cosine(vector, query)))
+                    })
+                    .max_by(|a, b| a.2.total_cmp(&b.2))
+            })
+            .collect();
+        results.sort_unstable_by(|a, b| b.2.total_cmp(&a.2));
+        results.truncate(MAX_RESULTS);
+        results
+    }
+}
+
+/// Embeds `texts` with the Gemini embedding model. Only available with an API key,
+/// Code Assist doesn't expose embeddings.
+pub async fn embed(settings: &Settings, texts: &[String], task: TaskType) -> Result<Vec<Vec<f32>>> {
+    if !matches!(settings.auth_method, AuthMethod::ApiKey) || settings.api_key.is_empty() {
+        return Err(anyhow!("Semantic search requires a Gemini API key."));
+    }
+
+    let client = crate::widgets::build_client(
+        &settings.api_key,
+        settings.proxy_path.clone(),
+        Model::Custom(EMBEDDING_MODEL.to_owned()),
+    )?;
+
+    let mut builder = client.batch_embed_contents().with_task_type(task);
+    for text in texts {
+        builder = builder.with_message(text);
+    }
+    let resp = builder.execute().await?;
+
+    if resp.embeddings.len() != texts.len() {
+        return Err(anyhow!(
+            "Expected {} embeddings, got {}",
+            texts.len(),
+            resp.embeddings.len()
+        ));
+    }
+    Ok(resp.embeddings.into_iter().map(|e| e.values).collect())
+}
+
+/// Embeds `texts` with `embedder`.
+pub async fn embed_with(
+    settings: &Settings,
+    embedder: Embedder,
+    texts: &[String],
+    task: TaskType,
+) -> Result<Vec<Vec<f32>>> {
+    match embedder {
+        Embedder::Gemini => embed(settings, texts, task).await,
+        Embedder::Local => embed_local(texts, task).await,
+    }
+}
+
+#[cfg(not(feature = "local-embeddings"))]
+async fn embed_local(_: &[String], _: TaskType) -> Result<Vec<Vec<f32>>> {
+    Err(anyhow!(tr!("embedder-local-unsupported")))
+}
+
+/// The local model, loaded on first use and kept for the rest of the session.
+#[cfg(feature = "local-embeddings")]
+static LOCAL_MODEL: std::sync::LazyLock<std::sync::Mutex<Option<fastembed::TextEmbedding>>> =
+    std::sync::LazyLock::new(|| std::sync::Mutex::new(None));
+
+/// Embeds `texts` with multilingual E5 on the CPU. The model files are downloaded
+/// into the app data on first use, after that it works offline.
+#[cfg(feature = "local-embeddings")]
+async fn embed_local(texts: &[String], task: TaskType) -> Result<Vec<Vec<f32>>> {
+    use fastembed::{EmbeddingModel, InitOptions, TextEmbedding};
+
+    // E5 was trained with these prefixes and does noticeably worse without them
+    let prefix = match task {
+        TaskType::RetrievalQuery => "query: ",
+        _ => "passage: ",
+    };
+    let texts: Vec<String> = texts
+        .iter()
+        .map(|t| {
+            let text: String = t.chars().take(MAX_EMBED_CHARS).collect();
+            format!("{prefix}{text}")
+        })
+        .collect();
+    tokio::task::spawn_blocking(move || {
+        let mut model = LOCAL_MODEL
+            .lock()
+            .map_err(|_| anyhow!("the local embedding model panicked"))?;
+        if model.is_none() {
+            let mut options = InitOptions::new(EmbeddingModel::MultilingualE5Small)
+                .with_show_download_progress(false);
+            if let Some(dir) = eframe::storage_dir(crate::TITLE) {
+                options = options.with_cache_dir(dir.join("models"));
+            }
+            log::info!("loading the local embedding model");
+            *model = Some(TextEmbedding::try_new(options)?);
+        }
+        let model = model.as_mut().expect("loaded above");
+        model.embed(texts, Some(BATCH_SIZE))
+    })
+    .await?
+}
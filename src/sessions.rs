@@ -1,7 +1,36 @@
 use crate::{
+    analytics::Analytics,
+    batch::BatchRunner,
     chat::{Chat, ChatAction, ChatExportFormat},
-    file_handler::Attachment,
-    widgets::{ModelPicker, RequestInfoType, Settings},
+    compare::DocumentCompare,
+    context_cache::{CachePanel, ContextCache},
+    deeplink::DeepLink,
+    diagnostics::Diagnostics,
+    embedding_playground::EmbeddingPlayground,
+    file_handler::{Attachment, AttachmentState},
+    folders::{DragItem, Folder},
+    functions::FunctionsPanel,
+    i18n::{tr, Language},
+    image_edit::ImageEditor,
+    instance::OpenRequest,
+    keymap::Command,
+    knowledge::Knowledge,
+    logs::LogViewer,
+    model_compare::ModelCompare,
+    models::ModelInfo,
+    onboarding::{KeyCheck, Onboarding, OnboardingAction},
+    palette::{CommandPalette, PaletteAction, PaletteEntry},
+    request_preview::{Preview, RequestPreview},
+    scheduler::Scheduler,
+    scripting::Hook,
+    search::{GlobalSearch, ParsedQuery, SavedSearch, SearchAction},
+    semantic::{Embedder, EmbeddingStore},
+    snippets::Snippet,
+    tags::Tag,
+    timeline::{Timeline, TimelineAction},
+    verify::Verification,
+    video::VideoStudio,
+    widgets::{AuthMethod, GeminiModel, ModelPicker, RequestInfoType, Settings},
 };
 use eframe::egui::{self, vec2, Color32, CornerRadius, Frame, Layout, Stroke};
 use egui_commonmark::CommonMarkCache;
@@ -15,6 +44,7 @@ use parking_lot::RwLock;
 use std::sync::Arc;
 use std::{
     cell::RefCell,
+    collections::HashSet,
     hash::{Hash, Hasher},
     path::PathBuf,
     rc::Rc,
@@ -27,6 +57,10 @@ use tts::Tts;
 enum SessionTab {
     #[default]
     Chats,
+    Prompts,
+    Schedules,
+    Knowledge,
+    Videos,
 }
 
 #[cfg(feature = "tts")]
@@ -38,7 +72,12 @@ enum BackendResponse {
         id: usize,
         files: Vec<PathBuf>,
     },
+    Download {
+        chat_id: usize,
+        result: Result<PathBuf, String>,
+    },
     Settings(Box<Settings>),
+    Snippets(Vec<Snippet>),
     TokenCount {
         chat_id: usize,
         count: u32,
@@ -47,8 +86,99 @@ enum BackendResponse {
         token: String,
         projects: Vec<String>,
     },
+    PromptSuggestion {
+        chat_id: usize,
+        /// The chatbox text the suggestion was made for, it may have been edited since
+        draft: String,
+        suggestion: Result<String, String>,
+    },
+    Verification {
+        chat_id: usize,
+        /// When the checked message was sent, indices shift as messages are deleted
+        time: chrono::DateTime<chrono::Utc>,
+        result: Result<Box<Verification>, String>,
+    },
+    Reupload {
+        chat_id: usize,
+        /// When the message with the attachment was sent
+        time: chrono::DateTime<chrono::Utc>,
+        path: PathBuf,
+        /// `None` if the user cancelled the upload
+        result: Result<Option<gemini_rust::File>, String>,
+    },
+    CommandOutput {
+        chat_id: usize,
+        /// When the message with the call was sent
+        time: chrono::DateTime<chrono::Utc>,
+        call: usize,
+        result: Result<String, String>,
+    },
+    ContextCache {
+        chat_id: usize,
+        result: Result<Box<ContextCache>, String>,
+    },
+    Summary {
+        chat_id: usize,
+        /// When the first message kept word for word was sent
+        kept_from: chrono::DateTime<chrono::Utc>,
+        result: Result<String, String>,
+    },
+    RequestPreview(Box<Result<Preview, String>>),
+    AbOutputs {
+        prompt_id: u64,
+        a: Result<String, String>,
+        b: Result<String, String>,
+    },
+    Embeddings {
+        embedder: Embedder,
+        result: Result<Vec<(u64, Vec<f32>)>, String>,
+    },
+    QueryEmbedding {
+        embedder: Embedder,
+        result: Result<Vec<f32>, String>,
+    },
+    KeyValidation(Result<(), String>),
+    Models {
+        /// What they were listed for, the setting may have changed since
+        auth_method: AuthMethod,
+        result: Result<Vec<ModelInfo>, String>,
+    },
+}
+
+/// Action applied to every chat selected in the sidebar, after confirmation.
+#[derive(Clone, Copy)]
+enum BulkAction {
+    Delete,
+    Archive(bool),
+    Tag(u64),
+    MoveToFolder(Option<u64>),
+    Export,
+}
+
+/// A local command a function call asked for, run once the user allows it.
+struct PendingCommand {
+    chat_id: usize,
+    time: chrono::DateTime<chrono::Utc>,
+    call: usize,
+    name: String,
+    command: String,
+    args: serde_json::Value,
+}
+
+/// Results gathered while drawing the chat list, applied once it's done.
+#[derive(Default)]
+struct SidebarOutput {
+    dropped: Option<(DragItem, Option<u64>)>,
+    clicked: Option<usize>,
+    /// Ids of the chats in the order they were shown
+    order: Vec<usize>,
 }
 
+/// How many removed chats are kept around for "Reopen closed chat".
+const MAX_CLOSED_CHATS: usize = 10;
+/// Typing pause after which the composed message is counted.
+const TOKEN_COUNT_DELAY: Duration = Duration::from_millis(700);
+
 // <progress, response, error>
 type BackendFlower = CompactFlower<(), BackendResponse, String>;
 type BackendFlowerHandle = CompactHandle<(), BackendResponse, String>;
@@ -81,6 +211,110 @@ pub struct Sessions {
     pub settings: Settings,
     #[serde(default = "default_true")]
     left_panel_visible: bool,
+    /// Distraction-free layout without the sidebar and top bar
+    focus_mode: bool,
+    #[serde(skip)]
+    search: GlobalSearch,
+    #[serde(skip)]
+    timeline: Timeline,
+    #[serde(skip)]
+    palette: CommandPalette,
+    /// Set once the first launch wizard was finished or skipped
+    onboarding_done: bool,
+    #[serde(skip)]
+    onboarding: Onboarding,
+    /// Loaded on first use of semantic search
+    #[serde(skip)]
+    embeddings: Option<EmbeddingStore>,
+    #[serde(skip)]
+    is_indexing: bool,
+    tags: Vec<Tag>,
+    #[serde(skip)]
+    tag_filter: Vec<u64>,
+    #[serde(skip)]
+    new_tag: String,
+    folders: Vec<Folder>,
+    #[serde(skip)]
+    edited_folder: Option<u64>,
+    saved_searches: Vec<SavedSearch>,
+    scheduler: Scheduler,
+    knowledge: Knowledge,
+    videos: VideoStudio,
+    /// Collection the chat edit panel remembers chats into
+    #[serde(skip)]
+    memory_target: Option<u64>,
+    batch: BatchRunner,
+    embedding_playground: EmbeddingPlayground,
+    compare: DocumentCompare,
+    model_compare: ModelCompare,
+    image_editor: ImageEditor,
+    folder_bundler: crate::folder_bundle::FolderBundler,
+    #[cfg(feature = "screenshots")]
+    #[serde(skip)]
+    screenshot: crate::screenshot::ScreenCapture,
+    #[serde(skip)]
+    request_preview: RequestPreview,
+    #[serde(skip)]
+    logs: LogViewer,
+    #[serde(skip)]
+    diagnostics: Diagnostics,
+    analytics: Analytics,
+    #[serde(skip)]
+    functions: FunctionsPanel,
+    #[serde(skip)]
+    pending_command: Option<PendingCommand>,
+    #[serde(skip)]
+    caches: CachePanel,
+    /// Auth method the model list was last fetched for
+    #[serde(skip)]
+    models_listed_for: Option<AuthMethod>,
+    /// Chat id and position of the open sidebar context menu
+    #[serde(skip)]
+    chat_menu: Option<(usize, egui::Pos2)>,
+    /// Move keyboard focus into the chat menu, it was opened with the keyboard
+    #[serde(skip)]
+    focus_chat_menu: bool,
+    /// Ids of the chats selected with Ctrl/Shift+click
+    #[serde(skip)]
+    selection: Vec<usize>,
+    #[serde(skip)]
+    selection_anchor: Option<usize>,
+    #[serde(skip)]
+    visible_order: Vec<usize>,
+    #[serde(skip)]
+    bulk_action: Option<BulkAction>,
+    /// Soft-deleted chats, most recent last
+    closed_chats: Vec<Chat>,
+    /// Ids of previously viewed chats, most recent last
+    #[serde(skip)]
+    nav_back: Vec<usize>,
+    #[serde(skip)]
+    nav_forward: Vec<usize>,
+    /// Id of the chat shown next to the selected one
+    #[serde(skip)]
+    split_chat: Option<usize>,
+    /// Ids of chats open in their own windows
+    #[serde(skip)]
+    detached: Vec<usize>,
+    #[cfg(feature = "tray")]
+    #[serde(skip)]
+    tray: Option<crate::tray::Tray>,
+    /// Set when quitting from the tray, so "close to tray" doesn't intercept it
+    #[cfg(feature = "tray")]
+    #[serde(skip)]
+    quitting: bool,
+    #[cfg(feature = "notifications")]
+    #[serde(skip)]
+    notifier: crate::notifications::Notifier,
+    /// Requests from `--automation` or `--automation-socket`
+    #[serde(skip)]
+    automation: Option<crate::automation::Automation>,
+    /// `send_message` calls waiting for the model, with the chat id and its request
+    #[serde(skip)]
+    automation_waits: Vec<(usize, u64, crate::automation::Call)>,
+    /// Arguments of later launches, forwarded to this one
+    #[serde(skip)]
+    instance: Option<crate::instance::Instance>,
 }
 
 fn default_true() -> bool {
@@ -154,6 +388,60 @@ impl Default for Sessions {
             settings_open: false,
             settings: Settings::default(),
             left_panel_visible: true,
+            focus_mode: false,
+            search: GlobalSearch::default(),
+            timeline: Timeline::default(),
+            palette: CommandPalette::default(),
+            onboarding_done: false,
+            onboarding: Onboarding::default(),
+            embeddings: None,
+            is_indexing: false,
+            tags: Vec::new(),
+            tag_filter: Vec::new(),
+            new_tag: String::new(),
+            folders: Vec::new(),
+            edited_folder: None,
+            saved_searches: Vec::new(),
+            scheduler: Scheduler::default(),
+            knowledge: Knowledge::default(),
+            videos: VideoStudio::default(),
+            memory_target: None,
+            batch: BatchRunner::default(),
+            embedding_playground: EmbeddingPlayground::default(),
+            compare: DocumentCompare::default(),
+            model_compare: ModelCompare::default(),
+            image_editor: ImageEditor::default(),
+            folder_bundler: Default::default(),
+            #[cfg(feature = "screenshots")]
+            screenshot: Default::default(),
+            request_preview: RequestPreview::default(),
+            logs: LogViewer::default(),
+            diagnostics: Diagnostics::default(),
+            analytics: Analytics::default(),
+            functions: FunctionsPanel::default(),
+            pending_command: None,
+            caches: CachePanel::default(),
+            models_listed_for: None,
+            chat_menu: None,
+            focus_chat_menu: false,
+            selection: Vec::new(),
+            selection_anchor: None,
+            visible_order: Vec::new(),
+            bulk_action: None,
+            closed_chats: Vec::new(),
+            nav_back: Vec::new(),
+            nav_forward: Vec::new(),
+            split_chat: None,
+            detached: Vec::new(),
+            #[cfg(feature = "tray")]
+            tray: None,
+            #[cfg(feature = "tray")]
+            quitting: false,
+            #[cfg(feature = "notifications")]
+            notifier: Default::default(),
+            automation: None,
+            automation_waits: Vec::new(),
+            instance: None,
         }
     }
 }
@@ -224,7 +512,7 @@ async fn load_settings(handle: &BackendFlowerHandle) {
         .pick_file()
         .await
     else {
-        handle.success(BackendResponse::Toast(Toast::info("No file selected")));
+        handle.success(BackendResponse::Toast(Toast::info(tr!("no-file-selected"))));
         return;
     };
 
@@ -245,13 +533,40 @@ async fn load_settings(handle: &BackendFlowerHandle) {
     }
 }
 
+async fn load_snippets(handle: &BackendFlowerHandle) {
+    let Some(file) = rfd::AsyncFileDialog::new()
+        .add_filter("JSON file", &["json"])
+        .pick_file()
+        .await
+    else {
+        handle.success(BackendResponse::Toast(Toast::info(tr!("no-file-selected"))));
+        return;
+    };
+
+    log::info!("reading snippets from `{}`", file.path().display());
+    let Ok(f) = std::fs::File::open(file.path()).map_err(|e| {
+        log::error!("failed to open file `{}`: {e}", file.path().display());
+        handle.success(BackendResponse::Toast(Toast::error(e.to_string())));
+    }) else {
+        return;
+    };
+
+    match serde_json::from_reader(std::io::BufReader::new(f)) {
+        Ok(snippets) => handle.success(BackendResponse::Snippets(snippets)),
+        Err(e) => {
+            log::error!("failed to load snippets: {e}");
+            handle.success(BackendResponse::Toast(Toast::error(e.to_string())));
+        }
+    }
+}
+
 fn preview_files_being_dropped(ctx: &egui::Context) {
     use egui::*;
     use std::fmt::Write as _;
 
     if !ctx.input(|i| i.raw.hovered_files.is_empty()) {
         let text = ctx.input(|i| {
-            let mut text = "Dropping files:".to_owned();
+            let mut text = tr!("dropping-files");
             for file in &i.raw.hovered_files {
                 if let Some(path) = &file.path {
                     write!(text, "\n{}", path.display()).ok();
@@ -293,7 +608,7 @@ impl Sessions {
             };
         }
 
-        if self.settings.let_it_snow {
+        if self.settings.let_it_snow && !self.settings.theme.reduced_motion {
             egui_snow::Snow::new("snow_effect")
                 .color(egui::Color32::from_white_alpha(200))
                 .speed(40.0..=100.0)
@@ -310,7 +625,7 @@ impl Sessions {
         let mut request_repaint = false;
 
         // Poll logs and show them as toasts
-        for log in crate::logger::pop_logs() {
+        for log in geminid_core::logger::pop_logs() {
             match log.level {
                 log::Level::Error => {
                     self.toasts.add(Toast::error(log.message));
@@ -323,6 +638,11 @@ impl Sessions {
             request_repaint = true;
         }
 
+        if self.models_listed_for != Some(self.settings.auth_method) {
+            self.models_listed_for = Some(self.settings.auth_method);
+            self.list_models();
+        }
+
         let mut modal = Modal::new(ctx, "sessions_main_modal");
         let mut chat_modal = Modal::new(ctx, "chat_main_modal").with_close_on_outside_click(true);
         let settings_modal =
@@ -330,9 +650,54 @@ impl Sessions {
 
         // poll all flowers
         for chat in self.chats.iter_mut() {
+            let project = crate::folders::project(&self.folders, chat.folder);
+            if chat.project.as_ref() != project {
+                chat.project = project.cloned();
+            }
+
             if chat.flower_active() {
                 request_repaint = true;
                 chat.poll_flower(&mut chat_modal);
+                let scheduled = if chat.flower_active() {
+                    None
+                } else {
+                    chat.run_response_hook(&self.settings);
+                    if self.settings.local_analytics {
+                        if let Some(last) = chat.messages.last() {
+                            self.analytics.record(last);
+                        }
+                    }
+                    self.scheduler
+                        .finished(chat.id)
+                        .map(|s| (s.name.clone(), s.notify))
+                };
+                if let Some((name, _)) = &scheduled {
+                    self.toasts
+                        .add(Toast::info(tr!("schedule-finished", name = name.as_str())));
+                }
+                #[cfg(feature = "tts")]
+                if self.settings.voice_mode && !chat.flower_active() {
+                    chat.speak_last(self.tts.clone(), &self.settings.personas);
+                }
+                #[cfg(feature = "notifications")]
+                if self.settings.desktop_notifications
+                    && !chat.flower_active()
+                    && (crate::notifications::window_inactive(ctx)
+                        || scheduled.as_ref().is_some_and(|(_, notify)| *notify))
+                {
+                    if let Some(last) = chat.messages.last().filter(|m| !m.is_user()) {
+                        let title = if last.is_error {
+                            tr!("notification-failed")
+                        } else if let Some((name, _)) = &scheduled {
+                            name.clone()
+                        } else if chat.summary.is_empty() {
+                            tr!("notification-ready")
+                        } else {
+                            chat.summary.clone()
+                        };
+                        self.notifier.notify(ctx, chat.id(), &title, &last.content);
+                    }
+                }
             }
         }
         if self.flower.is_active() {
@@ -343,9 +708,115 @@ impl Sessions {
         chat_modal.show_dialog();
         modal.show_dialog();
         self.settings.show_modal(&settings_modal);
+        let picker = self
+            .chats
+            .get(self.selected_chat)
+            .map_or(&self.settings.model_picker, |chat| &chat.model_picker);
+        self.batch.show(
+            ctx,
+            &self.settings,
+            picker,
+            &self.settings.prompt_library.prompts,
+        );
+        self.embedding_playground.show(ctx, &self.settings);
+        self.compare.show(ctx, &self.settings);
+        self.model_compare
+            .show(ctx, &self.settings, picker, &mut self.commonmark_cache);
+        self.image_editor.show(ctx, &self.settings);
+        if let Some((chat_id, path)) = self.folder_bundler.show(ctx, &self.settings) {
+            if let Some(chat) = self.chats.iter_mut().find(|c| c.id() == chat_id) {
+                chat.files.push(Attachment::from_path(path));
+            }
+        }
+        #[cfg(feature = "screenshots")]
+        self.handle_screenshot(ctx);
+        self.request_preview.show(ctx);
+        self.logs.show(ctx);
+        self.diagnostics.show(ctx, &self.settings);
+        self.analytics.show(ctx, &mut self.settings.local_analytics);
+        self.functions.show(
+            ctx,
+            &mut self.settings.functions,
+            &mut self.settings.run_commands,
+        );
+        self.caches.show(ctx, &self.settings);
+        let command_modal = Modal::new(ctx, "run_command_modal");
+        if self.pending_command.is_some() && !command_modal.is_open() {
+            command_modal.open();
+        }
+        command_modal.show(|ui| {
+            self.show_command_modal_inner(ui, &command_modal);
+        });
+
+        self.settings.theme.apply_if_changed(ctx);
+        self.settings
+            .fonts
+            .apply_if_changed(ctx, self.settings.reading.font_file());
+        self.settings.plugins.apply_if_changed();
+        crate::i18n::set_language(self.settings.language.unwrap_or_else(Language::system));
+        #[cfg(feature = "notifications")]
+        if let Some(chat_id) = self.notifier.poll_clicked() {
+            if let Some(idx) = self.chats.iter().position(|c| c.id() == chat_id) {
+                self.select_chat(idx);
+                self.settings_open = false;
+                self.edited_chat = None;
+            }
+        }
+        self.handle_shortcuts(ctx);
+        #[cfg(feature = "tray")]
+        self.handle_tray(ctx);
+        self.handle_automation();
+        self.handle_instance(ctx);
+        self.resolve_script_tags();
+        self.run_due_schedules(ctx);
+        self.knowledge.watch(ctx, &self.settings);
+        self.videos.poll(ctx, &self.settings);
+
+        if self.search.open && self.search.semantic {
+            self.update_embedding_index();
+        }
+        match self.search.show(ctx, &self.chats, &self.tags) {
+            SearchAction::None => (),
+            SearchAction::Jump { chat_idx, msg_idx } => self.jump_to_message(chat_idx, msg_idx),
+            SearchAction::Semantic(query) => self.spawn_semantic_query(query),
+            SearchAction::Save(query) => {
+                let id = self.saved_searches.iter().map(|s| s.id).max().unwrap_or(0) + 1;
+                self.saved_searches.push(SavedSearch {
+                    id,
+                    name: query.clone(),
+                    query,
+                    ..Default::default()
+                });
+                self.tab = SessionTab::Chats;
+                self.left_panel_visible = true;
+                self.toasts.add(Toast::success(tr!("search-saved")));
+            }
+        }
+
+        if let TimelineAction::Jump { chat_idx, msg_idx } = self.timeline.show(ctx, &self.chats) {
+            self.jump_to_message(chat_idx, msg_idx);
+        }
+
+        if self.palette.open {
+            let entries = self.palette_entries(ctx);
+            if let Some(action) = self.palette.show(ctx, entries) {
+                self.run_palette_action(action);
+            }
+        }
+
+        // setups from before the wizard existed don't need it
+        if !self.onboarding_done
+            && !self.onboarding.shown
+            && (!self.settings.api_key.is_empty() || !self.settings.oauth_token.is_empty())
+        {
+            self.onboarding_done = true;
+        }
+        if !self.onboarding_done {
+            self.show_onboarding(ctx);
+        }
 
         // Top bar for global controls
-        egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
+        egui::TopBottomPanel::top("top_panel").show_animated(ctx, !self.focus_mode, |ui| {
             ui.horizontal(|ui| {
                 if ui
                     .button(if self.left_panel_visible {
@@ -353,36 +824,96 @@ impl Sessions {
                     } else {
                         "▶"
                     })
-                    .on_hover_text("Toggle Sidebar")
+                    .on_hover_text(tr!("toggle-sidebar"))
                     .clicked()
                 {
                     self.left_panel_visible = !self.left_panel_visible;
                 }
+                if ui
+                    .add_enabled(!self.nav_back.is_empty(), egui::Button::new("⏴"))
+                    .on_hover_text(tr!("back") + &self.settings.keymap.hint(ctx, Command::Back))
+                    .clicked()
+                {
+                    self.navigate(true);
+                }
+                if ui
+                    .add_enabled(!self.nav_forward.is_empty(), egui::Button::new("⏵"))
+                    .on_hover_text(
+                        tr!("forward") + &self.settings.keymap.hint(ctx, Command::Forward),
+                    )
+                    .clicked()
+                {
+                    self.navigate(false);
+                }
+                if ui
+                    .add_enabled(!self.closed_chats.is_empty(), egui::Button::new("↺"))
+                    .on_hover_text(
+                        tr!("reopen-closed-chat")
+                            + &self.settings.keymap.hint(ctx, Command::ReopenClosed),
+                    )
+                    .clicked()
+                {
+                    self.reopen_closed_chat();
+                }
+                if ui
+                    .selectable_label(self.timeline.open, "📅")
+                    .on_hover_text(
+                        tr!("timeline") + &self.settings.keymap.hint(ctx, Command::Timeline),
+                    )
+                    .clicked()
+                {
+                    self.timeline.toggle();
+                }
 
                 ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
                     if ui
                         .toggle_value(&mut self.settings_open, "⚙")
-                        .on_hover_text("Settings")
+                        .on_hover_text(
+                            tr!("settings") + &self.settings.keymap.hint(ctx, Command::Settings),
+                        )
                         .clicked()
                     {
                         if self.settings_open {
                             self.edited_chat = None;
+                            self.edited_folder = None;
                         }
                     }
 
                     if let Some(chat) = self.chats.get(self.selected_chat) { // TODO!
-                        let count = chat.token_count.unwrap_or(0);
-                        ui.label(format!("{} tokens", count))
-                            .on_hover_text("Estimated total tokens in context");
-                        ui.separator();
+                        if let Some(persona) =
+                            crate::persona::find(&self.settings.personas, chat.persona)
+                        {
+                            EmojiLabel::new(persona.label()).show(ui);
+                            ui.separator();
+                        }
                     }
                 });
             });
         });
 
-        if self.left_panel_visible {
+        if self.focus_mode {
+            egui::Area::new(egui::Id::new("exit_focus_mode"))
+                .anchor(egui::Align2::RIGHT_TOP, vec2(-8.0, 8.0))
+                .show(ctx, |ui| {
+                    if ui
+                        .add(egui::Button::new("⛶").frame(false))
+                        .on_hover_text(
+                            tr!("exit-focus-mode")
+                                + &self.settings.keymap.hint(ctx, Command::FocusMode),
+                        )
+                        .clicked()
+                    {
+                        self.focus_mode = false;
+                    }
+                });
+        } else if self.left_panel_visible {
             let avail_width = ctx.available_rect().width();
-            egui::SidePanel::left("sessions_panel")
+            let side = if self.settings.rtl_layout {
+                egui::panel::Side::Right
+            } else {
+                egui::panel::Side::Left
+            };
+            egui::SidePanel::new(side, "sessions_panel")
                 .resizable(true)
                 .max_width(avail_width * 0.5)
                 .show(ctx, |ui| {
@@ -410,6 +941,13 @@ impl Sessions {
                                     load_settings(&handle).await;
                                 });
                             }
+                            RequestInfoType::LoadSnippets => {
+                                let handle = self.flower.handle();
+                                tokio::spawn(async move {
+                                    handle.activate();
+                                    load_snippets(&handle).await;
+                                });
+                            }
                             RequestInfoType::LoginGoogle => {
                                 let handle = self.flower.handle();
                                 tokio::spawn(async move {
@@ -429,6 +967,16 @@ impl Sessions {
                     if let Some(proj) = selected_project {
                         self.settings.project_id = proj;
                     }
+
+                    ui.separator();
+                    ui.heading("Personas");
+                    crate::persona::show_personas(
+                        ui,
+                        &mut self.settings.personas,
+                        &mut self.settings.prompt_library,
+                        #[cfg(feature = "tts")]
+                        &self.tts,
+                    );
                     if should_logout {
                         use gemini_code_assist_adapter::auth::GoogleAuthManager;
                         GoogleAuthManager::new().clear_token_cache();
@@ -436,7 +984,7 @@ impl Sessions {
                         self.settings.project_id.clear();
                         self.settings.available_projects.clear();
                         self.toasts
-                            .add(Toast::info("Logged out and cache cleared."));
+                            .add(Toast::info(tr!("settings-logged-out")));
                     }
                 });
             });
@@ -446,6 +994,49 @@ impl Sessions {
                     self.show_chat_edit_panel(ui, edited_chat);
                 })
             });
+        } else if let Some(edited_folder) = self.edited_folder {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                egui::ScrollArea::both().auto_shrink(false).show(ui, |ui| {
+                    self.show_folder_edit_panel(ui, edited_folder);
+                })
+            });
+        } else if self.tab == SessionTab::Prompts && self.settings.prompt_library.has_selection() {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                egui::ScrollArea::both().auto_shrink(false).show(ui, |ui| {
+                    let default_model = self.settings.model_picker.selected;
+                    if let Some(request) =
+                        self.settings.prompt_library.show_editor(ui, default_model)
+                    {
+                        self.spawn_ab_run(request);
+                    }
+                })
+            });
+        } else if self.tab == SessionTab::Schedules && self.scheduler.has_selection() {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                egui::ScrollArea::both().auto_shrink(false).show(ui, |ui| {
+                    let chats: Vec<(u64, String)> =
+                        self.chats.iter().map(|c| (c.id, c.title())).collect();
+                    if let Some(id) = self.scheduler.show_editor(
+                        ui,
+                        &self.settings.prompt_library.prompts,
+                        &chats,
+                    ) {
+                        self.run_schedule(id);
+                    }
+                })
+            });
+        } else if self.tab == SessionTab::Knowledge && self.knowledge.has_selection() {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                egui::ScrollArea::both().auto_shrink(false).show(ui, |ui| {
+                    self.knowledge.show_editor(ui, &self.settings);
+                })
+            });
+        } else if self.tab == SessionTab::Videos {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                egui::ScrollArea::both().auto_shrink(false).show(ui, |ui| {
+                    self.videos.show_editor(ui, &self.settings);
+                })
+            });
         } else {
             self.show_selected_chat(
                 ctx,
@@ -455,7 +1046,13 @@ impl Sessions {
             preview_files_being_dropped(ctx);
         }
 
-        // Token counting logic
+        self.show_detached_chats(
+            ctx,
+            #[cfg(feature = "tts")]
+            (prev_is_speaking && !self.is_speaking),
+        );
+
+        // Token counting, once the typing pauses
         if let Some(chat) = self.chats.get_mut(self.selected_chat) {
             let mut hasher = std::collections::hash_map::DefaultHasher::new();
             chat.chatbox.hash(&mut hasher);
@@ -468,47 +1065,67 @@ impl Sessions {
             for f in &chat.files {
                 f.path.hash(&mut hasher);
             }
+            chat.model_picker.selected.to_string().hash(&mut hasher);
+            chat.system_prompt().hash(&mut hasher);
             let current_hash = hasher.finish();
 
-            let should_update = chat.last_content_hash != current_hash
-                && chat
-                    .last_token_check
-                    .map_or(true, |t| t.elapsed() > Duration::from_secs(1));
-
-            if should_update && !self.settings.api_key.is_empty() {
+            if chat.last_content_hash != current_hash {
                 chat.last_content_hash = current_hash;
-                chat.last_token_check = Some(Instant::now());
+                chat.token_count_due = Some(Instant::now() + TOKEN_COUNT_DELAY);
+                ctx.request_repaint_after(TOKEN_COUNT_DELAY);
+            }
+            let due = chat
+                .token_count_due
+                .is_some_and(|due| due <= Instant::now());
+
+            if due
+                && self.settings.auth_method == AuthMethod::ApiKey
+                && !self.settings.api_key.is_empty()
+            {
+                chat.token_count_due = None;
 
                 let chat_id = chat.id();
                 let messages = chat.messages.clone();
                 let chatbox = chat.chatbox.clone();
                 let files = chat.files.clone();
+                let examples = chat.model_picker.examples.clone();
+                let system_prompt = chat.system_prompt().filter(|p| !p.trim().is_empty());
+                let picker = chat.model_picker.clone();
                 let handle = self.flower.handle();
                 let settings = self.settings.clone();
 
                 tokio::spawn(async move {
-                    if let Ok(client) = settings
-                        .model_picker
-                        .create_client(&settings.api_key, settings.proxy_path)
+                    if let Ok(client) = picker.create_client(&settings.api_key, settings.proxy_path)
                     {
                         if let Ok(contents) = crate::chat_completion::build_history(
                             &client,
                             &messages,
+                            0,
                             Some((&chatbox, &files)),
-                            false,
+                            None,
                             None,
                         )
                         .await
                         {
                             let mut builder = client.generate_content();
+                            // countTokens takes no system instruction, it costs about as much as text
+                            builder
+                                .contents
+                                .extend(system_prompt.map(gemini_rust::Content::text));
+                            builder
+                                .contents
+                                .extend(geminid_core::history::examples_to_history(&examples));
                             builder.contents.extend(contents);
 
-                            if let Ok(resp) = builder.count_tokens().await {
-                                handle.activate();
-                                handle.success(BackendResponse::TokenCount {
-                                    chat_id,
-                                    count: resp.total_tokens,
-                                });
+                            match builder.count_tokens().await {
+                                Ok(resp) => {
+                                    handle.activate();
+                                    handle.success(BackendResponse::TokenCount {
+                                        chat_id,
+                                        count: resp.total_tokens,
+                                    });
+                                }
+                                Err(e) => log::warn!("failed to count tokens: {e}"),
                             }
                         }
                     }
@@ -520,64 +1137,684 @@ impl Sessions {
         self.toasts.show(ctx);
     }
 
-    fn show_selected_chat(
-        // here: main chat
-        &mut self,
-        ctx: &egui::Context,
-        #[cfg(feature = "tts")] stopped_talking: bool,
-    ) {
-        let Some(chat) = self.chats.get_mut(self.selected_chat) else {
-            self.selected_chat = 0;
+    #[cfg(feature = "tray")]
+    pub fn init_tray(&mut self, ctx: &egui::Context) {
+        self.tray = crate::tray::Tray::new(ctx);
+    }
+
+    #[cfg(feature = "screenshots")]
+    fn handle_screenshot(&mut self, ctx: &egui::Context) {
+        let Some((chat_id, result)) = self.screenshot.show(ctx) else {
             return;
         };
+        match result {
+            Ok(path) => {
+                if let Some(chat) = self.chats.iter_mut().find(|c| c.id() == chat_id) {
+                    chat.files.push(Attachment::from_path(path));
+                    chat.focus_chatbox();
+                }
+            }
+            Err(e) => {
+                self.toasts
+                    .add(Toast::error(tr!("screenshot-failed", error = e)));
+            }
+        }
+    }
 
-        ctx.input(|i| {
-            for file in &i.raw.dropped_files {
-                if let Some(path) = &file.path {
-                    let filename = path.file_name().unwrap_or_default().to_string_lossy();
-                    let Some(ext) = path.extension().and_then(|s| s.to_str()) else {
-                        log::warn!("dropped file `{}` has no extension", path.display());
-                        self.toasts.add(Toast::info(format!(
-                            "Skipping file with no extension: `{filename}`"
-                        )));
-                        continue;
-                    };
-
-                    let all_formats: Vec<_> = [
-                        crate::IMAGE_FORMATS,
-                        crate::VIDEO_FORMATS,
-                        crate::TEXT_FORMATS,
-                        crate::MUSIC_FORMATS,
-                    ]
-                    .concat();
+    #[cfg(feature = "tray")]
+    fn handle_tray(&mut self, ctx: &egui::Context) {
+        use crate::tray::TrayCommand;
 
-                    if !all_formats.contains(&ext.to_lowercase().as_str()) {
-                        log::warn!(
-                            "dropped file `{}` has unsupported extension `{ext}`",
-                            path.display()
-                        );
-                        self.toasts.add(Toast::info(format!(
-                            "Skipping unsupported file type: `{filename}`"
-                        )));
-                        continue;
+        let Some(tray) = &self.tray else {
+            return;
+        };
+        let commands: Vec<TrayCommand> = std::iter::from_fn(|| tray.poll()).collect();
+        for command in commands {
+            match command {
+                TrayCommand::Show => (),
+                TrayCommand::NewChat => self.open_new_chat(),
+                TrayCommand::QuickAsk => {
+                    self.open_new_chat();
+                    if let Some(chat) = self.chats.get_mut(self.selected_chat) {
+                        chat.focus_chatbox();
                     }
-                    chat.files.push(Attachment::from_path(path.clone()));
+                }
+                #[cfg(feature = "tts")]
+                TrayCommand::ToggleVoiceMode => {
+                    self.settings.voice_mode = !self.settings.voice_mode;
+                    self.toasts.add(Toast::info(if self.settings.voice_mode {
+                        tr!("voice-mode-on")
+                    } else {
+                        tr!("voice-mode-off")
+                    }));
+                }
+                TrayCommand::Quit => {
+                    self.quitting = true;
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Close);
                 }
             }
-        });
+        }
 
-        let action = chat.show(
-            ctx,
-            &self.settings,
-            #[cfg(feature = "tts")]
-            self.tts.clone(),
-            #[cfg(feature = "tts")]
-            stopped_talking,
+        if self.settings.close_to_tray
+            && !self.quitting
+            && ctx.input(|i| i.viewport().close_requested())
+        {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            ctx.send_viewport_cmd(egui::ViewportCommand::Visible(false));
+        }
+    }
+
+    pub fn init_automation(
+        &mut self,
+        ctx: &egui::Context,
+        transport: crate::automation::Transport,
+    ) {
+        self.automation = Some(crate::automation::Automation::start(ctx, transport));
+    }
+
+    pub fn init_instance(&mut self, ctx: &egui::Context, listener: crate::instance::Listener) {
+        self.instance = Some(listener.start(ctx));
+    }
+
+    /// Opens what later launches forwarded, and brings the window up even if they gave nothing.
+    fn handle_instance(&mut self, ctx: &egui::Context) {
+        let Some(instance) = &self.instance else {
+            return;
+        };
+        let requests: Vec<OpenRequest> = std::iter::from_fn(|| instance.poll()).collect();
+        for request in requests {
+            self.open_request(ctx, request);
+            ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+            ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+        }
+    }
+
+    /// Opens the links of a launch, and a new chat with its files and prompt.
+    pub fn open_request(&mut self, ctx: &egui::Context, request: OpenRequest) {
+        for link in &request.links {
+            self.open_link(ctx, link);
+        }
+        if request.files.is_empty() && request.prompt.is_none() {
+            return;
+        }
+        self.open_new_chat();
+        let idx = self.selected_chat;
+        let chat = &mut self.chats[idx];
+        chat.files
+            .extend(request.files.into_iter().map(Attachment::from_path));
+        if let Some(prompt) = request.prompt {
+            chat.chatbox = prompt;
+        }
+        chat.focus_chatbox();
+    }
+
+    /// Runs queued automation requests and answers the `send_message` calls whose response is done.
+    fn handle_automation(&mut self) {
+        use crate::automation::{Method, RpcError};
+        use serde_json::{json, Value};
+
+        for (chat_id, request, call) in std::mem::take(&mut self.automation_waits) {
+            let Some(chat) = self.chats.iter().find(|c| c.id() == chat_id) else {
+                call.reply(Err(RpcError::app("the chat was deleted")));
+                continue;
+            };
+            if !chat.request_finished(request) {
+                self.automation_waits.push((chat_id, request, call));
+                continue;
+            }
+            call.reply(match chat.messages.last() {
+                Some(last) if last.is_error => Err(RpcError::app(last.content.clone())),
+                Some(last) => Ok(json!({ "chat_id": chat_id, "content": last.content })),
+                None => Err(RpcError::app("the chat was cleared")),
+            });
+        }
+
+        let Some(automation) = &self.automation else {
+            return;
+        };
+        let calls: Vec<_> = std::iter::from_fn(|| automation.poll()).collect();
+        let find = |chats: &[Chat], id: usize| {
+            chats
+                .iter()
+                .position(|c| c.id() == id)
+                .ok_or_else(|| RpcError::app(format!("no chat with id {id}")))
+        };
+        for call in calls {
+            let result = match &call.method {
+                Method::ListChats {} => Ok(Value::Array(
+                    self.chats
+                        .iter()
+                        .map(|c| json!({ "id": c.id(), "title": c.title() }))
+                        .collect(),
+                )),
+                Method::CreateChat { title } => {
+                    self.add_default_chat();
+                    let chat = self.chats.last_mut().expect("a chat was just added");
+                    if let Some(title) = title {
+                        chat.summary = title.clone();
+                    }
+                    Ok(json!({ "chat_id": chat.id() }))
+                }
+                Method::SelectChat { chat_id } => find(&self.chats, *chat_id).map(|idx| {
+                    self.select_chat(idx);
+                    self.settings_open = false;
+                    self.edited_chat = None;
+                    Value::Null
+                }),
+                Method::SendMessage {
+                    chat_id,
+                    text,
+                    wait,
+                } => {
+                    let idx = match chat_id {
+                        Some(id) => find(&self.chats, *id),
+                        None => Ok(self.selected_chat),
+                    };
+                    match idx.and_then(|idx| {
+                        self.chats
+                            .get_mut(idx)
+                            .ok_or_else(|| RpcError::app("no chat is selected"))
+                    }) {
+                        Err(e) => Err(e),
+                        Ok(chat) if chat.flower_active() => {
+                            Err(RpcError::app("the chat is still generating"))
+                        }
+                        Ok(chat) => {
+                            let request = chat.send_text(&self.settings, text);
+                            let chat_id = chat.id();
+                            match request {
+                                None => Err(RpcError::app(
+                                    "nothing was sent, the text is empty or a script cancelled it",
+                                )),
+                                Some(request) if *wait => {
+                                    self.automation_waits.push((chat_id, request, call));
+                                    continue;
+                                }
+                                Some(_) => Ok(json!({ "chat_id": chat_id })),
+                            }
+                        }
+                    }
+                }
+                Method::ListTools {} => Ok(Value::Array(
+                    crate::plugins::tools()
+                        .into_iter()
+                        .map(|(plugin, tool)| json!({ "plugin": plugin, "tool": tool }))
+                        .collect(),
+                )),
+                Method::CallTool { name, args } => {
+                    let (name, args) = (name.clone(), args.clone());
+                    // plugin calls may take a while, the reply is sent once it's done
+                    tokio::spawn(async move {
+                        let result = crate::plugins::call_tool(name, args)
+                            .await
+                            .map_err(|e| RpcError::app(e.to_string()));
+                        call.reply(result);
+                    });
+                    continue;
+                }
+                Method::ExportChat {
+                    chat_id,
+                    format,
+                    path,
+                } => find(&self.chats, *chat_id).and_then(|idx| {
+                    let mut content = Vec::new();
+                    crate::chat::write_messages(&mut content, &self.chats[idx].messages, *format)
+                        .map_err(|e| RpcError::app(e.to_string()))?;
+                    match path {
+                        Some(path) => std::fs::write(path, content)
+                            .map(|_| json!({ "path": path }))
+                            .map_err(|e| RpcError::app(e.to_string())),
+                        None => Ok(json!({ "content": String::from_utf8_lossy(&content) })),
+                    }
+                }),
+            };
+            call.reply(result);
+        }
+    }
+
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        let pressed: Vec<Command> = enum_iterator::all::<Command>()
+            .filter(|&c| c != Command::Send && self.settings.keymap.consume(ctx, c))
+            .collect();
+        for command in pressed {
+            self.run_command(command);
+        }
+
+        let (back, forward) = ctx.input(|i| {
+            (
+                i.pointer.button_clicked(egui::PointerButton::Extra1),
+                i.pointer.button_clicked(egui::PointerButton::Extra2),
+            )
+        });
+        if back || forward {
+            self.navigate(back);
+        }
+
+        if !self.settings.keymap.vim_mode || ctx.wants_keyboard_input() {
+            return;
+        }
+        let Some(chat) = self.chats.get_mut(self.selected_chat) else {
+            return;
+        };
+        let (down, up, top, bottom, insert) = ctx.input_mut(|i| {
+            // shift+g first, `consume_key` would also match it as a plain g
+            let bottom = i.consume_key(egui::Modifiers::SHIFT, egui::Key::G);
+            (
+                i.consume_key(egui::Modifiers::NONE, egui::Key::J),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::K),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::G),
+                bottom,
+                i.consume_key(egui::Modifiers::NONE, egui::Key::I),
+            )
+        });
+        if down {
+            chat.step_message(1);
+        } else if up {
+            chat.step_message(-1);
+        } else if top {
+            chat.step_message(isize::MIN);
+        } else if bottom {
+            chat.step_message(isize::MAX);
+        } else if insert {
+            chat.focus_chatbox();
+        }
+    }
+
+    fn run_command(&mut self, command: Command) {
+        match command {
+            // only meaningful inside the chatbox, handled there
+            Command::Send => (),
+            Command::NewChat => self.open_new_chat(),
+            Command::GlobalSearch => self.search.toggle(),
+            Command::CommandPalette => self.palette.toggle(),
+            Command::Settings => {
+                self.settings_open = !self.settings_open;
+                if self.settings_open {
+                    self.edited_chat = None;
+                    self.edited_folder = None;
+                }
+            }
+            Command::ToggleSidebar => self.left_panel_visible = !self.left_panel_visible,
+            Command::FocusMode => self.focus_mode = !self.focus_mode,
+            Command::Timeline => self.timeline.toggle(),
+            Command::Back => self.navigate(true),
+            Command::Forward => self.navigate(false),
+            Command::ReopenClosed => self.reopen_closed_chat(),
+            Command::Logs => self.logs.toggle(),
+            Command::Diagnostics => self.diagnostics.toggle(),
+            Command::Statistics => self.analytics.toggle(),
+            Command::Tools => self.functions.toggle(),
+            Command::ContextCaches => self.caches.toggle(),
+            Command::NextModel | Command::PreviousModel => {
+                let models = crate::models::available();
+                let current = self
+                    .chats
+                    .get(self.selected_chat)
+                    .and_then(|c| models.iter().position(|&m| m == c.model_picker.selected))
+                    .unwrap_or(0);
+                let idx = if command == Command::NextModel {
+                    (current + 1) % models.len()
+                } else {
+                    (current + models.len() - 1) % models.len()
+                };
+                self.set_model(models[idx]);
+            }
+            Command::FindInChat | Command::Regenerate | Command::Stop => {
+                let Some(chat) = self.chats.get_mut(self.selected_chat) else {
+                    return;
+                };
+                match command {
+                    Command::FindInChat => chat.toggle_find(),
+                    Command::Regenerate => chat.regenerate_last(&self.settings),
+                    _ => chat.stop(),
+                }
+            }
+        }
+    }
+
+    fn palette_entries(&self, ctx: &egui::Context) -> Vec<PaletteEntry> {
+        let mut entries: Vec<PaletteEntry> = enum_iterator::all::<Command>()
+            .filter(|&c| c != Command::Send && c != Command::CommandPalette)
+            .map(|c| PaletteEntry {
+                label: c.label(),
+                detail: self
+                    .settings
+                    .keymap
+                    .shortcut(c)
+                    .map(|s| ctx.format_shortcut(&s))
+                    .unwrap_or_default(),
+                action: PaletteAction::Command(c),
+            })
+            .collect();
+        entries.push(PaletteEntry {
+            label: if self.settings.use_streaming {
+                tr!("palette-disable-streaming")
+            } else {
+                tr!("palette-enable-streaming")
+            },
+            detail: tr!("palette-setting"),
+            action: PaletteAction::ToggleStreaming,
+        });
+        entries.extend(crate::models::available().into_iter().map(|model| PaletteEntry {
+            label: tr!("palette-switch-model", model = model.to_string()),
+            detail: tr!("palette-model"),
+            action: PaletteAction::SwitchModel(model),
+        }));
+        entries.extend(self.settings.prompt_library.prompts.iter().map(|prompt| PaletteEntry {
+            label: tr!("palette-use-prompt", prompt = prompt.name.as_str()),
+            detail: tr!("palette-prompt"),
+            action: PaletteAction::UsePrompt(prompt.id),
+        }));
+        entries.extend(self.chats.iter().enumerate().map(|(idx, chat)| PaletteEntry {
+            label: chat.title(),
+            detail: tr!("palette-chat"),
+            action: PaletteAction::JumpToChat(idx),
+        }));
+        entries
+    }
+
+    fn run_palette_action(&mut self, action: PaletteAction) {
+        match action {
+            PaletteAction::Command(command) => self.run_command(command),
+            PaletteAction::SwitchModel(model) => self.set_model(model),
+            PaletteAction::JumpToChat(idx) => {
+                self.select_chat(idx);
+                self.settings_open = false;
+                self.edited_chat = None;
+            }
+            PaletteAction::UsePrompt(id) => {
+                let Some(prompt) = self
+                    .settings
+                    .prompt_library
+                    .prompts
+                    .iter()
+                    .find(|p| p.id == id)
+                else {
+                    return;
+                };
+                let name = prompt.name.clone();
+                let text = prompt.latest().to_owned();
+                let examples = prompt.examples.clone();
+                self.settings_open = false;
+                self.edited_chat = None;
+                if let Some(chat) = self.chats.get_mut(self.selected_chat) {
+                    chat.model_picker.system_prompt = Some(text);
+                    chat.model_picker.examples = examples;
+                    chat.focus_chatbox();
+                    self.toasts.add(Toast::info(tr!(
+                        "palette-prompt-applied",
+                        prompt = name.as_str()
+                    )));
+                }
+            }
+            PaletteAction::ToggleStreaming => {
+                self.settings.use_streaming = !self.settings.use_streaming;
+                self.toasts.add(Toast::info(if self.settings.use_streaming {
+                    tr!("streaming-enabled")
+                } else {
+                    tr!("streaming-disabled")
+                }));
+            }
+        }
+    }
+
+    /// Switches the selected chat to `model`.
+    fn set_model(&mut self, model: GeminiModel) {
+        let Some(chat) = self.chats.get_mut(self.selected_chat) else {
+            return;
+        };
+        chat.model_picker.selected = model;
+        if self.settings.inherit_chat_picker {
+            self.settings.model_picker.selected = model;
+        }
+        self.toasts.add(Toast::info(format!("Model: {model}")));
+    }
+
+    fn show_selected_chat(
+        // here: main chat
+        &mut self,
+        ctx: &egui::Context,
+        #[cfg(feature = "tts")] stopped_talking: bool,
+    ) {
+        let Some(chat) = self.chats.get_mut(self.selected_chat) else {
+            self.selected_chat = 0;
+            return;
+        };
+
+        let chat_id = chat.id();
+        if self.detached.contains(&chat_id) {
+            let mut attach = false;
+            egui::CentralPanel::default().show(ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.add_space(ui.available_height() / 3.0);
+                    ui.weak(tr!("chat-detached"));
+                    attach = ui.button(tr!("chat-bring-back")).clicked();
+                });
+            });
+            if attach {
+                self.detached.retain(|&id| id != chat_id);
+            }
+            return;
+        }
+
+        ctx.input(|i| {
+            for file in &i.raw.dropped_files {
+                if let Some(path) = &file.path {
+                    let filename = path.file_name().unwrap_or_default().to_string_lossy();
+                    let Some(ext) = path.extension().and_then(|s| s.to_str()) else {
+                        log::warn!("dropped file `{}` has no extension", path.display());
+                        self.toasts.add(Toast::info(format!(
+                            "Skipping file with no extension: `{filename}`"
+                        )));
+                        continue;
+                    };
+
+                    let all_formats: Vec<_> = [
+                        crate::IMAGE_FORMATS,
+                        crate::VIDEO_FORMATS,
+                        crate::TEXT_FORMATS,
+                        crate::MUSIC_FORMATS,
+                    ]
+                    .concat();
+
+                    if !all_formats.contains(&ext.to_lowercase().as_str()) {
+                        log::warn!(
+                            "dropped file `{}` has unsupported extension `{ext}`",
+                            path.display()
+                        );
+                        self.toasts.add(Toast::info(format!(
+                            "Skipping unsupported file type: `{filename}`"
+                        )));
+                        continue;
+                    }
+                    chat.files.push(Attachment::from_path(path.clone()));
+                }
+            }
+        });
+
+        self.show_split_chat(
+            ctx,
+            #[cfg(feature = "tts")]
+            stopped_talking,
+        );
+
+        let Some(chat) = self.chats.get_mut(self.selected_chat) else {
+            return;
+        };
+        let action = chat.show(
+            ctx,
+            &self.settings,
+            #[cfg(feature = "tts")]
+            self.tts.clone(),
+            #[cfg(feature = "tts")]
+            stopped_talking,
             &mut self.commonmark_cache,
+            self.focus_mode,
         );
+        self.handle_chat_action(self.selected_chat, action);
+    }
+
+    /// Shows the split view chat in a right side panel, if there is one.
+    fn show_split_chat(&mut self, ctx: &egui::Context, #[cfg(feature = "tts")] stopped_talking: bool) {
+        let Some(idx) = self
+            .split_chat
+            .filter(|id| !self.detached.contains(id))
+            .and_then(|id| self.chats.iter().position(|c| c.id() == id))
+            .filter(|&idx| idx != self.selected_chat)
+        else {
+            return;
+        };
+
+        let chat = &mut self.chats[idx];
+        let mut close = false;
+        let mut focus = false;
+        let mut action = ChatAction::None;
+        let side = if self.settings.rtl_layout {
+            egui::panel::Side::Left
+        } else {
+            egui::panel::Side::Right
+        };
+        egui::SidePanel::new(side, "split_chat_panel")
+            .resizable(true)
+            .default_width(ctx.available_rect().width() / 2.0)
+            .frame(Frame::NONE)
+            .show(ctx, |ui| {
+                Frame::side_top_panel(ui.style()).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.strong(chat.title());
+                        ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                            close = ui
+                                .add(egui::Button::new("❌").frame(false))
+                                .on_hover_text(tr!("split-close"))
+                                .clicked();
+                            focus = ui
+                                .add(egui::Button::new("⮪").frame(false))
+                                .on_hover_text(tr!("split-swap"))
+                                .clicked();
+                        });
+                    });
+                });
+                action = chat.show_inside(
+                    ui,
+                    &self.settings,
+                    #[cfg(feature = "tts")]
+                    self.tts.clone(),
+                    #[cfg(feature = "tts")]
+                    stopped_talking,
+                    &mut self.commonmark_cache,
+                    false,
+                );
+            });
+        self.handle_chat_action(idx, action);
+
+        if close {
+            self.split_chat = None;
+        } else if focus {
+            self.split_chat = self.chats.get(self.selected_chat).map(|c| c.id());
+            self.select_chat(idx);
+        }
+    }
+
+    /// Shows every detached chat in its own viewport, or in a window when
+    /// the platform can't open more than one.
+    fn show_detached_chats(&mut self, ctx: &egui::Context, #[cfg(feature = "tts")] stopped_talking: bool) {
+        let mut closed = Vec::new();
+        for id in self.detached.clone() {
+            let Some(idx) = self.chats.iter().position(|c| c.id() == id) else {
+                closed.push(id);
+                continue;
+            };
+
+            let chat = &mut self.chats[idx];
+            let title = chat.title();
+            let settings = &self.settings;
+            let commonmark_cache = &mut self.commonmark_cache;
+            #[cfg(feature = "tts")]
+            let tts = self.tts.clone();
+
+            let (action, close) = ctx.show_viewport_immediate(
+                egui::ViewportId::from_hash_of(("chat_window", id)),
+                egui::ViewportBuilder::default()
+                    .with_title(format!("{title} - {}", crate::TITLE))
+                    .with_inner_size([640.0, 720.0]),
+                |ctx, class| {
+                    if class == egui::ViewportClass::Embedded {
+                        let mut open = true;
+                        let mut action = ChatAction::None;
+                        egui::Window::new(title)
+                            .id(egui::Id::new(("chat_window", id)))
+                            .open(&mut open)
+                            .default_size([480.0, 560.0])
+                            .show(ctx, |ui| {
+                                action = chat.show_inside(
+                                    ui,
+                                    settings,
+                                    #[cfg(feature = "tts")]
+                                    tts,
+                                    #[cfg(feature = "tts")]
+                                    stopped_talking,
+                                    commonmark_cache,
+                                    false,
+                                );
+                            });
+                        (action, !open)
+                    } else {
+                        let action = chat.show(
+                            ctx,
+                            settings,
+                            #[cfg(feature = "tts")]
+                            tts,
+                            #[cfg(feature = "tts")]
+                            stopped_talking,
+                            commonmark_cache,
+                            false,
+                        );
+                        (action, ctx.input(|i| i.viewport().close_requested()))
+                    }
+                },
+            );
+
+            self.handle_chat_action(idx, action);
+            if close {
+                closed.push(id);
+            }
+        }
+        self.detached.retain(|id| !closed.contains(id));
+    }
 
+    fn handle_chat_action(&mut self, chat_idx: usize, action: ChatAction) {
         match action {
             ChatAction::None => (),
+            ChatAction::IgnoreWord(word) => {
+                if !self.settings.spellcheck.ignored.contains(&word) {
+                    self.settings.spellcheck.ignored.push(word);
+                }
+            }
+            ChatAction::RelaxSafety(categories) => {
+                if let Some(chat) = self.chats.get_mut(chat_idx) {
+                    for category in categories {
+                        chat.model_picker
+                            .relax_safety(&mut self.settings.safety, category);
+                    }
+                    chat.regenerate_last(&self.settings);
+                }
+            }
+            ChatAction::EditImage(image) => self.image_editor.start_from(image),
+            #[cfg(feature = "screenshots")]
+            ChatAction::CaptureScreen { id } => self.screenshot.start(id),
+            ChatAction::Quarantine(source) => {
+                self.settings.injection.toggle(source.clone());
+                let quarantined = self.settings.injection.quarantined.contains(&source);
+                let citations = self
+                    .chats
+                    .iter_mut()
+                    .flat_map(|chat| chat.messages.iter_mut())
+                    .flat_map(|message| message.sources.iter_mut())
+                    .filter(|citation| citation.source == source);
+                for citation in citations {
+                    citation.quarantined = quarantined;
+                }
+            }
             ChatAction::PickFiles { id } => {
                 let handle = self.flower.handle();
                 tokio::spawn(async move {
@@ -585,37 +1822,383 @@ impl Sessions {
                     pick_files(id, &handle).await;
                 });
             }
+            ChatAction::AttachFolder { id } => {
+                if let Some(chat) = self.chats.get(chat_idx) {
+                    self.folder_bundler.start(id, chat.model_picker.selected);
+                }
+            }
+            ChatAction::AttachUrl { id, url } => {
+                let handle = self.flower.handle();
+                let proxy = self.settings.proxy_path.clone();
+                tokio::spawn(async move {
+                    handle.activate();
+                    let result = crate::file_handler::download(&url, proxy.as_deref())
+                        .await
+                        .map_err(|e| {
+                            log::error!("failed to download {url}: {e}");
+                            e.to_string()
+                        });
+                    handle.success(BackendResponse::Download {
+                        chat_id: id,
+                        result,
+                    });
+                });
+            }
+            ChatAction::PreviewRequest { id } => {
+                let Some(chat) = self.chats.get(chat_idx) else {
+                    return;
+                };
+                self.request_preview.start(id);
+                let handle = self.flower.handle();
+                let settings = self.settings.clone();
+                let draft = chat.draft_request(&settings);
+                tokio::spawn(async move {
+                    handle.activate();
+                    let preview = crate::request_preview::build(settings, draft)
+                        .await
+                        .map_err(|e| {
+                            log::error!("failed to assemble the request preview: {e}");
+                            e.to_string()
+                        });
+                    handle.success(BackendResponse::RequestPreview(Box::new(preview)));
+                });
+            }
+            ChatAction::ImprovePrompt { id } => {
+                let Some(chat) = self.chats.get(chat_idx) else {
+                    return;
+                };
+                let handle = self.flower.handle();
+                let settings = self.settings.clone();
+                let draft = chat.chatbox.clone();
+                tokio::spawn(async move {
+                    handle.activate();
+                    let suggestion =
+                        crate::prompt_improver::request_improvement(settings, draft.clone())
+                            .await
+                            .map_err(|e| {
+                                log::error!("failed to improve prompt: {e}");
+                                e.to_string()
+                            });
+                    handle.success(BackendResponse::PromptSuggestion {
+                        chat_id: id,
+                        draft,
+                        suggestion,
+                    });
+                });
+            }
+            ChatAction::Verify { id, message } => {
+                let Some(chat) = self.chats.get(chat_idx) else {
+                    return;
+                };
+                let Some(answer) = chat.messages.get(message) else {
+                    return;
+                };
+                let question = chat.messages[..message]
+                    .iter()
+                    .rev()
+                    .find(|m| m.is_user())
+                    .map(|m| m.content.clone())
+                    .unwrap_or_default();
+                let (model, time, answer) = (answer.model, answer.time, answer.content.clone());
+                let handle = self.flower.handle();
+                let settings = self.settings.clone();
+                tokio::spawn(async move {
+                    handle.activate();
+                    let result = crate::verify::verify(settings, model, question, answer)
+                        .await
+                        .map(Box::new)
+                        .map_err(|e| {
+                            log::error!("failed to verify the answer: {e}");
+                            e.to_string()
+                        });
+                    handle.success(BackendResponse::Verification {
+                        chat_id: id,
+                        time,
+                        result,
+                    });
+                });
+            }
+            ChatAction::CacheContext { id } => {
+                let Some(chat) = self.chats.get_mut(chat_idx) else {
+                    return;
+                };
+                chat.is_caching = true;
+                let request = chat.cache_request(&self.settings);
+                let handle = self.flower.handle();
+                let settings = self.settings.clone();
+                tokio::spawn(async move {
+                    handle.activate();
+                    let result = crate::context_cache::create(settings, request)
+                        .await
+                        .map(Box::new)
+                        .map_err(|e| {
+                            log::error!("failed to cache the chat: {e}");
+                            e.to_string()
+                        });
+                    handle.success(BackendResponse::ContextCache {
+                        chat_id: id,
+                        result,
+                    });
+                });
+            }
+            ChatAction::Summarize { id } => {
+                let Some(chat) = self.chats.get_mut(chat_idx) else {
+                    return;
+                };
+                let Some(split) = crate::summarize::split(&chat.messages) else {
+                    return;
+                };
+                chat.is_summarizing = true;
+                let transcript = crate::summarize::transcript(&chat.messages[..split]);
+                let kept_from = chat.messages[split].time;
+                let model = self
+                    .settings
+                    .summarize
+                    .model
+                    .unwrap_or(chat.model_picker.selected);
+                let handle = self.flower.handle();
+                let settings = self.settings.clone();
+                tokio::spawn(async move {
+                    handle.activate();
+                    let result = crate::summarize::summarize(settings, model, transcript)
+                        .await
+                        .map_err(|e| {
+                            log::error!("failed to summarize the chat: {e}");
+                            e.to_string()
+                        });
+                    handle.success(BackendResponse::Summary {
+                        chat_id: id,
+                        kept_from,
+                        result,
+                    });
+                });
+            }
+            ChatAction::DropCache(name) => {
+                let settings = self.settings.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = crate::context_cache::delete(&settings, &name).await {
+                        log::error!("failed to delete the context cache {name}: {e}");
+                    }
+                });
+            }
+            ChatAction::RunCommand { id, message, call } => {
+                let Some(call_message) = self
+                    .chats
+                    .get_mut(chat_idx)
+                    .and_then(|c| c.messages.get_mut(message))
+                else {
+                    return;
+                };
+                let time = call_message.time;
+                let Some(function_call) = call_message.calls.get_mut(call) else {
+                    return;
+                };
+                let command =
+                    crate::functions::command_for(&self.settings.functions, &function_call.name)
+                        .filter(|_| self.settings.run_commands);
+                let Some(command) = command else {
+                    // plugin tools run without asking, in their sandbox
+                    if crate::plugins::tool_plugin(&function_call.name).is_none() {
+                        return;
+                    }
+                    function_call.running = true;
+                    let (name, args) = (function_call.name.clone(), function_call.args.clone());
+                    let handle = self.flower.handle();
+                    tokio::spawn(async move {
+                        handle.activate();
+                        let result = crate::plugins::call_tool(name, args)
+                            .await
+                            .map(|output| output.to_string())
+                            .map_err(|e| {
+                                log::error!("plugin tool failed: {e}");
+                                e.to_string()
+                            });
+                        handle.success(BackendResponse::CommandOutput {
+                            chat_id: id,
+                            time,
+                            call,
+                            result,
+                        });
+                    });
+                    return;
+                };
+                self.pending_command = Some(PendingCommand {
+                    chat_id: id,
+                    time,
+                    call,
+                    name: function_call.name.clone(),
+                    command: command.to_owned(),
+                    args: function_call.args.clone(),
+                });
+            }
+            ChatAction::Reupload { id, message, path } => {
+                if self.settings.auth_method != AuthMethod::ApiKey
+                    || !self.settings.public_file_upload
+                {
+                    self.toasts
+                        .add(Toast::error(tr!("upload-reupload-unavailable")));
+                    return;
+                }
+                let Some(message) = self
+                    .chats
+                    .get_mut(chat_idx)
+                    .and_then(|c| c.messages.get_mut(message))
+                else {
+                    return;
+                };
+                let time = message.time;
+                let Some(attachment) = message.files.iter_mut().find(|a| a.path == path) else {
+                    return;
+                };
+                attachment.state = AttachmentState::Uploading { sent: 0, total: 0 };
+                attachment.renew_cancel();
+                let attachment = attachment.clone();
+                let model = message.model;
+                let handle = self.flower.handle();
+                let connection = self.settings.connection();
+                tokio::spawn(async move {
+                    handle.activate();
+                    let result = crate::file_handler::reupload(&connection, model, &attachment)
+                        .await
+                        .map_err(|e| {
+                            log::error!("failed to upload {} again: {e}", path.display());
+                            e.to_string()
+                        });
+                    handle.success(BackendResponse::Reupload {
+                        chat_id: id,
+                        time,
+                        path,
+                        result,
+                    });
+                });
+            }
+        }
+    }
+
+    /// Fetches the models for the current auth method, the built-in ones are offered
+    /// until they arrive.
+    fn list_models(&self) {
+        let handle = self.flower.handle();
+        let settings = self.settings.clone();
+        tokio::spawn(async move {
+            handle.activate();
+            let result = crate::models::fetch(&settings)
+                .await
+                .map_err(|e| e.to_string());
+            handle.success(BackendResponse::Models {
+                auth_method: settings.auth_method,
+                result,
+            });
+        });
+    }
+
+    fn spawn_ab_run(&self, request: crate::prompt_library::AbRequest) {
+        let handle = self.flower.handle();
+        let connection = self.settings.connection();
+        tokio::spawn(async move {
+            handle.activate();
+            let run = |(prompt, model): (String, crate::widgets::GeminiModel)| {
+                let connection = &connection;
+                let input = &request.input;
+                async move {
+                    geminid_core::pipeline::generate_once(connection, model, Some(&prompt), input)
+                        .await
+                        .map_err(|e| {
+                            log::error!("A/B run failed: {e}");
+                            e.to_string()
+                        })
+                }
+            };
+            let (a, b) = tokio::join!(run(request.a.clone()), run(request.b.clone()));
+            handle.success(BackendResponse::AbOutputs {
+                prompt_id: request.prompt_id,
+                a,
+                b,
+            });
+        });
+    }
+
+    /// Embeds the next batch of unindexed messages while semantic search is in use.
+    fn update_embedding_index(&mut self) {
+        if self.embeddings.is_none() {
+            let store = EmbeddingStore::load();
+            // the index remembers which embedder was picked last time
+            self.search.embedder = store.embedder;
+            self.embeddings = Some(store);
         }
+        let store = self.embeddings.as_mut().expect("loaded above");
+        store.set_embedder(self.search.embedder);
+        let (batch, progress) = store.missing(&self.chats, crate::semantic::BATCH_SIZE);
+        self.search.index_progress = progress;
+        if self.is_indexing || batch.is_empty() {
+            return;
+        }
+
+        self.is_indexing = true;
+        let settings = self.settings.clone();
+        let embedder = store.embedder;
+        let handle = self.flower.handle();
+        tokio::spawn(async move {
+            handle.activate();
+            let (keys, texts): (Vec<u64>, Vec<String>) = batch.into_iter().unzip();
+            let result = crate::semantic::embed_with(
+                &settings,
+                embedder,
+                &texts,
+                gemini_rust::TaskType::RetrievalDocument,
+            )
+            .await
+            .map(|vectors| keys.into_iter().zip(vectors).collect())
+            .map_err(|e| {
+                log::error!("failed to embed messages: {e}");
+                e.to_string()
+            });
+            handle.success(BackendResponse::Embeddings { embedder, result });
+        });
+    }
+
+    fn spawn_semantic_query(&self, query: String) {
+        let settings = self.settings.clone();
+        let embedder = self.search.embedder;
+        let handle = self.flower.handle();
+        tokio::spawn(async move {
+            handle.activate();
+            let result = crate::semantic::embed_with(
+                &settings,
+                embedder,
+                &[query],
+                gemini_rust::TaskType::RetrievalQuery,
+            )
+            .await
+            .map(|mut vectors| vectors.pop().unwrap_or_default())
+            .map_err(|e| {
+                log::error!("failed to embed search query: {e}");
+                e.to_string()
+            });
+            handle.success(BackendResponse::QueryEmbedding { embedder, result });
+        });
     }
 
     fn show_remove_chat_modal_inner(&mut self, ui: &mut egui::Ui, modal: &Modal) {
-        modal.title(ui, "Remove Chat");
+        modal.title(ui, tr!("remove-chat-title"));
         modal.frame(ui, |ui| {
+            let shortcut = self.settings.keymap.hint(ui.ctx(), Command::ReopenClosed);
             modal.body_and_icon(
                 ui,
-                "Do you really want to remove this chat? \
-                You cannot undo this action later.\n\
-                Hold Shift to surpass this warning.",
+                tr!("remove-chat-confirm", shortcut = shortcut),
                 Icon::Warning,
             );
             modal.buttons(ui, |ui| {
-                if modal.button(ui, "No").clicked() {
+                if modal.button(ui, tr!("no")).clicked() {
                     modal.close();
                 }
                 let summary = self
                     .chats
                     .get(self.chat_marked_for_deletion)
-                    .map(|c| {
-                        if c.summary.is_empty() {
-                            "New Chat"
-                        } else {
-                            c.summary.as_str()
-                        }
-                    })
-                    .unwrap_or("New Chat");
+                    .map_or_else(|| tr!("new-chat"), Chat::title);
                 if modal
-                    .caution_button(ui, "Yes")
-                    .on_hover_text(format!("Remove chat \"{summary}\"",))
+                    .caution_button(ui, tr!("yes"))
+                    .on_hover_text(tr!("remove-chat-named", name = summary))
                     .clicked()
                 {
                     modal.close();
@@ -625,12 +2208,231 @@ impl Sessions {
         });
     }
 
+    fn show_command_modal_inner(&mut self, ui: &mut egui::Ui, modal: &Modal) {
+        let Some(pending) = &self.pending_command else {
+            return;
+        };
+        let args = serde_json::to_string_pretty(&pending.args).unwrap_or_default();
+        modal.title(ui, tr!("functions-confirm-title"));
+        modal.frame(ui, |ui| {
+            modal.body_and_icon(
+                ui,
+                tr!("functions-confirm", name = pending.name.as_str()),
+                Icon::Warning,
+            );
+            egui::Frame::group(ui.style()).show(ui, |ui| {
+                ui.label(egui::RichText::new(&pending.command).monospace());
+                ui.label(egui::RichText::new(args).monospace().small());
+            });
+            modal.buttons(ui, |ui| {
+                if modal.button(ui, tr!("cancel")).clicked() {
+                    modal.close();
+                    self.pending_command = None;
+                }
+                if modal
+                    .caution_button(ui, tr!("functions-confirm-run"))
+                    .clicked()
+                {
+                    modal.close();
+                    if let Some(pending) = self.pending_command.take() {
+                        self.run_pending_command(pending);
+                    }
+                }
+            });
+        });
+    }
+
+    fn run_pending_command(&mut self, pending: PendingCommand) {
+        let call = self
+            .chats
+            .iter_mut()
+            .find(|c| c.id() == pending.chat_id)
+            .and_then(|c| c.messages.iter_mut().find(|m| m.time == pending.time))
+            .and_then(|m| m.calls.get_mut(pending.call));
+        let Some(call) = call else {
+            return;
+        };
+        call.running = true;
+        let handle = self.flower.handle();
+        tokio::spawn(async move {
+            handle.activate();
+            let PendingCommand {
+                command,
+                name,
+                args,
+                ..
+            } = pending;
+            let result = crate::functions::run_command(command, name, args)
+                .await
+                .map_err(|e| {
+                    log::error!("function command failed: {e}");
+                    e.to_string()
+                });
+            handle.success(BackendResponse::CommandOutput {
+                chat_id: pending.chat_id,
+                time: pending.time,
+                call: pending.call,
+                result,
+            });
+        });
+    }
+
+    fn show_bulk_modal_inner(&mut self, ui: &mut egui::Ui, modal: &Modal) {
+        let Some(action) = self.bulk_action else {
+            return;
+        };
+        let selected: Vec<&Chat> = self
+            .chats
+            .iter()
+            .filter(|c| self.selection.contains(&c.id()))
+            .collect();
+        let messages: usize = selected.iter().map(|c| c.messages.len()).sum();
+
+        let chats = tr!("chat-count", count = selected.len());
+        let messages = tr!("message-count", count = messages);
+        let (title, summary) = match action {
+            BulkAction::Delete => (
+                tr!("bulk-delete-title"),
+                tr!("bulk-delete-summary", chats = chats, messages = messages),
+            ),
+            BulkAction::Archive(true) => (
+                tr!("bulk-archive-title"),
+                tr!("bulk-archive-summary", chats = chats, messages = messages),
+            ),
+            BulkAction::Archive(false) => (
+                tr!("bulk-unarchive-title"),
+                tr!("bulk-unarchive-summary", chats = chats, messages = messages),
+            ),
+            BulkAction::Tag(id) => {
+                let tag = self
+                    .tags
+                    .iter()
+                    .find(|t| t.id == id)
+                    .map_or("?", |t| t.name.as_str());
+                let summary = if selected.iter().all(|c| c.tags.contains(&id)) {
+                    tr!("bulk-untag-summary", tag = tag, chats = chats, messages = messages)
+                } else {
+                    tr!("bulk-tag-summary", tag = tag, chats = chats, messages = messages)
+                };
+                (tr!("bulk-tag-title"), summary)
+            }
+            BulkAction::MoveToFolder(folder) => {
+                let folder = crate::folders::find(&self.folders, folder)
+                    .map_or_else(|| tr!("top-level"), |f| f.name.clone());
+                (
+                    tr!("bulk-move-title"),
+                    tr!("bulk-move-summary", folder = folder, chats = chats, messages = messages),
+                )
+            }
+            BulkAction::Export => (
+                tr!("bulk-export-title"),
+                tr!("bulk-export-summary", chats = chats, messages = messages),
+            ),
+        };
+
+        modal.title(ui, title);
+        modal.frame(ui, |ui| {
+            let icon = if matches!(action, BulkAction::Delete) {
+                Icon::Warning
+            } else {
+                Icon::Info
+            };
+            modal.body_and_icon(ui, summary, icon);
+            modal.buttons(ui, |ui| {
+                if modal.button(ui, tr!("cancel")).clicked() {
+                    modal.close();
+                    self.bulk_action = None;
+                }
+                let confirm = if matches!(action, BulkAction::Delete) {
+                    modal.caution_button(ui, tr!("delete"))
+                } else {
+                    modal.suggested_button(ui, tr!("confirm"))
+                };
+                if confirm.clicked() {
+                    modal.close();
+                    self.bulk_action = None;
+                    self.apply_bulk_action(action);
+                }
+            });
+        });
+    }
+
+    fn apply_bulk_action(&mut self, action: BulkAction) {
+        let ids = self.selection.clone();
+        let selected = |c: &&mut Chat| ids.contains(&c.id());
+        match action {
+            BulkAction::Delete => {
+                self.remove_chats(&ids);
+                self.selection.clear();
+            }
+            BulkAction::Archive(archived) => {
+                for chat in self.chats.iter_mut().filter(selected) {
+                    chat.archived = archived;
+                }
+            }
+            BulkAction::Tag(id) => {
+                let all_tagged = self
+                    .chats
+                    .iter()
+                    .filter(|c| ids.contains(&c.id()))
+                    .all(|c| c.tags.contains(&id));
+                for chat in self.chats.iter_mut().filter(selected) {
+                    if all_tagged {
+                        chat.tags.retain(|&t| t != id);
+                    } else if !chat.tags.contains(&id) {
+                        chat.tags.push(id);
+                    }
+                }
+            }
+            BulkAction::MoveToFolder(folder) => {
+                for chat in self.chats.iter_mut().filter(selected) {
+                    chat.folder = folder;
+                }
+            }
+            BulkAction::Export => self.export_chats(&ids),
+        }
+    }
+
+    fn export_chats(&self, ids: &[usize]) {
+        let chats: Vec<&Chat> = self.chats.iter().filter(|c| ids.contains(&c.id())).collect();
+        let json = match serde_json::to_string_pretty(&chats) {
+            Ok(json) => json,
+            Err(e) => {
+                log::error!("failed to serialize chats: {e}");
+                return;
+            }
+        };
+        let count = chats.len();
+        let task = rfd::AsyncFileDialog::new()
+            .add_filter("JSON file", &["json"])
+            .set_file_name("chats.json")
+            .save_file();
+        let handle = self.flower.handle();
+        tokio::spawn(async move {
+            let toast = match task.await {
+                None => Toast::info(tr!("export-cancelled")),
+                Some(file) => match std::fs::write(file.path(), json) {
+                    Ok(()) => Toast::success(format!(
+                        "Exported {count} chat(s) to {}",
+                        file.file_name()
+                    )),
+                    Err(e) => {
+                        log::error!("failed to export chats: {e}");
+                        Toast::error(e.to_string())
+                    }
+                },
+            };
+            handle.activate();
+            handle.success(BackendResponse::Toast(toast));
+        });
+    }
+
     fn show_chat_edit_panel(&mut self, ui: &mut egui::Ui, chat_idx: usize) {
         ui.horizontal(|ui| {
             if let Some(chat) = self.chats.get_mut(chat_idx) {
                 ui.add(
                     egui::TextEdit::singleline(&mut chat.summary)
-                        .hint_text("New Chat")
+                        .hint_text(tr!("new-chat"))
                         .desired_width(f32::INFINITY),
                 );
             }
@@ -642,31 +2444,105 @@ impl Sessions {
                             .fill(Color32::TRANSPARENT)
                             .frame(false),
                     )
-                    .on_hover_text("Close")
+                    .on_hover_text(tr!("close"))
                     .clicked()
                 {
-                    self.edited_chat = None;
+                    self.edited_chat = None;
+                }
+            });
+        });
+
+        egui::CollapsingHeader::new(tr!("chat-edit-persona"))
+            .default_open(true)
+            .show(ui, |ui| {
+                let Some(chat) = self.chats.get_mut(chat_idx) else {
+                    return;
+                };
+
+                if let Some(new) = crate::persona::picker(ui, &self.settings.personas, chat.persona)
+                {
+                    chat.set_persona(crate::persona::find(&self.settings.personas, new));
                 }
             });
-        });
 
-        egui::CollapsingHeader::new("Model")
+        egui::CollapsingHeader::new(tr!("chat-edit-knowledge"))
+            .default_open(true)
+            .show(ui, |ui| {
+                let Some(chat) = self.chats.get_mut(chat_idx) else {
+                    return;
+                };
+                crate::knowledge::picker(ui, &self.knowledge.collections, &mut chat.knowledge);
+                ui.weak(tr!("chat-edit-knowledge-help"));
+
+                ui.add_space(4.0);
+                ui.label(tr!("chat-edit-remember"));
+                ui.horizontal(|ui| {
+                    let mut target = self
+                        .memory_target
+                        .or(chat.knowledge)
+                        .or_else(|| chat.project.as_ref().and_then(|p| p.knowledge));
+                    ui.push_id("memory_target", |ui| {
+                        crate::knowledge::picker(ui, &self.knowledge.collections, &mut target);
+                    });
+                    self.memory_target = target;
+                    let busy = self.knowledge.is_remembering();
+                    if ui
+                        .add_enabled(
+                            !busy && target.is_some() && !chat.messages.is_empty(),
+                            egui::Button::new(tr!("chat-edit-remember-button")),
+                        )
+                        .on_hover_text(tr!("chat-edit-remember-help"))
+                        .clicked()
+                    {
+                        if let Some(id) = target {
+                            self.knowledge.remember(chat, id, &self.settings, ui.ctx());
+                        }
+                    }
+                    if busy {
+                        ui.spinner();
+                    }
+                });
+            });
+
+        egui::CollapsingHeader::new(tr!("chat-edit-tags"))
+            .default_open(true)
+            .show(ui, |ui| {
+                if let Some(chat) = self.chats.get_mut(chat_idx) {
+                    if let Some(id) = crate::tags::toggle_row(ui, &self.tags, &chat.tags) {
+                        crate::tags::toggle(&mut chat.tags, id);
+                    }
+                }
+                ui.add_space(4.0);
+                ui.collapsing(tr!("chat-edit-manage-tags"), |ui| {
+                    if let Some(removed) =
+                        crate::tags::show_tag_manager(ui, &mut self.tags, &mut self.new_tag)
+                    {
+                        for chat in self.chats.iter_mut() {
+                            chat.tags.retain(|&t| t != removed);
+                        }
+                        self.tag_filter.retain(|&t| t != removed);
+                    }
+                });
+            });
+
+        egui::CollapsingHeader::new(tr!("chat-edit-model"))
             .default_open(true)
             .show(ui, |ui| {
                 let Some(chat) = self.chats.get_mut(chat_idx) else {
                     return;
                 };
 
-                chat.model_picker.show(ui, &mut |_| {});
+                chat.model_picker
+                    .show(ui, &mut self.settings.prompt_library, &mut |_| {});
 
                 if self.settings.inherit_chat_picker {
                     self.settings.model_picker.selected = chat.model_picker.selected.clone();
                 }
             });
-        ui.collapsing("Export", |ui| {
-            ui.label("Export chat history to a file");
+        ui.collapsing(tr!("export"), |ui| {
+            ui.label(tr!("chat-edit-export-label"));
             let format = self.chat_export_format;
-            egui::ComboBox::from_label("Export Format")
+            egui::ComboBox::from_label(tr!("chat-edit-export-format"))
                 .selected_text(format.to_string())
                 .show_ui(ui, |ui| {
                     for format in ChatExportFormat::ALL {
@@ -677,7 +2553,7 @@ impl Sessions {
                         );
                     }
                 });
-            if ui.button("Save As…").clicked() {
+            if ui.button(tr!("save-as")).clicked() {
                 let task = rfd::AsyncFileDialog::new()
                     .add_filter(format!("{format:?} file"), format.extensions())
                     .save_file();
@@ -702,13 +2578,64 @@ impl Sessions {
                     };
                 });
             }
+            for (plugin, exporter) in crate::plugins::exporters() {
+                if !ui
+                    .button(tr!("export-with-plugin", name = exporter.name.as_str()))
+                    .clicked()
+                {
+                    continue;
+                }
+                let Some(chat) = self.chats.get(chat_idx) else {
+                    return;
+                };
+                let messages = chat.messages.clone();
+                let task = rfd::AsyncFileDialog::new()
+                    .add_filter(&exporter.name, &[exporter.extension.as_str()])
+                    .save_file();
+                tokio::spawn(async move {
+                    let content =
+                        match crate::plugins::export(plugin, exporter.name, messages).await {
+                            Ok(content) => content,
+                            Err(e) => {
+                                log::error!("failed to export messages: {e}");
+                                return;
+                            }
+                        };
+                    let Some(file) = task.await else {
+                        return;
+                    };
+                    if let Err(e) = std::fs::write(file.path(), content) {
+                        log::error!("failed to export messages: {e}");
+                    }
+                });
+            }
         });
     }
 
+    fn show_folder_edit_panel(&mut self, ui: &mut egui::Ui, folder_id: u64) {
+        let Some(folder) = self.folders.iter_mut().find(|f| f.id == folder_id) else {
+            self.edited_folder = None;
+            return;
+        };
+        if crate::folders::show_editor(
+            ui,
+            folder,
+            &self.settings.model_picker,
+            &self.knowledge.collections,
+            &mut self.settings.prompt_library,
+        ) {
+            self.edited_folder = None;
+        }
+    }
+
     fn show_left_panel(&mut self, ui: &mut egui::Ui) {
         ui.add_space(ui.style().spacing.window_margin.top as _);
         ui.horizontal(|ui| {
-            ui.selectable_value(&mut self.tab, SessionTab::Chats, "Chats");
+            ui.selectable_value(&mut self.tab, SessionTab::Chats, tr!("tab-chats"));
+            ui.selectable_value(&mut self.tab, SessionTab::Prompts, tr!("tab-prompts"));
+            ui.selectable_value(&mut self.tab, SessionTab::Schedules, tr!("tab-schedules"));
+            ui.selectable_value(&mut self.tab, SessionTab::Knowledge, tr!("tab-knowledge"));
+            ui.selectable_value(&mut self.tab, SessionTab::Videos, tr!("tab-videos"));
         });
 
         ui.add_space(8.0);
@@ -716,82 +2643,623 @@ impl Sessions {
         match self.tab {
             SessionTab::Chats => {
                 let modal = Modal::new(ui.ctx(), "remove_chat_modal");
-                self.show_chats(ui, &modal);
+                let bulk_modal = Modal::new(ui.ctx(), "bulk_action_modal");
+                self.show_chats(ui, &modal, &bulk_modal);
                 modal.show(|ui| {
                     self.show_remove_chat_modal_inner(ui, &modal);
                 });
+                bulk_modal.show(|ui| {
+                    self.show_bulk_modal_inner(ui, &bulk_modal);
+                });
+            }
+            SessionTab::Prompts => {
+                ui.vertical_centered_justified(|ui| {
+                    if ui
+                        .add(egui::Button::new(tr!("batch-open")).min_size(egui::vec2(0.0, 24.0)))
+                        .clicked()
+                    {
+                        self.batch.open = true;
+                    }
+                    if ui
+                        .add(
+                            egui::Button::new(tr!("compare-open-window"))
+                                .min_size(egui::vec2(0.0, 24.0)),
+                        )
+                        .clicked()
+                    {
+                        self.compare.open = true;
+                    }
+                    if ui
+                        .add(
+                            egui::Button::new(tr!("model-compare-open"))
+                                .min_size(egui::vec2(0.0, 24.0)),
+                        )
+                        .clicked()
+                    {
+                        self.model_compare.open = true;
+                    }
+                    if ui
+                        .add(
+                            egui::Button::new(tr!("image-edit-open-window"))
+                                .min_size(egui::vec2(0.0, 24.0)),
+                        )
+                        .clicked()
+                    {
+                        self.image_editor.open = true;
+                    }
+                });
+                self.settings.prompt_library.show_sidebar(ui);
+            }
+            SessionTab::Schedules => {
+                self.scheduler.show_sidebar(ui);
+            }
+            SessionTab::Knowledge => {
+                ui.vertical_centered_justified(|ui| {
+                    if ui
+                        .add(
+                            egui::Button::new(tr!("embedding-open"))
+                                .min_size(egui::vec2(0.0, 24.0)),
+                        )
+                        .clicked()
+                    {
+                        self.embedding_playground.open = true;
+                    }
+                });
+                if let Some(removed) = self.knowledge.show_sidebar(ui) {
+                    for chat in &mut self.chats {
+                        if chat.knowledge == Some(removed) {
+                            chat.knowledge = None;
+                        }
+                    }
+                }
+            }
+            SessionTab::Videos => {
+                self.videos.show_sidebar(ui);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn model_picker(&self) -> &ModelPicker {
+        &self.settings.model_picker
+    }
+
+    fn show_onboarding(&mut self, ctx: &egui::Context) {
+        match self.onboarding.show(ctx, &mut self.settings) {
+            OnboardingAction::None => (),
+            OnboardingAction::ValidateKey => {
+                let api_key = self.settings.api_key.trim().to_owned();
+                let proxy = self.settings.proxy_path.clone();
+                let handle = self.flower.handle();
+                tokio::spawn(async move {
+                    handle.activate();
+                    let result = crate::onboarding::validate_api_key(api_key, proxy)
+                        .await
+                        .map_err(|e| e.to_string());
+                    handle.success(BackendResponse::KeyValidation(result));
+                });
+            }
+            OnboardingAction::LoginGoogle => {
+                let handle = self.flower.handle();
+                tokio::spawn(async move {
+                    handle.activate();
+                    login_google(&handle).await;
+                });
+            }
+            OnboardingAction::Finish => {
+                self.onboarding_done = true;
+                self.toasts.add(Toast::info(tr!("onboarding-finished")));
+            }
+        }
+    }
+
+    fn poll_backend_flower(&mut self, modal: &Modal) {
+        self.flower.extract(|()| ()).finalize(|resp| {
+            match resp {
+                Ok(BackendResponse::Ignore) => (),
+                Ok(BackendResponse::Toast(toast)) => {
+                    self.toasts.add(toast);
+                }
+                Ok(BackendResponse::Files { id, files }) => {
+                    if let Some(chat) = self.chats.iter_mut().find(|c| c.id() == id) {
+                        log::debug!("adding {} file(s) to chat {}", files.len(), id);
+                        chat.files
+                            .extend(files.into_iter().map(Attachment::from_path));
+                    }
+                }
+                Ok(BackendResponse::Download { chat_id, result }) => {
+                    if let Some(chat) = self.chats.iter_mut().find(|c| c.id() == chat_id) {
+                        chat.is_downloading = false;
+                        match result {
+                            Ok(path) => {
+                                chat.attach_url = None;
+                                chat.files.push(Attachment::from_path(path));
+                            }
+                            Err(e) => {
+                                self.toasts
+                                    .add(Toast::error(tr!("attach-url-failed", error = e)));
+                            }
+                        }
+                    }
+                }
+                Ok(BackendResponse::Settings(settings)) => {
+                    self.settings = *settings;
+                }
+                Ok(BackendResponse::Snippets(snippets)) => {
+                    let count = snippets.len();
+                    // imported snippets replace existing ones with the same shortcut
+                    self.settings
+                        .snippets
+                        .retain(|s| !snippets.iter().any(|n| n.trigger == s.trigger));
+                    self.settings.snippets.extend(snippets);
+                    self.toasts
+                        .add(Toast::success(format!("Imported {count} snippet(s)")));
+                }
+                Ok(BackendResponse::TokenCount { chat_id, count }) => {
+                    if let Some(chat) = self.chats.iter_mut().find(|c| c.id() == chat_id) {
+                        chat.token_count = Some(count);
+                    }
+                }
+                Ok(BackendResponse::AuthResult { token, projects }) => {
+                    self.onboarding.logging_in = false;
+                    self.settings.oauth_token = token;
+                    self.settings.available_projects = projects;
+                    if self.settings.project_id.is_empty()
+                        && !self.settings.available_projects.is_empty()
+                    {
+                        self.settings.project_id = self.settings.available_projects[0].clone();
+                    }
+                    self.toasts.add(Toast::success(tr!("settings-login-success")));
+                }
+                Ok(BackendResponse::AbOutputs { prompt_id, a, b }) => {
+                    self.settings
+                        .prompt_library
+                        .set_ab_outputs(prompt_id, a, b);
+                }
+                Ok(BackendResponse::Embeddings { embedder, result }) => {
+                    self.is_indexing = false;
+                    match result {
+                        Ok(vectors) => {
+                            // vectors of an embedder switched away from don't belong in the index
+                            if let Some(store) =
+                                self.embeddings.as_mut().filter(|s| s.embedder == embedder)
+                            {
+                                store.insert(vectors);
+                                store.prune(&self.chats);
+                                store.save();
+                            }
+                        }
+                        // the error is already surfaced by the logger
+                        Err(_) => self.search.semantic_failed(),
+                    }
+                }
+                Ok(BackendResponse::QueryEmbedding { embedder, result }) => match result {
+                    Ok(query) => {
+                        let ranked = self
+                            .embeddings
+                            .as_ref()
+                            .filter(|store| store.embedder == embedder)
+                            .map(|store| store.rank(&self.chats, &query))
+                            .unwrap_or_default();
+                        self.search.set_semantic_results(ranked);
+                    }
+                    Err(_) => self.search.semantic_failed(),
+                },
+                Ok(BackendResponse::KeyValidation(result)) => {
+                    self.onboarding.key_check = match result {
+                        Ok(()) => {
+                            // list what the new key can use
+                            self.models_listed_for = None;
+                            KeyCheck::Valid
+                        }
+                        Err(e) => KeyCheck::Invalid(e),
+                    };
+                }
+                Ok(BackendResponse::Models {
+                    auth_method,
+                    result,
+                }) => {
+                    if auth_method != self.settings.auth_method {
+                        return;
+                    }
+                    match result {
+                        Ok(models) => crate::models::set_listed(models),
+                        // offline, the built-in models stay
+                        Err(e) => log::info!("couldn't list the models: {e}"),
+                    }
+                }
+                Ok(BackendResponse::PromptSuggestion {
+                    chat_id,
+                    draft,
+                    suggestion,
+                }) => {
+                    let Some(chat) = self.chats.iter_mut().find(|c| c.id() == chat_id) else {
+                        return;
+                    };
+                    chat.is_improving_prompt = false;
+                    match suggestion {
+                        Ok(suggestion) => {
+                            chat.prompt_improvement = Some(
+                                crate::prompt_improver::PromptImprovement::new(&draft, &suggestion),
+                            );
+                        }
+                        Err(e) => {
+                            self.toasts
+                                .add(Toast::error(tr!("improve-prompt-failed", error = e)));
+                        }
+                    }
+                }
+                Ok(BackendResponse::Verification {
+                    chat_id,
+                    time,
+                    result,
+                }) => {
+                    let message = self
+                        .chats
+                        .iter_mut()
+                        .find(|c| c.id() == chat_id)
+                        .and_then(|c| c.messages.iter_mut().find(|m| m.time == time));
+                    if let Some(message) = message {
+                        message.is_verifying = false;
+                        if let Ok(verification) = result {
+                            message.verification = Some(*verification);
+                        }
+                    }
+                }
+                Ok(BackendResponse::Reupload {
+                    chat_id,
+                    time,
+                    path,
+                    result,
+                }) => {
+                    let attachment = self
+                        .chats
+                        .iter_mut()
+                        .find(|c| c.id() == chat_id)
+                        .and_then(|c| c.messages.iter_mut().find(|m| m.time == time))
+                        .and_then(|m| m.files.iter_mut().find(|a| a.path == path));
+                    if let Some(attachment) = attachment {
+                        attachment.state = match result {
+                            Ok(Some(file)) => AttachmentState::Uploaded(file),
+                            Ok(None) => AttachmentState::Local,
+                            Err(e) => AttachmentState::Failed(e),
+                        };
+                    }
+                }
+                Ok(BackendResponse::ContextCache { chat_id, result }) => {
+                    let Some(chat) = self.chats.iter_mut().find(|c| c.id() == chat_id) else {
+                        return;
+                    };
+                    chat.is_caching = false;
+                    match result {
+                        Ok(cache) => chat.set_context_cache(*cache),
+                        Err(e) => {
+                            self.toasts
+                                .add(Toast::error(tr!("cache-failed", error = e)));
+                        }
+                    }
+                }
+                Ok(BackendResponse::Summary {
+                    chat_id,
+                    kept_from,
+                    result,
+                }) => {
+                    let Some(chat) = self.chats.iter_mut().find(|c| c.id() == chat_id) else {
+                        return;
+                    };
+                    chat.is_summarizing = false;
+                    match result {
+                        Ok(summary) => chat.apply_summary(kept_from, summary),
+                        Err(e) => {
+                            // keeps automatic summarizing from trying again on every frame
+                            chat.summarize_declined = true;
+                            self.toasts
+                                .add(Toast::error(tr!("summarize-failed", error = e)));
+                        }
+                    }
+                }
+                Ok(BackendResponse::CommandOutput {
+                    chat_id,
+                    time,
+                    call,
+                    result,
+                }) => {
+                    let Some(chat) = self.chats.iter_mut().find(|c| c.id() == chat_id) else {
+                        return;
+                    };
+                    let Some(message) = chat.messages.iter_mut().find(|m| m.time == time) else {
+                        return;
+                    };
+                    let Some(function_call) = message.calls.get_mut(call) else {
+                        return;
+                    };
+                    function_call.running = false;
+                    match result {
+                        Ok(output) => function_call.response = Some(output),
+                        Err(e) => {
+                            // left for the user to send or edit, the model may handle the error
+                            function_call.draft = serde_json::json!({ "error": e }).to_string();
+                            self.toasts
+                                .add(Toast::error(tr!("functions-command-failed")));
+                        }
+                    }
+                    let answered = message.calls.iter().all(|c| c.response.is_some());
+                    let is_last = chat.messages.last().is_some_and(|m| m.time == time);
+                    if answered && is_last {
+                        chat.continue_generation(&self.settings);
+                    }
+                }
+                Ok(BackendResponse::RequestPreview(preview)) => {
+                    self.request_preview.finish(*preview);
+                }
+                Err(flowync::error::Compact::Suppose(e)) => {
+                    self.onboarding.logging_in = false;
+                    modal
+                        .dialog()
+                        .with_icon(Icon::Error)
+                        .with_title("Request failed")
+                        .with_body(e)
+                        .open();
+                }
+                Err(flowync::error::Compact::Panicked(e)) => {
+                    log::error!("task panicked: {e}");
+                    modal
+                        .dialog()
+                        .with_icon(Icon::Error)
+                        .with_title("Task panicked")
+                        .with_body(format!("Task panicked: {e}"))
+                        .open();
+                }
+            };
+        });
+    }
+
+    #[inline]
+    fn add_default_chat(&mut self) {
+        self.add_chat(None);
+    }
+
+    /// Adds a top level chat and switches to it.
+    fn open_new_chat(&mut self) {
+        self.add_default_chat();
+        self.select_chat(self.chats.len() - 1);
+        self.edited_chat = None;
+        self.settings_open = false;
+    }
+
+    /// Opens a `geminid://` link: a new chat with a prompt, or an existing chat.
+    pub fn open_link(&mut self, ctx: &egui::Context, link: &str) {
+        let link = match DeepLink::parse(link) {
+            Ok(link) => link,
+            Err(e) => {
+                log::error!("can't open {link}: {e}");
+                return;
+            }
+        };
+        match link {
+            DeepLink::NewChat { prompt, model } => {
+                self.open_new_chat();
+                let idx = self.selected_chat;
+                let chat = &mut self.chats[idx];
+                if let Some(model) = model {
+                    chat.model_picker.selected = model;
+                }
+                chat.chatbox = prompt;
+                chat.focus_chatbox();
+            }
+            DeepLink::OpenChat(id) => match self.chats.iter().position(|c| c.id == id) {
+                Some(idx) => {
+                    self.select_chat(idx);
+                    self.edited_chat = None;
+                    self.settings_open = false;
+                }
+                None => {
+                    self.toasts
+                        .add(Toast::warning(tr!("deeplink-chat-not-found")));
+                }
+            },
+        }
+        ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+        ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+    }
+
+    /// Adds a chat to `folder`, starting from the folder's template if it has one.
+    fn add_chat(&mut self, folder: Option<u64>) {
+        // Find the highest existing ID to avoid collisions
+        let max_id = self.chats.iter().map(|c| c.id()).max().unwrap_or(0);
+        let picker = crate::folders::template(&self.folders, folder)
+            .unwrap_or(self.model_picker())
+            .clone();
+        let mut chat = Chat::new(max_id + 1, picker);
+        chat.folder = folder;
+        let mut effects = self.settings.scripting.run(Hook::ChatCreated, &chat, "");
+        if let Some(text) = effects.text.take() {
+            chat.chatbox = text;
+        }
+        chat.apply_script_effects(effects);
+        self.chats.push(chat);
+    }
+
+    /// Sends the scheduled prompts that are due and wakes up for the next one.
+    fn run_due_schedules(&mut self, ctx: &egui::Context) {
+        let now = chrono::Utc::now();
+        for id in self.scheduler.due(now) {
+            self.run_schedule(id);
+        }
+        if let Some(next) = self.scheduler.next_run() {
+            let wait = (next - now).to_std().unwrap_or_default();
+            ctx.request_repaint_after(wait.min(Duration::from_secs(60)));
+        }
+    }
+
+    /// Sends a scheduled prompt into its chat, creating the chat on the first run.
+    fn run_schedule(&mut self, id: u64) {
+        let Some(schedule) = self.scheduler.get(id).cloned() else {
+            return;
+        };
+        let text = schedule.prompt(&self.settings.prompt_library.prompts);
+        if text.trim().is_empty() && schedule.files.is_empty() {
+            log::warn!("the scheduled prompt `{}` is empty", schedule.name);
+            self.scheduler.skipped(id);
+            return;
+        }
+        let existing = self
+            .chats
+            .iter()
+            .position(|c| Some(c.id) == schedule.chat_id);
+        let idx = match existing {
+            Some(idx) => idx,
+            None => {
+                self.add_chat(None);
+                let chat = self.chats.last_mut().expect("a chat was just added");
+                chat.summary = schedule.name.clone();
+                self.chats.len() - 1
+            }
+        };
+        let chat = &mut self.chats[idx];
+        // tried again on the next check
+        if chat.flower_active() {
+            return;
+        }
+        let files = schedule
+            .files
+            .iter()
+            .map(|path| Attachment::from_path(path.clone()))
+            .collect();
+        chat.send_with_files(&self.settings, &text, files);
+        self.scheduler.started(id, chat.id);
+    }
+
+    /// Adds the tags requested by script hooks, creating the missing ones.
+    fn resolve_script_tags(&mut self) {
+        for chat in &mut self.chats {
+            for name in std::mem::take(&mut chat.pending_tags) {
+                let name = name.trim();
+                if name.is_empty() {
+                    continue;
+                }
+                let id = match self.tags.iter().find(|t| t.name.eq_ignore_ascii_case(name)) {
+                    Some(tag) => tag.id,
+                    None => crate::tags::add(&mut self.tags, name),
+                };
+                if !chat.tags.contains(&id) {
+                    chat.tags.push(id);
+                }
+            }
+        }
+    }
+
+    fn remove_chats(&mut self, ids: &[usize]) {
+        let selected_id = self.chats.get(self.selected_chat).map(|c| c.id());
+        let (removed, kept): (Vec<Chat>, Vec<Chat>) = std::mem::take(&mut self.chats)
+            .into_iter()
+            .partition(|c| ids.contains(&c.id()));
+        self.chats = kept;
+        for chat in removed {
+            self.close_chat_soft(chat);
+        }
+        self.edited_chat = None;
+        if self.chats.is_empty() {
+            self.add_default_chat();
+        }
+        let idx = selected_id
+            .and_then(|id| self.chats.iter().position(|c| c.id() == id))
+            .unwrap_or(0);
+        self.select_chat(idx);
+    }
+
+    /// Plain click selects the chat, Ctrl+click toggles it in the multi-selection
+    /// and Shift+click selects the range from the last clicked chat.
+    fn on_chat_clicked(&mut self, idx: usize, modifiers: egui::Modifiers) {
+        let Some(id) = self.chats.get(idx).map(|c| c.id()) else {
+            return;
+        };
+        let current = self.chats.get(self.selected_chat).map(|c| c.id());
+
+        if modifiers.command {
+            if self.selection.is_empty() {
+                self.selection.extend(current);
+            }
+            if let Some(pos) = self.selection.iter().position(|&s| s == id) {
+                self.selection.remove(pos);
+            } else {
+                self.selection.push(id);
             }
+            self.selection_anchor = Some(id);
+        } else if modifiers.shift {
+            let anchor = self.selection_anchor.or(current);
+            let pos = |id| self.visible_order.iter().position(|&o| o == id);
+            if let (Some(a), Some(b)) = (anchor.and_then(pos), pos(id)) {
+                self.selection = self.visible_order[a.min(b)..=a.max(b)].to_vec();
+            }
+        } else {
+            self.selection.clear();
+            self.selection_anchor = Some(id);
+            self.select_chat(idx);
+            self.settings_open = false;
+            self.edited_chat = None;
         }
     }
 
-    #[inline]
-    pub fn model_picker(&self) -> &ModelPicker {
-        &self.settings.model_picker
+    fn remove_folder(&mut self, id: u64) {
+        let parent = crate::folders::remove(&mut self.folders, id);
+        for chat in self.chats.iter_mut().filter(|c| c.folder == Some(id)) {
+            chat.folder = parent;
+        }
+        if self.edited_folder == Some(id) {
+            self.edited_folder = None;
+        }
     }
 
-    fn poll_backend_flower(&mut self, modal: &Modal) {
-        self.flower.extract(|()| ()).finalize(|resp| {
-            match resp {
-                Ok(BackendResponse::Ignore) => (),
-                Ok(BackendResponse::Toast(toast)) => {
-                    self.toasts.add(toast);
-                }
-                Ok(BackendResponse::Files { id, files }) => {
-                    if let Some(chat) = self.chats.iter_mut().find(|c| c.id() == id) {
-                        log::debug!("adding {} file(s) to chat {}", files.len(), id);
-                        chat.files
-                            .extend(files.into_iter().map(Attachment::from_path));
-                    }
-                }
-                Ok(BackendResponse::Settings(settings)) => {
-                    self.settings = *settings;
-                }
-                Ok(BackendResponse::TokenCount { chat_id, count }) => {
-                    if let Some(chat) = self.chats.iter_mut().find(|c| c.id() == chat_id) {
-                        chat.token_count = Some(count);
-                    }
-                }
-                Ok(BackendResponse::AuthResult { token, projects }) => {
-                    self.settings.oauth_token = token;
-                    self.settings.available_projects = projects;
-                    if self.settings.project_id.is_empty()
-                        && !self.settings.available_projects.is_empty()
-                    {
-                        self.settings.project_id = self.settings.available_projects[0].clone();
-                    }
-                    self.toasts.add(Toast::success("Google Login successful!"));
+    fn move_to_folder(&mut self, item: DragItem, target: Option<u64>) {
+        match item {
+            DragItem::Chat(chat_id) => {
+                if let Some(chat) = self.chats.iter_mut().find(|c| c.id() == chat_id) {
+                    chat.folder = target;
                 }
-                Err(flowync::error::Compact::Suppose(e)) => {
-                    modal
-                        .dialog()
-                        .with_icon(Icon::Error)
-                        .with_title("Request failed")
-                        .with_body(e)
-                        .open();
+            }
+            DragItem::Folder(id) => {
+                // a folder can't be moved into itself or one of its subfolders
+                if target.is_some_and(|t| crate::folders::is_within(&self.folders, t, id)) {
+                    return;
                 }
-                Err(flowync::error::Compact::Panicked(e)) => {
-                    log::error!("task panicked: {e}");
-                    modal
-                        .dialog()
-                        .with_icon(Icon::Error)
-                        .with_title("Task panicked")
-                        .with_body(format!("Task panicked: {e}"))
-                        .open();
+                if let Some(folder) = self.folders.iter_mut().find(|f| f.id == id) {
+                    folder.parent = target;
                 }
-            };
-        });
+            }
+        }
     }
 
-    #[inline]
-    fn add_default_chat(&mut self) {
-        // Find the highest existing ID to avoid collisions
-        let max_id = self.chats.iter().map(|c| c.id()).max().unwrap_or(0);
-        self.chats
-            .push(Chat::new(max_id + 1, self.model_picker().clone()));
+    fn close_chat_soft(&mut self, chat: Chat) {
+        // empty chats aren't worth restoring
+        if chat.messages.is_empty() {
+            return;
+        }
+        self.closed_chats.push(chat);
+        if self.closed_chats.len() > MAX_CLOSED_CHATS {
+            self.closed_chats.remove(0);
+        }
+    }
+
+    fn reopen_closed_chat(&mut self) {
+        let Some(mut chat) = self.closed_chats.pop() else {
+            self.toasts.add(Toast::info(tr!("no-closed-chats")));
+            return;
+        };
+        // a new chat may have taken the id in the meantime
+        if self.chats.iter().any(|c| c.id() == chat.id()) {
+            chat.id = self.chats.iter().map(|c| c.id).max().unwrap_or(0) + 1;
+        }
+        self.chats.push(chat);
+        self.select_chat(self.chats.len() - 1);
+        self.tab = SessionTab::Chats;
+        self.settings_open = false;
+        self.edited_chat = None;
     }
 
     fn remove_chat(&mut self, idx: usize) {
-        self.chats.remove(idx);
+        let chat = self.chats.remove(idx);
+        self.close_chat_soft(chat);
         if self.chats.is_empty() {
             self.add_default_chat();
             self.select_chat(0);
@@ -800,8 +3268,58 @@ impl Sessions {
         }
     }
 
+    /// Selects a chat and records the previous one in the navigation history.
     fn select_chat(&mut self, idx: usize) {
+        if let Some(prev) = self.chats.get(self.selected_chat).map(|c| c.id()) {
+            if idx != self.selected_chat && self.nav_back.last() != Some(&prev) {
+                self.nav_back.push(prev);
+                self.nav_forward.clear();
+            }
+        }
         self.selected_chat = idx;
+        self.edited_folder = None;
+    }
+
+    /// Moves through recently viewed chats, skipping ones that were removed since.
+    fn navigate(&mut self, back: bool) {
+        let current = self.chats.get(self.selected_chat).map(|c| c.id());
+        loop {
+            let next = if back {
+                self.nav_back.pop()
+            } else {
+                self.nav_forward.pop()
+            };
+            let Some(id) = next else {
+                return;
+            };
+            let Some(idx) = self.chats.iter().position(|c| c.id() == id) else {
+                continue;
+            };
+            if let Some(current) = current {
+                if back {
+                    self.nav_forward.push(current);
+                } else {
+                    self.nav_back.push(current);
+                }
+            }
+            self.selected_chat = idx;
+            self.edited_folder = None;
+            self.edited_chat = None;
+            self.settings_open = false;
+            self.tab = SessionTab::Chats;
+            return;
+        }
+    }
+
+    fn jump_to_message(&mut self, chat_idx: usize, msg_idx: usize) {
+        let Some(chat) = self.chats.get_mut(chat_idx) else {
+            return;
+        };
+        chat.jump_to_message = Some(msg_idx);
+        self.select_chat(chat_idx);
+        self.tab = SessionTab::Chats;
+        self.settings_open = false;
+        self.edited_chat = None;
     }
 
     /// Returns whether any chat was removed
@@ -813,13 +3331,13 @@ impl Sessions {
 
         let last_message = chat
             .last_message_contents()
-            .unwrap_or_else(|| "No recent messages".to_string());
+            .unwrap_or_else(|| tr!("no-recent-messages"));
 
         let summary = chat.summary.clone();
 
         ui.horizontal(|ui| {
             if summary.is_empty() {
-                ui.add(egui::Label::new("New Chat").selectable(false).truncate());
+                ui.add(egui::Label::new(tr!("new-chat")).selectable(false).truncate());
             } else {
                 EmojiLabel::new(summary)
                     .selectable(false)
@@ -836,7 +3354,7 @@ impl Sessions {
                             .fill(Color32::TRANSPARENT)
                             .stroke(Stroke::NONE),
                     )
-                    .on_hover_text("Remove chat")
+                    .on_hover_text(tr!("remove-chat"))
                     .clicked()
                 {
                     if self.chats[idx].messages.is_empty() || ui.input(|i| i.modifiers.shift) {
@@ -855,12 +3373,13 @@ impl Sessions {
                             .fill(Color32::TRANSPARENT)
                             .stroke(Stroke::NONE),
                     )
-                    .on_hover_text("Edit")
+                    .on_hover_text(tr!("edit"))
                     .clicked()
                 {
                     ignore_click = true;
 
                     // toggle editing
+                    self.edited_folder = None;
                     self.edited_chat = if self.edited_chat == Some(idx) {
                         None
                     } else {
@@ -874,24 +3393,38 @@ impl Sessions {
             false,
             egui::Label::new(last_message).selectable(false).truncate(),
         );
+        if let Some(chat) = self.chats.get(idx) {
+            crate::tags::show_chips(ui, &self.tags, &chat.tags);
+        }
         ignore_click
     }
 
     /// Returns whether the chat should be selected as the current one
     fn show_chat_in_sidepanel(&mut self, ui: &mut egui::Ui, idx: usize, modal: &Modal) -> bool {
         let mut ignore_click = false;
-        let resp = Frame::group(ui.style())
-            .corner_radius(CornerRadius::same(6))
-            .stroke(Stroke::new(2.0, ui.style().visuals.window_stroke.color))
-            .fill(if self.selected_chat == idx {
-                ui.style().visuals.faint_bg_color
-            } else {
-                ui.style().visuals.window_fill
-            })
-            .show(ui, |ui| {
-                ignore_click = self.show_chat_frame(ui, idx, modal);
-            })
-            .response;
+        let chat_id = self.chats[idx].id();
+        let resp = ui
+            .dnd_drag_source(
+                egui::Id::new(("chat_drag", chat_id)),
+                DragItem::Chat(chat_id),
+                |ui| {
+                    Frame::group(ui.style())
+                        .corner_radius(CornerRadius::same(6))
+                        .stroke(Stroke::new(2.0, ui.style().visuals.window_stroke.color))
+                        .fill(if self.selection.contains(&chat_id) {
+                            ui.style().visuals.selection.bg_fill.gamma_multiply(0.35)
+                        } else if self.selected_chat == idx {
+                            ui.style().visuals.faint_bg_color
+                        } else {
+                            ui.style().visuals.window_fill
+                        })
+                        .show(ui, |ui| {
+                            ignore_click = self.show_chat_frame(ui, idx, modal);
+                        })
+                        .response
+                },
+            )
+            .inner;
 
         // very hacky way to determine if the group has been clicked, for some reason
         // egui doens't register clicked() events on it
@@ -911,20 +3444,162 @@ impl Sessions {
 
         if hovered {
             ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+            if let Some(pos) = ui.input(|i| {
+                i.pointer
+                    .secondary_clicked()
+                    .then(|| i.pointer.interact_pos())
+                    .flatten()
+            }) {
+                self.chat_menu = Some((chat_id, pos));
+            }
+        }
+
+        // Tab stop for the chat itself, Enter opens it and Shift+F10 shows the menu
+        let focus = ui.interact(
+            resp.rect,
+            egui::Id::new(("chat_focus", chat_id)),
+            egui::Sense::focusable_noninteractive(),
+        );
+        focus.widget_info(|| {
+            egui::WidgetInfo::selected(
+                egui::WidgetType::SelectableLabel,
+                true,
+                self.selected_chat == idx,
+                self.chats[idx].title(),
+            )
+        });
+        let mut activated = false;
+        if focus.has_focus() {
+            ui.painter().rect_stroke(
+                resp.rect,
+                6.0,
+                ui.visuals().selection.stroke,
+                egui::StrokeKind::Outside,
+            );
+            activated = ui.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Enter));
+            if crate::widgets::context_menu_pressed(&focus) {
+                self.chat_menu = Some((chat_id, resp.rect.left_bottom()));
+                self.focus_chat_menu = true;
+            }
+        }
+
+        activated || (!ignore_click && primary_clicked && hovered)
+    }
+
+    fn show_bulk_bar(&mut self, ui: &mut egui::Ui, modal: &Modal) {
+        self.selection
+            .retain(|id| self.chats.iter().any(|c| c.id() == *id));
+        if self.selection.is_empty() {
+            return;
+        }
+
+        let mut action = None;
+        Frame::group(ui.style()).show(ui, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                ui.strong(tr!("selected-count", count = self.selection.len()));
+                if ui.small_button("🗑").on_hover_text(tr!("delete")).clicked() {
+                    action = Some(BulkAction::Delete);
+                }
+                let all_archived = self
+                    .chats
+                    .iter()
+                    .filter(|c| self.selection.contains(&c.id()))
+                    .all(|c| c.archived);
+                if ui
+                    .small_button("📦")
+                    .on_hover_text(if all_archived {
+                        tr!("unarchive")
+                    } else {
+                        tr!("archive")
+                    })
+                    .clicked()
+                {
+                    action = Some(BulkAction::Archive(!all_archived));
+                }
+                ui.menu_button("🏷", |ui| {
+                    if self.tags.is_empty() {
+                        ui.weak(tr!("no-tags"));
+                    }
+                    for tag in &self.tags {
+                        if crate::tags::chip(ui, tag, false).clicked() {
+                            action = Some(BulkAction::Tag(tag.id));
+                            ui.close();
+                        }
+                    }
+                })
+                .response
+                .on_hover_text(tr!("bulk-tag-hint"));
+                ui.menu_button("📁", |ui| {
+                    if ui.button(tr!("top-level")).clicked() {
+                        action = Some(BulkAction::MoveToFolder(None));
+                        ui.close();
+                    }
+                    for (depth, id) in crate::folders::flatten(&self.folders) {
+                        let Some(folder) = crate::folders::find(&self.folders, Some(id)) else {
+                            continue;
+                        };
+                        ui.horizontal(|ui| {
+                            ui.add_space(depth as f32 * 12.0);
+                            if ui.button(format!("📁 {}", folder.name)).clicked() {
+                                action = Some(BulkAction::MoveToFolder(Some(id)));
+                                ui.close();
+                            }
+                        });
+                    }
+                })
+                .response
+                .on_hover_text(tr!("move-to-folder"));
+                if ui.small_button("💾").on_hover_text(tr!("export")).clicked() {
+                    action = Some(BulkAction::Export);
+                }
+                if ui.small_button("✖").on_hover_text(tr!("clear-selection")).clicked() {
+                    self.selection.clear();
+                }
+            });
+        });
+
+        if action.is_some() {
+            self.bulk_action = action;
+            modal.open();
         }
+    }
 
-        !ignore_click && primary_clicked && hovered
+    /// Shows a single chat in the list and records it for click handling.
+    fn show_chat_entry(
+        &mut self,
+        ui: &mut egui::Ui,
+        idx: usize,
+        modal: &Modal,
+        out: &mut SidebarOutput,
+    ) {
+        if let Some(chat) = self.chats.get(idx) {
+            out.order.push(chat.id());
+        }
+        if self.show_chat_in_sidepanel(ui, idx, modal) {
+            out.clicked = Some(idx);
+        }
+        ui.add_space(2.0);
     }
 
-    fn show_chats(&mut self, ui: &mut egui::Ui, modal: &Modal) {
+    fn show_chats(&mut self, ui: &mut egui::Ui, modal: &Modal, bulk_modal: &Modal) {
+        // drawn before the list so the right click that opens it doesn't close it
+        self.show_chat_context_menu(ui.ctx());
+
         ui.vertical_centered_justified(|ui| {
             if ui
-                .add(egui::Button::new("➕ New Chat").min_size(vec2(0.0, 24.0)))
-                .on_hover_text("Create a new chat")
+                .add(egui::Button::new(tr!("new-chat-button")).min_size(vec2(0.0, 24.0)))
+                .on_hover_text(tr!("new-chat-hint"))
+                .clicked()
+            {
+                self.open_new_chat();
+            }
+            if ui
+                .button(tr!("new-folder-button"))
+                .on_hover_text(tr!("new-folder-hint"))
                 .clicked()
             {
-                self.add_default_chat();
-                self.select_chat(self.chats.len() - 1);
+                let id = crate::folders::add(&mut self.folders, None);
+                self.edited_folder = Some(id);
                 self.edited_chat = None;
                 self.settings_open = false;
             }
@@ -932,15 +3607,347 @@ impl Sessions {
 
         ui.add_space(2.0);
 
+        if !self.tags.is_empty() {
+            if let Some(id) = crate::tags::toggle_row(ui, &self.tags, &self.tag_filter) {
+                crate::tags::toggle(&mut self.tag_filter, id);
+            }
+            ui.add_space(2.0);
+        }
+
+        self.show_bulk_bar(ui, bulk_modal);
+
+        let mut out = SidebarOutput::default();
         egui::ScrollArea::vertical().show(ui, |ui| {
-            for i in 0..self.chats.len() {
-                if self.show_chat_in_sidepanel(ui, i, modal) {
-                    self.select_chat(i);
-                    self.settings_open = false;
-                    self.edited_chat = None;
+            if !self.saved_searches.is_empty() {
+                self.show_saved_searches(ui);
+                ui.separator();
+            }
+            self.show_folder_contents(ui, None, modal, &mut out, &mut HashSet::new());
+
+            if egui::DragAndDrop::has_payload_of_type::<DragItem>(ui.ctx()) {
+                let frame = Frame::group(ui.style());
+                let (_, payload) = ui.dnd_drop_zone::<DragItem, _>(frame, |ui| {
+                    ui.set_width(ui.available_width());
+                    ui.weak(tr!("drop-to-top-level"));
+                });
+                if let Some(item) = payload {
+                    out.dropped = Some((*item, None));
+                }
+            }
+
+            let archived: Vec<usize> = (0..self.chats.len())
+                .filter(|&i| self.chats[i].archived)
+                .collect();
+            if !archived.is_empty() {
+                egui::CollapsingHeader::new(tr!("archived-header", count = archived.len()))
+                    .id_salt("archived_chats")
+                    .show(ui, |ui| {
+                        for i in archived {
+                            if i < self.chats.len() {
+                                self.show_chat_entry(ui, i, modal, &mut out);
+                            }
+                        }
+                    });
+            }
+        });
+
+        self.visible_order = out.order;
+        if let Some((item, target)) = out.dropped {
+            self.move_to_folder(item, target);
+        }
+        if let Some(idx) = out.clicked {
+            let modifiers = ui.input(|i| i.modifiers);
+            self.on_chat_clicked(idx, modifiers);
+        }
+    }
+
+    /// Virtual folders listing the chats that currently match each saved query.
+    fn show_saved_searches(&mut self, ui: &mut egui::Ui) {
+        let mut remove = None;
+        let mut select = None;
+        for saved in self.saved_searches.iter_mut() {
+            ui.horizontal(|ui| {
+                let text = format!(
+                    "{} 🔍 {}",
+                    if saved.collapsed { "⏵" } else { "⏷" },
+                    saved.name
+                );
+                if ui
+                    .add(
+                        egui::Label::new(text)
+                            .selectable(false)
+                            .truncate()
+                            .sense(egui::Sense::click()),
+                    )
+                    .on_hover_text(&saved.query)
+                    .clicked()
+                {
+                    saved.collapsed = !saved.collapsed;
+                }
+                ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui
+                        .add(
+                            egui::Button::new("❌")
+                                .small()
+                                .fill(Color32::TRANSPARENT)
+                                .stroke(Stroke::NONE),
+                        )
+                        .on_hover_text(tr!("remove-saved-search"))
+                        .clicked()
+                    {
+                        remove = Some(saved.id);
+                    }
+                });
+            });
+            if saved.collapsed {
+                continue;
+            }
+
+            let query = ParsedQuery::parse(&saved.query);
+            ui.indent(("saved_search", saved.id), |ui| {
+                let mut any = false;
+                for (i, chat) in self.chats.iter().enumerate() {
+                    if !query.matches_chat(chat, &self.tags) {
+                        continue;
+                    }
+                    any = true;
+                    let name = chat.title();
+                    if ui.selectable_label(self.selected_chat == i, name).clicked() {
+                        select = Some(i);
+                    }
+                }
+                if !any {
+                    ui.weak(tr!("no-matching-chats"));
                 }
-                ui.add_space(2.0);
+            });
+        }
+
+        if let Some(id) = remove {
+            self.saved_searches.retain(|s| s.id != id);
+        }
+        if let Some(idx) = select {
+            self.select_chat(idx);
+            self.settings_open = false;
+            self.edited_chat = None;
+        }
+    }
+
+    /// Shows the subfolders and chats directly inside `parent`. `visited` holds the folders
+    /// shown so far, each is shown once even if the saved folders form a cycle.
+    fn show_folder_contents(
+        &mut self,
+        ui: &mut egui::Ui,
+        parent: Option<u64>,
+        modal: &Modal,
+        out: &mut SidebarOutput,
+        visited: &mut HashSet<u64>,
+    ) {
+        let subfolders: Vec<u64> = match parent {
+            None => crate::folders::top_level(&self.folders),
+            Some(_) => self
+                .folders
+                .iter()
+                .filter(|f| f.parent == parent)
+                .map(|f| f.id)
+                .collect(),
+        };
+        for id in subfolders {
+            if visited.insert(id) {
+                self.show_folder(ui, id, modal, out, visited);
+            }
+        }
+
+        for i in 0..self.chats.len() {
+            let Some(chat) = self.chats.get(i) else {
+                break;
+            };
+            // chats pointing at a folder that no longer exists end up at the top level
+            let folder = chat
+                .folder
+                .filter(|&f| crate::folders::find(&self.folders, Some(f)).is_some());
+            if chat.archived
+                || folder != parent
+                || !self.tag_filter.iter().all(|t| chat.tags.contains(t))
+            {
+                continue;
             }
+            self.show_chat_entry(ui, i, modal, out);
+        }
+    }
+
+    fn show_folder(
+        &mut self,
+        ui: &mut egui::Ui,
+        id: u64,
+        modal: &Modal,
+        out: &mut SidebarOutput,
+        visited: &mut HashSet<u64>,
+    ) {
+        let Some(folder) = crate::folders::find(&self.folders, Some(id)) else {
+            return;
+        };
+        let name = folder.name.clone();
+        let icon = match folder.project {
+            Some(_) => "📦",
+            None => "📁",
+        };
+        let collapsed = folder.collapsed;
+        let (mut toggle, mut new_chat, mut edit, mut remove) = (false, false, false, false);
+
+        let frame = Frame::NONE
+            .inner_margin(egui::Margin::same(2))
+            .corner_radius(CornerRadius::same(4));
+        let (_, payload) = ui.dnd_drop_zone::<DragItem, _>(frame, |ui| {
+            ui.set_width(ui.available_width());
+            ui.horizontal(|ui| {
+                ui.dnd_drag_source(egui::Id::new(("folder_drag", id)), DragItem::Folder(id), |ui| {
+                    let text = format!("{} {icon} {name}", if collapsed { "⏵" } else { "⏷" });
+                    toggle = ui
+                        .add(
+                            egui::Label::new(text)
+                                .selectable(false)
+                                .truncate()
+                                .sense(egui::Sense::click()),
+                        )
+                        .clicked();
+                });
+
+                ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.spacing_mut().item_spacing.x = 0.0;
+                    for (icon, hint, flag) in [
+                        ("❌", tr!("folder-remove-hint"), &mut remove),
+                        ("\u{270f}", tr!("folder-edit-hint"), &mut edit),
+                        ("➕", tr!("folder-new-chat-hint"), &mut new_chat),
+                    ] {
+                        *flag = ui
+                            .add(
+                                egui::Button::new(icon)
+                                    .small()
+                                    .fill(Color32::TRANSPARENT)
+                                    .stroke(Stroke::NONE),
+                            )
+                            .on_hover_text(hint)
+                            .clicked();
+                    }
+                });
+            });
         });
+
+        if let Some(item) = payload {
+            out.dropped = Some((*item, Some(id)));
+        }
+        if remove {
+            self.remove_folder(id);
+            return;
+        }
+        if toggle {
+            if let Some(folder) = self.folders.iter_mut().find(|f| f.id == id) {
+                folder.collapsed = !collapsed;
+            }
+        }
+        if edit {
+            self.edited_folder = if self.edited_folder == Some(id) {
+                None
+            } else {
+                Some(id)
+            };
+            self.edited_chat = None;
+            self.settings_open = false;
+        }
+        if new_chat {
+            self.add_chat(Some(id));
+            self.select_chat(self.chats.len() - 1);
+            self.edited_chat = None;
+            self.settings_open = false;
+        }
+
+        if !collapsed {
+            ui.indent(("folder_contents", id), |ui| {
+                self.show_folder_contents(ui, Some(id), modal, out, visited);
+            });
+        }
+    }
+
+    fn show_chat_context_menu(&mut self, ctx: &egui::Context) {
+        let Some((chat_id, pos)) = self.chat_menu else {
+            return;
+        };
+
+        let Some(archived) = self
+            .chats
+            .iter()
+            .find(|c| c.id() == chat_id)
+            .map(|c| c.archived)
+        else {
+            self.chat_menu = None;
+            return;
+        };
+
+        let mut target = None;
+        let mut toggle_archive = false;
+        let mut open_split = false;
+        let mut detach = false;
+        let resp = egui::Area::new(egui::Id::new("chat_context_menu"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(pos)
+            .show(ctx, |ui| {
+                Frame::menu(ui.style()).show(ui, |ui| {
+                    let first = ui.button(tr!("open-in-split-view"));
+                    if std::mem::take(&mut self.focus_chat_menu) {
+                        first.request_focus();
+                    }
+                    open_split = first.clicked();
+                    detach = ui.button(tr!("open-in-new-window")).clicked();
+                    ui.separator();
+                    ui.weak(tr!("move-to-folder"));
+                    if ui.button(tr!("top-level")).clicked() {
+                        target = Some(None);
+                    }
+                    for (depth, id) in crate::folders::flatten(&self.folders) {
+                        let Some(folder) = crate::folders::find(&self.folders, Some(id)) else {
+                            continue;
+                        };
+                        ui.horizontal(|ui| {
+                            ui.add_space(depth as f32 * 12.0);
+                            if ui.button(format!("📁 {}", folder.name)).clicked() {
+                                target = Some(Some(id));
+                            }
+                        });
+                    }
+                    ui.separator();
+                    toggle_archive = ui
+                        .button(if archived {
+                            tr!("unarchive")
+                        } else {
+                            tr!("archive-button")
+                        })
+                        .clicked();
+                });
+            })
+            .response;
+
+        if let Some(folder) = target {
+            self.move_to_folder(DragItem::Chat(chat_id), folder);
+            self.chat_menu = None;
+        } else if open_split {
+            self.split_chat = Some(chat_id);
+            self.detached.retain(|&id| id != chat_id);
+            self.chat_menu = None;
+        } else if detach {
+            if !self.detached.contains(&chat_id) {
+                self.detached.push(chat_id);
+            }
+            if self.split_chat == Some(chat_id) {
+                self.split_chat = None;
+            }
+            self.chat_menu = None;
+        } else if toggle_archive {
+            if let Some(chat) = self.chats.iter_mut().find(|c| c.id() == chat_id) {
+                chat.archived = !archived;
+            }
+            self.chat_menu = None;
+        } else if resp.clicked_elsewhere() || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            self.chat_menu = None;
+        }
     }
 }
@@ -0,0 +1,92 @@
+use crate::i18n::tr;
+use eframe::egui;
+
+/// A text shortcut (e.g. `;sig`) that expands into a longer snippet in the composer.
+#[derive(Default, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Snippet {
+    pub trigger: String,
+    pub expansion: String,
+}
+
+/// Expands a snippet trigger that was just terminated by whitespace in front of
+/// `cursor` (a char index). Returns the new cursor position if anything was expanded.
+pub fn expand(text: &mut String, cursor: usize, snippets: &[Snippet]) -> Option<usize> {
+    if snippets.is_empty() || cursor == 0 {
+        return None;
+    }
+
+    let byte_cursor = text
+        .char_indices()
+        .nth(cursor)
+        .map(|(i, _)| i)
+        .unwrap_or(text.len());
+    let before = &text[..byte_cursor];
+
+    // the character that was just typed must end the word
+    let terminator = before.chars().next_back()?;
+    if !terminator.is_whitespace() {
+        return None;
+    }
+    let word_end = byte_cursor - terminator.len_utf8();
+    let word_start = before[..word_end]
+        .rfind(char::is_whitespace)
+        .map(|i| i + before[i..].chars().next().map_or(1, char::len_utf8))
+        .unwrap_or(0);
+    let word = &text[word_start..word_end];
+
+    let snippet = snippets
+        .iter()
+        .find(|s| !s.trigger.is_empty() && s.trigger == word)?;
+
+    let trigger_chars = word.chars().count();
+    let expansion_chars = snippet.expansion.chars().count();
+    text.replace_range(word_start..word_end, &snippet.expansion);
+
+    log::debug!("expanded snippet `{}`", snippet.trigger);
+    Some(cursor - trigger_chars + expansion_chars)
+}
+
+pub fn show_snippets(ui: &mut egui::Ui, snippets: &mut Vec<Snippet>) {
+    ui.label(tr!("snippets-help"));
+
+    if !snippets.is_empty() {
+        let mut remove = None;
+        egui::Grid::new("snippets_grid")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.strong(tr!("snippets-shortcut"));
+                ui.strong(tr!("snippets-expansion"));
+                ui.end_row();
+
+                for (i, snippet) in snippets.iter_mut().enumerate() {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut snippet.trigger)
+                            .hint_text(";sig")
+                            .desired_width(80.0),
+                    );
+                    ui.add(
+                        egui::TextEdit::multiline(&mut snippet.expansion)
+                            .hint_text(tr!("snippets-expansion-hint"))
+                            .desired_rows(1),
+                    );
+                    if ui
+                        .button("❌")
+                        .on_hover_text(tr!("snippets-remove"))
+                        .clicked()
+                    {
+                        remove = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(i) = remove {
+            snippets.remove(i);
+        }
+    }
+
+    if ui.button(tr!("add")).clicked() {
+        snippets.push(Snippet::default());
+    }
+}
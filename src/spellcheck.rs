@@ -0,0 +1,310 @@
+use crate::i18n::tr;
+use eframe::egui::{self, text::LayoutJob, Align, Stroke, TextFormat, TextStyle};
+use spellbook::Dictionary;
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    ops::Range,
+    path::PathBuf,
+    sync::{Arc, LazyLock, Mutex, RwLock},
+};
+
+const MAX_SUGGESTIONS: usize = 6;
+
+/// Folders searched for `<name>.aff` and `<name>.dic` after the user's one.
+const SYSTEM_DICTIONARY_DIRS: &[&str] = &[
+    "/usr/share/hunspell",
+    "/usr/share/myspell",
+    "/usr/share/myspell/dicts",
+    "/Library/Spelling",
+];
+
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct SpellcheckSettings {
+    pub enabled: bool,
+    /// Hunspell dictionary name such as `en_US`, empty follows the UI language
+    pub language: String,
+    /// Extra folder with `.aff` and `.dic` files
+    pub dictionary_dir: String,
+    /// Words added with "Add to dictionary"
+    pub ignored: Vec<String>,
+}
+
+impl Default for SpellcheckSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            language: String::new(),
+            dictionary_dir: String::new(),
+            ignored: Vec::new(),
+        }
+    }
+}
+
+/// A misspelled word under the pointer, for the chatbox context menu.
+#[derive(Clone)]
+pub struct SpellTarget {
+    /// Byte range in the chatbox
+    pub range: Range<usize>,
+    pub word: String,
+    pub suggestions: Vec<String>,
+}
+
+enum Slot {
+    Loading,
+    Loaded(Arc<Dictionary>),
+    Missing,
+}
+
+static DICTIONARIES: LazyLock<RwLock<HashMap<String, Slot>>> = LazyLock::new(Default::default);
+
+/// Text hash and misspellings of the last check, the layouter runs every frame.
+static LAST_CHECK: Mutex<Option<(u64, Vec<Range<usize>>)>> = Mutex::new(None);
+
+fn search_dirs(extra: &str) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if !extra.trim().is_empty() {
+        dirs.push(PathBuf::from(extra.trim()));
+    }
+    dirs.extend(eframe::storage_dir(crate::TITLE).map(|p| p.join("dictionaries")));
+    dirs.extend(SYSTEM_DICTIONARY_DIRS.iter().map(PathBuf::from));
+    dirs
+}
+
+fn load(name: &str, dirs: &[PathBuf]) -> Option<Dictionary> {
+    for dir in dirs {
+        let aff = std::fs::read_to_string(dir.join(format!("{name}.aff")));
+        let dic = std::fs::read_to_string(dir.join(format!("{name}.dic")));
+        let (Ok(aff), Ok(dic)) = (aff, dic) else {
+            continue;
+        };
+        match Dictionary::new(&aff, &dic) {
+            Ok(dictionary) => {
+                log::info!("loaded the `{name}` dictionary from {}", dir.display());
+                return Some(dictionary);
+            }
+            Err(e) => log::error!("invalid `{name}` dictionary in {}: {e}", dir.display()),
+        }
+    }
+    log::warn!("no `{name}` hunspell dictionary found in {dirs:?}");
+    None
+}
+
+impl SpellcheckSettings {
+    fn dictionary_name(&self) -> String {
+        if self.language.trim().is_empty() {
+            crate::i18n::language().locale_code().to_owned()
+        } else {
+            self.language.trim().to_owned()
+        }
+    }
+
+    /// The dictionary if it is loaded, starts loading it in the background otherwise.
+    fn dictionary(&self, ctx: &egui::Context) -> Option<Arc<Dictionary>> {
+        let name = self.dictionary_name();
+        if let Some(slot) = DICTIONARIES.read().ok()?.get(&name) {
+            return match slot {
+                Slot::Loaded(dictionary) => Some(dictionary.clone()),
+                Slot::Loading | Slot::Missing => None,
+            };
+        }
+        DICTIONARIES
+            .write()
+            .ok()?
+            .insert(name.clone(), Slot::Loading);
+        let dirs = search_dirs(&self.dictionary_dir);
+        let ctx = ctx.clone();
+        tokio::task::spawn_blocking(move || {
+            let slot = match load(&name, &dirs) {
+                Some(dictionary) => Slot::Loaded(Arc::new(dictionary)),
+                None => Slot::Missing,
+            };
+            if let Ok(mut dictionaries) = DICTIONARIES.write() {
+                dictionaries.insert(name, slot);
+            }
+            ctx.request_repaint();
+        });
+        None
+    }
+
+    fn is_misspelled(&self, dictionary: &Dictionary, word: &str) -> bool {
+        !self.ignored.iter().any(|w| w == word) && !dictionary.check(word)
+    }
+
+    /// Byte ranges of the misspelled words in `text`.
+    pub fn misspelled(&self, ctx: &egui::Context, text: &str) -> Vec<Range<usize>> {
+        let Some(dictionary) = self.dictionary(ctx) else {
+            return Vec::new();
+        };
+        let mut hasher = DefaultHasher::new();
+        (text, self.dictionary_name(), &self.ignored).hash(&mut hasher);
+        let hash = hasher.finish();
+        let Ok(mut last) = LAST_CHECK.lock() else {
+            return Vec::new();
+        };
+        if let Some((last_hash, ranges)) = last.as_ref() {
+            if *last_hash == hash {
+                return ranges.clone();
+            }
+        }
+        let ranges: Vec<_> = words(text)
+            .into_iter()
+            .filter(|r| self.is_misspelled(&dictionary, &text[r.clone()]))
+            .collect();
+        *last = Some((hash, ranges.clone()));
+        ranges
+    }
+
+    /// The misspelled word at char index `cursor`, with suggestions.
+    pub fn target_at(&self, ctx: &egui::Context, text: &str, cursor: usize) -> Option<SpellTarget> {
+        let dictionary = self.dictionary(ctx)?;
+        let byte = text
+            .char_indices()
+            .nth(cursor)
+            .map_or(text.len(), |(i, _)| i);
+        let range = words(text)
+            .into_iter()
+            .find(|r| r.start <= byte && byte <= r.end)?;
+        let word = &text[range.clone()];
+        if !self.is_misspelled(&dictionary, word) {
+            return None;
+        }
+        let mut suggestions = Vec::new();
+        dictionary.suggest(word, &mut suggestions);
+        suggestions.truncate(MAX_SUGGESTIONS);
+        Some(SpellTarget {
+            word: word.to_owned(),
+            range,
+            suggestions,
+        })
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.enabled, tr!("spellcheck-enabled"))
+            .on_hover_text(tr!("spellcheck-enabled-help"));
+        ui.add_enabled_ui(self.enabled, |ui| {
+            egui::Grid::new("spellcheck_grid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label(tr!("spellcheck-language"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.language)
+                            .hint_text(crate::i18n::language().locale_code()),
+                    );
+                    ui.end_row();
+                    ui.label(tr!("spellcheck-folder"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.dictionary_dir)
+                            .hint_text(tr!("spellcheck-folder-hint")),
+                    );
+                    ui.end_row();
+                });
+            ui.horizontal(|ui| {
+                ui.label(tr!("spellcheck-ignored", count = self.ignored.len()));
+                if ui
+                    .add_enabled(
+                        !self.ignored.is_empty(),
+                        egui::Button::new(tr!("spellcheck-clear")),
+                    )
+                    .clicked()
+                {
+                    self.ignored.clear();
+                }
+            });
+            if ui.button(tr!("spellcheck-reload")).clicked() {
+                if let Ok(mut dictionaries) = DICTIONARIES.write() {
+                    dictionaries.clear();
+                }
+            }
+        });
+    }
+}
+
+/// Code-like words aren't checked: snake_case, camelCase, ACRONYMS and anything with digits.
+fn is_identifier(word: &str) -> bool {
+    word.chars().any(|c| c.is_ascii_digit() || c == '_')
+        || word.chars().skip(1).any(char::is_uppercase)
+}
+
+/// Byte ranges of the words worth checking. Skips fenced code, `inline code`, links and identifiers.
+fn words(text: &str) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    let mut in_fence = false;
+    let mut in_code = false;
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let is_fence = line.trim_start().starts_with("```");
+        if is_fence {
+            in_fence = !in_fence;
+        }
+        if in_fence || is_fence {
+            offset += line.len();
+            continue;
+        }
+        let mut token_start = None;
+        for (i, c) in line
+            .char_indices()
+            .chain(std::iter::once((line.len(), ' ')))
+        {
+            if !c.is_whitespace() {
+                token_start.get_or_insert(i);
+                continue;
+            }
+            let Some(start) = token_start.take() else {
+                continue;
+            };
+            let token = &line[start..i];
+            let ticks = token.matches('`').count();
+            // paths, links, emails and `module::paths`, sentence punctuation aside
+            let inner = token.trim_end_matches(|c: char| c.is_ascii_punctuation());
+            let skip = in_code || ticks > 0 || inner.contains(['/', '\\', '@', '.', ':']);
+            if ticks % 2 == 1 {
+                in_code = !in_code;
+            }
+            if skip {
+                continue;
+            }
+            let trimmed = token.trim_start_matches(|c: char| !c.is_alphabetic());
+            let lead = token.len() - trimmed.len();
+            let word = trimmed.trim_end_matches(|c: char| !c.is_alphabetic());
+            if word.is_empty() || is_identifier(word) {
+                continue;
+            }
+            let start = offset + start + lead;
+            words.push(start..start + word.len());
+        }
+        offset += line.len();
+    }
+    words
+}
+
+/// Chatbox layout with the misspelled words underlined.
+pub fn layout_job(
+    ui: &egui::Ui,
+    text: &str,
+    misspelled: &[Range<usize>],
+    wrap_width: f32,
+    halign: Align,
+) -> LayoutJob {
+    let normal = TextFormat {
+        font_id: TextStyle::Body.resolve(ui.style()),
+        color: ui.visuals().text_color(),
+        ..Default::default()
+    };
+    let underlined = TextFormat {
+        underline: Stroke::new(1.5, ui.visuals().error_fg_color),
+        ..normal.clone()
+    };
+    let mut job = LayoutJob::default();
+    let mut pos = 0;
+    for range in misspelled {
+        job.append(&text[pos..range.start], 0.0, normal.clone());
+        job.append(&text[range.clone()], 0.0, underlined.clone());
+        pos = range.end;
+    }
+    job.append(&text[pos..], 0.0, normal);
+    job.wrap.max_width = wrap_width;
+    job.halign = halign;
+    job
+}
@@ -0,0 +1,375 @@
+use crate::i18n::tr;
+use eframe::egui::{self, Color32, Id, RichText};
+use serde_json::Value;
+
+/// Levels of a JSON answer open when it is first shown.
+const OPEN_DEPTH: usize = 2;
+
+/// The response schema as typed, `None` when it is empty and any JSON will do.
+pub fn parse_schema(text: &str) -> Result<Option<Value>, String> {
+    if text.trim().is_empty() {
+        return Ok(None);
+    }
+    let schema: Value = serde_json::from_str(text)
+        .map_err(|e| tr!("structured-invalid-schema", error = e.to_string()))?;
+    if !schema.is_object() {
+        return Err(tr!("structured-schema-not-object"));
+    }
+    Ok(Some(schema))
+}
+
+/// Edits the schema answers have to follow, checking it as it is typed.
+pub fn show_schema_editor(ui: &mut egui::Ui, schema: &mut String) {
+    ui.add(
+        egui::TextEdit::multiline(schema)
+            .id_salt("response_schema")
+            .hint_text(tr!("structured-schema-hint"))
+            .code_editor()
+            .desired_rows(6)
+            .desired_width(f32::INFINITY),
+    );
+    match parse_schema(schema) {
+        Ok(Some(_)) => {
+            ui.weak(tr!("structured-schema-valid"));
+        }
+        Ok(None) => {
+            ui.weak(tr!("structured-schema-empty"));
+        }
+        Err(e) => {
+            ui.colored_label(ui.visuals().warn_fg_color, e);
+        }
+    }
+}
+
+/// Where in the answer a value sits, as in `$.items[2].name`.
+fn child_path(parent: &str, key: &str, in_array: bool) -> String {
+    if in_array {
+        format!("{parent}[{key}]")
+    } else if !key.is_empty() && key.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        format!("{parent}.{key}")
+    } else {
+        format!("{parent}[{}]", Value::from(key))
+    }
+}
+
+/// A place where the answer doesn't follow the schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Issue {
+    pub path: String,
+    pub message: String,
+}
+
+/// The types `schema` allows, lowercase. The API takes them uppercase as well.
+fn schema_types(schema: &Value) -> Vec<String> {
+    let mut types: Vec<String> = match schema.get("type") {
+        Some(Value::String(t)) => vec![t.to_lowercase()],
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_lowercase)
+            .collect(),
+        _ => Vec::new(),
+    };
+    if !types.is_empty() && schema.get("nullable").and_then(Value::as_bool) == Some(true) {
+        types.push("null".to_owned());
+    }
+    types
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn has_type(value: &Value, ty: &str) -> bool {
+    match (ty, value) {
+        ("integer", Value::Number(n)) => n.as_f64().is_some_and(|f| f.fract() == 0.0),
+        ("number", Value::Number(_)) => true,
+        _ => type_name(value) == ty,
+    }
+}
+
+/// Checks `value` against the parts of `schema` the API supports: types, enums, required
+/// and listed properties, items, their counts and numeric bounds.
+pub fn validate(schema: &Value, value: &Value) -> Vec<Issue> {
+    validate_at(schema, value, "$")
+}
+
+fn check(schema: &Value, value: &Value, path: &str, issues: &mut Vec<Issue>) {
+    let mut issue = |message: String| {
+        issues.push(Issue {
+            path: path.to_owned(),
+            message,
+        })
+    };
+
+    if let Some(variants) = schema.get("anyOf").and_then(Value::as_array) {
+        if !variants
+            .iter()
+            .any(|v| validate_at(v, value, path).is_empty())
+        {
+            issue(tr!("structured-issue-any-of"));
+        }
+        return;
+    }
+
+    let types = schema_types(schema);
+    if !types.is_empty() && !types.iter().any(|ty| has_type(value, ty)) {
+        issue(tr!(
+            "structured-issue-type",
+            expected = types.join(" | "),
+            actual = type_name(value)
+        ));
+        return;
+    }
+    if value.is_null() {
+        return;
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+            issue(tr!("structured-issue-enum", allowed = allowed.join(", ")));
+        }
+    }
+    if let Some(number) = value.as_f64() {
+        if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64) {
+            if number < minimum {
+                issue(tr!("structured-issue-minimum", limit = minimum));
+            }
+        }
+        if let Some(maximum) = schema.get("maximum").and_then(Value::as_f64) {
+            if number > maximum {
+                issue(tr!("structured-issue-maximum", limit = maximum));
+            }
+        }
+    }
+
+    match value {
+        Value::Array(items) => {
+            let count = |key: &str| schema.get(key).and_then(Value::as_u64);
+            if let Some(min) = count("minItems").filter(|&min| (items.len() as u64) < min) {
+                issue(tr!("structured-issue-min-items", count = min));
+            }
+            if let Some(max) = count("maxItems").filter(|&max| items.len() as u64 > max) {
+                issue(tr!("structured-issue-max-items", count = max));
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    check(
+                        item_schema,
+                        item,
+                        &child_path(path, &i.to_string(), true),
+                        issues,
+                    );
+                }
+            }
+        }
+        Value::Object(map) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            let required = schema.get("required").and_then(Value::as_array);
+            for name in required.into_iter().flatten().filter_map(Value::as_str) {
+                if !map.contains_key(name) {
+                    issue(tr!("structured-issue-required", name = name));
+                }
+            }
+            let closed = schema.get("additionalProperties") == Some(&Value::Bool(false));
+            for (key, child) in map {
+                let child_at = child_path(path, key, false);
+                match properties.and_then(|p| p.get(key)) {
+                    Some(child_schema) => check(child_schema, child, &child_at, issues),
+                    None if closed => issues.push(Issue {
+                        path: child_at,
+                        message: tr!("structured-issue-unexpected"),
+                    }),
+                    None => (),
+                }
+            }
+        }
+        _ => (),
+    }
+}
+
+fn validate_at(schema: &Value, value: &Value, path: &str) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    check(schema, value, path, &mut issues);
+    issues
+}
+
+/// Shows a JSON answer as a tree, with a button copying it pretty-printed. With the
+/// `schema` it was asked to follow, the places that don't are marked.
+pub fn show_json(ui: &mut egui::Ui, idx: usize, json: &Value, schema: Option<&Value>) {
+    let id = Id::new(("structured", idx));
+    let issues = schema.map(|schema| validate(schema, json));
+    egui::CollapsingHeader::new(RichText::new(tr!("structured-title")).strong())
+        .id_salt(id)
+        .default_open(true)
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                if ui.small_button(tr!("structured-copy")).clicked() {
+                    let pretty = serde_json::to_string_pretty(json).unwrap_or_default();
+                    ui.ctx().copy_text(pretty);
+                }
+                match &issues {
+                    Some(issues) if issues.is_empty() => {
+                        ui.weak(tr!("structured-valid"));
+                    }
+                    Some(issues) => {
+                        ui.colored_label(
+                            ui.visuals().error_fg_color,
+                            tr!("structured-invalid", count = issues.len()),
+                        );
+                    }
+                    None => (),
+                }
+            });
+            let issues = issues.unwrap_or_default();
+            if !issues.is_empty() {
+                egui::CollapsingHeader::new(tr!("structured-issues"))
+                    .id_salt(id.with("issues"))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        for issue in &issues {
+                            ui.horizontal_wrapped(|ui| {
+                                ui.label(RichText::new(&issue.path).monospace());
+                                ui.colored_label(ui.visuals().error_fg_color, &issue.message);
+                            });
+                        }
+                    });
+            }
+            show_value(ui, id, None, "$", json, 0, &issues);
+        });
+}
+
+/// Copies the path or the value of a node from its context menu.
+fn node_menu(response: &egui::Response, path: &str, value: &Value) {
+    response.context_menu(|ui| {
+        if ui.button(tr!("structured-copy-path")).clicked() {
+            ui.ctx().copy_text(path.to_owned());
+            ui.close();
+        }
+        if ui.button(tr!("structured-copy-value")).clicked() {
+            let text = match value {
+                Value::String(s) => s.clone(),
+                other => serde_json::to_string_pretty(other).unwrap_or_default(),
+            };
+            ui.ctx().copy_text(text);
+            ui.close();
+        }
+    });
+}
+
+fn show_value(
+    ui: &mut egui::Ui,
+    id: Id,
+    key: Option<&str>,
+    path: &str,
+    value: &Value,
+    depth: usize,
+    issues: &[Issue],
+) {
+    let own: Vec<&str> = issues
+        .iter()
+        .filter(|i| i.path == path)
+        .map(|i| i.message.as_str())
+        .collect();
+    // the issues below this node, when it is folded they would be out of sight
+    let nested = issues.iter().any(|i| {
+        i.path
+            .strip_prefix(path)
+            .is_some_and(|rest| rest.starts_with(['.', '[']))
+    });
+    let error = ui.visuals().error_fg_color;
+    let mark = |text: RichText| {
+        if own.is_empty() {
+            text
+        } else {
+            text.color(error).underline()
+        }
+    };
+    // the path, and what is wrong here
+    let hover = std::iter::once(path)
+        .chain(own.iter().copied())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let key_text = key.map(|k| format!("{k}: ")).unwrap_or_default();
+    let (open, close, in_array, children): (&str, &str, bool, Vec<(String, &Value)>) = match value {
+        Value::Object(map) => (
+            "{",
+            "}",
+            false,
+            map.iter().map(|(k, v)| (k.clone(), v)).collect(),
+        ),
+        Value::Array(items) => (
+            "[",
+            "]",
+            true,
+            items
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (i.to_string(), v))
+                .collect(),
+        ),
+        scalar => {
+            let response = ui
+                .horizontal_wrapped(|ui| {
+                    ui.spacing_mut().item_spacing.x = 0.0;
+                    ui.label(mark(RichText::new(key_text).monospace().weak()));
+                    ui.label(
+                        RichText::new(scalar.to_string())
+                            .monospace()
+                            .color(scalar_color(scalar)),
+                    );
+                })
+                .response
+                .interact(egui::Sense::click());
+            node_menu(&response.on_hover_text(hover), path, value);
+            return;
+        }
+    };
+    if children.is_empty() {
+        let response = ui.label(mark(
+            RichText::new(format!("{key_text}{open}{close}")).monospace(),
+        ));
+        node_menu(&response.on_hover_text(hover), path, value);
+        return;
+    }
+    let warn = if nested { " ⚠" } else { "" };
+    let header = format!("{key_text}{open} {} {close}{warn}", children.len());
+    let response = egui::CollapsingHeader::new(mark(RichText::new(header).monospace()))
+        .id_salt(id)
+        .default_open(depth < OPEN_DEPTH)
+        .show(ui, |ui| {
+            for (child_key, child) in children {
+                let child_id = id.with(&child_key);
+                let child_at = child_path(path, &child_key, in_array);
+                show_value(
+                    ui,
+                    child_id,
+                    Some(&child_key),
+                    &child_at,
+                    child,
+                    depth + 1,
+                    issues,
+                );
+            }
+        })
+        .header_response;
+    node_menu(&response.on_hover_text(hover), path, value);
+}
+
+fn scalar_color(value: &Value) -> Color32 {
+    match value {
+        Value::String(_) => Color32::from_rgb(141, 189, 156),
+        Value::Number(_) => Color32::from_rgb(214, 185, 120),
+        _ => Color32::from_rgb(140, 170, 214),
+    }
+}
@@ -1,4 +1,6 @@
+use crate::i18n::tr;
 use eframe::egui::{self, FontTweak};
+use std::path::{Path, PathBuf};
 
 pub fn set_style(ctx: &egui::Context) {
     ctx.style_mut(|s| {
@@ -6,6 +8,149 @@ pub fn set_style(ctx: &egui::Context) {
         s.url_in_tooltip = true;
     });
 
+    install_fonts(ctx, None, &[]);
+}
+
+/// Family for assistant text when a reading font is loaded, see [`crate::reading`].
+pub const READING_FAMILY: &str = "reading";
+
+/// Installed CJK fonts, the first one found is used.
+const SYSTEM_CJK_FONTS: &[&str] = &[
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/google-noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/truetype/wqy/wqy-microhei.ttc",
+    "C:\\Windows\\Fonts\\msyh.ttc",
+    "C:\\Windows\\Fonts\\YuGothM.ttc",
+    "C:\\Windows\\Fonts\\malgun.ttf",
+    "/System/Library/Fonts/PingFang.ttc",
+    "/System/Library/Fonts/Hiragino Sans GB.ttc",
+];
+
+/// Emoji fonts with plain outlines, egui can't draw color glyphs.
+const SYSTEM_EMOJI_FONTS: &[&str] = &[
+    "/usr/share/fonts/truetype/noto/NotoEmoji-Regular.ttf",
+    "/usr/share/fonts/noto/NotoEmoji-Regular.ttf",
+    "C:\\Windows\\Fonts\\seguiemj.ttf",
+    "C:\\Windows\\Fonts\\seguisym.ttf",
+    "/System/Library/Fonts/Apple Symbols.ttf",
+];
+
+/// Fonts tried after the bundled ones, for scripts and emoji they don't cover.
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct FontSettings {
+    /// Font files in the order they are tried
+    pub fallbacks: Vec<String>,
+    /// Append CJK and emoji fonts found in the usual OS locations
+    pub system_fallbacks: bool,
+    #[serde(skip)]
+    new_fallback: String,
+    /// Reading font and fallback chain that are installed, `None` until the first frame
+    #[serde(skip)]
+    installed: Option<(Option<PathBuf>, Vec<PathBuf>)>,
+}
+
+impl Default for FontSettings {
+    fn default() -> Self {
+        Self {
+            fallbacks: Vec::new(),
+            system_fallbacks: true,
+            new_fallback: String::new(),
+            installed: None,
+        }
+    }
+}
+
+impl FontSettings {
+    fn chain(&self) -> Vec<PathBuf> {
+        let mut chain: Vec<PathBuf> = self
+            .fallbacks
+            .iter()
+            .map(|p| PathBuf::from(p.trim()))
+            .filter(|p| p.is_file())
+            .collect();
+        if self.system_fallbacks {
+            for candidates in [SYSTEM_CJK_FONTS, SYSTEM_EMOJI_FONTS] {
+                chain.extend(candidates.iter().map(PathBuf::from).find(|p| p.is_file()));
+            }
+        }
+        chain
+    }
+
+    /// Reinstalls the fonts when the chain or the reading font changed.
+    pub fn apply_if_changed(&mut self, ctx: &egui::Context, reading_font: Option<PathBuf>) {
+        let wanted = (reading_font, self.chain());
+        if self.installed.as_ref() == Some(&wanted) {
+            return;
+        }
+        install_fonts(ctx, wanted.0.as_deref(), &wanted.1);
+        self.installed = Some(wanted);
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.label(tr!("fonts-fallbacks"));
+        let mut remove = None;
+        let mut raise = None;
+        for (i, path) in self.fallbacks.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if ui.small_button("❌").on_hover_text(tr!("remove")).clicked() {
+                    remove = Some(i);
+                }
+                if ui
+                    .add_enabled(i > 0, egui::Button::new("⏶").small())
+                    .on_hover_text(tr!("fonts-raise"))
+                    .clicked()
+                {
+                    raise = Some(i);
+                }
+                if Path::new(path.trim()).is_file() {
+                    ui.label(path);
+                } else {
+                    ui.colored_label(ui.visuals().error_fg_color, path)
+                        .on_hover_text(tr!("fonts-missing"));
+                }
+            });
+        }
+        if let Some(i) = remove {
+            self.fallbacks.remove(i);
+        }
+        if let Some(i) = raise {
+            self.fallbacks.swap(i - 1, i);
+        }
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.new_fallback)
+                    .hint_text(tr!("fonts-path-hint")),
+            );
+            if ui
+                .add_enabled(
+                    !self.new_fallback.trim().is_empty(),
+                    egui::Button::new(tr!("fonts-add")),
+                )
+                .clicked()
+            {
+                self.fallbacks
+                    .push(std::mem::take(&mut self.new_fallback).trim().to_owned());
+            }
+        });
+        ui.checkbox(&mut self.system_fallbacks, tr!("fonts-system"))
+            .on_hover_text(tr!("fonts-system-help"));
+        if cfg!(feature = "cjk-fonts") {
+            ui.weak(tr!("fonts-bundled-cjk"));
+        }
+    }
+}
+
+fn read_font(path: &Path) -> Option<egui::FontData> {
+    std::fs::read(path)
+        .map(egui::FontData::from_owned)
+        .map_err(|e| log::error!("failed to read font `{}`: {e}", path.display()))
+        .ok()
+}
+
+/// Sets the bundled fonts, then the fallback chain and the reading font if there is one.
+pub fn install_fonts(ctx: &egui::Context, reading_font: Option<&Path>, fallbacks: &[PathBuf]) {
     let mut fonts = egui::FontDefinitions::empty();
 
     log::info!("installing custom fonts");
@@ -57,5 +202,39 @@ pub fn set_style(ctx: &egui::Context) {
         ],
     );
 
+    let mut chain = Vec::new();
+    for path in fallbacks {
+        if let Some(data) = read_font(path) {
+            let name = path.display().to_string();
+            fonts.font_data.insert(name.clone(), data.into());
+            chain.push(name);
+        }
+    }
+    #[cfg(feature = "cjk-fonts")]
+    {
+        fonts.font_data.insert(
+            "NotoSansCJK-Subset".to_owned(),
+            egui::FontData::from_static(include_bytes!("../assets/fonts/NotoSansCJK-Subset.otf"))
+                .into(),
+        );
+        chain.push("NotoSansCJK-Subset".to_owned());
+    }
+    for family in [egui::FontFamily::Proportional, egui::FontFamily::Monospace] {
+        if let Some(list) = fonts.families.get_mut(&family) {
+            list.extend(chain.iter().cloned());
+        }
+    }
+
+    let mut reading = fonts.families[&egui::FontFamily::Proportional].clone();
+    if let Some(data) = reading_font.and_then(read_font) {
+        fonts
+            .font_data
+            .insert(READING_FAMILY.to_owned(), data.into());
+        reading.insert(0, READING_FAMILY.to_owned());
+    }
+    fonts
+        .families
+        .insert(egui::FontFamily::Name(READING_FAMILY.into()), reading);
+
     ctx.set_fonts(fonts);
 }
@@ -0,0 +1,146 @@
+use crate::{
+    chat::Message,
+    i18n::tr,
+    widgets::{GeminiModel, Settings},
+};
+use anyhow::{anyhow, Result};
+use eframe::egui;
+
+const SUMMARIZE_PROMPT: &str = "You condense the beginning of a conversation between a user and \
+an assistant, so that it can go on without the full transcript. Write a compact summary that keeps \
+every fact, decision, constraint, preference, name, number and piece of code the rest of the \
+conversation may rely on, and what was left open. Leave out pleasantries and repetition. Write in \
+the language of the conversation, as plain notes, without an introduction.";
+
+/// Sent in place of the summarized messages, before the summary itself.
+const SUMMARY_HEADER: &str = "Summary of the earlier conversation:";
+
+/// Messages at the end of the chat always kept word for word.
+const KEEP_RECENT: usize = 6;
+
+/// When the older turns of a chat are condensed.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct SummarizeSettings {
+    /// Summarizes without asking once the threshold is reached
+    pub auto: bool,
+    /// Share of the context window, in percent, from which summarizing is offered
+    pub threshold: f32,
+    /// Summarizes with another model, `None` uses the chat's
+    pub model: Option<GeminiModel>,
+}
+
+impl Default for SummarizeSettings {
+    fn default() -> Self {
+        Self {
+            auto: false,
+            threshold: 80.0,
+            model: None,
+        }
+    }
+}
+
+impl SummarizeSettings {
+    /// Whether `count` tokens fill enough of a context window of `limit` to summarize.
+    pub fn is_due(&self, count: u32, limit: Option<u64>) -> bool {
+        limit
+            .filter(|&limit| limit > 0)
+            .is_some_and(|limit| count as f64 / limit as f64 * 100.0 >= self.threshold as f64)
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(tr!("summarize-threshold"))
+                .on_hover_text(tr!("summarize-threshold-help"));
+            ui.add(
+                egui::Slider::new(&mut self.threshold, 50.0..=95.0)
+                    .suffix("%")
+                    .fixed_decimals(0),
+            );
+        });
+        ui.checkbox(&mut self.auto, tr!("summarize-auto"))
+            .on_hover_text(tr!("summarize-auto-help"));
+        ui.horizontal(|ui| {
+            ui.label(tr!("summarize-model"));
+            let selected = self
+                .model
+                .map_or_else(|| tr!("summarize-same-model"), |m| m.to_string());
+            egui::ComboBox::from_id_salt("summarize_model")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.model, None, tr!("summarize-same-model"));
+                    for model in crate::models::available() {
+                        ui.selectable_value(&mut self.model, Some(model), model.to_string());
+                    }
+                });
+        });
+    }
+}
+
+/// Sent to the model for a summary message, the summary alone is shown in the chat.
+pub fn for_model(summary: &str) -> String {
+    format!("{SUMMARY_HEADER}\n\n{summary}")
+}
+
+/// Where the messages kept word for word begin, everything before is summarized. The kept
+/// part starts with a user's turn so the roles keep alternating. `None` when there is too
+/// little before it to be worth a summary.
+pub fn split(messages: &[Message]) -> Option<usize> {
+    let start = messages.len().saturating_sub(KEEP_RECENT);
+    let split =
+        (start..messages.len()).find(|&i| messages[i].is_user() && !messages[i].is_marker)?;
+    let folded = messages[..split]
+        .iter()
+        .filter(|m| !m.is_marker && !m.is_thought && !m.content.is_empty())
+        .count();
+    (folded >= 2).then_some(split)
+}
+
+/// The messages as plain text for the summarizing model. Attachments are only named, their
+/// content would cost as much as the messages being saved.
+pub fn transcript(messages: &[Message]) -> String {
+    let mut transcript = String::new();
+    for message in messages {
+        if message.is_marker || message.is_thought || message.is_error {
+            continue;
+        }
+        let speaker = if !message.compacted.is_empty() {
+            SUMMARY_HEADER
+        } else if message.is_user() {
+            "User:"
+        } else {
+            "Assistant:"
+        };
+        transcript.push_str(speaker);
+        transcript.push('\n');
+        transcript.push_str(message.content.trim());
+        for file in &message.files {
+            transcript.push_str(&format!("\n[attached: {}]", file.path.display()));
+        }
+        transcript.push_str("\n\n");
+    }
+    transcript
+}
+
+/// Asks `model` to condense `transcript`.
+pub async fn summarize(
+    settings: Settings,
+    model: GeminiModel,
+    transcript: String,
+) -> Result<String> {
+    log::info!(
+        "summarizing {} chars of conversation with {model}",
+        transcript.len()
+    );
+    let text = geminid_core::pipeline::generate_once(
+        &settings.connection(),
+        model,
+        Some(SUMMARIZE_PROMPT),
+        &transcript,
+    )
+    .await?;
+    if text.trim().is_empty() {
+        return Err(anyhow!("The model returned an empty summary."));
+    }
+    Ok(text.trim().to_owned())
+}
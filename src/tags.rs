@@ -0,0 +1,125 @@
+use crate::i18n::tr;
+use eframe::egui::{self, Color32, CornerRadius, RichText};
+
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+pub struct Tag {
+    pub id: u64,
+    pub name: String,
+    pub color: Color32,
+}
+
+/// Black or white, whichever reads better on top of `background`.
+pub fn contrast_text(background: Color32) -> Color32 {
+    let [r, g, b, _] = background.to_array();
+    if 0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32 > 150.0 {
+        Color32::BLACK
+    } else {
+        Color32::WHITE
+    }
+}
+
+/// Small colored label. `selected` is drawn filled, otherwise outlined.
+pub fn chip(ui: &mut egui::Ui, tag: &Tag, selected: bool) -> egui::Response {
+    let text_color = if selected {
+        contrast_text(tag.color)
+    } else {
+        tag.color
+    };
+    ui.add(
+        egui::Button::new(RichText::new(&tag.name).small().color(text_color))
+            .small()
+            .fill(if selected {
+                tag.color
+            } else {
+                Color32::TRANSPARENT
+            })
+            .stroke(egui::Stroke::new(1.0, tag.color))
+            .corner_radius(CornerRadius::same(u8::MAX)),
+    )
+}
+
+/// Non-interactive chips for the sidebar.
+pub fn show_chips(ui: &mut egui::Ui, tags: &[Tag], ids: &[u64]) {
+    if ids.is_empty() {
+        return;
+    }
+    ui.horizontal_wrapped(|ui| {
+        ui.spacing_mut().item_spacing.x = 2.0;
+        for tag in tags.iter().filter(|t| ids.contains(&t.id)) {
+            let text = RichText::new(&tag.name).small().color(tag.color);
+            ui.add(egui::Label::new(text).selectable(false));
+        }
+    });
+}
+
+/// Toggle row used both for assigning tags to a chat and for filtering.
+/// Returns the id of the clicked tag.
+pub fn toggle_row(ui: &mut egui::Ui, tags: &[Tag], active: &[u64]) -> Option<u64> {
+    let mut clicked = None;
+    ui.horizontal_wrapped(|ui| {
+        for tag in tags {
+            if chip(ui, tag, active.contains(&tag.id)).clicked() {
+                clicked = Some(tag.id);
+            }
+        }
+    });
+    clicked
+}
+
+#[inline]
+pub fn toggle(ids: &mut Vec<u64>, id: u64) {
+    if let Some(pos) = ids.iter().position(|&t| t == id) {
+        ids.remove(pos);
+    } else {
+        ids.push(id);
+    }
+}
+
+/// Adds a tag with a fresh id and color, returns the id.
+pub fn add(tags: &mut Vec<Tag>, name: &str) -> u64 {
+    let id = tags.iter().map(|t| t.id).max().unwrap_or(0) + 1;
+    // spread hues so fresh tags are distinguishable
+    let hue = (id as f32 * 0.618_034).fract();
+    tags.push(Tag {
+        id,
+        name: name.to_owned(),
+        color: egui::ecolor::Hsva::new(hue, 0.55, 0.85, 1.0).into(),
+    });
+    id
+}
+
+/// Editor for the global tag list. Returns the id of a tag that was deleted.
+pub fn show_tag_manager(ui: &mut egui::Ui, tags: &mut Vec<Tag>, new_tag: &mut String) -> Option<u64> {
+    let mut removed = None;
+    for tag in tags.iter_mut() {
+        ui.horizontal(|ui| {
+            ui.color_edit_button_srgba(&mut tag.color);
+            ui.add(egui::TextEdit::singleline(&mut tag.name).desired_width(120.0));
+            if ui
+                .small_button("🗑")
+                .on_hover_text(tr!("tag-delete"))
+                .clicked()
+            {
+                removed = Some(tag.id);
+            }
+        });
+    }
+    if let Some(id) = removed {
+        tags.retain(|t| t.id != id);
+    }
+
+    ui.horizontal(|ui| {
+        let resp = ui.add(
+            egui::TextEdit::singleline(new_tag)
+                .hint_text(tr!("tag-new-hint"))
+                .desired_width(120.0),
+        );
+        let submit = resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        if (ui.button(tr!("add")).clicked() || submit) && !new_tag.trim().is_empty() {
+            add(tags, new_tag.trim());
+            new_tag.clear();
+        }
+    });
+
+    removed
+}
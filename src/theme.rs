@@ -0,0 +1,394 @@
+use crate::i18n::tr;
+use eframe::egui::{self, Color32, Stroke, ThemePreference, Visuals};
+use std::path::{Path, PathBuf};
+
+/// egui's default `Style::animation_time`.
+const ANIMATION_TIME: f32 = 1.0 / 12.0;
+
+/// Whether reduced motion is on, for widgets that animate on their own.
+pub fn reduced_motion(ctx: &egui::Context) -> bool {
+    ctx.style().animation_time == 0.0
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum ThemeMode {
+    /// Follow the system
+    #[default]
+    Auto,
+    Light,
+    Dark,
+}
+
+impl ThemeMode {
+    fn preference(self) -> ThemePreference {
+        match self {
+            Self::Auto => ThemePreference::System,
+            Self::Light => ThemePreference::Light,
+            Self::Dark => ThemePreference::Dark,
+        }
+    }
+}
+
+/// Background used for code blocks.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum CodeScheme {
+    #[default]
+    FollowTheme,
+    Dark,
+    Light,
+    Solarized,
+    Sepia,
+}
+
+impl CodeScheme {
+    const ALL: [Self; 5] = [Self::FollowTheme, Self::Dark, Self::Light, Self::Solarized, Self::Sepia];
+
+    fn label(self) -> String {
+        match self {
+            Self::FollowTheme => tr!("code-scheme-follow"),
+            Self::Dark => tr!("code-scheme-dark"),
+            Self::Light => tr!("code-scheme-light"),
+            Self::Solarized => "Solarized".to_owned(),
+            Self::Sepia => tr!("code-scheme-sepia"),
+        }
+    }
+
+    fn background(self) -> Option<Color32> {
+        match self {
+            Self::FollowTheme => None,
+            Self::Dark => Some(Color32::from_rgb(0x1e, 0x1e, 0x1e)),
+            Self::Light => Some(Color32::from_rgb(0xf3, 0xf3, 0xf3)),
+            Self::Solarized => Some(Color32::from_rgb(0x00, 0x2b, 0x36)),
+            Self::Sepia => Some(Color32::from_rgb(0xf4, 0xec, 0xd8)),
+        }
+    }
+}
+
+/// A user theme loaded from the themes directory. Colors are hex strings like `"#1e1e2e"`,
+/// anything left out falls back to the built-in light or dark visuals.
+#[derive(Clone, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct CustomTheme {
+    pub name: String,
+    pub dark: bool,
+    pub accent: Option<String>,
+    pub text: Option<String>,
+    pub window: Option<String>,
+    pub panel: Option<String>,
+    pub extreme_background: Option<String>,
+    pub faint_background: Option<String>,
+    pub code_background: Option<String>,
+    pub hyperlink: Option<String>,
+}
+
+fn parse_color(field: &str, hex: &Option<String>) -> Option<Color32> {
+    let hex = hex.as_deref()?;
+    Color32::from_hex(hex)
+        .map_err(|e| log::warn!("invalid `{field}` color `{hex}` in theme: {e:?}"))
+        .ok()
+}
+
+impl CustomTheme {
+    fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        let mut theme: Self = match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => toml::from_str(&text)?,
+            _ => serde_json::from_str(&text)?,
+        };
+        if theme.name.is_empty() {
+            theme.name = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default();
+        }
+        Ok(theme)
+    }
+
+    fn visuals(&self) -> Visuals {
+        let mut visuals = if self.dark {
+            Visuals::dark()
+        } else {
+            Visuals::light()
+        };
+        if let Some(c) = parse_color("text", &self.text) {
+            visuals.override_text_color = Some(c);
+        }
+        if let Some(c) = parse_color("window", &self.window) {
+            visuals.window_fill = c;
+        }
+        if let Some(c) = parse_color("panel", &self.panel) {
+            visuals.panel_fill = c;
+        }
+        if let Some(c) = parse_color("extreme_background", &self.extreme_background) {
+            visuals.extreme_bg_color = c;
+        }
+        if let Some(c) = parse_color("faint_background", &self.faint_background) {
+            visuals.faint_bg_color = c;
+        }
+        if let Some(c) = parse_color("code_background", &self.code_background) {
+            visuals.code_bg_color = c;
+        }
+        if let Some(c) = parse_color("accent", &self.accent) {
+            set_accent(&mut visuals, c);
+        }
+        if let Some(c) = parse_color("hyperlink", &self.hyperlink) {
+            visuals.hyperlink_color = c;
+        }
+        visuals
+    }
+}
+
+/// Pure black and white with outlined widgets.
+fn set_high_contrast(visuals: &mut Visuals) {
+    let (fg, bg) = if visuals.dark_mode {
+        (Color32::WHITE, Color32::BLACK)
+    } else {
+        (Color32::BLACK, Color32::WHITE)
+    };
+    visuals.override_text_color = Some(fg);
+    visuals.window_fill = bg;
+    visuals.panel_fill = bg;
+    visuals.extreme_bg_color = bg;
+    visuals.faint_bg_color = bg;
+    visuals.code_bg_color = bg;
+    visuals.window_stroke = Stroke::new(1.0, fg);
+    let widgets = &mut visuals.widgets;
+    for widget in [
+        &mut widgets.noninteractive,
+        &mut widgets.inactive,
+        &mut widgets.hovered,
+        &mut widgets.active,
+        &mut widgets.open,
+    ] {
+        widget.fg_stroke.color = fg;
+        widget.bg_stroke = Stroke::new(widget.bg_stroke.width.max(1.0), fg);
+    }
+}
+
+fn set_accent(visuals: &mut Visuals, accent: Color32) {
+    visuals.selection.bg_fill = accent;
+    visuals.selection.stroke.color = crate::tags::contrast_text(accent);
+    visuals.hyperlink_color = accent;
+    visuals.widgets.active.bg_fill = accent;
+    visuals.widgets.active.weak_bg_fill = accent;
+}
+
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct ThemeSettings {
+    pub mode: ThemeMode,
+    pub accent: Option<Color32>,
+    pub code_scheme: CodeScheme,
+    /// Name of the selected user theme, it overrides the mode
+    pub custom: Option<String>,
+    pub high_contrast: bool,
+    /// Wider outline around the focused widget, for keyboard use
+    pub thick_focus: bool,
+    /// Disables animations, the thinking indicator and the snow
+    pub reduced_motion: bool,
+    #[serde(skip)]
+    available: Vec<CustomTheme>,
+    /// Cleared whenever something changes, the visuals are reapplied on the next frame
+    #[serde(skip)]
+    applied: bool,
+}
+
+impl Default for ThemeSettings {
+    fn default() -> Self {
+        Self {
+            mode: ThemeMode::Auto,
+            accent: None,
+            code_scheme: CodeScheme::FollowTheme,
+            custom: None,
+            high_contrast: false,
+            thick_focus: false,
+            reduced_motion: false,
+            available: Vec::new(),
+            applied: false,
+        }
+    }
+}
+
+impl ThemeSettings {
+    pub fn themes_dir() -> Option<PathBuf> {
+        eframe::storage_dir(crate::TITLE).map(|p| p.join("themes"))
+    }
+
+    /// Rereads every `.json` and `.toml` file in the themes directory.
+    pub fn reload(&mut self) {
+        self.available.clear();
+        let Some(dir) = Self::themes_dir() else {
+            return;
+        };
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            return;
+        };
+        for path in entries.flatten().map(|e| e.path()) {
+            if !matches!(
+                path.extension().and_then(|e| e.to_str()),
+                Some("json" | "toml")
+            ) {
+                continue;
+            }
+            match CustomTheme::load(&path) {
+                Ok(theme) => self.available.push(theme),
+                Err(e) => log::error!("failed to load theme `{}`: {e}", path.display()),
+            }
+        }
+        self.available.sort_by(|a, b| a.name.cmp(&b.name));
+        log::debug!("loaded {} user theme(s)", self.available.len());
+    }
+
+    /// Applies the visuals if anything changed since the last call.
+    pub fn apply_if_changed(&mut self, ctx: &egui::Context) {
+        if self.applied {
+            return;
+        }
+        self.applied = true;
+        if self.custom.is_some() && self.available.is_empty() {
+            self.reload();
+        }
+
+        let custom = self
+            .custom
+            .as_ref()
+            .and_then(|name| self.available.iter().find(|t| &t.name == name));
+        if let Some(theme) = custom {
+            let which = if theme.dark {
+                egui::Theme::Dark
+            } else {
+                egui::Theme::Light
+            };
+            let mut visuals = theme.visuals();
+            self.tweak(&mut visuals);
+            ctx.set_theme(which);
+            ctx.set_visuals_of(which, visuals);
+        } else {
+            for which in [egui::Theme::Light, egui::Theme::Dark] {
+                let mut visuals = which.default_visuals();
+                self.tweak(&mut visuals);
+                ctx.set_visuals_of(which, visuals);
+            }
+            ctx.set_theme(self.mode.preference());
+        }
+        let animation_time = if self.reduced_motion {
+            0.0
+        } else {
+            ANIMATION_TIME
+        };
+        ctx.all_styles_mut(|style| style.animation_time = animation_time);
+    }
+
+    fn tweak(&self, visuals: &mut Visuals) {
+        visuals.interact_cursor = Some(egui::CursorIcon::PointingHand);
+        if self.high_contrast {
+            set_high_contrast(visuals);
+        }
+        if self.thick_focus {
+            visuals.selection.stroke.width = 2.5;
+            visuals.widgets.active.bg_stroke.width = 2.5;
+        }
+        if let Some(accent) = self.accent {
+            set_accent(visuals, accent);
+        }
+        if let Some(background) = self.code_scheme.background() {
+            visuals.code_bg_color = background;
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        let before = (
+            self.mode,
+            self.accent,
+            self.code_scheme,
+            self.custom.clone(),
+            self.high_contrast,
+            self.thick_focus,
+            self.reduced_motion,
+        );
+
+        ui.add_enabled_ui(self.custom.is_none(), |ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr!("theme-mode"));
+                ui.selectable_value(&mut self.mode, ThemeMode::Auto, tr!("theme-mode-auto"));
+                ui.selectable_value(&mut self.mode, ThemeMode::Light, tr!("theme-mode-light"));
+                ui.selectable_value(&mut self.mode, ThemeMode::Dark, tr!("theme-mode-dark"));
+            });
+        });
+
+        ui.horizontal(|ui| {
+            let mut custom_accent = self.accent.is_some();
+            ui.checkbox(&mut custom_accent, tr!("theme-accent"));
+            if !custom_accent {
+                self.accent = None;
+            } else {
+                let accent = self
+                    .accent
+                    .get_or_insert(ui.visuals().selection.bg_fill);
+                ui.color_edit_button_srgba(accent);
+            }
+        });
+
+        egui::ComboBox::from_label(tr!("theme-code-blocks"))
+            .selected_text(self.code_scheme.label())
+            .show_ui(ui, |ui| {
+                for scheme in CodeScheme::ALL {
+                    ui.selectable_value(&mut self.code_scheme, scheme, scheme.label());
+                }
+            });
+
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_label(tr!("theme-file"))
+                .selected_text(self.custom.clone().unwrap_or_else(|| tr!("none")))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.custom, None, tr!("none"));
+                    for theme in &self.available {
+                        ui.selectable_value(
+                            &mut self.custom,
+                            Some(theme.name.clone()),
+                            &theme.name,
+                        );
+                    }
+                });
+            if ui
+                .button("🔄")
+                .on_hover_text(tr!("theme-reload"))
+                .clicked()
+            {
+                self.reload();
+                self.applied = false;
+            }
+            if ui.button("📁").on_hover_text(tr!("theme-open-folder")).clicked() {
+                if let Some(dir) = Self::themes_dir() {
+                    if let Err(e) = std::fs::create_dir_all(&dir)
+                        .map_err(anyhow::Error::from)
+                        .and_then(|_| Ok(open::that(&dir)?))
+                    {
+                        log::error!("failed to open `{}`: {e}", dir.display());
+                    }
+                }
+            }
+        });
+        ui.weak(tr!("theme-file-help"));
+
+        ui.checkbox(&mut self.high_contrast, tr!("theme-high-contrast"))
+            .on_hover_text(tr!("theme-high-contrast-help"));
+        ui.checkbox(&mut self.thick_focus, tr!("theme-thick-focus"))
+            .on_hover_text(tr!("theme-thick-focus-help"));
+        ui.checkbox(&mut self.reduced_motion, tr!("theme-reduced-motion"))
+            .on_hover_text(tr!("theme-reduced-motion-help"));
+
+        let after = (
+            self.mode,
+            self.accent,
+            self.code_scheme,
+            self.custom.clone(),
+            self.high_contrast,
+            self.thick_focus,
+            self.reduced_motion,
+        );
+        if before != after {
+            self.applied = false;
+        }
+    }
+}
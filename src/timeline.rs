@@ -0,0 +1,189 @@
+use crate::{
+    chat::Chat,
+    i18n::{date_locale, tr},
+};
+use chrono::{Datelike, Duration, NaiveDate};
+use eframe::egui::{self, vec2, Sense};
+use std::collections::HashMap;
+
+/// Weeks shown on one page of the heatmap.
+const WEEKS: i64 = 26;
+const CELL_SIZE: f32 = 12.0;
+
+pub enum TimelineAction {
+    None,
+    Jump { chat_idx: usize, msg_idx: usize },
+}
+
+/// Calendar heatmap of chat activity, opened from the top bar.
+#[derive(Default)]
+pub struct Timeline {
+    pub open: bool,
+    /// How many pages back from the current one are shown
+    page: i64,
+    selected_day: Option<NaiveDate>,
+}
+
+#[inline]
+fn local_date(time: &chrono::DateTime<chrono::Utc>) -> NaiveDate {
+    time.with_timezone(&chrono::Local).date_naive()
+}
+
+fn count_per_day(chats: &[Chat]) -> HashMap<NaiveDate, usize> {
+    let mut counts = HashMap::new();
+    for msg in chats.iter().flat_map(|c| &c.messages) {
+        if !msg.is_marker {
+            *counts.entry(local_date(&msg.time)).or_default() += 1;
+        }
+    }
+    counts
+}
+
+impl Timeline {
+    #[inline]
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, chats: &[Chat]) -> TimelineAction {
+        let mut action = TimelineAction::None;
+        if !self.open {
+            return action;
+        }
+
+        let mut open = self.open;
+        egui::Window::new("📅 Timeline")
+            .open(&mut open)
+            .default_width(460.0)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                let counts = count_per_day(chats);
+                let max = counts.values().copied().max().unwrap_or(1).max(1);
+
+                let today = chrono::Local::now().date_naive();
+                let this_monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+                let start = this_monday - Duration::weeks(WEEKS - 1 + self.page * WEEKS);
+                let end = start + Duration::weeks(WEEKS) - Duration::days(1);
+
+                ui.horizontal(|ui| {
+                    if ui.button("⏴").on_hover_text(tr!("timeline-earlier")).clicked() {
+                        self.page += 1;
+                    }
+                    if ui
+                        .add_enabled(self.page > 0, egui::Button::new("⏵"))
+                        .on_hover_text(tr!("timeline-later"))
+                        .clicked()
+                    {
+                        self.page -= 1;
+                    }
+                    let format = tr!("date-short");
+                    ui.label(format!(
+                        "{} – {}",
+                        start.format_localized(&format, date_locale()),
+                        end.min(today).format_localized(&format, date_locale())
+                    ));
+                });
+
+                let empty = ui.visuals().faint_bg_color;
+                let accent = ui.visuals().selection.bg_fill;
+                let stroke = ui.visuals().selection.stroke;
+                egui::Grid::new("timeline_heatmap")
+                    .spacing(vec2(2.0, 2.0))
+                    .show(ui, |ui| {
+                        for weekday in 0..7 {
+                            for week in 0..WEEKS {
+                                let day = start + Duration::weeks(week) + Duration::days(weekday);
+                                let (rect, resp) =
+                                    ui.allocate_exact_size(vec2(CELL_SIZE, CELL_SIZE), Sense::click());
+                                if day > today {
+                                    continue;
+                                }
+                                let count = counts.get(&day).copied().unwrap_or(0);
+                                let fill = if count == 0 {
+                                    empty
+                                } else {
+                                    accent.gamma_multiply(0.25 + 0.75 * count as f32 / max as f32)
+                                };
+                                ui.painter().rect_filled(rect, 2.0, fill);
+                                if self.selected_day == Some(day) {
+                                    ui.painter().rect_stroke(
+                                        rect,
+                                        2.0,
+                                        stroke,
+                                        egui::StrokeKind::Inside,
+                                    );
+                                }
+                                let resp = resp.on_hover_text(format!(
+                                    "{}: {}",
+                                    day.format_localized(&tr!("date-day"), date_locale()),
+                                    tr!("message-count", count = count)
+                                ));
+                                if resp.clicked() {
+                                    self.selected_day = Some(day);
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+
+                let Some(day) = self.selected_day else {
+                    ui.weak(tr!("timeline-pick-day"));
+                    return;
+                };
+                ui.separator();
+                ui.strong(day.format_localized(&tr!("date-long"), date_locale()).to_string());
+
+                egui::ScrollArea::vertical().auto_shrink(false).show(ui, |ui| {
+                    let mut any = false;
+                    for (chat_idx, chat) in chats.iter().enumerate() {
+                        let messages: Vec<usize> = chat
+                            .messages
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, m)| !m.is_marker && local_date(&m.time) == day)
+                            .map(|(i, _)| i)
+                            .collect();
+                        if messages.is_empty() {
+                            continue;
+                        }
+                        any = true;
+                        let title = chat.title();
+                        egui::CollapsingHeader::new(format!("{title} ({})", messages.len()))
+                            .id_salt(("timeline_chat", chat.id))
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                for msg_idx in messages {
+                                    let msg = &chat.messages[msg_idx];
+                                    let line = msg
+                                        .content
+                                        .lines()
+                                        .find(|l| !l.trim().is_empty())
+                                        .unwrap_or("");
+                                    let text = format!(
+                                        "{} {} {line}",
+                                        msg.time.with_timezone(&chrono::Local).format("%H:%M"),
+                                        if msg.is_user() { "👤" } else { "✨" },
+                                    );
+                                    if ui
+                                        .add(
+                                            egui::Button::new(text)
+                                                .frame(false)
+                                                .truncate(),
+                                        )
+                                        .clicked()
+                                    {
+                                        action = TimelineAction::Jump { chat_idx, msg_idx };
+                                    }
+                                }
+                            });
+                    }
+                    if !any {
+                        ui.weak(tr!("timeline-no-messages"));
+                    }
+                });
+            });
+        self.open = open;
+
+        action
+    }
+}
@@ -0,0 +1,106 @@
+use crate::i18n::{date_locale, tr};
+use chrono::{DateTime, Utc};
+use eframe::egui;
+use std::time::Duration;
+
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    serde::Deserialize,
+    serde::Serialize,
+    enum_iterator::Sequence,
+)]
+pub enum TimestampStyle {
+    #[default]
+    Hidden,
+    /// "5 minutes ago", the date for anything older than a week
+    Relative,
+    Absolute,
+}
+
+impl TimestampStyle {
+    fn label(self) -> String {
+        match self {
+            Self::Hidden => tr!("timestamps-hidden"),
+            Self::Relative => tr!("timestamps-relative"),
+            Self::Absolute => tr!("timestamps-absolute"),
+        }
+    }
+}
+
+/// Times shown next to the role of every message.
+#[derive(Debug, Default, Clone, Copy, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Timestamps {
+    pub style: TimestampStyle,
+    /// Show times in UTC instead of the system time zone
+    pub utc: bool,
+}
+
+impl Timestamps {
+    /// Date and time in the format of the current language.
+    pub fn absolute(&self, time: DateTime<Utc>) -> String {
+        let format = tr!("date-time");
+        if self.utc {
+            format!("{} UTC", time.format_localized(&format, date_locale()))
+        } else {
+            time.with_timezone(&chrono::Local)
+                .format_localized(&format, date_locale())
+                .to_string()
+        }
+    }
+
+    pub fn relative(&self, time: DateTime<Utc>) -> String {
+        let elapsed = Utc::now().signed_duration_since(time);
+        if elapsed.num_minutes() < 1 {
+            tr!("time-just-now")
+        } else if elapsed.num_hours() < 1 {
+            tr!("time-minutes-ago", count = elapsed.num_minutes())
+        } else if elapsed.num_days() < 1 {
+            tr!("time-hours-ago", count = elapsed.num_hours())
+        } else if elapsed.num_days() < 7 {
+            tr!("time-days-ago", count = elapsed.num_days())
+        } else {
+            self.absolute(time)
+        }
+    }
+
+    /// Adds the timestamp of a message, the other form is in the hover text.
+    pub fn label(&self, ui: &mut egui::Ui, time: DateTime<Utc>) {
+        match self.style {
+            TimestampStyle::Hidden => (),
+            TimestampStyle::Relative => {
+                ui.weak(self.relative(time))
+                    .on_hover_text(self.absolute(time));
+                // keep "just now" from going stale
+                ui.ctx().request_repaint_after(Duration::from_secs(30));
+            }
+            TimestampStyle::Absolute => {
+                ui.weak(self.absolute(time))
+                    .on_hover_text(self.relative(time));
+            }
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        egui::ComboBox::from_label(tr!("timestamps"))
+            .selected_text(self.style.label())
+            .show_ui(ui, |ui| {
+                for style in enum_iterator::all::<TimestampStyle>() {
+                    ui.selectable_value(&mut self.style, style, style.label());
+                }
+            });
+        ui.add_enabled_ui(self.style != TimestampStyle::Hidden, |ui| {
+            crate::widgets::toggle_setting(
+                ui,
+                &mut self.utc,
+                &tr!("timestamps-utc"),
+                &tr!("timestamps-utc-help"),
+            );
+        });
+    }
+}
@@ -0,0 +1,142 @@
+use crate::i18n::tr;
+use eframe::egui::{self, ViewportCommand};
+use std::sync::mpsc::{self, Receiver, Sender};
+use tray_icon::{
+    menu::{Menu, MenuEvent, MenuId, MenuItem, PredefinedMenuItem},
+    MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayCommand {
+    Show,
+    NewChat,
+    /// Opens the window on a fresh chat with the chatbox focused
+    QuickAsk,
+    #[cfg(feature = "tts")]
+    ToggleVoiceMode,
+    Quit,
+}
+
+/// System tray icon. Menu clicks arrive on a background thread and are queued
+/// until the next frame, the window is brought back right away so that frame happens
+/// even if it was hidden.
+pub struct Tray {
+    commands: Receiver<TrayCommand>,
+    // the icon is removed when dropped
+    #[cfg(not(target_os = "linux"))]
+    _icon: tray_icon::TrayIcon,
+}
+
+/// Menu id, translation id and command of each item
+const ITEMS: &[(&str, &str, TrayCommand)] = &[
+    ("show", "tray-show", TrayCommand::Show),
+    ("new_chat", "new-chat", TrayCommand::NewChat),
+    ("quick_ask", "tray-quick-ask", TrayCommand::QuickAsk),
+    #[cfg(feature = "tts")]
+    ("voice_mode", "tray-voice-mode", TrayCommand::ToggleVoiceMode),
+];
+
+fn build_menu() -> Menu {
+    let menu = Menu::new();
+    for (id, text, _) in ITEMS {
+        let _ = menu.append(&MenuItem::with_id(*id, tr!(text), true, None));
+    }
+    let _ = menu.append(&PredefinedMenuItem::separator());
+    let _ = menu.append(&MenuItem::with_id("quit", tr!("tray-quit"), true, None));
+    menu
+}
+
+fn command(id: &MenuId) -> Option<TrayCommand> {
+    if id.0 == "quit" {
+        return Some(TrayCommand::Quit);
+    }
+    ITEMS
+        .iter()
+        .find(|(item, _, _)| id.0 == *item)
+        .map(|(_, _, command)| *command)
+}
+
+fn build_icon() -> anyhow::Result<tray_icon::TrayIcon> {
+    let icon = crate::load_icon();
+    let icon = tray_icon::Icon::from_rgba(icon.rgba, icon.width, icon.height)?;
+    Ok(TrayIconBuilder::new()
+        .with_menu(Box::new(build_menu()))
+        .with_tooltip(crate::TITLE)
+        .with_icon(icon)
+        .build()?)
+}
+
+fn forward(ctx: &egui::Context, tx: &Sender<TrayCommand>, command: TrayCommand) {
+    if matches!(
+        command,
+        TrayCommand::Show | TrayCommand::NewChat | TrayCommand::QuickAsk
+    ) {
+        ctx.send_viewport_cmd(ViewportCommand::Visible(true));
+        ctx.send_viewport_cmd(ViewportCommand::Minimized(false));
+        ctx.send_viewport_cmd(ViewportCommand::Focus);
+    }
+    let _ = tx.send(command);
+    ctx.request_repaint();
+}
+
+impl Tray {
+    pub fn new(ctx: &egui::Context) -> Option<Self> {
+        let (tx, commands) = mpsc::channel();
+
+        {
+            let ctx = ctx.clone();
+            let tx = tx.clone();
+            MenuEvent::set_event_handler(Some(move |event: MenuEvent| {
+                if let Some(command) = command(&event.id) {
+                    forward(&ctx, &tx, command);
+                }
+            }));
+        }
+        {
+            let ctx = ctx.clone();
+            TrayIconEvent::set_event_handler(Some(move |event: TrayIconEvent| {
+                if let TrayIconEvent::Click {
+                    button: MouseButton::Left,
+                    button_state: MouseButtonState::Up,
+                    ..
+                } = event
+                {
+                    forward(&ctx, &tx, TrayCommand::Show);
+                }
+            }));
+        }
+
+        // gtk wants the icon created and driven on its own thread
+        #[cfg(target_os = "linux")]
+        {
+            std::thread::spawn(move || {
+                if let Err(e) = gtk::init() {
+                    log::error!("failed to initialize gtk for the tray icon: {e}");
+                    return;
+                }
+                match build_icon() {
+                    Ok(_icon) => gtk::main(),
+                    Err(e) => log::error!("failed to create tray icon: {e}"),
+                }
+            });
+            Some(Self { commands })
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        match build_icon() {
+            Ok(icon) => Some(Self {
+                commands,
+                _icon: icon,
+            }),
+            Err(e) => {
+                log::error!("failed to create tray icon: {e}");
+                None
+            }
+        }
+    }
+
+    #[inline]
+    pub fn poll(&self) -> Option<TrayCommand> {
+        self.commands.try_recv().ok()
+    }
+}
@@ -0,0 +1,245 @@
+use crate::{
+    i18n::tr,
+    widgets::{GeminiModel, Settings},
+};
+use anyhow::{anyhow, Result};
+use eframe::egui::{self, Color32, RichText};
+use geminid_core::pipeline::{generate_grounded, generate_once};
+use std::borrow::Cow;
+
+const VERIFY_PROMPT: &str = "You fact-check an answer another assistant gave to a question. Split \
+the answer into its factual claims, skipping opinions, advice and pleasantries, and check each one. \
+Reply with JSON only, in this shape: {\"summary\": string, \"claims\": [{\"claim\": string, \
+\"verdict\": \"supported\" | \"disputed\" | \"unverifiable\", \"note\": string, \"sources\": \
+[string]}]}. `claim` quotes the answer word for word, a sentence or a part of one, `note` says in one \
+sentence why the claim holds or what is wrong with it, `sources` are URLs backing the verdict and may \
+be empty, `summary` is one or two sentences on how reliable the answer is overall. Write in the \
+language of the answer.";
+
+/// How a claim held up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    Supported,
+    Disputed,
+    #[serde(other)]
+    Unverifiable,
+}
+
+impl Verdict {
+    fn icon(self) -> &'static str {
+        match self {
+            Self::Supported => "✔",
+            Self::Disputed => "✖",
+            Self::Unverifiable => "？",
+        }
+    }
+
+    fn label(self) -> String {
+        tr!(match self {
+            Self::Supported => "verify-supported",
+            Self::Disputed => "verify-disputed",
+            Self::Unverifiable => "verify-unverifiable",
+        })
+    }
+
+    fn color(self) -> Color32 {
+        match self {
+            Self::Supported => Color32::from_rgb(141, 189, 156),
+            Self::Disputed => Color32::from_rgb(201, 141, 141),
+            Self::Unverifiable => Color32::from_rgb(214, 185, 120),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Claim {
+    /// Quoted from the answer
+    #[serde(default)]
+    pub claim: String,
+    pub verdict: Verdict,
+    #[serde(default)]
+    pub note: String,
+    #[serde(default)]
+    pub sources: Vec<String>,
+}
+
+/// A second opinion on an answer, kept with the message.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Verification {
+    /// The model that checked the answer
+    #[serde(default)]
+    pub model: GeminiModel,
+    /// Whether it could search the web
+    #[serde(default)]
+    pub grounded: bool,
+    #[serde(default)]
+    pub summary: String,
+    #[serde(default)]
+    pub claims: Vec<Claim>,
+}
+
+impl Verification {
+    /// Reads the model's answer, which may come wrapped in a code block.
+    fn parse(text: &str) -> Result<Self> {
+        let start = text.find('{');
+        let end = text.rfind('}');
+        let (Some(start), Some(end)) = (start, end) else {
+            return Err(anyhow!(tr!("verify-invalid")));
+        };
+        serde_json::from_str(&text[start..=end])
+            .map_err(|e| anyhow!("{}: {e}", tr!("verify-invalid")))
+    }
+
+    fn count(&self, verdict: Verdict) -> usize {
+        self.claims.iter().filter(|c| c.verdict == verdict).count()
+    }
+
+    /// Puts each claim's verdict after the place `content` makes it. Claims the
+    /// checker didn't quote exactly are only listed below the answer.
+    pub fn annotate<'a>(&self, content: &'a str) -> Cow<'a, str> {
+        let mut marks: Vec<(usize, usize)> = self
+            .claims
+            .iter()
+            .enumerate()
+            .filter_map(|(i, claim)| {
+                let quote = claim.claim.trim().trim_end_matches(['.', '!', '?']);
+                let at = (quote.len() > 8).then(|| content.find(quote)).flatten()?;
+                Some((at + quote.len(), i))
+            })
+            .collect();
+        if marks.is_empty() {
+            return Cow::Borrowed(content);
+        }
+        marks.sort_unstable();
+
+        let mut out = String::with_capacity(content.len() + marks.len() * 8);
+        let mut last = 0;
+        for (at, i) in marks {
+            out.push_str(&content[last..at]);
+            out.push_str(&format!(" `{}{}`", self.claims[i].verdict.icon(), i + 1));
+            last = at;
+        }
+        out.push_str(&content[last..]);
+        Cow::Owned(out)
+    }
+
+    /// Lists the claims with their verdicts, under a header with the totals.
+    pub fn show(&self, ui: &mut egui::Ui, idx: usize) {
+        let totals = tr!(
+            "verify-totals",
+            supported = self.count(Verdict::Supported),
+            disputed = self.count(Verdict::Disputed),
+            unverifiable = self.count(Verdict::Unverifiable),
+        );
+        let title = tr!(
+            "verify-title",
+            model = self.model.to_string(),
+            totals = totals
+        );
+        let open = self.count(Verdict::Disputed) > 0;
+        egui::CollapsingHeader::new(title)
+            .id_salt(("verification", idx))
+            .default_open(open)
+            .show(ui, |ui| {
+                if !self.summary.is_empty() {
+                    ui.label(&self.summary);
+                }
+                if !self.grounded {
+                    ui.weak(tr!("verify-ungrounded"));
+                }
+                if self.claims.is_empty() {
+                    ui.weak(tr!("verify-no-claims"));
+                }
+                for (i, claim) in self.claims.iter().enumerate() {
+                    ui.add_space(4.0);
+                    ui.horizontal_wrapped(|ui| {
+                        let verdict = claim.verdict;
+                        ui.label(
+                            RichText::new(format!("{}{}", verdict.icon(), i + 1))
+                                .strong()
+                                .color(verdict.color()),
+                        )
+                        .on_hover_text(verdict.label());
+                        ui.label(RichText::new(format!("“{}”", claim.claim)).italics());
+                    });
+                    if !claim.note.is_empty() {
+                        ui.label(&claim.note);
+                    }
+                    ui.horizontal_wrapped(|ui| {
+                        for source in &claim.sources {
+                            ui.hyperlink_to(RichText::new(source).small(), source);
+                        }
+                    });
+                }
+            });
+    }
+}
+
+/// Asks a model to fact-check `answer`, given the `question` it answered.
+pub async fn verify(
+    settings: Settings,
+    model: GeminiModel,
+    question: String,
+    answer: String,
+) -> Result<Verification> {
+    let verifier = settings.verify.model.unwrap_or(model);
+    let grounded = settings.verify.grounding;
+    log::info!(
+        "verifying an answer ({} chars) with {verifier}",
+        answer.len()
+    );
+    let input = format!("Question:\n{question}\n\nAnswer:\n{answer}");
+    let connection = settings.connection();
+    let text = if grounded {
+        generate_grounded(&connection, verifier, Some(VERIFY_PROMPT), &input)
+            .await?
+            .text()
+    } else {
+        generate_once(&connection, verifier, Some(VERIFY_PROMPT), &input).await?
+    };
+    let mut verification = Verification::parse(&text)?;
+    verification.model = verifier;
+    verification.grounded = grounded;
+    Ok(verification)
+}
+
+/// How answers are double-checked.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct VerifySettings {
+    /// Checks with another model, `None` uses the one that answered
+    pub model: Option<GeminiModel>,
+    /// Lets the checker search the web
+    pub grounding: bool,
+}
+
+impl Default for VerifySettings {
+    fn default() -> Self {
+        Self {
+            model: None,
+            grounding: true,
+        }
+    }
+}
+
+impl VerifySettings {
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(tr!("verify-model"));
+            let selected = self
+                .model
+                .map_or_else(|| tr!("verify-same-model"), |m| m.to_string());
+            egui::ComboBox::from_id_salt("verify_model")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.model, None, tr!("verify-same-model"));
+                    for model in crate::models::available() {
+                        ui.selectable_value(&mut self.model, Some(model), model.to_string());
+                    }
+                });
+        });
+        ui.checkbox(&mut self.grounding, tr!("verify-grounding"))
+            .on_hover_text(tr!("verify-grounding-help"));
+    }
+}
@@ -0,0 +1,559 @@
+use crate::{i18n::tr, widgets::Settings};
+use anyhow::{anyhow, Result};
+use base64::Engine;
+use chrono::{DateTime, Local, Utc};
+use eframe::egui::{self, Color32, Layout, Stroke};
+use serde_json::{json, Value};
+use std::{path::PathBuf, time::Duration};
+use tokio::sync::{mpsc, oneshot};
+
+const API: &str = "https://generativelanguage.googleapis.com/v1beta";
+
+/// The Veo models, the first is picked for new videos.
+const MODELS: [&str; 3] = [
+    "veo-3.0-generate-001",
+    "veo-3.0-fast-generate-001",
+    "veo-2.0-generate-001",
+];
+
+const ASPECT_RATIOS: [&str; 2] = ["16:9", "9:16"];
+
+/// How often a running operation is asked whether it's done.
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// About how long a video takes, for the progress bar. The API doesn't say.
+const TYPICAL_DURATION: Duration = Duration::from_secs(150);
+
+/// A video asked from Veo, kept in the Videos tab.
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Video {
+    pub id: u64,
+    pub prompt: String,
+    pub negative_prompt: String,
+    pub model: String,
+    pub aspect_ratio: String,
+    /// Picture the video starts from
+    pub image: Option<PathBuf>,
+    /// `models/…/operations/…`, polled again after a restart until it's done
+    pub operation: String,
+    pub created: DateTime<Utc>,
+    /// The downloaded MP4, in the app data folder
+    pub path: Option<PathBuf>,
+    pub error: Option<String>,
+    #[serde(skip)]
+    downloading: bool,
+}
+
+impl Default for Video {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            prompt: String::new(),
+            negative_prompt: String::new(),
+            model: MODELS[0].to_owned(),
+            aspect_ratio: ASPECT_RATIOS[0].to_owned(),
+            image: None,
+            operation: String::new(),
+            created: Utc::now(),
+            path: None,
+            error: None,
+            downloading: false,
+        }
+    }
+}
+
+impl Video {
+    fn running(&self) -> bool {
+        self.path.is_none() && self.error.is_none()
+    }
+
+    fn title(&self) -> String {
+        let title: String = self.prompt.chars().take(40).collect();
+        if title.len() < self.prompt.len() {
+            format!("{title}…")
+        } else {
+            title
+        }
+    }
+
+    fn status(&self) -> String {
+        if let Some(error) = &self.error {
+            tr!("video-failed", error = error.as_str())
+        } else if self.path.is_some() {
+            tr!("video-done")
+        } else if self.downloading {
+            tr!("video-downloading")
+        } else if self.operation.is_empty() {
+            tr!("video-submitting")
+        } else {
+            tr!("video-generating")
+        }
+    }
+}
+
+/// What a generation task reports back.
+enum Update {
+    Submitted(String),
+    Downloading,
+    Finished(PathBuf),
+    Failed(String),
+}
+
+fn videos_dir() -> Option<PathBuf> {
+    eframe::storage_dir(crate::TITLE).map(|dir| dir.join("videos"))
+}
+
+fn client(settings: &Settings) -> Result<reqwest::Client> {
+    let mut builder = reqwest::Client::builder();
+    if let Some(proxy) = settings
+        .proxy_path
+        .as_deref()
+        .filter(|p| !p.trim().is_empty())
+    {
+        builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+    }
+    Ok(builder.build()?)
+}
+
+/// Sends `request` and reads the answer, or the message of the error.
+async fn send(request: reqwest::RequestBuilder, api_key: &str) -> Result<Value> {
+    let response = request.header("x-goog-api-key", api_key).send().await?;
+    let status = response.status();
+    let body: Value = response.json().await.unwrap_or_default();
+    if status.is_success() {
+        return Ok(body);
+    }
+    Err(anyhow!(body["error"]["message"]
+        .as_str()
+        .map(str::to_owned)
+        .unwrap_or_else(|| status.to_string())))
+}
+
+/// Starts the operation making `video`, returns its name.
+async fn submit(settings: &Settings, video: &Video) -> Result<String> {
+    let mut instance = json!({ "prompt": video.prompt });
+    if let Some(path) = &video.image {
+        let bytes = tokio::fs::read(path).await?;
+        instance["image"] = json!({
+            "bytesBase64Encoded": base64::engine::general_purpose::STANDARD.encode(bytes),
+            "mimeType": mime_guess::from_path(path).first_or_octet_stream().to_string(),
+        });
+    }
+    let mut parameters = json!({ "aspectRatio": video.aspect_ratio });
+    if !video.negative_prompt.trim().is_empty() {
+        parameters["negativePrompt"] = video.negative_prompt.trim().into();
+    }
+    let body = json!({ "instances": [instance], "parameters": parameters });
+    let url = format!("{API}/models/{}:predictLongRunning", video.model);
+    let response = send(client(settings)?.post(url).json(&body), &settings.api_key).await?;
+    response["name"]
+        .as_str()
+        .map(str::to_owned)
+        .ok_or_else(|| anyhow!(tr!("video-invalid")))
+}
+
+/// Waits for `operation` to finish and gives the URI of the video.
+async fn wait(settings: &Settings, operation: &str) -> Result<String> {
+    let client = client(settings)?;
+    loop {
+        let response = send(client.get(format!("{API}/{operation}")), &settings.api_key).await?;
+        if !response["done"].as_bool().unwrap_or(false) {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            continue;
+        }
+        if let Some(message) = response["error"]["message"].as_str() {
+            return Err(anyhow!(message.to_owned()));
+        }
+        let result = &response["response"]["generateVideoResponse"];
+        if let Some(uri) = result["generatedSamples"][0]["video"]["uri"].as_str() {
+            return Ok(uri.to_owned());
+        }
+        // the safety filters keep the video and say why
+        let reasons: Vec<&str> = result["raiMediaFilteredReasons"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect();
+        return Err(anyhow!(if reasons.is_empty() {
+            tr!("video-invalid")
+        } else {
+            reasons.join(" ")
+        }));
+    }
+}
+
+async fn download(settings: &Settings, uri: &str, id: u64) -> Result<PathBuf> {
+    let dir = videos_dir().ok_or_else(|| anyhow!("no data folder"))?;
+    tokio::fs::create_dir_all(&dir).await?;
+    let response = client(settings)?
+        .get(uri)
+        .header("x-goog-api-key", &settings.api_key)
+        .send()
+        .await?
+        .error_for_status()?;
+    let path = dir.join(format!("{id}.mp4"));
+    tokio::fs::write(&path, response.bytes().await?).await?;
+    Ok(path)
+}
+
+/// Makes `video`, or picks up its operation when it was already submitted.
+async fn generate(settings: Settings, video: Video, tx: mpsc::UnboundedSender<(u64, Update)>) {
+    let id = video.id;
+    let result = async {
+        let operation = if video.operation.is_empty() {
+            let operation = submit(&settings, &video).await?;
+            let _ = tx.send((id, Update::Submitted(operation.clone())));
+            operation
+        } else {
+            video.operation.clone()
+        };
+        let uri = wait(&settings, &operation).await?;
+        let _ = tx.send((id, Update::Downloading));
+        download(&settings, &uri, id).await
+    }
+    .await;
+    let update = match result {
+        Ok(path) => Update::Finished(path),
+        Err(e) => {
+            log::error!("failed to generate a video: {e}");
+            Update::Failed(e.to_string())
+        }
+    };
+    let _ = tx.send((id, update));
+}
+
+/// The Videos tab: Veo videos from a prompt and an optional starting picture.
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct VideoStudio {
+    pub videos: Vec<Video>,
+    /// What the next video is made from
+    draft: Video,
+    #[serde(skip)]
+    selected: Option<u64>,
+    #[serde(skip)]
+    picking: Option<oneshot::Receiver<Option<PathBuf>>>,
+    #[serde(skip)]
+    updates: Option<(
+        mpsc::UnboundedSender<(u64, Update)>,
+        mpsc::UnboundedReceiver<(u64, Update)>,
+    )>,
+}
+
+impl VideoStudio {
+    fn sender(&mut self) -> mpsc::UnboundedSender<(u64, Update)> {
+        self.updates
+            .get_or_insert_with(mpsc::unbounded_channel)
+            .0
+            .clone()
+    }
+
+    /// Applies what the generation tasks reported. The first call resumes the videos
+    /// still generating when the app was closed.
+    pub fn poll(&mut self, ctx: &egui::Context, settings: &Settings) {
+        if self.updates.is_none() {
+            let tx = self.sender();
+            for video in self.videos.iter().filter(|v| v.running()) {
+                tokio::spawn(generate(settings.clone(), video.clone(), tx.clone()));
+            }
+        }
+        let Some((_, rx)) = &mut self.updates else {
+            return;
+        };
+        while let Ok((id, update)) = rx.try_recv() {
+            let Some(video) = self.videos.iter_mut().find(|v| v.id == id) else {
+                continue;
+            };
+            match update {
+                Update::Submitted(operation) => video.operation = operation,
+                Update::Downloading => video.downloading = true,
+                Update::Finished(path) => {
+                    video.downloading = false;
+                    video.path = Some(path);
+                }
+                Update::Failed(error) => {
+                    video.downloading = false;
+                    video.error = Some(error);
+                }
+            }
+            ctx.request_repaint();
+        }
+        if self.videos.iter().any(Video::running) {
+            // for the elapsed time
+            ctx.request_repaint_after(Duration::from_secs(1));
+        }
+    }
+
+    fn start(&mut self, settings: &Settings) {
+        let id = self.videos.iter().map(|v| v.id).max().unwrap_or(0) + 1;
+        let video = Video {
+            id,
+            prompt: self.draft.prompt.trim().to_owned(),
+            created: Utc::now(),
+            ..self.draft.clone()
+        };
+        tokio::spawn(generate(settings.clone(), video.clone(), self.sender()));
+        self.videos.push(video);
+        self.selected = Some(id);
+        self.draft.prompt.clear();
+        self.draft.image = None;
+    }
+
+    fn pick_image(&mut self, ctx: &egui::Context) {
+        let (tx, rx) = oneshot::channel();
+        self.picking = Some(rx);
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            let file = rfd::AsyncFileDialog::new()
+                .add_filter("Images", &["png", "jpg", "jpeg", "webp"])
+                .pick_file()
+                .await;
+            let _ = tx.send(file.map(|f| f.path().to_owned()));
+            ctx.request_repaint();
+        });
+    }
+
+    pub fn show_sidebar(&mut self, ui: &mut egui::Ui) {
+        ui.vertical_centered_justified(|ui| {
+            if ui
+                .add(
+                    egui::Button::new(format!("➕ {}", tr!("video-new")))
+                        .min_size(egui::vec2(0.0, 24.0)),
+                )
+                .clicked()
+            {
+                self.selected = None;
+            }
+        });
+        ui.add_space(2.0);
+
+        if self.videos.is_empty() {
+            ui.weak(tr!("video-none"));
+        }
+        let mut remove = None;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for video in self.videos.iter().rev() {
+                ui.horizontal(|ui| {
+                    if video.running() {
+                        ui.spinner();
+                    } else if video.error.is_some() {
+                        ui.colored_label(ui.visuals().warn_fg_color, "⚠");
+                    }
+                    if ui
+                        .selectable_label(self.selected == Some(video.id), video.title())
+                        .on_hover_text(video.status())
+                        .clicked()
+                    {
+                        self.selected = Some(video.id);
+                    }
+                    ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui
+                            .add(
+                                egui::Button::new("❌")
+                                    .small()
+                                    .fill(Color32::TRANSPARENT)
+                                    .stroke(Stroke::NONE),
+                            )
+                            .on_hover_text(tr!("video-remove"))
+                            .clicked()
+                        {
+                            remove = Some(video.id);
+                        }
+                    });
+                });
+            }
+        });
+
+        if let Some(id) = remove {
+            if let Some(path) = self
+                .videos
+                .iter()
+                .find(|v| v.id == id)
+                .and_then(|v| v.path.clone())
+            {
+                if let Err(e) = std::fs::remove_file(&path) {
+                    log::warn!("failed to remove {}: {e}", path.display());
+                }
+            }
+            self.videos.retain(|v| v.id != id);
+            if self.selected == Some(id) {
+                self.selected = None;
+            }
+        }
+    }
+
+    /// Shows the selected video, or the form for a new one.
+    pub fn show_editor(&mut self, ui: &mut egui::Ui, settings: &Settings) {
+        if let Some(rx) = &mut self.picking {
+            if let Ok(path) = rx.try_recv() {
+                if path.is_some() {
+                    self.draft.image = path;
+                }
+                self.picking = None;
+            }
+        }
+        match self
+            .selected
+            .and_then(|id| self.videos.iter().find(|v| v.id == id))
+        {
+            Some(video) => show_video(ui, video),
+            None => self.show_form(ui, settings),
+        }
+    }
+
+    fn show_form(&mut self, ui: &mut egui::Ui, settings: &Settings) {
+        ui.heading(tr!("video-title"));
+        ui.weak(tr!("video-help"));
+        ui.add_space(8.0);
+
+        ui.add(
+            egui::TextEdit::multiline(&mut self.draft.prompt)
+                .hint_text(tr!("video-prompt"))
+                .desired_rows(4)
+                .desired_width(f32::INFINITY),
+        );
+        ui.add(
+            egui::TextEdit::singleline(&mut self.draft.negative_prompt)
+                .hint_text(tr!("video-negative-prompt"))
+                .desired_width(f32::INFINITY),
+        );
+        egui::Grid::new("video_options")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label(tr!("video-model"));
+                egui::ComboBox::from_id_salt("video_model")
+                    .selected_text(&self.draft.model)
+                    .show_ui(ui, |ui| {
+                        for model in MODELS {
+                            ui.selectable_value(&mut self.draft.model, model.to_owned(), model);
+                        }
+                    });
+                ui.end_row();
+
+                ui.label(tr!("video-aspect-ratio"));
+                ui.horizontal(|ui| {
+                    for ratio in ASPECT_RATIOS {
+                        ui.selectable_value(&mut self.draft.aspect_ratio, ratio.to_owned(), ratio);
+                    }
+                });
+                ui.end_row();
+
+                ui.label(tr!("video-image"));
+                ui.horizontal(|ui| {
+                    match &self.draft.image {
+                        Some(path) => {
+                            ui.label(path.display().to_string());
+                            if ui.small_button("❌").clicked() {
+                                self.draft.image = None;
+                            }
+                        }
+                        None => {
+                            ui.weak(tr!("video-image-none"));
+                        }
+                    }
+                    if ui
+                        .add_enabled(self.picking.is_none(), egui::Button::new(tr!("video-pick")))
+                        .clicked()
+                    {
+                        self.pick_image(ui.ctx());
+                    }
+                });
+                ui.end_row();
+            });
+        if let Some(path) = &self.draft.image {
+            ui.add(
+                egui::Image::new(format!("file://{}", path.display()))
+                    .max_height(160.0)
+                    .fit_to_original_size(1.0),
+            );
+        }
+
+        ui.add_space(8.0);
+        let api_key = settings.auth_method == crate::widgets::AuthMethod::ApiKey;
+        let ready = api_key && !self.draft.prompt.trim().is_empty();
+        if ui
+            .add_enabled(ready, egui::Button::new(tr!("video-generate")))
+            .clicked()
+        {
+            self.start(settings);
+        }
+        if !api_key {
+            ui.colored_label(ui.visuals().warn_fg_color, tr!("video-needs-key"));
+        }
+    }
+}
+
+fn show_video(ui: &mut egui::Ui, video: &Video) {
+    ui.heading(video.title());
+    ui.weak(format!(
+        "{} · {} · {}",
+        video.model,
+        video.aspect_ratio,
+        video.created.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+    ));
+    ui.add_space(8.0);
+    ui.label(&video.prompt);
+    if !video.negative_prompt.is_empty() {
+        ui.weak(tr!("video-avoiding", text = video.negative_prompt.as_str()));
+    }
+    if let Some(image) = &video.image {
+        ui.add(
+            egui::Image::new(format!("file://{}", image.display()))
+                .max_height(160.0)
+                .fit_to_original_size(1.0),
+        );
+    }
+    ui.add_space(8.0);
+
+    if video.running() {
+        let elapsed = (Utc::now() - video.created).to_std().unwrap_or_default();
+        let secs = elapsed.as_secs();
+        ui.horizontal(|ui| {
+            ui.spinner();
+            ui.label(video.status());
+        });
+        ui.add(
+            egui::ProgressBar::new(
+                (elapsed.as_secs_f32() / TYPICAL_DURATION.as_secs_f32()).min(0.95),
+            )
+            .animate(true)
+            .text(format!("{}:{:02}", secs / 60, secs % 60)),
+        );
+        return;
+    }
+    if let Some(error) = &video.error {
+        ui.colored_label(ui.visuals().error_fg_color, error);
+        return;
+    }
+    let Some(path) = &video.path else {
+        return;
+    };
+    ui.horizontal(|ui| {
+        if ui.button(tr!("video-open")).clicked() {
+            if let Err(e) = open::that_detached(path) {
+                log::error!("failed to open {}: {e}", path.display());
+            }
+        }
+        if ui.button(tr!("video-save")).clicked() {
+            save(path.clone(), video.id);
+        }
+    });
+}
+
+fn save(path: PathBuf, id: u64) {
+    tokio::spawn(async move {
+        let Some(file) = rfd::AsyncFileDialog::new()
+            .set_file_name(format!("geminid-video-{id}.mp4"))
+            .save_file()
+            .await
+        else {
+            return;
+        };
+        match tokio::fs::copy(&path, file.path()).await {
+            Ok(_) => log::info!("saved the video to {}", file.path().display()),
+            Err(e) => log::error!("failed to save the video: {e}"),
+        }
+    });
+}
@@ -1,142 +1,124 @@
-use std::fmt;
-
 use chrono::{Datelike, Local};
 use eframe::{
     egui::{self, collapsing_header::CollapsingState, CornerRadius, Frame, Layout, Stroke, Vec2},
     emath::Numeric,
 };
 use egui_modal::{Icon, Modal};
-use gemini_rust::{Gemini, GeminiBuilder, GenerationConfig, Model, ThinkingConfig};
-use reqwest;
+use gemini_rust::{Gemini, GenerationConfig, Modality, Model, ThinkingConfig, Tool};
 use serde::{Deserialize, Serialize};
 
+pub use geminid_core::connection::{build_client, Connection};
+pub use geminid_core::model::{AuthMethod, FewShotExample, GeminiModel};
+
+use crate::{
+    audio::SpeechSettings,
+    context_cache::CacheSettings,
+    file_handler::{resolution_picker, MediaResolution},
+    functions::FunctionDecl,
+    i18n::{tr, Language},
+    injection::InjectionSettings,
+    keymap::Keymap,
+    knowledge::RetrievalSettings,
+    persona::Persona,
+    plugins::PluginSettings,
+    prompt_library::PromptLibrary,
+    reading::ReadingSettings,
+    retry::RetrySettings,
+    safety::{SafetyCategory, SafetySettings},
+    scripting::ScriptSettings,
+    snippets::Snippet,
+    spellcheck::SpellcheckSettings,
+    style::FontSettings,
+    summarize::SummarizeSettings,
+    theme::ThemeSettings,
+    timestamps::Timestamps,
+    verify::VerifySettings,
+};
+
 #[derive(Default, Clone, Deserialize, Serialize)]
 #[serde(default)]
 pub struct ModelPicker {
     pub selected: GeminiModel,
     settings: ModelSettings,
     pub system_prompt: Option<String>,
+    /// Input/output pairs sent as leading user/model turns on every request
+    pub examples: Vec<FewShotExample>,
 }
 
 pub enum RequestInfoType {
     LoadSettings,
+    LoadSnippets,
     LoginGoogle,
     LogoutGoogle,
     SelectProject(String),
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum AuthMethod {
-    #[default]
-    ApiKey,
-    CodeAssist,
+pub fn model_selector(ui: &mut egui::Ui, id_salt: impl std::hash::Hash, selected: &mut GeminiModel) {
+    egui::ComboBox::from_id_salt(id_salt)
+        .selected_text(selected.to_string())
+        .show_ui(ui, |ui| {
+            for model in crate::models::available() {
+                let mut resp = ui.selectable_label(*selected == model, model.to_string());
+                if let Some(info) = crate::models::info(model) {
+                    resp = resp.on_hover_text(info.hover_text());
+                }
+                if resp.clicked() {
+                    *selected = model;
+                }
+            }
+        });
 }
 
-impl fmt::Display for AuthMethod {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            AuthMethod::ApiKey => write!(f, "API Key"),
-            AuthMethod::CodeAssist => write!(f, "Google Code Assist"),
+/// Edits few-shot input/output pairs, in a model picker or a saved prompt.
+pub fn examples_editor(
+    ui: &mut egui::Ui,
+    id_salt: impl std::hash::Hash,
+    examples: &mut Vec<FewShotExample>,
+) {
+    if !examples.is_empty() {
+        let mut remove = None;
+        egui::Grid::new(id_salt)
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.strong(tr!("few-shot-input"));
+                ui.strong(tr!("few-shot-output"));
+                ui.end_row();
+
+                for (i, example) in examples.iter_mut().enumerate() {
+                    ui.add(
+                        egui::TextEdit::multiline(&mut example.input)
+                            .hint_text(tr!("few-shot-input-hint"))
+                            .desired_rows(2),
+                    );
+                    ui.add(
+                        egui::TextEdit::multiline(&mut example.output)
+                            .hint_text(tr!("few-shot-output-hint"))
+                            .desired_rows(2),
+                    );
+                    if ui
+                        .button("❌")
+                        .on_hover_text(tr!("few-shot-remove"))
+                        .clicked()
+                    {
+                        remove = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+        if let Some(i) = remove {
+            examples.remove(i);
         }
     }
-}
 
-/// Represents the available Gemini models.
-#[derive(
-    Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, enum_iterator::Sequence,
-)]
-pub enum GeminiModel {
-    #[default]
-    #[serde(rename = "gemini-3-flash-preview")]
-    Gemini30Flash,
-    #[serde(rename = "gemini-3-pro-preview")]
-    Gemini30Pro,
-    #[serde(rename = "gemini-2.0-flash")]
-    Gemini20Flash,
-
-    #[serde(rename = "gemini-2.0-flash-lite")]
-    Gemini20FlashLite,
-
-    #[serde(rename = "gemini-2.5-pro")]
-    Gemini25Pro,
-
-    #[serde(rename = "gemini-2.5-flash")]
-    Gemini25Flash,
-
-    #[serde(rename = "gemini-1.5-flash")]
-    Gemini15Flash,
-
-    #[serde(rename = "gemini-1.5-flash-8b")]
-    Gemini15Flash8b,
-
-    #[serde(rename = "gemini-2.5-flash-preview-05-20")]
-    Gemini25FlashPreview0520,
-
-    #[serde(rename = "gemini-2.0-flash-thinking-exp-01-21")]
-    Gemini20FlashThinkingExp0121,
-
-    #[serde(rename = "gemini-2.0-flash-thinking-exp-1219")]
-    Gemini20FlashThinkingExp1219,
-
-    #[serde(rename = "gemma-3-1b-it")]
-    Gemma31bIt,
-
-    #[serde(rename = "gemma-3-4b-it")]
-    Gemma34bIt,
-
-    #[serde(rename = "gemma-3-12b-it")]
-    Gemma312bIt,
-
-    #[serde(rename = "gemma-3-27b-it")]
-    Gemma327bIt,
-
-    #[serde(rename = "gemma-3n-e4b-it")]
-    Gemma3nE4bIt,
-
-    #[serde(rename = "gemma-3n-e2b-it")]
-    Gemma3nE2bIt,
-
-    // Models for paid quota
-    #[serde(rename = "gemini-1.5-pro")]
-    Gemini15Pro,
-
-    #[serde(rename = "gemini-2.5-pro-preview-03-25")]
-    Gemini25ProPreview0325,
-
-    #[serde(rename = "gemini-2.5-pro-preview-05-06")]
-    Gemini25ProPreview0506,
-
-    #[serde(rename = "gemini-2.5-pro-preview-06-05")]
-    Gemini25ProPreview0605,
-    // To add a new model, simply add a new variant here
-    // and its corresponding `rename` attribute.
-    // #[serde(rename = "new-model-name")]
-    // NewModelName,
-}
-
-impl From<GeminiModel> for Model {
-    fn from(val: GeminiModel) -> Self {
-        let model_id = serde_json::to_value(val)
-            .expect("Failed to serialize model enum")
-            .as_str()
-            .expect("Model enum should serialize to string")
-            .to_string();
-
-        Model::Custom(format!("models/{}", model_id))
-    }
-}
-
-/// Allows the enum to be printed or converted to its string representation.
-impl fmt::Display for GeminiModel {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            serde_json::to_string(self)
-                .expect("Failed to serialize model")
-                .trim_matches('"')
-        )
-    }
+    ui.horizontal(|ui| {
+        if ui.button(tr!("add")).clicked() {
+            examples.push(FewShotExample::default());
+        }
+        if !examples.is_empty() && ui.button(tr!("clear")).clicked() {
+            examples.clear();
+        }
+    });
 }
 
 fn collapsing_frame<R>(
@@ -168,6 +150,15 @@ fn collapsing_frame<R>(
             if resp.clicked() {
                 state.toggle(ui);
             }
+            resp.widget_info(|| {
+                egui::WidgetInfo::labeled(
+                    egui::WidgetType::CollapsingHeader,
+                    ui.is_enabled(),
+                    heading,
+                )
+            });
+            resp.ctx
+                .accesskit_node_builder(resp.id, |node| node.set_expanded(state.is_open()));
 
             state.show_body_unindented(ui, |ui| {
                 ui.separator();
@@ -182,63 +173,36 @@ fn collapsing_frame<R>(
     .response
 }
 
-const TEMPLATE_HINT_TEXT: &str =
-    "A system prompt for the model. E.g., 'You are a helpful assistant that specializes in writing Rust code.'";
-
 impl ModelPicker {
+    #[inline]
     pub fn create_client(
         &self,
         api_key: &str,
         proxy_path: Option<String>,
     ) -> Result<Gemini, gemini_rust::ClientError> {
-        let mut client_builder = reqwest::Client::builder();
-
-        if let Some(proxy_url) = proxy_path {
-            if !proxy_url.is_empty() {
-                if let Ok(proxy) = reqwest::Proxy::all(proxy_url) {
-                    client_builder = client_builder.proxy(proxy);
-                } else {
-                    log::error!("Invalid proxy URL, ignoring it.");
-                }
-            }
-        }
-
-        GeminiBuilder::new(api_key)
-            .with_model(Model::from(self.selected))
-            .with_http_client(client_builder)
-            .build()
+        build_client(api_key, proxy_path, Model::from(self.selected))
     }
 
-    pub fn show<R>(&mut self, ui: &mut egui::Ui, _request_info: &mut R)
+    pub fn show<R>(&mut self, ui: &mut egui::Ui, library: &mut PromptLibrary, _request_info: &mut R)
     where
         R: FnMut(RequestInfoType),
     {
-        egui::ComboBox::from_id_salt("model_selector_combobox")
-            .selected_text(self.selected.to_string())
-            .show_ui(ui, |ui| {
-                for model in enum_iterator::all::<GeminiModel>() {
-                    if ui
-                        .selectable_label(self.selected == model, model.to_string())
-                        .clicked()
-                    {
-                        self.selected = model;
-                    }
-                }
-            });
+        model_selector(ui, "model_selector_combobox", &mut self.selected);
 
-        ui.collapsing("Inference Settings", |ui| {
+        ui.collapsing(tr!("inference-settings"), |ui| {
             self.settings.show(ui);
         });
 
-        collapsing_frame(ui, "System Prompt", |ui| {
+        collapsing_frame(ui, &tr!("system-prompt-title"), |ui| {
             ui.horizontal_wrapped(|ui| {
-                ui.label("A system prompt can be used to specify custom behavior from the model.");
+                ui.label(tr!("system-prompt-help"));
             });
 
             let mut enabled = self.system_prompt.is_some();
             ui.horizontal(|ui| {
-                ui.add(toggle(&mut enabled));
-                ui.label("Enable custom system prompt");
+                let label = tr!("system-prompt-enable");
+                ui.add(toggle(&mut enabled, &label));
+                ui.label(label);
             });
             if !enabled {
                 self.system_prompt = None;
@@ -250,18 +214,69 @@ impl ModelPicker {
                 if let Some(ref mut template) = self.system_prompt {
                     ui.add(
                         egui::TextEdit::multiline(template)
-                            .hint_text(TEMPLATE_HINT_TEXT)
+                            .hint_text(tr!("system-prompt-hint"))
                             .desired_rows(3),
                     );
+                    library.show_picker(ui, template, &mut self.examples);
                 }
             });
         });
+
+        collapsing_frame(ui, &tr!("few-shot-title"), |ui| {
+            ui.horizontal_wrapped(|ui| {
+                ui.label(tr!("few-shot-help"));
+            });
+            examples_editor(ui, "few_shot_examples_grid", &mut self.examples);
+        });
     }
 
     #[inline]
     pub fn get_generation_config(&self) -> GenerationConfig {
         self.settings.clone().into()
     }
+
+    /// The safety filters of this picker's requests, its own or the app-wide `fallback`.
+    #[inline]
+    pub fn safety_settings<'a>(&'a self, fallback: &'a SafetySettings) -> &'a SafetySettings {
+        self.settings.safety.as_ref().unwrap_or(fallback)
+    }
+
+    /// Tools sent with the requests.
+    pub fn tools(&self) -> Vec<Tool> {
+        let mut tools = Vec::new();
+        if self.settings.google_search {
+            tools.push(Tool::google_search());
+        }
+        if self.settings.url_context {
+            tools.push(Tool::url_context());
+        }
+        tools
+    }
+
+    /// Whether the answers come as JSON.
+    #[inline]
+    pub fn json_output(&self) -> bool {
+        self.settings.json_output
+    }
+
+    /// The schema JSON answers have to follow, if one is set and valid.
+    pub fn response_schema(&self) -> Option<serde_json::Value> {
+        if !self.settings.json_output {
+            return None;
+        }
+        crate::structured::parse_schema(&self.settings.response_schema)
+            .ok()
+            .flatten()
+    }
+
+    /// Relaxes `category` in the filters the requests are sent with.
+    pub fn relax_safety(&mut self, fallback: &mut SafetySettings, category: SafetyCategory) {
+        self.settings
+            .safety
+            .as_mut()
+            .unwrap_or(fallback)
+            .relax(category);
+    }
 }
 
 #[derive(Default, Clone, Deserialize, Serialize)]
@@ -270,10 +285,33 @@ struct ModelSettings {
     pub temperature: Option<f32>,
     pub top_p: Option<f32>,
     pub top_k: Option<u32>,
+    pub presence_penalty: Option<f32>,
+    pub frequency_penalty: Option<f32>,
     pub num_predict: Option<i32>, // Mapped to maxOutputTokens
     pub stop: Option<Vec<String>>,
+    /// Answers generated per request, the others are shown as alternatives
+    pub candidate_count: Option<i32>,
     pub include_thoughts: bool,
     pub thinking_budget: Option<i32>,
+    /// Overrides the app-wide safety filters
+    pub safety: Option<SafetySettings>,
+    /// Lets the model search the web and cite what it found
+    pub google_search: bool,
+    /// Lets the model fetch the pages linked in the prompt
+    pub url_context: bool,
+    /// Makes the model answer in JSON
+    pub json_output: bool,
+    /// JSON schema the answers have to follow, empty for any JSON
+    pub response_schema: String,
+    /// Replaced by `response_modalities`, only read from older settings
+    #[serde(skip_serializing)]
+    pub image_output: bool,
+    /// Kinds of output the model is asked for
+    pub response_modalities: ResponseModalities,
+    /// Spoken answers of the speech models
+    pub speech: SpeechSettings,
+    /// Tokens per image, video frame and PDF page, attachments can override it
+    pub media_resolution: MediaResolution,
 }
 
 impl From<ModelSettings> for GenerationConfig {
@@ -282,8 +320,12 @@ impl From<ModelSettings> for GenerationConfig {
         config.temperature = value.temperature;
         config.top_p = value.top_p;
         config.top_k = value.top_k.map(|k| k as i32);
+        config.presence_penalty = value.presence_penalty;
+        config.frequency_penalty = value.frequency_penalty;
         config.max_output_tokens = value.num_predict;
         config.stop_sequences = value.stop;
+        config.candidate_count = value.candidate_count;
+        config.media_resolution = value.media_resolution.config();
 
         if value.include_thoughts || value.thinking_budget.is_some() {
             let mut thinking_config = ThinkingConfig::default();
@@ -295,10 +337,132 @@ impl From<ModelSettings> for GenerationConfig {
             }
             config.thinking_config = Some(thinking_config);
         }
+
+        if value.json_output {
+            config.response_mime_type = Some("application/json".to_owned());
+            config.response_schema = crate::structured::parse_schema(&value.response_schema)
+                .unwrap_or_else(|e| {
+                    log::warn!("ignoring the response schema: {e}");
+                    None
+                });
+        }
+        let mut modalities = value.response_modalities;
+        if value.image_output {
+            modalities.text = true;
+            modalities.image = true;
+        }
+        config.response_modalities = modalities.config();
+        // the speech models answer with audio alone
+        if let Some(speech) = value.speech.config() {
+            config.response_modalities = Some(vec![Modality::Audio]);
+            config.speech_config = Some(speech);
+        }
         config
     }
 }
 
+/// Kinds of output asked for in the generation config. None picked leaves the choice to
+/// the model, which answers with text.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ResponseModalities {
+    pub text: bool,
+    pub image: bool,
+    pub audio: bool,
+}
+
+impl ResponseModalities {
+    fn config(self) -> Option<Vec<Modality>> {
+        let modalities: Vec<Modality> = [
+            (self.text, Modality::Text),
+            (self.image, Modality::Image),
+            (self.audio, Modality::Audio),
+        ]
+        .into_iter()
+        .filter_map(|(picked, modality)| picked.then_some(modality))
+        .collect();
+        (!modalities.is_empty()).then_some(modalities)
+    }
+
+    fn show(&mut self, ui: &mut egui::Ui) {
+        ui.label(tr!("response-modalities-help"));
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.text, tr!("response-modalities-text"));
+            ui.checkbox(&mut self.image, tr!("response-modalities-image"));
+            ui.checkbox(&mut self.audio, tr!("response-modalities-audio"));
+        });
+        if self.config().is_none() {
+            ui.weak(tr!("response-modalities-default"));
+        }
+    }
+}
+
+/// Sampling settings for the next request alone, the chat's own stay as they are.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ParamOverride {
+    /// The override is being edited above the chatbox
+    pub open: bool,
+    pub temperature: Option<f32>,
+    pub thinking_budget: Option<i32>,
+}
+
+impl ParamOverride {
+    #[inline]
+    pub fn is_set(&self) -> bool {
+        self.temperature.is_some() || self.thinking_budget.is_some()
+    }
+
+    pub fn apply(&self, config: &mut GenerationConfig) {
+        if let Some(temperature) = self.temperature {
+            config.temperature = Some(temperature);
+        }
+        if let Some(budget) = self.thinking_budget {
+            config
+                .thinking_config
+                .get_or_insert_with(ThinkingConfig::default)
+                .thinking_budget = Some(budget);
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal_wrapped(|ui| {
+            ui.label(tr!("param-override-title"))
+                .on_hover_text(tr!("param-override-help"));
+
+            let mut enabled = self.temperature.is_some();
+            if ui
+                .checkbox(&mut enabled, tr!("param-override-temperature"))
+                .changed()
+            {
+                self.temperature = enabled.then_some(1.0);
+            }
+            if let Some(temperature) = &mut self.temperature {
+                ui.add(egui::Slider::new(temperature, 0.0..=2.0).step_by(0.05));
+            }
+
+            let mut enabled = self.thinking_budget.is_some();
+            if ui
+                .checkbox(&mut enabled, tr!("param-override-thinking"))
+                .changed()
+            {
+                self.thinking_budget = enabled.then_some(-1);
+            }
+            if let Some(budget) = &mut self.thinking_budget {
+                ui.add(egui::DragValue::new(budget).speed(100.0).range(-1..=32768))
+                    .on_hover_text(tr!("thinking-budget-help"));
+            }
+
+            if ui
+                .small_button("🗙")
+                .on_hover_text(tr!("param-override-clear"))
+                .clicked()
+            {
+                *self = Self::default();
+            }
+        });
+    }
+}
+
 impl ModelSettings {
     fn edit_numeric<N: Numeric>(
         ui: &mut egui::Ui,
@@ -313,8 +477,8 @@ impl ModelSettings {
             ui.label(doc);
             let mut enabled = val.is_some();
             ui.horizontal(|ui| {
-                ui.add(toggle(&mut enabled));
-                ui.label("Enable");
+                ui.add(toggle(&mut enabled, name));
+                ui.label(tr!("inference-enable"));
             });
 
             if !enabled {
@@ -335,8 +499,8 @@ impl ModelSettings {
                         );
                     }
                     if ui
-                        .button("reset")
-                        .on_hover_text("Reset to default")
+                        .button(tr!("inference-reset"))
+                        .on_hover_text(tr!("inference-reset-hint"))
                         .clicked()
                     {
                         *val = None;
@@ -347,19 +511,20 @@ impl ModelSettings {
     }
 
     fn show(&mut self, ui: &mut egui::Ui) {
-        if ui.button("Reset Settings").clicked() {
+        if ui.button(tr!("inference-reset-all")).clicked() {
             *self = Self::default();
         }
 
-        collapsing_frame(ui, "Thinking", |ui| {
-            ui.label("Enable native thinking for Gemini 2.5 models to improve reasoning.");
-            ui.checkbox(&mut self.include_thoughts, "Include thought summaries");
+        collapsing_frame(ui, &tr!("thinking-title"), |ui| {
+            ui.label(tr!("thinking-help"));
+            ui.checkbox(&mut self.include_thoughts, tr!("thinking-include"));
 
             ui.add_enabled_ui(self.include_thoughts, |ui| {
                 let mut budget_enabled = self.thinking_budget.is_some();
                 ui.horizontal(|ui| {
-                    ui.add(toggle(&mut budget_enabled));
-                    ui.label("Set thinking budget");
+                    let label = tr!("thinking-budget");
+                    ui.add(toggle(&mut budget_enabled, &label));
+                    ui.label(label);
                 });
 
                 if !budget_enabled {
@@ -370,31 +535,83 @@ impl ModelSettings {
 
                 if let Some(ref mut budget) = self.thinking_budget {
                     ui.add(egui::DragValue::new(budget).speed(100.0).range(-1..=32768))
-                        .on_hover_text("Token budget for thinking. -1 for dynamic, 0 to disable.");
+                        .on_hover_text(tr!("thinking-budget-help"));
                 }
             });
         });
 
-        Self::edit_numeric(ui, &mut self.temperature, 0.9, 0.01, 0.0..=1.0, "Temperature", "Controls the randomness of the output. Higher values (e.g., 1.0) produce more creative responses, while lower values (e.g., 0.2) make the output more deterministic.");
+        Self::edit_numeric(
+            ui,
+            &mut self.temperature,
+            0.9,
+            0.01,
+            0.0..=1.0,
+            &tr!("temperature-title"),
+            &tr!("temperature-help"),
+        );
         Self::edit_numeric(
             ui,
             &mut self.num_predict,
             2048,
             1.0,
             1..=8192,
-            "Max Output Tokens",
-            "Maximum number of tokens to generate in the response.",
+            &tr!("max-tokens-title"),
+            &tr!("max-tokens-help"),
+        );
+        Self::edit_numeric(
+            ui,
+            &mut self.top_k,
+            40,
+            1.0,
+            1..=100,
+            &tr!("top-k-title"),
+            &tr!("top-k-help"),
+        );
+        Self::edit_numeric(
+            ui,
+            &mut self.top_p,
+            0.95,
+            0.01,
+            0.0..=1.0,
+            &tr!("top-p-title"),
+            &tr!("top-p-help"),
+        );
+        Self::edit_numeric(
+            ui,
+            &mut self.presence_penalty,
+            0.0,
+            0.01,
+            -2.0..=2.0,
+            &tr!("penalty-presence"),
+            &tr!("penalty-presence-help"),
+        );
+        Self::edit_numeric(
+            ui,
+            &mut self.frequency_penalty,
+            0.0,
+            0.01,
+            -2.0..=2.0,
+            &tr!("penalty-frequency"),
+            &tr!("penalty-frequency-help"),
+        );
+        Self::edit_numeric(
+            ui,
+            &mut self.candidate_count,
+            2,
+            1.0,
+            1..=8,
+            &tr!("candidates-title"),
+            &tr!("candidates-help"),
         );
-        Self::edit_numeric(ui, &mut self.top_k, 40, 1.0, 1..=100, "Top-K", "Changes how the model selects tokens for output. A lower value limits the sampling to a smaller set of the most likely tokens.");
-        Self::edit_numeric(ui, &mut self.top_p, 0.95, 0.01, 0.0..=1.0, "Top-P", "Changes how the model selects tokens for output, sampling from a cumulative probability distribution. Use either Top-K or Top-P, not both.");
 
-        collapsing_frame(ui, "Stop Sequence", |ui| {
-            ui.label("A set of up to 5 character sequences that will stop output generation.");
+        let stop_title = tr!("stop-title");
+        collapsing_frame(ui, &stop_title, |ui| {
+            ui.label(tr!("stop-help"));
             let mut enabled = self.stop.is_some();
 
             ui.horizontal(|ui| {
-                ui.add(toggle(&mut enabled));
-                ui.label("Enable");
+                ui.add(toggle(&mut enabled, &stop_title));
+                ui.label(tr!("inference-enable"));
             });
 
             if !enabled {
@@ -409,21 +626,91 @@ impl ModelSettings {
                         let mut keep = true;
                         ui.horizontal(|ui| {
                             ui.text_edit_singleline(pat);
-                            if ui.button("❌").clicked() {
+                            if ui.button("❌").on_hover_text(tr!("remove")).clicked() {
                                 keep = false;
                             }
                         });
                         keep
                     });
-                    if stop.len() < 5 && ui.button("➕ Add").clicked() {
+                    if stop.len() < 5 && ui.button(tr!("add")).clicked() {
                         stop.push(String::new());
                     }
-                    if ui.button("Clear").clicked() {
+                    if ui.button(tr!("clear")).clicked() {
                         stop.clear();
                     }
                 }
             });
         });
+
+        collapsing_frame(ui, &tr!("grounding-title"), |ui| {
+            ui.label(tr!("grounding-help"));
+            ui.horizontal(|ui| {
+                ui.add(toggle(&mut self.google_search, &tr!("grounding-enabled")));
+                ui.label(tr!("grounding-enabled"));
+            });
+        });
+
+        collapsing_frame(ui, &tr!("url-context-title"), |ui| {
+            ui.label(tr!("url-context-help"));
+            ui.horizontal(|ui| {
+                ui.add(toggle(&mut self.url_context, &tr!("url-context-enabled")));
+                ui.label(tr!("url-context-enabled"));
+            });
+        });
+
+        collapsing_frame(ui, &tr!("structured-output"), |ui| {
+            ui.label(tr!("structured-help"));
+            ui.horizontal(|ui| {
+                ui.add(toggle(&mut self.json_output, &tr!("structured-enabled")));
+                ui.label(tr!("structured-enabled"));
+            });
+            ui.add_enabled_ui(self.json_output, |ui| {
+                crate::structured::show_schema_editor(ui, &mut self.response_schema);
+            });
+        });
+
+        collapsing_frame(ui, &tr!("media-resolution-title"), |ui| {
+            ui.label(tr!("media-resolution-help"));
+            resolution_picker(ui, "media_resolution", &mut self.media_resolution);
+        });
+
+        collapsing_frame(ui, &tr!("response-modalities-title"), |ui| {
+            // settings saved before the picker asked for images with a toggle
+            if std::mem::take(&mut self.image_output) {
+                self.response_modalities.text = true;
+                self.response_modalities.image = true;
+            }
+            self.response_modalities.show(ui);
+        });
+
+        collapsing_frame(ui, &tr!("audio-title"), |ui| {
+            ui.label(tr!("audio-help"));
+            ui.horizontal(|ui| {
+                ui.add(toggle(&mut self.speech.enabled, &tr!("audio-enabled")));
+                ui.label(tr!("audio-enabled"));
+            });
+            self.speech.show(ui);
+        });
+
+        collapsing_frame(ui, &tr!("settings-safety"), |ui| {
+            let mut enabled = self.safety.is_some();
+            ui.horizontal(|ui| {
+                ui.add(toggle(&mut enabled, &tr!("safety-override")));
+                ui.label(tr!("safety-override"));
+            });
+
+            if !enabled {
+                self.safety = None;
+            } else if self.safety.is_none() {
+                self.safety = Some(SafetySettings::default());
+            }
+
+            if let Some(ref mut safety) = self.safety {
+                ui.push_id("model_safety", |ui| safety.show(ui));
+            } else {
+                ui.weak(tr!("safety-override-help"));
+            }
+        });
     }
 }
 
@@ -462,7 +749,7 @@ pub fn centerer(ui: &mut egui::Ui, add_contents: impl FnOnce(&mut egui::Ui)) {
 }
 
 pub fn suggestion(ui: &mut egui::Ui, text: &str, subtext: &str) -> egui::Response {
-    let mut resp = Frame::group(ui.style())
+    let frame = Frame::group(ui.style())
         .corner_radius(CornerRadius::same(6))
         .stroke(Stroke::NONE)
         .fill(ui.style().visuals.faint_bg_color)
@@ -475,24 +762,20 @@ pub fn suggestion(ui: &mut egui::Ui, text: &str, subtext: &str) -> egui::Respons
         })
         .response;
 
+    // `Frame::group` doesn't sense clicks, so interact with its rect. Being a real
+    // widget also makes it focusable and activatable with the keyboard
+    let resp = ui.interact(frame.rect, frame.id.with("suggestion"), egui::Sense::click());
+    resp.widget_info(|| {
+        egui::WidgetInfo::labeled(
+            egui::WidgetType::Button,
+            ui.is_enabled(),
+            format!("{text} {subtext}"),
+        )
+    });
     if resp.hovered() {
         ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
     }
 
-    // for some reason egui sets `Frame::group` to not sense clicks, so we
-    // have to hack it here
-    if resp.hovered()
-        && ui.input(|i| {
-            i.pointer.any_click()
-                && i.pointer
-                    .interact_pos()
-                    .map(|p| resp.rect.contains(p))
-                    .unwrap_or(false)
-        })
-    {
-        resp.flags.insert(egui::response::Flags::CLICKED);
-    }
-
     resp
 }
 
@@ -500,7 +783,7 @@ pub fn dummy(ui: &mut egui::Ui) {
     ui.add_sized(Vec2::ZERO, egui::Label::new("").selectable(false));
 }
 
-fn toggle_ui(ui: &mut egui::Ui, on: &mut bool) -> egui::Response {
+fn toggle_ui(ui: &mut egui::Ui, on: &mut bool, label: &str) -> egui::Response {
     let desired_size = ui.spacing().interact_size.y * egui::vec2(2.0, 1.0);
     let (rect, mut response) = ui.allocate_exact_size(desired_size, egui::Sense::click());
     if response.clicked() {
@@ -508,7 +791,7 @@ fn toggle_ui(ui: &mut egui::Ui, on: &mut bool) -> egui::Response {
         response.mark_changed();
     }
     response.widget_info(|| {
-        egui::WidgetInfo::selected(egui::WidgetType::Checkbox, *on, response.hovered(), "")
+        egui::WidgetInfo::selected(egui::WidgetType::Checkbox, ui.is_enabled(), *on, label)
     });
 
     if ui.is_rect_visible(rect) {
@@ -532,12 +815,46 @@ fn toggle_ui(ui: &mut egui::Ui, on: &mut bool) -> egui::Response {
     response
 }
 
+/// `label` is what screen readers announce, it isn't drawn.
 #[inline]
-fn toggle(on: &mut bool) -> impl egui::Widget + '_ {
-    move |ui: &mut egui::Ui| toggle_ui(ui, on)
+pub(crate) fn toggle<'a>(on: &'a mut bool, label: &'a str) -> impl egui::Widget + 'a {
+    move |ui: &mut egui::Ui| toggle_ui(ui, on, label)
+}
+
+/// Toggle followed by its label and a help hint, the usual settings row.
+pub(crate) fn toggle_setting(
+    ui: &mut egui::Ui,
+    on: &mut bool,
+    label: &str,
+    help_text: &str,
+) -> egui::Response {
+    ui.horizontal(|ui| {
+        let resp = toggle_ui(ui, on, label);
+        help(ui, help_text, |ui| {
+            ui.label(label);
+        });
+        describe(&resp, help_text);
+        resp
+    })
+    .inner
+}
+
+/// Whether Shift+F10, the keyboard stand-in for a right click, was pressed on the focused `response`.
+pub(crate) fn context_menu_pressed(response: &egui::Response) -> bool {
+    response.has_focus()
+        && response
+            .ctx
+            .input_mut(|i| i.consume_key(egui::Modifiers::SHIFT, egui::Key::F10))
+}
+
+/// Exposes hover-only help to screen readers as the widget's description.
+pub(crate) fn describe(response: &egui::Response, text: &str) {
+    response
+        .ctx
+        .accesskit_node_builder(response.id, |node| node.set_description(text));
 }
 
-fn help(ui: &mut egui::Ui, text: &str, add_contents: impl FnOnce(&mut egui::Ui)) {
+pub(crate) fn help(ui: &mut egui::Ui, text: &str, add_contents: impl FnOnce(&mut egui::Ui)) {
     ui.horizontal(|ui| {
         add_contents(ui);
         ui.add_enabled(false, egui::Label::new("(?)").selectable(false))
@@ -566,6 +883,43 @@ pub struct Settings {
     pub proxy_path: Option<String>,
     pub let_it_snow: bool,
     is_winter: bool,
+    /// Read finished responses aloud
+    pub voice_mode: bool,
+    /// Hide the window instead of quitting when it is closed, the tray icon brings it back
+    pub close_to_tray: bool,
+    /// Notify about finished responses while the window is in the background
+    pub desktop_notifications: bool,
+    pub personas: Vec<Persona>,
+    pub snippets: Vec<Snippet>,
+    /// Saved system prompts, also offered by the model pickers
+    pub prompt_library: PromptLibrary,
+    pub keymap: Keymap,
+    pub theme: ThemeSettings,
+    /// UI language, `None` follows the system
+    pub language: Option<Language>,
+    /// Mirrors the sidebar and the chatbox for right-to-left languages
+    pub rtl_layout: bool,
+    pub reading: ReadingSettings,
+    pub fonts: FontSettings,
+    pub spellcheck: SpellcheckSettings,
+    pub timestamps: Timestamps,
+    pub plugins: PluginSettings,
+    pub scripting: ScriptSettings,
+    pub retrieval: RetrievalSettings,
+    pub safety: SafetySettings,
+    pub injection: InjectionSettings,
+    pub verify: VerifySettings,
+    pub cache: CacheSettings,
+    pub retry: RetrySettings,
+    pub summarize: SummarizeSettings,
+    /// Functions declared in the tools panel
+    pub functions: Vec<FunctionDecl>,
+    /// Lets functions be answered by their local commands, each run confirmed
+    pub run_commands: bool,
+    /// Keeps usage statistics on this machine
+    pub local_analytics: bool,
+    /// Opens cited files at a line, `{path}` and `{line}` are filled in. Empty uses the default app
+    pub editor_command: String,
 }
 
 impl Default for Settings {
@@ -585,27 +939,60 @@ impl Default for Settings {
             proxy_path: None,
             is_winter: is_winter,
             let_it_snow: is_winter,
+            voice_mode: false,
+            close_to_tray: false,
+            desktop_notifications: true,
+            personas: Vec::new(),
+            snippets: Vec::new(),
+            prompt_library: PromptLibrary::default(),
+            keymap: Keymap::default(),
+            theme: ThemeSettings::default(),
+            language: None,
+            rtl_layout: false,
+            reading: ReadingSettings::default(),
+            fonts: FontSettings::default(),
+            spellcheck: SpellcheckSettings::default(),
+            timestamps: Timestamps::default(),
+            plugins: PluginSettings::default(),
+            scripting: ScriptSettings::default(),
+            retrieval: RetrievalSettings::default(),
+            safety: SafetySettings::default(),
+            injection: InjectionSettings::default(),
+            verify: VerifySettings::default(),
+            cache: CacheSettings::default(),
+            retry: RetrySettings::default(),
+            summarize: SummarizeSettings::default(),
+            functions: Vec::new(),
+            run_commands: false,
+            local_analytics: false,
+            editor_command: String::new(),
         }
     }
 }
 
 impl Settings {
+    /// What the request pipeline needs of the settings.
+    pub fn connection(&self) -> Connection {
+        Connection {
+            auth_method: self.auth_method,
+            api_key: self.api_key.clone(),
+            oauth_token: self.oauth_token.clone(),
+            project_id: self.project_id.clone(),
+            proxy_path: self.proxy_path.clone(),
+        }
+    }
+
     pub fn show_modal(&mut self, modal: &Modal) {
         modal.show(|ui| {
-            modal.title(ui, "Reset Settings");
+            modal.title(ui, tr!("settings-reset-title"));
             modal.frame(ui, |ui| {
-                modal.body_and_icon(
-                    ui,
-                    "Are you sure you want to reset global settings? \
-                    This action cannot be undone!",
-                    Icon::Warning,
-                );
+                modal.body_and_icon(ui, tr!("settings-reset-confirm"), Icon::Warning);
             });
             modal.buttons(ui, |ui| {
-                if modal.button(ui, "No").clicked() {
+                if modal.button(ui, tr!("no")).clicked() {
                     modal.close();
                 }
-                if modal.caution_button(ui, "Yes").clicked() {
+                if modal.caution_button(ui, tr!("yes")).clicked() {
                     *self = Self::default();
                     modal.close();
                 }
@@ -633,12 +1020,33 @@ impl Settings {
             .map_err(|e| log::error!("failed to save settings: {e}"));
     }
 
+    async fn ask_save_snippets(snippets: Vec<Snippet>) {
+        let Some(file) = rfd::AsyncFileDialog::new()
+            .add_filter("JSON file", &["json"])
+            .set_file_name("snippets.json")
+            .save_file()
+            .await
+        else {
+            log::warn!("no file selected");
+            return;
+        };
+
+        let Ok(f) = std::fs::File::create(file.path())
+            .map_err(|e| log::error!("failed to create file: {e}"))
+        else {
+            return;
+        };
+
+        let _ = serde_json::to_writer_pretty(f, &snippets)
+            .map_err(|e| log::error!("failed to save snippets: {e}"));
+    }
+
     pub fn show<R>(&mut self, ui: &mut egui::Ui, request_info: &mut R, modal: &Modal)
     where
         R: FnMut(RequestInfoType),
     {
-        ui.heading("Authentication");
-        egui::ComboBox::from_label("Method")
+        ui.heading(tr!("settings-authentication"));
+        egui::ComboBox::from_label(tr!("settings-auth-method"))
             .selected_text(self.auth_method.to_string())
             .show_ui(ui, |ui| {
                 ui.selectable_value(&mut self.auth_method, AuthMethod::ApiKey, "API Key");
@@ -658,23 +1066,23 @@ impl Settings {
                     .striped(true)
                     .min_row_height(32.0)
                     .show(ui, |ui| {
-                        ui.label("API Key");
+                        ui.label(tr!("settings-api-key"));
                         ui.add(
                             egui::TextEdit::singleline(&mut self.api_key)
                                 .password(true)
-                                .hint_text("Enter your Google AI Studio API Key"),
+                                .hint_text(tr!("settings-api-key-hint")),
                         );
                         ui.end_row();
                     });
             }
             AuthMethod::CodeAssist => {
                 ui.horizontal(|ui| {
-                    if ui.button("🔑 Login with Google").clicked() {
+                    if ui.button(tr!("settings-login-google")).clicked() {
                         request_info(RequestInfoType::LoginGoogle);
                     }
                     if !self.oauth_token.is_empty() {
-                        ui.label("✅ Logged in");
-                        if ui.button("🚪 Logout").clicked() {
+                        ui.label(tr!("settings-logged-in"));
+                        if ui.button(tr!("settings-logout")).clicked() {
                             request_info(RequestInfoType::LogoutGoogle);
                         }
                     }
@@ -682,11 +1090,11 @@ impl Settings {
 
                 if !self.available_projects.is_empty() {
                     ui.add_space(4.0);
-                    egui::ComboBox::from_label("Project")
+                    egui::ComboBox::from_label(tr!("settings-project"))
                         .selected_text(if self.project_id.is_empty() {
-                            "Select Project"
+                            tr!("settings-select-project")
                         } else {
-                            &self.project_id
+                            self.project_id.clone()
                         })
                         .show_ui(ui, |ui| {
                             for proj in &self.available_projects {
@@ -699,56 +1107,183 @@ impl Settings {
                             }
                         });
                 } else if !self.oauth_token.is_empty() {
-                    ui.label("No projects found or loading...");
+                    ui.label(tr!("settings-no-projects"));
                 }
             }
         }
 
         ui.separator();
 
-        ui.heading("Model");
-        ui.label("Default model for new chats");
-        ui.horizontal(|ui| {
-            ui.add(toggle(&mut self.inherit_chat_picker));
-            help(ui, "Inherit model changes from chats", |ui| {
-                ui.label("Inherit from chats");
-            });
-        });
+        ui.heading(tr!("settings-model"));
+        ui.label(tr!("settings-default-model"));
+        toggle_setting(
+            ui,
+            &mut self.inherit_chat_picker,
+            &tr!("settings-inherit-model"),
+            &tr!("settings-inherit-model-help"),
+        );
         ui.add_space(2.0);
-        self.model_picker.show(ui, request_info);
+        self.model_picker
+            .show(ui, &mut self.prompt_library, request_info);
 
         ui.separator();
-        ui.heading("Behavior");
-        ui.horizontal(|ui| {
-            ui.add(toggle(&mut self.use_streaming));
-            help(ui, "Receive the response as it's being generated. Disabling this will wait for the full response before displaying it", |ui| {
-                ui.label("Stream response");
-            });
-        });
+        ui.heading(tr!("settings-behavior"));
+        toggle_setting(
+            ui,
+            &mut self.use_streaming,
+            &tr!("settings-streaming"),
+            &tr!("settings-streaming-help"),
+        );
+        toggle_setting(
+            ui,
+            &mut self.include_thoughts_in_history,
+            &tr!("settings-persist-thoughts"),
+            &tr!("settings-persist-thoughts-help"),
+        );
+        toggle_setting(
+            ui,
+            &mut self.public_file_upload,
+            &tr!("settings-file-api"),
+            &tr!("settings-file-api-help"),
+        );
+        #[cfg(feature = "tts")]
+        toggle_setting(
+            ui,
+            &mut self.voice_mode,
+            &tr!("settings-voice-mode"),
+            &tr!("settings-voice-mode-help"),
+        );
+        #[cfg(feature = "notifications")]
+        toggle_setting(
+            ui,
+            &mut self.desktop_notifications,
+            &tr!("settings-notifications"),
+            &tr!("settings-notifications-help"),
+        );
+        #[cfg(feature = "tray")]
+        toggle_setting(
+            ui,
+            &mut self.close_to_tray,
+            &tr!("settings-close-to-tray"),
+            &tr!("settings-close-to-tray-help"),
+        );
+
+        ui.separator();
+
+        ui.heading(tr!("settings-snippets"));
+        crate::snippets::show_snippets(ui, &mut self.snippets);
         ui.horizontal(|ui| {
-            ui.add(toggle(&mut self.include_thoughts_in_history));
-            help(ui, "When enabled, the model's 'thought' parts are appended to the session context for subsequent requests. Warning: This will rapidly increase token consumption", |ui| {
-                ui.label("Persist Thoughts in Context");
-            });
+            if ui.button(tr!("export")).clicked() {
+                let snippets = self.snippets.clone();
+                tokio::spawn(async move {
+                    Self::ask_save_snippets(snippets).await;
+                });
+            }
+            if ui.button(tr!("import")).clicked() {
+                request_info(RequestInfoType::LoadSnippets);
+            }
         });
-        ui.horizontal(|ui| {
-            ui.add(toggle(&mut self.public_file_upload));
-            help(ui, "When enabled, files will be uploaded to Google's servers (File API) instead of being sent as base64 inline data. Uploaded files are temporary and only accessible by you.", |ui| {
-                ui.label("Upload files (File API)");
+
+        ui.separator();
+
+        ui.heading(tr!("settings-appearance"));
+        let system = tr!("settings-language-system", language = Language::system().native_name());
+        egui::ComboBox::from_label(tr!("settings-language"))
+            .selected_text(match self.language {
+                Some(language) => language.native_name().to_owned(),
+                None => system.clone(),
+            })
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut self.language, None, system);
+                for language in enum_iterator::all::<Language>() {
+                    ui.selectable_value(&mut self.language, Some(language), language.native_name());
+                }
             });
-        });
+        self.timestamps.show(ui);
+        toggle_setting(
+            ui,
+            &mut self.rtl_layout,
+            &tr!("settings-rtl-layout"),
+            &tr!("settings-rtl-layout-help"),
+        );
+        self.theme.show(ui);
+
+        ui.separator();
+
+        ui.heading(tr!("settings-reading"));
+        self.reading.show(ui);
+
+        ui.separator();
+
+        ui.heading(tr!("settings-fonts"));
+        self.fonts.show(ui);
+
+        ui.separator();
+
+        ui.heading(tr!("settings-spellcheck"));
+        self.spellcheck.show(ui);
+
+        ui.separator();
+
+        ui.heading(tr!("settings-retrieval"));
+        self.retrieval.show(ui);
+
+        ui.separator();
+
+        ui.heading(tr!("settings-safety"));
+        self.safety.show(ui);
 
         ui.separator();
 
-        ui.heading("Miscellaneous");
+        ui.heading(tr!("settings-injection"));
+        self.injection.show(ui);
+
+        ui.separator();
+
+        ui.heading(tr!("settings-verify"));
+        self.verify.show(ui);
+
+        ui.separator();
+
+        ui.heading(tr!("settings-cache"));
+        self.cache.show(ui);
+
+        ui.separator();
+
+        ui.heading(tr!("settings-retry"));
+        self.retry.show(ui);
+
+        ui.separator();
+
+        ui.heading(tr!("settings-summarize"));
+        self.summarize.show(ui);
+
+        ui.separator();
+
+        ui.heading(tr!("settings-plugins"));
+        self.plugins.show(ui);
+
+        ui.separator();
+
+        ui.heading(tr!("settings-scripting"));
+        self.scripting.show(ui);
+
+        ui.separator();
+
+        ui.heading(tr!("settings-shortcuts"));
+        self.keymap.show_editor(ui);
+
+        ui.separator();
+
+        ui.heading(tr!("settings-misc"));
 
         let mut enabled = self.proxy_path.is_some();
-        ui.horizontal(|ui| {
-            ui.add(toggle(&mut enabled));
-            help(ui, "Use the proxy for gemini api request", |ui| {
-                ui.label("Use proxy");
-            });
-        });
+        toggle_setting(
+            ui,
+            &mut enabled,
+            &tr!("settings-proxy"),
+            &tr!("settings-proxy-help"),
+        );
         if !enabled {
             self.proxy_path = None;
         } else if self.proxy_path.is_none() {
@@ -761,8 +1296,29 @@ impl Settings {
             );
         }
 
+        toggle_setting(
+            ui,
+            &mut self.local_analytics,
+            &tr!("analytics-enabled"),
+            &tr!("analytics-enabled-help"),
+        );
+
+        crate::deeplink::show_register_button(ui);
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("settings-editor-command"))
+                .on_hover_text(tr!("settings-editor-command-help"));
+            ui.add(
+                egui::TextEdit::singleline(&mut self.editor_command)
+                    .hint_text("code --goto {path}:{line}"),
+            );
+        });
+
         // ui.toggle_value(&mut self.let_it_snow, "Let It Snow!");
-        if ui.add(egui::Button::new("Let It Snow!").selected(self.let_it_snow)).clicked() {
+        if ui
+            .add(egui::Button::new(tr!("settings-snow")).selected(self.let_it_snow))
+            .clicked()
+        {
             self.let_it_snow = !self.let_it_snow;
         }
 
@@ -770,8 +1326,8 @@ impl Settings {
 
         ui.horizontal(|ui| {
             zoom_control_widget(ui, &mut zoom);
-            help(ui, "Adjust the overall size of the user interface", |ui| {
-                ui.label("UI Scale");
+            help(ui, &tr!("settings-ui-scale-help"), |ui| {
+                ui.label(tr!("settings-ui-scale"));
             });
         });
 
@@ -779,20 +1335,20 @@ impl Settings {
             ui.ctx().set_zoom_factor(zoom);
         }
 
-        ui.label("Reset global settings to defaults");
-        if ui.button("Reset").clicked() {
+        ui.label(tr!("settings-reset-label"));
+        if ui.button(tr!("settings-reset")).clicked() {
             modal.open();
         }
 
-        ui.label("Save and load settings as JSON");
+        ui.label(tr!("settings-save-load-label"));
         ui.horizontal(|ui| {
-            if ui.button("Save").clicked() {
+            if ui.button(tr!("save")).clicked() {
                 let settings = self.clone();
                 tokio::spawn(async move {
                     Self::ask_save_settings(settings).await;
                 });
             }
-            if ui.button("Load").clicked() {
+            if ui.button(tr!("load")).clicked() {
                 request_info(RequestInfoType::LoadSettings);
             }
         });
@@ -889,7 +1445,7 @@ pub fn thinking_icon(
             let radius = egui::lerp(1.0..=2.0, 1.0 - distance_to_center);
 
             if radius > 0.1 {
-                if !done_thinking {
+                if !done_thinking && !crate::theme::reduced_motion(ui.ctx()) {
                     let anim = (ui.input(|i| i.time) + (x as f64 + y as f64) / 16.0) % 0.9;
                     ui.painter().circle_filled(pos, radius + anim as f32, color);
                 } else {
@@ -0,0 +1,59 @@
+use crate::i18n::tr;
+use eframe::egui;
+
+pub use geminid_core::youtube::*;
+
+/// Start and end of the clip, in the attachment's context menu.
+pub fn show_clip(video: &mut YouTubeVideo, ui: &mut egui::Ui) {
+    ui.label(tr!("youtube-clip"));
+    offset_row(ui, tr!("youtube-clip-start"), &mut video.start, 0);
+    let default_end = video.start.unwrap_or(0) + 60;
+    offset_row(ui, tr!("youtube-clip-end"), &mut video.end, default_end);
+    if let (Some(start), Some(end)) = (video.start, video.end) {
+        if end <= start {
+            ui.colored_label(ui.visuals().warn_fg_color, tr!("youtube-clip-invalid"));
+        }
+    }
+}
+
+fn offset_row(ui: &mut egui::Ui, label: String, offset: &mut Option<u32>, default: u32) {
+    ui.horizontal(|ui| {
+        let mut set = offset.is_some();
+        if ui.checkbox(&mut set, label).changed() {
+            *offset = set.then_some(default);
+        }
+        if let Some(secs) = offset {
+            ui.add(
+                egui::DragValue::new(secs)
+                    .custom_formatter(|secs, _| format_time(secs as u32))
+                    .custom_parser(|text| {
+                        let (minutes, secs) = text.split_once(':')?;
+                        let secs = minutes.parse::<u32>().ok()? * 60 + secs.parse::<u32>().ok()?;
+                        Some(secs as f64)
+                    }),
+            );
+        }
+    });
+}
+
+/// Offers to send the `count` YouTube links of the chatbox as videos. `Some(true)` once
+/// the user agreed, `Some(false)` if they'd rather send the links as text.
+pub fn show_banner(ui: &mut egui::Ui, count: usize) -> Option<bool> {
+    let mut choice = None;
+    egui::Frame::group(ui.style()).show(ui, |ui| {
+        ui.horizontal_wrapped(|ui| {
+            ui.label(tr!("youtube-detected", count = count));
+            if ui
+                .button(tr!("youtube-attach"))
+                .on_hover_text(tr!("youtube-attach-hint"))
+                .clicked()
+            {
+                choice = Some(true);
+            }
+            if ui.button(tr!("youtube-keep-text")).clicked() {
+                choice = Some(false);
+            }
+        });
+    });
+    choice
+}